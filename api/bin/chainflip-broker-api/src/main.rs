@@ -1,4 +1,4 @@
-use cf_chains::{RefundParametersRpc, VaultSwapExtraParametersRpc};
+use cf_chains::{EgressMemo, RefundParametersRpc, VaultSwapExtraParametersRpc};
 use cf_utilities::{
 	health::{self, HealthCheckOptions},
 	task_scope::{task_scope, Scope},
@@ -10,7 +10,7 @@ use chainflip_api::{
 			ChainAccounts, TransactionScreeningEvents, VaultSwapDetails,
 		},
 		AccountRole, AffiliateShortId, Affiliates, Asset, BasisPoints, CcmChannelMetadata,
-		DcaParameters,
+		DcaParameters, TrackingId,
 	},
 	settings::StateChain,
 	AccountId32, AddressString, BlockUpdate, BrokerApi, ChannelId, DepositMonitorApi, OperatorApi,
@@ -92,6 +92,9 @@ pub trait Rpc {
 		affiliate_fees: Option<Affiliates<AccountId32>>,
 		refund_parameters: Option<RefundParametersRpc>,
 		dca_parameters: Option<DcaParameters>,
+		tracking_id: Option<TrackingId>,
+		use_default_affiliates: Option<bool>,
+		egress_memo: Option<EgressMemo>,
 	) -> RpcResult<SwapDepositAddress>;
 
 	#[method(name = "withdraw_fees", aliases = ["broker_withdrawFees"])]
@@ -182,6 +185,9 @@ impl RpcServer for RpcServerImpl {
 		affiliate_fees: Option<Affiliates<AccountId32>>,
 		refund_parameters: Option<RefundParametersRpc>,
 		dca_parameters: Option<DcaParameters>,
+		tracking_id: Option<TrackingId>,
+		use_default_affiliates: Option<bool>,
+		egress_memo: Option<EgressMemo>,
 	) -> RpcResult<SwapDepositAddress> {
 		Ok(self
 			.api
@@ -196,6 +202,9 @@ impl RpcServer for RpcServerImpl {
 				affiliate_fees,
 				refund_parameters,
 				dca_parameters,
+				tracking_id,
+				use_default_affiliates,
+				egress_memo,
 			)
 			.await?)
 	}
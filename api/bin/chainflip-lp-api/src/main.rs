@@ -153,6 +153,23 @@ pub trait Rpc {
 		destination_account: AccountId32,
 	) -> RpcResult<Hash>;
 
+	#[method(name = "open_withdrawal_session")]
+	async fn open_withdrawal_session(&self) -> RpcResult<Hash>;
+
+	#[method(name = "add_withdrawal_to_session")]
+	async fn add_withdrawal_to_session(
+		&self,
+		amount: NumberOrHex,
+		asset: Asset,
+		destination_address: AddressString,
+	) -> RpcResult<Hash>;
+
+	#[method(name = "close_withdrawal_session")]
+	async fn close_withdrawal_session(
+		&self,
+		wait_for: Option<WaitFor>,
+	) -> RpcResult<ApiWaitForResult<Vec<EgressId>>>;
+
 	#[method(name = "update_range_order")]
 	async fn update_range_order(
 		&self,
@@ -350,6 +367,31 @@ impl RpcServer for RpcServerImpl {
 			.await?)
 	}
 
+	async fn open_withdrawal_session(&self) -> RpcResult<Hash> {
+		Ok(self.api.lp_api().open_withdrawal_session().await?)
+	}
+
+	async fn add_withdrawal_to_session(
+		&self,
+		amount: NumberOrHex,
+		asset: Asset,
+		destination_address: AddressString,
+	) -> RpcResult<Hash> {
+		Ok(self
+			.api
+			.lp_api()
+			.add_withdrawal_to_session(try_parse_number_or_hex(amount)?, asset, destination_address)
+			.await?)
+	}
+
+	/// Returns the egress ids of the consolidated withdrawals
+	async fn close_withdrawal_session(
+		&self,
+		wait_for: Option<WaitFor>,
+	) -> RpcResult<ApiWaitForResult<Vec<EgressId>>> {
+		Ok(self.api.lp_api().close_withdrawal_session(wait_for.unwrap_or_default()).await?)
+	}
+
 	/// Returns a list of all assets and their free balance in json format
 	async fn free_balances(&self) -> RpcResult<AssetMap<U256>> {
 		Ok(self
@@ -5,10 +5,10 @@ use async_trait::async_trait;
 pub use cf_chains::{address::AddressString, RefundParametersRpc};
 use cf_chains::{
 	evm::to_evm_address, CcmChannelMetadata, Chain, ChainCrypto, ChannelRefundParameters,
-	ChannelRefundParametersEncoded, ForeignChain,
+	ChannelRefundParametersEncoded, EgressMemo, ForeignChain,
 };
 pub use cf_primitives::{AccountRole, Affiliates, Asset, BasisPoints, ChannelId, SemVer};
-use cf_primitives::{AffiliateShortId, DcaParameters};
+use cf_primitives::{AffiliateShortId, DcaParameters, EgressId, TrackingId};
 use custom_rpc::CustomApiClient;
 use pallet_cf_account_roles::MAX_LENGTH_FOR_VANITY_NAME;
 use pallet_cf_governance::ExecutionMode;
@@ -322,7 +322,7 @@ pub struct SwapDepositAddress {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct WithdrawFeesDetail {
 	pub tx_hash: H256,
-	pub egress_id: (ForeignChain, u64),
+	pub egress_id: EgressId,
 	pub egress_amount: U256,
 	pub egress_fee: U256,
 	pub destination_address: AddressString,
@@ -375,13 +375,16 @@ pub trait BrokerApi: SignedExtrinsicApi + StorageApi + Sized + Send + Sync + 'st
 		affiliate_fees: Option<Affiliates<AccountId32>>,
 		refund_parameters: Option<RefundParametersRpc>,
 		dca_parameters: Option<DcaParameters>,
+		tracking_id: Option<TrackingId>,
+		use_default_affiliates: Option<bool>,
+		egress_memo: Option<EgressMemo>,
 	) -> Result<SwapDepositAddress> {
 		let destination_address = destination_address
 			.try_parse_to_encoded_address(destination_asset.into())
 			.map_err(anyhow::Error::msg)?;
 		let (_tx_hash, events, header, ..) = self
 			.submit_signed_extrinsic_with_dry_run(
-				pallet_cf_swapping::Call::request_swap_deposit_address_with_affiliates {
+				pallet_cf_swapping::Call::request_swap_deposit_address_with_parameters {
 					source_asset,
 					destination_asset,
 					destination_address,
@@ -401,6 +404,9 @@ pub trait BrokerApi: SignedExtrinsicApi + StorageApi + Sized + Send + Sync + 'st
 						})
 						.transpose()?,
 					dca_parameters,
+					tracking_id,
+					use_default_affiliates: use_default_affiliates.unwrap_or_default(),
+					egress_memo,
 				},
 			)
 			.await?
@@ -316,6 +316,67 @@ pub trait LpApi: SignedExtrinsicApi + Sized + Send + Sync + 'static {
 		Ok(tx_hash)
 	}
 
+	async fn open_withdrawal_session(&self) -> Result<H256> {
+		let (tx_hash, ..) = self
+			.submit_signed_extrinsic(RuntimeCall::from(
+				pallet_cf_lp::Call::open_withdrawal_session {},
+			))
+			.await
+			.until_in_block()
+			.await?;
+		Ok(tx_hash)
+	}
+
+	async fn add_withdrawal_to_session(
+		&self,
+		amount: AssetAmount,
+		asset: Asset,
+		destination_address: AddressString,
+	) -> Result<H256> {
+		if amount == 0 {
+			bail!("Withdrawal amount must be greater than 0");
+		}
+
+		let (tx_hash, ..) = self
+			.submit_signed_extrinsic(RuntimeCall::from(
+				pallet_cf_lp::Call::add_withdrawal_to_session {
+					amount,
+					asset,
+					destination_address: destination_address
+						.try_parse_to_encoded_address(asset.into())
+						.map_err(anyhow::Error::msg)?,
+				},
+			))
+			.await
+			.until_in_block()
+			.await?;
+		Ok(tx_hash)
+	}
+
+	async fn close_withdrawal_session(
+		&self,
+		wait_for: WaitFor,
+	) -> Result<ApiWaitForResult<Vec<EgressId>>> {
+		Ok(into_api_wait_for_result(
+			self.submit_signed_extrinsic_wait_for(
+				pallet_cf_lp::Call::close_withdrawal_session {},
+				wait_for,
+			)
+			.await?,
+			|events| {
+				events
+					.into_iter()
+					.filter_map(|event| match event {
+						state_chain_runtime::RuntimeEvent::LiquidityProvider(
+							pallet_cf_lp::Event::WithdrawalEgressScheduled { egress_id, .. },
+						) => Some(egress_id),
+						_ => None,
+					})
+					.collect()
+			},
+		))
+	}
+
 	async fn update_range_order(
 		&self,
 		base_asset: Asset,
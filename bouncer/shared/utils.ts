@@ -384,7 +384,7 @@ export function getBtcClient(): Client {
 // eslint-disable-next-line @typescript-eslint/no-explicit-any
 export type Event = { name: any; data: any; block: number; event_index: number };
 
-export type EgressId = [Chain, number];
+export type EgressId = [Chain, number, number];
 type BroadcastChainAndId = [Chain, number];
 // Observe multiple events related to the same swap that could be emitted in the same block
 export async function observeSwapEvents(
@@ -458,12 +458,18 @@ export async function observeSwapEvents(
           if (data.swapRequestId === swapRequestId) {
             expectedEvent = batchBroadcastRequested;
             egressId = data.egressId as EgressId;
-            console.log(`${tag} swap egress scheduled with id: (${egressId[0]}, ${egressId[1]})`);
+            console.log(
+              `${tag} swap egress scheduled with id: (${egressId[0]}, ${egressId[1]}, ${egressId[2]})`,
+            );
           }
           break;
         case batchBroadcastRequested:
           for (const eventEgressId of data.egressIds) {
-            if (egressId[0] === eventEgressId[0] && egressId[1] === eventEgressId[1]) {
+            if (
+              egressId[0] === eventEgressId[0] &&
+              egressId[1] === eventEgressId[1] &&
+              egressId[2] === eventEgressId[2]
+            ) {
               broadcastId = [egressId[0], Number(data.broadcastId)] as BroadcastChainAndId;
               console.log(`${tag} broadcast requested, with id: (${broadcastId})`);
               broadcastEventFound = true;
@@ -141,7 +141,9 @@ async function executeAndTrackCcmSwap(
   const broadcastId = (
     await observeEvent(`${destChain.toLowerCase()}IngressEgress:CcmBroadcastRequested`, {
       test: (event) =>
-        event.data.egressId[0] === egressId[0] && event.data.egressId[1] === egressId[1],
+        event.data.egressId[0] === egressId[0] &&
+        event.data.egressId[1] === egressId[1] &&
+        event.data.egressId[2] === egressId[2],
       historicalCheckBlocks: CHECK_PAST_BLOCKS_FOR_EVENTS,
     }).event
   ).data.broadcastId;
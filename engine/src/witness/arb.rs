@@ -8,7 +8,9 @@ use cf_chains::{
 	evm::{DepositDetails, H256},
 	Arbitrum, CcmDepositMetadata,
 };
-use cf_primitives::{chains::assets::arb::Asset as ArbAsset, Asset, AssetAmount, EpochIndex};
+use cf_primitives::{
+	chains::assets::arb::Asset as ArbAsset, Asset, AssetAmount, EpochIndex, TrackingId,
+};
 use cf_utilities::task_scope::Scope;
 use futures_core::Future;
 use itertools::Itertools;
@@ -192,7 +194,9 @@ impl super::evm::vault::IngressCallBuilder for ArbCallBuilder {
 		destination_address: EncodedAddress,
 		deposit_metadata: Option<CcmDepositMetadata>,
 		tx_id: H256,
+		sender: H160,
 		vault_swap_parameters: Option<VaultSwapParameters>,
+		tracking_id: Option<TrackingId>,
 	) -> state_chain_runtime::RuntimeCall {
 		let deposit = vault_deposit_witness!(
 			source_asset,
@@ -201,7 +205,9 @@ impl super::evm::vault::IngressCallBuilder for ArbCallBuilder {
 			destination_address,
 			deposit_metadata,
 			tx_id,
-			vault_swap_parameters
+			sender,
+			vault_swap_parameters,
+			tracking_id
 		);
 
 		state_chain_runtime::RuntimeCall::ArbitrumIngressEgress(
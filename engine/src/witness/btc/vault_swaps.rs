@@ -172,6 +172,8 @@ pub fn try_extract_vault_swap_witness(
 		boost_fee: data.parameters.boost_fee.into(),
 		channel_id: Some(channel_id),
 		deposit_address: Some(vault_address.script_pubkey()),
+		tracking_id: data.tracking_id,
+		verified_sender: None,
 	})
 }
 
@@ -215,6 +217,7 @@ mod tests {
 				fee: 7
 			}],
 		},
+		tracking_id: None,
 	});
 
 	fn add_opcodes_to_data(data: Vec<u8>) -> ScriptBuf {
@@ -341,6 +344,8 @@ mod tests {
 				boost_fee: MOCK_SWAP_PARAMS.parameters.boost_fee.into(),
 				deposit_address: Some(vault_deposit_address.script_pubkey()),
 				channel_id: Some(CHANNEL_ID),
+				tracking_id: MOCK_SWAP_PARAMS.tracking_id,
+				verified_sender: None,
 			})
 		);
 	}
@@ -9,7 +9,9 @@ use cf_chains::{
 	evm::{DepositDetails, H256},
 	CcmDepositMetadata, Ethereum,
 };
-use cf_primitives::{chains::assets::eth::Asset as EthAsset, Asset, AssetAmount, EpochIndex};
+use cf_primitives::{
+	chains::assets::eth::Asset as EthAsset, Asset, AssetAmount, EpochIndex, TrackingId,
+};
 use cf_utilities::task_scope::Scope;
 use futures_core::Future;
 use itertools::Itertools;
@@ -241,7 +243,9 @@ impl super::evm::vault::IngressCallBuilder for EthCallBuilder {
 		destination_address: EncodedAddress,
 		deposit_metadata: Option<CcmDepositMetadata>,
 		tx_id: H256,
+		sender: H160,
 		vault_swap_parameters: Option<VaultSwapParameters>,
+		tracking_id: Option<TrackingId>,
 	) -> state_chain_runtime::RuntimeCall {
 		let deposit = vault_deposit_witness!(
 			source_asset,
@@ -250,7 +254,9 @@ impl super::evm::vault::IngressCallBuilder for EthCallBuilder {
 			destination_address,
 			deposit_metadata,
 			tx_id,
-			vault_swap_parameters
+			sender,
+			vault_swap_parameters,
+			tracking_id
 		);
 		state_chain_runtime::RuntimeCall::EthereumIngressEgress(
 			pallet_cf_ingress_egress::Call::vault_swap_request {
@@ -21,7 +21,7 @@ use cf_chains::{
 	evm::DepositDetails,
 	CcmChannelMetadata, CcmDepositMetadata, Chain,
 };
-use cf_primitives::{Asset, AssetAmount, EpochIndex, ForeignChain};
+use cf_primitives::{Asset, AssetAmount, EpochIndex, ForeignChain, TrackingId};
 use ethers::prelude::*;
 use state_chain_runtime::{EthereumInstance, Runtime, RuntimeCall};
 
@@ -30,20 +30,17 @@ abigen!(Vault, "$CF_ETH_CONTRACT_ABI_ROOT/$CF_ETH_CONTRACT_ABI_TAG/IVault.json")
 fn decode_cf_parameters<CcmData>(
 	cf_parameters: &[u8],
 	block_height: u64,
-) -> (Option<VaultSwapParameters>, CcmData)
+) -> (Option<VaultSwapParameters>, CcmData, Option<TrackingId>)
 where
 	CcmData: Default + Decode,
 {
 	if let Ok(decoded) = VersionedCfParameters::<CcmData>::decode(&mut &cf_parameters[..]) {
-		match decoded {
-			VersionedCfParameters::V0(CfParameters {
-				ccm_additional_data,
-				vault_swap_parameters,
-			}) => (Some(vault_swap_parameters), ccm_additional_data),
-		}
+		let (CfParameters { ccm_additional_data, vault_swap_parameters }, tracking_id) =
+			decoded.into_parts();
+		(Some(vault_swap_parameters), ccm_additional_data, tracking_id)
 	} else {
 		tracing::warn!("Failed to decode cf_parameters: {cf_parameters:?} at block {block_height}");
-		(None, Default::default())
+		(None, Default::default(), None)
 	}
 }
 
@@ -83,11 +80,11 @@ where
 			dst_address,
 			dst_token,
 			amount,
-			sender: _,
+			sender,
 			cf_parameters,
 		}) => {
-			let vault_swap_parameters =
-				decode_cf_parameters::<()>(&cf_parameters[..], block_height).0;
+			let (vault_swap_parameters, (), tracking_id) =
+				decode_cf_parameters(&cf_parameters[..], block_height);
 
 			Some(CallBuilder::vault_swap_request(
 				block_height,
@@ -97,7 +94,9 @@ where
 				try_into_encoded_address(try_into_primitive(dst_chain)?, dst_address.to_vec())?,
 				None,
 				event.tx_hash,
+				sender,
 				vault_swap_parameters,
+				tracking_id,
 			))
 		},
 		VaultEvents::SwapTokenFilter(SwapTokenFilter {
@@ -106,11 +105,11 @@ where
 			dst_token,
 			src_token,
 			amount,
-			sender: _,
+			sender,
 			cf_parameters,
 		}) => {
-			let vault_swap_parameters =
-				decode_cf_parameters::<()>(&cf_parameters[..], block_height).0;
+			let (vault_swap_parameters, (), tracking_id) =
+				decode_cf_parameters(&cf_parameters[..], block_height);
 
 			Some(CallBuilder::vault_swap_request(
 				block_height,
@@ -122,7 +121,9 @@ where
 				try_into_encoded_address(try_into_primitive(dst_chain)?, dst_address.to_vec())?,
 				None,
 				event.tx_hash,
+				sender,
 				vault_swap_parameters,
+				tracking_id,
 			))
 		},
 		VaultEvents::XcallNativeFilter(XcallNativeFilter {
@@ -135,7 +136,7 @@ where
 			gas_amount,
 			cf_parameters,
 		}) => {
-			let (vault_swap_parameters, ccm_additional_data) =
+			let (vault_swap_parameters, ccm_additional_data, tracking_id) =
 				decode_cf_parameters(&cf_parameters[..], block_height);
 
 			Some(CallBuilder::vault_swap_request(
@@ -157,7 +158,9 @@ where
 					},
 				}),
 				event.tx_hash,
+				sender,
 				vault_swap_parameters,
+				tracking_id,
 			))
 		},
 		VaultEvents::XcallTokenFilter(XcallTokenFilter {
@@ -171,7 +174,7 @@ where
 			gas_amount,
 			cf_parameters,
 		}) => {
-			let (vault_swap_parameters, ccm_additional_data) =
+			let (vault_swap_parameters, ccm_additional_data, tracking_id) =
 				decode_cf_parameters(&cf_parameters[..], block_height);
 
 			Some(CallBuilder::vault_swap_request(
@@ -195,7 +198,9 @@ where
 					},
 				}),
 				event.tx_hash,
+				sender,
 				vault_swap_parameters,
+				tracking_id,
 			))
 		},
 		VaultEvents::TransferNativeFailedFilter(TransferNativeFailedFilter {
@@ -230,7 +235,7 @@ where
 }
 
 macro_rules! vault_deposit_witness {
-	($source_asset: expr, $deposit_amount: expr, $dest_asset: expr, $dest_address: expr, $metadata: expr, $tx_id: expr, $params: expr) => {
+	($source_asset: expr, $deposit_amount: expr, $dest_asset: expr, $dest_address: expr, $metadata: expr, $tx_id: expr, $sender: expr, $params: expr, $tracking_id: expr) => {
 		if let Some(params) = $params {
 			VaultDepositWitness {
 				input_asset: $source_asset.try_into().expect("invalid asset for chain"),
@@ -252,6 +257,8 @@ macro_rules! vault_deposit_witness {
 				refund_params: Some(params.refund_params),
 				channel_id: None,
 				deposit_address: None,
+				tracking_id: $tracking_id,
+				verified_sender: Some($sender),
 			}
 		} else {
 			VaultDepositWitness {
@@ -269,6 +276,8 @@ macro_rules! vault_deposit_witness {
 				refund_params: None,
 				channel_id: None,
 				deposit_address: None,
+				tracking_id: None,
+				verified_sender: Some($sender),
 			}
 		}
 	}
@@ -287,7 +296,9 @@ pub trait IngressCallBuilder {
 		destination_address: EncodedAddress,
 		deposit_metadata: Option<CcmDepositMetadata>,
 		tx_hash: H256,
+		sender: EthereumAddress,
 		vault_swap_parameters: Option<VaultSwapParameters>,
+		tracking_id: Option<TrackingId>,
 	) -> state_chain_runtime::RuntimeCall;
 
 	fn vault_transfer_failed(
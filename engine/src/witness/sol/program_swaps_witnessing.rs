@@ -123,23 +123,22 @@ pub async fn get_program_swaps(
 							},
 						) = match ccm_parameters {
 							None => {
-								let VersionedCfParameters::V0(CfParameters {
-									ccm_additional_data: (),
-									vault_swap_parameters,
-								}) = VersionedCfParameters::decode(&mut &cf_parameters[..])
-									.map_err(|e| {
-										anyhow!("Error while decoding VersionedCfParameters for solana vault swap: {}.", e)
-									})?;
+								// Solana vault swaps don't carry tracking ids.
+								let (CfParameters { ccm_additional_data: (), vault_swap_parameters }, _) =
+									VersionedCfParameters::decode(&mut &cf_parameters[..])
+										.map_err(|e| {
+											anyhow!("Error while decoding VersionedCfParameters for solana vault swap: {}.", e)
+										})?
+										.into_parts();
 								(None, vault_swap_parameters)
 							},
 							Some(ccm_parameters) => {
-								let VersionedCcmCfParameters::V0(CfParameters {
-									ccm_additional_data,
-									vault_swap_parameters
-									}) = VersionedCcmCfParameters::decode(&mut &cf_parameters[..]).map_err(|e| {
+								let (CfParameters { ccm_additional_data, vault_swap_parameters }, _) =
+									VersionedCcmCfParameters::decode(&mut &cf_parameters[..])
+										.map_err(|e| {
 											anyhow!("Error while decoding VersionedCcmCfParameters for solana vault swap: {}.", e)
-										},
-									)?;
+										})?
+										.into_parts();
 
 								(
 									Some(CcmDepositMetadata {
@@ -25,7 +25,7 @@ fn bitcoin_broadcast_delay_works() {
 			assert_eq!(Validator::current_authorities().len(), 150);
 			let epoch = Validator::epoch_index();
 			let bitcoin_agg_key = BitcoinThresholdSigner::keys(epoch).unwrap().current;
-			let egress_id = (ForeignChain::Bitcoin, 1u64);
+			let egress_id = (ForeignChain::Bitcoin, 0, 1u64);
 			Environment::add_bitcoin_utxo_to_list(Utxo {
 				id: Default::default(),
 				amount: 1_000_000_000_000u64,
@@ -40,6 +40,7 @@ fn bitcoin_broadcast_delay_works() {
 						asset: btc::Asset::Btc,
 						amount: 1_000_000,
 						to: ScriptPubkey::P2PKH([0u8; 20]),
+						memo: None,
 					},
 					egress_id,
 				)],
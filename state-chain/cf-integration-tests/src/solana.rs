@@ -20,6 +20,7 @@ use cf_chains::{
 };
 use cf_primitives::{AccountRole, AuthorityCount, ForeignChain, SwapRequestId};
 use cf_test_utilities::{assert_events_match, assert_has_matching_event};
+use cf_traits::EgressOrigin;
 use cf_utilities::bs58_array;
 use codec::Encode;
 use frame_support::{
@@ -101,7 +102,7 @@ fn schedule_deposit_to_swap(
 	to: Asset,
 	ccm: Option<CcmChannelMetadata>,
 ) -> SwapRequestId {
-	assert_ok!(Swapping::request_swap_deposit_address_with_affiliates(
+	assert_ok!(Swapping::request_swap_deposit_address_with_parameters(
 		RuntimeOrigin::signed(who.clone()),
 		from,
 		to,
@@ -112,6 +113,9 @@ fn schedule_deposit_to_swap(
 		Default::default(),
 		None,
 		None,
+		None,
+		false,
+		None,
 	));
 
 	let deposit_address = <AddressDerivation as AddressDerivationApi<Solana>>::generate_address(
@@ -202,6 +206,7 @@ fn can_build_solana_batch_all() {
 				>::BatchBroadcastRequested {
 					broadcast_id: 2,
 					egress_ids: vec![],
+					egress_origins: vec![],
 				}),
 			);
 
@@ -214,7 +219,8 @@ fn can_build_solana_batch_all() {
 					SolanaInstance,
 				>::BatchBroadcastRequested {
 					broadcast_id: 3,
-					egress_ids: vec![(ForeignChain::Solana, 1)],
+					egress_ids: vec![(ForeignChain::Solana, 0, 1)],
+					egress_origins: vec![((ForeignChain::Solana, 0, 1), EgressOrigin::Swap)],
 				}),
 			);
 			System::assert_has_event(
@@ -223,7 +229,8 @@ fn can_build_solana_batch_all() {
 					SolanaInstance,
 				>::BatchBroadcastRequested {
 					broadcast_id: 4,
-					egress_ids: vec![(ForeignChain::Solana, 2)],
+					egress_ids: vec![(ForeignChain::Solana, 0, 2)],
+					egress_origins: vec![((ForeignChain::Solana, 0, 2), EgressOrigin::Swap)],
 				}),
 			);
 		});
@@ -347,6 +354,7 @@ fn can_send_solana_ccm() {
 				>::BatchBroadcastRequested {
 					broadcast_id: 2,
 					egress_ids: vec![],
+					egress_origins: vec![],
 				}),
 			);
 
@@ -355,13 +363,15 @@ fn can_send_solana_ccm() {
 			System::assert_has_event(RuntimeEvent::SolanaIngressEgress(
 				pallet_cf_ingress_egress::Event::<Runtime, SolanaInstance>::CcmBroadcastRequested {
 					broadcast_id: 3,
-					egress_id: (ForeignChain::Solana, 1),
+					egress_id: (ForeignChain::Solana, 0, 1),
+					origin: EgressOrigin::Swap,
 				},
 			));
 			System::assert_has_event(RuntimeEvent::SolanaIngressEgress(
 				pallet_cf_ingress_egress::Event::<Runtime, SolanaInstance>::CcmBroadcastRequested {
 					broadcast_id: 4,
-					egress_id: (ForeignChain::Solana, 2),
+					egress_id: (ForeignChain::Solana, 0, 2),
+					origin: EgressOrigin::Swap,
 				},
 			));
 		});
@@ -385,6 +395,8 @@ fn vault_swap_deposit_witness(
 		boost_fee: 0,
 		deposit_address: Some(SolAddress([2u8; 32])),
 		channel_id: Some(0),
+		tracking_id: None,
+		verified_sender: None,
 	}
 }
 
@@ -428,7 +440,7 @@ fn solana_ccm_fails_with_invalid_input() {
 
 			// Unable to register a deposit channel using an invalid CCM
 			assert_noop!(
-				Swapping::request_swap_deposit_address_with_affiliates(
+				Swapping::request_swap_deposit_address_with_parameters(
 					RuntimeOrigin::signed(ZION),
 					Asset::Sol,
 					Asset::SolUsdc,
@@ -439,6 +451,9 @@ fn solana_ccm_fails_with_invalid_input() {
 					Default::default(),
 					None,
 					None,
+					None,
+					false,
+					None,
 				),
 				pallet_cf_swapping::Error::<Runtime>::InvalidCcm,
 			);
@@ -504,7 +519,11 @@ fn solana_ccm_fails_with_invalid_input() {
 			witness_call(RuntimeCall::SolanaIngressEgress(
 				pallet_cf_ingress_egress::Call::vault_swap_request {
 					block_height: 0,
-					deposit: Box::new(vault_swap_deposit_witness(Some(ccm))),
+					// A different transaction from the one above, which is not processed twice.
+					deposit: Box::new(VaultDepositWitness {
+						tx_id: (SolAddress([3u8; 32]), 1),
+						..vault_swap_deposit_witness(Some(ccm))
+					}),
 				},
 			));
 			// Setting the current agg key will invalidate the CCM.
@@ -524,7 +543,7 @@ fn solana_ccm_fails_with_invalid_input() {
 					Runtime,
 					SolanaInstance,
 				>::CcmEgressInvalid {
-					egress_id: (ForeignChain::Solana, 1u64),
+					egress_id: (ForeignChain::Solana, 0, 1u64),
 					error: ExecutexSwapAndCallError::FailedToBuildCcmForSolana(
 						SolanaTransactionBuildingError::InvalidCcm(
 							CcmValidityError::CcmAdditionalDataContainsInvalidAccounts
@@ -764,7 +783,7 @@ fn solana_ccm_execution_error_can_trigger_fallback() {
 			assert_eq!(pallet_cf_ingress_egress::ScheduledEgressFetchOrTransfer::<Runtime, SolanaInstance>::decode_len(), Some(1));
 			assert!(matches!(pallet_cf_ingress_egress::ScheduledEgressFetchOrTransfer::<Runtime, SolanaInstance>::get()[0],
 				FetchOrTransfer::Transfer {
-					egress_id: (ForeignChain::Solana, 2),
+					egress_id: (ForeignChain::Solana, 0, 2),
 					asset: SolAsset::SolUsdc,
 					destination_address: FALLBACK_ADDRESS,
 					..
@@ -258,7 +258,7 @@ fn basic_pool_setup_provision_and_swap() {
 			set_limit_order(&DORIS, Asset::Flip, Asset::Usdc, 0, Some(0), 1_000_000 * DECIMALS);
 			set_limit_order(&DORIS, Asset::Usdc, Asset::Flip, 0, Some(0), 1_000_000 * DECIMALS);
 
-			assert_ok!(Swapping::request_swap_deposit_address_with_affiliates(
+			assert_ok!(Swapping::request_swap_deposit_address_with_parameters(
 				RuntimeOrigin::signed(ZION.clone()),
 				Asset::Eth,
 				Asset::Flip,
@@ -269,6 +269,9 @@ fn basic_pool_setup_provision_and_swap() {
 				Default::default(),
 				None,
 				None,
+				None,
+				false,
+				None,
 			));
 
 			let deposit_address =
@@ -343,7 +346,7 @@ fn basic_pool_setup_provision_and_swap() {
 				) if executed_swap_request_id == swap_request_id => (),
 				RuntimeEvent::Swapping(
 					pallet_cf_swapping::Event::SwapEgressScheduled {
-						egress_id: egress_id @ (ForeignChain::Ethereum, _),
+						egress_id: egress_id @ (ForeignChain::Ethereum, _, _),
 						asset: Asset::Flip,
 						..
 					},
@@ -382,7 +385,7 @@ fn can_process_ccm_via_swap_deposit_address() {
 			ccm_additional_data: Default::default(),
 		};
 
-		assert_ok!(Swapping::request_swap_deposit_address_with_affiliates(
+		assert_ok!(Swapping::request_swap_deposit_address_with_parameters(
 			RuntimeOrigin::signed(ZION.clone()),
 			Asset::Flip,
 			Asset::Usdc,
@@ -393,6 +396,9 @@ fn can_process_ccm_via_swap_deposit_address() {
 			Default::default(),
 			None,
 			None,
+			None,
+			false,
+			None,
 		));
 
 		// Deposit funds for the ccm.
@@ -488,7 +494,7 @@ fn can_process_ccm_via_swap_deposit_address() {
 			RuntimeEvent::Swapping(
 				pallet_cf_swapping::Event::SwapEgressScheduled {
 					swap_request_id: swap_request_id_in_event,
-					egress_id: egress_id @ (ForeignChain::Ethereum, _),
+					egress_id: egress_id @ (ForeignChain::Ethereum, _, _),
 					..
 				},
 			) if swap_request_id_in_event == swap_request_id => egress_id
@@ -537,6 +543,8 @@ fn vault_swap_deposit_witness(
 		boost_fee: 0,
 		deposit_address: Some(H160::from([0x03; 20])),
 		channel_id: Some(0),
+		tracking_id: None,
+		verified_sender: None,
 	}
 }
 
@@ -679,6 +687,7 @@ fn ethereum_ccm_can_calculate_gas_limits() {
 					asset: EthAsset::Flip,
 					amount: 1_000,
 					to: Default::default(),
+					memo: None,
 				},
 				ForeignChain::Ethereum,
 				None,
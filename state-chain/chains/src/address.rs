@@ -28,6 +28,19 @@ pub enum AddressDerivationError {
 	SolanaDerivationError(sol::AddressDerivationError),
 }
 
+impl AddressDerivationError {
+	/// Whether the error is caused by chain state that is expected to become available, such
+	/// as a vault that hasn't been activated yet, so that derivation may succeed later.
+	pub fn is_transient(&self) -> bool {
+		matches!(
+			self,
+			Self::MissingPolkadotVault |
+				Self::MissingBitcoinVault |
+				Self::MissingSolanaApiEnvironment
+		)
+	}
+}
+
 impl From<sol::AddressDerivationError> for AddressDerivationError {
 	fn from(value: sol::AddressDerivationError) -> Self {
 		Self::SolanaDerivationError(value)
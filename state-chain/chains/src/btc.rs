@@ -9,6 +9,7 @@ use self::deposit_address::DepositAddress;
 use crate::{
 	benchmarking_value::BenchmarkValue, Chain, ChainCrypto, DepositChannel,
 	DepositDetailsToTransactionInId, FeeEstimationApi, FeeRefundCalculator, RetryPolicy,
+	UniqueDepositId,
 };
 use alloc::{collections::VecDeque, string::String};
 use arrayref::array_ref;
@@ -31,7 +32,7 @@ use libsecp256k1::{curve::*, PublicKey, SecretKey};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sp_core::H256;
-use sp_io::hashing::sha2_256;
+use sp_io::hashing::{blake2_256, sha2_256};
 use sp_runtime::traits::Member;
 use sp_std::{vec, vec::Vec};
 
@@ -404,6 +405,12 @@ impl DepositDetailsToTransactionInId<BitcoinCrypto> for Utxo {
 	fn deposit_id(&self) -> Option<Hash> {
 		Some(self.id.tx_id)
 	}
+
+	/// A transaction can pay into the same address several times, so the output index is needed
+	/// to tell the deposits apart.
+	fn unique_deposit_id(&self) -> Option<UniqueDepositId> {
+		Some(blake2_256(&self.id.encode()).into())
+	}
 }
 
 #[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, RuntimeDebug, PartialEq, Eq)]
@@ -164,6 +164,11 @@ where
 			vec![BitcoinOutput { amount: refund_amount, script_pubkey: refund_address }],
 		)))
 	}
+
+	fn refund_transaction_in_id(&self) -> Option<<BitcoinCrypto as ChainCrypto>::TransactionInId> {
+		// The txid doesn't commit to the witness, so it is known before the call is signed.
+		Some(self.transaction_out_id())
+	}
 }
 
 // transfer_fallback is unsupported for Bitcoin.
@@ -1,5 +1,5 @@
 use crate::address::EncodedAddress;
-use cf_primitives::{AffiliateAndFee, Asset, AssetAmount, ForeignChain};
+use cf_primitives::{AffiliateAndFee, Asset, AssetAmount, ForeignChain, TrackingId};
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::ConstU32;
@@ -7,21 +7,26 @@ use sp_runtime::BoundedVec;
 use sp_std::vec::Vec;
 
 // The maximum length of data that can be encoded in a nulldata utxo
-const MAX_NULLDATA_LENGTH: usize = 80;
+pub const MAX_NULLDATA_LENGTH: usize = 80;
 const CURRENT_VERSION: u8 = 0;
+// Same as the current version, followed by a tracking id.
+const VERSION_WITH_TRACKING_ID: u8 = 1;
 
 #[derive(Clone, PartialEq, Debug, TypeInfo)]
 pub struct UtxoEncodedData {
 	pub output_asset: Asset,
 	pub output_address: EncodedAddress,
 	pub parameters: BtcCfParameters,
+	/// Only fits in the nulldata output if the destination address and affiliates are short.
+	pub tracking_id: Option<TrackingId>,
 }
 
 impl Encode for UtxoEncodedData {
 	fn encode(&self) -> Vec<u8> {
 		let mut r = Vec::with_capacity(MAX_NULLDATA_LENGTH);
 
-		CURRENT_VERSION.encode_to(&mut r);
+		if self.tracking_id.is_some() { VERSION_WITH_TRACKING_ID } else { CURRENT_VERSION }
+			.encode_to(&mut r);
 
 		self.output_asset.encode_to(&mut r);
 
@@ -37,6 +42,10 @@ impl Encode for UtxoEncodedData {
 
 		self.parameters.encode_to(&mut r);
 
+		if let Some(tracking_id) = &self.tracking_id {
+			tracking_id.encode_to(&mut r);
+		}
+
 		r
 	}
 }
@@ -45,9 +54,9 @@ impl Decode for UtxoEncodedData {
 	fn decode<I: codec::Input>(input: &mut I) -> Result<Self, codec::Error> {
 		let version = u8::decode(input)?;
 
-		if version != CURRENT_VERSION {
+		if version != CURRENT_VERSION && version != VERSION_WITH_TRACKING_ID {
 			log::warn!(
-				"Unexpected version of utxo encoding: {version} (expected: {CURRENT_VERSION})"
+				"Unexpected version of utxo encoding: {version} (expected: {CURRENT_VERSION} or {VERSION_WITH_TRACKING_ID})"
 			);
 			return Err("unexpected version".into());
 		}
@@ -64,7 +73,13 @@ impl Decode for UtxoEncodedData {
 
 		let parameters = BtcCfParameters::decode(input)?;
 
-		Ok(UtxoEncodedData { output_asset, output_address, parameters })
+		let tracking_id = if version == VERSION_WITH_TRACKING_ID {
+			Some(TrackingId::decode(input)?)
+		} else {
+			None
+		};
+
+		Ok(UtxoEncodedData { output_asset, output_address, parameters, tracking_id })
 	}
 }
 
@@ -118,6 +133,7 @@ mod tests {
 					AffiliateAndFee { affiliate: 8.into(), fee: 9 }
 				],
 			},
+			tracking_id: None,
 		};
 		// The following encoding is expected for MOCK_SWAP_PARAMS:
 		// (not using "insta" because we want to be precise about how the data
@@ -144,4 +160,29 @@ mod tests {
 
 		assert_eq!(UtxoEncodedData::decode(&mut expected_encoding.as_ref()), Ok(mock_swap_params));
 	}
+
+	#[test]
+	fn check_utxo_encoding_with_tracking_id() {
+		let mock_swap_params = UtxoEncodedData {
+			output_asset: Asset::Eth,
+			output_address: EncodedAddress::Eth([7u8; 20]),
+			parameters: BtcCfParameters {
+				retry_duration: 5,
+				min_output_amount: 1,
+				number_of_chunks: 1,
+				chunk_interval: 2,
+				boost_fee: 5,
+				broker_fee: 0xa,
+				affiliates: bounded_vec![],
+			},
+			tracking_id: Some([3u8; 32]),
+		};
+
+		let encoded = mock_swap_params.encode();
+		assert_eq!(encoded[0], VERSION_WITH_TRACKING_ID);
+		assert_eq!(encoded[encoded.len() - 32..], [3u8; 32]);
+		assert!(encoded.len() <= MAX_NULLDATA_LENGTH);
+
+		assert_eq!(UtxoEncodedData::decode(&mut encoded.as_ref()), Ok(mock_swap_params));
+	}
 }
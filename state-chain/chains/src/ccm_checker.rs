@@ -2,8 +2,8 @@ use crate::{
 	sol::{SolAsset, SolCcmAccounts, SolPubkey, MAX_CCM_BYTES_SOL, MAX_CCM_BYTES_USDC},
 	CcmChannelMetadata,
 };
-use cf_primitives::{Asset, ForeignChain};
-use codec::{Decode, Encode};
+use cf_primitives::{Asset, ForeignChain, GasAmount};
+use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sol_prim::consts::{
 	ACCOUNT_KEY_LENGTH_IN_TRANSACTION, ACCOUNT_REFERENCE_LENGTH_IN_TRANSACTION,
@@ -17,6 +17,8 @@ pub enum CcmValidityError {
 	CcmIsTooLong,
 	CcmAdditionalDataContainsInvalidAccounts,
 	RedundantDataSupplied,
+	CcmGasBudgetTooHigh,
+	CcmAdditionalDataTooLong,
 }
 impl From<CcmValidityError> for DispatchError {
 	fn from(value: CcmValidityError) -> Self {
@@ -28,10 +30,36 @@ impl From<CcmValidityError> for DispatchError {
 				"Invalid Ccm: additional data contains invalid accounts".into(),
 			CcmValidityError::RedundantDataSupplied =>
 				"Invalid Ccm: Additional data supplied but they will not be used".into(),
+			CcmValidityError::CcmGasBudgetTooHigh => "Invalid Ccm: gas budget too high".into(),
+			CcmValidityError::CcmAdditionalDataTooLong =>
+				"Invalid Ccm: additional data too long".into(),
 		}
 	}
 }
 
+/// Limits imposed on CCMs egressed to a given chain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct CcmLimits {
+	pub max_message_length: u32,
+	pub max_gas_budget: GasAmount,
+	pub max_additional_data_length: u32,
+}
+
+impl CcmLimits {
+	pub fn check(&self, ccm: &CcmChannelMetadata) -> Result<(), CcmValidityError> {
+		if ccm.message.len() > self.max_message_length as usize {
+			return Err(CcmValidityError::CcmIsTooLong)
+		}
+		if ccm.gas_budget > self.max_gas_budget {
+			return Err(CcmValidityError::CcmGasBudgetTooHigh)
+		}
+		if ccm.ccm_additional_data.len() > self.max_additional_data_length as usize {
+			return Err(CcmValidityError::CcmAdditionalDataTooLong)
+		}
+		Ok(())
+	}
+}
+
 pub trait CcmValidityCheck {
 	fn check_and_decode(
 		_ccm: &CcmChannelMetadata,
@@ -401,4 +429,38 @@ mod test {
 			CcmValidityError::CcmAdditionalDataContainsInvalidAccounts
 		);
 	}
+
+	#[test]
+	fn can_check_ccm_limits() {
+		let limits = CcmLimits {
+			max_message_length: 5,
+			max_gas_budget: 1_000,
+			max_additional_data_length: 3,
+		};
+		let ccm = CcmChannelMetadata {
+			message: vec![0x01; 5].try_into().unwrap(),
+			gas_budget: 1_000,
+			ccm_additional_data: vec![0x01; 3].try_into().unwrap(),
+		};
+		assert_ok!(limits.check(&ccm));
+
+		assert_err!(
+			limits.check(&CcmChannelMetadata {
+				message: vec![0x01; 6].try_into().unwrap(),
+				..ccm.clone()
+			}),
+			CcmValidityError::CcmIsTooLong
+		);
+		assert_err!(
+			limits.check(&CcmChannelMetadata { gas_budget: 1_001, ..ccm.clone() }),
+			CcmValidityError::CcmGasBudgetTooHigh
+		);
+		assert_err!(
+			limits.check(&CcmChannelMetadata {
+				ccm_additional_data: vec![0x01; 4].try_into().unwrap(),
+				..ccm
+			}),
+			CcmValidityError::CcmAdditionalDataTooLong
+		);
+	}
 }
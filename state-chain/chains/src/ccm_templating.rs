@@ -0,0 +1,98 @@
+//! Placeholders that can be included in a CCM message and are substituted with values that are
+//! only known once the swap has been executed.
+//!
+//! Placeholders are 32 bytes long and are replaced in place, so the length of the message is
+//! unchanged. The value is encoded as a 32-byte word in the native encoding of the destination
+//! chain: big-endian (ie. an ABI-encoded `uint256`) for EVM chains and little-endian for Solana.
+use cf_primitives::ForeignChain;
+
+pub const CCM_PLACEHOLDER_LENGTH: usize = 32;
+
+/// Substituted with the amount egressed to the destination address, after fees.
+pub const CCM_OUTPUT_AMOUNT_PLACEHOLDER: [u8; CCM_PLACEHOLDER_LENGTH] =
+	*b"{{CHAINFLIP::CCM_OUTPUT_AMOUNT}}";
+
+/// Substituted with the id of the swap request that produced the output.
+pub const CCM_SWAP_REQUEST_ID_PLACEHOLDER: [u8; CCM_PLACEHOLDER_LENGTH] =
+	*b"{{CHAINFLIP::CCM_SWAPREQUESTID}}";
+
+fn encode_for_chain(chain: ForeignChain, value: u128) -> Option<[u8; CCM_PLACEHOLDER_LENGTH]> {
+	let mut word = [0u8; CCM_PLACEHOLDER_LENGTH];
+	match chain {
+		ForeignChain::Ethereum | ForeignChain::Arbitrum =>
+			word[CCM_PLACEHOLDER_LENGTH - 16..].copy_from_slice(&value.to_be_bytes()),
+		ForeignChain::Solana => word[..16].copy_from_slice(&value.to_le_bytes()),
+		ForeignChain::Bitcoin | ForeignChain::Polkadot => return None,
+	}
+	Some(word)
+}
+
+/// Replaces every occurrence of `placeholder` in `message` with `value`, encoded for `chain`.
+/// Messages egressed to chains without CCM support are left unchanged.
+pub fn substitute_ccm_placeholder(
+	message: &mut [u8],
+	placeholder: &[u8; CCM_PLACEHOLDER_LENGTH],
+	value: u128,
+	chain: ForeignChain,
+) {
+	let Some(encoded_value) = encode_for_chain(chain, value) else { return };
+
+	let mut i = 0;
+	while i + CCM_PLACEHOLDER_LENGTH <= message.len() {
+		if message[i..i + CCM_PLACEHOLDER_LENGTH] == placeholder[..] {
+			message[i..i + CCM_PLACEHOLDER_LENGTH].copy_from_slice(&encoded_value);
+			i += CCM_PLACEHOLDER_LENGTH;
+		} else {
+			i += 1;
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn placeholders_are_substituted_per_chain_encoding() {
+		let message =
+			[&[0xAA][..], &CCM_OUTPUT_AMOUNT_PLACEHOLDER, &CCM_OUTPUT_AMOUNT_PLACEHOLDER].concat();
+
+		let mut evm_message = message.clone();
+		substitute_ccm_placeholder(
+			&mut evm_message,
+			&CCM_OUTPUT_AMOUNT_PLACEHOLDER,
+			0x0102,
+			ForeignChain::Ethereum,
+		);
+		let mut expected_word = [0u8; 32];
+		expected_word[30..].copy_from_slice(&[0x01, 0x02]);
+		assert_eq!(evm_message, [&[0xAA][..], &expected_word, &expected_word].concat());
+
+		let mut sol_message = message.clone();
+		substitute_ccm_placeholder(
+			&mut sol_message,
+			&CCM_OUTPUT_AMOUNT_PLACEHOLDER,
+			0x0102,
+			ForeignChain::Solana,
+		);
+		let mut expected_word = [0u8; 32];
+		expected_word[..2].copy_from_slice(&[0x02, 0x01]);
+		assert_eq!(sol_message, [&[0xAA][..], &expected_word, &expected_word].concat());
+
+		// Other placeholders and chains without CCM support are left untouched:
+		let mut unchanged = message.clone();
+		substitute_ccm_placeholder(
+			&mut unchanged,
+			&CCM_SWAP_REQUEST_ID_PLACEHOLDER,
+			1,
+			ForeignChain::Ethereum,
+		);
+		substitute_ccm_placeholder(
+			&mut unchanged,
+			&CCM_OUTPUT_AMOUNT_PLACEHOLDER,
+			1,
+			ForeignChain::Bitcoin,
+		);
+		assert_eq!(unchanged, message);
+	}
+}
@@ -1,6 +1,6 @@
 use crate::{CcmAdditionalData, CcmChannelMetadata, ChannelRefundParametersDecoded};
 use cf_primitives::{
-	AccountId, AffiliateAndFee, BasisPoints, Beneficiary, DcaParameters, MAX_AFFILIATES,
+	AccountId, AffiliateAndFee, BasisPoints, Beneficiary, DcaParameters, TrackingId, MAX_AFFILIATES,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
@@ -10,6 +10,21 @@ use sp_runtime::{BoundedVec, Vec};
 #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Debug)]
 pub enum VersionedCfParameters<CcmData = ()> {
 	V0(CfParameters<CcmData>),
+	/// The V0 parameters, along with the tracking id chosen by the broker.
+	V1 {
+		parameters: CfParameters<CcmData>,
+		tracking_id: TrackingId,
+	},
+}
+
+impl<CcmData> VersionedCfParameters<CcmData> {
+	pub fn into_parts(self) -> (CfParameters<CcmData>, Option<TrackingId>) {
+		match self {
+			VersionedCfParameters::V0(parameters) => (parameters, None),
+			VersionedCfParameters::V1 { parameters, tracking_id } =>
+				(parameters, Some(tracking_id)),
+		}
+	}
 }
 
 #[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Debug)]
@@ -42,6 +57,9 @@ pub struct VaultSwapParameters {
 /// Provide a function that builds and encodes `cf_parameters`.
 /// The return type is encoded Vec<u8>, which circumvents the difference in return types depending
 /// on if CCM data is available.
+///
+/// The V0 encoding is used unless a tracking id is given, so that existing encodings are unchanged.
+#[allow(clippy::too_many_arguments)]
 pub fn build_cf_parameters(
 	refund_parameters: ChannelRefundParametersDecoded,
 	dca_parameters: Option<DcaParameters>,
@@ -50,6 +68,7 @@ pub fn build_cf_parameters(
 	broker_commission: BasisPoints,
 	affiliate_fees: BoundedVec<AffiliateAndFee, ConstU32<MAX_AFFILIATES>>,
 	ccm: Option<&CcmChannelMetadata>,
+	tracking_id: Option<TrackingId>,
 ) -> Vec<u8> {
 	let vault_swap_parameters = VaultSwapParameters {
 		refund_params: refund_parameters,
@@ -59,17 +78,28 @@ pub fn build_cf_parameters(
 		affiliate_fees,
 	};
 
+	fn versioned<CcmData>(
+		parameters: CfParameters<CcmData>,
+		tracking_id: Option<TrackingId>,
+	) -> VersionedCfParameters<CcmData> {
+		match tracking_id {
+			Some(tracking_id) => VersionedCfParameters::V1 { parameters, tracking_id },
+			None => VersionedCfParameters::V0(parameters),
+		}
+	}
+
 	match ccm {
-		Some(ccm) => VersionedCcmCfParameters::V0(CfParameters {
-			ccm_additional_data: ccm.ccm_additional_data.clone(),
-			vault_swap_parameters,
-		})
-		.encode(),
-		None => VersionedCfParameters::V0(CfParameters {
-			ccm_additional_data: (),
-			vault_swap_parameters,
-		})
+		Some(ccm) => versioned(
+			CfParameters {
+				ccm_additional_data: ccm.ccm_additional_data.clone(),
+				vault_swap_parameters,
+			},
+			tracking_id,
+		)
 		.encode(),
+		None =>
+			versioned(CfParameters { ccm_additional_data: (), vault_swap_parameters }, tracking_id)
+				.encode(),
 	}
 }
 
@@ -133,4 +163,39 @@ mod tests {
 
 		assert_eq!(encoded, expected_encoded_with_metadata);
 	}
+
+	#[test]
+	fn tracking_id_is_only_encoded_when_given() {
+		let encode = |tracking_id| {
+			build_cf_parameters(
+				ChannelRefundParametersDecoded {
+					retry_duration: 1,
+					refund_address: ForeignChainAddress::Eth(sp_core::H160::from([2; 20])),
+					min_price: Default::default(),
+				},
+				None,
+				0,
+				AccountId::new([3; 32]),
+				4,
+				Default::default(),
+				None,
+				tracking_id,
+			)
+		};
+
+		assert_eq!(
+			encode(None),
+			hex::decode(REFERENCE_EXPECTED_ENCODED_HEX).expect("Decoding hex string failed")
+		);
+
+		let encoded = encode(Some([5; 32]));
+		assert_eq!(encoded[0], 1);
+		let (parameters, tracking_id) =
+			VersionedCfParameters::<()>::decode(&mut &encoded[..]).unwrap().into_parts();
+		assert_eq!(tracking_id, Some([5; 32]));
+		assert_eq!(
+			parameters.vault_swap_parameters.broker_fee,
+			Beneficiary { account: AccountId::new([3; 32]), bps: 4 }
+		);
+	}
 }
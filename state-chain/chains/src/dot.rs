@@ -324,6 +324,11 @@ impl Chain for Polkadot {
 	const GAS_ASSET: Self::ChainAsset = assets::dot::Asset::Dot;
 	const WITNESS_PERIOD: Self::ChainBlockNumber = 1;
 
+	/// Memos are attached as a remark in the same batch as the transfer.
+	fn is_valid_egress_memo(memo: &EgressMemo) -> bool {
+		!memo.is_empty()
+	}
+
 	type ChainCrypto = PolkadotCrypto;
 	type ChainBlockNumber = PolkadotBlockNumber;
 	type ChainAmount = PolkadotBalance;
@@ -518,9 +523,20 @@ pub enum PolkadotRuntimeCall {
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
-pub enum SystemCall {}
+pub enum SystemCall {
+	/// Make some on-chain remark and emit an event.
+	#[codec(index = 7u8)]
+	remark_with_event {
+		#[allow(missing_docs)]
+		remark: Vec<u8>,
+	},
+}
 
-impl DepositDetailsToTransactionInId<PolkadotCrypto> for u32 {}
+impl DepositDetailsToTransactionInId<PolkadotCrypto> for u32 {
+	fn unique_deposit_id(&self) -> Option<UniqueDepositId> {
+		Some(H256::from_low_u64_be(*self as u64))
+	}
+}
 
 #[allow(non_camel_case_types)]
 #[derive(Debug, Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
@@ -2,13 +2,13 @@ use crate::{
 	dot::{
 		BalancesCall, Polkadot, PolkadotAccountId, PolkadotAccountIdLookup,
 		PolkadotExtrinsicBuilder, PolkadotProxyType, PolkadotReplayProtection, PolkadotRuntimeCall,
-		ProxyCall, UtilityCall,
+		ProxyCall, SystemCall, UtilityCall,
 	},
 	FetchAssetParams, TransferAssetParams,
 };
 use cf_primitives::ChannelId;
 use cf_utilities::SliceToArray;
-use sp_std::{boxed::Box, vec::Vec};
+use sp_std::{boxed::Box, vec, vec::Vec};
 
 pub fn extrinsic_builder(
 	replay_protection: PolkadotReplayProtection,
@@ -31,12 +31,7 @@ pub fn extrinsic_builder(
 						.collect::<Vec<PolkadotRuntimeCall>>(),
 					transfer_params
 						.into_iter()
-						.map(|transfer_param| {
-							PolkadotRuntimeCall::Balances(BalancesCall::transfer_allow_death {
-								dest: PolkadotAccountIdLookup::from(transfer_param.to),
-								value: transfer_param.amount,
-							})
-						})
+						.map(utility_transfer)
 						.collect::<Vec<PolkadotRuntimeCall>>(),
 				]
 				.concat(),
@@ -45,6 +40,24 @@ pub fn extrinsic_builder(
 	)
 }
 
+/// A transfer with a memo is batched atomically with a remark carrying the memo, so that the
+/// funds are never sent without it.
+fn utility_transfer(transfer_param: TransferAssetParams<Polkadot>) -> PolkadotRuntimeCall {
+	let transfer = PolkadotRuntimeCall::Balances(BalancesCall::transfer_allow_death {
+		dest: PolkadotAccountIdLookup::from(transfer_param.to),
+		value: transfer_param.amount,
+	});
+	match transfer_param.memo {
+		Some(memo) => PolkadotRuntimeCall::Utility(UtilityCall::batch_all {
+			calls: vec![
+				transfer,
+				PolkadotRuntimeCall::System(SystemCall::remark_with_event { remark: memo.into() }),
+			],
+		}),
+		None => transfer,
+	}
+}
+
 fn utility_fetch(channel_id: ChannelId, vault_account: PolkadotAccountId) -> PolkadotRuntimeCall {
 	let layers = channel_id
 		.to_be_bytes()
@@ -89,16 +102,19 @@ mod test_batch_fetch {
 				to: PolkadotAccountId::from_aliased([7u8; 32]),
 				amount: 4,
 				asset: assets::dot::Asset::Dot,
+				memo: None,
 			},
 			TransferAssetParams::<Polkadot> {
 				to: PolkadotAccountId::from_aliased([8u8; 32]),
 				amount: 5,
 				asset: assets::dot::Asset::Dot,
+				memo: None,
 			},
 			TransferAssetParams::<Polkadot> {
 				to: PolkadotAccountId::from_aliased([9u8; 32]),
 				amount: 6,
 				asset: assets::dot::Asset::Dot,
+				memo: None,
 			},
 		];
 
@@ -126,6 +142,41 @@ mod test_batch_fetch {
 		assert!(builder.is_signed());
 	}
 
+	#[test]
+	fn transfer_with_memo_is_batched_with_remark() {
+		let to = PolkadotAccountId::from_aliased([7u8; 32]);
+		let transfer = PolkadotRuntimeCall::Balances(BalancesCall::transfer_allow_death {
+			dest: PolkadotAccountIdLookup::from(to),
+			value: 4,
+		});
+
+		assert_eq!(
+			utility_transfer(TransferAssetParams {
+				to,
+				amount: 4,
+				asset: assets::dot::Asset::Dot,
+				memo: None
+			}),
+			transfer
+		);
+		assert_eq!(
+			utility_transfer(TransferAssetParams {
+				to,
+				amount: 4,
+				asset: assets::dot::Asset::Dot,
+				memo: Some(b"exchange-tag".to_vec().try_into().unwrap()),
+			}),
+			PolkadotRuntimeCall::Utility(UtilityCall::batch_all {
+				calls: vec![
+					transfer,
+					PolkadotRuntimeCall::System(SystemCall::remark_with_event {
+						remark: b"exchange-tag".to_vec()
+					}),
+				],
+			})
+		);
+	}
+
 	#[test]
 	fn nested_fetch() {
 		let channel_id = 0x0004_0003_0002_0001;
@@ -656,6 +656,13 @@ impl DepositDetailsToTransactionInId<EvmCrypto> for DepositDetails {
 	fn deposit_id(&self) -> Option<H256> {
 		None
 	}
+
+	/// Token deposits are witnessed per transaction. Native deposits to addresses without a
+	/// deployed contract are witnessed from the balance change, without any transaction hashes,
+	/// but there is at most one such deposit per address and block.
+	fn unique_deposit_id(&self) -> Option<UniqueDepositId> {
+		Some(H256(Blake2_256::hash(&self.tx_hashes.encode())))
+	}
 }
 
 #[cfg(test)]
@@ -303,7 +303,7 @@ pub fn evm_all_batch_builder<
 				fetch_only_params,
 				transfer_params
 					.into_iter()
-					.map(|TransferAssetParams { asset, to, amount }| {
+					.map(|TransferAssetParams { asset, to, amount, .. }| {
 						token_address_fn(asset)
 							.map(|address| EncodableTransferAssetParams {
 								to,
@@ -52,6 +52,7 @@ pub mod test_utils {
 			broker_fee().bps,
 			affiliate_fees(),
 			with_ccm.then_some(&channel_metadata()),
+			None,
 		)
 	}
 }
@@ -66,6 +66,7 @@ use cf_primitives::chains::assets::any::GetChainAssetMap;
 pub use deposit_channel::*;
 use strum::IntoEnumIterator;
 pub mod ccm_checker;
+pub mod ccm_templating;
 pub mod cf_parameters;
 pub mod instances;
 
@@ -123,6 +124,12 @@ pub trait Chain: Member + Parameter + ChainInstanceAlias {
 
 	const WITNESS_PERIOD: Self::ChainBlockNumber;
 
+	/// Whether a transfer on this chain can carry `memo`, for example the remark an exchange
+	/// requires to credit a deposit. Chains that don't support memos reject all of them.
+	fn is_valid_egress_memo(_memo: &EgressMemo) -> bool {
+		false
+	}
+
 	/// Outputs the root block that witnesses the range of blocks after (not including)
 	/// `block_number`
 	fn checked_block_witness_next(
@@ -440,6 +447,8 @@ pub struct TransferAssetParams<C: Chain> {
 	pub asset: <C as Chain>::ChainAsset,
 	pub amount: <C as Chain>::ChainAmount,
 	pub to: <C as Chain>::ChainAccount,
+	/// Attached to the transfer on chains that support it, see [Chain::is_valid_egress_memo].
+	pub memo: Option<EgressMemo>,
 }
 
 /// Similar to [frame_support::StaticLookup] but with the `Key` as a type parameter instead of an
@@ -535,7 +544,7 @@ impl From<DispatchError> for AllBatchError {
 	}
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
 pub enum ConsolidationError {
 	NotRequired,
 	Other,
@@ -559,6 +568,12 @@ pub trait RejectCall<C: Chain>: ApiCall<C::ChainCrypto> {
 	) -> Result<Self, RejectError> {
 		Err(RejectError::NotSupportedForAsset)
 	}
+
+	/// The id the refund transaction will be witnessed with if it is sent to one of our own
+	/// deposit addresses. Only available on chains where the id is known before signing.
+	fn refund_transaction_in_id(&self) -> Option<<C::ChainCrypto as ChainCrypto>::TransactionInId> {
+		None
+	}
 }
 
 pub trait AllBatch<C: Chain>: ApiCall<C::ChainCrypto> {
@@ -576,6 +591,9 @@ pub enum ExecutexSwapAndCallError {
 	FailedToBuildCcmForSolana(SolanaTransactionBuildingError),
 	/// Some other DispatchError occurred.
 	DispatchError(DispatchError),
+	/// The data required to build the call is not available yet. Building the call may succeed
+	/// if retried later.
+	NotReady,
 }
 
 pub trait ExecutexSwapAndCall<C: Chain>: ApiCall<C::ChainCrypto> {
@@ -680,8 +698,12 @@ pub enum SwapOrigin<AccountId> {
 	Vault {
 		tx_id: TransactionInIdForAnyChain,
 		broker_id: Option<AccountId>,
+		/// The sender of the deposit, if it could be verified when the deposit was witnessed.
+		verified_sender: Option<address::EncodedAddress>,
 	},
 	Internal,
+	/// Requested directly by an account, using its free balance.
+	OnChainAccount(AccountId),
 }
 
 impl<AccountId> SwapOrigin<AccountId> {
@@ -689,7 +711,7 @@ impl<AccountId> SwapOrigin<AccountId> {
 		match self {
 			Self::DepositChannel { ref broker_id, .. } => Some(broker_id),
 			Self::Vault { ref broker_id, .. } => broker_id.as_ref(),
-			Self::Internal => None,
+			Self::Internal | Self::OnChainAccount(_) => None,
 		}
 	}
 }
@@ -706,6 +728,23 @@ pub const MAX_CCM_ADDITIONAL_DATA_LENGTH: u32 = 1_000;
 pub type CcmMessage = BoundedVec<u8, ConstU32<MAX_CCM_MSG_LENGTH>>;
 pub type CcmAdditionalData = BoundedVec<u8, ConstU32<MAX_CCM_ADDITIONAL_DATA_LENGTH>>;
 
+pub const MAX_EGRESS_MEMO_LENGTH: u32 = 32;
+
+/// A memo, or destination tag, that some destinations such as exchange accounts need attached to
+/// a transfer in order to credit it.
+pub type EgressMemo = BoundedVec<u8, ConstU32<MAX_EGRESS_MEMO_LENGTH>>;
+
+/// Whether transfers on `chain` can carry `memo`, see [Chain::is_valid_egress_memo].
+pub fn is_valid_egress_memo(chain: ForeignChain, memo: &EgressMemo) -> bool {
+	match chain {
+		ForeignChain::Ethereum => Ethereum::is_valid_egress_memo(memo),
+		ForeignChain::Polkadot => Polkadot::is_valid_egress_memo(memo),
+		ForeignChain::Bitcoin => Bitcoin::is_valid_egress_memo(memo),
+		ForeignChain::Arbitrum => Arbitrum::is_valid_egress_memo(memo),
+		ForeignChain::Solana => Solana::is_valid_egress_memo(memo),
+	}
+}
+
 #[cfg(feature = "std")]
 mod bounded_hex {
 	use super::*;
@@ -950,10 +989,75 @@ pub enum RequiresSignatureRefresh<C: ChainCrypto, Api: ApiCall<C>> {
 	_Phantom(PhantomData<C>, Never),
 }
 
+/// Identifies a deposit among all deposits to the same address at the same block height.
+pub type UniqueDepositId = H256;
+
 pub trait DepositDetailsToTransactionInId<C: ChainCrypto> {
 	fn deposit_id(&self) -> Option<C::TransactionInId> {
 		None
 	}
+
+	/// Identifies the deposit among all deposits to the same address at the same block height,
+	/// so that a deposit that is witnessed more than once can be recognised. `None` if the
+	/// deposit details don't allow this.
+	fn unique_deposit_id(&self) -> Option<UniqueDepositId> {
+		None
+	}
+}
+
+/// The maximum encoded length of deposit details that are stored or emitted in full.
+pub const MAX_DEPOSIT_DETAILS_ENCODED_LEN: usize = 1024;
+
+/// Identifies deposit details that were too large to be kept in full.
+pub type DepositDetailsHash = H256;
+
+/// Deposit details whose size is bounded by [MAX_DEPOSIT_DETAILS_ENCODED_LEN]. Larger details are
+/// replaced by their hash and the id of the deposit transaction, and it is up to the owner to keep
+/// the full details in a lookup keyed by that hash for as long as they are needed.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum BoundedDepositDetails<C: Chain> {
+	Full(C::DepositDetails),
+	Pruned {
+		tx_id: Option<<C::ChainCrypto as ChainCrypto>::TransactionInId>,
+		hash: DepositDetailsHash,
+	},
+}
+
+impl<C: Chain> BoundedDepositDetails<C> {
+	pub fn new(deposit_details: C::DepositDetails) -> Self {
+		if deposit_details.encoded_size() > MAX_DEPOSIT_DETAILS_ENCODED_LEN {
+			Self::Pruned {
+				tx_id: deposit_details.deposit_id(),
+				hash: Blake2_256::hash(&deposit_details.encode()).into(),
+			}
+		} else {
+			Self::Full(deposit_details)
+		}
+	}
+
+	/// The hash under which the full details are kept, if they were pruned.
+	pub fn pruned_hash(&self) -> Option<DepositDetailsHash> {
+		match self {
+			Self::Full(_) => None,
+			Self::Pruned { hash, .. } => Some(*hash),
+		}
+	}
+}
+
+impl<C: Chain> DepositDetailsToTransactionInId<C::ChainCrypto> for BoundedDepositDetails<C> {
+	fn deposit_id(&self) -> Option<<C::ChainCrypto as ChainCrypto>::TransactionInId> {
+		match self {
+			Self::Full(deposit_details) => deposit_details.deposit_id(),
+			Self::Pruned { tx_id, .. } => tx_id.clone(),
+		}
+	}
+
+	fn unique_deposit_id(&self) -> Option<UniqueDepositId> {
+		match self {
+			Self::Full(deposit_details) => deposit_details.unique_deposit_id(),
+			Self::Pruned { .. } => None,
+		}
+	}
 }
 
 #[derive(
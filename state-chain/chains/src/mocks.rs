@@ -449,4 +449,8 @@ impl DepositDetailsToTransactionInId<MockEthereumChainCrypto> for [u8; 4] {
 	fn deposit_id(&self) -> Option<[u8; 4]> {
 		Some(*self)
 	}
+
+	fn unique_deposit_id(&self) -> Option<UniqueDepositId> {
+		Some(sp_core::H256::from_low_u64_be(u32::from_be_bytes(*self) as u64))
+	}
 }
@@ -398,6 +398,7 @@ impl<Environment: SolanaEnvironment> SolanaApi<Environment> {
 			asset: transfer_param.asset,
 			amount: transfer_param.amount,
 			to: ccm_accounts.fallback_address.into(),
+			memo: None,
 		};
 
 		let compute_limit =
@@ -612,6 +613,9 @@ impl<Env: 'static> ApiCall<SolanaCrypto> for SolanaApi<Env> {
 
 impl<Env: 'static> ConsolidateCall<Solana> for SolanaApi<Env> {
 	fn consolidate_utxos() -> Result<Self, ConsolidationError> {
+		// Deposits are fetched into the vault's single token account for each mint, and vault
+		// swap event accounts are closed through the elections, so there is nothing to
+		// consolidate here.
 		Err(ConsolidationError::NotRequired)
 	}
 }
@@ -645,9 +649,17 @@ impl<Env: 'static + SolanaEnvironment> ExecutexSwapAndCall<Solana> for SolanaApi
 			message,
 			ccm_additional_data,
 		)
-		.map_err(|e| {
-			log::error!("Failed to construct Solana CCM transfer transaction! \nError: {:?}", e);
-			ExecutexSwapAndCallError::FailedToBuildCcmForSolana(e)
+		.map_err(|e| match e {
+			// Nonce accounts are released once in-flight transactions are witnessed.
+			SolanaTransactionBuildingError::NoAvailableNonceAccount =>
+				ExecutexSwapAndCallError::NotReady,
+			e => {
+				log::error!(
+					"Failed to construct Solana CCM transfer transaction! \nError: {:?}",
+					e
+				);
+				ExecutexSwapAndCallError::FailedToBuildCcmForSolana(e)
+			},
 		})
 	}
 }
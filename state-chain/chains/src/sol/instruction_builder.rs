@@ -192,6 +192,7 @@ mod test {
 			BROKER_COMMISSION,
 			affiliate_and_fees(),
 			with_ccm.then_some(&ccm_parameter().channel_metadata),
+			None,
 		)
 	}
 
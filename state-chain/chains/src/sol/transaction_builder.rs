@@ -763,6 +763,7 @@ pub mod test {
 			asset: SOL,
 			amount: TRANSFER_AMOUNT,
 			to: TRANSFER_TO_ACCOUNT,
+			memo: None,
 		};
 		let env = api_env();
 
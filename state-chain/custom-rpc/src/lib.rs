@@ -6,17 +6,20 @@ use cf_amm::{
 	range_orders::Liquidity,
 };
 use cf_chains::{
-	address::{AddressString, ForeignChainAddressHumanreadable, ToHumanreadableAddress},
+	address::{
+		AddressString, EncodedAddress, ForeignChainAddressHumanreadable, ToHumanreadableAddress,
+	},
 	dot::PolkadotAccountId,
 	eth::Address as EthereumAddress,
 	sol::SolAddress,
-	CcmChannelMetadata, Chain, VaultSwapExtraParametersRpc, MAX_CCM_MSG_LENGTH,
+	CcmChannelMetadata, Chain, ForeignChainAddress, VaultSwapExtraParametersRpc,
+	MAX_CCM_MSG_LENGTH,
 };
 use cf_primitives::{
 	chains::assets::any::{self, AssetMap},
 	AccountId, AccountRole, AffiliateShortId, Affiliates, Asset, AssetAmount, BasisPoints,
-	BlockNumber, BroadcastId, DcaParameters, EpochIndex, ForeignChain, NetworkEnvironment, SemVer,
-	SwapId, SwapRequestId,
+	BlockNumber, BroadcastId, ChannelId, DcaParameters, EpochIndex, ForeignChain,
+	NetworkEnvironment, SemVer, SwapId, SwapRequestId, TrackingId,
 };
 use cf_utilities::rpc::NumberOrHex;
 use core::ops::Range;
@@ -45,7 +48,7 @@ use sc_rpc_spec_v2::chain_head::{
 	api::ChainHeadApiServer, ChainHead, ChainHeadConfig, FollowEvent,
 };
 use serde::{Deserialize, Serialize};
-use sp_api::{ApiError, CallApiAt};
+use sp_api::{ApiError, ApiExt, CallApiAt};
 use sp_core::U256;
 use sp_runtime::{
 	traits::{Block as BlockT, Header as HeaderT, UniqueSaturatedInto},
@@ -61,10 +64,13 @@ use state_chain_runtime::{
 		PendingBroadcasts, PendingTssCeremonies, RedemptionsInfo, SolanaNonces,
 	},
 	runtime_apis::{
-		AuctionState, BoostPoolDepth, BoostPoolDetails, BrokerInfo, CcmData, ChainAccounts,
-		CustomRuntimeApi, DispatchErrorWithMessage, ElectoralRuntimeApi, FailingWitnessValidators,
-		FeeTypes, LiquidityProviderBoostPoolInfo, LiquidityProviderInfo, RuntimeApiPenalty,
-		SimulatedSwapInformation, TransactionScreeningEvents, ValidatorInfo, VaultSwapDetails,
+		AccountingReconciliation, AuctionState, BoostPoolDepth, BoostPoolDetails, BrokerDetails,
+		BrokerInfo, CcmData, ChainAccounts, ChainWindDownProgress, CustomRuntimeApi,
+		DepositChannelQrPayload, DispatchErrorWithMessage, ElectoralRuntimeApi,
+		FailingWitnessValidators, FeeTypes, InFlightValue, LiquidityProviderBoostPoolInfo,
+		LiquidityProviderInfo, OpenChannelUsage, RuntimeApiPenalty, SimulatedSwapInformation,
+		TransactionScreeningEvents, ValidatorInfo, VaultSwapDetails, VaultSwapParametersPayload,
+		MAX_SCHEDULED_SWAPS_PAGE_SIZE,
 	},
 	safe_mode::RuntimeSafeMode,
 	Hash, NetworkFee, SolanaInstance,
@@ -900,6 +906,9 @@ pub trait CustomApi {
 		&self,
 		base_asset: Asset,
 		quote_asset: Asset,
+		page: Option<u32>,
+		page_size: Option<u32>,
+		estimate_only: Option<bool>,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<Vec<ScheduledSwap>>;
 
@@ -978,6 +987,18 @@ pub trait CustomApi {
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<Vec<u8>>;
 
+	#[method(name = "solana_consensus_archive")]
+	fn cf_solana_consensus_archive(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<u8>>;
+
+	#[method(name = "solana_vote_storage_usage")]
+	fn cf_solana_vote_storage_usage(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<u8>>;
+
 	#[method(name = "validate_dca_params")]
 	fn cf_validate_dca_params(
 		&self,
@@ -1009,6 +1030,23 @@ pub trait CustomApi {
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<VaultSwapDetails<AddressString>>;
 
+	#[method(name = "encode_vault_swap_parameters")]
+	fn cf_encode_vault_swap_parameters(
+		&self,
+		broker: state_chain_runtime::AccountId,
+		source_chain: ForeignChain,
+		destination_asset: Asset,
+		destination_address: AddressString,
+		broker_commission: BasisPoints,
+		extra_parameters: VaultSwapExtraParametersRpc,
+		channel_metadata: Option<CcmChannelMetadata>,
+		boost_fee: Option<BasisPoints>,
+		affiliate_fees: Option<Affiliates<state_chain_runtime::AccountId>>,
+		dca_parameters: Option<DcaParameters>,
+		tracking_id: Option<TrackingId>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<VaultSwapParametersPayload>;
+
 	#[method(name = "get_open_deposit_channels")]
 	fn cf_get_open_deposit_channels(
 		&self,
@@ -1028,6 +1066,86 @@ pub trait CustomApi {
 		broker: state_chain_runtime::AccountId,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<Vec<(AffiliateShortId, state_chain_runtime::AccountId)>>;
+
+	/// Returns the channels, affiliates, balances and withdrawal addresses of the broker in a
+	/// single call.
+	#[method(name = "broker_details")]
+	fn cf_broker_details(
+		&self,
+		broker: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<BrokerDetails>;
+
+	/// Returns the number of deposit channels the account has open on each chain, and the maximum
+	/// number it may have open.
+	#[method(name = "open_channel_usage")]
+	fn cf_open_channel_usage(
+		&self,
+		account_id: state_chain_runtime::AccountId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<OpenChannelUsage>>;
+
+	/// Returns the payment URI for deposits into the open channel at the given address, for
+	/// wallets to render as a QR code.
+	#[method(name = "deposit_channel_qr_payload")]
+	fn cf_deposit_channel_qr_payload(
+		&self,
+		deposit_address: EncodedAddress,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<DepositChannelQrPayload>;
+
+	/// Returns the value held by the protocol per asset between deposit and egress: in swap
+	/// requests, and scheduled for egress but not yet broadcast.
+	#[method(name = "in_flight_value")]
+	fn cf_in_flight_value(&self, at: Option<state_chain_runtime::Hash>)
+		-> RpcResult<InFlightValue>;
+
+	/// Returns cumulative deposit, egress, fee and rejection totals per asset, to be diffed
+	/// against the balances observed in the vaults.
+	#[method(name = "accounting_reconciliation")]
+	fn cf_accounting_reconciliation(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<AccountingReconciliation>;
+
+	/// Returns the progress of the given chain's wind-down, or `null` if it hasn't been started.
+	#[method(name = "chain_wind_down_progress")]
+	fn cf_chain_wind_down_progress(
+		&self,
+		chain: ForeignChain,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<ChainWindDownProgress>>;
+
+	#[method(name = "private_channel_owner")]
+	fn cf_private_channel_owner(
+		&self,
+		channel_id: ChannelId,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Option<state_chain_runtime::AccountId>>;
+
+	#[method(name = "decode_and_validate_address")]
+	fn cf_decode_and_validate_address(
+		&self,
+		encoded_address: EncodedAddress,
+		asset: Asset,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<ForeignChainAddress>;
+
+	#[method(name = "encode_address")]
+	fn cf_encode_address(
+		&self,
+		address: ForeignChainAddress,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<EncodedAddress>;
+
+	/// Converts the addresses into the format shown to users (e.g. bech32 for Bitcoin, SS58 for
+	/// Polkadot), using the network environment of the state chain.
+	#[method(name = "humanreadable_addresses")]
+	fn cf_humanreadable_addresses(
+		&self,
+		addresses: Vec<ForeignChainAddress>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<ForeignChainAddressHumanreadable>>;
 }
 
 /// An RPC extension for the state chain node.
@@ -1293,6 +1411,13 @@ where
 		cf_pool_price(from_asset: Asset, to_asset: Asset) -> Option<PoolPriceV1>,
 		cf_get_open_deposit_channels(account_id: Option<state_chain_runtime::AccountId>) -> ChainAccounts,
 		cf_get_affiliates(broker: state_chain_runtime::AccountId) -> Vec<(AffiliateShortId, state_chain_runtime::AccountId)>,
+		cf_broker_details(broker: state_chain_runtime::AccountId) -> BrokerDetails,
+		cf_open_channel_usage(account_id: state_chain_runtime::AccountId) -> Vec<OpenChannelUsage>,
+		cf_private_channel_owner(channel_id: ChannelId) -> Option<state_chain_runtime::AccountId>,
+		cf_encode_address(address: ForeignChainAddress) -> EncodedAddress,
+		cf_in_flight_value() -> InFlightValue,
+		cf_accounting_reconciliation() -> AccountingReconciliation,
+		cf_chain_wind_down_progress(chain: ForeignChain) -> Option<ChainWindDownProgress>,
 	}
 
 	pass_through_and_flatten! {
@@ -1309,6 +1434,8 @@ where
 		) -> PoolPairsMap<AmmAmount>,
 		cf_validate_dca_params(number_of_chunks: u32, chunk_interval: u32) -> (),
 		cf_validate_refund_params(retry_duration: BlockNumber) -> (),
+		cf_decode_and_validate_address(encoded_address: EncodedAddress, asset: Asset) -> ForeignChainAddress,
+		cf_deposit_channel_qr_payload(deposit_address: EncodedAddress) -> DepositChannelQrPayload,
 	}
 
 	fn cf_current_compatibility_version(&self) -> RpcResult<SemVer> {
@@ -1708,11 +1835,15 @@ where
 			pending_sink,
 			move |client, hash| {
 				Ok(SwapResponse {
-					swaps: (*client.runtime_api())
-						.cf_scheduled_swaps(hash, base_asset, quote_asset)?
-						.into_iter()
-						.map(|(swap, execute_at)| ScheduledSwap::new(swap, execute_at))
-						.collect(),
+					swaps: scheduled_swaps(
+						&*client.runtime_api(),
+						hash,
+						base_asset,
+						quote_asset,
+						None,
+						None,
+						None,
+					)?,
 				})
 			},
 		)
@@ -1723,6 +1854,9 @@ where
 		&self,
 		base_asset: Asset,
 		quote_asset: Asset,
+		page: Option<u32>,
+		page_size: Option<u32>,
+		estimate_only: Option<bool>,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<Vec<ScheduledSwap>> {
 		// Check that the requested pool exists:
@@ -1732,13 +1866,9 @@ where
 			quote_asset,
 		)?;
 
-		self.with_runtime_api(at, |api, hash| api.cf_scheduled_swaps(hash, base_asset, quote_asset))
-			.map(|swaps| {
-				swaps
-					.into_iter()
-					.map(|(swap, execute_at)| ScheduledSwap::new(swap, execute_at))
-					.collect()
-			})
+		self.with_runtime_api(at, |api, hash| {
+			scheduled_swaps(api, hash, base_asset, quote_asset, page, page_size, estimate_only)
+		})
 	}
 
 	async fn cf_subscribe_prewitness_swaps(
@@ -1870,6 +2000,42 @@ where
 		self.with_runtime_api(at, |api, hash| api.cf_filter_votes(hash, validator, proposed_votes))
 	}
 
+	fn cf_solana_consensus_archive(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<u8>> {
+		self.with_runtime_api(at, |api, hash| {
+			if api
+				.api_version::<dyn ElectoralRuntimeApi<B, SolanaInstance>>(hash)?
+				.unwrap_or_default() <
+				2
+			{
+				return Err(CfApiError::ErrorObject(call_error(
+					"The consensus archive is not supported by the runtime at this block.",
+				)));
+			}
+			Ok::<_, CfApiError>(api.cf_consensus_archive(hash)?)
+		})
+	}
+
+	fn cf_solana_vote_storage_usage(
+		&self,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<u8>> {
+		self.with_runtime_api(at, |api, hash| {
+			if api
+				.api_version::<dyn ElectoralRuntimeApi<B, SolanaInstance>>(hash)?
+				.unwrap_or_default() <
+				3
+			{
+				return Err(CfApiError::ErrorObject(call_error(
+					"Vote storage usage is not supported by the runtime at this block.",
+				)));
+			}
+			Ok::<_, CfApiError>(api.cf_vote_storage_usage(hash)?)
+		})
+	}
+
 	fn cf_get_vault_swap_details(
 		&self,
 		broker: state_chain_runtime::AccountId,
@@ -1906,12 +2072,67 @@ where
 		})
 	}
 
+	fn cf_encode_vault_swap_parameters(
+		&self,
+		broker: state_chain_runtime::AccountId,
+		source_chain: ForeignChain,
+		destination_asset: Asset,
+		destination_address: AddressString,
+		broker_commission: BasisPoints,
+		extra_parameters: VaultSwapExtraParametersRpc,
+		channel_metadata: Option<CcmChannelMetadata>,
+		boost_fee: Option<BasisPoints>,
+		affiliate_fees: Option<Affiliates<state_chain_runtime::AccountId>>,
+		dca_parameters: Option<DcaParameters>,
+		tracking_id: Option<TrackingId>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<VaultSwapParametersPayload> {
+		self.with_runtime_api(at, |api, hash| {
+			if api.api_version::<dyn CustomRuntimeApi<B>>(hash)?.unwrap_or_default() < 4 {
+				return Err(CfApiError::ErrorObject(call_error(
+					"Encoding vault swap parameters is not supported by the runtime at this block.",
+				)));
+			}
+			Ok::<_, CfApiError>(
+				api.cf_encode_vault_swap_parameters(
+					hash,
+					broker,
+					source_chain,
+					destination_asset,
+					destination_address.try_parse_to_encoded_address(destination_asset.into())?,
+					broker_commission,
+					extra_parameters
+						.try_into_encoded_params(source_chain)
+						.map_err(DispatchErrorWithMessage::from)?,
+					channel_metadata,
+					boost_fee.unwrap_or_default(),
+					affiliate_fees.unwrap_or_default(),
+					dca_parameters,
+					tracking_id,
+				)??,
+			)
+		})
+	}
+
 	fn cf_get_transaction_screening_events(
 		&self,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<TransactionScreeningEvents> {
 		self.with_runtime_api(at, |api, hash| api.cf_transaction_screening_events(hash))
 	}
+
+	fn cf_humanreadable_addresses(
+		&self,
+		addresses: Vec<ForeignChainAddress>,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<Vec<ForeignChainAddressHumanreadable>> {
+		self.with_runtime_api(at, |api, hash| {
+			let network = api.cf_network_environment(hash)?;
+			Ok::<_, CfApiError>(
+				addresses.iter().map(|address| address.to_humanreadable(network)).collect(),
+			)
+		})
+	}
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -2133,6 +2354,50 @@ where
 	}
 }
 
+/// Returns a page of the scheduled swaps. The page size defaults to (and is capped at)
+/// [MAX_SCHEDULED_SWAPS_PAGE_SIZE]. Runtimes that don't support paging return all scheduled
+/// swaps, so the page is taken here instead.
+fn scheduled_swaps<B, Api>(
+	api: &Api,
+	hash: Hash,
+	base_asset: Asset,
+	quote_asset: Asset,
+	page: Option<u32>,
+	page_size: Option<u32>,
+	estimate_only: Option<bool>,
+) -> Result<Vec<ScheduledSwap>, CfApiError>
+where
+	B: BlockT<Hash = Hash>,
+	Api: CustomRuntimeApi<B> + ApiExt<B>,
+{
+	let page = page.unwrap_or_default();
+	let page_size = page_size
+		.unwrap_or(MAX_SCHEDULED_SWAPS_PAGE_SIZE)
+		.min(MAX_SCHEDULED_SWAPS_PAGE_SIZE);
+
+	let swaps = if api.api_version::<dyn CustomRuntimeApi<B>>(hash)?.unwrap_or_default() < 4 {
+		api.cf_scheduled_swaps_before_version_4(hash, base_asset, quote_asset)?
+			.into_iter()
+			.skip(page.saturating_mul(page_size) as usize)
+			.take(page_size as usize)
+			.collect()
+	} else {
+		api.cf_scheduled_swaps(
+			hash,
+			base_asset,
+			quote_asset,
+			page,
+			page_size,
+			estimate_only.unwrap_or_default(),
+		)?
+	};
+
+	Ok(swaps
+		.into_iter()
+		.map(|(swap, execute_at)| ScheduledSwap::new(swap, execute_at))
+		.collect())
+}
+
 #[cfg(test)]
 mod test {
 	use std::collections::BTreeSet;
@@ -1,6 +1,7 @@
 use super::pass_through;
 use crate::{BlockT, CustomRpc, RpcAccountInfoV2, RpcFeeImbalance, RpcMonitoringData, RpcResult};
 use cf_chains::{dot::PolkadotAccountId, sol::SolAddress};
+use cf_primitives::ForeignChain;
 use cf_utilities::rpc::NumberOrHex;
 use jsonrpsee::proc_macros::rpc;
 use sc_client_api::{BlockchainEvents, HeaderBackend};
@@ -11,7 +12,8 @@ use state_chain_runtime::{
 	self,
 	chainflip::Offence,
 	monitoring_apis::{
-		ActivateKeysBroadcastIds, AuthoritiesInfo, BtcUtxos, EpochState, ExternalChainsBlockHeight,
+		ActivateKeysBroadcastIds, AuthoritiesInfo, BroadcastBudgetUsage, BtcUtxos,
+		EgressThroughput, EpochState, ExternalChainsBlockHeight, FailedCalls,
 		LastRuntimeUpgradeInfo, MonitoringRuntimeApi, OpenDepositChannels, PendingBroadcasts,
 		PendingTssCeremonies, RedemptionsInfo, SolanaNonces,
 	},
@@ -125,6 +127,24 @@ pub trait MonitoringApi {
 		accounts: BoundedVec<state_chain_runtime::AccountId, ConstU32<10>>,
 		at: Option<state_chain_runtime::Hash>,
 	) -> RpcResult<Vec<RpcAccountInfoV2>>;
+	#[method(name = "egress_throughput")]
+	fn cf_egress_throughput(
+		&self,
+		chain: ForeignChain,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<EgressThroughput>;
+	#[method(name = "failed_calls")]
+	fn cf_failed_calls(
+		&self,
+		chain: ForeignChain,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<FailedCalls>;
+	#[method(name = "broadcast_budget_usage")]
+	fn cf_broadcast_budget_usage(
+		&self,
+		chain: ForeignChain,
+		at: Option<state_chain_runtime::Hash>,
+	) -> RpcResult<BroadcastBudgetUsage>;
 }
 
 impl<C, B, BE> MonitoringApiServer for CustomRpc<C, B, BE>
@@ -155,7 +175,10 @@ where
 		cf_rotation_broadcast_ids() -> ActivateKeysBroadcastIds,
 		cf_sol_nonces() -> SolanaNonces,
 		cf_sol_aggkey() -> SolAddress,
-		cf_sol_onchain_key() -> SolAddress
+		cf_sol_onchain_key() -> SolAddress,
+		cf_egress_throughput(chain: ForeignChain) -> EgressThroughput,
+		cf_failed_calls(chain: ForeignChain) -> FailedCalls,
+		cf_broadcast_budget_usage(chain: ForeignChain) -> BroadcastBudgetUsage,
 	}
 
 	fn cf_fee_imbalance(
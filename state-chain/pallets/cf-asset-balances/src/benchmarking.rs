@@ -1,7 +1,8 @@
-use crate::{Config, Pallet};
+use crate::{Config, Pallet, WithdrawalAddressHistory, WithdrawalAddressProtection};
+use cf_chains::address::EncodedAddress;
 use frame_benchmarking::v2::*;
+use frame_system::RawOrigin;
 
-// Keep this to avoid CI warnings about no benchmarks in the crate.
 #[benchmarks]
 mod benchmarks {
 	use super::*;
@@ -13,4 +14,34 @@ mod benchmarks {
 			// Do nothing.
 		}
 	}
+
+	#[benchmark]
+	fn set_withdrawal_address_protection() {
+		let caller: T::AccountId = whitelisted_caller();
+
+		#[extrinsic_call]
+		set_withdrawal_address_protection(RawOrigin::Signed(caller.clone()), true);
+
+		assert!(WithdrawalAddressProtection::<T>::get(&caller));
+	}
+
+	#[benchmark]
+	fn confirm_withdrawal_address() {
+		let caller: T::AccountId = whitelisted_caller();
+		for i in 0..crate::MAX_WITHDRAWAL_ADDRESS_HISTORY {
+			Pallet::<T>::record_withdrawal_address(&caller, &EncodedAddress::Eth([i as u8; 20]));
+		}
+
+		#[extrinsic_call]
+		confirm_withdrawal_address(
+			RawOrigin::Signed(caller.clone()),
+			EncodedAddress::Eth([0xff; 20]),
+		);
+
+		assert!(
+			WithdrawalAddressHistory::<T>::get(&caller).contains(&EncodedAddress::Eth([0xff; 20]))
+		);
+	}
+
+	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test);
 }
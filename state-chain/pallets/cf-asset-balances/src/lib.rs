@@ -1,12 +1,14 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![doc = include_str!("../../cf-doc-head.md")]
 
-use cf_chains::{assets::any::AssetMap, AnyChain, ForeignChain, ForeignChainAddress};
+use cf_chains::{
+	address::EncodedAddress, assets::any::AssetMap, AnyChain, ForeignChain, ForeignChainAddress,
+};
 use cf_primitives::{AccountId, Asset, AssetAmount};
 use cf_runtime_utilities::log_or_panic;
 use cf_traits::{
-	impl_pallet_safe_mode, AssetWithholding, BalanceApi, Chainflip, EgressApi, KeyProvider,
-	LiabilityTracker, ScheduledEgressDetails,
+	impl_pallet_safe_mode, AssetWithholding, BalanceApi, Chainflip, EgressApi, EgressOrigin,
+	KeyProvider, LiabilityTracker, ScheduledEgressDetails,
 };
 use frame_support::{
 	pallet_prelude::*,
@@ -26,10 +28,33 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod weights;
+pub use weights::WeightInfo;
+
 pub const PALLET_VERSION: StorageVersion = StorageVersion::new(1);
 
 pub const REFUND_FEE_MULTIPLE: AssetAmount = 100;
 
+/// The maximum number of withdrawal addresses remembered per account.
+pub const MAX_WITHDRAWAL_ADDRESS_HISTORY: u32 = 20;
+
+/// Returns true if the two addresses are different but share their leading and trailing bytes,
+/// as is typical of addresses used in address poisoning attacks.
+fn is_lookalike_address(address: &EncodedAddress, known: &EncodedAddress) -> bool {
+	let match_length = match address {
+		// Bitcoin addresses are encoded as strings that share a common human-readable prefix.
+		EncodedAddress::Btc(_) => 8,
+		_ => 2,
+	};
+	let (address, known) = (address.inner_bytes(), known.inner_bytes());
+
+	address != known &&
+		address.len() == known.len() &&
+		address.len() > 2 * match_length &&
+		address[..match_length] == known[..match_length] &&
+		address[address.len() - match_length..] == known[known.len() - match_length..]
+}
+
 #[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
 pub enum ExternalOwner {
 	Vault,
@@ -70,6 +95,7 @@ impl_pallet_safe_mode!(PalletSafeMode; reconciliation_enabled);
 pub mod pallet {
 	use cf_chains::{dot::PolkadotCrypto, ForeignChain};
 	use cf_primitives::EgressId;
+	use frame_system::pallet_prelude::*;
 
 	use super::*;
 	#[pallet::config]
@@ -87,6 +113,9 @@ pub mod pallet {
 
 		/// Safe mode configuration.
 		type SafeMode: Get<PalletSafeMode>;
+
+		/// Benchmark weights.
+		type WeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
@@ -97,6 +126,9 @@ pub mod pallet {
 		InsufficientBalance,
 		/// The user has reached the maximum balance.
 		BalanceOverflow,
+		/// The withdrawal address looks like, but is not, an address previously withdrawn to.
+		/// If this is intended, the address has to be confirmed first.
+		WithdrawalAddressResemblesKnownAddress,
 	}
 
 	#[pallet::event]
@@ -130,6 +162,10 @@ pub mod pallet {
 			amount_credited: AssetAmount,
 			new_balance: AssetAmount,
 		},
+		/// Address poisoning protection was enabled or disabled for the account.
+		WithdrawalAddressProtectionSet { account_id: T::AccountId, enabled: bool },
+		/// The address was added to the account's withdrawal history.
+		WithdrawalAddressConfirmed { account_id: T::AccountId, address: EncodedAddress },
 	}
 
 	#[pallet::pallet]
@@ -158,6 +194,68 @@ pub mod pallet {
 		AssetAmount,
 		ValueQuery,
 	>;
+
+	/// Accounts that have opted into address poisoning protection for their withdrawals.
+	#[pallet::storage]
+	pub type WithdrawalAddressProtection<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, bool, ValueQuery>;
+
+	/// The most recent addresses each protected account has withdrawn to or confirmed, oldest
+	/// first.
+	#[pallet::storage]
+	pub type WithdrawalAddressHistory<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<EncodedAddress, ConstU32<MAX_WITHDRAWAL_ADDRESS_HISTORY>>,
+		ValueQuery,
+	>;
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Enables or disables address poisoning protection for the caller's withdrawals. While
+		/// enabled, withdrawals to addresses that resemble a previously used address are rejected
+		/// unless the address has been confirmed.
+		///
+		/// ## Events
+		///
+		/// - [WithdrawalAddressProtectionSet](Event::WithdrawalAddressProtectionSet)
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::WeightInfo::set_withdrawal_address_protection())]
+		pub fn set_withdrawal_address_protection(
+			origin: OriginFor<T>,
+			enabled: bool,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			WithdrawalAddressProtection::<T>::set(&account_id, enabled);
+
+			Self::deposit_event(Event::WithdrawalAddressProtectionSet { account_id, enabled });
+
+			Ok(())
+		}
+
+		/// Adds an address to the caller's withdrawal history, so that it can be withdrawn to
+		/// even if it resembles another known address.
+		///
+		/// ## Events
+		///
+		/// - [WithdrawalAddressConfirmed](Event::WithdrawalAddressConfirmed)
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::WeightInfo::confirm_withdrawal_address())]
+		pub fn confirm_withdrawal_address(
+			origin: OriginFor<T>,
+			address: EncodedAddress,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+
+			Self::record_withdrawal_address(&account_id, &address);
+
+			Self::deposit_event(Event::WithdrawalAddressConfirmed { account_id, address });
+
+			Ok(())
+		}
+	}
 }
 
 impl<T: Config> Pallet<T> {
@@ -167,17 +265,24 @@ impl<T: Config> Pallet<T> {
 		amount: AssetAmount,
 	) -> Result<(), DispatchError> {
 		match with_storage_layer(|| {
-			T::EgressHandler::schedule_egress(chain.gas_asset(), amount, address.clone(), None)
-				.map_err(Into::into)
-				.and_then(
-					|result @ ScheduledEgressDetails { egress_amount, fee_withheld, .. }| {
-						if egress_amount < REFUND_FEE_MULTIPLE * fee_withheld {
-							Err(Error::<T>::RefundAmountTooLow.into())
-						} else {
-							Ok(result)
-						}
-					},
-				)
+			T::EgressHandler::schedule_egress(
+				chain.gas_asset(),
+				amount,
+				address.clone(),
+				None,
+				None,
+				EgressOrigin::FeeManagement,
+			)
+			.map_err(Into::into)
+			.and_then(
+				|result @ ScheduledEgressDetails { egress_amount, fee_withheld, .. }| {
+					if egress_amount < REFUND_FEE_MULTIPLE * fee_withheld {
+						Err(Error::<T>::RefundAmountTooLow.into())
+					} else {
+						Ok(result)
+					}
+				},
+			)
 		}) {
 			Ok(ScheduledEgressDetails { egress_id, .. }) => {
 				Self::deposit_event(Event::RefundScheduled {
@@ -313,6 +418,14 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	fn record_withdrawal_address(account_id: &T::AccountId, address: &EncodedAddress) {
+		WithdrawalAddressHistory::<T>::mutate(account_id, |history| {
+			history.retain(|known| known != address);
+			// Evict the oldest address if the history is full.
+			let _ = history.force_push(address.clone());
+		});
+	}
+
 	pub fn vault_imbalance(asset: Asset) -> VaultImbalance<AssetAmount> {
 		let owed = Liabilities::<T>::get(asset).values().sum::<u128>();
 		let withheld = WithheldAssets::<T>::get(asset);
@@ -442,6 +555,24 @@ where
 	fn get_balance(who: &Self::AccountId, asset: Asset) -> AssetAmount {
 		FreeBalances::<T>::get(who, asset)
 	}
+
+	fn check_withdrawal_address(
+		account_id: &Self::AccountId,
+		destination: &EncodedAddress,
+	) -> DispatchResult {
+		// The history is only kept for accounts that have opted in.
+		if WithdrawalAddressProtection::<T>::get(account_id) {
+			let history = WithdrawalAddressHistory::<T>::get(account_id);
+			ensure!(
+				history.contains(destination) ||
+					!history.iter().any(|known| is_lookalike_address(destination, known)),
+				Error::<T>::WithdrawalAddressResemblesKnownAddress
+			);
+			Self::record_withdrawal_address(account_id, destination);
+		}
+
+		Ok(())
+	}
 }
 
 pub struct DeleteAccount<T: Config>(PhantomData<T>);
@@ -449,5 +580,7 @@ pub struct DeleteAccount<T: Config>(PhantomData<T>);
 impl<T: Config> OnKilledAccount<T::AccountId> for DeleteAccount<T> {
 	fn on_killed_account(who: &T::AccountId) {
 		let _ = FreeBalances::<T>::clear_prefix(who, u32::MAX, None);
+		WithdrawalAddressProtection::<T>::remove(who);
+		WithdrawalAddressHistory::<T>::remove(who);
 	}
 }
@@ -56,6 +56,7 @@ impl crate::Config for Test {
 	type EgressHandler = MockEgressHandler<AnyChain>;
 	type PolkadotKeyProvider = MockKeyProvider<PolkadotCrypto>;
 	type SafeMode = MockRuntimeSafeMode;
+	type WeightInfo = ();
 }
 
 cf_test_utilities::impl_test_helpers! {
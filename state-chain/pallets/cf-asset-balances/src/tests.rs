@@ -276,7 +276,8 @@ fn can_reconciliate_multiple_chains_at_once() {
 }
 
 pub mod balance_api {
-	use crate::DeleteAccount;
+	use crate::{DeleteAccount, WithdrawalAddressHistory};
+	use cf_chains::address::EncodedAddress;
 
 	use super::*;
 
@@ -366,4 +367,46 @@ pub mod balance_api {
 			);
 		});
 	}
+
+	#[test]
+	pub fn withdrawals_to_lookalike_addresses_require_confirmation() {
+		new_test_ext().execute_with(|| {
+			let alice = AccountId::from([1; 32]);
+			const KNOWN: EncodedAddress = EncodedAddress::Eth([0xab; 20]);
+			const LOOKALIKE: EncodedAddress = EncodedAddress::Eth({
+				let mut address = [0xab; 20];
+				address[10] = 0;
+				address
+			});
+			const UNRELATED: EncodedAddress = EncodedAddress::Eth([0xcd; 20]);
+
+			// Without protection, any address can be withdrawn to, and none are recorded.
+			assert_ok!(Pallet::<Test>::check_withdrawal_address(&alice, &KNOWN));
+			assert_ok!(Pallet::<Test>::check_withdrawal_address(&alice, &LOOKALIKE));
+			assert!(WithdrawalAddressHistory::<Test>::get(&alice).is_empty());
+
+			assert_ok!(Pallet::<Test>::set_withdrawal_address_protection(
+				RuntimeOrigin::signed(alice.clone()),
+				true
+			));
+
+			assert_ok!(Pallet::<Test>::check_withdrawal_address(&alice, &KNOWN));
+			assert_ok!(Pallet::<Test>::check_withdrawal_address(&alice, &UNRELATED));
+			assert_noop!(
+				Pallet::<Test>::check_withdrawal_address(&alice, &LOOKALIKE),
+				crate::Error::<Test>::WithdrawalAddressResemblesKnownAddress
+			);
+
+			// Once confirmed, the address can be withdrawn to.
+			assert_ok!(Pallet::<Test>::confirm_withdrawal_address(
+				RuntimeOrigin::signed(alice.clone()),
+				LOOKALIKE
+			));
+			assert_ok!(Pallet::<Test>::check_withdrawal_address(&alice, &LOOKALIKE));
+			assert_eq!(
+				WithdrawalAddressHistory::<Test>::get(&alice).into_inner(),
+				vec![KNOWN, UNRELATED, LOOKALIKE]
+			);
+		});
+	}
 }
@@ -33,6 +33,8 @@ use core::marker::PhantomData;
 /// Weight functions needed for pallet_cf_asset_balances.
 pub trait WeightInfo {
 	fn noop() -> Weight;
+	fn set_withdrawal_address_protection() -> Weight;
+	fn confirm_withdrawal_address() -> Weight;
 }
 
 /// Weights for pallet_cf_asset_balances using the Substrate node and recommended hardware.
@@ -45,6 +47,27 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 		// Minimum execution time: 136_000 picoseconds.
 		Weight::from_parts(174_000, 0)
 	}
+	/// Storage: `AssetBalances::WithdrawalAddressProtection` (r:0 w:1)
+	/// Proof: `AssetBalances::WithdrawalAddressProtection` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_withdrawal_address_protection() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AssetBalances::WithdrawalAddressHistory` (r:1 w:1)
+	/// Proof: `AssetBalances::WithdrawalAddressHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn confirm_withdrawal_address() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1213`
+		//  Estimated: `4678`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 4678)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -56,4 +79,25 @@ impl WeightInfo for () {
 		// Minimum execution time: 136_000 picoseconds.
 		Weight::from_parts(174_000, 0)
 	}
+	/// Storage: `AssetBalances::WithdrawalAddressProtection` (r:0 w:1)
+	/// Proof: `AssetBalances::WithdrawalAddressProtection` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_withdrawal_address_protection() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 0)
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AssetBalances::WithdrawalAddressHistory` (r:1 w:1)
+	/// Proof: `AssetBalances::WithdrawalAddressHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn confirm_withdrawal_address() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1213`
+		//  Estimated: `4678`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 4678)
+			.saturating_add(ParityDbWeight::get().reads(1_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
 }
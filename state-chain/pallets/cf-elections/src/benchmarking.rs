@@ -317,6 +317,49 @@ mod benchmarks {
 		assert!(ElectoralSettings::<T, I>::get(next_election).is_some());
 	}
 
+	#[benchmark]
+	fn set_consensus_archive_size() {
+		// Initialize the elections
+		Status::<T, I>::set(None);
+		assert_ok!(Call::<T, I>::initialize { initial_state: BenchmarkValue::benchmark_value() }
+			.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap()));
+
+		let call = Call::<T, I>::set_consensus_archive_size {
+			size: MAXIMUM_CONSENSUS_ARCHIVE_SIZE,
+			ignore_corrupt_storage: CorruptStorageAdherance::Heed,
+		};
+
+		#[block]
+		{
+			assert_ok!(
+				call.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap())
+			);
+		}
+
+		assert_eq!(ConsensusArchiveSize::<T, I>::get(), MAXIMUM_CONSENSUS_ARCHIVE_SIZE);
+	}
+
+	#[benchmark]
+	fn import_snapshot() {
+		// Export the state of a running instance, then reset the status so it can be re-imported.
+		Status::<T, I>::set(None);
+		assert_ok!(Call::<T, I>::initialize { initial_state: BenchmarkValue::benchmark_value() }
+			.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap()));
+		let snapshot = Pallet::<T, I>::export_snapshot().unwrap();
+		Status::<T, I>::set(None);
+
+		let call = Call::<T, I>::import_snapshot { snapshot };
+
+		#[block]
+		{
+			assert_ok!(
+				call.dispatch_bypass_filter(T::EnsureGovernance::try_successful_origin().unwrap())
+			);
+		}
+
+		assert_eq!(Status::<T, I>::get(), Some(ElectionPalletStatus::Running));
+	}
+
 	#[benchmark]
 	fn set_shared_data_reference_lifetime() {
 		// Initialize the elections
@@ -572,6 +615,8 @@ mod benchmarks {
 			test_provide_shared_data: _provide_shared_data(),
 			test_initialize: _initialize(),
 			test_update_settings: _update_settings(),
+			test_import_snapshot: _import_snapshot(),
+			test_set_consensus_archive_size: _set_consensus_archive_size(),
 			test_set_shared_data_reference_lifetime: _set_shared_data_reference_lifetime(),
 			test_clear_election_votes: _clear_election_votes(),
 			test_invalidate_election_consensus_cache: _invalidate_election_consensus_cache(),
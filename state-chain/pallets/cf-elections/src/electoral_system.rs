@@ -1,8 +1,10 @@
-use cf_primitives::AuthorityCount;
+use cf_primitives::{AuthorityCount, BlockNumber, FlipBalance};
+use codec::{Decode, Encode};
 use frame_support::{
 	pallet_prelude::{MaybeSerializeDeserialize, Member},
 	Parameter,
 };
+use scale_info::TypeInfo;
 use sp_std::vec::Vec;
 
 use crate::{
@@ -15,6 +17,8 @@ pub struct ConsensusVote<ES: ElectoralSystem> {
 	// If the validator hasn't voted, they will get a None.
 	pub vote: Option<(VotePropertiesOf<ES>, <ES::Vote as VoteStorage>::Vote)>,
 	pub validator_id: ES::ValidatorId,
+	// The validator's stake. Only provided if the pallet is configured to include voter stake.
+	pub stake: Option<FlipBalance>,
 }
 
 pub struct ConsensusVotes<ES: ElectoralSystem> {
@@ -34,6 +38,36 @@ impl<ES: ElectoralSystem> ConsensusVotes<ES> {
 			.filter_map(|ConsensusVote { vote, .. }| vote.map(|v| v.1))
 			.collect()
 	}
+
+	// The total stake of all authorities, if stake is provided for every authority. Can be used as
+	// the denominator for stake-weighted thresholds.
+	pub fn total_stake(&self) -> Option<FlipBalance> {
+		self.votes.iter().try_fold(0u128, |total, ConsensusVote { stake, .. }| {
+			Some(total.saturating_add((*stake)?))
+		})
+	}
+
+	// Returns all votes of those who actually voted, along with the voter's stake.
+	pub fn active_votes_with_stake(
+		self,
+	) -> Vec<(<ES::Vote as VoteStorage>::Vote, Option<FlipBalance>)> {
+		self.votes
+			.into_iter()
+			.filter_map(|ConsensusVote { vote, stake, .. }| vote.map(|v| (v.1, stake)))
+			.collect()
+	}
+}
+
+/// What happens to an election once its deadline has passed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum DeadlineResolution {
+	/// The electoral system has resolved the election. The election is kept, but its deadline is
+	/// removed.
+	Resolved,
+	/// The election has failed definitively and is deleted.
+	Delete,
+	/// The deadline is extended by the given number of blocks.
+	Extend(BlockNumber),
 }
 
 /// A trait that describes a method of coming to consensus on some aspect of an external chain, and
@@ -103,6 +137,10 @@ pub trait ElectoralSystem: 'static + Sized {
 	/// sensibly represented in the same form as a single vote.
 	type Consensus: Parameter + Member + Eq;
 
+	/// Events the electoral system can emit through the elections pallet, for example to report
+	/// its progress to indexers. Use `()` if the electoral system doesn't emit events.
+	type Event: Parameter + Member;
+
 	/// Custom parameters for `on_finalize`. Used to communicate information like the latest chain
 	/// tracking block to the electoral system. While it gives more flexibility to use a generic
 	/// type here, instead of an associated type, particularly as it would allow `on_finalize` to
@@ -175,6 +213,15 @@ pub trait ElectoralSystem: 'static + Sized {
 		previous_consensus: Option<&Self::Consensus>,
 		votes: ConsensusVotes<Self>,
 	) -> Result<Option<Self::Consensus>, CorruptStorageError>;
+
+	/// This is called during the pallet's `on_finalize` callback, before `on_finalize`, for each
+	/// election whose deadline has passed. It decides whether the election is resolved, deleted,
+	/// or given more time. By default the election is deleted.
+	fn on_deadline<ElectionAccess: ElectionWriteAccess<ElectoralSystem = Self>>(
+		_election_access: &ElectionAccess,
+	) -> Result<DeadlineResolution, CorruptStorageError> {
+		Ok(DeadlineResolution::Delete)
+	}
 }
 
 #[allow(type_alias_bounds)]
@@ -213,7 +260,7 @@ mod access {
 	//! to allow the pallet to at restrict write access when it should be done, to help ensure
 	//! correct ElectoralSystem implementation.
 
-	use super::{CorruptStorageError, ElectionIdentifierOf, ElectoralSystem};
+	use super::{BlockNumber, CorruptStorageError, ElectionIdentifierOf, ElectoralSystem};
 
 	#[cfg(test)]
 	use codec::{Decode, Encode};
@@ -306,6 +353,9 @@ mod access {
 		) -> Result<(), CorruptStorageError>;
 		fn clear_votes(&self);
 		fn delete(self);
+		/// Sets the block after which `ElectoralSystem::on_deadline` is called for this election.
+		/// `None` removes the deadline.
+		fn set_deadline(&self, deadline: Option<BlockNumber>);
 		/// This will change the `ElectionIdentifierExtra` value of the election, and allows you to
 		/// optionally change the properties. Note the `extra` must be strictly greater than the
 		/// previous value of this election, this function will return `Err` if it is not. This
@@ -367,6 +417,18 @@ mod access {
 			properties: <Self::ElectoralSystem as ElectoralSystem>::ElectionProperties,
 			state: <Self::ElectoralSystem as ElectoralSystem>::ElectionState,
 		) -> Result<Self::ElectionWriteAccess, CorruptStorageError>;
+		/// Creates a new election that must be resolved by the given block, see
+		/// `ElectoralSystem::on_deadline`.
+		fn new_election_with_deadline(
+			extra: <Self::ElectoralSystem as ElectoralSystem>::ElectionIdentifierExtra,
+			properties: <Self::ElectoralSystem as ElectoralSystem>::ElectionProperties,
+			state: <Self::ElectoralSystem as ElectoralSystem>::ElectionState,
+			deadline: BlockNumber,
+		) -> Result<Self::ElectionWriteAccess, CorruptStorageError> {
+			let election = Self::new_election(extra, properties, state)?;
+			election.set_deadline(Some(deadline));
+			Ok(election)
+		}
 		fn election_mut(
 			id: ElectionIdentifierOf<Self::ElectoralSystem>,
 		) -> Self::ElectionWriteAccess;
@@ -382,6 +444,9 @@ mod access {
 			>,
 		);
 
+		/// Emits an event of the electoral system through the elections pallet.
+		fn deposit_event(event: <Self::ElectoralSystem as ElectoralSystem>::Event);
+
 		/// Allows you to mutate the unsynchronised state. This is more efficient than a read
 		/// (`unsynchronised_state`) and then a write (`set_unsynchronised_state`) in the case of
 		/// composite ElectoralSystems, as a write from one of the sub-ElectoralSystems internally
@@ -1,3 +1,4 @@
+use cf_primitives::{BlockNumber, FlipBalance};
 use frame_support::{
 	pallet_prelude::{MaybeSerializeDeserialize, Member},
 	Parameter,
@@ -9,7 +10,7 @@ use crate::{
 	CorruptStorageError, ElectionIdentifier,
 };
 
-use crate::electoral_system::ConsensusStatus;
+use crate::electoral_system::{ConsensusStatus, DeadlineResolution};
 
 #[allow(type_alias_bounds)]
 pub type CompositeElectionIdentifierOf<E: ElectoralSystemRunner> =
@@ -34,6 +35,8 @@ pub struct CompositeConsensusVote<ES: ElectoralSystemRunner> {
 	// If the validator hasn't voted, they will get a None.
 	pub vote: Option<(CompositeVotePropertiesOf<ES>, <ES::Vote as VoteStorage>::Vote)>,
 	pub validator_id: ES::ValidatorId,
+	// The validator's stake. Only provided if the pallet is configured to include voter stake.
+	pub stake: Option<FlipBalance>,
 }
 
 pub struct CompositeConsensusVotes<ES: ElectoralSystemRunner> {
@@ -119,6 +122,9 @@ pub trait ElectoralSystemRunner: 'static + Sized {
 	/// sensibly represented in the same form as a single vote.
 	type Consensus: Parameter + Member + Eq;
 
+	/// Events emitted by the electoral systems, see `ElectoralSystem::Event`.
+	type Event: Parameter + Member;
+
 	/// This is not used by the pallet, but is used to tell a validator that it should attempt
 	/// to vote in a given Election. Validators are expected to call this indirectly via RPC once
 	/// per state-chain block, for each active election.
@@ -199,6 +205,15 @@ pub trait ElectoralSystemRunner: 'static + Sized {
 		previous_consensus: Option<&Self::Consensus>,
 		votes: CompositeConsensusVotes<Self>,
 	) -> Result<Option<Self::Consensus>, CorruptStorageError>;
+
+	/// This is called during the pallet's `on_finalize` callback, before `on_finalize`, for each
+	/// election whose deadline has passed. It decides whether the election is resolved, deleted,
+	/// or given more time. By default the election is deleted.
+	fn on_deadline(
+		_election_identifier: CompositeElectionIdentifierOf<Self>,
+	) -> Result<DeadlineResolution, CorruptStorageError> {
+		Ok(DeadlineResolution::Delete)
+	}
 }
 
 use crate::UniqueMonotonicIdentifier;
@@ -242,6 +257,13 @@ pub trait RunnerStorageAccessTrait {
 	fn delete_election(
 		composite_election_identifier: CompositeElectionIdentifierOf<Self::ElectoralSystemRunner>,
 	);
+
+	/// Sets the block after which `ElectoralSystemRunner::on_deadline` is called for the election.
+	/// `None` removes the deadline.
+	fn set_election_deadline(
+		unique_monotonic_identifier: UniqueMonotonicIdentifier,
+		deadline: Option<BlockNumber>,
+	);
 	/// This will change the `ElectionIdentifierExtra` value of the election, and allows you to
 	/// optionally change the properties. Note the `extra` must be strictly greater than the
 	/// previous value of this election, this function will return `Err` if it is not. This
@@ -301,6 +323,9 @@ pub trait RunnerStorageAccessTrait {
 			>,
 	);
 
+	/// Emits an event of one of the electoral systems as a pallet event.
+	fn deposit_event(event: <Self::ElectoralSystemRunner as ElectoralSystemRunner>::Event);
+
 	/// Allows you to mutate the unsynchronised state. This is more efficient than a read
 	/// (`unsynchronised_state`) and then a write (`set_unsynchronised_state`) in the case of
 	/// composite ElectoralSystems, as a write from one of the sub-ElectoralSystems internally
@@ -1,6 +1,7 @@
 pub mod blockchain;
 pub mod composite;
 pub mod egress_success;
+pub mod engine_liveness;
 pub mod liveness;
 #[cfg(test)]
 pub mod mock;
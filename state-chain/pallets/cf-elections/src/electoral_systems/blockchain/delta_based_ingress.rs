@@ -94,6 +94,75 @@ where
 
 		Ok(())
 	}
+
+	/// Brings forward the close block of an open channel. Has no effect if the channel is not
+	/// being witnessed, or already closes earlier.
+	pub fn close_channel<
+		ElectoralAccess: ElectoralWriteAccess<ElectoralSystem = Self> + 'static,
+	>(
+		election_identifiers: Vec<
+			ElectionIdentifier<<Self as ElectoralSystem>::ElectionIdentifierExtra>,
+		>,
+		channel: Sink::Account,
+		close_block: Sink::BlockNumber,
+	) -> Result<(), CorruptStorageError> {
+		Self::update_close_block::<ElectoralAccess>(
+			election_identifiers,
+			channel,
+			close_block,
+			|current_close_block| close_block < current_close_block,
+		)
+	}
+
+	/// Pushes back the close block of an open channel. Has no effect if the channel is not
+	/// being witnessed, or already closes later.
+	pub fn extend_channel<
+		ElectoralAccess: ElectoralWriteAccess<ElectoralSystem = Self> + 'static,
+	>(
+		election_identifiers: Vec<
+			ElectionIdentifier<<Self as ElectoralSystem>::ElectionIdentifierExtra>,
+		>,
+		channel: Sink::Account,
+		close_block: Sink::BlockNumber,
+	) -> Result<(), CorruptStorageError> {
+		Self::update_close_block::<ElectoralAccess>(
+			election_identifiers,
+			channel,
+			close_block,
+			|current_close_block| close_block > current_close_block,
+		)
+	}
+
+	fn update_close_block<
+		ElectoralAccess: ElectoralWriteAccess<ElectoralSystem = Self> + 'static,
+	>(
+		election_identifiers: Vec<
+			ElectionIdentifier<<Self as ElectoralSystem>::ElectionIdentifierExtra>,
+		>,
+		channel: Sink::Account,
+		close_block: Sink::BlockNumber,
+		should_update: impl FnOnce(Sink::BlockNumber) -> bool,
+	) -> Result<(), CorruptStorageError> {
+		for election_identifier in election_identifiers {
+			let mut election_access = ElectoralAccess::election_mut(election_identifier);
+			let mut channels = election_access.properties()?;
+			if let Some((details, _)) = channels.get_mut(&channel) {
+				if should_update(details.close_block) {
+					details.close_block = close_block;
+					election_access.refresh(
+						election_identifier
+							.extra()
+							.checked_add(1)
+							.ok_or_else(CorruptStorageError::new)?,
+						channels,
+					)?;
+				}
+				return Ok(())
+			}
+		}
+
+		Ok(())
+	}
 }
 impl<Sink, Settings, ValidatorId> ElectoralSystem for DeltaBasedIngress<Sink, Settings, ValidatorId>
 where
@@ -134,6 +203,7 @@ where
 		>,
 	>;
 	type Consensus = BTreeMap<Sink::Account, ChannelTotalIngressedFor<Sink>>;
+	type Event = ();
 	type OnFinalizeContext = Sink::BlockNumber;
 	type OnFinalizeReturn = ();
 
@@ -43,6 +43,7 @@ macro_rules! generate_electoral_system_tuple_impls {
                     ConsensusVotes,
                     ElectionIdentifierOf,
                     ConsensusStatus,
+                    DeadlineResolution,
                 },
                 electoral_system_runner::{ElectoralSystemRunner, CompositeAuthorityVoteOf, RunnerStorageAccessTrait,
                     CompositeVotePropertiesOf, CompositeConsensusVotes, CompositeElectionIdentifierOf, CompositeConsensusVote},
@@ -56,6 +57,7 @@ macro_rules! generate_electoral_system_tuple_impls {
 
             use frame_support::{Parameter, pallet_prelude::{Member, MaybeSerializeDeserialize}};
 
+            use cf_primitives::BlockNumber;
             use codec::{Encode, Decode};
             use scale_info::TypeInfo;
             use sp_std::vec::Vec;
@@ -91,6 +93,10 @@ macro_rules! generate_electoral_system_tuple_impls {
             pub enum CompositeConsensus<$($electoral_system,)*> {
                 $($electoral_system($electoral_system),)*
             }
+            #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Encode, Decode, TypeInfo)]
+            pub enum CompositeEvent<$($electoral_system,)*> {
+                $($electoral_system($electoral_system),)*
+            }
 
             impl<$($electoral_system: ElectoralSystem<ValidatorId = ValidatorId>,)* ValidatorId: MaybeSerializeDeserialize + Parameter + Member, StorageAccess: RunnerStorageAccessTrait<ElectoralSystemRunner = Self> + 'static, H: Hooks<$($electoral_system),*> + 'static> CompositeRunner<($($electoral_system,)*), ValidatorId, StorageAccess, H> {
                 pub fn with_identifiers<R, F: for<'a> FnOnce(
@@ -130,6 +136,7 @@ macro_rules! generate_electoral_system_tuple_impls {
                 type ElectionState = CompositeElectionState<$(<$electoral_system as ElectoralSystem>::ElectionState,)*>;
                 type Vote = ($(<$electoral_system as ElectoralSystem>::Vote,)*);
                 type Consensus = CompositeConsensus<$(<$electoral_system as ElectoralSystem>::Consensus,)*>;
+                type Event = CompositeEvent<$(<$electoral_system as ElectoralSystem>::Event,)*>;
 
                 fn is_vote_desired(
                     election_identifier: ElectionIdentifier<Self::ElectionIdentifierExtra>,
@@ -253,7 +260,7 @@ macro_rules! generate_electoral_system_tuple_impls {
                                     }
                                 }).transpose()?,
                                 ConsensusVotes {
-                                    votes: consensus_votes.votes.into_iter().map(|CompositeConsensusVote { vote, validator_id }| {
+                                    votes: consensus_votes.votes.into_iter().map(|CompositeConsensusVote { vote, validator_id, stake }| {
                                         if let Some((properties, vote)) = vote {
                                             match (properties, vote) {
                                                 (
@@ -261,14 +268,16 @@ macro_rules! generate_electoral_system_tuple_impls {
                                                     CompositeVote::$electoral_system(vote),
                                                 ) => Ok(ConsensusVote {
                                                     vote: Some((properties, vote)),
-                                                    validator_id
+                                                    validator_id,
+                                                    stake,
                                                 }),
                                                 _ => Err(CorruptStorageError::new()),
                                             }
                                         } else {
                                             Ok(ConsensusVote {
                                                 vote: None,
-                                                validator_id
+                                                validator_id,
+                                                stake,
                                             })
                                         }
 
@@ -278,6 +287,18 @@ macro_rules! generate_electoral_system_tuple_impls {
                         },)*
                     })
                 }
+
+                fn on_deadline(
+                    election_identifier: ElectionIdentifier<Self::ElectionIdentifierExtra>,
+                ) -> Result<DeadlineResolution, CorruptStorageError> {
+                    match *election_identifier.extra() {
+                        $(CompositeElectionIdentifierExtra::$electoral_system(extra) => {
+                            <$electoral_system as ElectoralSystem>::on_deadline(
+                                &DerivedElectionAccess::<tags::$electoral_system, _, StorageAccess>::new(election_identifier.with_extra(extra)),
+                            )
+                        },)*
+                    }
+                }
             }
 
             pub struct DerivedElectionAccess<Tag, ES: ElectoralSystem, StorageAccess> {
@@ -345,6 +366,9 @@ macro_rules! generate_electoral_system_tuple_impls {
             fn delete(self) {
                 StorageAccess::delete_election(self.id.with_extra(CompositeElectionIdentifierExtra::$current(*self.id.extra())));
             }
+            fn set_deadline(&self, deadline: Option<BlockNumber>) {
+                StorageAccess::set_election_deadline(*self.id.unique_monotonic(), deadline);
+            }
             fn refresh(
                 &mut self,
                 new_extra: $current::ElectionIdentifierExtra,
@@ -440,6 +464,10 @@ macro_rules! generate_electoral_system_tuple_impls {
                 );
             }
 
+            fn deposit_event(event: $current::Event) {
+                StorageAccess::deposit_event(CompositeEvent::$current(event));
+            }
+
             fn mutate_unsynchronised_state<
                 T,
                 F: for<'b> FnOnce(
@@ -61,6 +61,7 @@ impl<
 	type ElectionState = ();
 	type Vote = vote_storage::bitmap::Bitmap<Value>;
 	type Consensus = Value;
+	type Event = ();
 	type OnFinalizeContext = ();
 	type OnFinalizeReturn = ();
 
@@ -0,0 +1,130 @@
+use sp_std::collections::btree_set::BTreeSet;
+
+use crate::{
+	electoral_system::{
+		AuthorityVoteOf, ConsensusVote, ConsensusVotes, ElectionIdentifierOf, ElectionReadAccess,
+		ElectionWriteAccess, ElectoralSystem, ElectoralWriteAccess, VotePropertiesOf,
+	},
+	electoral_systems::liveness::OnCheckComplete,
+	vote_storage::{self, VoteStorage},
+	CorruptStorageError,
+};
+use cf_primitives::AuthorityCount;
+use cf_utilities::success_threshold_from_share_count;
+use frame_support::{
+	pallet_prelude::{MaybeSerializeDeserialize, Member},
+	Parameter,
+};
+
+use itertools::Itertools;
+use sp_std::vec::Vec;
+
+/// Periodically opens an election that every validator's engine is expected to vote in. The vote
+/// carries no information, it only proves that the engine is running and able to submit
+/// extrinsics. Once the voting window has elapsed, any validators that did not vote are passed to
+/// the `Hook`, as long as enough validators voted for the result to be meaningful.
+pub struct EngineLiveness<BlockNumber, Hook, ValidatorId> {
+	_phantom: core::marker::PhantomData<(BlockNumber, Hook, ValidatorId)>,
+}
+
+impl<
+		BlockNumber: Member
+			+ Parameter
+			+ Eq
+			+ MaybeSerializeDeserialize
+			+ frame_support::sp_runtime::Saturating
+			+ Ord
+			+ Copy,
+		Hook: OnCheckComplete<ValidatorId> + 'static,
+		ValidatorId: Member + Parameter + Ord + MaybeSerializeDeserialize,
+	> ElectoralSystem for EngineLiveness<BlockNumber, Hook, ValidatorId>
+{
+	type ValidatorId = ValidatorId;
+	type ElectoralUnsynchronisedState = ();
+	type ElectoralUnsynchronisedStateMapKey = ();
+	type ElectoralUnsynchronisedStateMapValue = ();
+
+	type ElectoralUnsynchronisedSettings = ();
+	// How many SC blocks each election stays open for. A new election is started as soon as the
+	// previous one closes, so this is also the period between checks.
+	type ElectoralSettings = BlockNumber;
+	type ElectionIdentifierExtra = ();
+
+	type ElectionProperties = ();
+
+	// The SC block number that we started the election at.
+	type ElectionState = BlockNumber;
+	type Vote = vote_storage::bitmap::Bitmap<()>;
+	// The validators that did not vote.
+	type Consensus = BTreeSet<Self::ValidatorId>;
+	type Event = ();
+	// The current SC block number.
+	type OnFinalizeContext = BlockNumber;
+	type OnFinalizeReturn = ();
+
+	fn generate_vote_properties(
+		_election_identifier: ElectionIdentifierOf<Self>,
+		_previous_vote: Option<(VotePropertiesOf<Self>, AuthorityVoteOf<Self>)>,
+		_vote: &<Self::Vote as VoteStorage>::PartialVote,
+	) -> Result<VotePropertiesOf<Self>, CorruptStorageError> {
+		Ok(())
+	}
+
+	fn on_finalize<ElectoralAccess: ElectoralWriteAccess<ElectoralSystem = Self> + 'static>(
+		election_identifiers: Vec<ElectionIdentifierOf<Self>>,
+		current_sc_block: &Self::OnFinalizeContext,
+	) -> Result<Self::OnFinalizeReturn, CorruptStorageError> {
+		if let Some(election_identifier) = election_identifiers
+			.into_iter()
+			.at_most_one()
+			.map_err(|_| CorruptStorageError::new())?
+		{
+			let election_access = ElectoralAccess::election_mut(election_identifier);
+
+			if election_access.state()?.saturating_add(election_access.settings()?) <=
+				*current_sc_block
+			{
+				if let Some(unresponsive_validators) =
+					election_access.check_consensus()?.has_consensus()
+				{
+					if !unresponsive_validators.is_empty() {
+						Hook::on_check_complete(unresponsive_validators);
+					}
+				}
+				election_access.delete();
+				ElectoralAccess::new_election((), (), *current_sc_block)?;
+			}
+		} else {
+			ElectoralAccess::new_election((), (), *current_sc_block)?;
+		}
+
+		Ok(())
+	}
+
+	fn check_consensus<ElectionAccess: ElectionReadAccess<ElectoralSystem = Self>>(
+		_election_access: &ElectionAccess,
+		_previous_consensus: Option<&Self::Consensus>,
+		consensus_votes: ConsensusVotes<Self>,
+	) -> Result<Option<Self::Consensus>, CorruptStorageError> {
+		let success_threshold =
+			success_threshold_from_share_count(consensus_votes.num_authorities());
+
+		let (voted, did_not_vote): (Vec<_>, Vec<_>) = consensus_votes
+			.votes
+			.into_iter()
+			.partition(|ConsensusVote { vote, .. }| vote.is_some());
+
+		// If too few validators voted, the problem is more likely to be with the network than with
+		// the individual engines, so we don't report anyone.
+		Ok(if voted.len() as AuthorityCount >= success_threshold {
+			Some(
+				did_not_vote
+					.into_iter()
+					.map(|ConsensusVote { validator_id, .. }| validator_id)
+					.collect(),
+			)
+		} else {
+			None
+		})
+	}
+}
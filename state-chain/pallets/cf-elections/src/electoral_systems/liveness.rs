@@ -63,6 +63,7 @@ impl<
 	type ElectionState = BlockNumber;
 	type Vote = vote_storage::bitmap::Bitmap<ChainBlockHash>;
 	type Consensus = BTreeSet<Self::ValidatorId>;
+	type Event = ();
 	// The current SC block number, and the current chain tracking height.
 	type OnFinalizeContext = (BlockNumber, ChainBlockNumber);
 	type OnFinalizeReturn = ();
@@ -145,7 +146,7 @@ impl<
 		let success_threshold = success_threshold_from_share_count(num_authorities);
 
 		let mut grouped_votes = BTreeMap::new();
-		for ConsensusVote { vote, validator_id } in consensus_votes.votes {
+		for ConsensusVote { vote, validator_id, .. } in consensus_votes.votes {
 			grouped_votes
 				.entry(vote.map(|v| v.1))
 				.or_insert_with(Vec::new)
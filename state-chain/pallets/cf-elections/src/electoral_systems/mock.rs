@@ -1,5 +1,5 @@
 use crate::{
-	electoral_system::ConsensusStatus,
+	electoral_system::{ConsensusStatus, DeadlineResolution},
 	electoral_system_runner::{CompositeConsensusVotes, RunnerStorageAccessTrait},
 	mock::Test,
 	vote_storage::{self, VoteStorage},
@@ -22,6 +22,7 @@ thread_local! {
 		BTreeMap<UniqueMonotonicIdentifier, ConsensusStatus<AuthorityCount>>
 	> = RefCell::new(Default::default());
 	static DELETE_ELECTIONS_ON_FINALIZE_CONSENSUS: RefCell<bool> = RefCell::new(false);
+	static DEADLINE_RESOLUTION: RefCell<DeadlineResolution> = RefCell::new(DeadlineResolution::Delete);
 }
 
 /// Mock electoral system for testing.
@@ -30,6 +31,7 @@ thread_local! {
 /// - `vote_desired`, `vote_needed`, and `vote_valid` are all set to `true` by default.
 /// - `assume_consensus` is set to `false` by default.
 /// - `consensus_status` is set to `None` by default.
+/// - `deadline_resolution` is set to `Delete` by default.
 ///
 /// If assume_consensus is set to `true`, then the consensus value will be the number of votes.
 pub struct MockElectoralSystemRunner;
@@ -41,6 +43,7 @@ pub enum BehaviourUpdate {
 	VoteValid(bool),
 	AssumeConsensus(bool),
 	DeleteOnFinalizeConsensus(bool),
+	DeadlineResolution(DeadlineResolution),
 }
 
 impl BehaviourUpdate {
@@ -61,6 +64,9 @@ impl BehaviourUpdate {
 			BehaviourUpdate::DeleteOnFinalizeConsensus(delete) => {
 				DELETE_ELECTIONS_ON_FINALIZE_CONSENSUS.with(|v| *v.borrow_mut() = *delete);
 			},
+			BehaviourUpdate::DeadlineResolution(resolution) => {
+				DEADLINE_RESOLUTION.with(|v| *v.borrow_mut() = *resolution);
+			},
 		}
 	}
 }
@@ -86,6 +92,10 @@ impl MockElectoralSystemRunner {
 		DELETE_ELECTIONS_ON_FINALIZE_CONSENSUS.with(|v| *v.borrow())
 	}
 
+	pub fn deadline_resolution() -> DeadlineResolution {
+		DEADLINE_RESOLUTION.with(|v| *v.borrow())
+	}
+
 	pub fn consensus_status(umi: UniqueMonotonicIdentifier) -> ConsensusStatus<AuthorityCount> {
 		CONSENSUS_STATUS.with_borrow(|v| v.get(&umi).cloned().unwrap_or(ConsensusStatus::None))
 	}
@@ -110,6 +120,7 @@ impl MockElectoralSystemRunner {
 			BehaviourUpdate::VoteValid(true),
 			BehaviourUpdate::AssumeConsensus(false),
 			BehaviourUpdate::DeleteOnFinalizeConsensus(false),
+			BehaviourUpdate::DeadlineResolution(DeadlineResolution::Delete),
 		]);
 		CONSENSUS_STATUS.with(|v| v.borrow_mut().clear());
 	}
@@ -130,6 +141,7 @@ impl ElectoralSystemRunner for MockElectoralSystemRunner {
 	type Vote =
 		vote_storage::individual::Individual<(), vote_storage::individual::shared::Shared<()>>;
 	type Consensus = AuthorityCount;
+	type Event = ();
 
 	fn generate_vote_properties(
 		_election_identifier: CompositeElectionIdentifierOf<Self>,
@@ -166,6 +178,12 @@ impl ElectoralSystemRunner for MockElectoralSystemRunner {
 		})
 	}
 
+	fn on_deadline(
+		_election_identifier: CompositeElectionIdentifierOf<Self>,
+	) -> Result<DeadlineResolution, CorruptStorageError> {
+		Ok(Self::deadline_resolution())
+	}
+
 	fn is_vote_desired(
 		_election_identifier: CompositeElectionIdentifierOf<Self>,
 		_current_vote: Option<(CompositeVotePropertiesOf<Self>, CompositeAuthorityVoteOf<Self>)>,
@@ -186,4 +204,11 @@ impl ElectoralSystemRunner for MockElectoralSystemRunner {
 	) -> bool {
 		Self::vote_needed()
 	}
+
+	fn is_vote_valid(
+		_election_identifier: CompositeElectionIdentifierOf<Self>,
+		_partial_vote: &<Self::Vote as VoteStorage>::PartialVote,
+	) -> Result<bool, CorruptStorageError> {
+		Ok(Self::vote_valid())
+	}
 }
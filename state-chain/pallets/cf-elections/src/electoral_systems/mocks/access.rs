@@ -5,6 +5,7 @@ use crate::{
 	},
 	CorruptStorageError, ElectionIdentifier, UniqueMonotonicIdentifier,
 };
+use cf_primitives::BlockNumber;
 use codec::{Decode, Encode};
 use core::{cell::RefCell, cmp::Ord};
 use frame_support::{CloneNoBound, DebugNoBound, EqNoBound, PartialEqNoBound};
@@ -88,6 +89,7 @@ thread_local! {
 	pub static ELECTORAL_UNSYNCHRONISED_STATE: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
 	pub static ELECTORAL_UNSYNCHRONISED_STATE_MAP: RefCell<BTreeMap<Vec<u8>, Vec<u8>>> = const { RefCell::new(BTreeMap::new()) };
 	pub static CONSENSUS_STATUS: RefCell<BTreeMap<Vec<u8>, Vec<u8>>> = const { RefCell::new(BTreeMap::new()) };
+	pub static EVENTS: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
 	pub static NEXT_ELECTION_ID: RefCell<UniqueMonotonicIdentifier> = const { RefCell::new(UniqueMonotonicIdentifier::from_u64(0)) };
 }
 
@@ -105,6 +107,9 @@ impl<ES: ElectoralSystem> ElectionWriteAccess for MockWriteAccess<ES> {
 	fn delete(self) {
 		MockStorageAccess::delete_election::<ES>(self.identifier());
 	}
+	fn set_deadline(&self, _deadline: Option<BlockNumber>) {
+		// nothing
+	}
 	fn refresh(
 		&mut self,
 		new_extra: <Self::ElectoralSystem as ElectoralSystem>::ElectionIdentifierExtra,
@@ -187,6 +192,10 @@ impl<ES: ElectoralSystem> ElectoralWriteAccess for MockAccess<ES> {
 	) {
 		MockStorageAccess::set_unsynchronised_state_map::<ES>(key, value);
 	}
+
+	fn deposit_event(event: <Self::ElectoralSystem as ElectoralSystem>::Event) {
+		MockStorageAccess::deposit_event::<ES>(event);
+	}
 }
 
 pub struct MockStorageAccess;
@@ -225,6 +234,10 @@ impl MockStorageAccess {
 			let mut consensus_ref = consensus.borrow_mut();
 			consensus_ref.clear();
 		});
+		EVENTS.with(|events| {
+			let mut events_ref = events.borrow_mut();
+			events_ref.clear();
+		});
 		NEXT_ELECTION_ID.with(|next_id| {
 			let mut next_id_ref = next_id.borrow_mut();
 			*next_id_ref = UniqueMonotonicIdentifier::from_u64(0);
@@ -429,6 +442,20 @@ impl MockStorageAccess {
 		});
 	}
 
+	pub fn deposit_event<ES: ElectoralSystem>(event: ES::Event) {
+		EVENTS.with(|events| {
+			let mut events_ref = events.borrow_mut();
+			events_ref.push(event.encode());
+		});
+	}
+
+	pub fn events<ES: ElectoralSystem>() -> Vec<ES::Event> {
+		EVENTS.with(|events| {
+			let events_ref = events.borrow();
+			events_ref.iter().map(|e| ES::Event::decode(&mut &e[..]).unwrap()).collect()
+		})
+	}
+
 	pub fn election_identifiers<ES: ElectoralSystem>() -> Vec<ElectionIdentifierOf<ES>> {
 		ELECTION_PROPERTIES.with(|properties| {
 			let properties_ref = properties.borrow();
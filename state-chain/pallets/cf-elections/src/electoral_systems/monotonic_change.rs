@@ -73,6 +73,7 @@ impl<
 	type ElectionState = ();
 	type Vote = vote_storage::change::MonotonicChange<Value, BlockHeight>;
 	type Consensus = (Value, BlockHeight);
+	type Event = ();
 	type OnFinalizeContext = ();
 	type OnFinalizeReturn = ();
 
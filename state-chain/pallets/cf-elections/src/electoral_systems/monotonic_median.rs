@@ -52,6 +52,7 @@ impl<
 	type Vote =
 		vote_storage::individual::Individual<(), vote_storage::individual::shared::Shared<Value>>;
 	type Consensus = Value;
+	type Event = ();
 	type OnFinalizeContext = ();
 	type OnFinalizeReturn = Value;
 
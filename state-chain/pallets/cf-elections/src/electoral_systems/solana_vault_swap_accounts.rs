@@ -35,7 +35,7 @@ use sp_std::vec::Vec;
 
 pub trait SolanaVaultSwapAccountsHook<Account, SwapDetails, E> {
 	fn maybe_fetch_and_close_accounts(accounts: Vec<Account>) -> Result<(), E>;
-	fn initiate_vault_swap(swap_details: SwapDetails);
+	fn initiate_vault_swap(account: &Account, swap_details: SwapDetails);
 	fn get_number_of_available_sol_nonce_accounts(critical: bool) -> usize;
 }
 
@@ -128,6 +128,7 @@ impl<
 	type ElectionState = ();
 	type Vote = vote_storage::bitmap::Bitmap<SolanaVaultSwapsVote<Account, SwapDetails>>;
 	type Consensus = SolanaVaultSwapsVote<Account, SwapDetails>;
+	type Event = ();
 	type OnFinalizeContext = BlockNumber;
 	type OnFinalizeReturn = ();
 
@@ -178,7 +179,7 @@ impl<
 					|(account, maybe_swap_details)| {
 						let mut sol_or_not = false;
 						if let Some(swap_details) = maybe_swap_details.as_ref() {
-							Hook::initiate_vault_swap(swap_details.clone());
+							Hook::initiate_vault_swap(account, swap_details.clone());
 							sol_or_not = SwapDetails::sol_or_not(swap_details);
 						}
 						(account.clone(), sol_or_not)
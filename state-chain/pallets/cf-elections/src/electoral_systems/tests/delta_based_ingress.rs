@@ -803,3 +803,83 @@ fn pending_ingresses_update_with_consensus() {
 			],
 		);
 }
+
+#[test]
+fn closing_a_channel_brings_forward_its_close_block() {
+	const CLOSE_BLOCK: BlockNumber = 1_000;
+	let deposit_channel = DepositChannel {
+		account: 1u32,
+		asset: Asset::Sol,
+		total_ingressed: 1_000u64,
+		block_number: CLOSE_BLOCK,
+		close_block: 2 * CLOSE_BLOCK,
+	};
+
+	with_default_setup()
+		.build()
+		.then(|| {
+			assert_ok!(DeltaBasedIngress::open_channel::<MockAccess<SimpleDeltaBasedIngress>>(
+				TestContext::<SimpleDeltaBasedIngress>::identifiers(),
+				deposit_channel.account,
+				deposit_channel.asset,
+				deposit_channel.close_block
+			));
+			assert_ok!(DeltaBasedIngress::close_channel::<MockAccess<SimpleDeltaBasedIngress>>(
+				TestContext::<SimpleDeltaBasedIngress>::identifiers(),
+				deposit_channel.account,
+				CLOSE_BLOCK,
+			));
+		})
+		.force_consensus_update(ConsensusStatus::Gained {
+			most_recent: None,
+			new: to_state(vec![deposit_channel]),
+		})
+		.test_on_finalize(
+			&CLOSE_BLOCK,
+			|_| (),
+			vec![
+				Check::ingressed(vec![(1u32, Asset::Sol, 1_000u64)]),
+				Check::channel_closed(vec![1u32]),
+			],
+		);
+}
+
+#[test]
+fn extending_a_channel_pushes_back_its_close_block() {
+	const CLOSE_BLOCK: BlockNumber = 1_000;
+	let deposit_channel = DepositChannel {
+		account: 1u32,
+		asset: Asset::Sol,
+		total_ingressed: 1_000u64,
+		block_number: CLOSE_BLOCK,
+		close_block: 2 * CLOSE_BLOCK,
+	};
+
+	with_default_setup()
+		.build()
+		.then(|| {
+			assert_ok!(DeltaBasedIngress::open_channel::<MockAccess<SimpleDeltaBasedIngress>>(
+				TestContext::<SimpleDeltaBasedIngress>::identifiers(),
+				deposit_channel.account,
+				deposit_channel.asset,
+				CLOSE_BLOCK
+			));
+			assert_ok!(DeltaBasedIngress::extend_channel::<MockAccess<SimpleDeltaBasedIngress>>(
+				TestContext::<SimpleDeltaBasedIngress>::identifiers(),
+				deposit_channel.account,
+				deposit_channel.close_block,
+			));
+		})
+		.force_consensus_update(ConsensusStatus::Gained {
+			most_recent: None,
+			new: to_state(vec![deposit_channel]),
+		})
+		.test_on_finalize(
+			&CLOSE_BLOCK,
+			|_| (),
+			vec![
+				Check::ingressed(vec![(1u32, Asset::Sol, 1_000u64)]),
+				Check::channel_closed(vec![]),
+			],
+		);
+}
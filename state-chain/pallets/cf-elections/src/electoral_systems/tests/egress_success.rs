@@ -60,14 +60,19 @@ fn generate_votes(
 			.map(|_| ConsensusVote {
 				vote: Some(((), correct_value as EgressData)),
 				validator_id: (),
+				stake: None,
 			})
 			.chain((0..incorrect_voters).map(|_| ConsensusVote {
 				vote: Some(((), incorrect_value as EgressData)),
 				validator_id: (),
+				stake: None,
 			}))
 			.chain(
-				(0..AUTHORITY_COUNT - correct_voters - incorrect_voters)
-					.map(|_| ConsensusVote { vote: None, validator_id: () }),
+				(0..AUTHORITY_COUNT - correct_voters - incorrect_voters).map(|_| ConsensusVote {
+					vote: None,
+					validator_id: (),
+					stake: None,
+				}),
 			)
 			.collect(),
 	}
@@ -78,7 +83,11 @@ fn consensus_not_possible_because_of_different_votes() {
 	with_default_state().expect_consensus(
 		ConsensusVotes {
 			votes: (0..AUTHORITY_COUNT)
-				.map(|i| ConsensusVote { vote: Some(((), i as EgressData)), validator_id: () })
+				.map(|i| ConsensusVote {
+					vote: Some(((), i as EgressData)),
+					validator_id: (),
+					stake: None,
+				})
 				.collect(),
 		},
 		None,
@@ -0,0 +1,140 @@
+use sp_std::collections::btree_set::BTreeSet;
+
+use super::mocks::*;
+use crate::{
+	electoral_system::{ConsensusVote, ConsensusVotes},
+	electoral_systems::{engine_liveness::*, liveness::OnCheckComplete},
+	register_checks,
+};
+
+pub type BlockNumber = u32;
+pub type ValidatorId = u16;
+
+thread_local! {
+	pub static REPORTED_VALIDATORS: std::cell::RefCell<Vec<BTreeSet<ValidatorId>>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+struct MockHook;
+
+impl OnCheckComplete<ValidatorId> for MockHook {
+	fn on_check_complete(validator_ids: BTreeSet<ValidatorId>) {
+		REPORTED_VALIDATORS.with(|reported| reported.borrow_mut().push(validator_ids));
+	}
+}
+
+type SimpleEngineLiveness = EngineLiveness<BlockNumber, MockHook, ValidatorId>;
+
+register_checks! {
+	SimpleEngineLiveness {
+		only_one_election(_pre, post) {
+			assert_eq!(post.election_identifiers.len(), 1, "Only one election should exist.");
+		},
+		reported(_pre, _post, expected: Vec<BTreeSet<ValidatorId>>) {
+			assert_eq!(REPORTED_VALIDATORS.with(|reported| reported.borrow().clone()), expected);
+		},
+	}
+}
+
+fn generate_votes(
+	voters: BTreeSet<ValidatorId>,
+	did_not_vote: BTreeSet<ValidatorId>,
+) -> ConsensusVotes<SimpleEngineLiveness> {
+	ConsensusVotes {
+		votes: voters
+			.into_iter()
+			.map(|v| ConsensusVote { vote: Some(((), ())), validator_id: v, stake: None })
+			.chain(did_not_vote.into_iter().map(|v| ConsensusVote {
+				vote: None,
+				validator_id: v,
+				stake: None,
+			}))
+			.collect(),
+	}
+}
+
+fn with_default_state() -> TestContext<SimpleEngineLiveness> {
+	TestSetup::<SimpleEngineLiveness>::default().build_with_initial_election()
+}
+
+#[test]
+fn everyone_voting_means_no_one_is_reported() {
+	with_default_state().expect_consensus(
+		generate_votes((0..50).collect(), BTreeSet::default()),
+		Some(BTreeSet::default()),
+	);
+}
+
+#[test]
+fn non_voters_are_reported_once_threshold_is_reached() {
+	let non_voters: BTreeSet<_> = (50..60).collect();
+	with_default_state()
+		.expect_consensus(generate_votes((0..50).collect(), non_voters.clone()), Some(non_voters));
+}
+
+#[test]
+fn no_one_is_reported_below_threshold() {
+	with_default_state()
+		.expect_consensus(generate_votes((0..10).collect(), (10..50).collect()), None);
+	with_default_state()
+		.expect_consensus(generate_votes(BTreeSet::default(), (0..50).collect()), None);
+}
+
+#[test]
+fn on_finalize() {
+	const INIT_BLOCK: BlockNumber = 100;
+	const CHECK_PERIOD: BlockNumber = 10;
+
+	let voters: BTreeSet<_> = (0..50).collect();
+	let non_voters: BTreeSet<_> = (50..60).collect();
+
+	TestSetup::default()
+		.with_electoral_settings(CHECK_PERIOD)
+		.build()
+		.test_on_finalize(
+			&INIT_BLOCK,
+			|_| {},
+			vec![
+				Check::<SimpleEngineLiveness>::only_one_election(),
+				Check::<SimpleEngineLiveness>::reported(vec![]),
+			],
+		)
+		.expect_consensus(
+			generate_votes(voters.clone(), non_voters.clone()),
+			Some(non_voters.clone()),
+		)
+		.test_on_finalize(
+			// The voting window is still open.
+			&(INIT_BLOCK + CHECK_PERIOD - 1),
+			|_| {},
+			vec![
+				Check::<SimpleEngineLiveness>::only_one_election(),
+				Check::<SimpleEngineLiveness>::reported(vec![]),
+			],
+		)
+		.test_on_finalize(
+			&(INIT_BLOCK + CHECK_PERIOD),
+			|_| {},
+			vec![
+				Check::<SimpleEngineLiveness>::only_one_election(),
+				Check::<SimpleEngineLiveness>::reported(vec![non_voters.clone()]),
+			],
+		)
+		// The new election has no votes, so no one is reported when it closes.
+		.test_on_finalize(
+			&(INIT_BLOCK + CHECK_PERIOD * 2),
+			|_| {},
+			vec![
+				Check::<SimpleEngineLiveness>::only_one_election(),
+				Check::<SimpleEngineLiveness>::reported(vec![non_voters.clone()]),
+			],
+		)
+		.expect_consensus(generate_votes(voters, non_voters.clone()), Some(non_voters.clone()))
+		.test_on_finalize(
+			&(INIT_BLOCK + CHECK_PERIOD * 3),
+			|_| {},
+			vec![
+				Check::<SimpleEngineLiveness>::only_one_election(),
+				Check::<SimpleEngineLiveness>::reported(vec![non_voters.clone(), non_voters]),
+			],
+		);
+}
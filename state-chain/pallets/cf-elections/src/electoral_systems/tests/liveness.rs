@@ -55,13 +55,17 @@ fn generate_votes(
 	ConsensusVotes {
 		votes: correct_voters
 			.into_iter()
-			.map(|v| ConsensusVote { vote: Some(((), CORRECT_VOTE)), validator_id: v })
-			.chain(
-				incorrect_voters
-					.into_iter()
-					.map(|v| ConsensusVote { vote: Some(((), INCORRECT_VOTE)), validator_id: v }),
-			)
-			.chain(did_not_vote.into_iter().map(|v| ConsensusVote { vote: None, validator_id: v }))
+			.map(|v| ConsensusVote { vote: Some(((), CORRECT_VOTE)), validator_id: v, stake: None })
+			.chain(incorrect_voters.into_iter().map(|v| ConsensusVote {
+				vote: Some(((), INCORRECT_VOTE)),
+				validator_id: v,
+				stake: None,
+			}))
+			.chain(did_not_vote.into_iter().map(|v| ConsensusVote {
+				vote: None,
+				validator_id: v,
+				stake: None,
+			}))
 			.collect(),
 	}
 }
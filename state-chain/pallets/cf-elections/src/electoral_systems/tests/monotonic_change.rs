@@ -61,14 +61,22 @@ fn generate_votes(
 ) -> ConsensusVotes<SimpleMonotonicChange> {
 	ConsensusVotes {
 		votes: (0..correct_voters)
-			.map(|_| ConsensusVote { vote: Some(((), correct_value.clone())), validator_id: () })
+			.map(|_| ConsensusVote {
+				vote: Some(((), correct_value.clone())),
+				validator_id: (),
+				stake: None,
+			})
 			.chain((0..incorrect_voters).map(|_| ConsensusVote {
 				vote: Some(((), incorrect_value.clone())),
 				validator_id: (),
+				stake: None,
 			}))
 			.chain(
-				(0..AUTHORITY_COUNT - correct_voters - incorrect_voters)
-					.map(|_| ConsensusVote { vote: None, validator_id: () }),
+				(0..AUTHORITY_COUNT - correct_voters - incorrect_voters).map(|_| ConsensusVote {
+					vote: None,
+					validator_id: (),
+					stake: None,
+				}),
 			)
 			.collect(),
 	}
@@ -90,14 +98,19 @@ fn generate_votes_with_different_slots(
 					MonotonicChangeVote { value: correct_value, block: (index + 1) as u32 },
 				)),
 				validator_id: (),
+				stake: None,
 			})
 			.chain((0..incorrect_voters).map(|_| ConsensusVote {
 				vote: Some(((), incorrect_value.clone())),
 				validator_id: (),
+				stake: None,
 			}))
 			.chain(
-				(0..AUTHORITY_COUNT - correct_voters - incorrect_voters)
-					.map(|_| ConsensusVote { vote: None, validator_id: () }),
+				(0..AUTHORITY_COUNT - correct_voters - incorrect_voters).map(|_| ConsensusVote {
+					vote: None,
+					validator_id: (),
+					stake: None,
+				}),
 			)
 			.collect(),
 	}
@@ -111,6 +124,7 @@ fn consensus_not_possible_because_of_different_votes() {
 				.map(|i| ConsensusVote {
 					vote: Some(((), MonotonicChangeVote { value: i as u64, block: 0u32 })),
 					validator_id: (),
+					stake: None,
 				})
 				.collect(),
 		},
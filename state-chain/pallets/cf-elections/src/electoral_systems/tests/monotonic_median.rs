@@ -186,17 +186,20 @@ fn minority_can_not_influence_consensus() {
 	) -> ConsensusVotes<MonotonicMedianTest> {
 		ConsensusVotes {
 			votes: (0..honest_votes)
-				.map(|_| ConsensusVote { vote: Some(((), HONEST_VALUE)), validator_id: () })
-				.chain(
-					(0..dishonest_votes).map(|_| ConsensusVote {
-						vote: Some(((), DISHONEST_VALUE)),
-						validator_id: (),
-					}),
-				)
+				.map(|_| ConsensusVote {
+					vote: Some(((), HONEST_VALUE)),
+					validator_id: (),
+					stake: None,
+				})
+				.chain((0..dishonest_votes).map(|_| ConsensusVote {
+					vote: Some(((), DISHONEST_VALUE)),
+					validator_id: (),
+					stake: None,
+				}))
 				.chain(
 					// didn't vote at all
 					(0..(authority_count - honest_votes - dishonest_votes))
-						.map(|_| ConsensusVote { vote: None, validator_id: () }),
+						.map(|_| ConsensusVote { vote: None, validator_id: (), stake: None }),
 				)
 				.collect(),
 		}
@@ -43,7 +43,7 @@ impl SolanaVaultSwapAccountsHook<Account, SwapDetails, ()> for MockHook {
 		}
 	}
 
-	fn initiate_vault_swap(_swap_details: SwapDetails) {
+	fn initiate_vault_swap(_account: &Account, _swap_details: SwapDetails) {
 		INITIATE_VAULT_SWAP_CALLED.with(|hook_called| hook_called.set(hook_called.get() + 1));
 	}
 
@@ -421,11 +421,16 @@ fn generate_vote_no_consensus() -> ConsensusVotes<MinimalVaultSwapAccounts> {
 
 	ConsensusVotes {
 		votes: (0..80)
-			.map(|_| ConsensusVote { vote: Some(((), vote_1.clone())), validator_id: () })
-			.chain(
-				(0..80)
-					.map(|_| ConsensusVote { vote: Some(((), vote_2.clone())), validator_id: () }),
-			)
+			.map(|_| ConsensusVote {
+				vote: Some(((), vote_1.clone())),
+				validator_id: (),
+				stake: None,
+			})
+			.chain((0..80).map(|_| ConsensusVote {
+				vote: Some(((), vote_2.clone())),
+				validator_id: (),
+				stake: None,
+			}))
 			.collect::<Vec<_>>(),
 	}
 }
@@ -464,6 +469,7 @@ fn generate_votes_specific_case(
 				std::iter::repeat_with(move || ConsensusVote {
 					vote: Some(((), vote.clone())),
 					validator_id: (),
+					stake: None,
 				})
 				.take(count)
 			})
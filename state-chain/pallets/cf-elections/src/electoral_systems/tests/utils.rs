@@ -17,11 +17,12 @@ where
 {
 	ConsensusVotes {
 		votes: (0..success_votes)
-			.map(|v| ConsensusVote { vote: Some(((), v as u64)), validator_id: () })
-			.chain(
-				(0..(authority_count - success_votes))
-					.map(|_| ConsensusVote { vote: None, validator_id: () }),
-			)
+			.map(|v| ConsensusVote { vote: Some(((), v as u64)), validator_id: (), stake: None })
+			.chain((0..(authority_count - success_votes)).map(|_| ConsensusVote {
+				vote: None,
+				validator_id: (),
+				stake: None,
+			}))
 			.collect(),
 	}
 }
@@ -135,9 +135,9 @@ pub mod pallet {
 	#[cfg(feature = "runtime-benchmarks")]
 	use cf_chains::benchmarking_value::BenchmarkValue;
 	use cf_primitives::{AuthorityCount, EpochIndex};
-	use cf_traits::{AccountRoleRegistry, Chainflip, EpochInfo};
+	use cf_traits::{AccountInfo, AccountRoleRegistry, Chainflip, EpochInfo};
 
-	use crate::electoral_system::ConsensusStatus;
+	use crate::electoral_system::{ConsensusStatus, DeadlineResolution};
 	pub use access_impls::RunnerStorageAccess;
 
 	use crate::electoral_system_runner::{
@@ -150,7 +150,8 @@ pub mod pallet {
 	};
 
 	use frame_support::{
-		sp_runtime::traits::BlockNumberProvider, storage::bounded_btree_map::BoundedBTreeMap,
+		sp_runtime::traits::{BlockNumberProvider, Saturating, UniqueSaturatedInto},
+		storage::bounded_btree_map::BoundedBTreeMap,
 		Deserialize, Serialize, StorageDoubleMap as _,
 	};
 	use itertools::Itertools;
@@ -161,6 +162,7 @@ pub mod pallet {
 	use vote_storage::{AuthorityVote, VoteComponents, VoteStorage};
 
 	pub const MAXIMUM_VOTES_PER_EXTRINSIC: u32 = 16;
+	pub const MAXIMUM_CONSENSUS_ARCHIVE_SIZE: u32 = 256;
 	const BLOCKS_BETWEEN_CLEANUP: u64 = 128;
 
 	#[derive(Clone, Debug, Encode, Decode, TypeInfo)]
@@ -196,6 +198,23 @@ pub mod pallet {
 		BlockNumberFor<T>,
 	>;
 
+	/// The final consensus of an election, recorded in the `ConsensusArchive` when the election is
+	/// deleted.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct ArchivedConsensus<ElectionIdentifier, Consensus, BlockNumber> {
+		pub election_identifier: ElectionIdentifier,
+		pub consensus: Consensus,
+		/// The block at which the election was resolved and deleted.
+		pub resolved_at: BlockNumber,
+	}
+
+	#[allow(type_alias_bounds)]
+	pub type ArchivedConsensusFor<T: Config<I>, I: 'static> = ArchivedConsensus<
+		CompositeElectionIdentifierOf<T::ElectoralSystemRunner>,
+		<T::ElectoralSystemRunner as ElectoralSystemRunner>::Consensus,
+		BlockNumberFor<T>,
+	>;
+
 	/// A unique identifier for an election.
 	#[derive(
 		PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug, Encode, Decode, TypeInfo, Default,
@@ -325,6 +344,54 @@ pub mod pallet {
 		<T::ElectoralSystemRunner as ElectoralSystemRunner>::ElectoralSettings,
 	>;
 
+	/// The electoral state of a single election, as captured in an [ElectoralSnapshot].
+	#[derive(CloneNoBound, DebugNoBound, PartialEqNoBound, EqNoBound, Encode, Decode, TypeInfo)]
+	#[scale_info(skip_type_params(ES))]
+	pub struct ElectionSnapshot<ES: ElectoralSystemRunner> {
+		pub identifier: CompositeElectionIdentifierOf<ES>,
+		pub properties: ES::ElectionProperties,
+		pub state: ES::ElectionState,
+	}
+
+	/// A snapshot of the electoral system's state, without any votes or consensus history. It can
+	/// be exported from one chain and imported into an uninitialized instance of the pallet on
+	/// another, for example to start a testnet that mirrors mainnet's elections.
+	#[derive(CloneNoBound, DebugNoBound, PartialEqNoBound, EqNoBound, Encode, Decode, TypeInfo)]
+	#[scale_info(skip_type_params(ES))]
+	pub struct ElectoralSnapshotV1<ES: ElectoralSystemRunner> {
+		pub unsynchronised_state: ES::ElectoralUnsynchronisedState,
+		pub unsynchronised_state_map:
+			Vec<(ES::ElectoralUnsynchronisedStateMapKey, ES::ElectoralUnsynchronisedStateMapValue)>,
+		pub unsynchronised_settings: ES::ElectoralUnsynchronisedSettings,
+		pub settings: Vec<(UniqueMonotonicIdentifier, ES::ElectoralSettings)>,
+		pub elections: Vec<ElectionSnapshot<ES>>,
+		pub next_election_identifier: UniqueMonotonicIdentifier,
+	}
+
+	/// Versioned wrapper around the snapshot encoding, so that snapshots exported by older
+	/// runtimes can still be decoded after the snapshot format changes.
+	#[derive(CloneNoBound, DebugNoBound, PartialEqNoBound, EqNoBound, Encode, Decode, TypeInfo)]
+	#[scale_info(skip_type_params(ES))]
+	pub enum ElectoralSnapshot<ES: ElectoralSystemRunner> {
+		V1(ElectoralSnapshotV1<ES>),
+	}
+
+	impl<ES: ElectoralSystemRunner> ElectoralSnapshot<ES> {
+		/// The number of storage entries written when importing the snapshot.
+		pub fn storage_entry_count(&self) -> u64 {
+			match self {
+				ElectoralSnapshot::V1(snapshot) =>
+					(snapshot.unsynchronised_state_map.len() +
+						snapshot.settings.len() +
+						2 * snapshot.elections.len()) as u64,
+			}
+		}
+	}
+
+	#[allow(type_alias_bounds)]
+	pub type ElectoralSnapshotOf<T: Config<I>, I: 'static> =
+		ElectoralSnapshot<<T as Config<I>>::ElectoralSystemRunner>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
 		#[allow(clippy::type_complexity)]
@@ -363,6 +430,10 @@ pub mod pallet {
 			ValidatorId = <Self as Chainflip>::ValidatorId,
 		>;
 
+		/// Sets if each validator's stake should be included in the votes passed to
+		/// `check_consensus`, so electoral systems can use stake-weighted thresholds.
+		const INCLUDE_VOTER_STAKE: bool;
+
 		/// The weights for the pallet
 		type WeightInfo: WeightInfo;
 	}
@@ -379,6 +450,21 @@ pub mod pallet {
 		/// Not all vote data was cleared. *You should continue clearing votes until you receive
 		/// the AllVotesCleared event*.
 		AllVotesNotCleared,
+		/// Governance paused elections. Votes are kept, but no new votes are accepted and engines
+		/// receive no electoral data until elections are resumed.
+		ElectionsPaused,
+		/// Governance resumed elections.
+		ElectionsResumed,
+		/// The electoral state was initialized from an imported snapshot.
+		SnapshotImported { election_count: u32 },
+		/// The deadline of an election has passed, and the electoral system decided how to
+		/// resolve it.
+		ElectionDeadlineReached {
+			election: UniqueMonotonicIdentifier,
+			resolution: DeadlineResolution,
+		},
+		/// An event emitted by one of the electoral systems.
+		ElectoralSystemEvent { event: <T::ElectoralSystemRunner as ElectoralSystemRunner>::Event },
 	}
 
 	#[derive(CloneNoBound, PartialEqNoBound, EqNoBound)]
@@ -395,6 +481,9 @@ pub mod pallet {
 		VotesNotCleared,
 		NotContributing,
 		NoVotesSpecified,
+		InvalidSnapshot,
+		InvalidVote,
+		ArchiveSizeTooLarge,
 	}
 
 	// ---------------------------------------------------------------------------------------- //
@@ -434,6 +523,41 @@ pub mod pallet {
 		pub expires: BlockNumber,
 	}
 
+	/// Estimated number of bytes the votes of an election occupy in storage, by vote component.
+	#[derive(
+		PartialEq, Eq, Clone, Copy, Debug, Encode, Decode, TypeInfo, Default, Serialize, Deserialize,
+	)]
+	pub struct VoteStorageUsage {
+		pub bitmap_components: u32,
+		pub individual_components: u32,
+		/// Shared data referenced by several elections counts towards each of them.
+		pub shared_data: u32,
+	}
+
+	impl VoteStorageUsage {
+		pub fn total(&self) -> u32 {
+			self.bitmap_components
+				.saturating_add(self.individual_components)
+				.saturating_add(self.shared_data)
+		}
+
+		fn saturating_add(self, other: Self) -> Self {
+			Self {
+				bitmap_components: self.bitmap_components.saturating_add(other.bitmap_components),
+				individual_components: self
+					.individual_components
+					.saturating_add(other.individual_components),
+				shared_data: self.shared_data.saturating_add(other.shared_data),
+			}
+		}
+	}
+
+	/// Tracks the estimated storage consumed by the votes of each election, so that electoral
+	/// systems with disproportionately large votes can be identified.
+	#[pallet::storage]
+	pub type ElectionVoteStorageUsage<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, UniqueMonotonicIdentifier, VoteStorageUsage, OptionQuery>;
+
 	/// Stores the *shared* parts of validator votes. Any duplicates will only be stored once,
 	/// thereby decreasing the storage costs of validator votes as generally most validator's votes
 	/// will be duplicates. A validator can choose to only provide the hashes of these pieces of
@@ -540,6 +664,12 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// Stores the block after which `ElectoralSystemRunner::on_deadline` is called for an
+	/// election. Elections without an entry stay open until the electoral system deletes them.
+	#[pallet::storage]
+	pub type ElectionDeadlines<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, UniqueMonotonicIdentifier, BlockNumberFor<T>, OptionQuery>;
+
 	/// Stores the most recent consensus, i.e. the most recent result of
 	/// `ElectoralSystemRunner::check_consensus` that returned `Some(...)`, and whether it is
 	/// `current` / has not been `lost` since.
@@ -581,6 +711,17 @@ pub mod pallet {
 	pub(crate) type ContributingAuthorities<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Identity, T::ValidatorId, (), OptionQuery>;
 
+	/// The number of resolved elections whose final consensus is kept in the `ConsensusArchive`.
+	/// Archiving is disabled if this is zero.
+	#[pallet::storage]
+	pub type ConsensusArchiveSize<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	/// The final consensus of the most recently deleted elections, oldest first. Kept for auditing
+	/// purposes, as the consensus history of an election is removed along with the election.
+	#[pallet::storage]
+	pub type ConsensusArchive<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, Vec<ArchivedConsensusFor<T, I>>, ValueQuery>;
+
 	/// Stores the status of the ElectoralSystem, i.e. if it is initialized, paused, or running. If
 	/// this is None, the pallet is considered uninitialized.
 	#[pallet::storage]
@@ -682,6 +823,7 @@ pub mod pallet {
 					});
 				}
 				ElectionConsensusHistoryUpToDate::<T, I>::remove(unique_monotonic_identifier);
+				ElectionVoteStorageUsage::<T, I>::remove(unique_monotonic_identifier);
 			}
 			fn delete_election(
 				composite_election_identifier: CompositeElectionIdentifierOf<
@@ -692,7 +834,25 @@ pub mod pallet {
 				Self::clear_election_votes(*unique_monotonic_identifier);
 				ElectionProperties::<T, I>::remove(composite_election_identifier);
 				ElectionState::<T, I>::remove(unique_monotonic_identifier);
-				ElectionConsensusHistory::<T, I>::remove(unique_monotonic_identifier);
+				ElectionDeadlines::<T, I>::remove(unique_monotonic_identifier);
+				if let Some(consensus_history) =
+					ElectionConsensusHistory::<T, I>::take(unique_monotonic_identifier)
+				{
+					Pallet::<T, I>::archive_consensus(
+						composite_election_identifier,
+						consensus_history.most_recent,
+					);
+				}
+			}
+
+			fn set_election_deadline(
+				unique_monotonic_identifier: UniqueMonotonicIdentifier,
+				deadline: Option<cf_primitives::BlockNumber>,
+			) {
+				ElectionDeadlines::<T, I>::set(
+					unique_monotonic_identifier,
+					deadline.map(Into::into),
+				);
 			}
 
 			fn refresh_election(
@@ -786,6 +946,12 @@ pub mod pallet {
 								}
 								.map(|props_and_vote| CompositeConsensusVote {
 									vote: props_and_vote,
+									stake: T::INCLUDE_VOTER_STAKE.then(|| {
+										<T::FundingInfo as AccountInfo>::balance(
+											&validator_id.clone().into(),
+										)
+										.unique_saturated_into()
+									}),
 									validator_id,
 								})
 							})
@@ -794,7 +960,18 @@ pub mod pallet {
 						debug_assert!(votes.len() == current_authorities_count as usize);
 
 						// Remove individual components from non-authorities
-						for (validator_id, (_, individual_component)) in individual_components {
+						for (validator_id, (properties, individual_component)) in
+							individual_components
+						{
+							let individual_bytes =
+								(&properties, &individual_component).encoded_size() as u32;
+							Pallet::<T, I>::update_vote_storage_usage(
+								*unique_monotonic_identifier,
+								|usage| {
+									usage.individual_components =
+										usage.individual_components.saturating_sub(individual_bytes)
+								},
+							);
 							<<T::ElectoralSystemRunner as ElectoralSystemRunner>::Vote as
 				VoteStorage>::visit_shared_data_references_in_individual_component(
 								&individual_component,
@@ -909,6 +1086,10 @@ pub mod pallet {
 			) {
 				ElectoralUnsynchronisedStateMap::<T, I>::set(key, value);
 			}
+
+			fn deposit_event(event: <T::ElectoralSystemRunner as ElectoralSystemRunner>::Event) {
+				Pallet::<T, I>::deposit_event(Event::<T, I>::ElectoralSystemEvent { event });
+			}
 		}
 	}
 
@@ -1056,6 +1237,12 @@ pub mod pallet {
 						}
 						retain
 					});
+					let bitmap_bytes =
+						if this.bitmaps.is_empty() { 0 } else { this.encoded_size() as u32 };
+					Pallet::<T, I>::update_vote_storage_usage(
+						unique_monotonic_identifier,
+						|usage| usage.bitmap_components = bitmap_bytes,
+					);
 					BitmapComponents::<T, I>::set(
 						unique_monotonic_identifier,
 						if this.bitmaps.is_empty() { None } else { Some(this) },
@@ -1198,6 +1385,10 @@ pub mod pallet {
 						);
 					}
 					BitmapComponents::<T, I>::set(unique_monotonic_identifier, None);
+					Pallet::<T, I>::update_vote_storage_usage(
+						unique_monotonic_identifier,
+						|usage| usage.bitmap_components = 0,
+					);
 				}
 			}
 
@@ -1252,6 +1443,16 @@ pub mod pallet {
 					},
 				};
 
+				ensure!(
+					Self::handle_corrupt_storage(
+						<T::ElectoralSystemRunner as ElectoralSystemRunner>::is_vote_valid(
+							election_identifier,
+							&partial_vote,
+						)
+					)?,
+					Error::<T, I>::InvalidVote
+				);
+
 				Self::handle_corrupt_storage(Self::take_vote_and_then(
 					epoch_index,
 					unique_monotonic_identifier,
@@ -1286,6 +1487,12 @@ pub mod pallet {
 								|shared_data_hash| Self::add_shared_data_reference(shared_data_hash, unique_monotonic_identifier, block_number),
 							);
 							// Store individual component
+							let individual_bytes =
+								(&properties, &individual_component).encoded_size() as u32;
+							Self::update_vote_storage_usage(unique_monotonic_identifier, |usage| {
+								usage.individual_components =
+									usage.individual_components.saturating_add(individual_bytes)
+							});
 							IndividualComponents::<T, I>::set(
 								unique_monotonic_identifier,
 								authority.clone(),
@@ -1421,6 +1628,65 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Sets the number of resolved elections whose final consensus is kept in the
+		/// `ConsensusArchive`. Setting this to zero disables archiving and clears the archive.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::set_consensus_archive_size())]
+		pub fn set_consensus_archive_size(
+			origin: OriginFor<T>,
+			size: u32,
+			ignore_corrupt_storage: CorruptStorageAdherance,
+		) -> DispatchResult {
+			Self::ensure_governance(origin, ignore_corrupt_storage)?;
+			ensure!(size <= MAXIMUM_CONSENSUS_ARCHIVE_SIZE, Error::<T, I>::ArchiveSizeTooLarge);
+			ConsensusArchiveSize::<T, I>::set(size);
+			ConsensusArchive::<T, I>::mutate(|archive| {
+				archive.drain(..archive.len().saturating_sub(size as usize));
+			});
+			Ok(())
+		}
+
+		/// Initializes the pallet from a snapshot previously produced by
+		/// [Pallet::export_snapshot], typically on another chain. Votes are not part of the
+		/// snapshot, so the imported elections will be voted on afresh by the current authorities.
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::import_snapshot().saturating_add(
+			T::DbWeight::get().writes(snapshot.storage_entry_count())
+		))]
+		pub fn import_snapshot(
+			origin: OriginFor<T>,
+			snapshot: ElectoralSnapshotOf<T, I>,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+			ensure!(Status::<T, I>::get().is_none(), Error::<T, I>::AlreadyInitialized);
+
+			let ElectoralSnapshot::V1(snapshot) = snapshot;
+			Self::validate_snapshot(&snapshot)?;
+
+			ElectoralUnsynchronisedState::<T, I>::put(snapshot.unsynchronised_state);
+			for (key, value) in snapshot.unsynchronised_state_map {
+				ElectoralUnsynchronisedStateMap::<T, I>::insert(key, value);
+			}
+			ElectoralUnsynchronisedSettings::<T, I>::put(snapshot.unsynchronised_settings);
+			for (boundary, settings) in snapshot.settings {
+				ElectoralSettings::<T, I>::insert(boundary, settings);
+			}
+			let election_count = snapshot.elections.len() as u32;
+			for election in snapshot.elections {
+				ElectionState::<T, I>::insert(
+					*election.identifier.unique_monotonic(),
+					election.state,
+				);
+				ElectionProperties::<T, I>::insert(election.identifier, election.properties);
+			}
+			NextElectionIdentifier::<T, I>::put(snapshot.next_election_identifier);
+			Status::<T, I>::put(ElectionPalletStatus::Running);
+
+			Self::deposit_event(Event::<T, I>::SnapshotImported { election_count });
+
+			Ok(())
+		}
+
 		// ------------------------------------------------------------------------------------ //
 
 		// These are governance extrinsics designed to help fix any potential issues that may arise,
@@ -1466,22 +1732,19 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Pauses this electoral system instance, for example while the external chain is halted.
+		/// Existing votes are retained, so elections continue from where they left off once
+		/// resumed with `unpause_elections`.
 		#[pallet::call_index(34)]
 		#[pallet::weight(T::WeightInfo::pause_elections())]
 		pub fn pause_elections(origin: OriginFor<T>) -> DispatchResult {
 			T::EnsureGovernance::ensure_origin(origin)?;
-			match Status::<T, I>::get() {
-				None => Err(Error::<T, I>::Uninitialized.into()),
-				Some(ElectionPalletStatus::Paused { .. }) => Err(Error::<T, I>::Paused.into()),
-				Some(_) => {
-					Status::<T, I>::put(ElectionPalletStatus::Paused {
-						detected_corrupt_storage: false,
-					});
-					Ok(())
-				},
-			}
+			Self::pause().map_err(Into::into)
 		}
 
+		/// Resumes a paused electoral system instance. If `require_votes_cleared` is set, this
+		/// fails unless all votes have been cleared, otherwise votes cast before the pause are
+		/// kept.
 		#[pallet::call_index(35)]
 		#[pallet::weight(T::WeightInfo::unpause_elections())]
 		pub fn unpause_elections(
@@ -1506,6 +1769,7 @@ pub mod pallet {
 						Error::<T, I>::VotesNotCleared
 					);
 					Status::<T, I>::put(ElectionPalletStatus::Running);
+					Self::deposit_event(Event::<T, I>::ElectionsResumed);
 					Ok(())
 				},
 				Some(_) => Err(Error::<T, I>::NotPaused.into()),
@@ -1528,6 +1792,7 @@ pub mod pallet {
 					SharedData::<T, I>::clear(limit, None).maybe_cursor.is_none() &
 					BitmapComponents::<T, I>::clear(limit, None).maybe_cursor.is_none() &
 					IndividualComponents::<T, I>::clear(limit, None).maybe_cursor.is_none() &
+					ElectionVoteStorageUsage::<T, I>::clear(limit, None).maybe_cursor.is_none() &
 					ElectionConsensusHistoryUpToDate::<T, I>::clear(limit, None)
 						.maybe_cursor
 						.is_none()
@@ -1613,6 +1878,18 @@ pub mod pallet {
 								}
 							}
 
+							let election_identifiers = if Self::process_election_deadlines(
+								block_number,
+								&election_identifiers,
+							)? {
+								let mut election_identifiers =
+									ElectionProperties::<T, I>::iter_keys().collect::<Vec<_>>();
+								election_identifiers.sort();
+								election_identifiers
+							} else {
+								election_identifiers
+							};
+
 							T::ElectoralSystemRunner::on_finalize(election_identifiers)?;
 
 							Ok(())
@@ -1651,6 +1928,22 @@ pub mod pallet {
 	}
 
 	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Pauses the electoral system instance, as `pause_elections` does, for use by other
+		/// pallets.
+		pub fn pause() -> Result<(), Error<T, I>> {
+			match Status::<T, I>::get() {
+				None => Err(Error::<T, I>::Uninitialized),
+				Some(ElectionPalletStatus::Paused { .. }) => Err(Error::<T, I>::Paused),
+				Some(_) => {
+					Status::<T, I>::put(ElectionPalletStatus::Paused {
+						detected_corrupt_storage: false,
+					});
+					Self::deposit_event(Event::<T, I>::ElectionsPaused);
+					Ok(())
+				},
+			}
+		}
+
 		/// This function allows other pallets to initialize an Elections pallet, instead of needing
 		/// to initialize it via a governance extrinsic or at genesis.
 		pub fn internally_initialize(
@@ -1667,6 +1960,126 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Captures the current electoral state so it can be imported on another chain with the
+		/// `import_snapshot` extrinsic. Returns `None` if the pallet is not initialized.
+		pub fn export_snapshot() -> Option<ElectoralSnapshotOf<T, I>> {
+			Status::<T, I>::get()?;
+			let mut settings = ElectoralSettings::<T, I>::iter().collect::<Vec<_>>();
+			settings.sort_by_key(|(boundary, _)| *boundary);
+			let mut elections = ElectionProperties::<T, I>::iter()
+				.map(|(identifier, properties)| {
+					ElectionState::<T, I>::get(identifier.unique_monotonic())
+						.map(|state| ElectionSnapshot { identifier, properties, state })
+				})
+				.collect::<Option<Vec<_>>>()?;
+			elections.sort_by_key(|election| election.identifier);
+
+			Some(ElectoralSnapshot::V1(ElectoralSnapshotV1 {
+				unsynchronised_state: ElectoralUnsynchronisedState::<T, I>::get()?,
+				unsynchronised_state_map: ElectoralUnsynchronisedStateMap::<T, I>::iter().collect(),
+				unsynchronised_settings: ElectoralUnsynchronisedSettings::<T, I>::get()?,
+				settings,
+				elections,
+				next_election_identifier: NextElectionIdentifier::<T, I>::get(),
+			}))
+		}
+
+		/// Calls `on_deadline` for every election whose deadline has passed. Returns whether any
+		/// deadline was processed, in which case the set of elections may have changed.
+		fn process_election_deadlines(
+			block_number: BlockNumberFor<T>,
+			election_identifiers: &[CompositeElectionIdentifierOf<T::ElectoralSystemRunner>],
+		) -> Result<bool, CorruptStorageError> {
+			let mut deadline_reached = false;
+			for election_identifier in election_identifiers {
+				let unique_monotonic_identifier = *election_identifier.unique_monotonic();
+				if ElectionDeadlines::<T, I>::get(unique_monotonic_identifier)
+					.is_some_and(|deadline| deadline <= block_number)
+				{
+					deadline_reached = true;
+					let resolution = T::ElectoralSystemRunner::on_deadline(*election_identifier)?;
+					match resolution {
+						DeadlineResolution::Resolved => {
+							ElectionDeadlines::<T, I>::remove(unique_monotonic_identifier);
+						},
+						DeadlineResolution::Delete => {
+							RunnerStorageAccess::<T, I>::delete_election(*election_identifier);
+						},
+						DeadlineResolution::Extend(blocks) => {
+							ElectionDeadlines::<T, I>::insert(
+								unique_monotonic_identifier,
+								block_number.saturating_add(blocks.into()),
+							);
+						},
+					}
+					Self::deposit_event(Event::<T, I>::ElectionDeadlineReached {
+						election: unique_monotonic_identifier,
+						resolution,
+					});
+				}
+			}
+			Ok(deadline_reached)
+		}
+
+		fn archive_consensus(
+			election_identifier: CompositeElectionIdentifierOf<T::ElectoralSystemRunner>,
+			consensus: <T::ElectoralSystemRunner as ElectoralSystemRunner>::Consensus,
+		) {
+			let archive_size = ConsensusArchiveSize::<T, I>::get() as usize;
+			if archive_size > 0 {
+				ConsensusArchive::<T, I>::mutate(|archive| {
+					archive.push(ArchivedConsensus {
+						election_identifier,
+						consensus,
+						resolved_at: frame_system::Pallet::<T>::block_number(),
+					});
+					archive.drain(..archive.len().saturating_sub(archive_size));
+				});
+			}
+		}
+
+		/// Checks that a snapshot is internally consistent: every election has a unique identifier
+		/// lower than the next identifier, and has settings that apply to it.
+		fn validate_snapshot(
+			snapshot: &ElectoralSnapshotV1<T::ElectoralSystemRunner>,
+		) -> Result<(), Error<T, I>> {
+			let election_ids = snapshot
+				.elections
+				.iter()
+				.map(|election| *election.identifier.unique_monotonic())
+				.collect::<BTreeSet<_>>();
+			ensure!(election_ids.len() == snapshot.elections.len(), Error::<T, I>::InvalidSnapshot);
+
+			let settings_boundaries =
+				snapshot.settings.iter().map(|(boundary, _)| *boundary).collect::<BTreeSet<_>>();
+			ensure!(
+				settings_boundaries.len() == snapshot.settings.len(),
+				Error::<T, I>::InvalidSnapshot
+			);
+			// Settings for new elections are stored at the next election identifier.
+			ensure!(
+				settings_boundaries
+					.last()
+					.is_some_and(|boundary| *boundary <= snapshot.next_election_identifier),
+				Error::<T, I>::InvalidSnapshot
+			);
+
+			if let Some(lowest_election_id) = election_ids.first() {
+				ensure!(
+					election_ids.last().is_some_and(|id| *id < snapshot.next_election_identifier),
+					Error::<T, I>::InvalidSnapshot
+				);
+				ensure!(
+					settings_boundaries
+						.first()
+						.is_some_and(|boundary| boundary <= lowest_election_id),
+					Error::<T, I>::InvalidSnapshot
+				);
+			}
+
+			Ok(())
+		}
+
 		/// Provides access into the ElectoralSystem's current election
 		/// identifiers.
 		pub fn with_election_identifiers<
@@ -1818,6 +2231,19 @@ pub mod pallet {
 									if let Some(proposed_vote) =
 										proposed_votes.get(&election_identifier)
 									{
+										let proposed_partial_vote = <<T::ElectoralSystemRunner as ElectoralSystemRunner>::Vote as VoteStorage>::vote_into_partial_vote(
+											proposed_vote,
+											|shared_data| SharedDataHash::of(&shared_data)
+										);
+										// Votes that would be rejected by the vote extrinsic are
+										// never needed.
+										if !<T::ElectoralSystemRunner as ElectoralSystemRunner>::is_vote_valid(
+											election_identifier,
+											&proposed_partial_vote,
+										)? {
+											return Ok((election_identifier, false))
+										}
+
 										let unique_monotonic_identifier =
 											*election_identifier.unique_monotonic();
 
@@ -1864,13 +2290,7 @@ pub mod pallet {
 													},
 													existing_authority_vote,
 												),
-												(
-													<<T::ElectoralSystemRunner as ElectoralSystemRunner>::Vote as VoteStorage>::vote_into_partial_vote(
-														proposed_vote,
-														|shared_data| SharedDataHash::of(&shared_data)
-													),
-													proposed_vote.clone(),
-												),
+												(proposed_partial_vote, proposed_vote.clone()),
 											)
 										} else {
 											true
@@ -1936,6 +2356,13 @@ pub mod pallet {
 				|election_bitmap_components| {
 					let individual_component =
 						IndividualComponents::<T, I>::take(unique_monotonic_identifier, authority);
+					if let Some(individual_component) = &individual_component {
+						let individual_bytes = individual_component.encoded_size() as u32;
+						Self::update_vote_storage_usage(unique_monotonic_identifier, |usage| {
+							usage.individual_components =
+								usage.individual_components.saturating_sub(individual_bytes)
+						});
+					}
 
 					let r = f(
 						<<T::ElectoralSystemRunner as ElectoralSystemRunner>::Vote as VoteStorage>::components_into_authority_vote(
@@ -2063,6 +2490,23 @@ pub mod pallet {
 			}
 		}
 
+		fn update_vote_storage_usage(
+			unique_monotonic_identifier: UniqueMonotonicIdentifier,
+			f: impl FnOnce(&mut VoteStorageUsage),
+		) {
+			ElectionVoteStorageUsage::<T, I>::mutate_exists(unique_monotonic_identifier, |usage| {
+				let mut updated_usage = usage.take().unwrap_or_default();
+				f(&mut updated_usage);
+				*usage = (updated_usage != Default::default()).then_some(updated_usage);
+			});
+		}
+
+		/// The estimated storage consumed by the votes of all elections.
+		pub fn vote_storage_usage() -> VoteStorageUsage {
+			ElectionVoteStorageUsage::<T, I>::iter_values()
+				.fold(VoteStorageUsage::default(), VoteStorageUsage::saturating_add)
+		}
+
 		fn add_shared_data_reference(
 			shared_data_hash: SharedDataHash,
 			unique_monotonic_identifier: UniqueMonotonicIdentifier,
@@ -2072,10 +2516,19 @@ pub mod pallet {
 				shared_data_hash,
 				unique_monotonic_identifier,
 			)
-			.unwrap_or_else(|| ReferenceDetails {
-				count: 0,
-				created: block_number,
-				expires: block_number + SharedDataReferenceLifetime::<T, I>::get(),
+			.unwrap_or_else(|| {
+				// The first reference from this election to data that has already been provided.
+				if let Some(shared_data) = SharedData::<T, I>::get(shared_data_hash) {
+					let shared_bytes = shared_data.encoded_size() as u32;
+					Self::update_vote_storage_usage(unique_monotonic_identifier, |usage| {
+						usage.shared_data = usage.shared_data.saturating_add(shared_bytes)
+					});
+				}
+				ReferenceDetails {
+					count: 0,
+					created: block_number,
+					expires: block_number + SharedDataReferenceLifetime::<T, I>::get(),
+				}
 			});
 
 			reference_details.count = reference_details.count.saturating_add(1);
@@ -2099,6 +2552,12 @@ pub mod pallet {
 						shared_data_hash,
 						unique_monotonic_identifier,
 					);
+					if let Some(shared_data) = SharedData::<T, I>::get(shared_data_hash) {
+						let shared_bytes = shared_data.encoded_size() as u32;
+						Self::update_vote_storage_usage(unique_monotonic_identifier, |usage| {
+							usage.shared_data = usage.shared_data.saturating_sub(shared_bytes)
+						});
+					}
 					if !SharedDataReferenceCount::<T, I>::contains_prefix(shared_data_hash) {
 						SharedData::<T, I>::remove(shared_data_hash);
 					}
@@ -2122,9 +2581,16 @@ pub mod pallet {
 				.into_iter()
 				.any(|reference_details| reference_details.count != 0)
 			{
+				let newly_provided = !SharedData::<T, I>::contains_key(shared_data_hash);
+				let shared_bytes = shared_data.encoded_size() as u32;
 				SharedData::<T, I>::insert(shared_data_hash, shared_data);
 				for unique_monotonic_identifier in unique_monotonic_identifiers {
 					ElectionConsensusHistoryUpToDate::<T, I>::remove(unique_monotonic_identifier);
+					if newly_provided {
+						Self::update_vote_storage_usage(unique_monotonic_identifier, |usage| {
+							usage.shared_data = usage.shared_data.saturating_add(shared_bytes)
+						});
+					}
 				}
 				Ok(())
 			} else {
@@ -25,6 +25,7 @@ impl pallet_cf_elections::Config<Instance1> for Test {
 
 	// TODO: Use Settings?
 	type ElectoralSystemRunner = crate::electoral_systems::mock::MockElectoralSystemRunner;
+	const INCLUDE_VOTER_STAKE: bool = false;
 
 	type WeightInfo = ();
 }
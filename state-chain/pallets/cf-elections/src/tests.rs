@@ -1,10 +1,10 @@
 #![cfg(test)]
 use crate::{mock::*, *};
 use cf_primitives::AuthorityCount;
-use electoral_system::ConsensusStatus;
+use electoral_system::{ConsensusStatus, DeadlineResolution};
 use electoral_system_runner::RunnerStorageAccessTrait;
 use electoral_systems::mock::{BehaviourUpdate, MockElectoralSystemRunner};
-use frame_support::traits::OriginTrait;
+use frame_support::{assert_noop, assert_ok, traits::OriginTrait};
 use mock::Test;
 use std::collections::BTreeMap;
 use vote_storage::AuthorityVote;
@@ -327,3 +327,246 @@ fn authority_removes_and_re_adds_itself_from_contributing_set() {
 		.submit_votes(&[1], VOTE, Ok(()))
 		.expect_consensus(ConsensusStatus::Changed { previous: 2, new: 3 });
 }
+
+#[test]
+fn electoral_state_can_be_exported_and_imported() {
+	election_test_ext(Default::default()).new_election().then_execute_with(|_| {
+		let snapshot = Pallet::<Test, Instance1>::export_snapshot().expect("Pallet is initialized");
+		let ElectoralSnapshot::V1(ref snapshot_v1) = snapshot;
+		assert_eq!(snapshot_v1.elections.len(), 1);
+
+		// The snapshot survives a round trip through its encoding.
+		assert_eq!(
+			ElectoralSnapshotOf::<Test, Instance1>::decode(&mut &snapshot.encode()[..]).unwrap(),
+			snapshot
+		);
+
+		// Snapshots can only be imported into an uninitialized pallet.
+		assert_noop!(
+			Pallet::<Test, Instance1>::import_snapshot(OriginTrait::root(), snapshot.clone()),
+			Error::<Test, Instance1>::AlreadyInitialized
+		);
+
+		Status::<Test, Instance1>::kill();
+
+		// Elections must have identifiers lower than the next identifier.
+		let mut invalid_snapshot = snapshot_v1.clone();
+		invalid_snapshot.next_election_identifier =
+			*invalid_snapshot.elections[0].identifier.unique_monotonic();
+		assert_noop!(
+			Pallet::<Test, Instance1>::import_snapshot(
+				OriginTrait::root(),
+				ElectoralSnapshot::V1(invalid_snapshot)
+			),
+			Error::<Test, Instance1>::InvalidSnapshot
+		);
+
+		assert_ok!(Pallet::<Test, Instance1>::import_snapshot(
+			OriginTrait::root(),
+			snapshot.clone()
+		));
+		assert_eq!(Status::<Test, Instance1>::get(), Some(ElectionPalletStatus::Running));
+		assert_eq!(Pallet::<Test, Instance1>::export_snapshot(), Some(snapshot));
+	});
+}
+
+#[test]
+fn votes_are_retained_while_elections_are_paused() {
+	const VOTE: CompositeAuthorityVoteOf<MockElectoralSystemRunner> = AuthorityVote::Vote(());
+
+	election_test_ext(Default::default())
+		.new_election()
+		.submit_votes(&[0, 1, 2], VOTE, Ok(()))
+		.then_apply_extrinsics(|_| {
+			[(OriginTrait::root(), Call::<Test, _>::pause_elections {}, Ok(()))]
+		})
+		.then_execute_with_keep_context(|_| {
+			cf_test_utilities::assert_has_event::<Test>(RuntimeEvent::Elections(
+				Event::ElectionsPaused,
+			));
+			// Engines are given no electoral data, so they stop voting.
+			assert!(Pallet::<Test, Instance1>::electoral_data(&0).is_none());
+		})
+		.submit_votes(&[0, 1, 2], VOTE, Err(Error::Paused))
+		.then_apply_extrinsics(|_| {
+			[(
+				OriginTrait::root(),
+				Call::<Test, _>::unpause_elections { require_votes_cleared: false },
+				Ok(()),
+			)]
+		})
+		.then_execute_with_keep_context(|_| {
+			cf_test_utilities::assert_has_event::<Test>(RuntimeEvent::Elections(
+				Event::ElectionsResumed,
+			));
+			let electoral_data = Pallet::<Test, Instance1>::electoral_data(&0)
+				.expect("Expected electoral data once resumed.");
+			assert!(electoral_data
+				.current_elections
+				.values()
+				.all(|election_data| election_data.option_existing_vote.is_some()));
+		});
+}
+
+#[test]
+fn invalid_votes_are_filtered_and_rejected() {
+	const VOTE: CompositeAuthorityVoteOf<MockElectoralSystemRunner> = AuthorityVote::Vote(());
+
+	fn proposed_votes(
+		umis: &[UniqueMonotonicIdentifier],
+	) -> BTreeMap<CompositeElectionIdentifierOf<MockElectoralSystemRunner>, ()> {
+		umis.iter().map(|umi| (ElectionIdentifier::new(*umi, ()), ())).collect()
+	}
+
+	election_test_ext(Default::default())
+		.new_election()
+		.then_execute_with_keep_context(|TestContext { umis, .. }| {
+			assert_eq!(
+				Pallet::<Test, Instance1>::filter_votes(&0, proposed_votes(umis)).len(),
+				umis.len()
+			);
+		})
+		.update_settings(&[BehaviourUpdate::VoteValid(false)])
+		.then_execute_with_keep_context(|TestContext { umis, .. }| {
+			assert!(Pallet::<Test, Instance1>::filter_votes(&0, proposed_votes(umis)).is_empty());
+		})
+		.submit_votes(&[0, 1], VOTE, Err(Error::InvalidVote))
+		.update_settings(&[BehaviourUpdate::VoteValid(true)])
+		.submit_votes(&[0, 1], VOTE, Ok(()));
+}
+
+#[test]
+fn consensus_of_deleted_elections_is_archived() {
+	const VOTE: CompositeAuthorityVoteOf<MockElectoralSystemRunner> = AuthorityVote::Vote(());
+
+	election_test_ext(Default::default())
+		.then_apply_extrinsics(|_| {
+			[(
+				OriginTrait::root(),
+				Call::<Test, _>::set_consensus_archive_size {
+					size: MAXIMUM_CONSENSUS_ARCHIVE_SIZE + 1,
+					ignore_corrupt_storage: CorruptStorageAdherance::Heed,
+				},
+				Err(Error::<Test, Instance1>::ArchiveSizeTooLarge.into()),
+			)]
+		})
+		.then_apply_extrinsics(|_| {
+			[(
+				OriginTrait::root(),
+				Call::<Test, _>::set_consensus_archive_size {
+					size: 1,
+					ignore_corrupt_storage: CorruptStorageAdherance::Heed,
+				},
+				Ok(()),
+			)]
+		})
+		.new_election()
+		.assume_consensus()
+		.update_settings(&[BehaviourUpdate::DeleteOnFinalizeConsensus(true)])
+		.submit_votes(&[0, 1, 2], VOTE, Ok(()))
+		.then_execute_with_keep_context(|TestContext { umis, .. }| {
+			let archive = ConsensusArchive::<Test, Instance1>::get();
+			assert_eq!(archive.len(), 1);
+			assert_eq!(archive[0].election_identifier, ElectionIdentifier::new(umis[0], ()));
+			assert_eq!(archive[0].consensus, 3);
+		})
+		// Disabling the archive clears it.
+		.then_apply_extrinsics(|_| {
+			[(
+				OriginTrait::root(),
+				Call::<Test, _>::set_consensus_archive_size {
+					size: 0,
+					ignore_corrupt_storage: CorruptStorageAdherance::Heed,
+				},
+				Ok(()),
+			)]
+		})
+		.then_execute_with_keep_context(|_| {
+			assert!(ConsensusArchive::<Test, Instance1>::get().is_empty());
+		});
+}
+
+#[test]
+fn vote_storage_usage_is_tracked() {
+	const VOTE: CompositeAuthorityVoteOf<MockElectoralSystemRunner> = AuthorityVote::Vote(());
+	let expected_usage = VoteStorageUsage {
+		bitmap_components: 0,
+		individual_components: 3 * SharedDataHash::of(&()).encoded_size() as u32,
+		shared_data: 0,
+	};
+
+	election_test_ext(Default::default())
+		.new_election()
+		.submit_votes(&[0, 1, 2], VOTE, Ok(()))
+		.then_execute_with_keep_context(|TestContext { umis, .. }| {
+			assert_eq!(
+				ElectionVoteStorageUsage::<Test, Instance1>::get(umis[0]),
+				Some(expected_usage)
+			);
+			assert_eq!(Pallet::<Test, Instance1>::vote_storage_usage(), expected_usage);
+		})
+		// Replacing a vote does not change the usage.
+		.submit_votes(&[0], VOTE, Ok(()))
+		.then_execute_with_keep_context(|_| {
+			assert_eq!(Pallet::<Test, Instance1>::vote_storage_usage(), expected_usage);
+		})
+		// Deleting the election releases the storage.
+		.assume_consensus()
+		.update_settings(&[BehaviourUpdate::DeleteOnFinalizeConsensus(true)])
+		.submit_votes(&[0, 1, 2], VOTE, Ok(()))
+		.then_execute_with_keep_context(|_| {
+			assert_eq!(ElectionVoteStorageUsage::<Test, Instance1>::iter().count(), 0);
+			assert_eq!(
+				Pallet::<Test, Instance1>::vote_storage_usage(),
+				VoteStorageUsage::default()
+			);
+		});
+}
+
+#[test]
+fn on_deadline_decides_the_fate_of_elections() {
+	fn set_deadline_in(umi: UniqueMonotonicIdentifier, blocks: u32) {
+		RunnerStorageAccess::<Test, Instance1>::set_election_deadline(
+			umi,
+			Some(System::block_number() as u32 + blocks),
+		);
+	}
+
+	election_test_ext(Default::default())
+		.new_election()
+		.then_execute_with_keep_context(|TestContext { umis, .. }| set_deadline_in(umis[0], 1))
+		// Elections can be given more time.
+		.update_settings(&[BehaviourUpdate::DeadlineResolution(DeadlineResolution::Extend(2))])
+		.then_process_next_block()
+		.then_execute_with_keep_context(|TestContext { umis, .. }| {
+			assert_eq!(
+				ElectionDeadlines::<Test, Instance1>::get(umis[0]),
+				Some(System::block_number() + 2)
+			);
+			System::assert_last_event(RuntimeEvent::Elections(
+				Event::<Test, Instance1>::ElectionDeadlineReached {
+					election: umis[0],
+					resolution: DeadlineResolution::Extend(2),
+				},
+			));
+		})
+		// Resolved elections are kept, without a deadline.
+		.update_settings(&[BehaviourUpdate::DeadlineResolution(DeadlineResolution::Resolved)])
+		.then_process_next_block()
+		.then_execute_with_keep_context(|TestContext { umis, .. }| {
+			assert!(ElectionDeadlines::<Test, Instance1>::get(umis[0]).is_some());
+		})
+		.then_process_next_block()
+		.then_execute_with_keep_context(|TestContext { umis, .. }| {
+			assert!(ElectionDeadlines::<Test, Instance1>::get(umis[0]).is_none());
+			assert!(RunnerStorageAccess::<Test, Instance1>::election_state(umis[0]).is_ok());
+			set_deadline_in(umis[0], 1);
+		})
+		// Failed elections are deleted.
+		.update_settings(&[BehaviourUpdate::DeadlineResolution(DeadlineResolution::Delete)])
+		.then_process_next_block()
+		.then_execute_with_keep_context(|TestContext { umis, .. }| {
+			assert!(ElectionDeadlines::<Test, Instance1>::get(umis[0]).is_none());
+			assert!(RunnerStorageAccess::<Test, Instance1>::election_state(umis[0]).is_err());
+		});
+}
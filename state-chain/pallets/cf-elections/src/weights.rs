@@ -40,6 +40,8 @@ pub trait WeightInfo {
 	fn provide_shared_data() -> Weight;
 	fn initialize() -> Weight;
 	fn update_settings() -> Weight;
+	fn import_snapshot() -> Weight;
+	fn set_consensus_archive_size() -> Weight;
 	fn set_shared_data_reference_lifetime() -> Weight;
 	fn clear_election_votes() -> Weight;
 	fn invalidate_election_consensus_cache() -> Weight;
@@ -236,6 +238,38 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 	}
 	/// Storage: `SolanaElections::Status` (r:1 w:0)
 	/// Proof: `SolanaElections::Status` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `SolanaElections::ConsensusArchive` (r:1 w:1)
+	/// Proof: `SolanaElections::ConsensusArchive` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `SolanaElections::ConsensusArchiveSize` (r:0 w:1)
+	/// Proof: `SolanaElections::ConsensusArchiveSize` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_consensus_archive_size() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `271`
+		//  Estimated: `1756`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(7_000_000, 1756)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `SolanaElections::Status` (r:1 w:1)
+	/// Proof: `SolanaElections::Status` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `SolanaElections::ElectoralUnsynchronisedState` (r:0 w:1)
+	/// Proof: `SolanaElections::ElectoralUnsynchronisedState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `SolanaElections::ElectoralUnsynchronisedSettings` (r:0 w:1)
+	/// Proof: `SolanaElections::ElectoralUnsynchronisedSettings` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `SolanaElections::NextElectionIdentifier` (r:0 w:1)
+	/// Proof: `SolanaElections::NextElectionIdentifier` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn import_snapshot() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `251`
+		//  Estimated: `1736`
+		// Minimum execution time: 10_000_000 picoseconds.
+		Weight::from_parts(11_000_000, 1736)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: `SolanaElections::Status` (r:1 w:0)
+	/// Proof: `SolanaElections::Status` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `SolanaElections::SharedDataReferenceLifetime` (r:0 w:1)
 	/// Proof: `SolanaElections::SharedDataReferenceLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	fn set_shared_data_reference_lifetime() -> Weight {
@@ -561,6 +595,38 @@ impl WeightInfo for () {
 	}
 	/// Storage: `SolanaElections::Status` (r:1 w:0)
 	/// Proof: `SolanaElections::Status` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `SolanaElections::ConsensusArchive` (r:1 w:1)
+	/// Proof: `SolanaElections::ConsensusArchive` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `SolanaElections::ConsensusArchiveSize` (r:0 w:1)
+	/// Proof: `SolanaElections::ConsensusArchiveSize` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn set_consensus_archive_size() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `271`
+		//  Estimated: `1756`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(7_000_000, 1756)
+			.saturating_add(ParityDbWeight::get().reads(2_u64))
+			.saturating_add(ParityDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `SolanaElections::Status` (r:1 w:1)
+	/// Proof: `SolanaElections::Status` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `SolanaElections::ElectoralUnsynchronisedState` (r:0 w:1)
+	/// Proof: `SolanaElections::ElectoralUnsynchronisedState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `SolanaElections::ElectoralUnsynchronisedSettings` (r:0 w:1)
+	/// Proof: `SolanaElections::ElectoralUnsynchronisedSettings` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `SolanaElections::NextElectionIdentifier` (r:0 w:1)
+	/// Proof: `SolanaElections::NextElectionIdentifier` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn import_snapshot() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `251`
+		//  Estimated: `1736`
+		// Minimum execution time: 10_000_000 picoseconds.
+		Weight::from_parts(11_000_000, 1736)
+			.saturating_add(ParityDbWeight::get().reads(1_u64))
+			.saturating_add(ParityDbWeight::get().writes(4_u64))
+	}
+	/// Storage: `SolanaElections::Status` (r:1 w:0)
+	/// Proof: `SolanaElections::Status` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `SolanaElections::SharedDataReferenceLifetime` (r:0 w:1)
 	/// Proof: `SolanaElections::SharedDataReferenceLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	fn set_shared_data_reference_lifetime() -> Weight {
@@ -6,7 +6,7 @@ use cf_chains::{eth::api::StateChainGatewayAddressProvider, UpdateFlipSupply};
 use cf_primitives::{AssetAmount, EgressId};
 use cf_traits::{
 	impl_pallet_safe_mode, BackupRewardsNotifier, BlockEmissions, Broadcaster, EgressApi,
-	FlipBurnInfo, Issuance, RewardsDistribution, ScheduledEgressDetails,
+	EgressOrigin, FlipBurnInfo, Issuance, RewardsDistribution, ScheduledEgressDetails,
 };
 use codec::MaxEncodedLen;
 use frame_support::storage::transactional::with_storage_layer;
@@ -319,6 +319,8 @@ impl<T: Config> Pallet<T> {
 				flip_to_burn,
 				T::EthEnvironment::state_chain_gateway_address(),
 				None,
+				None,
+				EgressOrigin::FeeManagement,
 			)
 			.map_err(Into::into)
 			.and_then(
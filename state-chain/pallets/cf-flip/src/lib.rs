@@ -437,6 +437,10 @@ impl<T: Config> FeePayment for Pallet<T> {
 			Err(Error::<T>::InsufficientLiquidity.into())
 		}
 	}
+
+	fn refund_fee(account_id: &Self::AccountId, amount: Self::Amount) {
+		Pallet::<T>::settle(account_id, Pallet::<T>::mint(amount).into());
+	}
 }
 
 pub struct Bonder<T>(PhantomData<T>);
@@ -1,7 +1,7 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
-use crate::{BoostStatus, DisabledEgressAssets};
+use crate::{BoostStatus, ChannelOpenAttempt, DisabledEgressAssets};
 use cf_chains::{
 	address::EncodedAddress,
 	benchmarking_value::{BenchmarkValue, BenchmarkValueExtended},
@@ -93,6 +93,30 @@ mod benchmarks {
 			));
 		}
 	}
+	#[benchmark]
+	fn record_deposit_failure() {
+		// The history is full, so the oldest entry is evicted.
+		DepositFailureHistoryRange::<T, I>::put((0, MAX_DEPOSIT_FAILURE_HISTORY));
+
+		#[block]
+		{
+			Pallet::<T, I>::deposit_failed(
+				BenchmarkValue::benchmark_value(),
+				DepositFailedReason::BelowMinimumDeposit,
+				DepositFailedDetails::DepositChannel {
+					deposit_witness: DepositWitness {
+						deposit_address: BenchmarkValue::benchmark_value(),
+						asset: BenchmarkValue::benchmark_value(),
+						amount: BenchmarkValue::benchmark_value(),
+						deposit_details: BenchmarkValue::benchmark_value(),
+					},
+				},
+			);
+		}
+
+		assert_eq!(DepositFailureHistoryRange::<T, I>::get(), (1, MAX_DEPOSIT_FAILURE_HISTORY + 1));
+	}
+
 	#[benchmark]
 	fn finalise_ingress(a: Linear<1, 100>) {
 		let mut addresses = vec![];
@@ -132,6 +156,55 @@ mod benchmarks {
 		}
 	}
 
+	#[benchmark]
+	fn egress_scheduled_fetches(n: Linear<1, 100>) {
+		// Two fetches are scheduled from each channel, as happens when a channel receives several
+		// deposits before it is fetched from. New channels change state when their first fetch is
+		// scheduled, so every channel is written back.
+		for i in 0..n {
+			let deposit_address =
+				<<T as Config<I>>::TargetChain as Chain>::ChainAccount::benchmark_value_by_id(
+					i as u8,
+				);
+			let source_asset: <<T as Config<I>>::TargetChain as Chain>::ChainAsset =
+				BenchmarkValue::benchmark_value();
+			let block_number = TargetChainBlockNumber::<T, I>::benchmark_value();
+			DepositChannelLookup::<T, I>::insert(
+				deposit_address.clone(),
+				DepositChannelDetails::<T, I> {
+					owner: account("doogle", 0, 0),
+					opened_at: block_number,
+					expires_at: block_number,
+					deposit_channel: DepositChannel::generate_new::<
+						<T as Config<I>>::AddressDerivation,
+					>(i as u64, source_asset)
+					.unwrap(),
+					action: ChannelAction::<T::AccountId>::LiquidityProvision {
+						lp_account: account("doogle", 0, 0),
+						refund_address: None,
+					},
+					boost_fee: 0,
+					boost_status: BoostStatus::NotBoosted,
+				},
+			);
+			for _ in 0..2 {
+				ScheduledEgressFetchOrTransfer::<T, I>::append(FetchOrTransfer::Fetch {
+					asset: source_asset,
+					deposit_address: deposit_address.clone(),
+					deposit_fetch_id: None,
+					amount: 1_000u32.into(),
+				});
+			}
+		}
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T, I>::do_egress_scheduled_fetch_transfer(
+				&mut Pallet::<T, I>::broadcast_budget_tracker()
+			));
+		}
+	}
+
 	#[benchmark]
 	fn vault_transfer_failed() {
 		let epoch = T::EpochInfo::epoch_index();
@@ -235,6 +308,7 @@ mod benchmarks {
 				refund_address: None,
 			},
 			fee_tier,
+			ChannelOpenAttempt::New,
 		)
 		.unwrap();
 
@@ -325,6 +399,8 @@ mod benchmarks {
 				boost_fee: 0,
 				channel_id: None,
 				deposit_address: None,
+				tracking_id: None,
+				verified_sender: None,
 			}),
 		};
 
@@ -404,6 +480,7 @@ mod benchmarks {
 				refund_address: None,
 			},
 			TIER_5_BPS,
+			ChannelOpenAttempt::New,
 		)
 		.unwrap();
 
@@ -529,6 +606,265 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn update_swap_channel() {
+		let caller =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Broker).unwrap();
+		let deposit_address: <<T as Config<I>>::TargetChain as Chain>::ChainAccount =
+			BenchmarkValue::benchmark_value();
+		let (opened_at, expires_at, _) = Pallet::<T, I>::expiry_and_recycle_block_height();
+		DepositChannelLookup::<T, I>::insert(
+			&deposit_address,
+			DepositChannelDetails {
+				owner: caller.clone(),
+				opened_at,
+				expires_at,
+				deposit_channel:
+					DepositChannel::generate_new::<<T as Config<I>>::AddressDerivation>(
+						1,
+						BenchmarkValue::benchmark_value(),
+					)
+					.unwrap(),
+				action: ChannelAction::<T::AccountId>::Swap {
+					destination_asset: Asset::Eth,
+					destination_address: ForeignChainAddress::Eth(Default::default()),
+					broker_fees: Default::default(),
+					channel_metadata: None,
+					refund_params: None,
+					dca_params: None,
+					tracking_id: None,
+					egress_memo: None,
+				},
+				boost_fee: 0,
+				boost_status: BoostStatus::NotBoosted,
+			},
+		);
+		let broker_fees: Beneficiaries<T::AccountId> =
+			vec![Beneficiary { account: caller.clone(), bps: 10 }].try_into().unwrap();
+		let destination_address = ForeignChainAddress::Eth([1; 20].into());
+
+		#[extrinsic_call]
+		update_swap_channel(
+			RawOrigin::Signed(caller),
+			deposit_address.clone(),
+			Some((10, Default::default())),
+			Some(EncodedAddress::Eth([1; 20])),
+		);
+
+		assert!(matches!(
+			DepositChannelLookup::<T, I>::get(deposit_address).unwrap().action,
+			ChannelAction::Swap { broker_fees: fees, destination_address: address, .. }
+				if fees == broker_fees && address == destination_address
+		));
+	}
+
+	#[benchmark]
+	fn update_refund_address() {
+		let caller =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Broker).unwrap();
+		let deposit_address: <<T as Config<I>>::TargetChain as Chain>::ChainAccount =
+			BenchmarkValue::benchmark_value();
+		let (opened_at, expires_at, _) = Pallet::<T, I>::expiry_and_recycle_block_height();
+		DepositChannelLookup::<T, I>::insert(
+			&deposit_address,
+			DepositChannelDetails {
+				owner: caller.clone(),
+				opened_at,
+				expires_at,
+				deposit_channel:
+					DepositChannel::generate_new::<<T as Config<I>>::AddressDerivation>(
+						1,
+						BenchmarkValue::benchmark_value(),
+					)
+					.unwrap(),
+				action: ChannelAction::<T::AccountId>::LiquidityProvision {
+					lp_account: caller.clone(),
+					refund_address: Some(BenchmarkValue::benchmark_value()),
+				},
+				boost_fee: 0,
+				boost_status: BoostStatus::NotBoosted,
+			},
+		);
+
+		#[extrinsic_call]
+		update_refund_address(
+			RawOrigin::Signed(caller),
+			deposit_address.clone(),
+			EncodedAddress::Eth([1; 20]),
+		);
+
+		assert!(PendingRefundAddressUpdates::<T, I>::contains_key(deposit_address));
+	}
+
+	#[benchmark]
+	fn close_deposit_channel() {
+		let caller =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Broker).unwrap();
+		let deposit_address: <<T as Config<I>>::TargetChain as Chain>::ChainAccount =
+			BenchmarkValue::benchmark_value();
+		let (opened_at, expires_at, recycle_height) =
+			Pallet::<T, I>::expiry_and_recycle_block_height();
+		DepositChannelLookup::<T, I>::insert(
+			&deposit_address,
+			DepositChannelDetails {
+				owner: caller.clone(),
+				opened_at,
+				expires_at,
+				deposit_channel:
+					DepositChannel::generate_new::<<T as Config<I>>::AddressDerivation>(
+						1,
+						BenchmarkValue::benchmark_value(),
+					)
+					.unwrap(),
+				action: ChannelAction::<T::AccountId>::Swap {
+					destination_asset: Asset::Eth,
+					destination_address: ForeignChainAddress::Eth(Default::default()),
+					broker_fees: Default::default(),
+					channel_metadata: None,
+					refund_params: None,
+					dca_params: None,
+					tracking_id: None,
+					egress_memo: None,
+				},
+				boost_fee: 0,
+				boost_status: BoostStatus::NotBoosted,
+			},
+		);
+		Pallet::<T, I>::schedule_channel_recycling(recycle_height, deposit_address.clone());
+
+		#[extrinsic_call]
+		close_deposit_channel(RawOrigin::Signed(caller), deposit_address.clone());
+
+		assert_eq!(
+			DepositChannelLookup::<T, I>::get(deposit_address).unwrap().expires_at,
+			opened_at
+		);
+	}
+
+	#[benchmark]
+	fn report_reorg() {
+		let origin = T::EnsureWitnessed::try_successful_origin().unwrap();
+		ObservedReorgs::<T, I>::put(vec![(1u32.into(), 1u32.into()); MAX_OBSERVED_REORGS]);
+		WitnessSafetyMarginAutoTuning::<T, I>::put(WitnessSafetyMarginBounds {
+			min: 1u32.into(),
+			max: 100u32.into(),
+		});
+
+		let call = Call::<T, I>::report_reorg { block_height: 2u32.into(), depth: 5u32.into() };
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin));
+		}
+
+		assert_eq!(WitnessSafetyMargin::<T, I>::get(), Some(5u32.into()));
+	}
+
+	#[benchmark]
+	fn start_deposit_address_verification() {
+		let origin = T::EnsureGovernance::try_successful_origin().unwrap();
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T, I>::start_deposit_address_verification(origin));
+		}
+
+		assert!(DepositAddressVerificationInProgress::<T, I>::exists());
+	}
+
+	#[benchmark]
+	fn start_chain_wind_down() {
+		let origin = T::EnsureGovernance::try_successful_origin().unwrap();
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T, I>::start_chain_wind_down(origin, 100u32.into()));
+		}
+
+		assert!(ChainWindDown::<T, I>::exists());
+	}
+
+	#[benchmark]
+	fn set_broker_minimum_deposit() {
+		let caller =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Broker).unwrap();
+		let asset: TargetChainAsset<T, I> = BenchmarkValue::benchmark_value();
+
+		#[extrinsic_call]
+		set_broker_minimum_deposit(RawOrigin::Signed(caller.clone()), asset, Some(1_000u32.into()));
+
+		assert!(BrokerMinimumDeposits::<T, I>::get(caller, asset).is_some());
+	}
+
+	#[benchmark]
+	fn extend_deposit_channel_expiries(n: Linear<1, 100>) {
+		let origin = T::EnsureGovernance::try_successful_origin().unwrap();
+		let owner: T::AccountId = whitelisted_caller();
+		DepositChannelLifetime::<T, I>::put(TargetChainBlockNumber::<T, I>::from(100u32));
+		let (opened_at, expires_at, recycle_height) =
+			Pallet::<T, I>::expiry_and_recycle_block_height();
+		let mut deposit_addresses = Vec::new();
+		for i in 0..n {
+			let deposit_address =
+				<<T as Config<I>>::TargetChain as Chain>::ChainAccount::benchmark_value_by_id(
+					i as u8,
+				);
+			DepositChannelLookup::<T, I>::insert(
+				&deposit_address,
+				DepositChannelDetails {
+					owner: owner.clone(),
+					opened_at,
+					expires_at,
+					deposit_channel: DepositChannel::generate_new::<
+						<T as Config<I>>::AddressDerivation,
+					>(1, BenchmarkValue::benchmark_value())
+					.unwrap(),
+					action: ChannelAction::<T::AccountId>::LiquidityProvision {
+						lp_account: owner.clone(),
+						refund_address: None,
+					},
+					boost_fee: 0,
+					boost_status: BoostStatus::NotBoosted,
+				},
+			);
+			Pallet::<T, I>::schedule_channel_recycling(recycle_height, deposit_address.clone());
+			deposit_addresses.push(deposit_address);
+		}
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T, I>::extend_deposit_channel_expiries(origin, 1u32.into()));
+		}
+
+		for deposit_address in deposit_addresses {
+			assert!(
+				DepositChannelLookup::<T, I>::get(deposit_address).unwrap().expires_at > expires_at
+			);
+		}
+	}
+
+	#[benchmark]
+	fn credit_abandoned_egress() {
+		let origin = T::EnsureGovernance::try_successful_origin().unwrap();
+		let account_id: T::AccountId = whitelisted_caller();
+		let egress_id = (<T as Config<I>>::TargetChain::get(), T::InstanceId::get(), 1);
+		AbandonedEgresses::<T, I>::insert(
+			egress_id,
+			AbandonedEgress {
+				asset: BenchmarkValue::benchmark_value(),
+				amount: 1_000u32.into(),
+				destination_address: BenchmarkValue::benchmark_value(),
+			},
+		);
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T, I>::credit_abandoned_egress(origin, egress_id, account_id));
+		}
+
+		assert!(!AbandonedEgresses::<T, I>::contains_key(egress_id));
+	}
+
 	#[cfg(test)]
 	use crate::mock_eth::*;
 
@@ -543,9 +879,15 @@ mod benchmarks {
 		new_test_ext().execute_with(|| {
 			_finalise_ingress::<Test, ()>(100, true);
 		});
+		new_test_ext().execute_with(|| {
+			_egress_scheduled_fetches::<Test, ()>(100, true);
+		});
 		new_test_ext().execute_with(|| {
 			_process_channel_deposit_full_witness::<Test, ()>(true);
 		});
+		new_test_ext().execute_with(|| {
+			_record_deposit_failure::<Test, ()>(true);
+		});
 		new_test_ext().execute_with(|| {
 			_disable_asset_egress::<Test, ()>(true);
 		});
@@ -576,5 +918,29 @@ mod benchmarks {
 		new_test_ext().execute_with(|| {
 			_mark_transaction_for_rejection::<Test, ()>(true);
 		});
+		new_test_ext().execute_with(|| {
+			_update_swap_channel::<Test, ()>(true);
+		});
+		new_test_ext().execute_with(|| {
+			_close_deposit_channel::<Test, ()>(true);
+		});
+		new_test_ext().execute_with(|| {
+			_report_reorg::<Test, ()>(true);
+		});
+		new_test_ext().execute_with(|| {
+			_start_deposit_address_verification::<Test, ()>(true);
+		});
+		new_test_ext().execute_with(|| {
+			_start_chain_wind_down::<Test, ()>(true);
+		});
+		new_test_ext().execute_with(|| {
+			_set_broker_minimum_deposit::<Test, ()>(true);
+		});
+		new_test_ext().execute_with(|| {
+			_extend_deposit_channel_expiries::<Test, ()>(100, true);
+		});
+		new_test_ext().execute_with(|| {
+			_credit_abandoned_egress::<Test, ()>(true);
+		});
 	}
 }
@@ -153,6 +153,8 @@ where
 {
 	pub unlocked_funds: Vec<(AccountId, C::ChainAmount)>,
 	pub amount_credited_to_boosters: C::ChainAmount,
+	/// The portion of the boosters' fees that is returned to the depositor.
+	pub fee_rebated: C::ChainAmount,
 }
 
 impl<AccountId, C: Chain> BoostPool<AccountId, C>
@@ -359,9 +361,29 @@ where
 		Ok(())
 	}
 
+	/// Credits the boosters with the funds they provided plus their fee, less `fee_rebate` of the
+	/// fee, which is returned to the depositor instead.
+	/// The amount that will be credited to boosters when the given boost is finalised, before
+	/// any fee rebate.
+	pub(crate) fn get_amount_owed_for_boost(
+		&self,
+		prewitnessed_deposit_id: PrewitnessedDepositId,
+	) -> C::ChainAmount {
+		self.pending_boosts
+			.get(&prewitnessed_deposit_id)
+			.map(|boost_contributions| {
+				boost_contributions.values().fold(ScaledAmount::<C>::from(0), |total, amount| {
+					total.saturating_add(amount.total)
+				})
+			})
+			.unwrap_or_default()
+			.into_chain_amount()
+	}
+
 	pub(crate) fn process_deposit_as_finalised(
 		&mut self,
 		prewitnessed_deposit_id: PrewitnessedDepositId,
+		fee_rebate: Percent,
 	) -> DepositFinalisationOutcomeForPool<AccountId, C> {
 		let Some(boost_contributions) = self.pending_boosts.remove(&prewitnessed_deposit_id) else {
 			// The deposit hadn't been boosted
@@ -370,8 +392,13 @@ where
 
 		let mut unlocked_funds = vec![];
 		let mut amount_credited: ScaledAmount<C> = 0.into();
+		let mut fee_rebated: ScaledAmount<C> = 0.into();
+
+		for (booster_id, mut amount) in boost_contributions {
+			let rebate = ScaledAmount::from_raw(fee_rebate * amount.fee.val);
+			amount.total.saturating_reduce(rebate);
+			fee_rebated.saturating_accrue(rebate);
 
-		for (booster_id, amount) in boost_contributions {
 			// Depending on whether the booster is withdrawing, add deposits to
 			// their free balance or back to the available boost pool:
 			if let Some(pending_deposits) = self.pending_withdrawals.get_mut(&booster_id) {
@@ -394,6 +421,7 @@ where
 		DepositFinalisationOutcomeForPool {
 			unlocked_funds,
 			amount_credited_to_boosters: amount_credited.into_chain_amount(),
+			fee_rebated: fee_rebated.into_chain_amount(),
 		}
 	}
 
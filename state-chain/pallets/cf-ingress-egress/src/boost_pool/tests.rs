@@ -16,6 +16,7 @@ const BOOST_1: PrewitnessedDepositId = 1;
 const BOOST_2: PrewitnessedDepositId = 2;
 
 const NO_DEDUCTION: Percent = Percent::from_percent(0);
+const NO_REBATE: Percent = Percent::from_percent(0);
 
 #[test]
 fn check_fee_math() {
@@ -171,9 +172,10 @@ fn boosting_with_fees() {
 	);
 
 	assert_eq!(
-		pool.process_deposit_as_finalised(BOOST_1),
+		pool.process_deposit_as_finalised(BOOST_1, NO_REBATE),
 		DepositFinalisationOutcomeForPool {
 			amount_credited_to_boosters: 1010,
+			fee_rebated: 0,
 			unlocked_funds: vec![]
 		}
 	);
@@ -181,6 +183,27 @@ fn boosting_with_fees() {
 	check_pool(&pool, [(BOOSTER_1, 1003), (BOOSTER_2, 2006)]);
 }
 
+#[test]
+fn finalising_with_fee_rebate() {
+	let mut pool = TestPool::new(100);
+
+	pool.add_funds(BOOSTER_1, 1000);
+
+	assert_eq!(pool.provide_funds_for_boosting(BOOST_1, 1010, NO_DEDUCTION), Ok((1010, 10)));
+
+	// Half of the fee is returned to the depositor rather than the booster:
+	assert_eq!(
+		pool.process_deposit_as_finalised(BOOST_1, Percent::from_percent(50)),
+		DepositFinalisationOutcomeForPool {
+			amount_credited_to_boosters: 1005,
+			fee_rebated: 5,
+			unlocked_funds: vec![]
+		}
+	);
+
+	check_pool(&pool, [(BOOSTER_1, 1005)]);
+}
+
 #[test]
 fn boosting_with_max_network_fee_deduction() {
 	const BOOST_FEE_BPS: u16 = 100;
@@ -211,9 +234,10 @@ fn boosting_with_max_network_fee_deduction() {
 	check_pending_boosts(&pool, [(BOOST_1, vec![(BOOSTER_1, PROVIDED_AMOUNT, 0)])]);
 
 	assert_eq!(
-		pool.process_deposit_as_finalised(BOOST_1),
+		pool.process_deposit_as_finalised(BOOST_1, NO_REBATE),
 		DepositFinalisationOutcomeForPool {
 			amount_credited_to_boosters: PROVIDED_AMOUNT,
+			fee_rebated: 0,
 			unlocked_funds: vec![]
 		}
 	);
@@ -275,9 +299,10 @@ fn boosting_with_fees_including_network_fee_portion() {
 	);
 
 	assert_eq!(
-		pool.process_deposit_as_finalised(BOOST_1),
+		pool.process_deposit_as_finalised(BOOST_1, NO_REBATE),
 		DepositFinalisationOutcomeForPool {
 			amount_credited_to_boosters: PROVIDED_AMOUNT + TOTAL_BOOSTERS_FEE,
+			fee_rebated: 0,
 			unlocked_funds: vec![]
 		}
 	);
@@ -318,9 +343,10 @@ fn adding_funds_during_pending_withdrawal_from_same_booster() {
 	// Booster 1 is no longer withdrawing, so pending funds go into available pool
 	// on finalisation:
 	assert_eq!(
-		pool.process_deposit_as_finalised(BOOST_1),
+		pool.process_deposit_as_finalised(BOOST_1, NO_REBATE),
 		DepositFinalisationOutcomeForPool {
 			amount_credited_to_boosters: DEPOSIT_AMOUNT,
+			fee_rebated: 0,
 			unlocked_funds: vec![]
 		}
 	);
@@ -341,9 +367,10 @@ fn withdrawing_funds_before_finalisation() {
 	check_pool(&pool, [(BOOSTER_2, 500)]);
 
 	assert_eq!(
-		pool.process_deposit_as_finalised(BOOST_1),
+		pool.process_deposit_as_finalised(BOOST_1, NO_REBATE),
 		DepositFinalisationOutcomeForPool {
 			amount_credited_to_boosters: 1000,
+			fee_rebated: 0,
 			unlocked_funds: vec![(BOOSTER_1, 500)]
 		}
 	);
@@ -368,9 +395,10 @@ fn adding_funds_with_pending_withdrawals() {
 	check_pool(&pool, [(BOOSTER_2, 500), (BOOSTER_3, 1000)]);
 
 	assert_eq!(
-		pool.process_deposit_as_finalised(BOOST_1),
+		pool.process_deposit_as_finalised(BOOST_1, NO_REBATE),
 		DepositFinalisationOutcomeForPool {
 			amount_credited_to_boosters: 1000,
+			fee_rebated: 0,
 			unlocked_funds: vec![(BOOSTER_1, 500)]
 		}
 	);
@@ -438,9 +466,10 @@ fn partially_losing_pending_withdrawals() {
 	// Booster 2's available boost amount:
 	{
 		assert_eq!(
-			pool.process_deposit_as_finalised(BOOST_1),
+			pool.process_deposit_as_finalised(BOOST_1, NO_REBATE),
 			DepositFinalisationOutcomeForPool {
 				amount_credited_to_boosters: 500,
+				fee_rebated: 0,
 				unlocked_funds: vec![(BOOSTER_1, 250)]
 			}
 		);
@@ -481,9 +510,10 @@ fn booster_joins_then_funds_lost() {
 	// Deposit of 500 is finalised. Importantly this doesn't affect Booster 3 as they
 	// didn't participate in the boost:
 	assert_eq!(
-		pool.process_deposit_as_finalised(BOOST_1),
+		pool.process_deposit_as_finalised(BOOST_1, NO_REBATE),
 		DepositFinalisationOutcomeForPool {
 			amount_credited_to_boosters: 500,
+			fee_rebated: 0,
 			unlocked_funds: vec![(BOOSTER_1, 250)]
 		}
 	);
@@ -529,9 +559,10 @@ fn booster_joins_between_boosts() {
 	// remaining 250 goes to Booster 2; Booster 3 joined after this boost, so they
 	// get nothing; there is only one pending boost now (Boost 2):
 	assert_eq!(
-		pool.process_deposit_as_finalised(BOOST_1),
+		pool.process_deposit_as_finalised(BOOST_1, NO_REBATE),
 		DepositFinalisationOutcomeForPool {
 			amount_credited_to_boosters: 500,
+			fee_rebated: 0,
 			unlocked_funds: vec![(BOOSTER_1, 250)]
 		},
 	);
@@ -552,9 +583,10 @@ fn booster_joins_between_boosts() {
 		// the contributed boosters:
 		let mut pool = pool.clone();
 		assert_eq!(
-			pool.process_deposit_as_finalised(BOOST_2),
+			pool.process_deposit_as_finalised(BOOST_2, NO_REBATE),
 			DepositFinalisationOutcomeForPool {
 				amount_credited_to_boosters: 1000,
+				fee_rebated: 0,
 				unlocked_funds: vec![]
 			}
 		);
@@ -579,9 +611,10 @@ fn small_rewards_accumulate() {
 		Ok((SMALL_DEPOSIT, 5))
 	);
 	assert_eq!(
-		pool.process_deposit_as_finalised(BOOST_1),
+		pool.process_deposit_as_finalised(BOOST_1, NO_REBATE),
 		DepositFinalisationOutcomeForPool {
 			amount_credited_to_boosters: SMALL_DEPOSIT,
+			fee_rebated: 0,
 			unlocked_funds: vec![]
 		}
 	);
@@ -597,9 +630,10 @@ fn small_rewards_accumulate() {
 			Ok((SMALL_DEPOSIT, 5))
 		);
 		assert_eq!(
-			pool.process_deposit_as_finalised(prewitnessed_deposit_id),
+			pool.process_deposit_as_finalised(prewitnessed_deposit_id, NO_REBATE),
 			DepositFinalisationOutcomeForPool {
 				amount_credited_to_boosters: SMALL_DEPOSIT,
+				fee_rebated: 0,
 				unlocked_funds: vec![]
 			}
 		);
@@ -629,9 +663,10 @@ fn use_max_available_amount() {
 	pool.add_funds(BOOSTER_1, 200);
 
 	assert_eq!(
-		pool.process_deposit_as_finalised(BOOST_1),
+		pool.process_deposit_as_finalised(BOOST_1, NO_REBATE),
 		DepositFinalisationOutcomeForPool {
 			amount_credited_to_boosters: 1_010_101,
+			fee_rebated: 0,
 			unlocked_funds: vec![]
 		}
 	);
@@ -26,32 +26,40 @@ use cf_chains::{
 		AddressConverter, AddressDerivationApi, AddressDerivationError, IntoForeignChainAddress,
 	},
 	assets::any::GetChainAssetMap,
-	ccm_checker::CcmValidityCheck,
-	AllBatch, AllBatchError, CcmAdditionalData, CcmChannelMetadata, CcmDepositMetadata, CcmMessage,
-	Chain, ChainCrypto, ChannelLifecycleHooks, ChannelRefundParametersDecoded, ConsolidateCall,
-	DepositChannel, DepositDetailsToTransactionInId, DepositOriginType, ExecutexSwapAndCall,
-	FetchAssetParams, ForeignChainAddress, IntoTransactionInIdForAnyChain, RejectCall, SwapOrigin,
-	TransferAssetParams,
+	ccm_checker::{
+		CcmValidityCheck, CcmValidityError, DecodedCcmAdditionalData,
+		VersionedSolanaCcmAdditionalData,
+	},
+	ccm_templating::{substitute_ccm_placeholder, CCM_OUTPUT_AMOUNT_PLACEHOLDER},
+	AllBatch, AllBatchError, BoundedDepositDetails, CcmAdditionalData, CcmChannelMetadata,
+	CcmDepositMetadata, CcmMessage, Chain, ChainCrypto, ChannelLifecycleHooks,
+	ChannelRefundParametersDecoded, ConsolidateCall, ConsolidationError, DepositChannel,
+	DepositDetailsHash, DepositDetailsToTransactionInId, DepositOriginType, EgressMemo,
+	ExecutexSwapAndCall, ExecutexSwapAndCallError, FetchAssetParams, ForeignChainAddress,
+	IntoTransactionInIdForAnyChain, RejectCall, SwapOrigin, TransferAssetParams, UniqueDepositId,
 };
 use cf_primitives::{
 	AccountRole, AffiliateShortId, Affiliates, Asset, AssetAmount, BasisPoints, Beneficiaries,
-	Beneficiary, BoostPoolTier, BroadcastId, ChannelId, DcaParameters, EgressCounter, EgressId,
-	EpochIndex, ForeignChain, GasAmount, PrewitnessedDepositId, SwapRequestId,
-	ThresholdSignatureRequestId, TransactionHash, SECONDS_PER_BLOCK,
+	Beneficiary, BlockNumber, BoostPoolTier, BroadcastId, ChannelId, DcaParameters, EgressCounter,
+	EgressId, EpochIndex, ForeignChain, GasAmount, IngressEgressInstanceId, PrewitnessedDepositId,
+	SwapRequestId, ThresholdSignatureRequestId, TrackingId, TransactionHash,
+	BASIS_POINTS_PER_MILLION, SECONDS_PER_BLOCK,
 };
 use cf_runtime_utilities::log_or_panic;
 use cf_traits::{
 	impl_pallet_safe_mode, AccountRoleRegistry, AdjustedFeeEstimationApi, AffiliateRegistry,
 	AssetConverter, AssetWithholding, BalanceApi, BoostApi, Broadcaster, Chainflip,
-	ChannelIdAllocator, DepositApi, EgressApi, EpochInfo, FeePayment,
-	FetchesTransfersLimitProvider, GetBlockHeight, IngressEgressFeeApi, IngressSink, IngressSource,
-	NetworkEnvironmentProvider, OnDeposit, PoolApi, ScheduledEgressDetails, SwapLimitsProvider,
-	SwapRequestHandler, SwapRequestType,
+	ChannelIdAllocator, DepositApi, DepositRiskScorer, EgressApi, EgressOrigin, EpochInfo,
+	FeePayment, FetchesTransfersLimitProvider, GetBlockHeight, IngressEgressFeeApi, IngressSink,
+	IngressSource, NetworkEnvironmentProvider, OnChainWoundDown, OnDeposit, PoolApi,
+	ScheduledEgressDetails, SwapLimitsProvider, SwapRequestHandler, SwapRequestType,
+	MAX_REJECTION_REQUESTS_EXPIRED_ON_DEREGISTRATION,
 };
 use frame_support::{
 	pallet_prelude::{OptionQuery, *},
 	sp_runtime::{traits::Zero, DispatchError, Permill, Saturating},
 	transactional,
+	weights::WeightMeter,
 };
 use frame_system::pallet_prelude::*;
 pub use pallet::*;
@@ -59,10 +67,16 @@ use scale_info::{
 	build::{Fields, Variants},
 	Path, Type,
 };
-use sp_runtime::{traits::UniqueSaturatedInto, Percent};
+use sp_runtime::{
+	traits::{AtLeast32BitUnsigned, UniqueSaturatedInto},
+	Percent,
+};
 use sp_std::{
 	boxed::Box,
-	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	collections::{
+		btree_map::{self, BTreeMap},
+		btree_set::BTreeSet,
+	},
 	marker::PhantomData,
 	vec,
 	vec::Vec,
@@ -70,6 +84,40 @@ use sp_std::{
 pub use weights::WeightInfo;
 
 const MARKED_TX_EXPIRATION_BLOCKS: u32 = 3600 / SECONDS_PER_BLOCK as u32;
+/// The number of blocks a finalised deposit is remembered for replay protection (7 days).
+const PROCESSED_DEPOSIT_RETENTION_BLOCKS: u32 = 7 * 24 * 3600 / SECONDS_PER_BLOCK as u32;
+/// The number of blocks a rejection refund transaction is remembered for (7 days).
+const PROTOCOL_REFUND_RETENTION_BLOCKS: u32 = 7 * 24 * 3600 / SECONDS_PER_BLOCK as u32;
+
+/// The maximum number of failed deposits kept in the deposit failure history.
+pub const MAX_DEPOSIT_FAILURE_HISTORY: u64 = 1_000;
+/// The default number of blocks a failed deposit is kept in the deposit failure history (1 day).
+const DEFAULT_DEPOSIT_FAILURE_RETENTION_PERIOD: u32 = 24 * 3600 / SECONDS_PER_BLOCK as u32;
+/// The default number of blocks a CCM can be deferred for before it expires (1 hour).
+const DEFAULT_CCM_DEFERRAL_TIMEOUT: u32 = 3600 / SECONDS_PER_BLOCK as u32;
+/// The default number of blocks before a refund address update requested by a channel owner
+/// takes effect (1 hour).
+const DEFAULT_REFUND_ADDRESS_UPDATE_DELAY: u32 = 3600 / SECONDS_PER_BLOCK as u32;
+/// The number of most recently observed reorgs that the recommended witness safety margin is
+/// based on.
+pub const MAX_OBSERVED_REORGS: usize = 100;
+/// A warning event is emitted every time UTXO consolidation has been skipped for this many
+/// consecutive blocks (1 hour).
+pub const CONSOLIDATION_SKIP_WARNING_THRESHOLD: u32 = 3600 / SECONDS_PER_BLOCK as u32;
+/// The maximum number of open deposit channels whose expiry can be extended at once.
+pub const MAX_EXTENDED_DEPOSIT_CHANNELS: u32 = 10_000;
+/// The maximum number of blocks of fee estimates a [FeeEstimationStrategy] can be based on.
+pub const MAX_FEE_ESTIMATE_SAMPLES: u32 = 100;
+/// The maximum number of deferred channel opens that are retried per block.
+pub const MAX_CHANNEL_OPEN_RETRIES_PER_BLOCK: usize = 10;
+
+/// Whether a channel open is requested for the first time, or is the retry of a deferred request
+/// whose channel opening fee has already been paid.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+enum ChannelOpenAttempt {
+	New,
+	Retry,
+}
 
 #[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
 pub enum BoostStatus<ChainAmount> {
@@ -117,6 +165,10 @@ pub enum FetchOrTransfer<C: Chain> {
 		asset: C::ChainAsset,
 		destination_address: C::ChainAccount,
 		amount: C::ChainAmount,
+		// The State Chain block at which the transfer was scheduled.
+		scheduled_at: BlockNumber,
+		memo: Option<EgressMemo>,
+		origin: EgressOrigin,
 	},
 }
 
@@ -127,22 +179,131 @@ impl<C: Chain> FetchOrTransfer<C> {
 			FetchOrTransfer::Transfer { asset, .. } => asset,
 		}
 	}
+
+	/// Fetches are not egresses and have no scheduling time, so they always sort first.
+	fn scheduled_at(&self) -> Option<BlockNumber> {
+		match self {
+			FetchOrTransfer::Fetch { .. } => None,
+			FetchOrTransfer::Transfer { scheduled_at, .. } => Some(*scheduled_at),
+		}
+	}
 }
 
+/// The reason a deposit could not be processed.
+///
+/// Each variant has a stable numeric code, which is also its SCALE index. Codes must never be
+/// changed or reused: new variants are given the next unused code. See
+/// [DEPOSIT_FAILED_REASON_CODES].
 #[derive(RuntimeDebug, Eq, PartialEq, Clone, Encode, Decode, TypeInfo)]
 pub enum DepositFailedReason {
+	#[codec(index = 0)]
 	BelowMinimumDeposit,
 	/// The deposit was ignored because the amount provided was not high enough to pay for the fees
 	/// required to process the requisite transactions.
+	#[codec(index = 1)]
 	NotEnoughToPayFees,
+	#[codec(index = 2)]
 	TransactionRejectedByBroker,
+	#[codec(index = 3)]
 	DepositWitnessRejected(DispatchError),
+	#[codec(index = 4)]
 	InvalidDestinationAddress,
+	#[codec(index = 5)]
 	InvalidBrokerFees,
+	#[codec(index = 6)]
 	InvalidRefundParameters,
+	#[codec(index = 7)]
 	InvalidDcaParameters,
+	#[codec(index = 8)]
 	CcmUnsupportedForTargetChain,
+	#[codec(index = 9)]
 	CcmInvalidMetadata,
+	/// The CCM message exceeds the maximum length configured for the destination chain.
+	#[codec(index = 10)]
+	CcmMessageTooLong,
+	/// The CCM gas budget exceeds the maximum configured for the destination chain.
+	#[codec(index = 11)]
+	CcmGasBudgetTooHigh,
+	/// The CCM additional data exceeds the maximum length configured for the destination chain.
+	#[codec(index = 12)]
+	CcmAdditionalDataTooLong,
+	/// The broker of the deposit has been suspended.
+	#[codec(index = 13)]
+	BrokerSuspended,
+	/// The CCM destination address is not on the broker's allowlist.
+	#[codec(index = 14)]
+	CcmDestinationNotAllowed,
+	/// The destination chain is being wound down and no longer accepts new swaps.
+	#[codec(index = 15)]
+	DestinationChainWindingDown,
+}
+
+/// The code, name and description of every [DepositFailedReason], ordered by code.
+pub const DEPOSIT_FAILED_REASON_CODES: &[(u8, &str, &str)] = &[
+	(0, "BelowMinimumDeposit", "The deposit amount is below the minimum deposit amount."),
+	(1, "NotEnoughToPayFees", "The deposit amount is not high enough to pay the ingress fee."),
+	(2, "TransactionRejectedByBroker", "The deposit transaction was rejected by the broker."),
+	(3, "DepositWitnessRejected", "The deposit could not be credited or swapped."),
+	(4, "InvalidDestinationAddress", "The destination address is not valid."),
+	(5, "InvalidBrokerFees", "The broker or affiliate fees are not valid."),
+	(6, "InvalidRefundParameters", "The refund parameters are not valid."),
+	(7, "InvalidDcaParameters", "The DCA parameters are not valid."),
+	(8, "CcmUnsupportedForTargetChain", "CCM is not supported for the destination chain."),
+	(9, "CcmInvalidMetadata", "The CCM metadata is not valid."),
+	(10, "CcmMessageTooLong", "The CCM message exceeds the maximum length."),
+	(11, "CcmGasBudgetTooHigh", "The CCM gas budget exceeds the maximum."),
+	(12, "CcmAdditionalDataTooLong", "The CCM additional data exceeds the maximum length."),
+	(13, "BrokerSuspended", "The broker of the deposit channel has been suspended."),
+	(14, "CcmDestinationNotAllowed", "The CCM destination address is not allowed by the broker."),
+	(15, "DestinationChainWindingDown", "The destination chain is being wound down."),
+];
+
+impl DepositFailedReason {
+	/// The stable numeric code of this reason.
+	pub fn code(&self) -> u8 {
+		match self {
+			DepositFailedReason::BelowMinimumDeposit => 0,
+			DepositFailedReason::NotEnoughToPayFees => 1,
+			DepositFailedReason::TransactionRejectedByBroker => 2,
+			DepositFailedReason::DepositWitnessRejected(_) => 3,
+			DepositFailedReason::InvalidDestinationAddress => 4,
+			DepositFailedReason::InvalidBrokerFees => 5,
+			DepositFailedReason::InvalidRefundParameters => 6,
+			DepositFailedReason::InvalidDcaParameters => 7,
+			DepositFailedReason::CcmUnsupportedForTargetChain => 8,
+			DepositFailedReason::CcmInvalidMetadata => 9,
+			DepositFailedReason::CcmMessageTooLong => 10,
+			DepositFailedReason::CcmGasBudgetTooHigh => 11,
+			DepositFailedReason::CcmAdditionalDataTooLong => 12,
+			DepositFailedReason::BrokerSuspended => 13,
+			DepositFailedReason::CcmDestinationNotAllowed => 14,
+			DepositFailedReason::DestinationChainWindingDown => 15,
+		}
+	}
+}
+
+impl From<CcmValidityError> for DepositFailedReason {
+	fn from(error: CcmValidityError) -> Self {
+		match error {
+			CcmValidityError::CcmIsTooLong => DepositFailedReason::CcmMessageTooLong,
+			CcmValidityError::CcmGasBudgetTooHigh => DepositFailedReason::CcmGasBudgetTooHigh,
+			CcmValidityError::CcmAdditionalDataTooLong =>
+				DepositFailedReason::CcmAdditionalDataTooLong,
+			_ => DepositFailedReason::CcmInvalidMetadata,
+		}
+	}
+}
+
+/// A problem with the parameters a broker encoded into a vault swap, which didn't prevent the
+/// swap from going ahead.
+#[derive(RuntimeDebug, Eq, PartialEq, Clone, Encode, Decode, TypeInfo)]
+pub enum VaultSwapParameterWarning {
+	/// The retry duration was above the maximum allowed, so the maximum is used instead.
+	RetryDurationClamped { encoded: BlockNumber, clamped_to: BlockNumber },
+	/// The min price is so far above the market price that it was most likely encoded the wrong
+	/// way round. The swap still goes ahead with the encoded min price, so it will be refunded
+	/// if the price can't be met.
+	MinPriceLikelyInverted,
 }
 
 enum FullWitnessDepositOutcome {
@@ -165,6 +326,7 @@ mod deposit_origin {
 		Vault {
 			tx_id: TransactionInIdFor<T, I>,
 			broker_id: Option<T::AccountId>,
+			verified_sender: Option<<T::TargetChain as Chain>::ChainAccount>,
 		},
 	}
 
@@ -183,8 +345,12 @@ mod deposit_origin {
 			}
 		}
 
-		pub fn vault(tx_id: TransactionInIdFor<T, I>, broker_id: Option<T::AccountId>) -> Self {
-			DepositOrigin::Vault { tx_id, broker_id }
+		pub fn vault(
+			tx_id: TransactionInIdFor<T, I>,
+			broker_id: Option<T::AccountId>,
+			verified_sender: Option<<T::TargetChain as Chain>::ChainAccount>,
+		) -> Self {
+			DepositOrigin::Vault { tx_id, broker_id, verified_sender }
 		}
 
 		pub fn broker_id(&self) -> Option<&T::AccountId> {
@@ -207,9 +373,16 @@ mod deposit_origin {
 	impl<T: Config<I>, I: 'static> From<DepositOrigin<T, I>> for SwapOrigin<T::AccountId> {
 		fn from(origin: DepositOrigin<T, I>) -> SwapOrigin<T::AccountId> {
 			match origin {
-				DepositOrigin::Vault { tx_id, broker_id } => SwapOrigin::Vault {
+				DepositOrigin::Vault { tx_id, broker_id, verified_sender } => SwapOrigin::Vault {
 					tx_id: tx_id.into_transaction_in_id_for_any_chain(),
 					broker_id,
+					verified_sender: verified_sender.map(|sender| {
+						T::AddressConverter::to_encoded_address(
+							<T::TargetChain as Chain>::ChainAccount::into_foreign_chain_address(
+								sender,
+							),
+						)
+					}),
 				},
 				DepositOrigin::DepositChannel {
 					deposit_address,
@@ -240,7 +413,7 @@ pub struct TransactionRejectionDetails<T: Config<I>, I: 'static> {
 	pub refund_address: Option<ForeignChainAddress>,
 	pub asset: TargetChainAsset<T, I>,
 	pub amount: TargetChainAmount<T, I>,
-	pub deposit_details: <T::TargetChain as Chain>::DepositDetails,
+	pub deposit_details: BoundedDepositDetails<T::TargetChain>,
 }
 
 /// Cross-chain messaging requests.
@@ -257,6 +430,9 @@ pub struct CrossChainMessage<C: Chain> {
 	// Where funds might be returned to if the message fails.
 	pub ccm_additional_data: CcmAdditionalData,
 	pub gas_budget: GasAmount,
+	// The State Chain block at which the message was scheduled.
+	pub scheduled_at: BlockNumber,
+	pub origin: EgressOrigin,
 }
 
 impl<C: Chain> CrossChainMessage<C> {
@@ -265,7 +441,215 @@ impl<C: Chain> CrossChainMessage<C> {
 	}
 }
 
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(20);
+/// Progress of an ongoing verification of the deposit channel addresses.
+#[derive(Clone, RuntimeDebug, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct DepositAddressVerificationProgress {
+	/// The raw storage key of the last channel checked, `None` if no channel has been checked yet.
+	pub cursor: Option<Vec<u8>>,
+	pub channels_checked: u32,
+	pub mismatches: u32,
+}
+
+/// The wind-down of the pallet's chain. Once started, no new deposit channels can be opened and
+/// no new swaps to the chain are accepted. Transfers and CCMs that are still scheduled when the
+/// grace period ends, or that are requested after it, are abandoned. The wind-down is completed
+/// once the remaining fetches have been sent.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct ChainWindDownStatus<BlockNumber> {
+	pub started_at: BlockNumber,
+	pub grace_period_ends_at: BlockNumber,
+	/// The number of transfers and CCMs abandoned since the grace period ended.
+	pub abandoned_egresses: u32,
+	pub completed: bool,
+}
+
+/// A transfer or CCM that was not sent because its chain was wound down. The funds remain in the
+/// vault until governance credits them to an account.
+#[derive(RuntimeDebug, Eq, PartialEq, Clone, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct AbandonedEgress<C: Chain> {
+	pub asset: C::ChainAsset,
+	pub amount: C::ChainAmount,
+	pub destination_address: C::ChainAccount,
+}
+
+/// A deposit channel whose address doesn't match the address derived for it from the current
+/// vault key.
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct DepositAddressMismatch<Address> {
+	pub channel_id: ChannelId,
+	/// `None` if no address could be derived for the channel.
+	pub derived_address: Option<Address>,
+}
+
+/// Cumulative totals of the amounts of an asset that passed through the pallet, for
+/// reconciliation against the balances held in the vault.
+#[derive(
+	RuntimeDebug, Copy, Clone, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+pub struct AssetAccounting<Amount> {
+	/// Finalised deposits that were accepted, before fees.
+	pub deposited: Amount,
+	/// Scheduled egresses, after fees.
+	pub egressed: Amount,
+	/// Ingress and egress fees withheld, in units of the asset they were withheld from.
+	pub fees_withheld: Amount,
+	/// Finalised deposits that were rejected and scheduled to be refunded.
+	pub rejected: Amount,
+}
+
+/// The number and total amount of the egresses of one origin.
+#[derive(
+	RuntimeDebug, Copy, Clone, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+pub struct EgressTotal<Amount> {
+	pub count: u64,
+	/// The amount egressed, after fees. Consolidations move funds within the vault, so they
+	/// don't add to it.
+	pub amount: Amount,
+}
+
+/// Tracks a CCM whose egress has been deferred because the data required to build it was not
+/// ready.
+#[derive(RuntimeDebug, Copy, Clone, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct CcmDeferral {
+	/// The number of times the egress has been deferred.
+	pub count: u32,
+	/// The State Chain block at which the egress was first deferred.
+	pub first_deferred_at: BlockNumber,
+}
+
+/// The number of egresses sent in a State Chain block, counted against the limits of the
+/// [FetchesTransfersLimitProvider].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct EgressUsage<BlockNumber> {
+	pub block_number: BlockNumber,
+	pub fetches: u32,
+	pub transfers: u32,
+	pub ccms: u32,
+}
+
+/// Limits the number of broadcasts initiated by the pallet at the end of each block. Budget that
+/// isn't used in a block is carried over to later blocks, up to `max_carry_over`.
+#[derive(Copy, Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct BroadcastBudgetSettings {
+	pub per_block: u32,
+	pub max_carry_over: u32,
+}
+
+/// The broadcasts initiated by the pallet at the end of a State Chain block.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct BroadcastUsage<BlockNumber> {
+	pub block_number: BlockNumber,
+	pub broadcasts: u32,
+	/// Whether broadcasts were held back because the budget was used up.
+	pub budget_exhausted: bool,
+}
+
+/// How the ingress and egress fees withheld by the pallet are derived from the fee estimates
+/// provided by chain tracking. Strategies other than `Latest` smooth out estimates on chains
+/// whose fees are bursty.
+#[derive(
+	Copy, Clone, RuntimeDebug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+pub enum FeeEstimationStrategy {
+	/// The latest estimate.
+	#[default]
+	Latest,
+	/// An exponential moving average of the estimates over the given number of blocks.
+	ExponentialMovingAverage { blocks: u32 },
+	/// The given percentile of the estimates over the given number of blocks.
+	Percentile { blocks: u32, percentile: Percent },
+}
+
+impl FeeEstimationStrategy {
+	/// The number of blocks of estimates that need to be kept for the strategy.
+	pub fn sample_window(&self) -> u32 {
+		match self {
+			FeeEstimationStrategy::Latest => 0,
+			FeeEstimationStrategy::ExponentialMovingAverage { blocks } |
+			FeeEstimationStrategy::Percentile { blocks, .. } => (*blocks).min(MAX_FEE_ESTIMATE_SAMPLES),
+		}
+	}
+
+	/// Applies the strategy to the previous estimates, oldest first, followed by the latest
+	/// estimate.
+	pub fn estimate<Amount: AtLeast32BitUnsigned + Copy>(
+		&self,
+		previous: impl IntoIterator<Item = Amount>,
+		latest: Amount,
+	) -> Amount {
+		let estimates = previous.into_iter().chain(core::iter::once(latest));
+		match self {
+			FeeEstimationStrategy::Latest => latest,
+			FeeEstimationStrategy::ExponentialMovingAverage { blocks } => {
+				// Smoothing factor of 2 / (blocks + 1):
+				let blocks = (*blocks).max(1);
+				estimates
+					.reduce(|average, estimate| {
+						estimate
+							.saturating_mul(2u32.into())
+							.saturating_add(average.saturating_mul((blocks - 1).into())) /
+							blocks.saturating_add(1).into()
+					})
+					.unwrap_or(latest)
+			},
+			FeeEstimationStrategy::Percentile { percentile, .. } => {
+				let mut estimates: Vec<_> = estimates.collect();
+				estimates.sort_unstable();
+				let rank = percentile.mul_ceil(estimates.len() as u32).max(1);
+				estimates[rank as usize - 1]
+			},
+		}
+	}
+}
+
+/// The ingress and egress fee estimates provided by chain tracking in a State Chain block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct FeeEstimateSample<Amount> {
+	pub ingress: Amount,
+	pub egress: Amount,
+}
+
+/// Cumulative totals of the ingress and egress fees estimated by the [FeeEstimationStrategy]
+/// for an asset, compared with what the latest chain tracking estimate would have been.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct FeeEstimationTelemetry<Amount> {
+	/// The number of fees estimated.
+	pub estimates: u32,
+	/// The fees estimated by the strategy, in units of the gas asset.
+	pub strategy_estimates: Amount,
+	/// The latest chain tracking estimates at the time, in units of the gas asset.
+	pub latest_estimates: Amount,
+}
+
+/// Tracks the broadcast budget while the pallet's broadcasts are initiated at the end of a block.
+struct BroadcastBudgetTracker {
+	/// `None` if the number of broadcasts is not limited.
+	remaining: Option<u32>,
+	initiated: u32,
+	exhausted: bool,
+}
+
+impl BroadcastBudgetTracker {
+	/// Whether the next step may initiate broadcasts. A step that initiates several broadcasts at
+	/// once, such as a batch split into several transactions, may overdraw the budget.
+	fn has_budget(&mut self) -> bool {
+		if self.remaining == Some(0) {
+			self.exhausted = true;
+			return false
+		}
+		true
+	}
+
+	fn record_broadcast(&mut self) {
+		if let Some(remaining) = self.remaining.as_mut() {
+			remaining.saturating_reduce(1);
+		}
+		self.initiated.saturating_accrue(1);
+	}
+}
+
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(31);
 
 impl_pallet_safe_mode! {
 	PalletSafeMode<I>;
@@ -273,6 +657,7 @@ impl_pallet_safe_mode! {
 	add_boost_funds_enabled,
 	stop_boosting_enabled,
 	deposits_enabled,
+	consolidation_enabled,
 }
 
 /// Calls to the external chains that has failed to be broadcast/accepted by the target chain.
@@ -287,6 +672,70 @@ pub struct FailedForeignChainCall {
 	pub original_epoch: EpochIndex,
 }
 
+impl FailedForeignChainCall {
+	/// The number of epochs after which the call is no longer re-signed and is removed from
+	/// storage.
+	pub const EXPIRY_EPOCHS: EpochIndex = 2;
+
+	/// The number of epoch transitions left before the call expires.
+	pub fn epochs_until_expiry(&self, current_epoch: EpochIndex) -> EpochIndex {
+		self.original_epoch
+			.saturating_add(Self::EXPIRY_EPOCHS)
+			.saturating_sub(current_epoch)
+	}
+}
+
+/// Settings for detecting a halt of the external chain. The chain is considered halted if the
+/// tracked block height has not progressed for `threshold` state chain blocks.
+#[derive(Copy, Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct ChainHaltDetectionSettings {
+	pub threshold: BlockNumber,
+	/// Whether fully witnessed deposits should be held back until the chain makes progress again.
+	pub pause_deposit_witnessing: bool,
+}
+
+/// The bounds within which the witness safety margin is automatically adjusted to the depth of the
+/// observed reorgs.
+#[derive(Copy, Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct WitnessSafetyMarginBounds<BlockNumber> {
+	pub min: BlockNumber,
+	pub max: BlockNumber,
+}
+
+/// How much the fully witnessed amount of a boosted deposit may differ from the boosted amount
+/// for the boost to still be finalised.
+#[derive(Copy, Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum BoostAmountTolerance<ChainAmount> {
+	Absolute(ChainAmount),
+	/// Relative to the boosted amount.
+	BasisPoints(BasisPoints),
+}
+
+/// Settings for rebating part of the boost fee to the depositor when a boosted deposit is
+/// finalised quickly, since the boosters were exposed to the deposit for less time than expected.
+#[derive(Copy, Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct BoostFeeRebateSettings<BlockNumber> {
+	/// The maximum number of external chain blocks between the prewitness and the finalisation of
+	/// a deposit for it to qualify for the rebate.
+	pub fast_window: BlockNumber,
+	/// The portion of the boosters' fee that is rebated.
+	pub rebate: Percent,
+}
+
+/// When prewitnessed deposits are processed relative to the full witnesses that arrive in the
+/// same block.
+#[derive(
+	Copy, Clone, Default, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen,
+)]
+pub enum PrewitnessProcessingOrder {
+	/// Prewitnesses are processed as soon as they are received.
+	#[default]
+	Immediate,
+	/// Prewitnesses are processed at the end of the block, after all full witnesses, so that
+	/// deposits that are fully witnessed in the same block are not boosted.
+	EndOfBlock,
+}
+
 #[derive(
 	CloneNoBound, RuntimeDebugNoBound, PartialEqNoBound, EqNoBound, Encode, Decode, MaxEncodedLen,
 )]
@@ -308,6 +757,90 @@ pub enum PalletConfigUpdate<T: Config<I>, I: 'static> {
 	SetNetworkFeeDeductionFromBoost {
 		deduction_percent: Percent,
 	},
+	/// Set the number of blocks that failed deposits are kept in the deposit failure history.
+	SetDepositFailureRetentionPeriod {
+		blocks: BlockNumber,
+	},
+	/// Set the maximum risk score of deposits that the boost pools of the given tier are allowed
+	/// to boost. `None` removes the threshold.
+	SetBoostRiskThreshold {
+		tier: BoostPoolTier,
+		threshold: Option<BasisPoints>,
+	},
+	/// Set the maximum total amount of the given asset that can be boosted but not yet
+	/// finalised. `None` removes the cap.
+	SetBoostExposureCap {
+		asset: TargetChainAsset<T, I>,
+		cap: Option<TargetChainAmount<T, I>>,
+	},
+	/// Set the chain halt detection settings. `None` disables chain halt detection.
+	SetChainHaltDetection {
+		settings: Option<ChainHaltDetectionSettings>,
+	},
+	/// Set the number of blocks a CCM can be deferred for before it expires.
+	SetCcmDeferralTimeout {
+		blocks: BlockNumber,
+	},
+	/// Set the bounds for automatically adjusting the witness safety margin. `None` disables the
+	/// automatic adjustment.
+	SetWitnessSafetyMarginBounds {
+		bounds: Option<WitnessSafetyMarginBounds<TargetChainBlockNumber<T, I>>>,
+	},
+	/// Set the number of blocks during which vault swaps with identical parameters are merged
+	/// into the first swap request. Zero disables aggregation.
+	SetVaultSwapAggregationWindow {
+		blocks: BlockNumber,
+	},
+	/// Set how much the witnessed amount of a boosted deposit may differ from the boosted amount
+	/// for the boost to be finalised. `None` requires the amounts to match exactly.
+	SetBoostAmountTolerance {
+		tolerance: Option<BoostAmountTolerance<TargetChainAmount<T, I>>>,
+	},
+	/// Set whether prewitnesses are processed immediately or deferred until the full witnesses
+	/// of the same block have been processed.
+	SetPrewitnessProcessingOrder {
+		order: PrewitnessProcessingOrder,
+	},
+	/// Set the number of blocks before a refund address update requested by a channel owner
+	/// takes effect.
+	SetRefundAddressUpdateDelay {
+		blocks: BlockNumber,
+	},
+	/// Set the boost fee rebate for deposits that are finalised quickly. `None` disables the
+	/// rebate.
+	SetBoostFeeRebate {
+		settings: Option<BoostFeeRebateSettings<TargetChainBlockNumber<T, I>>>,
+	},
+	/// Set the maximum number of deposit channels that accounts with the given role can have
+	/// open at the same time. `None` removes the limit.
+	SetMaxOpenChannels {
+		role: AccountRole,
+		max_channels: Option<u32>,
+	},
+	/// Set the multiple of the current estimated ingress fee below which deposits are rejected,
+	/// if this is higher than the configured minimum deposit. `None` disables the scaling.
+	SetMinimumDepositIngressFeeMultiplier {
+		multiplier: Option<u32>,
+	},
+	/// Set the budget of broadcasts initiated at the end of each block. `None` removes the
+	/// limit.
+	SetBroadcastBudget {
+		settings: Option<BroadcastBudgetSettings>,
+	},
+	/// Set how the ingress and egress fees withheld by the pallet are estimated.
+	SetFeeEstimationStrategy {
+		strategy: FeeEstimationStrategy,
+	},
+	/// Set the number of blocks between attempts to consolidate the vault's funds. Zero attempts
+	/// consolidation in every block.
+	SetConsolidationInterval {
+		blocks: BlockNumber,
+	},
+	/// Set the number of blocks for which channel opens that failed due to a transient address
+	/// derivation error are retried. Zero disables retries.
+	SetChannelOpenRetryPeriod {
+		blocks: BlockNumber,
+	},
 }
 
 macro_rules! append_chain_to_name {
@@ -357,6 +890,99 @@ where
 						v.index(3).fields(
 							Fields::named().field(|f| f.ty::<Percent>().name("deduction_percent")),
 						)
+					})
+					.variant("SetDepositFailureRetentionPeriod", |v| {
+						v.index(4)
+							.fields(Fields::named().field(|f| f.ty::<BlockNumber>().name("blocks")))
+					})
+					.variant("SetBoostRiskThreshold", |v| {
+						v.index(5).fields(
+							Fields::named()
+								.field(|f| f.ty::<BoostPoolTier>().name("tier"))
+								.field(|f| f.ty::<Option<BasisPoints>>().name("threshold")),
+						)
+					})
+					.variant(append_chain_to_name!(SetBoostExposureCap), |v| {
+						v.index(6).fields(
+							Fields::named()
+								.field(|f| f.ty::<TargetChainAsset<T, I>>().name("asset"))
+								.field(|f| f.ty::<Option<TargetChainAmount<T, I>>>().name("cap")),
+						)
+					})
+					.variant("SetChainHaltDetection", |v| {
+						v.index(7).fields(Fields::named().field(|f| {
+							f.ty::<Option<ChainHaltDetectionSettings>>().name("settings")
+						}))
+					})
+					.variant("SetCcmDeferralTimeout", |v| {
+						v.index(8)
+							.fields(Fields::named().field(|f| f.ty::<BlockNumber>().name("blocks")))
+					})
+					.variant(append_chain_to_name!(SetWitnessSafetyMarginBounds), |v| {
+						v.index(9).fields(Fields::named().field(|f| {
+							f.ty::<Option<WitnessSafetyMarginBounds<TargetChainBlockNumber<T, I>>>>(
+							)
+							.name("bounds")
+						}))
+					})
+					.variant("SetVaultSwapAggregationWindow", |v| {
+						v.index(10)
+							.fields(Fields::named().field(|f| f.ty::<BlockNumber>().name("blocks")))
+					})
+					.variant(append_chain_to_name!(SetBoostAmountTolerance), |v| {
+						v.index(11).fields(Fields::named().field(|f| {
+							f.ty::<Option<BoostAmountTolerance<TargetChainAmount<T, I>>>>()
+								.name("tolerance")
+						}))
+					})
+					.variant("SetPrewitnessProcessingOrder", |v| {
+						v.index(12).fields(
+							Fields::named()
+								.field(|f| f.ty::<PrewitnessProcessingOrder>().name("order")),
+						)
+					})
+					.variant("SetRefundAddressUpdateDelay", |v| {
+						v.index(13)
+							.fields(Fields::named().field(|f| f.ty::<BlockNumber>().name("blocks")))
+					})
+					.variant(append_chain_to_name!(SetBoostFeeRebate), |v| {
+						v.index(14).fields(Fields::named().field(|f| {
+							f.ty::<Option<BoostFeeRebateSettings<TargetChainBlockNumber<T, I>>>>()
+								.name("settings")
+						}))
+					})
+					.variant("SetMaxOpenChannels", |v| {
+						v.index(15).fields(
+							Fields::named()
+								.field(|f| f.ty::<AccountRole>().name("role"))
+								.field(|f| f.ty::<Option<u32>>().name("max_channels")),
+						)
+					})
+					.variant("SetMinimumDepositIngressFeeMultiplier", |v| {
+						v.index(16).fields(
+							Fields::named().field(|f| f.ty::<Option<u32>>().name("multiplier")),
+						)
+					})
+					.variant("SetBroadcastBudget", |v| {
+						v.index(17).fields(
+							Fields::named().field(|f| {
+								f.ty::<Option<BroadcastBudgetSettings>>().name("settings")
+							}),
+						)
+					})
+					.variant("SetFeeEstimationStrategy", |v| {
+						v.index(18).fields(
+							Fields::named()
+								.field(|f| f.ty::<FeeEstimationStrategy>().name("strategy")),
+						)
+					})
+					.variant("SetConsolidationInterval", |v| {
+						v.index(19)
+							.fields(Fields::named().field(|f| f.ty::<BlockNumber>().name("blocks")))
+					})
+					.variant("SetChannelOpenRetryPeriod", |v| {
+						v.index(20)
+							.fields(Fields::named().field(|f| f.ty::<BlockNumber>().name("blocks")))
 					}),
 			)
 	}
@@ -374,9 +1000,6 @@ pub mod pallet {
 	use sp_runtime::{Percent, SaturatedConversion};
 	use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
-	pub(crate) type ChannelRecycleQueue<T, I> =
-		Vec<(TargetChainBlockNumber<T, I>, TargetChainAccount<T, I>)>;
-
 	pub type TargetChainAsset<T, I> = <<T as Config<I>>::TargetChain as Chain>::ChainAsset;
 	pub(crate) type TargetChainAccount<T, I> =
 		<<T as Config<I>>::TargetChain as Chain>::ChainAccount;
@@ -387,6 +1010,9 @@ pub mod pallet {
 	pub type TransactionInIdFor<T, I> =
 		<<<T as Config<I>>::TargetChain as Chain>::ChainCrypto as ChainCrypto>::TransactionInId;
 
+	pub(crate) type ProcessedDepositKey<T, I> =
+		(TargetChainAccount<T, I>, UniqueDepositId, TargetChainBlockNumber<T, I>);
+
 	#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 	pub struct DepositWitness<C: Chain> {
 		pub deposit_address: C::ChainAccount,
@@ -414,6 +1040,10 @@ pub mod pallet {
 		pub refund_params: Option<ChannelRefundParametersDecoded>,
 		pub dca_params: Option<DcaParameters>,
 		pub boost_fee: BasisPoints,
+		pub tracking_id: Option<TrackingId>,
+		/// The sender of the deposit, for chains on which engines can verify it when witnessing
+		/// (e.g. the signer of a Solana vault swap).
+		pub verified_sender: Option<TargetChainAccount<T, I>>,
 	}
 
 	#[derive(
@@ -425,6 +1055,88 @@ pub mod pallet {
 		Vault { vault_witness: Box<VaultDepositWitness<T, I>> },
 	}
 
+	/// A prewitness whose processing has been deferred until the end of the block.
+	#[derive(
+		CloneNoBound, RuntimeDebugNoBound, PartialEqNoBound, EqNoBound, Encode, Decode, TypeInfo,
+	)]
+	#[scale_info(skip_type_params(T, I))]
+	pub enum PendingPrewitness<T: Config<I>, I: 'static> {
+		DepositChannel {
+			deposit_witness: DepositWitness<T::TargetChain>,
+			block_height: TargetChainBlockNumber<T, I>,
+		},
+		Vault {
+			vault_witness: Box<VaultDepositWitness<T, I>>,
+			block_height: TargetChainBlockNumber<T, I>,
+		},
+	}
+
+	impl<T: Config<I>, I: 'static> DepositFailedDetails<T, I> {
+		pub fn deposit_address(&self) -> Option<&TargetChainAccount<T, I>> {
+			match self {
+				DepositFailedDetails::DepositChannel { deposit_witness } =>
+					Some(&deposit_witness.deposit_address),
+				DepositFailedDetails::Vault { vault_witness } =>
+					vault_witness.deposit_address.as_ref(),
+			}
+		}
+
+		pub fn tx_id(&self) -> Option<TransactionInIdFor<T, I>> {
+			match self {
+				DepositFailedDetails::DepositChannel { deposit_witness } =>
+					deposit_witness.deposit_details.deposit_id(),
+				DepositFailedDetails::Vault { vault_witness } => Some(vault_witness.tx_id.clone()),
+			}
+		}
+
+		pub fn asset_and_amount(&self) -> (TargetChainAsset<T, I>, TargetChainAmount<T, I>) {
+			match self {
+				DepositFailedDetails::DepositChannel { deposit_witness } =>
+					(deposit_witness.asset, deposit_witness.amount),
+				DepositFailedDetails::Vault { vault_witness } =>
+					(vault_witness.input_asset, vault_witness.deposit_amount),
+			}
+		}
+	}
+
+	/// A failed deposit, as recorded in the deposit failure history. Only what is needed to look
+	/// up the deposit is kept, rather than the full [DepositFailedDetails] of the `DepositFailed`
+	/// event, so that the size of each entry is bounded.
+	#[derive(
+		CloneNoBound, RuntimeDebugNoBound, PartialEqNoBound, EqNoBound, Encode, Decode, TypeInfo,
+	)]
+	#[scale_info(skip_type_params(T, I))]
+	pub struct DepositFailure<T: Config<I>, I: 'static> {
+		pub block_height: TargetChainBlockNumber<T, I>,
+		pub reason: DepositFailedReason,
+		pub deposit_address: Option<TargetChainAccount<T, I>>,
+		pub tx_id: Option<TransactionInIdFor<T, I>>,
+		pub asset: TargetChainAsset<T, I>,
+		pub amount: TargetChainAmount<T, I>,
+		/// The State Chain block at which the failure was recorded.
+		pub recorded_at: BlockNumber,
+	}
+
+	impl<T: Config<I>, I: 'static> DepositFailure<T, I> {
+		pub fn new(
+			block_height: TargetChainBlockNumber<T, I>,
+			reason: DepositFailedReason,
+			details: &DepositFailedDetails<T, I>,
+			recorded_at: BlockNumber,
+		) -> Self {
+			let (asset, amount) = details.asset_and_amount();
+			DepositFailure {
+				block_height,
+				reason,
+				deposit_address: details.deposit_address().cloned(),
+				tx_id: details.tx_id(),
+				asset,
+				amount,
+				recorded_at,
+			}
+		}
+	}
+
 	#[derive(CloneNoBound, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
 	#[scale_info(skip_type_params(T, I))]
 	pub struct DepositChannelDetails<T: Config<I>, I: 'static> {
@@ -445,6 +1157,22 @@ pub mod pallet {
 		pub boost_status: BoostStatus<TargetChainAmount<T, I>>,
 	}
 
+	/// A channel open that failed because its deposit address could not be derived yet.
+	#[derive(
+		CloneNoBound, RuntimeDebugNoBound, PartialEqNoBound, EqNoBound, Encode, Decode, TypeInfo,
+	)]
+	#[scale_info(skip_type_params(T, I))]
+	pub struct PendingChannelOpen<T: Config<I>, I: 'static> {
+		pub requester: T::AccountId,
+		pub source_asset: TargetChainAsset<T, I>,
+		pub action: ChannelAction<T::AccountId>,
+		pub boost_fee: BasisPoints,
+		/// The last State Chain block at which the channel open is retried.
+		pub retry_until: BlockNumberFor<T>,
+		/// The channel opening fee paid by the requester. Refunded if the channel can't be opened.
+		pub channel_opening_fee: T::Amount,
+	}
+
 	pub enum IngressOrEgress {
 		Ingress,
 		Egress,
@@ -466,11 +1194,27 @@ pub mod pallet {
 			channel_metadata: Option<CcmChannelMetadata>,
 			refund_params: Option<ChannelRefundParametersDecoded>,
 			dca_params: Option<DcaParameters>,
+			tracking_id: Option<TrackingId>,
+			egress_memo: Option<EgressMemo>,
 		},
 		LiquidityProvision {
 			lp_account: AccountId,
 			refund_address: Option<ForeignChainAddress>,
 		},
+		/// Only used for vault swaps that failed validation for a refundable reason.
+		Refund {
+			refund_address: ForeignChainAddress,
+			reason: DepositFailedReason,
+		},
+	}
+
+	impl<AccountId> ChannelAction<AccountId> {
+		pub fn tracking_id(&self) -> Option<TrackingId> {
+			match self {
+				ChannelAction::Swap { tracking_id, .. } => *tracking_id,
+				ChannelAction::LiquidityProvision { .. } | ChannelAction::Refund { .. } => None,
+			}
+		}
 	}
 
 	/// Contains identifying information about the particular actions that have occurred for a
@@ -493,6 +1237,16 @@ pub mod pallet {
 			// Optional since we only swap if the amount is non-zero
 			network_fee_swap_request_id: Option<SwapRequestId>,
 		},
+		Refund {
+			reason: DepositFailedReason,
+			amount: TargetChainAmount<T, I>,
+			// Optional since the refund can't be egressed if it's below the dust limit
+			egress_id: Option<EgressId>,
+		},
+		/// The deposit was added to an earlier vault swap request with identical parameters.
+		SwapToppedUp {
+			swap_request_id: SwapRequestId,
+		},
 	}
 
 	#[pallet::genesis_config]
@@ -548,6 +1302,11 @@ pub mod pallet {
 		/// Marks which chain this pallet is interacting with.
 		type TargetChain: Chain + Get<ForeignChain>;
 
+		/// Identifies this instance among the instances of this pallet that target the same chain.
+		/// It is part of every [EgressId] issued by this instance, so it must be unique per chain.
+		#[pallet::constant]
+		type InstanceId: Get<IngressEgressInstanceId>;
+
 		/// Generates deposit addresses.
 		type AddressDerivation: AddressDerivationApi<Self::TargetChain>;
 
@@ -609,6 +1368,12 @@ pub mod pallet {
 
 		#[pallet::constant]
 		type AllowTransactionReports: Get<bool>;
+
+		/// Assesses the risk of pre-witnessed deposits before they are boosted.
+		type DepositRiskScorer: DepositRiskScorer<Self::TargetChain>;
+
+		/// Called once the wind-down of the chain has completed.
+		type OnChainWoundDown: OnChainWoundDown;
 	}
 
 	/// Lookup table for addresses to corresponding deposit channels.
@@ -652,6 +1417,35 @@ pub mod pallet {
 	pub type ScheduledEgressCcm<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, Vec<CrossChainMessage<T::TargetChain>>, ValueQuery>;
 
+	/// The total amount of each asset that is scheduled for egress, as a transfer or a CCM, and
+	/// not yet broadcast.
+	#[pallet::storage]
+	pub type PendingEgressAmounts<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TargetChainAsset<T, I>, TargetChainAmount<T, I>, ValueQuery>;
+
+	/// Cumulative totals of the egresses scheduled for each origin and asset. Consolidations and
+	/// rejections are counted once they are broadcast.
+	#[pallet::storage]
+	pub type EgressTotals<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EgressOrigin,
+		Twox64Concat,
+		TargetChainAsset<T, I>,
+		EgressTotal<TargetChainAmount<T, I>>,
+		ValueQuery,
+	>;
+
+	/// Cumulative deposit, egress, fee and rejection totals for each asset.
+	#[pallet::storage]
+	pub type AssetAccountingTotals<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		TargetChainAsset<T, I>,
+		AssetAccounting<TargetChainAmount<T, I>>,
+		ValueQuery,
+	>;
+
 	/// Stores the list of assets that are not allowed to be egressed.
 	#[pallet::storage]
 	pub type DisabledEgressAssets<T: Config<I>, I: 'static = ()> =
@@ -691,24 +1485,99 @@ pub mod pallet {
 	pub type FailedForeignChainCalls<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Twox64Concat, EpochIndex, Vec<FailedForeignChainCall>, ValueQuery>;
 
+	/// Indexes the deposit channels by owner, so they can be looked up without iterating over all
+	/// open channels.
 	#[pallet::storage]
-	pub type DepositChannelRecycleBlocks<T: Config<I>, I: 'static = ()> =
-		StorageValue<_, ChannelRecycleQueue<T, I>, ValueQuery>;
+	pub type DepositChannelsByOwner<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		TargetChainAccount<T, I>,
+		(),
+		OptionQuery,
+	>;
 
-	// Determines the number of block confirmations is required for a block on
-	// an external chain before CFE can submit any witness extrinsics for it.
+	/// The addresses of deposit channels that can be recycled once the target chain reaches the
+	/// given block height.
 	#[pallet::storage]
-	#[pallet::getter(fn witness_safety_margin)]
-	pub type WitnessSafetyMargin<T: Config<I>, I: 'static = ()> =
-		StorageValue<_, TargetChainBlockNumber<T, I>, OptionQuery>;
+	pub type DepositChannelRecycleQueue<T: Config<I>, I: 'static = ()> = CountedStorageMap<
+		_,
+		Twox64Concat,
+		TargetChainBlockNumber<T, I>,
+		Vec<TargetChainAccount<T, I>>,
+		ValueQuery,
+	>;
 
-	/// The fixed fee charged for opening a channel, in Flipperinos.
+	/// The number of consecutive blocks in which UTXO consolidation could not be performed, and
+	/// the reason it failed most recently. Cleared once consolidation succeeds or is no longer
+	/// required.
 	#[pallet::storage]
-	#[pallet::getter(fn channel_opening_fee)]
-	pub type ChannelOpeningFee<T: Config<I>, I: 'static = ()> =
-		StorageValue<_, T::Amount, ValueQuery>;
+	pub type SkippedConsolidations<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, (u32, ConsolidationError), OptionQuery>;
 
-	/// Stores the latest prewitnessed deposit id used.
+	/// The number of blocks between attempts to consolidate the vault's funds. Zero attempts
+	/// consolidation in every block.
+	#[pallet::storage]
+	pub type ConsolidationInterval<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BlockNumber, ValueQuery>;
+
+	/// The number of blocks for which channel opens that failed due to a transient address
+	/// derivation error are retried. Zero disables retries.
+	#[pallet::storage]
+	pub type ChannelOpenRetryPeriod<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BlockNumber, ValueQuery>;
+
+	/// Channel opens that are waiting for their deposit address to become derivable.
+	#[pallet::storage]
+	pub type PendingChannelOpens<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u64, PendingChannelOpen<T, I>, OptionQuery>;
+
+	/// The id of the most recently deferred channel open.
+	#[pallet::storage]
+	pub type PendingChannelOpenIdCounter<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u64, ValueQuery>;
+
+	/// The raw storage key of the last pending channel open that was retried. Retries continue
+	/// from the next request, so that every request is retried regardless of the queue length.
+	#[pallet::storage]
+	pub type PendingChannelOpenCursor<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, Vec<u8>, OptionQuery>;
+
+	/// The lowest block height in the recycle queue that has not been processed yet.
+	#[pallet::storage]
+	pub type NextRecycleBlockHeight<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, TargetChainBlockNumber<T, I>, OptionQuery>;
+
+	// Determines the number of block confirmations is required for a block on
+	// an external chain before CFE can submit any witness extrinsics for it.
+	#[pallet::storage]
+	#[pallet::getter(fn witness_safety_margin)]
+	pub type WitnessSafetyMargin<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, TargetChainBlockNumber<T, I>, OptionQuery>;
+
+	/// The most recently observed reorgs of the external chain, as (block height, depth), oldest
+	/// first.
+	#[pallet::storage]
+	pub type ObservedReorgs<T: Config<I>, I: 'static = ()> = StorageValue<
+		_,
+		Vec<(TargetChainBlockNumber<T, I>, TargetChainBlockNumber<T, I>)>,
+		ValueQuery,
+	>;
+
+	/// If set, the witness safety margin is automatically adjusted to the recommended margin,
+	/// within these bounds.
+	#[pallet::storage]
+	pub type WitnessSafetyMarginAutoTuning<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, WitnessSafetyMarginBounds<TargetChainBlockNumber<T, I>>, OptionQuery>;
+
+	/// The fixed fee charged for opening a channel, in Flipperinos.
+	#[pallet::storage]
+	#[pallet::getter(fn channel_opening_fee)]
+	pub type ChannelOpeningFee<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, T::Amount, ValueQuery>;
+
+	/// Stores the latest prewitnessed deposit id used.
 	#[pallet::storage]
 	pub type PrewitnessedDepositIdCounter<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, PrewitnessedDepositId, ValueQuery>;
@@ -726,6 +1595,59 @@ pub mod pallet {
 			OptionQuery,
 		>;
 
+	/// Finalised channel deposits, keyed by deposit address, unique deposit id and block height.
+	/// Used to make sure that the same deposit is never credited twice.
+	#[pallet::storage]
+	pub(crate) type ProcessedDeposits<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, ProcessedDepositKey<T, I>, (), OptionQuery>;
+
+	/// A queue of the processed deposits in the order they were recorded, keyed by a sequential
+	/// id, together with the block at which each of them is forgotten.
+	#[pallet::storage]
+	pub(crate) type ProcessedDepositsQueue<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		u64,
+		(BlockNumberFor<T>, ProcessedDepositKey<T, I>),
+		OptionQuery,
+	>;
+
+	/// The ids of the oldest entry and the next entry in the processed deposits queue.
+	#[pallet::storage]
+	pub(crate) type ProcessedDepositsQueueRange<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, (u64, u64), ValueQuery>;
+
+	/// Fully witnessed vault swap transactions, keyed by the epoch in which they were processed.
+	/// Witnesses can be re-submitted under the new epoch during a rotation, so both the current
+	/// and the previous epoch are checked for duplicates. Older epochs are cleared in `on_idle`.
+	#[pallet::storage]
+	pub(crate) type ProcessedVaultSwaps<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EpochIndex,
+		Twox64Concat,
+		TransactionInIdFor<T, I>,
+		(),
+		OptionQuery,
+	>;
+
+	/// The oldest epoch whose processed vault swaps may not have been cleared yet.
+	#[pallet::storage]
+	pub(crate) type OldestUnclearedVaultSwapEpoch<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, EpochIndex, OptionQuery>;
+
+	/// Transactions by which rejected deposits were refunded, keyed by the id they are witnessed
+	/// with if they land in one of our deposit channels. Such deposits have already paid the
+	/// ingress fee once, so they are not charged again.
+	#[pallet::storage]
+	pub(crate) type ProtocolRefundTransactions<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TransactionInIdFor<T, I>, (), OptionQuery>;
+
+	/// Stores the block number at which rejection refund transactions are forgotten.
+	#[pallet::storage]
+	pub(crate) type ProtocolRefundTransactionsExpireAt<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<TransactionInIdFor<T, I>>, ValueQuery>;
+
 	/// Stores the block number when the report expires to gather with the reporter and the tx_id.
 	#[pallet::storage]
 	pub(crate) type ReportExpiresAt<T: Config<I>, I: 'static = ()> = StorageMap<
@@ -736,6 +1658,18 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// Swap requests started by boosted deposits, with the broker and transaction id of the
+	/// deposit, so that the swap output can be withheld from the destination if the deposit is
+	/// marked for rejection before the swap request completes.
+	#[pallet::storage]
+	pub(crate) type BoostedSwapRequests<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		SwapRequestId,
+		(T::AccountId, TransactionInIdFor<T, I>),
+		OptionQuery,
+	>;
+
 	/// Stores the details of transactions that are scheduled for rejecting.
 	#[pallet::storage]
 	pub(crate) type ScheduledTransactionsForRejection<T: Config<I>, I: 'static = ()> =
@@ -746,6 +1680,17 @@ pub mod pallet {
 	pub(crate) type FailedRejections<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, Vec<TransactionRejectionDetails<T, I>>, ValueQuery>;
 
+	/// The full deposit details of transactions marked for rejection whose details were too large
+	/// to be stored in [TransactionRejectionDetails].
+	#[pallet::storage]
+	pub type PrunedDepositDetails<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Identity,
+		DepositDetailsHash,
+		<T::TargetChain as Chain>::DepositDetails,
+		OptionQuery,
+	>;
+
 	/// Stores transaction ids that have been boosted but have not yet been finalised.
 	#[pallet::storage]
 	pub(crate) type BoostedVaultTransactions<T: Config<I>, I: 'static = ()> = StorageMap<
@@ -761,6 +1706,285 @@ pub mod pallet {
 	pub type NetworkFeeDeductionFromBoostPercent<T: Config<I>, I: 'static = ()> =
 		StorageValue<_, Percent, ValueQuery>;
 
+	#[pallet::type_value]
+	pub fn DefaultDepositFailureRetentionPeriod() -> BlockNumber {
+		DEFAULT_DEPOSIT_FAILURE_RETENTION_PERIOD
+	}
+
+	/// The number of blocks that failed deposits are kept in the deposit failure history.
+	#[pallet::storage]
+	pub type DepositFailureRetentionPeriod<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BlockNumber, ValueQuery, DefaultDepositFailureRetentionPeriod>;
+
+	/// A ring buffer of recently failed deposits, keyed by a sequential id.
+	#[pallet::storage]
+	pub type DepositFailureHistory<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, u64, DepositFailure<T, I>, OptionQuery>;
+
+	/// The ids of the oldest entry and the next entry in the deposit failure history.
+	#[pallet::storage]
+	pub type DepositFailureHistoryRange<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, (u64, u64), ValueQuery>;
+
+	/// The maximum risk score of deposits that the boost pools of each tier are allowed to boost.
+	/// Tiers without an entry boost deposits regardless of their risk score.
+	#[pallet::storage]
+	pub type BoostRiskThresholds<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, BoostPoolTier, BasisPoints, OptionQuery>;
+
+	/// The maximum total amount of each asset that can be boosted but not yet finalised. Assets
+	/// without an entry are not capped.
+	#[pallet::storage]
+	pub type BoostExposureCaps<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TargetChainAsset<T, I>, TargetChainAmount<T, I>, OptionQuery>;
+
+	/// The total amount of each asset that has been boosted but not yet finalised or lost.
+	#[pallet::storage]
+	pub type BoostExposure<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TargetChainAsset<T, I>, TargetChainAmount<T, I>, ValueQuery>;
+
+	/// How much the witnessed amount of a boosted deposit may differ from the boosted amount for
+	/// the boost to be finalised. If not set, the amounts must match exactly. A shortfall must
+	/// also be covered by the network fee taken from the boost.
+	#[pallet::storage]
+	pub type BoostFinalisationTolerance<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoostAmountTolerance<TargetChainAmount<T, I>>, OptionQuery>;
+
+	/// The boost fee rebate for deposits that are finalised quickly. Disabled if not set.
+	#[pallet::storage]
+	pub type BoostFeeRebate<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoostFeeRebateSettings<TargetChainBlockNumber<T, I>>, OptionQuery>;
+
+	/// The number of deposit channels each account currently has open.
+	#[pallet::storage]
+	pub type OpenChannelCount<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The maximum number of deposit channels that accounts with the given role can have open at
+	/// the same time. No limit if not set.
+	#[pallet::storage]
+	pub type MaxOpenChannels<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, AccountRole, u32, OptionQuery>;
+
+	/// The minimum deposit of each asset is raised to at least this multiple of the current
+	/// estimated ingress fee. If not set, only the configured minimum deposit applies.
+	#[pallet::storage]
+	pub type MinimumDepositIngressFeeMultiplier<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, OptionQuery>;
+
+	/// Minimum deposits set by brokers for their own deposit channels. They can only be higher
+	/// than the configured minimum deposit.
+	#[pallet::storage]
+	pub type BrokerMinimumDeposits<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Identity,
+		T::AccountId,
+		Twox64Concat,
+		TargetChainAsset<T, I>,
+		TargetChainAmount<T, I>,
+		OptionQuery,
+	>;
+
+	/// Assets whose minimum deposit is currently raised above the configured minimum because of
+	/// high ingress fees.
+	#[pallet::storage]
+	pub type DynamicMinimumDepositActive<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, TargetChainAsset<T, I>, (), OptionQuery>;
+
+	/// The external chain block height at which boosted deposits were prewitnessed. Only recorded
+	/// while the boost fee rebate is enabled.
+	#[pallet::storage]
+	pub type BoostedDepositPrewitnessHeights<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		PrewitnessedDepositId,
+		TargetChainBlockNumber<T, I>,
+		OptionQuery,
+	>;
+
+	/// Whether prewitnesses are processed immediately or at the end of the block.
+	#[pallet::storage]
+	pub type PrewitnessOrder<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, PrewitnessProcessingOrder, ValueQuery>;
+
+	/// Prewitnesses received in the current block that will be processed at the end of it.
+	#[pallet::storage]
+	pub(crate) type PendingPrewitnesses<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, Vec<PendingPrewitness<T, I>>, ValueQuery>;
+
+	/// Transaction ids of the deposits that have been fully witnessed in the current block.
+	/// Prewitnesses for these deposits are ignored.
+	#[pallet::storage]
+	pub(crate) type FullyWitnessedThisBlock<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, Vec<TransactionInIdFor<T, I>>, ValueQuery>;
+
+	/// Settings for detecting a halt of the external chain. Detection is disabled if not set.
+	#[pallet::storage]
+	pub type ChainHaltDetection<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, ChainHaltDetectionSettings, OptionQuery>;
+
+	/// The last tracked block height of the external chain, and the state chain block at which
+	/// it was first observed.
+	#[pallet::storage]
+	pub type LastChainProgress<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, (TargetChainBlockNumber<T, I>, BlockNumberFor<T>), OptionQuery>;
+
+	/// Whether the external chain is considered halted. While halted, deposit channels are not
+	/// recycled.
+	#[pallet::storage]
+	pub type ChainHalted<T: Config<I>, I: 'static = ()> = StorageValue<_, bool, ValueQuery>;
+
+	/// Fully witnessed deposits that are held back while the external chain is halted, along with
+	/// the block height they were witnessed at. They are processed once the chain makes progress.
+	#[pallet::storage]
+	pub type DeferredDepositWitnesses<T: Config<I>, I: 'static = ()> = StorageValue<
+		_,
+		Vec<(Vec<DepositWitness<T::TargetChain>>, TargetChainBlockNumber<T, I>)>,
+		ValueQuery,
+	>;
+
+	#[pallet::type_value]
+	pub fn DefaultCcmDeferralTimeout() -> BlockNumber {
+		DEFAULT_CCM_DEFERRAL_TIMEOUT
+	}
+
+	/// The number of blocks a CCM can be deferred for before it expires and its funds are sent to
+	/// the fallback address instead.
+	#[pallet::storage]
+	pub type CcmDeferralTimeout<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BlockNumber, ValueQuery, DefaultCcmDeferralTimeout>;
+
+	/// Scheduled CCMs whose egress has been deferred because the data required to build them was
+	/// not ready.
+	#[pallet::storage]
+	pub type CcmDeferrals<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, EgressId, CcmDeferral, OptionQuery>;
+
+	#[pallet::type_value]
+	pub fn DefaultRefundAddressUpdateDelay() -> BlockNumber {
+		DEFAULT_REFUND_ADDRESS_UPDATE_DELAY
+	}
+
+	/// The number of blocks before a refund address update requested by a channel owner takes
+	/// effect. Zero applies updates immediately.
+	#[pallet::storage]
+	pub type RefundAddressUpdateDelay<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BlockNumber, ValueQuery, DefaultRefundAddressUpdateDelay>;
+
+	/// Refund address updates waiting for the safety delay to pass, keyed by deposit address.
+	/// Stores the id of the channel the update was requested for, the new refund address and the
+	/// block at which the update is applied.
+	#[pallet::storage]
+	pub type PendingRefundAddressUpdates<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		TargetChainAccount<T, I>,
+		(ChannelId, ForeignChainAddress, BlockNumberFor<T>),
+		OptionQuery,
+	>;
+
+	/// The deposit addresses whose pending refund address updates are due at each block.
+	#[pallet::storage]
+	pub(crate) type RefundAddressUpdatesDueAt<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<TargetChainAccount<T, I>>, ValueQuery>;
+
+	/// The number of blocks during which vault swaps with identical parameters are merged into the
+	/// first swap request. Zero disables aggregation.
+	#[pallet::storage]
+	pub type VaultSwapAggregationWindow<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BlockNumber, ValueQuery>;
+
+	/// Vault swap requests that can still be topped up, keyed by the hash of their swap
+	/// parameters, along with the block at which they can no longer be topped up.
+	#[pallet::storage]
+	pub(crate) type AggregatableVaultSwaps<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Identity, [u8; 32], (SwapRequestId, BlockNumberFor<T>), OptionQuery>;
+
+	/// Stores the block number at which aggregatable vault swaps are forgotten.
+	#[pallet::storage]
+	pub(crate) type AggregatableVaultSwapsExpireAt<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<[u8; 32]>, ValueQuery>;
+
+	/// The egresses sent in the most recent block in which anything was egressed.
+	#[pallet::storage]
+	pub type LastEgressUsage<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, EgressUsage<BlockNumberFor<T>>, ValueQuery>;
+
+	/// The budget of broadcasts initiated at the end of each block. Unlimited if not set.
+	#[pallet::storage]
+	pub type BroadcastBudget<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BroadcastBudgetSettings, OptionQuery>;
+
+	/// Broadcast budget left unused in previous blocks.
+	#[pallet::storage]
+	pub type CarriedOverBroadcastBudget<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, u32, ValueQuery>;
+
+	/// The broadcasts initiated in the most recent block in which anything was broadcast or the
+	/// budget was used up.
+	#[pallet::storage]
+	pub type LastBroadcastUsage<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BroadcastUsage<BlockNumberFor<T>>, ValueQuery>;
+
+	/// How the ingress and egress fees withheld by the pallet are estimated.
+	#[pallet::storage]
+	pub type FeeEstimation<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, FeeEstimationStrategy, ValueQuery>;
+
+	/// The fee estimates of the most recent blocks for each asset, oldest first, as far back as
+	/// needed by the [FeeEstimationStrategy].
+	#[pallet::storage]
+	pub type FeeEstimateSamples<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		TargetChainAsset<T, I>,
+		BoundedVec<FeeEstimateSample<TargetChainAmount<T, I>>, ConstU32<MAX_FEE_ESTIMATE_SAMPLES>>,
+		ValueQuery,
+	>;
+
+	/// How the fees estimated by the [FeeEstimationStrategy] compare with the latest estimates,
+	/// per asset. Only recorded while a strategy other than `Latest` is in use.
+	#[pallet::storage]
+	pub type FeeEstimationTelemetryTotals<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		TargetChainAsset<T, I>,
+		FeeEstimationTelemetry<TargetChainAmount<T, I>>,
+		ValueQuery,
+	>;
+
+	/// The progress of the ongoing stepped migration, if any.
+	#[pallet::storage]
+	pub type SteppedMigrationInProgress<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, migrations::stepped_migration::SteppedMigrationProgress, OptionQuery>;
+
+	/// The wind-down of the chain, if it has been started.
+	#[pallet::storage]
+	pub type ChainWindDown<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, ChainWindDownStatus<BlockNumberFor<T>>, OptionQuery>;
+
+	/// Transfers and CCMs abandoned because of the chain's wind-down, awaiting settlement by
+	/// governance.
+	#[pallet::storage]
+	pub type AbandonedEgresses<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Twox64Concat, EgressId, AbandonedEgress<T::TargetChain>, OptionQuery>;
+
+	/// The progress of the ongoing verification of the deposit channel addresses, if any.
+	#[pallet::storage]
+	pub type DepositAddressVerificationInProgress<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, DepositAddressVerificationProgress, OptionQuery>;
+
+	/// The deposit channels flagged by the most recent verification of the deposit channel
+	/// addresses, by deposit address.
+	#[pallet::storage]
+	pub type DepositAddressMismatches<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		TargetChainAccount<T, I>,
+		DepositAddressMismatch<TargetChainAccount<T, I>>,
+		OptionQuery,
+	>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -777,6 +2001,7 @@ pub mod pallet {
 			action: DepositAction<T, I>,
 			channel_id: Option<ChannelId>,
 			origin_type: DepositOriginType,
+			tracking_id: Option<TrackingId>,
 		},
 		AssetEgressStatusChanged {
 			asset: TargetChainAsset<T, I>,
@@ -785,6 +2010,7 @@ pub mod pallet {
 		CcmBroadcastRequested {
 			broadcast_id: BroadcastId,
 			egress_id: EgressId,
+			origin: EgressOrigin,
 		},
 		CcmEgressInvalid {
 			egress_id: EgressId,
@@ -797,6 +2023,14 @@ pub mod pallet {
 		BatchBroadcastRequested {
 			broadcast_id: BroadcastId,
 			egress_ids: Vec<EgressId>,
+			/// Why each of the transfers in the batch was scheduled.
+			egress_origins: Vec<(EgressId, EgressOrigin)>,
+		},
+		/// The number of State Chain blocks each egress spent waiting in the queue before being
+		/// included in a broadcast.
+		EgressWaitTimes {
+			broadcast_id: BroadcastId,
+			wait_times: Vec<(EgressId, BlockNumber)>,
 		},
 		MinimumDepositSet {
 			asset: TargetChainAsset<T, I>,
@@ -834,6 +2068,11 @@ pub mod pallet {
 		UtxoConsolidation {
 			broadcast_id: BroadcastId,
 		},
+		/// UTXO consolidation has been skipped for a number of consecutive blocks.
+		UtxoConsolidationSkipped {
+			consecutive_skips: u32,
+			reason: ConsolidationError,
+		},
 		FailedToBuildAllBatchCall {
 			error: AllBatchError,
 		},
@@ -859,6 +2098,7 @@ pub mod pallet {
 			boost_fee: TargetChainAmount<T, I>,
 			action: DepositAction<T, I>,
 			origin_type: DepositOriginType,
+			tracking_id: Option<TrackingId>,
 		},
 		BoostFundsAdded {
 			booster_id: T::AccountId,
@@ -898,12 +2138,19 @@ pub mod pallet {
 			account_id: T::AccountId,
 			tx_id: TransactionInIdFor<T, I>,
 		},
+		DuplicateDepositIgnored {
+			deposit_address: TargetChainAccount<T, I>,
+			asset: TargetChainAsset<T, I>,
+			amount: TargetChainAmount<T, I>,
+			deposit_details: <T::TargetChain as Chain>::DepositDetails,
+			block_height: TargetChainBlockNumber<T, I>,
+		},
 		TransactionRejectedByBroker {
 			broadcast_id: BroadcastId,
-			tx_id: <T::TargetChain as Chain>::DepositDetails,
+			tx_id: BoundedDepositDetails<T::TargetChain>,
 		},
 		TransactionRejectionFailed {
-			tx_id: <T::TargetChain as Chain>::DepositDetails,
+			tx_id: BoundedDepositDetails<T::TargetChain>,
 		},
 		UnknownBroker {
 			broker_id: T::AccountId,
@@ -915,95 +2162,417 @@ pub mod pallet {
 		NetworkFeeDeductionFromBoostSet {
 			deduction_percent: Percent,
 		},
-	}
-
-	#[derive(CloneNoBound, PartialEqNoBound, EqNoBound)]
-	#[pallet::error]
-	pub enum Error<T, I = ()> {
-		/// The deposit address is not valid. It may have expired or may never have been issued.
-		InvalidDepositAddress,
-		/// A deposit was made using the wrong asset.
-		AssetMismatch,
-		/// Channel ID has reached maximum
-		ChannelIdsExhausted,
-		/// Polkadot's Vault Account does not exist in storage.
-		MissingPolkadotVault,
-		/// Bitcoin's Vault key does not exist for the current epoch.
-		MissingBitcoinVault,
-		/// Channel ID is too large for Bitcoin address derivation
-		BitcoinChannelIdTooLarge,
-		/// The amount is below the minimum egress amount.
-		BelowEgressDustLimit,
-		/// Solana address derivation error.
-		SolanaAddressDerivationError,
-		/// Solana's Environment variables cannot be loaded via the SolanaEnvironment.
-		MissingSolanaApiEnvironment,
-		/// You cannot add 0 to a boost pool.
-		AddBoostAmountMustBeNonZero,
-		/// Adding boost funds is disabled due to safe mode.
-		AddBoostFundsDisabled,
-		/// Retrieving boost funds disabled due to safe mode.
-		StopBoostingDisabled,
-		/// Cannot create a boost pool if it already exists.
-		BoostPoolAlreadyExists,
-		/// Cannot create a boost pool of 0 bps
-		InvalidBoostPoolTier,
-		/// Disabled due to safe mode for the chain
-		DepositChannelCreationDisabled,
-		/// The specified boost pool does not exist.
-		BoostPoolDoesNotExist,
-		/// CCM parameters from a vault swap failed validity check.
-		InvalidCcm,
-		/// Unsupported chain
-		UnsupportedChain,
-		/// Transaction cannot be reported after being pre-witnessed or boosted.
-		TransactionAlreadyPrewitnessed,
-	}
-
-	#[pallet::hooks]
-	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
-		/// Recycle addresses if we can
-		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
-			let mut used_weight = Weight::zero();
-
-			// Approximate weight calculation: r/w DepositChannelLookup + w DepositChannelPool
-			let recycle_weight_per_address =
-				frame_support::weights::constants::ParityDbWeight::get().reads_writes(1, 2);
-
-			let maximum_addresses_to_recycle = remaining_weight
-				.ref_time()
-				.checked_div(recycle_weight_per_address.ref_time())
-				.unwrap_or_default()
-				.saturated_into::<usize>();
-
-			// In some instances, like Solana, the channel lifetime is managed by the electoral
-			// system.
-			if T::MANAGE_CHANNEL_LIFETIME {
-				let addresses_to_recycle =
-					DepositChannelRecycleBlocks::<T, I>::mutate(|recycle_queue| {
-						if recycle_queue.is_empty() {
-							vec![]
-						} else {
-							Self::take_recyclable_addresses(
-								recycle_queue,
-								maximum_addresses_to_recycle,
-								T::ChainTracking::get_block_height(),
-							)
-						}
-					});
+		DepositFailureRetentionPeriodSet {
+			blocks: BlockNumber,
+		},
+		BoostRiskThresholdSet {
+			tier: BoostPoolTier,
+			threshold: Option<BasisPoints>,
+		},
+		BoostExposureCapSet {
+			asset: TargetChainAsset<T, I>,
+			cap: Option<TargetChainAmount<T, I>>,
+		},
+		ChainHaltDetectionSet {
+			settings: Option<ChainHaltDetectionSettings>,
+		},
+		/// The tracked block height of the external chain has not progressed since
+		/// `last_progress_at`. Channel recycling is paused until the chain makes progress.
+		ChainHaltDetected {
+			block_height: TargetChainBlockNumber<T, I>,
+			last_progress_at: BlockNumberFor<T>,
+		},
+		/// The external chain is no longer considered halted. Any deferred deposits have been
+		/// processed.
+		ChainHaltEnded {
+			block_height: TargetChainBlockNumber<T, I>,
+			deferred_deposits_processed: u32,
+		},
+		/// Fully witnessed deposits were held back because the external chain is halted.
+		DepositWitnessesDeferred {
+			block_height: TargetChainBlockNumber<T, I>,
+			deposit_count: u32,
+		},
+		BoostSkippedDueToExposureCap {
+			prewitnessed_deposit_id: PrewitnessedDepositId,
+			asset: TargetChainAsset<T, I>,
+			amount_attempted: TargetChainAmount<T, I>,
+			current_exposure: TargetChainAmount<T, I>,
+			cap: TargetChainAmount<T, I>,
+			channel_id: Option<ChannelId>,
+			origin_type: DepositOriginType,
+		},
+		BoostSkippedDueToRisk {
+			prewitnessed_deposit_id: PrewitnessedDepositId,
+			asset: TargetChainAsset<T, I>,
+			amount_attempted: TargetChainAmount<T, I>,
+			risk_score: BasisPoints,
+			channel_id: Option<ChannelId>,
+			origin_type: DepositOriginType,
+		},
+		SwapChannelUpdated {
+			deposit_address: TargetChainAccount<T, I>,
+			channel_id: ChannelId,
+			broker_id: T::AccountId,
+			broker_fees: Beneficiaries<T::AccountId>,
+			destination_address: ForeignChainAddress,
+		},
+		DepositChannelClosed {
+			deposit_address: TargetChainAccount<T, I>,
+			channel_id: ChannelId,
+			broker_id: T::AccountId,
+			expires_at: TargetChainBlockNumber<T, I>,
+		},
+		CcmDeferralTimeoutSet {
+			blocks: BlockNumber,
+		},
+		VaultSwapAggregationWindowSet {
+			blocks: BlockNumber,
+		},
+		SteppedMigrationCompleted {
+			migration_id: Vec<u8>,
+			steps: u32,
+		},
+		ReorgObserved {
+			block_height: TargetChainBlockNumber<T, I>,
+			depth: TargetChainBlockNumber<T, I>,
+		},
+		WitnessSafetyMarginBoundsSet {
+			bounds: Option<WitnessSafetyMarginBounds<TargetChainBlockNumber<T, I>>>,
+		},
+		WitnessSafetyMarginChanged {
+			old_margin: Option<TargetChainBlockNumber<T, I>>,
+			new_margin: TargetChainBlockNumber<T, I>,
+		},
+		/// The egress of a CCM has been deferred because the data required to build it was not
+		/// ready. It will be retried in the following blocks.
+		CcmEgressDeferred {
+			egress_id: EgressId,
+		},
+		/// A deferred CCM has expired and will not be sent. If a fallback address is available,
+		/// the funds are sent there instead.
+		CcmEgressExpired {
+			egress_id: EgressId,
+			deferral: CcmDeferral,
+			fallback_egress: Option<ScheduledEgressDetails<T::TargetChain>>,
+		},
+		/// A witnessed deposit amount is larger than is plausible given the asset's decimals,
+		/// which usually indicates a decimal mismatch. The deposit is processed as normal.
+		ImplausibleDepositAmount {
+			deposit_address: Option<TargetChainAccount<T, I>>,
+			asset: TargetChainAsset<T, I>,
+			amount: TargetChainAmount<T, I>,
+			block_height: TargetChainBlockNumber<T, I>,
+		},
+		/// A vault swap was witnessed that was sent to a broker's private channel.
+		PrivateChannelVaultSwapWitnessed {
+			channel_id: ChannelId,
+			broker_id: T::AccountId,
+			tx_id: TransactionInIdFor<T, I>,
+			asset: TargetChainAsset<T, I>,
+			amount: TargetChainAmount<T, I>,
+			block_height: TargetChainBlockNumber<T, I>,
+		},
+		BoostAmountToleranceSet {
+			tolerance: Option<BoostAmountTolerance<TargetChainAmount<T, I>>>,
+		},
+		PrewitnessProcessingOrderSet {
+			order: PrewitnessProcessingOrder,
+		},
+		RefundAddressUpdateDelaySet {
+			blocks: BlockNumber,
+		},
+		/// The owner of a channel requested an update of its refund address, which will be
+		/// applied at `apply_at`.
+		RefundAddressUpdateScheduled {
+			deposit_address: TargetChainAccount<T, I>,
+			channel_id: ChannelId,
+			owner: T::AccountId,
+			refund_address: ForeignChainAddress,
+			apply_at: BlockNumberFor<T>,
+		},
+		RefundAddressUpdated {
+			deposit_address: TargetChainAccount<T, I>,
+			channel_id: ChannelId,
+			owner: T::AccountId,
+			previous_refund_address: Option<ForeignChainAddress>,
+			refund_address: ForeignChainAddress,
+		},
+		/// A prewitness was ignored because the deposit had already been fully witnessed in the
+		/// same block.
+		BoostSkippedDueToFullWitness {
+			tx_id: TransactionInIdFor<T, I>,
+			block_height: TargetChainBlockNumber<T, I>,
+		},
+		/// A boost was finalised with a witnessed amount above the boosted amount, and the surplus
+		/// was passed on to the depositor. A shortfall is instead deducted from the network fee
+		/// taken from the boost, as reported in `DepositFinalised`.
+		BoostedDepositAmountReconciled {
+			prewitnessed_deposit_id: PrewitnessedDepositId,
+			asset: TargetChainAsset<T, I>,
+			boosted_amount: TargetChainAmount<T, I>,
+			deposit_amount: TargetChainAmount<T, I>,
+			surplus_action: DepositAction<T, I>,
+		},
+		BoostFeeRebateSet {
+			settings: Option<BoostFeeRebateSettings<TargetChainBlockNumber<T, I>>>,
+		},
+		/// Part of the boost fee was returned to the depositor because the deposit was finalised
+		/// within the fast window after being prewitnessed. Any surplus from reconciling the
+		/// deposit amount is paid out with the rebate, as part of the same `action`.
+		BoostFeeRebated {
+			prewitnessed_deposit_id: PrewitnessedDepositId,
+			asset: TargetChainAsset<T, I>,
+			prewitness_block_height: TargetChainBlockNumber<T, I>,
+			finalisation_block_height: TargetChainBlockNumber<T, I>,
+			amount: TargetChainAmount<T, I>,
+			action: DepositAction<T, I>,
+		},
+		MaxOpenChannelsSet {
+			role: AccountRole,
+			max_channels: Option<u32>,
+		},
+		MinimumDepositIngressFeeMultiplierSet {
+			multiplier: Option<u32>,
+		},
+		/// The minimum deposit of the asset has been raised above the configured minimum because
+		/// of high ingress fees.
+		DynamicMinimumDepositActivated {
+			asset: TargetChainAsset<T, I>,
+			minimum_deposit: TargetChainAmount<T, I>,
+		},
+		/// The minimum deposit of the asset is back to the configured minimum.
+		DynamicMinimumDepositDeactivated {
+			asset: TargetChainAsset<T, I>,
+		},
+		DepositAddressVerificationStarted,
+		/// The address of an open deposit channel doesn't match the address derived for it from
+		/// the current vault key.
+		DepositAddressMismatchDetected {
+			deposit_address: TargetChainAccount<T, I>,
+			channel_id: ChannelId,
+			derived_address: Option<TargetChainAccount<T, I>>,
+		},
+		DepositAddressVerificationCompleted {
+			channels_checked: u32,
+			mismatches: u32,
+		},
+		/// A vault swap that was already processed was witnessed again, for example when the
+		/// witness was re-submitted during an epoch rotation. It's ignored.
+		DuplicateVaultSwapWitnessIgnored {
+			tx_id: TransactionInIdFor<T, I>,
+			block_height: TargetChainBlockNumber<T, I>,
+		},
+		/// The refund of a failed CCM to its fallback address was not sent because the amount
+		/// doesn't cover the egress fee. Whatever is left after the fee is added to the withheld
+		/// fees, so `egress_fee` and `residual_withheld` add up to `amount`.
+		CcmRefundBelowFeesIgnored {
+			asset: TargetChainAsset<T, I>,
+			amount: TargetChainAmount<T, I>,
+			destination_address: TargetChainAccount<T, I>,
+			egress_fee: TargetChainAmount<T, I>,
+			residual_withheld: TargetChainAmount<T, I>,
+		},
+		ChainWindDownStarted {
+			grace_period_ends_at: BlockNumberFor<T>,
+		},
+		/// An egress was not sent because it was still scheduled when the grace period of the
+		/// chain's wind-down ended, or was requested after it. It's kept in [AbandonedEgresses]
+		/// until governance credits it to an account.
+		EgressAbandonedOnWindDown {
+			egress_id: EgressId,
+			asset: TargetChainAsset<T, I>,
+			amount: TargetChainAmount<T, I>,
+			destination_address: TargetChainAccount<T, I>,
+		},
+		/// The funds of an abandoned egress were credited to an account.
+		AbandonedEgressCredited {
+			egress_id: EgressId,
+			account_id: T::AccountId,
+			asset: TargetChainAsset<T, I>,
+			amount: TargetChainAmount<T, I>,
+		},
+		ChainWindDownCompleted {
+			abandoned_egresses: u32,
+		},
+		BroadcastBudgetSet {
+			settings: Option<BroadcastBudgetSettings>,
+		},
+		/// Broadcasts were held back until a later block because the broadcast budget was used
+		/// up.
+		BroadcastBudgetExhausted {
+			broadcasts_initiated: u32,
+		},
+		BrokerMinimumDepositSet {
+			broker_id: T::AccountId,
+			asset: TargetChainAsset<T, I>,
+			minimum_deposit: Option<TargetChainAmount<T, I>>,
+		},
+		FeeEstimationStrategySet {
+			strategy: FeeEstimationStrategy,
+		},
+		DepositChannelExpiriesExtended {
+			extended_by: TargetChainBlockNumber<T, I>,
+			channels: u32,
+		},
+		/// The parameters of a vault swap were adjusted or look wrong. The broker that encoded
+		/// the swap is included, if known, so that it can fix its encoding.
+		VaultSwapParametersFlagged {
+			tx_id: TransactionInIdFor<T, I>,
+			broker_id: Option<T::AccountId>,
+			warning: VaultSwapParameterWarning,
+		},
+		ConsolidationIntervalSet {
+			blocks: BlockNumber,
+		},
+		ChannelOpenRetryPeriodSet {
+			blocks: BlockNumber,
+		},
+		/// The deposit address could not be derived yet. The channel open is retried until
+		/// `retry_until`. The channel opening fee has already been paid.
+		DepositChannelOpenDeferred {
+			request_id: u64,
+			requester: T::AccountId,
+			source_asset: TargetChainAsset<T, I>,
+			error: DispatchError,
+			retry_until: BlockNumberFor<T>,
+		},
+		/// A deferred channel open succeeded and the channel is ready to receive deposits.
+		DeferredDepositChannelOpened {
+			request_id: u64,
+			requester: T::AccountId,
+			channel_id: ChannelId,
+			deposit_address: TargetChainAccount<T, I>,
+			expiry_block: TargetChainBlockNumber<T, I>,
+		},
+		/// A deferred channel open failed, or its address could not be derived within the retry
+		/// period. The channel opening fee is refunded to the requester.
+		DeferredDepositChannelOpenFailed {
+			request_id: u64,
+			requester: T::AccountId,
+			error: DispatchError,
+			refunded_fee: T::Amount,
+		},
+	}
 
-				// Add weight for the DepositChannelRecycleBlocks read/write plus the
-				// DepositChannelLookup read/writes in the for loop below
-				used_weight = used_weight.saturating_add(
-					frame_support::weights::constants::ParityDbWeight::get().reads_writes(
-						(addresses_to_recycle.len() + 1) as u64,
-						(addresses_to_recycle.len() + 1) as u64,
-					),
+	#[derive(CloneNoBound, PartialEqNoBound, EqNoBound)]
+	#[pallet::error]
+	pub enum Error<T, I = ()> {
+		/// The deposit address is not valid. It may have expired or may never have been issued.
+		InvalidDepositAddress,
+		/// A deposit was made using the wrong asset.
+		AssetMismatch,
+		/// Channel ID has reached maximum
+		ChannelIdsExhausted,
+		/// Polkadot's Vault Account does not exist in storage.
+		MissingPolkadotVault,
+		/// Bitcoin's Vault key does not exist for the current epoch.
+		MissingBitcoinVault,
+		/// Channel ID is too large for Bitcoin address derivation
+		BitcoinChannelIdTooLarge,
+		/// The amount is below the minimum egress amount.
+		BelowEgressDustLimit,
+		/// Solana address derivation error.
+		SolanaAddressDerivationError,
+		/// Solana's Environment variables cannot be loaded via the SolanaEnvironment.
+		MissingSolanaApiEnvironment,
+		/// You cannot add 0 to a boost pool.
+		AddBoostAmountMustBeNonZero,
+		/// Adding boost funds is disabled due to safe mode.
+		AddBoostFundsDisabled,
+		/// Retrieving boost funds disabled due to safe mode.
+		StopBoostingDisabled,
+		/// Cannot create a boost pool if it already exists.
+		BoostPoolAlreadyExists,
+		/// Cannot create a boost pool of 0 bps
+		InvalidBoostPoolTier,
+		/// The deposit's risk score exceeds the threshold of all boost pools that could boost it.
+		BoostRiskThresholdExceeded,
+		/// Disabled due to safe mode for the chain
+		DepositChannelCreationDisabled,
+		/// The specified boost pool does not exist.
+		BoostPoolDoesNotExist,
+		/// CCM parameters from a vault swap failed validity check.
+		InvalidCcm,
+		/// Unsupported chain
+		UnsupportedChain,
+		/// Transaction cannot be reported after being pre-witnessed or boosted.
+		TransactionAlreadyPrewitnessed,
+		/// Only the broker that opened the channel can update it.
+		NotChannelOwner,
+		/// Only swap channels can be updated.
+		NotASwapChannel,
+		/// The destination address is not valid for the channel's destination asset.
+		InvalidDestinationAddress,
+		/// The deposit channel has already expired.
+		DepositChannelAlreadyExpired,
+		/// The minimum witness safety margin must not exceed the maximum.
+		InvalidWitnessSafetyMarginBounds,
+		/// The refund address is not valid for the channel's asset.
+		InvalidRefundAddress,
+		/// The channel has no refund parameters whose refund address could be updated.
+		RefundAddressNotUpdatable,
+		/// The account already has the maximum number of deposit channels open.
+		TooManyOpenChannels,
+		/// The egress memo is not supported by the destination chain, or is attached to a CCM.
+		InvalidEgressMemo,
+		/// A verification of the deposit channel addresses is already in progress.
+		DepositAddressVerificationAlreadyInProgress,
+		/// The chain is being wound down.
+		ChainWindingDown,
+		/// There is no abandoned egress with the given id.
+		UnknownAbandonedEgress,
+		/// A broker's minimum deposit must not be lower than the configured minimum deposit.
+		BrokerMinimumDepositTooLow,
+		/// Channel expiries can only be extended by up to one channel lifetime at a time.
+		InvalidChannelExpiryExtension,
+		/// There are too many open deposit channels to extend their expiries at once.
+		TooManyChannelsToExtend,
+		/// The deposit address could not be derived yet. The channel open has been queued and
+		/// will be retried automatically.
+		DepositChannelOpenDeferred,
+		/// The deposit address of a deferred channel open could not be derived within the retry
+		/// period.
+		ChannelOpenRetryPeriodExpired,
+		/// The broker has been suspended and cannot update its channels.
+		BrokerSuspended,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		/// Check whether the external chain has halted or resumed, and apply refund address
+		/// updates whose safety delay has passed. Also accounts for the fetches that will be
+		/// egressed in `on_finalize`.
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			Self::update_chain_halt_status(now)
+				.saturating_add(Self::apply_due_refund_address_updates(now))
+				.saturating_add(Self::complete_chain_wind_down(now))
+				.saturating_add(Self::record_fee_estimate_samples())
+				.saturating_add(Self::scheduled_fetches_weight())
+		}
+
+		/// Recycle addresses if we can
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let mut used_weight = Weight::zero();
+
+			// In some instances, like Solana, the channel lifetime is managed by the electoral
+			// system. Channels are not recycled while the external chain is halted, since the
+			// tracked block height can't be relied upon.
+			if T::MANAGE_CHANNEL_LIFETIME && !ChainHalted::<T, I>::get() {
+				// Visiting the queue and recycling the addresses are budgeted against the same
+				// meter, so that together they never exceed the remaining weight.
+				let mut meter = WeightMeter::with_limit(remaining_weight);
+				let addresses_to_recycle = Self::take_recyclable_addresses(
+					&mut meter,
+					T::ChainTracking::get_block_height(),
 				);
 
+				let mut recycle_weight = Weight::zero();
 				for address in addresses_to_recycle {
-					Self::recycle_channel(&mut used_weight, address);
+					Self::recycle_channel(&mut recycle_weight, address);
 				}
+				meter.consume(recycle_weight);
+				used_weight.saturating_accrue(meter.consumed());
 			}
 
 			// A report gets cleaned up after approx 1 hour and needs to be re-reported by the
@@ -1033,26 +2602,58 @@ pub mod pallet {
 				);
 			}
 
+			Self::expire_processed_deposits(&mut used_weight, remaining_weight, now);
+
+			for tx_id in ProtocolRefundTransactionsExpireAt::<T, I>::take(now) {
+				ProtocolRefundTransactions::<T, I>::remove(tx_id);
+			}
+
+			for key in AggregatableVaultSwapsExpireAt::<T, I>::take(now) {
+				// The entry may have been replaced by a later swap request with the same key.
+				AggregatableVaultSwaps::<T, I>::mutate_exists(key, |entry| {
+					if entry.is_some_and(|(_, expires_at)| expires_at <= now) {
+						*entry = None;
+					}
+				});
+			}
+
+			Self::clear_stale_processed_vault_swaps(&mut used_weight, remaining_weight);
+
+			Self::expire_deposit_failures(&mut used_weight, remaining_weight, now);
+
+			used_weight.saturating_accrue(Self::step_deposit_address_verification(
+				remaining_weight.saturating_sub(used_weight),
+			));
+
+			used_weight.saturating_accrue(Self::retry_pending_channel_opens(
+				now,
+				remaining_weight.saturating_sub(used_weight),
+			));
+
+			used_weight.saturating_accrue(Self::step_migration::<
+				migrations::CurrentSteppedMigration,
+			>(remaining_weight.saturating_sub(used_weight)));
+
 			used_weight
 		}
 
 		/// Take all scheduled Egress and send them out
-		fn on_finalize(_n: BlockNumberFor<T>) {
+		fn on_finalize(n: BlockNumberFor<T>) {
+			// Prewitnesses are processed before anything else so that their boosts are included
+			// in this block's egress batch.
+			Self::process_pending_prewitnesses();
+
+			let mut budget = Self::broadcast_budget_tracker();
+
 			// Send all fetch/transfer requests as a batch. Revert storage if failed.
-			if let Err(error) = Self::do_egress_scheduled_fetch_transfer() {
+			if let Err(error) = Self::do_egress_scheduled_fetch_transfer(&mut budget) {
 				Self::deposit_event(Event::<T, I>::FailedToBuildAllBatchCall { error });
 			}
 
-			if let Ok(egress_transaction) =
-				<T::ChainApiCall as ConsolidateCall<T::TargetChain>>::consolidate_utxos()
-			{
-				let (broadcast_id, _) =
-					T::Broadcaster::threshold_sign_and_broadcast(egress_transaction);
-				Self::deposit_event(Event::<T, I>::UtxoConsolidation { broadcast_id });
-			};
+			Self::do_consolidate_utxos(n, &mut budget);
 
 			// Egress all scheduled Cross chain messages
-			Self::do_egress_scheduled_ccm();
+			Self::do_egress_scheduled_ccm(&mut budget);
 
 			// Process failed external chain calls: re-sign or cull storage.
 			// Take 1 call per block to avoid weight spike.
@@ -1070,7 +2671,7 @@ pub mod pallet {
 			) {
 				match current_epoch.saturating_sub(call.original_epoch) {
 					// The call is stale, clean up storage.
-					n if n >= 2 => {
+					n if n >= FailedForeignChainCall::EXPIRY_EPOCHS => {
 						T::Broadcaster::expire_broadcast(call.broadcast_id);
 						Self::deposit_event(Event::<T, I>::FailedForeignChainCallExpired {
 							broadcast_id: call.broadcast_id,
@@ -1106,17 +2707,40 @@ pub mod pallet {
 				}
 			}
 
+			let mut deferred_rejections = Vec::new();
 			for tx in ScheduledTransactionsForRejection::<T, I>::take() {
-				if let Some(Ok(refund_address)) = tx.refund_address.clone().map(TryInto::try_into) {
+				if !budget.has_budget() {
+					deferred_rejections.push(tx);
+					continue
+				}
+				if let (Some(Ok(refund_address)), Some(deposit_details)) = (
+					tx.refund_address.clone().map(TryInto::try_into),
+					Self::full_deposit_details(&tx.deposit_details),
+				) {
+					let refund_amount =
+						tx.amount.saturating_sub(T::ChainTracking::estimate_egress_fee(tx.asset));
 					if let Ok(api_call) =
 						<T::ChainApiCall as RejectCall<T::TargetChain>>::new_unsigned(
-							tx.deposit_details.clone(),
+							deposit_details,
 							refund_address,
-							tx.amount
-								.saturating_sub(T::ChainTracking::estimate_egress_fee(tx.asset)),
+							refund_amount,
 						) {
+						if let Some(refund_tx_id) = api_call.refund_transaction_in_id() {
+							ProtocolRefundTransactions::<T, I>::insert(&refund_tx_id, ());
+							ProtocolRefundTransactionsExpireAt::<T, I>::append(
+								frame_system::Pallet::<T>::block_number().saturating_add(
+									BlockNumberFor::<T>::from(PROTOCOL_REFUND_RETENTION_BLOCKS),
+								),
+								refund_tx_id,
+							);
+						}
 						let (broadcast_id, _) =
 							T::Broadcaster::threshold_sign_and_broadcast(api_call);
+						budget.record_broadcast();
+						Self::record_egress_total(EgressOrigin::Rejection, tx.asset, refund_amount);
+						if let Some(hash) = tx.deposit_details.pruned_hash() {
+							PrunedDepositDetails::<T, I>::remove(hash);
+						}
 						Self::deposit_event(Event::<T, I>::TransactionRejectedByBroker {
 							broadcast_id,
 							tx_id: tx.deposit_details,
@@ -1134,6 +2758,11 @@ pub mod pallet {
 					});
 				}
 			}
+			if !deferred_rejections.is_empty() {
+				ScheduledTransactionsForRejection::<T, I>::put(deferred_rejections);
+			}
+
+			Self::settle_broadcast_budget(budget);
 		}
 	}
 
@@ -1199,9 +2828,11 @@ pub mod pallet {
 		/// Requires `EnsurePrewitnessed` or `EnsureWitnessed` origin.
 		///
 		/// We calculate weight assuming the most expensive code path is taken, i.e. the deposit
-		/// had been boosted and is now being finalised
+		/// had been boosted and is now being finalised, or the deposit failed and is recorded in
+		/// the deposit failure history.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::boost_finalised().saturating_mul(deposit_witnesses.len() as u64))]
+		#[pallet::weight(Pallet::<T, I>::channel_deposit_full_witness_weight()
+			.saturating_mul(deposit_witnesses.len() as u64))]
 		pub fn process_deposits(
 			origin: OriginFor<T>,
 			deposit_witnesses: Vec<DepositWitness<T::TargetChain>>,
@@ -1209,12 +2840,23 @@ pub mod pallet {
 		) -> DispatchResult {
 			if T::EnsurePrewitnessed::ensure_origin(origin.clone()).is_ok() {
 				for deposit_witness in deposit_witnesses {
-					// TODO: emit event on error?
-					let _ = Self::process_channel_deposit_prewitness(deposit_witness, block_height);
+					Self::prewitness_or_defer(PendingPrewitness::DepositChannel {
+						deposit_witness,
+						block_height,
+					});
 				}
 			} else {
 				T::EnsureWitnessed::ensure_origin(origin)?;
 
+				if Self::deposit_witnessing_paused() {
+					Self::deposit_event(Event::<T, I>::DepositWitnessesDeferred {
+						block_height,
+						deposit_count: deposit_witnesses.len() as u32,
+					});
+					DeferredDepositWitnesses::<T, I>::append((deposit_witnesses, block_height));
+					return Ok(())
+				}
+
 				for deposit_witness in deposit_witnesses {
 					Self::process_channel_deposit_full_witness(deposit_witness, block_height);
 				}
@@ -1240,7 +2882,7 @@ pub mod pallet {
 
 			let current_epoch = T::EpochInfo::epoch_index();
 			match <T::ChainApiCall as TransferFallback<T::TargetChain>>::new_unsigned(
-				TransferAssetParams { asset, amount, to: destination_address.clone() },
+				TransferAssetParams { asset, amount, to: destination_address.clone(), memo: None },
 			) {
 				Ok(api_call) => {
 					let (broadcast_id, _) = T::Broadcaster::threshold_sign(api_call);
@@ -1332,6 +2974,115 @@ pub mod pallet {
 							deduction_percent,
 						});
 					},
+					PalletConfigUpdate::SetDepositFailureRetentionPeriod { blocks } => {
+						DepositFailureRetentionPeriod::<T, I>::set(blocks);
+
+						Self::deposit_event(Event::<T, I>::DepositFailureRetentionPeriodSet {
+							blocks,
+						});
+					},
+					PalletConfigUpdate::SetBoostRiskThreshold { tier, threshold } => {
+						BoostRiskThresholds::<T, I>::set(tier, threshold);
+
+						Self::deposit_event(Event::<T, I>::BoostRiskThresholdSet {
+							tier,
+							threshold,
+						});
+					},
+					PalletConfigUpdate::SetBoostExposureCap { asset, cap } => {
+						BoostExposureCaps::<T, I>::set(asset, cap);
+
+						Self::deposit_event(Event::<T, I>::BoostExposureCapSet { asset, cap });
+					},
+					PalletConfigUpdate::SetChainHaltDetection { settings } => {
+						ChainHaltDetection::<T, I>::set(settings);
+
+						Self::deposit_event(Event::<T, I>::ChainHaltDetectionSet { settings });
+					},
+					PalletConfigUpdate::SetCcmDeferralTimeout { blocks } => {
+						CcmDeferralTimeout::<T, I>::set(blocks);
+
+						Self::deposit_event(Event::<T, I>::CcmDeferralTimeoutSet { blocks });
+					},
+					PalletConfigUpdate::SetWitnessSafetyMarginBounds { bounds } => {
+						if let Some(WitnessSafetyMarginBounds { min, max }) = bounds {
+							ensure!(min <= max, Error::<T, I>::InvalidWitnessSafetyMarginBounds);
+						}
+						WitnessSafetyMarginAutoTuning::<T, I>::set(bounds);
+
+						Self::deposit_event(Event::<T, I>::WitnessSafetyMarginBoundsSet { bounds });
+
+						Self::adjust_witness_safety_margin();
+					},
+					PalletConfigUpdate::SetVaultSwapAggregationWindow { blocks } => {
+						VaultSwapAggregationWindow::<T, I>::set(blocks);
+
+						Self::deposit_event(Event::<T, I>::VaultSwapAggregationWindowSet {
+							blocks,
+						});
+					},
+					PalletConfigUpdate::SetBoostAmountTolerance { tolerance } => {
+						BoostFinalisationTolerance::<T, I>::set(tolerance);
+
+						Self::deposit_event(Event::<T, I>::BoostAmountToleranceSet { tolerance });
+					},
+					PalletConfigUpdate::SetPrewitnessProcessingOrder { order } => {
+						PrewitnessOrder::<T, I>::set(order);
+
+						Self::deposit_event(Event::<T, I>::PrewitnessProcessingOrderSet { order });
+					},
+					PalletConfigUpdate::SetRefundAddressUpdateDelay { blocks } => {
+						RefundAddressUpdateDelay::<T, I>::set(blocks);
+
+						Self::deposit_event(Event::<T, I>::RefundAddressUpdateDelaySet { blocks });
+					},
+					PalletConfigUpdate::SetBoostFeeRebate { settings } => {
+						BoostFeeRebate::<T, I>::set(settings);
+
+						Self::deposit_event(Event::<T, I>::BoostFeeRebateSet { settings });
+					},
+					PalletConfigUpdate::SetMaxOpenChannels { role, max_channels } => {
+						MaxOpenChannels::<T, I>::set(role, max_channels);
+
+						Self::deposit_event(Event::<T, I>::MaxOpenChannelsSet {
+							role,
+							max_channels,
+						});
+					},
+					PalletConfigUpdate::SetMinimumDepositIngressFeeMultiplier { multiplier } => {
+						MinimumDepositIngressFeeMultiplier::<T, I>::set(multiplier);
+
+						Self::deposit_event(Event::<T, I>::MinimumDepositIngressFeeMultiplierSet {
+							multiplier,
+						});
+					},
+					PalletConfigUpdate::SetBroadcastBudget { settings } => {
+						BroadcastBudget::<T, I>::set(settings);
+						if settings.is_none() {
+							CarriedOverBroadcastBudget::<T, I>::kill();
+						}
+
+						Self::deposit_event(Event::<T, I>::BroadcastBudgetSet { settings });
+					},
+					PalletConfigUpdate::SetFeeEstimationStrategy { strategy } => {
+						FeeEstimation::<T, I>::put(strategy);
+						if strategy == FeeEstimationStrategy::Latest {
+							let _ = FeeEstimateSamples::<T, I>::clear(u32::MAX, None);
+							let _ = FeeEstimationTelemetryTotals::<T, I>::clear(u32::MAX, None);
+						}
+
+						Self::deposit_event(Event::<T, I>::FeeEstimationStrategySet { strategy });
+					},
+					PalletConfigUpdate::SetConsolidationInterval { blocks } => {
+						ConsolidationInterval::<T, I>::set(blocks);
+
+						Self::deposit_event(Event::<T, I>::ConsolidationIntervalSet { blocks });
+					},
+					PalletConfigUpdate::SetChannelOpenRetryPeriod { blocks } => {
+						ChannelOpenRetryPeriod::<T, I>::set(blocks);
+
+						Self::deposit_event(Event::<T, I>::ChannelOpenRetryPeriodSet { blocks });
+					},
 				}
 			}
 
@@ -1471,7 +3222,8 @@ pub mod pallet {
 		}
 
 		#[pallet::call_index(13)]
-		#[pallet::weight(T::WeightInfo::vault_swap_request())]
+		#[pallet::weight(T::WeightInfo::vault_swap_request()
+			.saturating_add(T::WeightInfo::record_deposit_failure()))]
 		pub fn vault_swap_request(
 			origin: OriginFor<T>,
 			block_height: TargetChainBlockNumber<T, I>,
@@ -1482,13 +3234,454 @@ pub mod pallet {
 			} else {
 				T::EnsurePrewitnessed::ensure_origin(origin)?;
 
-				Self::process_vault_swap_request_prewitness(block_height, *deposit);
+				Self::prewitness_or_defer(PendingPrewitness::Vault {
+					vault_witness: deposit,
+					block_height,
+				});
 			}
 
 			Ok(())
 		}
-	}
-}
+
+		/// Allows the broker that opened a swap deposit channel to update the broker fees and/or
+		/// the destination address of the channel before it expires. The new values are subject
+		/// to the same validation as when opening a channel.
+		///
+		/// ## Events
+		///
+		/// - [SwapChannelUpdated](Event::SwapChannelUpdated)
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::update_swap_channel())]
+		pub fn update_swap_channel(
+			origin: OriginFor<T>,
+			deposit_address: TargetChainAccount<T, I>,
+			broker_fees: Option<(BasisPoints, Affiliates<T::AccountId>)>,
+			destination_address: Option<EncodedAddress>,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+			ensure!(
+				!T::SwapRequestHandler::is_broker_suspended(&broker_id),
+				Error::<T, I>::BrokerSuspended
+			);
+
+			let (current_height, ..) = Self::expiry_and_recycle_block_height();
+
+			DepositChannelLookup::<T, I>::try_mutate(&deposit_address, |maybe_details| {
+				let details = maybe_details.as_mut().ok_or(Error::<T, I>::InvalidDepositAddress)?;
+				ensure!(details.owner == broker_id, Error::<T, I>::NotChannelOwner);
+				ensure!(
+					details.expires_at > current_height,
+					Error::<T, I>::DepositChannelAlreadyExpired
+				);
+
+				let ChannelAction::Swap {
+					destination_asset,
+					destination_address: current_destination_address,
+					broker_fees: current_broker_fees,
+					channel_metadata,
+					..
+				} = &mut details.action
+				else {
+					return Err(Error::<T, I>::NotASwapChannel.into())
+				};
+
+				if let Some((broker_commission, affiliate_fees)) = broker_fees {
+					let mut beneficiaries = Beneficiaries::new();
+					for beneficiary in
+						[Beneficiary { account: broker_id.clone(), bps: broker_commission }]
+							.into_iter()
+							.chain(affiliate_fees)
+					{
+						if beneficiary.bps > 0 {
+							beneficiaries.try_push(beneficiary).expect(
+								"We are pushing affiliates + 1 which is exactly the maximum Beneficiaries size",
+							);
+						}
+					}
+					T::SwapLimitsProvider::validate_broker_fees(&beneficiaries)?;
+					*current_broker_fees = beneficiaries;
+				}
+
+				if let Some(destination_address) = destination_address {
+					let destination_address =
+						T::AddressConverter::decode_and_validate_address_for_asset(
+							destination_address,
+							*destination_asset,
+						)
+						.map_err(|_| Error::<T, I>::InvalidDestinationAddress)?;
+					T::SwapLimitsProvider::validate_destination(&destination_address)?;
+					if channel_metadata.is_some() {
+						T::SwapLimitsProvider::validate_ccm_destination(
+							&broker_id,
+							&destination_address,
+						)?;
+					}
+					*current_destination_address = destination_address;
+				}
+
+				Self::deposit_event(Event::<T, I>::SwapChannelUpdated {
+					deposit_address: deposit_address.clone(),
+					channel_id: details.deposit_channel.channel_id,
+					broker_id,
+					broker_fees: current_broker_fees.clone(),
+					destination_address: current_destination_address.clone(),
+				});
+
+				Ok(())
+			})
+		}
+
+		/// Allows the broker that opened a deposit channel to close it before it expires. The
+		/// channel expires immediately, but deposits made before closing are still witnessed
+		/// until the channel is recycled.
+		///
+		/// ## Events
+		///
+		/// - [DepositChannelClosed](Event::DepositChannelClosed)
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::close_deposit_channel())]
+		pub fn close_deposit_channel(
+			origin: OriginFor<T>,
+			deposit_address: TargetChainAccount<T, I>,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let (current_height, ..) = Self::expiry_and_recycle_block_height();
+
+			let (channel_id, previous_expiry) =
+				DepositChannelLookup::<T, I>::try_mutate(&deposit_address, |maybe_details| {
+					let details =
+						maybe_details.as_mut().ok_or(Error::<T, I>::InvalidDepositAddress)?;
+					ensure!(details.owner == broker_id, Error::<T, I>::NotChannelOwner);
+					ensure!(
+						details.expires_at > current_height,
+						Error::<T, I>::DepositChannelAlreadyExpired
+					);
+
+					let previous_expiry = details.expires_at;
+					details.expires_at = current_height;
+
+					Ok::<_, DispatchError>((details.deposit_channel.channel_id, previous_expiry))
+				})?;
+
+			if T::MANAGE_CHANNEL_LIFETIME {
+				Self::reschedule_channel_recycling(
+					&deposit_address,
+					previous_expiry,
+					current_height,
+				);
+			}
+			<T::IngressSource as IngressSource>::close_channel(
+				deposit_address.clone(),
+				current_height,
+			)?;
+
+			Self::deposit_event(Event::<T, I>::DepositChannelClosed {
+				deposit_address,
+				channel_id,
+				broker_id,
+				expires_at: current_height,
+			});
+
+			Ok(())
+		}
+
+		/// Records a reorg of the external chain. Requires Witness origin.
+		///
+		/// If automatic tuning is enabled, the witness safety margin is adjusted to the deepest
+		/// recently observed reorg.
+		///
+		/// ## Events
+		///
+		/// - [ReorgObserved](Event::ReorgObserved)
+		/// - [WitnessSafetyMarginChanged](Event::WitnessSafetyMarginChanged)
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::report_reorg())]
+		pub fn report_reorg(
+			origin: OriginFor<T>,
+			block_height: TargetChainBlockNumber<T, I>,
+			depth: TargetChainBlockNumber<T, I>,
+		) -> DispatchResult {
+			T::EnsureWitnessed::ensure_origin(origin)?;
+
+			ObservedReorgs::<T, I>::mutate(|reorgs| {
+				reorgs.push((block_height, depth));
+				if reorgs.len() > MAX_OBSERVED_REORGS {
+					reorgs.remove(0);
+				}
+			});
+
+			Self::deposit_event(Event::<T, I>::ReorgObserved { block_height, depth });
+
+			Self::adjust_witness_safety_margin();
+
+			Ok(())
+		}
+
+		/// Allows the owner of a deposit channel to update the refund address of the channel, for
+		/// example if they lost access to the original one. The update takes effect after
+		/// [RefundAddressUpdateDelay] blocks. A new request replaces any pending one.
+		///
+		/// Only swap channels with refund parameters and liquidity channels can be updated.
+		///
+		/// ## Events
+		///
+		/// - [RefundAddressUpdateScheduled](Event::RefundAddressUpdateScheduled)
+		/// - [RefundAddressUpdated](Event::RefundAddressUpdated)
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::update_refund_address())]
+		pub fn update_refund_address(
+			origin: OriginFor<T>,
+			deposit_address: TargetChainAccount<T, I>,
+			refund_address: EncodedAddress,
+		) -> DispatchResult {
+			let owner = ensure_signed(origin)?;
+
+			let details = DepositChannelLookup::<T, I>::get(&deposit_address)
+				.ok_or(Error::<T, I>::InvalidDepositAddress)?;
+			ensure!(details.owner == owner, Error::<T, I>::NotChannelOwner);
+			ensure!(
+				matches!(
+					details.action,
+					ChannelAction::Swap { refund_params: Some(_), .. } |
+						ChannelAction::LiquidityProvision { .. }
+				),
+				Error::<T, I>::RefundAddressNotUpdatable
+			);
+
+			let refund_address = T::AddressConverter::decode_and_validate_address_for_asset(
+				refund_address,
+				details.deposit_channel.asset.into(),
+			)
+			.map_err(|_| Error::<T, I>::InvalidRefundAddress)?;
+			let channel_id = details.deposit_channel.channel_id;
+
+			let delay = RefundAddressUpdateDelay::<T, I>::get();
+			if delay == 0 {
+				PendingRefundAddressUpdates::<T, I>::remove(&deposit_address);
+				Self::apply_refund_address_update(&deposit_address, channel_id, refund_address);
+			} else {
+				let apply_at = frame_system::Pallet::<T>::block_number()
+					.saturating_add(BlockNumberFor::<T>::from(delay));
+				PendingRefundAddressUpdates::<T, I>::insert(
+					&deposit_address,
+					(channel_id, refund_address.clone(), apply_at),
+				);
+				RefundAddressUpdatesDueAt::<T, I>::append(apply_at, &deposit_address);
+
+				Self::deposit_event(Event::<T, I>::RefundAddressUpdateScheduled {
+					deposit_address,
+					channel_id,
+					owner,
+					refund_address,
+					apply_at,
+				});
+			}
+
+			Ok(())
+		}
+
+		/// Starts re-deriving the addresses of all open deposit channels from the current vault
+		/// key, to verify that they still match. Requires Governance.
+		///
+		/// The channels are checked in `on_idle`, as many per block as the remaining weight
+		/// allows. Mismatches are recorded in [DepositAddressMismatches], replacing those of any
+		/// previous verification.
+		///
+		/// Some chains, such as Bitcoin, derive deposit addresses from the vault key at the time
+		/// the channel is opened, so channels opened before the latest key rotation are expected
+		/// to be flagged.
+		///
+		/// ## Events
+		///
+		/// - [DepositAddressVerificationStarted](Event::DepositAddressVerificationStarted)
+		/// - [DepositAddressMismatchDetected](Event::DepositAddressMismatchDetected)
+		/// - [DepositAddressVerificationCompleted](Event::DepositAddressVerificationCompleted)
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::start_deposit_address_verification())]
+		pub fn start_deposit_address_verification(origin: OriginFor<T>) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			ensure!(
+				!DepositAddressVerificationInProgress::<T, I>::exists(),
+				Error::<T, I>::DepositAddressVerificationAlreadyInProgress
+			);
+
+			let _ = DepositAddressMismatches::<T, I>::clear(u32::MAX, None);
+			DepositAddressVerificationInProgress::<T, I>::put(
+				DepositAddressVerificationProgress::default(),
+			);
+
+			Self::deposit_event(Event::<T, I>::DepositAddressVerificationStarted);
+
+			Ok(())
+		}
+
+		/// Starts winding down the chain. From now on no new deposit channels can be opened and
+		/// no new swaps to the chain are accepted, while scheduled egresses continue to be sent.
+		/// Transfers and CCMs still scheduled when the grace period ends, or requested after it,
+		/// are abandoned and can be credited to an account with `credit_abandoned_egress`.
+		///
+		/// Requires Governance.
+		///
+		/// ## Events
+		///
+		/// - [ChainWindDownStarted](Event::ChainWindDownStarted)
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::start_chain_wind_down())]
+		pub fn start_chain_wind_down(
+			origin: OriginFor<T>,
+			grace_period: BlockNumberFor<T>,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			ensure!(!ChainWindDown::<T, I>::exists(), Error::<T, I>::ChainWindingDown);
+
+			let started_at = frame_system::Pallet::<T>::block_number();
+			let grace_period_ends_at = started_at.saturating_add(grace_period);
+			ChainWindDown::<T, I>::put(ChainWindDownStatus {
+				started_at,
+				grace_period_ends_at,
+				abandoned_egresses: 0,
+				completed: false,
+			});
+
+			Self::deposit_event(Event::<T, I>::ChainWindDownStarted { grace_period_ends_at });
+
+			Ok(())
+		}
+
+		/// Sets the minimum deposit of the asset for the broker's deposit channels, to be applied
+		/// instead of the configured minimum deposit. It can only raise the minimum. `None`
+		/// removes it.
+		///
+		/// ## Events
+		///
+		/// - [BrokerMinimumDepositSet](Event::BrokerMinimumDepositSet)
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::set_broker_minimum_deposit())]
+		pub fn set_broker_minimum_deposit(
+			origin: OriginFor<T>,
+			asset: TargetChainAsset<T, I>,
+			minimum_deposit: Option<TargetChainAmount<T, I>>,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			if let Some(minimum_deposit) = minimum_deposit {
+				ensure!(
+					minimum_deposit >= MinimumDeposit::<T, I>::get(asset),
+					Error::<T, I>::BrokerMinimumDepositTooLow
+				);
+			}
+			BrokerMinimumDeposits::<T, I>::set(&broker_id, asset, minimum_deposit);
+
+			Self::deposit_event(Event::<T, I>::BrokerMinimumDepositSet {
+				broker_id,
+				asset,
+				minimum_deposit,
+			});
+
+			Ok(())
+		}
+
+		/// Extends the expiry of all open deposit channels by the given number of external chain
+		/// blocks, e.g. to make up for the time the external chain was halted. Recycling of the
+		/// channels is pushed back accordingly.
+		///
+		/// Requires Governance.
+		///
+		/// ## Events
+		///
+		/// - [DepositChannelExpiriesExtended](Event::DepositChannelExpiriesExtended)
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::extend_deposit_channel_expiries(
+			MAX_EXTENDED_DEPOSIT_CHANNELS
+		))]
+		pub fn extend_deposit_channel_expiries(
+			origin: OriginFor<T>,
+			extend_by: TargetChainBlockNumber<T, I>,
+		) -> DispatchResultWithPostInfo {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			ensure!(
+				!extend_by.is_zero() && extend_by <= DepositChannelLifetime::<T, I>::get(),
+				Error::<T, I>::InvalidChannelExpiryExtension
+			);
+
+			let (current_height, ..) = Self::expiry_and_recycle_block_height();
+			let open_channels = DepositChannelLookup::<T, I>::iter()
+				.filter(|(_, details)| details.expires_at > current_height)
+				.map(|(deposit_address, details)| (deposit_address, details.expires_at))
+				.take(MAX_EXTENDED_DEPOSIT_CHANNELS as usize + 1)
+				.collect::<Vec<_>>();
+			ensure!(
+				open_channels.len() <= MAX_EXTENDED_DEPOSIT_CHANNELS as usize,
+				Error::<T, I>::TooManyChannelsToExtend
+			);
+
+			for (deposit_address, previous_expiry) in &open_channels {
+				let new_expiry = <T::TargetChain as Chain>::saturating_block_witness_next(
+					previous_expiry.saturating_add(extend_by),
+				);
+				DepositChannelLookup::<T, I>::mutate(deposit_address, |details| {
+					if let Some(details) = details {
+						details.expires_at = new_expiry;
+					}
+				});
+				if T::MANAGE_CHANNEL_LIFETIME {
+					Self::reschedule_channel_recycling(
+						deposit_address,
+						*previous_expiry,
+						new_expiry,
+					);
+				}
+				<T::IngressSource as IngressSource>::extend_channel(
+					deposit_address.clone(),
+					new_expiry,
+				)?;
+			}
+
+			let channels = open_channels.len() as u32;
+			Self::deposit_event(Event::<T, I>::DepositChannelExpiriesExtended {
+				extended_by: extend_by,
+				channels,
+			});
+
+			Ok(Some(T::WeightInfo::extend_deposit_channel_expiries(channels)).into())
+		}
+
+		/// Credits the funds of an egress abandoned because of the chain's wind-down to an
+		/// account's free balance, for example to the account of the user the egress was for.
+		///
+		/// Requires Governance.
+		///
+		/// ## Events
+		///
+		/// - [AbandonedEgressCredited](Event::AbandonedEgressCredited)
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::credit_abandoned_egress())]
+		pub fn credit_abandoned_egress(
+			origin: OriginFor<T>,
+			egress_id: EgressId,
+			account_id: T::AccountId,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			let AbandonedEgress { asset, amount, .. } = AbandonedEgresses::<T, I>::take(egress_id)
+				.ok_or(Error::<T, I>::UnknownAbandonedEgress)?;
+			T::Balance::credit_account(&account_id, asset.into(), amount.into());
+
+			Self::deposit_event(Event::<T, I>::AbandonedEgressCredited {
+				egress_id,
+				account_id,
+				asset,
+				amount,
+			});
+
+			Ok(())
+		}
+	}
+}
 
 impl<T: Config<I>, I: 'static> IngressSink for Pallet<T, I> {
 	type Account = <T::TargetChain as Chain>::ChainAccount;
@@ -1538,18 +3731,149 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		});
 		Ok(())
 	}
+	fn deposit_witnessing_paused() -> bool {
+		ChainHalted::<T, I>::get() &&
+			ChainHaltDetection::<T, I>::get()
+				.is_some_and(|settings| settings.pause_deposit_witnessing)
+	}
+
+	/// Applies the refund address updates scheduled for this block. Updates that were replaced
+	/// by a later request are left pending until their own block.
+	fn apply_due_refund_address_updates(now: BlockNumberFor<T>) -> Weight {
+		let due = RefundAddressUpdatesDueAt::<T, I>::take(now);
+		let weight =
+			T::DbWeight::get().reads_writes(1 + 2 * due.len() as u64, 1 + 2 * due.len() as u64);
+
+		for deposit_address in due {
+			match PendingRefundAddressUpdates::<T, I>::get(&deposit_address) {
+				Some((channel_id, refund_address, apply_at)) if apply_at == now => {
+					PendingRefundAddressUpdates::<T, I>::remove(&deposit_address);
+					Self::apply_refund_address_update(&deposit_address, channel_id, refund_address);
+				},
+				_ => {},
+			}
+		}
+
+		weight
+	}
+
+	fn apply_refund_address_update(
+		deposit_address: &TargetChainAccount<T, I>,
+		channel_id: ChannelId,
+		refund_address: ForeignChainAddress,
+	) {
+		DepositChannelLookup::<T, I>::mutate(deposit_address, |maybe_details| {
+			// The address may have been recycled and reused for another channel in the meantime.
+			let Some(details) = maybe_details
+				.as_mut()
+				.filter(|details| details.deposit_channel.channel_id == channel_id)
+			else {
+				return
+			};
+
+			let previous_refund_address = match &mut details.action {
+				ChannelAction::Swap { refund_params: Some(refund_params), .. } => Some(
+					sp_std::mem::replace(&mut refund_params.refund_address, refund_address.clone()),
+				),
+				ChannelAction::LiquidityProvision { refund_address: current, .. } =>
+					current.replace(refund_address.clone()),
+				_ => return,
+			};
+
+			Self::deposit_event(Event::<T, I>::RefundAddressUpdated {
+				deposit_address: deposit_address.clone(),
+				channel_id,
+				owner: details.owner.clone(),
+				previous_refund_address,
+				refund_address,
+			});
+		});
+	}
+
+	/// Marks the external chain as halted if its tracked block height hasn't progressed for the
+	/// configured number of blocks, and resumes normal operation once it does.
+	fn update_chain_halt_status(now: BlockNumberFor<T>) -> Weight {
+		let mut weight = T::DbWeight::get().reads(4);
+		let block_height = T::ChainTracking::get_block_height();
+		let settings = ChainHaltDetection::<T, I>::get();
+		let halted = ChainHalted::<T, I>::get();
+
+		match LastChainProgress::<T, I>::get() {
+			Some((last_height, last_progress_at)) if last_height == block_height =>
+				match settings {
+					Some(ChainHaltDetectionSettings { threshold, .. })
+						if !halted &&
+							now.saturating_sub(last_progress_at) >=
+								BlockNumberFor::<T>::from(threshold) =>
+					{
+						weight.saturating_accrue(T::DbWeight::get().writes(1));
+						ChainHalted::<T, I>::put(true);
+						Self::deposit_event(Event::<T, I>::ChainHaltDetected {
+							block_height,
+							last_progress_at,
+						});
+					},
+					None if halted => {
+						weight.saturating_accrue(Self::end_chain_halt(block_height));
+					},
+					_ => {},
+				},
+			_ => {
+				weight.saturating_accrue(T::DbWeight::get().writes(1));
+				LastChainProgress::<T, I>::put((block_height, now));
+				if halted {
+					weight.saturating_accrue(Self::end_chain_halt(block_height));
+				}
+			},
+		}
+
+		weight
+	}
+
+	fn end_chain_halt(block_height: TargetChainBlockNumber<T, I>) -> Weight {
+		ChainHalted::<T, I>::kill();
+
+		let mut deferred_deposits_processed = 0u32;
+		for (deposit_witnesses, witnessed_at) in DeferredDepositWitnesses::<T, I>::take() {
+			for deposit_witness in deposit_witnesses {
+				deferred_deposits_processed.saturating_accrue(1);
+				Self::process_channel_deposit_full_witness(deposit_witness, witnessed_at);
+			}
+		}
+
+		Self::deposit_event(Event::<T, I>::ChainHaltEnded {
+			block_height,
+			deferred_deposits_processed,
+		});
+
+		T::DbWeight::get().writes(2).saturating_add(
+			Self::channel_deposit_full_witness_weight()
+				.saturating_mul(deferred_deposits_processed as u64),
+		)
+	}
+
+	/// The weight of fully witnessing a channel deposit, assuming the most expensive path: either
+	/// a boost is finalised, or the deposit fails and is recorded in the failure history.
+	fn channel_deposit_full_witness_weight() -> Weight {
+		T::WeightInfo::boost_finalised().max(
+			T::WeightInfo::process_channel_deposit_full_witness()
+				.saturating_add(T::WeightInfo::record_deposit_failure()),
+		)
+	}
+
 	fn recycle_channel(used_weight: &mut Weight, address: <T::TargetChain as Chain>::ChainAccount) {
-		if let Some(DepositChannelDetails { deposit_channel, boost_status, .. }) =
-			DepositChannelLookup::<T, I>::take(address)
+		if let Some(DepositChannelDetails { owner, deposit_channel, boost_status, .. }) =
+			DepositChannelLookup::<T, I>::take(&address)
 		{
+			DepositChannelsByOwner::<T, I>::remove(&owner, address);
+			OpenChannelCount::<T, I>::mutate_exists(&owner, |count| {
+				*count = count.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+			});
 			if let Some(state) = deposit_channel.state.maybe_recycle() {
 				DepositChannelPool::<T, I>::insert(
 					deposit_channel.channel_id,
 					DepositChannel { state, ..deposit_channel },
 				);
-				*used_weight = used_weight.saturating_add(
-					frame_support::weights::constants::ParityDbWeight::get().reads_writes(0, 1),
-				);
 			}
 
 			if let BoostStatus::Boosted { prewitnessed_deposit_id, pools, amount } = boost_status {
@@ -1569,6 +3893,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						}
 					});
 				}
+				BoostExposure::<T, I>::mutate(deposit_channel.asset, |exposure| {
+					exposure.saturating_reduce(amount)
+				});
+				BoostedDepositPrewitnessHeights::<T, I>::remove(prewitnessed_deposit_id);
 				Self::deposit_event(Event::<T, I>::BoostedDepositLost {
 					prewitnessed_deposit_id,
 					amount,
@@ -1577,20 +3905,392 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 	}
 
+	/// Emits a `DepositFailed` event and records the failure in the deposit failure history,
+	/// evicting the oldest entry if the history is full.
+	fn deposit_failed(
+		block_height: TargetChainBlockNumber<T, I>,
+		reason: DepositFailedReason,
+		details: DepositFailedDetails<T, I>,
+	) {
+		DepositFailureHistoryRange::<T, I>::mutate(|(oldest, next)| {
+			DepositFailureHistory::<T, I>::insert(
+				*next,
+				DepositFailure::new(
+					block_height,
+					reason.clone(),
+					&details,
+					frame_system::Pallet::<T>::block_number().unique_saturated_into(),
+				),
+			);
+			next.saturating_accrue(1);
+			if next.saturating_sub(*oldest) > MAX_DEPOSIT_FAILURE_HISTORY {
+				DepositFailureHistory::<T, I>::remove(*oldest);
+				oldest.saturating_accrue(1);
+			}
+		});
+
+		Self::deposit_event(Event::<T, I>::DepositFailed { block_height, reason, details });
+	}
+
+	/// Removes entries from the deposit failure history once they are older than the retention
+	/// period.
+	fn expire_deposit_failures(
+		used_weight: &mut Weight,
+		remaining_weight: Weight,
+		now: BlockNumberFor<T>,
+	) {
+		let weight_per_entry =
+			frame_support::weights::constants::ParityDbWeight::get().reads_writes(1, 1);
+		let now: BlockNumber = now.unique_saturated_into();
+		let retention_period = DepositFailureRetentionPeriod::<T, I>::get();
+
+		used_weight.saturating_accrue(
+			frame_support::weights::constants::ParityDbWeight::get().reads_writes(2, 1),
+		);
+		DepositFailureHistoryRange::<T, I>::mutate(|(oldest, next)| {
+			while oldest < next &&
+				used_weight.saturating_add(weight_per_entry).all_lte(remaining_weight)
+			{
+				used_weight.saturating_accrue(weight_per_entry);
+				match DepositFailureHistory::<T, I>::get(*oldest) {
+					Some(failure) if failure.recorded_at.saturating_add(retention_period) > now =>
+						break,
+					_ => {
+						DepositFailureHistory::<T, I>::remove(*oldest);
+						oldest.saturating_accrue(1);
+					},
+				}
+			}
+		});
+	}
+
+	fn record_processed_deposit(key: ProcessedDepositKey<T, I>) {
+		ProcessedDeposits::<T, I>::insert(&key, ());
+		ProcessedDepositsQueueRange::<T, I>::mutate(|(_, next)| {
+			ProcessedDepositsQueue::<T, I>::insert(
+				*next,
+				(
+					frame_system::Pallet::<T>::block_number().saturating_add(
+						BlockNumberFor::<T>::from(PROCESSED_DEPOSIT_RETENTION_BLOCKS),
+					),
+					key,
+				),
+			);
+			next.saturating_accrue(1);
+		});
+	}
+
+	/// Forgets the processed deposits whose retention period has passed, oldest first, for as
+	/// long as there is weight left.
+	fn expire_processed_deposits(
+		used_weight: &mut Weight,
+		remaining_weight: Weight,
+		now: BlockNumberFor<T>,
+	) {
+		let weight_per_entry =
+			frame_support::weights::constants::ParityDbWeight::get().reads_writes(1, 2);
+
+		used_weight.saturating_accrue(
+			frame_support::weights::constants::ParityDbWeight::get().reads_writes(1, 1),
+		);
+		ProcessedDepositsQueueRange::<T, I>::mutate(|(oldest, next)| {
+			while oldest < next &&
+				used_weight.saturating_add(weight_per_entry).all_lte(remaining_weight)
+			{
+				used_weight.saturating_accrue(weight_per_entry);
+				match ProcessedDepositsQueue::<T, I>::get(*oldest) {
+					Some((expires_at, _)) if expires_at > now => break,
+					entry => {
+						if let Some((_, key)) = entry {
+							ProcessedDeposits::<T, I>::remove(key);
+						}
+						ProcessedDepositsQueue::<T, I>::remove(*oldest);
+						oldest.saturating_accrue(1);
+					},
+				}
+			}
+		});
+	}
+
+	/// Clears the processed vault swaps of every epoch before the previous one, since they can no
+	/// longer be re-submitted. Epochs are cleared oldest first, resuming from where the last call
+	/// left off.
+	fn clear_stale_processed_vault_swaps(used_weight: &mut Weight, remaining_weight: Weight) {
+		let removal_weight = frame_support::weights::constants::ParityDbWeight::get().writes(1);
+		let weight_per_epoch = frame_support::weights::constants::ParityDbWeight::get().reads(1);
+
+		used_weight.saturating_accrue(
+			frame_support::weights::constants::ParityDbWeight::get().reads_writes(2, 1),
+		);
+		let oldest_epoch_to_keep = T::EpochInfo::epoch_index().saturating_sub(1);
+		// Before any epoch has been cleared, only the one preceding the oldest kept epoch can
+		// still hold records.
+		let mut epoch = OldestUnclearedVaultSwapEpoch::<T, I>::get()
+			.unwrap_or(oldest_epoch_to_keep.saturating_sub(1));
+
+		while epoch < oldest_epoch_to_keep {
+			used_weight.saturating_accrue(weight_per_epoch);
+			let max_removals = remaining_weight
+				.saturating_sub(*used_weight)
+				.ref_time()
+				.checked_div(removal_weight.ref_time())
+				.unwrap_or_default()
+				.saturated_into::<u32>();
+			if max_removals == 0 {
+				break
+			}
+			let result = ProcessedVaultSwaps::<T, I>::clear_prefix(epoch, max_removals, None);
+			used_weight.saturating_accrue(removal_weight.saturating_mul(result.unique.into()));
+			if result.maybe_cursor.is_some() {
+				break
+			}
+			epoch.saturating_accrue(1);
+		}
+
+		OldestUnclearedVaultSwapEpoch::<T, I>::put(epoch);
+	}
+
+	/// Advances the ongoing verification of the deposit channel addresses, using at most
+	/// `weight_limit`.
+	fn step_deposit_address_verification(weight_limit: Weight) -> Weight {
+		let mut meter = WeightMeter::with_limit(weight_limit);
+		if meter.try_consume(T::DbWeight::get().reads_writes(1, 1)).is_err() {
+			return Weight::zero()
+		}
+		let Some(mut progress) = DepositAddressVerificationInProgress::<T, I>::get() else {
+			return meter.consumed()
+		};
+
+		// Reading the channel, and flagging it if its address doesn't match.
+		let weight_per_channel = T::DbWeight::get().reads_writes(1, 1);
+		let mut channels = match progress.cursor.clone() {
+			Some(raw_key) => DepositChannelLookup::<T, I>::iter_from(raw_key),
+			None => DepositChannelLookup::<T, I>::iter(),
+		};
+
+		loop {
+			if !meter.can_consume(weight_per_channel) {
+				DepositAddressVerificationInProgress::<T, I>::put(progress);
+				return meter.consumed()
+			}
+			let Some((deposit_address, details)) = channels.next() else {
+				DepositAddressVerificationInProgress::<T, I>::kill();
+				Self::deposit_event(Event::<T, I>::DepositAddressVerificationCompleted {
+					channels_checked: progress.channels_checked,
+					mismatches: progress.mismatches,
+				});
+				return meter.consumed()
+			};
+			meter.consume(weight_per_channel);
+
+			let channel_id = details.deposit_channel.channel_id;
+			let derived_address =
+				T::AddressDerivation::generate_address(details.deposit_channel.asset, channel_id)
+					.ok();
+			if derived_address.as_ref() != Some(&deposit_address) {
+				DepositAddressMismatches::<T, I>::insert(
+					&deposit_address,
+					DepositAddressMismatch { channel_id, derived_address: derived_address.clone() },
+				);
+				Self::deposit_event(Event::<T, I>::DepositAddressMismatchDetected {
+					deposit_address: deposit_address.clone(),
+					channel_id,
+					derived_address,
+				});
+				progress.mismatches.saturating_inc();
+			}
+			progress.channels_checked.saturating_inc();
+			progress.cursor = Some(DepositChannelLookup::<T, I>::hashed_key_for(&deposit_address));
+		}
+	}
+
+	/// Returns the recorded deposit failures for the given deposit address or transaction id,
+	/// oldest first.
+	pub fn deposit_failures(
+		deposit_address: Option<&TargetChainAccount<T, I>>,
+		tx_id: Option<&TransactionInIdFor<T, I>>,
+	) -> Vec<DepositFailure<T, I>> {
+		let (oldest, next) = DepositFailureHistoryRange::<T, I>::get();
+		(oldest..next)
+			.filter_map(DepositFailureHistory::<T, I>::get)
+			.filter(|failure| {
+				deposit_address
+					.is_none_or(|address| failure.deposit_address.as_ref() == Some(address)) &&
+					tx_id.is_none_or(|tx_id| failure.tx_id.as_ref() == Some(tx_id))
+			})
+			.collect()
+	}
+
+	/// Returns the transactions marked for rejection by the given broker, with their status and
+	/// the block at which the report expires.
+	pub fn transactions_marked_for_rejection(
+		broker: &T::AccountId,
+	) -> Vec<(TransactionInIdFor<T, I>, TransactionPrewitnessedStatus, Option<BlockNumberFor<T>>)>
+	{
+		let expiries = ReportExpiresAt::<T, I>::iter()
+			.flat_map(|(expires_at, reports)| {
+				reports
+					.into_iter()
+					.filter(|(account_id, _)| account_id == broker)
+					.map(move |(_, tx_id)| (tx_id, expires_at))
+			})
+			.collect::<Vec<_>>();
+
+		TransactionsMarkedForRejection::<T, I>::iter_prefix(broker)
+			.map(|(tx_id, status)| {
+				let expires_at = expiries
+					.iter()
+					.find(|(reported_tx_id, _)| *reported_tx_id == tx_id)
+					.map(|(_, expires_at)| *expires_at);
+				(tx_id, status, expires_at)
+			})
+			.collect()
+	}
+
+	/// Returns the transactions that are scheduled to be rejected.
+	pub fn scheduled_transactions_for_rejection() -> Vec<TransactionRejectionDetails<T, I>> {
+		ScheduledTransactionsForRejection::<T, I>::get()
+	}
+
+	/// Bounds the size of the deposit details, keeping the full details in
+	/// [PrunedDepositDetails] if they are too large.
+	pub fn bound_deposit_details(
+		deposit_details: <T::TargetChain as Chain>::DepositDetails,
+	) -> BoundedDepositDetails<T::TargetChain> {
+		let bounded = BoundedDepositDetails::new(deposit_details.clone());
+		if let Some(hash) = bounded.pruned_hash() {
+			PrunedDepositDetails::<T, I>::insert(hash, deposit_details);
+		}
+		bounded
+	}
+
+	/// The full deposit details, looked up in [PrunedDepositDetails] if they were pruned.
+	pub fn full_deposit_details(
+		deposit_details: &BoundedDepositDetails<T::TargetChain>,
+	) -> Option<<T::TargetChain as Chain>::DepositDetails> {
+		match deposit_details {
+			BoundedDepositDetails::Full(deposit_details) => Some(deposit_details.clone()),
+			BoundedDepositDetails::Pruned { hash, .. } => PrunedDepositDetails::<T, I>::get(hash),
+		}
+	}
+
+	/// Returns the transactions for which the rejection could not be broadcast.
+	pub fn failed_rejections() -> Vec<TransactionRejectionDetails<T, I>> {
+		FailedRejections::<T, I>::get()
+	}
+
+	/// Returns the number of deposit channels the account has open, and the maximum number it may
+	/// have open given its role.
+	pub fn open_channel_usage(owner: &T::AccountId) -> (u32, Option<u32>) {
+		(
+			OpenChannelCount::<T, I>::get(owner),
+			MaxOpenChannels::<T, I>::iter().find_map(|(role, max_channels)| {
+				T::AccountRoleRegistry::has_account_role(owner, role).then_some(max_channels)
+			}),
+		)
+	}
+
+	/// Takes the addresses from the recycle queue whose recycle height has been reached, for as
+	/// long as the meter can pay for visiting their block height and recycling them.
+	///
+	/// The weight charged per address covers the storage accessed by `recycle_channel`, except
+	/// for settling the boost pools of a boosted channel, which is left to the caller.
 	fn take_recyclable_addresses(
-		channel_recycle_blocks: &mut ChannelRecycleQueue<T, I>,
-		maximum_addresses_to_take: usize,
+		meter: &mut WeightMeter,
 		current_block_height: TargetChainBlockNumber<T, I>,
 	) -> Vec<TargetChainAccount<T, I>> {
-		channel_recycle_blocks.sort_by_key(|(block, _)| *block);
-		let partition_point = sp_std::cmp::min(
-			channel_recycle_blocks.partition_point(|(block, _)| *block <= current_block_height),
-			maximum_addresses_to_take,
-		);
-		channel_recycle_blocks
-			.drain(..partition_point)
-			.map(|(_, address)| address)
-			.collect()
+		// r/w DepositChannelLookup, w DepositChannelsByOwner, r/w OpenChannelCount,
+		// w DepositChannelPool.
+		let recycle_weight_per_address =
+			frame_support::weights::constants::ParityDbWeight::get().reads_writes(2, 4);
+		// r/w DepositChannelRecycleQueue.
+		let weight_per_block_height =
+			frame_support::weights::constants::ParityDbWeight::get().reads_writes(1, 1);
+
+		let mut addresses = Vec::new();
+
+		// Read the queue counter and the next block height.
+		if meter
+			.try_consume(
+				frame_support::weights::constants::ParityDbWeight::get().reads_writes(2, 1),
+			)
+			.is_err()
+		{
+			return addresses
+		}
+		if DepositChannelRecycleQueue::<T, I>::count() == 0 {
+			NextRecycleBlockHeight::<T, I>::kill();
+			return addresses
+		}
+		let Some(mut block_height) = NextRecycleBlockHeight::<T, I>::get() else {
+			log_or_panic!("The next recycle block height must be set if the queue is not empty.");
+			return addresses
+		};
+
+		while block_height <= current_block_height &&
+			meter.can_consume(weight_per_block_height.saturating_add(recycle_weight_per_address))
+		{
+			meter.consume(weight_per_block_height);
+
+			let mut due = DepositChannelRecycleQueue::<T, I>::take(block_height);
+			let affordable = due
+				.iter()
+				.take_while(|_| meter.try_consume(recycle_weight_per_address).is_ok())
+				.count();
+			if due.len() > affordable {
+				// Leave the remaining addresses for the next block.
+				DepositChannelRecycleQueue::<T, I>::insert(block_height, due.split_off(affordable));
+				addresses.extend(due);
+				break
+			}
+			addresses.extend(due);
+			block_height.saturating_inc();
+		}
+		NextRecycleBlockHeight::<T, I>::put(block_height);
+
+		addresses
+	}
+
+	fn broadcast_budget_tracker() -> BroadcastBudgetTracker {
+		BroadcastBudgetTracker {
+			remaining: BroadcastBudget::<T, I>::get().map(|settings| {
+				settings.per_block.saturating_add(CarriedOverBroadcastBudget::<T, I>::get())
+			}),
+			initiated: 0,
+			exhausted: false,
+		}
+	}
+
+	/// Carries the unused budget over to the next block and records the broadcasts initiated in
+	/// this block.
+	fn settle_broadcast_budget(budget: BroadcastBudgetTracker) {
+		if let (Some(settings), Some(remaining)) =
+			(BroadcastBudget::<T, I>::get(), budget.remaining)
+		{
+			CarriedOverBroadcastBudget::<T, I>::put(remaining.min(settings.max_carry_over));
+		}
+		if budget.initiated > 0 || budget.exhausted {
+			LastBroadcastUsage::<T, I>::put(BroadcastUsage {
+				block_number: frame_system::Pallet::<T>::block_number(),
+				broadcasts: budget.initiated,
+				budget_exhausted: budget.exhausted,
+			});
+		}
+		if budget.exhausted {
+			Self::deposit_event(Event::<T, I>::BroadcastBudgetExhausted {
+				broadcasts_initiated: budget.initiated,
+			});
+		}
+	}
+
+	fn record_egress_usage(f: impl FnOnce(&mut EgressUsage<BlockNumberFor<T>>)) {
+		let current_block = frame_system::Pallet::<T>::block_number();
+		LastEgressUsage::<T, I>::mutate(|usage| {
+			if usage.block_number != current_block {
+				*usage = EgressUsage { block_number: current_block, ..Default::default() };
+			}
+			f(usage);
+		});
 	}
 
 	fn should_fetch_or_transfer(
@@ -1609,17 +4309,209 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			.unwrap_or(true)
 	}
 
+	fn current_block_number() -> BlockNumber {
+		frame_system::Pallet::<T>::block_number().unique_saturated_into()
+	}
+
+	fn blocks_waited_since(scheduled_at: BlockNumber) -> BlockNumber {
+		Self::current_block_number().saturating_sub(scheduled_at)
+	}
+
+	fn record_accounting(
+		asset: TargetChainAsset<T, I>,
+		f: impl FnOnce(&mut AssetAccounting<TargetChainAmount<T, I>>),
+	) {
+		AssetAccountingTotals::<T, I>::mutate(asset, f);
+	}
+
+	fn record_egress_total(
+		origin: EgressOrigin,
+		asset: TargetChainAsset<T, I>,
+		amount: TargetChainAmount<T, I>,
+	) {
+		EgressTotals::<T, I>::mutate(origin, asset, |total| {
+			total.count.saturating_accrue(1);
+			total.amount.saturating_accrue(amount);
+		});
+	}
+
+	fn accrue_pending_egress_amount(
+		asset: TargetChainAsset<T, I>,
+		amount: TargetChainAmount<T, I>,
+	) {
+		if !amount.is_zero() {
+			PendingEgressAmounts::<T, I>::mutate(asset, |total| total.saturating_accrue(amount));
+		}
+	}
+
+	fn reduce_pending_egress_amount(
+		asset: TargetChainAsset<T, I>,
+		amount: TargetChainAmount<T, I>,
+	) {
+		PendingEgressAmounts::<T, I>::mutate_exists(asset, |total| {
+			*total = total
+				.map(|total| total.saturating_sub(amount))
+				.filter(|remaining| !remaining.is_zero());
+		});
+	}
+
+	/// Completes the wind-down of the chain once its grace period has ended: scheduled transfers
+	/// and CCMs are abandoned, fetches are kept so that deposited funds still reach the vault.
+	/// Since the chain may no longer be witnessed once it is wound down, for example because its
+	/// elections are stopped, completion waits until the remaining fetches have been sent.
+	fn complete_chain_wind_down(now: BlockNumberFor<T>) -> Weight {
+		let Some(mut status) = ChainWindDown::<T, I>::get() else {
+			return T::DbWeight::get().reads(1)
+		};
+		if status.completed || now < status.grace_period_ends_at {
+			return T::DbWeight::get().reads(1)
+		}
+
+		let mut abandoned = Vec::new();
+		let mut fetches_remaining = false;
+		ScheduledEgressFetchOrTransfer::<T, I>::mutate(|requests| {
+			requests.retain(|request| match request {
+				FetchOrTransfer::Fetch { .. } => {
+					fetches_remaining = true;
+					true
+				},
+				FetchOrTransfer::Transfer {
+					egress_id, asset, amount, destination_address, ..
+				} => {
+					abandoned.push((*egress_id, *asset, *amount, destination_address.clone()));
+					false
+				},
+			})
+		});
+		for ccm in ScheduledEgressCcm::<T, I>::take() {
+			abandoned.push((ccm.egress_id, ccm.asset, ccm.amount, ccm.destination_address));
+		}
+
+		let abandoned_now = abandoned.len() as u64;
+		for (egress_id, asset, amount, destination_address) in abandoned {
+			Self::reduce_pending_egress_amount(asset, amount);
+			Self::abandon_egress(&mut status, egress_id, asset, amount, destination_address);
+		}
+
+		if !fetches_remaining {
+			status.completed = true;
+			T::OnChainWoundDown::on_chain_wound_down();
+			Self::deposit_event(Event::<T, I>::ChainWindDownCompleted {
+				abandoned_egresses: status.abandoned_egresses,
+			});
+		}
+		ChainWindDown::<T, I>::put(status);
+
+		T::DbWeight::get().reads_writes(3, 3u64.saturating_add(abandoned_now.saturating_mul(2)))
+	}
+
+	/// Records an egress that won't be sent because of the chain's wind-down, so that its funds
+	/// can be credited to an account.
+	fn abandon_egress(
+		status: &mut ChainWindDownStatus<BlockNumberFor<T>>,
+		egress_id: EgressId,
+		asset: TargetChainAsset<T, I>,
+		amount: TargetChainAmount<T, I>,
+		destination_address: TargetChainAccount<T, I>,
+	) {
+		status.abandoned_egresses.saturating_accrue(1);
+		AbandonedEgresses::<T, I>::insert(
+			egress_id,
+			AbandonedEgress { asset, amount, destination_address: destination_address.clone() },
+		);
+		Self::deposit_event(Event::<T, I>::EgressAbandonedOnWindDown {
+			egress_id,
+			asset,
+			amount,
+			destination_address,
+		});
+	}
+
+	/// The total amount of each asset in the scheduled transfers and CCMs. This is what
+	/// [PendingEgressAmounts] tracks.
+	pub(crate) fn pending_egress_amounts_of_scheduled_egresses(
+	) -> BTreeMap<TargetChainAsset<T, I>, TargetChainAmount<T, I>> {
+		let mut amounts = BTreeMap::<_, TargetChainAmount<T, I>>::new();
+		for request in ScheduledEgressFetchOrTransfer::<T, I>::get() {
+			if let FetchOrTransfer::Transfer { asset, amount, .. } = request {
+				amounts.entry(asset).or_default().saturating_accrue(amount);
+			}
+		}
+		for ccm in ScheduledEgressCcm::<T, I>::get() {
+			amounts.entry(ccm.asset).or_default().saturating_accrue(ccm.amount);
+		}
+		amounts.retain(|_, amount| !amount.is_zero());
+		amounts
+	}
+
+	/// Marks a fetch as scheduled on the channel at `deposit_address` if the channel can currently
+	/// be fetched from, and sets the fetch id of the request. Returns whether the fetch was
+	/// scheduled.
+	///
+	/// Channels are read from storage once and then updated in `channels_to_fetch`, so that
+	/// several fetches from the same channel see each other's changes.
+	fn schedule_fetch(
+		channels_to_fetch: &mut BTreeMap<
+			TargetChainAccount<T, I>,
+			(DepositChannelDetails<T, I>, bool),
+		>,
+		deposit_address: &TargetChainAccount<T, I>,
+		deposit_fetch_id: &mut Option<<T::TargetChain as Chain>::DepositFetchId>,
+	) -> bool {
+		let (details, modified) = match channels_to_fetch.entry(deposit_address.clone()) {
+			btree_map::Entry::Occupied(entry) => entry.into_mut(),
+			btree_map::Entry::Vacant(entry) =>
+				match DepositChannelLookup::<T, I>::get(deposit_address) {
+					Some(details) => entry.insert((details, false)),
+					None => return false,
+				},
+		};
+		let can_fetch = details.deposit_channel.state.can_fetch();
+		if can_fetch {
+			deposit_fetch_id.replace(details.deposit_channel.fetch_id());
+			*modified |= details.deposit_channel.state.on_fetch_scheduled();
+		}
+		can_fetch
+	}
+
+	/// The weight of the fetches that `on_finalize` may egress this block. Every scheduled request
+	/// is assumed to be a fetch from a distinct channel, up to the chain's fetch limit.
+	fn scheduled_fetches_weight() -> Weight {
+		let scheduled = ScheduledEgressFetchOrTransfer::<T, I>::decode_len().unwrap_or_default();
+		let fetches = T::FetchesTransfersLimitProvider::maybe_fetches_limit()
+			.map_or(scheduled, |limit| scheduled.min(limit));
+		T::DbWeight::get().reads(1).saturating_add(if fetches > 0 {
+			T::WeightInfo::egress_scheduled_fetches(fetches as u32)
+		} else {
+			Weight::zero()
+		})
+	}
+
 	/// Take all scheduled egress requests and send them out in an `AllBatch` call.
 	///
 	/// Note: Egress transactions with Blacklisted assets are not sent, and kept in storage.
 	#[transactional]
-	fn do_egress_scheduled_fetch_transfer() -> Result<(), AllBatchError> {
+	fn do_egress_scheduled_fetch_transfer(
+		budget: &mut BroadcastBudgetTracker,
+	) -> Result<(), AllBatchError> {
+		if !budget.has_budget() {
+			return Ok(())
+		}
+
+		// The channels of the fetches in this batch, and whether scheduling a fetch changed their
+		// state. Changed channels are written back once the batch is assembled, rather than once
+		// per fetch.
+		let mut channels_to_fetch = BTreeMap::new();
+
 		let batch_to_send: Vec<_> =
 			ScheduledEgressFetchOrTransfer::<T, I>::mutate(|requests: &mut Vec<_>| {
 				let mut maybe_no_of_transfers_remaining =
 					T::FetchesTransfersLimitProvider::maybe_transfers_limit();
 				let mut maybe_no_of_fetches_remaining =
 					T::FetchesTransfersLimitProvider::maybe_fetches_limit();
+				// Serve the longest-waiting requests first so that, when throughput is limited,
+				// older egresses can't be starved by newer ones.
+				requests.sort_by_key(|request| request.scheduled_at());
 				// Filter out disabled assets and requests that are not ready to be egressed.
 				requests
 					.extract_if(|request| {
@@ -1632,28 +4524,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 								} =>
 									Self::should_fetch_or_transfer(
 										&mut maybe_no_of_fetches_remaining,
-									) && DepositChannelLookup::<T, I>::mutate(
+									) && Self::schedule_fetch(
+										&mut channels_to_fetch,
 										deposit_address,
-										|details| {
-											details
-												.as_mut()
-												.map(|details| {
-													let can_fetch =
-														details.deposit_channel.state.can_fetch();
-
-													if can_fetch {
-														deposit_fetch_id.replace(
-															details.deposit_channel.fetch_id(),
-														);
-														details
-															.deposit_channel
-															.state
-															.on_fetch_scheduled();
-													}
-													can_fetch
-												})
-												.unwrap_or(false)
-										},
+										deposit_fetch_id,
 									),
 								FetchOrTransfer::Transfer { .. } => Self::should_fetch_or_transfer(
 									&mut maybe_no_of_transfers_remaining,
@@ -1663,13 +4537,30 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					.collect()
 			});
 
+		for (deposit_address, (details, modified)) in channels_to_fetch {
+			if modified {
+				DepositChannelLookup::<T, I>::insert(deposit_address, details);
+			}
+		}
+
 		if batch_to_send.is_empty() {
 			return Ok(())
 		}
 
+		let fetches = batch_to_send
+			.iter()
+			.filter(|request| matches!(request, FetchOrTransfer::Fetch { .. }))
+			.count() as u32;
+		Self::record_egress_usage(|usage| {
+			usage.fetches.saturating_accrue(fetches);
+			usage.transfers.saturating_accrue(batch_to_send.len() as u32 - fetches);
+		});
+
 		let mut fetch_params = vec![];
 		let mut transfer_params = vec![];
 		let mut addresses = vec![];
+		let mut egress_scheduled_at = BTreeMap::new();
+		let mut egress_origins = BTreeMap::new();
 
 		for request in batch_to_send {
 			match request {
@@ -1690,9 +4581,15 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					amount,
 					destination_address,
 					egress_id,
+					scheduled_at,
+					memo,
+					origin,
 				} => {
+					Self::reduce_pending_egress_amount(asset, amount);
+					egress_scheduled_at.insert(egress_id, scheduled_at);
+					egress_origins.insert(egress_id, origin);
 					transfer_params.push((
-						TransferAssetParams { asset, amount, to: destination_address },
+						TransferAssetParams { asset, amount, to: destination_address, memo },
 						egress_id,
 					));
 				},
@@ -1711,10 +4608,32 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						Some(Call::finalise_ingress { addresses: addresses.clone() }.into()),
 						|_| None,
 					);
+					budget.record_broadcast();
+					let wait_times = egress_ids
+						.iter()
+						.filter_map(|egress_id| {
+							egress_scheduled_at.get(egress_id).map(|scheduled_at| {
+								(*egress_id, Self::blocks_waited_since(*scheduled_at))
+							})
+						})
+						.collect::<Vec<_>>();
+					let origins = egress_ids
+						.iter()
+						.filter_map(|egress_id| {
+							egress_origins.get(egress_id).map(|origin| (*egress_id, *origin))
+						})
+						.collect::<Vec<_>>();
 					Self::deposit_event(Event::<T, I>::BatchBroadcastRequested {
 						broadcast_id,
 						egress_ids,
+						egress_origins: origins,
 					});
+					if !wait_times.is_empty() {
+						Self::deposit_event(Event::<T, I>::EgressWaitTimes {
+							broadcast_id,
+							wait_times,
+						});
+					}
 				});
 				Ok(())
 			},
@@ -1725,49 +4644,215 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 	/// Send all scheduled Cross Chain Messages out to the target chain.
 	///
-	/// Blacklisted assets are not sent and will remain in storage.
-	fn do_egress_scheduled_ccm() {
+	/// Blacklisted assets are not sent and will remain in storage. Messages that can't be built
+	/// yet are deferred until they can be built or expire.
+	fn do_egress_scheduled_ccm(budget: &mut BroadcastBudgetTracker) {
 		let mut maybe_no_of_transfers_remaining =
 			T::FetchesTransfersLimitProvider::maybe_ccm_limit();
+		let mut maybe_no_of_broadcasts_remaining =
+			budget.remaining.map(|remaining| remaining as usize);
 
 		let ccms_to_send: Vec<CrossChainMessage<T::TargetChain>> =
 			ScheduledEgressCcm::<T, I>::mutate(|ccms: &mut Vec<_>| {
+				// Oldest messages first, see `do_egress_scheduled_fetch_transfer`.
+				ccms.sort_by_key(|ccm| ccm.scheduled_at);
 				// Filter out disabled assets, and take up to batch_size requests to be sent.
 				ccms.extract_if(|ccm| {
 					!DisabledEgressAssets::<T, I>::contains_key(ccm.asset()) &&
-						Self::should_fetch_or_transfer(&mut maybe_no_of_transfers_remaining)
+						Self::should_fetch_or_transfer(&mut maybe_no_of_transfers_remaining) &&
+						{
+							let within_budget = Self::should_fetch_or_transfer(
+								&mut maybe_no_of_broadcasts_remaining,
+							);
+							budget.exhausted |= !within_budget;
+							within_budget
+						}
 				})
 				.collect()
 			});
+		if !ccms_to_send.is_empty() {
+			Self::record_egress_usage(|usage| {
+				usage.ccms.saturating_accrue(ccms_to_send.len() as u32);
+			});
+		}
 		for ccm in ccms_to_send {
+			// The output amount is only known once the egress fee has been withheld.
+			let mut message = ccm.message.to_vec();
+			substitute_ccm_placeholder(
+				&mut message,
+				&CCM_OUTPUT_AMOUNT_PLACEHOLDER,
+				ccm.amount.into(),
+				T::TargetChain::get(),
+			);
+
 			match <T::ChainApiCall as ExecutexSwapAndCall<T::TargetChain>>::new_unsigned(
 				TransferAssetParams {
 					asset: ccm.asset,
 					amount: ccm.amount,
-					to: ccm.destination_address,
+					to: ccm.destination_address.clone(),
+					memo: None,
 				},
 				ccm.source_chain,
-				ccm.source_address,
+				ccm.source_address.clone(),
 				ccm.gas_budget,
-				ccm.message.to_vec(),
+				message,
 				ccm.ccm_additional_data.to_vec(),
 			) {
 				Ok(api_call) => {
+					Self::reduce_pending_egress_amount(ccm.asset, ccm.amount);
+					CcmDeferrals::<T, I>::remove(ccm.egress_id);
 					let broadcast_id = T::Broadcaster::threshold_sign_and_broadcast_with_callback(
 						api_call,
 						None,
 						|broadcast_id| Some(Call::ccm_broadcast_failed { broadcast_id }.into()),
 					);
+					budget.record_broadcast();
 					Self::deposit_event(Event::<T, I>::CcmBroadcastRequested {
 						broadcast_id,
 						egress_id: ccm.egress_id,
+						origin: ccm.origin,
+					});
+					Self::deposit_event(Event::<T, I>::EgressWaitTimes {
+						broadcast_id,
+						wait_times: vec![(
+							ccm.egress_id,
+							Self::blocks_waited_since(ccm.scheduled_at),
+						)],
 					});
 				},
-				Err(error) => Self::deposit_event(Event::<T, I>::CcmEgressInvalid {
-					egress_id: ccm.egress_id,
-					error,
-				}),
-			};
+				Err(ExecutexSwapAndCallError::NotReady) => Self::defer_ccm(ccm),
+				Err(error) => {
+					Self::reduce_pending_egress_amount(ccm.asset, ccm.amount);
+					CcmDeferrals::<T, I>::remove(ccm.egress_id);
+					Self::deposit_event(Event::<T, I>::CcmEgressInvalid {
+						egress_id: ccm.egress_id,
+						error,
+					});
+				},
+			};
+		}
+	}
+
+	/// The witness safety margin recommended based on the observed reorgs, ie. the depth of the
+	/// deepest recently observed reorg. `None` if no reorgs have been observed.
+	pub fn recommended_witness_safety_margin() -> Option<TargetChainBlockNumber<T, I>> {
+		ObservedReorgs::<T, I>::get().into_iter().map(|(_, depth)| depth).max()
+	}
+
+	/// Sets the witness safety margin to the recommended margin, clamped to the configured bounds.
+	/// Does nothing if automatic tuning is disabled.
+	fn adjust_witness_safety_margin() {
+		if let Some(WitnessSafetyMarginBounds { min, max }) =
+			WitnessSafetyMarginAutoTuning::<T, I>::get()
+		{
+			let new_margin =
+				Self::recommended_witness_safety_margin().unwrap_or(min).clamp(min, max);
+			let old_margin = WitnessSafetyMargin::<T, I>::get();
+			if old_margin != Some(new_margin) {
+				WitnessSafetyMargin::<T, I>::put(new_margin);
+				Self::deposit_event(Event::<T, I>::WitnessSafetyMarginChanged {
+					old_margin,
+					new_margin,
+				});
+			}
+		}
+	}
+
+	/// Re-schedules a CCM that can't be built yet, or sends its funds to the fallback address if
+	/// it has been deferred for longer than the [CcmDeferralTimeout].
+	fn defer_ccm(ccm: CrossChainMessage<T::TargetChain>) {
+		let current_block = Self::current_block_number();
+		let deferral = CcmDeferrals::<T, I>::mutate(ccm.egress_id, |deferral| {
+			let deferral =
+				deferral.get_or_insert(CcmDeferral { count: 0, first_deferred_at: current_block });
+			deferral.count.saturating_accrue(1);
+			*deferral
+		});
+
+		if deferral.count == 1 {
+			Self::deposit_event(Event::<T, I>::CcmEgressDeferred { egress_id: ccm.egress_id });
+		}
+
+		if current_block.saturating_sub(deferral.first_deferred_at) <
+			CcmDeferralTimeout::<T, I>::get()
+		{
+			ScheduledEgressCcm::<T, I>::append(ccm);
+			return
+		}
+
+		CcmDeferrals::<T, I>::remove(ccm.egress_id);
+		Self::reduce_pending_egress_amount(ccm.asset, ccm.amount);
+		let fallback_egress = Self::ccm_fallback_address(&ccm).and_then(|fallback_address| {
+			Self::schedule_ccm_refund(ccm.asset, ccm.amount, fallback_address)
+		});
+		Self::deposit_event(Event::<T, I>::CcmEgressExpired {
+			egress_id: ccm.egress_id,
+			deferral,
+			fallback_egress,
+		});
+	}
+
+	/// The address that the funds of a CCM can be sent to if the message can't be delivered. Only
+	/// Solana CCMs specify a fallback address.
+	fn ccm_fallback_address(
+		ccm: &CrossChainMessage<T::TargetChain>,
+	) -> Option<TargetChainAccount<T, I>> {
+		match T::CcmValidityChecker::check_and_decode(
+			&CcmChannelMetadata {
+				message: ccm.message.clone(),
+				gas_budget: ccm.gas_budget,
+				ccm_additional_data: ccm.ccm_additional_data.clone(),
+			},
+			ccm.asset.into(),
+		) {
+			Ok(DecodedCcmAdditionalData::Solana(VersionedSolanaCcmAdditionalData::V0(
+				ccm_accounts,
+			))) => ForeignChainAddress::Sol(ccm_accounts.fallback_address.into()).try_into().ok(),
+			_ => None,
+		}
+	}
+
+	fn do_consolidate_utxos(block_number: BlockNumberFor<T>, budget: &mut BroadcastBudgetTracker) {
+		if !T::SafeMode::get().consolidation_enabled || !budget.has_budget() {
+			return
+		}
+		let block_number: BlockNumber = block_number.unique_saturated_into();
+		let interval = ConsolidationInterval::<T, I>::get();
+		if interval > 1 && block_number % interval != 0 {
+			return
+		}
+		match <T::ChainApiCall as ConsolidateCall<T::TargetChain>>::consolidate_utxos() {
+			Ok(egress_transaction) => {
+				let (broadcast_id, _) =
+					T::Broadcaster::threshold_sign_and_broadcast(egress_transaction);
+				budget.record_broadcast();
+				Self::record_egress_total(
+					EgressOrigin::Consolidation,
+					<T::TargetChain as Chain>::GAS_ASSET,
+					Zero::zero(),
+				);
+				Self::deposit_event(Event::<T, I>::UtxoConsolidation { broadcast_id });
+				SkippedConsolidations::<T, I>::kill();
+			},
+			Err(ConsolidationError::NotRequired) =>
+				if SkippedConsolidations::<T, I>::exists() {
+					SkippedConsolidations::<T, I>::kill();
+				},
+			Err(reason) => {
+				let consecutive_skips = SkippedConsolidations::<T, I>::mutate(|skipped| {
+					let consecutive_skips = skipped.map_or(1, |(count, _)| count.saturating_add(1));
+					*skipped = Some((consecutive_skips, reason));
+					consecutive_skips
+				});
+				log::warn!("UTXO consolidation skipped: {reason:?}");
+				// Only warn periodically to avoid emitting an event every block.
+				if consecutive_skips % CONSOLIDATION_SKIP_WARNING_THRESHOLD == 0 {
+					Self::deposit_event(Event::<T, I>::UtxoConsolidationSkipped {
+						consecutive_skips,
+						reason,
+					});
+				}
+			},
 		}
 	}
 
@@ -1778,9 +4863,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		required_amount: TargetChainAmount<T, I>,
 		max_boost_fee_bps: BasisPoints,
 		prewitnessed_deposit_id: PrewitnessedDepositId,
+		risk_score: BasisPoints,
 	) -> Result<BoostOutput<T::TargetChain>, DispatchError> {
 		let mut remaining_amount = required_amount;
 
+		let mut skipped_due_to_risk = false;
+
 		let mut total_fee_amount: TargetChainAmount<T, I> = 0u32.into();
 
 		let mut used_pools = BTreeMap::new();
@@ -1804,6 +4892,13 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				break
 			}
 
+			if BoostRiskThresholds::<T, I>::get(boost_tier)
+				.is_some_and(|threshold| risk_score > threshold)
+			{
+				skipped_due_to_risk = true;
+				continue
+			}
+
 			// For each fee tier, get the amount that the pool is boosting and the boost fee
 			let (boosted_amount, fee) = BoostPools::<T, I>::mutate(asset, boost_tier, |pool| {
 				let pool = match pool {
@@ -1837,7 +4932,54 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			}
 		}
 
-		Err("Insufficient boost funds".into())
+		if skipped_due_to_risk {
+			Err(Error::<T, I>::BoostRiskThresholdExceeded.into())
+		} else {
+			Err("Insufficient boost funds".into())
+		}
+	}
+
+	fn prewitness_or_defer(prewitness: PendingPrewitness<T, I>) {
+		match PrewitnessOrder::<T, I>::get() {
+			PrewitnessProcessingOrder::Immediate => Self::process_prewitness(prewitness),
+			PrewitnessProcessingOrder::EndOfBlock =>
+				PendingPrewitnesses::<T, I>::append(prewitness),
+		}
+	}
+
+	fn process_prewitness(prewitness: PendingPrewitness<T, I>) {
+		match prewitness {
+			PendingPrewitness::DepositChannel { deposit_witness, block_height } => {
+				// TODO: emit event on error?
+				let _ = Self::process_channel_deposit_prewitness(deposit_witness, block_height);
+			},
+			PendingPrewitness::Vault { vault_witness, block_height } =>
+				Self::process_vault_swap_request_prewitness(block_height, *vault_witness),
+		}
+	}
+
+	fn process_pending_prewitnesses() {
+		for prewitness in PendingPrewitnesses::<T, I>::take() {
+			Self::process_prewitness(prewitness);
+		}
+		FullyWitnessedThisBlock::<T, I>::kill();
+	}
+
+	/// Returns true if the deposit has already been fully witnessed in the current block, in which
+	/// case there is no need to boost it.
+	fn fully_witnessed_this_block(
+		tx_id: &TransactionInIdFor<T, I>,
+		block_height: TargetChainBlockNumber<T, I>,
+	) -> bool {
+		if FullyWitnessedThisBlock::<T, I>::get().contains(tx_id) {
+			Self::deposit_event(Event::<T, I>::BoostSkippedDueToFullWitness {
+				tx_id: tx_id.clone(),
+				block_height,
+			});
+			true
+		} else {
+			false
+		}
 	}
 
 	fn process_channel_deposit_prewitness(
@@ -1846,6 +4988,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		>,
 		block_height: TargetChainBlockNumber<T, I>,
 	) -> DispatchResult {
+		if let Some(tx_id) = deposit_details.deposit_id() {
+			if Self::fully_witnessed_this_block(&tx_id, block_height) {
+				return Ok(())
+			}
+		}
+
 		let DepositChannelDetails {
 			deposit_channel, action, boost_fee, boost_status, owner, ..
 		} = DepositChannelLookup::<T, I>::get(&deposit_address)
@@ -1898,7 +5046,58 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				channel_metadata,
 				refund_params,
 				dca_params,
+				tracking_id,
+				egress_memo,
 			} => {
+				// Deposits on behalf of a suspended broker are refunded instead of swapped. Without
+				// a refund address the swap goes ahead: the broker's fees can't be withdrawn
+				// while it is suspended.
+				if let Some(refund_params) = refund_params.as_ref().filter(|_| {
+					origin.broker_id().is_some_and(T::SwapRequestHandler::is_broker_suspended)
+				}) {
+					return Self::perform_channel_action(
+						ChannelAction::Refund {
+							refund_address: refund_params.refund_address.clone(),
+							reason: DepositFailedReason::BrokerSuspended,
+						},
+						asset,
+						source_address,
+						amount_after_fees,
+						origin,
+					)
+				}
+
+				// Vault deposits split across several transactions are merged into one request.
+				let aggregation_key = match origin {
+					DepositOrigin::Vault { .. }
+						if channel_metadata.is_none() &&
+							VaultSwapAggregationWindow::<T, I>::get() > 0 =>
+						Some(frame_support::Hashable::blake2_256(&(
+							asset,
+							destination_asset,
+							&destination_address,
+							&broker_fees,
+							&refund_params,
+							&dca_params,
+							tracking_id,
+						))),
+					_ => None,
+				};
+
+				if let Some((swap_request_id, _)) =
+					aggregation_key.and_then(AggregatableVaultSwaps::<T, I>::get).filter(
+						|(_, expires_at)| *expires_at > frame_system::Pallet::<T>::block_number(),
+					) {
+					if T::SwapRequestHandler::top_up_swap_request(
+						swap_request_id,
+						amount_after_fees.into(),
+					)
+					.is_ok()
+					{
+						return DepositAction::SwapToppedUp { swap_request_id }
+					}
+				}
+
 				let deposit_metadata = channel_metadata.map(|metadata| CcmDepositMetadata {
 					channel_metadata: metadata,
 					source_chain: asset.into(),
@@ -1912,14 +5111,45 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					SwapRequestType::Regular {
 						ccm_deposit_metadata: deposit_metadata,
 						output_address: destination_address,
+						egress_memo,
 					},
 					broker_fees,
 					refund_params,
 					dca_params,
 					origin.into(),
+					tracking_id,
 				);
+
+				if let Some(key) = aggregation_key {
+					let expires_at = frame_system::Pallet::<T>::block_number() +
+						VaultSwapAggregationWindow::<T, I>::get().into();
+					AggregatableVaultSwaps::<T, I>::insert(key, (swap_request_id, expires_at));
+					AggregatableVaultSwapsExpireAt::<T, I>::append(expires_at, key);
+				}
+
 				DepositAction::Swap { swap_request_id }
 			},
+			ChannelAction::Refund { refund_address, reason } => {
+				let egress_id = refund_address
+					.try_into()
+					.ok()
+					.and_then(|refund_address| {
+						Self::schedule_egress(
+							asset,
+							amount_after_fees,
+							refund_address,
+							None,
+							None,
+							EgressOrigin::Refund,
+						)
+						.map_err(|e| {
+							log::warn!("Failed to schedule refund of vault deposit: {e:?}")
+						})
+						.ok()
+					})
+					.map(|ScheduledEgressDetails { egress_id, .. }| egress_id);
+				DepositAction::Refund { reason, amount: amount_after_fees, egress_id }
+			},
 		}
 	}
 
@@ -1931,11 +5161,11 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 	) {
 		Self::process_channel_deposit_full_witness_inner(&deposit_witness, block_height)
 			.unwrap_or_else(|e| {
-				Self::deposit_event(Event::<T, I>::DepositFailed {
+				Self::deposit_failed(
 					block_height,
-					reason: DepositFailedReason::DepositWitnessRejected(e),
-					details: DepositFailedDetails::DepositChannel { deposit_witness },
-				});
+					DepositFailedReason::DepositWitnessRejected(e),
+					DepositFailedDetails::DepositChannel { deposit_witness },
+				);
 			})
 	}
 
@@ -1966,6 +5196,33 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			return Err(Error::<T, I>::InvalidDepositAddress.into())
 		}
 
+		// Deposits marked for rejection are refunded instead, which consumes the mark, so they
+		// can't be refunded twice.
+		let marked_for_rejection = deposit_details.deposit_id().is_some_and(|tx_id| {
+			TransactionsMarkedForRejection::<T, I>::contains_key(
+				&deposit_channel_details.owner,
+				&tx_id,
+			)
+		});
+		// Deposits can only be recognised as duplicates if the chain can identify them.
+		if let Some(unique_id) = deposit_details.unique_deposit_id() {
+			let key = (deposit_address.clone(), unique_id, block_height);
+			if ProcessedDeposits::<T, I>::contains_key(&key) && !marked_for_rejection {
+				Self::deposit_event(Event::<T, I>::DuplicateDepositIgnored {
+					deposit_address: deposit_address.clone(),
+					asset: *asset,
+					amount: *amount,
+					deposit_details: deposit_details.clone(),
+					block_height,
+				});
+				return Ok(())
+			}
+			Self::record_processed_deposit(key);
+		}
+		if let Some(tx_id) = deposit_details.deposit_id() {
+			FullyWitnessedThisBlock::<T, I>::append(&tx_id);
+		}
+
 		let deposit_origin = DepositOrigin::deposit_channel(
 			deposit_address.clone(),
 			channel_id,
@@ -1995,10 +5252,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				});
 			},
 			Err(reason) => {
-				Self::deposit_event(Event::<T, I>::DepositFailed {
+				Self::deposit_failed(
 					block_height,
 					reason,
-					details: DepositFailedDetails::DepositChannel {
+					DepositFailedDetails::DepositChannel {
 						deposit_witness: DepositWitness {
 							deposit_address: deposit_address.clone(),
 							asset: *asset,
@@ -2006,7 +5263,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 							deposit_details: deposit_details.clone(),
 						},
 					},
-				});
+				);
 			},
 			_ => {},
 		};
@@ -2074,7 +5331,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		block_height: TargetChainBlockNumber<T, I>,
 		origin: DepositOrigin<T, I>,
 	) -> Option<BoostStatus<TargetChainAmount<T, I>>> {
-		if amount < MinimumDeposit::<T, I>::get(asset) {
+		if amount < Self::update_minimum_deposit(asset) {
 			// We do not process/record pre-witnessed deposits for amounts smaller
 			// than MinimumDeposit to match how this is done on finalisation
 			return None;
@@ -2109,7 +5366,31 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			boost_fee > 0 &&
 			!matches!(boost_status, BoostStatus::Boosted { .. })
 		{
-			match Self::try_boosting(asset, amount, boost_fee, prewitnessed_deposit_id) {
+			let current_exposure = BoostExposure::<T, I>::get(asset);
+			if let Some(cap) = BoostExposureCaps::<T, I>::get(asset)
+				.filter(|cap| current_exposure.saturating_add(amount) > *cap)
+			{
+				Self::deposit_event(Event::BoostSkippedDueToExposureCap {
+					prewitnessed_deposit_id,
+					asset,
+					amount_attempted: amount,
+					current_exposure,
+					cap,
+					channel_id,
+					origin_type: origin.into(),
+				});
+				return None;
+			}
+
+			let risk_score = T::DepositRiskScorer::risk_score(
+				asset,
+				amount,
+				&deposit_details,
+				source_address.as_ref(),
+			);
+
+			match Self::try_boosting(asset, amount, boost_fee, prewitnessed_deposit_id, risk_score)
+			{
 				Ok(BoostOutput { used_pools, total_fee: boost_fee_amount }) => {
 					let amount_after_boost_fee = amount.saturating_sub(boost_fee_amount);
 
@@ -2121,9 +5402,22 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 							asset,
 							amount_after_boost_fee,
 							&origin,
+							&deposit_details,
 						);
 
 					let used_pool_tiers = used_pools.keys().cloned().collect();
+					let tracking_id = action.tracking_id();
+
+					BoostExposure::<T, I>::mutate(asset, |exposure| {
+						exposure.saturating_accrue(amount)
+					});
+
+					if BoostFeeRebate::<T, I>::exists() {
+						BoostedDepositPrewitnessHeights::<T, I>::insert(
+							prewitnessed_deposit_id,
+							block_height,
+						);
+					}
 
 					let action = Self::perform_channel_action(
 						action,
@@ -2133,6 +5427,20 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						origin.clone(),
 					);
 
+					// The deposit can still be marked for rejection until the swap completes.
+					if let (
+						DepositAction::Swap { swap_request_id } |
+						DepositAction::CcmTransfer { swap_request_id },
+						Some(tx_id),
+						Some(broker_id),
+					) = (&action, deposit_details.deposit_id(), origin.broker_id())
+					{
+						BoostedSwapRequests::<T, I>::insert(
+							swap_request_id,
+							(broker_id.clone(), tx_id),
+						);
+					}
+
 					Self::deposit_event(Event::DepositBoosted {
 						deposit_address,
 						asset,
@@ -2146,6 +5454,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						boost_fee: boost_fee_amount,
 						action,
 						origin_type: origin.into(),
+						tracking_id,
 					});
 
 					return Some(BoostStatus::Boosted {
@@ -2154,6 +5463,16 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						amount,
 					});
 				},
+				Err(error) if error == Error::<T, I>::BoostRiskThresholdExceeded.into() => {
+					Self::deposit_event(Event::BoostSkippedDueToRisk {
+						prewitnessed_deposit_id,
+						asset,
+						amount_attempted: amount,
+						risk_score,
+						channel_id,
+						origin_type: origin.into(),
+					});
+				},
 				Err(_) => {
 					Self::deposit_event(Event::InsufficientBoostLiquidity {
 						prewitnessed_deposit_id,
@@ -2169,15 +5488,16 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		None
 	}
 
-	fn process_vault_swap_request_prewitness(
-		block_height: TargetChainBlockNumber<T, I>,
+	/// Validates a vault swap and determines the action to take for its deposit. Swaps that fail
+	/// validation for a refundable reason are refunded to the refund address instead, if one was
+	/// provided. Any other validation failure is returned as an error.
+	///
+	/// Refund parameters that are out of range but can be safely corrected are adjusted rather
+	/// than refunded, and the adjustments are returned as warnings.
+	fn vault_swap_action(
 		VaultDepositWitness {
-			input_asset: asset,
-			deposit_address,
-			channel_id,
-			deposit_amount: amount,
-			deposit_details,
-			output_asset,
+			input_asset: source_asset,
+			output_asset: destination_asset,
 			destination_address,
 			deposit_metadata,
 			tx_id,
@@ -2185,57 +5505,148 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			affiliate_fees,
 			refund_params,
 			dca_params,
-			boost_fee,
-		}: VaultDepositWitness<T, I>,
-	) {
+			tracking_id,
+			..
+		}: &VaultDepositWitness<T, I>,
+	) -> Result<
+		(ChannelAction<T::AccountId>, Option<ForeignChainAddress>, Vec<VaultSwapParameterWarning>),
+		DepositFailedReason,
+	> {
 		let destination_address_internal =
-			match T::AddressConverter::decode_and_validate_address_for_asset(
+			T::AddressConverter::decode_and_validate_address_for_asset(
 				destination_address.clone(),
-				output_asset,
-			) {
-				Ok(address) => address,
-				Err(err) => {
-					log::warn!("Failed to process vault swap due to invalid destination address. Tx hash: {tx_id:?}. Error: {err:?}");
-					return;
-				},
-			};
+				*destination_asset,
+			)
+			.map_err(|_| DepositFailedReason::InvalidDestinationAddress)?;
+
+		let (channel_metadata, source_address) = if let Some(metadata) = deposit_metadata.clone() {
+			T::CcmValidityChecker::check_and_decode(&metadata.channel_metadata, *destination_asset)
+				.map_err(|_| DepositFailedReason::CcmInvalidMetadata)?;
+
+			T::SwapLimitsProvider::validate_ccm_limits(
+				&metadata.channel_metadata,
+				(*destination_asset).into(),
+			)?;
+
+			let destination_chain: ForeignChain = (*destination_asset).into();
+			if !destination_chain.ccm_support() {
+				return Err(DepositFailedReason::CcmUnsupportedForTargetChain);
+			}
+
+			(Some(metadata.channel_metadata), metadata.source_address)
+		} else {
+			(None, None)
+		};
 
-		if let Some(metadata) = deposit_metadata.clone() {
-			if T::CcmValidityChecker::check_and_decode(&metadata.channel_metadata, output_asset)
-				.is_err()
+		let mut warnings = Vec::new();
+		let refund_params = if let Some(refund_params) = refund_params {
+			let mut refund_params = refund_params.clone();
+			if T::SwapLimitsProvider::validate_refund_params(refund_params.retry_duration).is_err()
 			{
-				log::warn!("Failed to process vault swap due to invalid CCM metadata");
-				return;
+				let max_retry_duration =
+					T::SwapLimitsProvider::get_swap_limits().max_swap_retry_duration_blocks;
+				warnings.push(VaultSwapParameterWarning::RetryDurationClamped {
+					encoded: refund_params.retry_duration,
+					clamped_to: max_retry_duration,
+				});
+				refund_params.retry_duration = max_retry_duration;
+			}
+			if T::SwapLimitsProvider::validate_min_price(
+				(*source_asset).into(),
+				*destination_asset,
+				refund_params.min_price,
+			)
+			.is_err()
+			{
+				warnings.push(VaultSwapParameterWarning::MinPriceLikelyInverted);
 			}
+			Some(refund_params)
+		} else {
+			log::warn!("No refund parameter provided for tx id: {tx_id:?}!");
+			None
+		};
 
-			let destination_chain: ForeignChain = output_asset.into();
-			if !destination_chain.ccm_support() {
+		let broker_fees = Self::assemble_broker_fees(broker_fee.clone(), affiliate_fees.clone());
+
+		// The remaining checks don't affect our ability to refund the deposit.
+		let refund_reason = if T::SwapLimitsProvider::validate_broker_fees(&broker_fees).is_err() {
+			Some(DepositFailedReason::InvalidBrokerFees)
+		} else if dca_params
+			.as_ref()
+			.is_some_and(|params| T::SwapLimitsProvider::validate_dca_params(params).is_err())
+		{
+			Some(DepositFailedReason::InvalidDcaParameters)
+		} else if T::SwapLimitsProvider::validate_destination(&destination_address_internal)
+			.is_err()
+		{
+			Some(DepositFailedReason::DestinationChainWindingDown)
+		} else if let (Some(_), Some(broker)) = (deposit_metadata, broker_fee) {
+			T::SwapLimitsProvider::validate_ccm_destination(
+				&broker.account,
+				&destination_address_internal,
+			)
+			.err()
+			.map(|_| DepositFailedReason::CcmDestinationNotAllowed)
+		} else {
+			None
+		};
+
+		let action = match (refund_reason, refund_params) {
+			(None, refund_params) => ChannelAction::Swap {
+				destination_asset: *destination_asset,
+				destination_address: destination_address_internal,
+				broker_fees,
+				channel_metadata,
+				refund_params,
+				dca_params: dca_params.clone(),
+				tracking_id: *tracking_id,
+				egress_memo: None,
+			},
+			(Some(reason), Some(refund_params)) =>
+				ChannelAction::Refund { refund_address: refund_params.refund_address, reason },
+			(Some(reason), None) => return Err(reason),
+		};
+
+		Ok((action, source_address, warnings))
+	}
+
+	fn process_vault_swap_request_prewitness(
+		block_height: TargetChainBlockNumber<T, I>,
+		vault_deposit_witness: VaultDepositWitness<T, I>,
+	) {
+		if Self::fully_witnessed_this_block(&vault_deposit_witness.tx_id, block_height) {
+			return;
+		}
+
+		let (action, source_address, _) = match Self::vault_swap_action(&vault_deposit_witness) {
+			Ok(result) => result,
+			Err(reason) => {
 				log::warn!(
-					"Failed to process vault swap due to destination chain not supporting CCM"
+					"Not prewitnessing vault swap with tx id {:?}: {reason:?}",
+					vault_deposit_witness.tx_id
 				);
 				return;
-			}
-		}
+			},
+		};
+
+		let VaultDepositWitness {
+			input_asset: asset,
+			deposit_address,
+			channel_id,
+			deposit_amount: amount,
+			deposit_details,
+			tx_id,
+			broker_fee,
+			boost_fee,
+			verified_sender,
+			..
+		} = vault_deposit_witness;
 
 		let origin = DepositOrigin::vault(
 			tx_id.clone(),
 			broker_fee.as_ref().map(|Beneficiary { account, .. }| account.clone()),
+			verified_sender,
 		);
-		let broker_fees = Self::assemble_broker_fees(broker_fee, affiliate_fees);
-
-		let (channel_metadata, source_address) = match deposit_metadata {
-			Some(metadata) => (Some(metadata.channel_metadata), metadata.source_address),
-			None => (None, None),
-		};
-
-		let action = ChannelAction::Swap {
-			destination_asset: output_asset,
-			destination_address: destination_address_internal,
-			broker_fees,
-			refund_params,
-			dca_params,
-			channel_metadata,
-		};
 
 		let boost_status =
 			BoostedVaultTransactions::<T, I>::get(&tx_id).unwrap_or(BoostStatus::NotBoosted);
@@ -2257,6 +5668,47 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 	}
 
+	/// Whether a fully witnessed deposit amount is close enough to the boosted amount for the
+	/// boost to be finalised, according to [BoostFinalisationTolerance]. A shortfall is only
+	/// tolerated if the deposit still covers the amount owed to the boosters, i.e. if it can be
+	/// absorbed by the network fee taken from the boost.
+	fn boosted_amount_matches(
+		asset: TargetChainAsset<T, I>,
+		prewitnessed_deposit_id: PrewitnessedDepositId,
+		used_pools: &[BoostPoolTier],
+		boosted_amount: TargetChainAmount<T, I>,
+		deposit_amount: TargetChainAmount<T, I>,
+	) -> bool {
+		if boosted_amount == deposit_amount {
+			return true
+		}
+		if deposit_amount < boosted_amount {
+			let amount_owed_to_boosters = used_pools
+				.iter()
+				.filter_map(|boost_tier| BoostPools::<T, I>::get(asset, boost_tier))
+				.map(|pool| pool.get_amount_owed_for_boost(prewitnessed_deposit_id))
+				.fold(0u32.into(), |total: TargetChainAmount<T, I>, owed| {
+					total.saturating_add(owed)
+				});
+			if deposit_amount < amount_owed_to_boosters {
+				return false
+			}
+		}
+		let difference = if boosted_amount > deposit_amount {
+			boosted_amount.saturating_sub(deposit_amount)
+		} else {
+			deposit_amount.saturating_sub(boosted_amount)
+		};
+		match BoostFinalisationTolerance::<T, I>::get() {
+			Some(BoostAmountTolerance::Absolute(tolerance)) => difference <= tolerance,
+			Some(BoostAmountTolerance::BasisPoints(bps)) =>
+				Into::<AssetAmount>::into(difference) <=
+					Permill::from_parts(bps as u32 * BASIS_POINTS_PER_MILLION) *
+						Into::<AssetAmount>::into(boosted_amount),
+			None => false,
+		}
+	}
+
 	fn process_full_witness_deposit_inner(
 		deposit_address: Option<TargetChainAccount<T, I>>,
 		asset: TargetChainAsset<T, I>,
@@ -2270,8 +5722,29 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		block_height: TargetChainBlockNumber<T, I>,
 		origin: DepositOrigin<T, I>,
 	) -> Result<FullWitnessDepositOutcome, DepositFailedReason> {
+		if !Asset::from(asset).is_plausible_amount(deposit_amount.into()) {
+			log::warn!(
+				"Implausible deposit amount {deposit_amount:?} of {asset:?} witnessed at {block_height:?}."
+			);
+			Self::deposit_event(Event::<T, I>::ImplausibleDepositAmount {
+				deposit_address: deposit_address.clone(),
+				asset,
+				amount: deposit_amount,
+				block_height,
+			});
+		}
+
 		if !matches!(boost_status, BoostStatus::Boosted { .. }) {
-			if deposit_amount < MinimumDeposit::<T, I>::get(asset) {
+			let broker_minimum_deposit = match &origin {
+				DepositOrigin::DepositChannel { broker_id, .. } =>
+					BrokerMinimumDeposits::<T, I>::get(broker_id, asset),
+				DepositOrigin::Vault { .. } => None,
+			};
+			if deposit_amount <
+				sp_std::cmp::max(
+					Self::update_minimum_deposit(asset),
+					broker_minimum_deposit.unwrap_or_default(),
+				) {
 				// If the deposit amount is below the minimum allowed, the deposit is ignored.
 				// TODO: track these funds somewhere, for example add them to the withheld fees.
 				return Err(DepositFailedReason::BelowMinimumDeposit);
@@ -2288,14 +5761,19 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 							.map(|refund_params| refund_params.refund_address.clone()),
 						ChannelAction::LiquidityProvision { refund_address, .. } =>
 							refund_address.clone(),
+						ChannelAction::Refund { refund_address, .. } =>
+							Some(refund_address.clone()),
 					};
 
+					Self::record_accounting(asset, |totals| {
+						totals.rejected.saturating_accrue(deposit_amount)
+					});
 					ScheduledTransactionsForRejection::<T, I>::append(
 						TransactionRejectionDetails {
 							refund_address,
 							amount: deposit_amount,
 							asset,
-							deposit_details: deposit_details.clone(),
+							deposit_details: Self::bound_deposit_details(deposit_details.clone()),
 						},
 					);
 
@@ -2326,20 +5804,45 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 
 		// Add the deposit to the balance.
 		T::DepositHandler::on_deposit_made(deposit_details.clone());
+		Self::record_accounting(asset, |totals| totals.deposited.saturating_accrue(deposit_amount));
 
 		// We received a deposit on a channel. If channel has been boosted earlier
 		// (i.e. awaiting finalisation), *and* the boosted amount matches the amount
-		// in this deposit, finalise the boost by crediting boost pools with the deposit.
-		// Process as non-boosted deposit otherwise:
+		// in this deposit (within the configured tolerance), finalise the boost by crediting
+		// boost pools with the deposit. Process as non-boosted deposit otherwise:
 		let maybe_boost_to_process = match boost_status {
 			BoostStatus::Boosted { prewitnessed_deposit_id, pools, amount }
-				if amount == deposit_amount =>
-				Some((prewitnessed_deposit_id, pools)),
+				if Self::boosted_amount_matches(
+					asset,
+					prewitnessed_deposit_id,
+					&pools,
+					amount,
+					deposit_amount,
+				) =>
+				Some((prewitnessed_deposit_id, pools, amount)),
 			_ => None,
 		};
 
-		if let Some((prewitnessed_deposit_id, used_pools)) = maybe_boost_to_process {
+		if let Some((prewitnessed_deposit_id, used_pools, boosted_amount)) = maybe_boost_to_process
+		{
+			BoostExposure::<T, I>::mutate(asset, |exposure| {
+				exposure.saturating_reduce(boosted_amount)
+			});
+
+			// Deposits that are finalised within the fast window after being prewitnessed are
+			// rebated part of the boost fee:
+			let prewitness_height_and_rebate =
+				BoostedDepositPrewitnessHeights::<T, I>::take(prewitnessed_deposit_id)
+					.zip(BoostFeeRebate::<T, I>::get())
+					.filter(|(prewitness_height, settings)| {
+						block_height.saturating_sub(*prewitness_height) <= settings.fast_window
+					})
+					.map(|(prewitness_height, settings)| (prewitness_height, settings.rebate));
+			let fee_rebate =
+				prewitness_height_and_rebate.map(|(_, rebate)| rebate).unwrap_or_default();
+
 			let mut total_amount_credited_to_boosters: TargetChainAmount<T, I> = 0u32.into();
+			let mut total_fee_rebated: TargetChainAmount<T, I> = 0u32.into();
 			// Note that ingress fee is not payed here, as it has already been payed at the time
 			// of boosting
 			for boost_tier in used_pools {
@@ -2348,10 +5851,12 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 						let DepositFinalisationOutcomeForPool {
 							unlocked_funds,
 							amount_credited_to_boosters,
-						} = pool.process_deposit_as_finalised(prewitnessed_deposit_id);
+							fee_rebated,
+						} = pool.process_deposit_as_finalised(prewitnessed_deposit_id, fee_rebate);
 
 						total_amount_credited_to_boosters
 							.saturating_accrue(amount_credited_to_boosters);
+						total_fee_rebated.saturating_accrue(fee_rebated);
 
 						for (booster_id, finalised_withdrawn_amount) in unlocked_funds {
 							T::Balance::credit_account(
@@ -2364,9 +5869,46 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				});
 			}
 
+			// The depositor has already been credited based on the boosted amount, so any surplus
+			// is passed on to them. A shortfall can't be reconciled against the depositor's credit,
+			// since it may already have been swapped or egressed. Instead it reduces the network
+			// fee below, which is the only part of the deposit that hasn't been paid out yet.
+			// `boosted_amount_matches` ensures that the deposit covers what is owed to the
+			// boosters, so the shortfall never exceeds the network fee.
+			let surplus = deposit_amount.saturating_sub(boosted_amount);
+
 			// Any excess amount is charged as network fee:
-			let network_fee_from_boost =
-				deposit_amount.saturating_sub(total_amount_credited_to_boosters);
+			let network_fee_from_boost = deposit_amount
+				.saturating_sub(surplus)
+				.saturating_sub(total_amount_credited_to_boosters)
+				.saturating_sub(total_fee_rebated);
+
+			// The surplus and the fee rebate are paid out to the depositor in a single action. No
+			// ingress fee is charged on either, since it was charged at the time of boosting.
+			let amount_owed_to_depositor = surplus.saturating_add(total_fee_rebated);
+			let depositor_action = (!amount_owed_to_depositor.is_zero()).then(|| {
+				Self::perform_channel_action(
+					action.clone(),
+					asset,
+					source_address,
+					amount_owed_to_depositor,
+					origin.clone(),
+				)
+			});
+
+			if let (Some((prewitness_block_height, _)), Some(action)) = (
+				prewitness_height_and_rebate.filter(|_| !total_fee_rebated.is_zero()),
+				depositor_action.clone(),
+			) {
+				Self::deposit_event(Event::<T, I>::BoostFeeRebated {
+					prewitnessed_deposit_id,
+					asset,
+					prewitness_block_height,
+					finalisation_block_height: block_height,
+					amount: total_fee_rebated,
+					action,
+				});
+			}
 
 			let network_fee_swap_request_id = if network_fee_from_boost > 0u32.into() {
 				// NOTE: if asset is FLIP, we shouldn't need to swap, but it should still work, and
@@ -2376,16 +5918,27 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					asset.into(),
 					network_fee_from_boost.into(),
 					Asset::Flip,
-					SwapRequestType::NetworkFee,
+					SwapRequestType::BoostNetworkFee,
 					Default::default(),
 					None,
 					None,
 					SwapOrigin::Internal,
+					None,
 				))
 			} else {
 				None
 			};
 
+			if let Some(surplus_action) = depositor_action.filter(|_| !surplus.is_zero()) {
+				Self::deposit_event(Event::<T, I>::BoostedDepositAmountReconciled {
+					prewitnessed_deposit_id,
+					asset,
+					boosted_amount,
+					deposit_amount,
+					surplus_action,
+				});
+			}
+
 			Self::deposit_event(Event::DepositFinalised {
 				deposit_address,
 				asset,
@@ -2402,17 +5955,24 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				},
 				channel_id,
 				origin_type: origin.into(),
+				tracking_id: action.tracking_id(),
 			});
 
 			Ok(FullWitnessDepositOutcome::BoostFinalised)
 		} else {
 			let AmountAndFeesWithheld { amount_after_fees, fees_withheld } =
-				Self::conditionally_withhold_ingress_fee(asset, deposit_amount, &origin);
+				Self::conditionally_withhold_ingress_fee(
+					asset,
+					deposit_amount,
+					&origin,
+					&deposit_details,
+				);
 
 			if amount_after_fees.is_zero() {
 				Err(DepositFailedReason::NotEnoughToPayFees)
 			} else {
 				// Processing as a non-boosted deposit:
+				let tracking_id = action.tracking_id();
 				let action = Self::perform_channel_action(
 					action,
 					asset,
@@ -2432,6 +5992,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 					action,
 					channel_id,
 					origin_type: origin.into(),
+					tracking_id,
 				});
 
 				Ok(FullWitnessDepositOutcome::DepositActionPerformed)
@@ -2449,101 +6010,78 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			channel_id,
 			deposit_amount,
 			deposit_details,
-			output_asset: destination_asset,
-			destination_address,
-			deposit_metadata,
 			tx_id,
 			broker_fee,
-			affiliate_fees,
-			refund_params,
-			dca_params,
 			boost_fee,
+			verified_sender,
+			..
 		} = vault_deposit_witness.clone();
 
+		let current_epoch = T::EpochInfo::epoch_index();
+		if ProcessedVaultSwaps::<T, I>::contains_key(current_epoch, &tx_id) ||
+			ProcessedVaultSwaps::<T, I>::contains_key(current_epoch.saturating_sub(1), &tx_id)
+		{
+			Self::deposit_event(Event::<T, I>::DuplicateVaultSwapWitnessIgnored {
+				tx_id,
+				block_height,
+			});
+			return;
+		}
+		ProcessedVaultSwaps::<T, I>::insert(current_epoch, &tx_id, ());
+
+		FullyWitnessedThisBlock::<T, I>::append(&tx_id);
+
+		// Attribute deposits into private channels to the owning broker, regardless of whether
+		// the swap itself succeeds.
+		if let (Some(channel_id), Some(Beneficiary { account: broker_id, .. })) =
+			(channel_id, broker_fee.as_ref())
+		{
+			Self::deposit_event(Event::<T, I>::PrivateChannelVaultSwapWitnessed {
+				channel_id,
+				broker_id: broker_id.clone(),
+				tx_id: tx_id.clone(),
+				asset: source_asset,
+				amount: deposit_amount,
+				block_height,
+			});
+		}
+
 		let boost_status =
 			BoostedVaultTransactions::<T, I>::get(&tx_id).unwrap_or(BoostStatus::NotBoosted);
 
+		let validation_result = Self::vault_swap_action(&vault_deposit_witness);
+
 		let emit_deposit_failed_event = move |reason: DepositFailedReason| {
-			Self::deposit_event(Event::<T, I>::DepositFailed {
+			Self::deposit_failed(
 				block_height,
 				reason,
-				details: DepositFailedDetails::Vault {
-					vault_witness: Box::new(vault_deposit_witness),
-				},
-			});
+				DepositFailedDetails::Vault { vault_witness: Box::new(vault_deposit_witness) },
+			);
 		};
 
-		let destination_address_internal =
-			match T::AddressConverter::decode_and_validate_address_for_asset(
-				destination_address.clone(),
-				destination_asset,
-			) {
-				Ok(address) => address,
-				Err(_) => {
-					emit_deposit_failed_event(DepositFailedReason::InvalidDestinationAddress);
-					return;
-				},
-			};
-
-		let deposit_origin = DepositOrigin::vault(
-			tx_id.clone(),
-			broker_fee.as_ref().map(|Beneficiary { account, .. }| account.clone()),
-		);
-		let broker_fees = Self::assemble_broker_fees(broker_fee.clone(), affiliate_fees.clone());
-
-		if T::SwapLimitsProvider::validate_broker_fees(&broker_fees).is_err() {
-			emit_deposit_failed_event(DepositFailedReason::InvalidBrokerFees);
-			return;
-		}
-
-		let (channel_metadata, source_address) = if let Some(metadata) = deposit_metadata.clone() {
-			if T::CcmValidityChecker::check_and_decode(
-				&metadata.channel_metadata,
-				destination_asset,
-			)
-			.is_err()
-			{
-				emit_deposit_failed_event(DepositFailedReason::CcmInvalidMetadata);
-				return;
-			}
-
-			let destination_chain: ForeignChain = (destination_asset).into();
-			if !destination_chain.ccm_support() {
-				emit_deposit_failed_event(DepositFailedReason::CcmUnsupportedForTargetChain);
+		let (action, source_address, warnings) = match validation_result {
+			Ok(result) => result,
+			Err(reason) => {
+				emit_deposit_failed_event(reason);
 				return;
-			}
-
-			(Some(metadata.channel_metadata), metadata.source_address)
-		} else {
-			(None, None)
+			},
 		};
 
-		if let Some(refund_params) = refund_params.clone() {
-			if let Err(_err) =
-				T::SwapLimitsProvider::validate_refund_params(refund_params.retry_duration)
-			{
-				emit_deposit_failed_event(DepositFailedReason::InvalidRefundParameters);
-				return;
-			}
-		} else {
-			log::warn!("No refund parameter provided for tx id: {tx_id:?}!");
-		}
-
-		if let Some(params) = &dca_params {
-			if T::SwapLimitsProvider::validate_dca_params(params).is_err() {
-				emit_deposit_failed_event(DepositFailedReason::InvalidDcaParameters);
-				return;
-			}
+		// Warnings are only emitted once the swap is fully witnessed, so that they're not
+		// duplicated by prewitnessing.
+		for warning in warnings {
+			Self::deposit_event(Event::<T, I>::VaultSwapParametersFlagged {
+				tx_id: tx_id.clone(),
+				broker_id: broker_fee.as_ref().map(|Beneficiary { account, .. }| account.clone()),
+				warning,
+			});
 		}
 
-		let action = ChannelAction::Swap {
-			destination_asset,
-			destination_address: destination_address_internal,
-			broker_fees,
-			channel_metadata: channel_metadata.clone(),
-			refund_params: refund_params.clone(),
-			dca_params: dca_params.clone(),
-		};
+		let deposit_origin = DepositOrigin::vault(
+			tx_id.clone(),
+			broker_fee.as_ref().map(|Beneficiary { account, .. }| account.clone()),
+			verified_sender,
+		);
 
 		match Self::process_full_witness_deposit_inner(
 			deposit_address.clone(),
@@ -2599,9 +6137,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		let expiry_height = <T::TargetChain as Chain>::saturating_block_witness_next(
 			current_height.saturating_add(lifetime),
 		);
-		let recycle_height = <T::TargetChain as Chain>::saturating_block_witness_next(
-			expiry_height.saturating_add(lifetime),
-		);
+		let recycle_height = Self::recycle_height_for_expiry(expiry_height);
 
 		debug_assert!(current_height < expiry_height);
 		debug_assert!(expiry_height < recycle_height);
@@ -2609,26 +6145,55 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		(current_height, expiry_height, recycle_height)
 	}
 
+	fn address_derivation_error(error: AddressDerivationError) -> Error<T, I> {
+		match error {
+			AddressDerivationError::MissingPolkadotVault => Error::<T, I>::MissingPolkadotVault,
+			AddressDerivationError::MissingBitcoinVault => Error::<T, I>::MissingBitcoinVault,
+			AddressDerivationError::BitcoinChannelIdTooLarge =>
+				Error::<T, I>::BitcoinChannelIdTooLarge,
+			AddressDerivationError::SolanaDerivationError { .. } =>
+				Error::<T, I>::SolanaAddressDerivationError,
+			AddressDerivationError::MissingSolanaApiEnvironment =>
+				Error::<T, I>::MissingSolanaApiEnvironment,
+		}
+	}
+
 	/// Opens a channel for the given asset and registers it with the given action.
 	///
 	/// May re-use an existing deposit address, depending on chain configuration.
 	///
-	/// The requester must have enough FLIP available to pay the channel opening fee.
+	/// The requester must have enough FLIP available to pay the channel opening fee. If the
+	/// deposit address can't be derived yet and channel open retries are enabled, the request is
+	/// queued and [Error::DepositChannelOpenDeferred] is returned. Retries of queued requests
+	/// don't pay the fee again and are not queued again.
 	#[allow(clippy::type_complexity)]
 	fn open_channel(
 		requester: &T::AccountId,
 		source_asset: TargetChainAsset<T, I>,
 		action: ChannelAction<T::AccountId>,
 		boost_fee: BasisPoints,
+		attempt: ChannelOpenAttempt,
 	) -> Result<
 		(ChannelId, TargetChainAccount<T, I>, TargetChainBlockNumber<T, I>, T::Amount),
 		DispatchError,
 	> {
 		ensure!(T::SafeMode::get().deposits_enabled, Error::<T, I>::DepositChannelCreationDisabled);
+		ensure!(!ChainWindDown::<T, I>::exists(), Error::<T, I>::ChainWindingDown);
+		let (open_channels, max_channels) = Self::open_channel_usage(requester);
+		ensure!(
+			max_channels.map_or(true, |max_channels| open_channels < max_channels),
+			Error::<T, I>::TooManyOpenChannels
+		);
 
-		let channel_opening_fee = ChannelOpeningFee::<T, I>::get();
-		T::FeePayment::try_burn_fee(requester, channel_opening_fee)?;
-		Self::deposit_event(Event::<T, I>::ChannelOpeningFeePaid { fee: channel_opening_fee });
+		let channel_opening_fee = match attempt {
+			ChannelOpenAttempt::New => {
+				let fee = ChannelOpeningFee::<T, I>::get();
+				T::FeePayment::try_burn_fee(requester, fee)?;
+				Self::deposit_event(Event::<T, I>::ChannelOpeningFeePaid { fee });
+				fee
+			},
+			ChannelOpenAttempt::Retry => Zero::zero(),
+		};
 
 		let (deposit_channel, channel_id) = if let Some((channel_id, mut deposit_channel)) =
 			DepositChannelPool::<T, I>::drain().next()
@@ -2637,22 +6202,26 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			(deposit_channel, channel_id)
 		} else {
 			let next_channel_id = Self::allocate_next_channel_id()?;
-			(
-				DepositChannel::generate_new::<T::AddressDerivation>(next_channel_id, source_asset)
-					.map_err(|e| match e {
-						AddressDerivationError::MissingPolkadotVault =>
-							Error::<T, I>::MissingPolkadotVault,
-						AddressDerivationError::MissingBitcoinVault =>
-							Error::<T, I>::MissingBitcoinVault,
-						AddressDerivationError::BitcoinChannelIdTooLarge =>
-							Error::<T, I>::BitcoinChannelIdTooLarge,
-						AddressDerivationError::SolanaDerivationError { .. } =>
-							Error::<T, I>::SolanaAddressDerivationError,
-						AddressDerivationError::MissingSolanaApiEnvironment =>
-							Error::<T, I>::MissingSolanaApiEnvironment,
-					})?,
+			match DepositChannel::generate_new::<T::AddressDerivation>(
 				next_channel_id,
-			)
+				source_asset,
+			) {
+				Ok(deposit_channel) => (deposit_channel, next_channel_id),
+				Err(error) if error.is_transient() =>
+					return Err(match attempt {
+						ChannelOpenAttempt::New => Self::defer_channel_open(
+							requester,
+							source_asset,
+							action,
+							boost_fee,
+							channel_opening_fee,
+							error,
+						),
+						ChannelOpenAttempt::Retry =>
+							Error::<T, I>::DepositChannelOpenDeferred.into(),
+					}),
+				Err(error) => return Err(Self::address_derivation_error(error).into()),
+			}
 		};
 
 		let deposit_address = deposit_channel.address.clone();
@@ -2661,8 +6230,10 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			Self::expiry_and_recycle_block_height();
 
 		if T::MANAGE_CHANNEL_LIFETIME {
-			DepositChannelRecycleBlocks::<T, I>::append((recycle_height, deposit_address.clone()));
+			Self::schedule_channel_recycling(recycle_height, deposit_address.clone());
 		}
+		DepositChannelsByOwner::<T, I>::insert(requester, &deposit_address, ());
+		OpenChannelCount::<T, I>::mutate(requester, |count| count.saturating_accrue(1));
 
 		DepositChannelLookup::<T, I>::insert(
 			&deposit_address,
@@ -2685,6 +6256,187 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		Ok((channel_id, deposit_address, expiry_height, channel_opening_fee))
 	}
 
+	/// Queues a channel open whose deposit address can't be derived yet, if retries are enabled.
+	/// Returns the error to report to the requester.
+	fn defer_channel_open(
+		requester: &T::AccountId,
+		source_asset: TargetChainAsset<T, I>,
+		action: ChannelAction<T::AccountId>,
+		boost_fee: BasisPoints,
+		channel_opening_fee: T::Amount,
+		error: AddressDerivationError,
+	) -> DispatchError {
+		let error: DispatchError = Self::address_derivation_error(error).into();
+		let retry_period = ChannelOpenRetryPeriod::<T, I>::get();
+		if retry_period == 0 {
+			return error
+		}
+
+		let request_id = PendingChannelOpenIdCounter::<T, I>::mutate(|id| {
+			id.saturating_accrue(1);
+			*id
+		});
+		let retry_until =
+			frame_system::Pallet::<T>::block_number().saturating_add(retry_period.into());
+		PendingChannelOpens::<T, I>::insert(
+			request_id,
+			PendingChannelOpen {
+				requester: requester.clone(),
+				source_asset,
+				action,
+				boost_fee,
+				retry_until,
+				channel_opening_fee,
+			},
+		);
+		Self::deposit_event(Event::<T, I>::DepositChannelOpenDeferred {
+			request_id,
+			requester: requester.clone(),
+			source_asset,
+			error,
+			retry_until,
+		});
+
+		Error::<T, I>::DepositChannelOpenDeferred.into()
+	}
+
+	#[transactional]
+	#[allow(clippy::type_complexity)]
+	fn retry_channel_open(
+		pending: &PendingChannelOpen<T, I>,
+	) -> Result<
+		(ChannelId, TargetChainAccount<T, I>, TargetChainBlockNumber<T, I>, T::Amount),
+		DispatchError,
+	> {
+		Self::open_channel(
+			&pending.requester,
+			pending.source_asset,
+			pending.action.clone(),
+			pending.boost_fee,
+			ChannelOpenAttempt::Retry,
+		)
+	}
+
+	/// Retries deferred channel opens. Requests whose address still can't be derived are kept
+	/// until their retry period is over, all other requests are resolved. Each block continues
+	/// from where the previous one stopped.
+	fn retry_pending_channel_opens(now: BlockNumberFor<T>, weight_limit: Weight) -> Weight {
+		let mut meter = WeightMeter::with_limit(weight_limit);
+		// Reading and updating the cursor.
+		if meter.try_consume(T::DbWeight::get().reads_writes(1, 1)).is_err() {
+			return Weight::zero()
+		}
+		// Approximate weight of opening a channel, including the read and removal of the request.
+		let weight_per_retry = T::DbWeight::get().reads_writes(10, 8);
+
+		let mut cursor = PendingChannelOpenCursor::<T, I>::get();
+		let request_ids = match cursor.clone() {
+			Some(raw_key) => PendingChannelOpens::<T, I>::iter_keys_from(raw_key),
+			None => PendingChannelOpens::<T, I>::iter_keys(),
+		}
+		.take(MAX_CHANNEL_OPEN_RETRIES_PER_BLOCK)
+		.collect::<Vec<_>>();
+		let mut reached_end = request_ids.len() < MAX_CHANNEL_OPEN_RETRIES_PER_BLOCK;
+
+		for request_id in request_ids {
+			if meter.try_consume(weight_per_retry).is_err() {
+				reached_end = false;
+				break
+			}
+			cursor = Some(PendingChannelOpens::<T, I>::hashed_key_for(request_id));
+			let Some(pending) = PendingChannelOpens::<T, I>::get(request_id) else { continue };
+
+			let error = match Self::retry_channel_open(&pending) {
+				Ok((channel_id, deposit_address, expiry_block, _)) => {
+					PendingChannelOpens::<T, I>::remove(request_id);
+					Self::deposit_event(Event::<T, I>::DeferredDepositChannelOpened {
+						request_id,
+						requester: pending.requester,
+						channel_id,
+						deposit_address,
+						expiry_block,
+					});
+					continue
+				},
+				Err(error) if error == Error::<T, I>::DepositChannelOpenDeferred.into() => {
+					if now < pending.retry_until {
+						continue
+					}
+					Error::<T, I>::ChannelOpenRetryPeriodExpired.into()
+				},
+				Err(error) => error,
+			};
+
+			PendingChannelOpens::<T, I>::remove(request_id);
+			T::FeePayment::refund_fee(&pending.requester, pending.channel_opening_fee);
+			Self::deposit_event(Event::<T, I>::DeferredDepositChannelOpenFailed {
+				request_id,
+				requester: pending.requester,
+				error,
+				refunded_fee: pending.channel_opening_fee,
+			});
+		}
+
+		// Start over from the first request once all requests have been retried.
+		if reached_end {
+			PendingChannelOpenCursor::<T, I>::kill();
+		} else {
+			PendingChannelOpenCursor::<T, I>::set(cursor);
+		}
+
+		meter.consumed()
+	}
+
+	fn recycle_height_for_expiry(
+		expiry_height: TargetChainBlockNumber<T, I>,
+	) -> TargetChainBlockNumber<T, I> {
+		<T::TargetChain as Chain>::saturating_block_witness_next(
+			expiry_height.saturating_add(DepositChannelLifetime::<T, I>::get()),
+		)
+	}
+
+	fn schedule_channel_recycling(
+		recycle_height: TargetChainBlockNumber<T, I>,
+		deposit_address: TargetChainAccount<T, I>,
+	) {
+		DepositChannelRecycleQueue::<T, I>::append(recycle_height, deposit_address);
+		NextRecycleBlockHeight::<T, I>::mutate(|next_height| match next_height {
+			Some(height) if *height <= recycle_height => {},
+			_ => *next_height = Some(recycle_height),
+		});
+	}
+
+	/// Moves a channel's recycling after its expiry has been brought forward or pushed back. If
+	/// the channel can't be found at its expected recycle height (e.g. because the channel
+	/// lifetime has changed since it was opened), it is left to be recycled as originally
+	/// scheduled.
+	fn reschedule_channel_recycling(
+		deposit_address: &TargetChainAccount<T, I>,
+		previous_expiry: TargetChainBlockNumber<T, I>,
+		new_expiry: TargetChainBlockNumber<T, I>,
+	) {
+		let previous_recycle_height = Self::recycle_height_for_expiry(previous_expiry);
+		let removed = DepositChannelRecycleQueue::<T, I>::mutate_exists(
+			previous_recycle_height,
+			|maybe_addresses| {
+				let Some(addresses) = maybe_addresses else { return false };
+				let count_before = addresses.len();
+				addresses.retain(|address| address != deposit_address);
+				let removed = addresses.len() < count_before;
+				if addresses.is_empty() {
+					*maybe_addresses = None;
+				}
+				removed
+			},
+		);
+		if removed {
+			Self::schedule_channel_recycling(
+				Self::recycle_height_for_expiry(new_expiry),
+				deposit_address.clone(),
+			);
+		}
+	}
+
 	pub fn get_failed_call(broadcast_id: BroadcastId) -> Option<FailedForeignChainCall> {
 		let epoch = T::EpochInfo::epoch_index();
 		FailedForeignChainCalls::<T, I>::get(epoch)
@@ -2693,13 +6445,19 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			.cloned()
 	}
 
-	// Withholds ingress fee, but only after checking the origin
+	// Withholds ingress fee, but only after checking the origin. Refunds of rejected deposits
+	// that land in one of our deposit channels have already been charged, so they are exempt.
 	fn conditionally_withhold_ingress_fee(
 		asset: TargetChainAsset<T, I>,
 		available_amount: TargetChainAmount<T, I>,
 		origin: &DepositOrigin<T, I>,
+		deposit_details: &<T::TargetChain as Chain>::DepositDetails,
 	) -> AmountAndFeesWithheld<T, I> {
-		if matches!(origin, &DepositOrigin::DepositChannel { .. }) {
+		if matches!(origin, &DepositOrigin::DepositChannel { .. }) &&
+			!deposit_details
+				.deposit_id()
+				.is_some_and(|tx_id| ProtocolRefundTransactions::<T, I>::take(tx_id).is_some())
+		{
 			Self::withhold_ingress_or_egress_fee(IngressOrEgress::Ingress, asset, available_amount)
 		} else {
 			// No ingress fee for vault swaps.
@@ -2710,6 +6468,51 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 	}
 
+	/// The minimum deposit of the given asset: the configured minimum, raised to the configured
+	/// multiple of the current estimated ingress fee if that is higher.
+	pub fn effective_minimum_deposit(asset: TargetChainAsset<T, I>) -> TargetChainAmount<T, I> {
+		let configured_minimum = MinimumDeposit::<T, I>::get(asset);
+
+		MinimumDepositIngressFeeMultiplier::<T, I>::get()
+			.and_then(|multiplier| {
+				let ingress_fee = T::ChainTracking::estimate_ingress_fee(asset);
+				let ingress_fee = if asset == <T::TargetChain as Chain>::GAS_ASSET {
+					Some(ingress_fee)
+				} else {
+					T::AssetConverter::calculate_input_for_gas_output::<T::TargetChain>(
+						asset,
+						ingress_fee,
+					)
+				}?;
+				Some(ingress_fee.saturating_mul(multiplier.into()))
+			})
+			.map_or(configured_minimum, |scaled_minimum| {
+				sp_std::cmp::max(configured_minimum, scaled_minimum)
+			})
+	}
+
+	/// Returns the effective minimum deposit of the given asset and emits an event whenever
+	/// scaling by the ingress fee becomes active or inactive.
+	fn update_minimum_deposit(asset: TargetChainAsset<T, I>) -> TargetChainAmount<T, I> {
+		let minimum_deposit = Self::effective_minimum_deposit(asset);
+		let is_scaled = minimum_deposit > MinimumDeposit::<T, I>::get(asset);
+
+		if is_scaled != DynamicMinimumDepositActive::<T, I>::contains_key(asset) {
+			if is_scaled {
+				DynamicMinimumDepositActive::<T, I>::insert(asset, ());
+				Self::deposit_event(Event::<T, I>::DynamicMinimumDepositActivated {
+					asset,
+					minimum_deposit,
+				});
+			} else {
+				DynamicMinimumDepositActive::<T, I>::remove(asset);
+				Self::deposit_event(Event::<T, I>::DynamicMinimumDepositDeactivated { asset });
+			}
+		}
+
+		minimum_deposit
+	}
+
 	/// Withholds the fee for a given amount.
 	///
 	/// Returns the remaining amount after the fee has been withheld, and the fee itself, both
@@ -2722,8 +6525,8 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		available_amount: TargetChainAmount<T, I>,
 	) -> AmountAndFeesWithheld<T, I> {
 		let fee_estimate = match ingress_or_egress {
-			IngressOrEgress::Ingress => T::ChainTracking::estimate_ingress_fee(asset),
-			IngressOrEgress::Egress => T::ChainTracking::estimate_egress_fee(asset),
+			IngressOrEgress::Ingress => Self::estimate_fee(asset, true),
+			IngressOrEgress::Egress => Self::estimate_fee(asset, false),
 			IngressOrEgress::EgressCcm{gas_budget, message_length} =>
 			T::ChainTracking::estimate_ccm_fee(asset, gas_budget, message_length)
 			.unwrap_or_else(|| {
@@ -2733,8 +6536,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		};
 
 		let fees_withheld = if asset == <T::TargetChain as Chain>::GAS_ASSET {
-			// No need to schedule a swap for gas, it's already in the gas asset.
-			Self::accrue_withheld_fee(asset, sp_std::cmp::min(fee_estimate, available_amount));
+			Self::withhold_in_gas_asset(asset, sp_std::cmp::min(fee_estimate, available_amount));
 			fee_estimate
 		} else {
 			let transaction_fee = sp_std::cmp::min(T::AssetConverter::calculate_input_for_gas_output::<T::TargetChain>(
@@ -2746,18 +6548,7 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 				<T::TargetChain as Chain>::ChainAmount::zero()
 			}), available_amount);
 
-			if !transaction_fee.is_zero() {
-				T::SwapRequestHandler::init_swap_request(
-					asset.into(),
-					transaction_fee.into(),
-					<T::TargetChain as Chain>::GAS_ASSET.into(),
-					SwapRequestType::IngressEgressFee,
-					Default::default(),
-					None, /* no refund params */
-					None, /* no DCA */
-					SwapOrigin::Internal,
-				);
-			}
+			Self::withhold_in_gas_asset(asset, transaction_fee);
 
 			transaction_fee
 		};
@@ -2768,6 +6559,79 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		}
 	}
 
+	/// Estimates the ingress or egress fee of the asset using the [FeeEstimationStrategy], in
+	/// units of the gas asset.
+	fn estimate_fee(asset: TargetChainAsset<T, I>, is_ingress: bool) -> TargetChainAmount<T, I> {
+		let sample =
+			|sample: FeeEstimateSample<_>| if is_ingress { sample.ingress } else { sample.egress };
+		let latest = if is_ingress {
+			T::ChainTracking::estimate_ingress_fee(asset)
+		} else {
+			T::ChainTracking::estimate_egress_fee(asset)
+		};
+
+		let strategy = FeeEstimation::<T, I>::get();
+		if strategy == FeeEstimationStrategy::Latest {
+			return latest
+		}
+
+		let estimate = strategy
+			.estimate(FeeEstimateSamples::<T, I>::get(asset).into_iter().map(sample), latest);
+		FeeEstimationTelemetryTotals::<T, I>::mutate(asset, |telemetry| {
+			telemetry.estimates.saturating_accrue(1);
+			telemetry.strategy_estimates.saturating_accrue(estimate);
+			telemetry.latest_estimates.saturating_accrue(latest);
+		});
+		estimate
+	}
+
+	/// Records the current fee estimates of every asset, if required by the
+	/// [FeeEstimationStrategy].
+	fn record_fee_estimate_samples() -> Weight {
+		let window = FeeEstimation::<T, I>::get().sample_window();
+		if window == 0 {
+			return T::DbWeight::get().reads(1)
+		}
+
+		use strum::IntoEnumIterator;
+		let mut assets = 0u64;
+		for asset in TargetChainAsset::<T, I>::iter() {
+			let sample = FeeEstimateSample {
+				ingress: T::ChainTracking::estimate_ingress_fee(asset),
+				egress: T::ChainTracking::estimate_egress_fee(asset),
+			};
+			FeeEstimateSamples::<T, I>::mutate(asset, |samples| {
+				while samples.len() >= window as usize {
+					samples.remove(0);
+				}
+				let _ = samples.try_push(sample);
+			});
+			assets += 1;
+		}
+		T::DbWeight::get().reads_writes(1 + assets * 2, assets)
+	}
+
+	/// Adds the amount to the withheld fees, scheduling a swap into the gas asset if necessary.
+	fn withhold_in_gas_asset(asset: TargetChainAsset<T, I>, amount: TargetChainAmount<T, I>) {
+		Self::record_accounting(asset, |totals| totals.fees_withheld.saturating_accrue(amount));
+		if asset == <T::TargetChain as Chain>::GAS_ASSET {
+			// No need to schedule a swap for gas, it's already in the gas asset.
+			Self::accrue_withheld_fee(asset, amount);
+		} else if !amount.is_zero() {
+			T::SwapRequestHandler::init_swap_request(
+				asset.into(),
+				amount.into(),
+				<T::TargetChain as Chain>::GAS_ASSET.into(),
+				SwapRequestType::IngressEgressFee,
+				Default::default(),
+				None, /* no refund params */
+				None, /* no DCA */
+				SwapOrigin::Internal,
+				None, /* no tracking id */
+			);
+		}
+	}
+
 	/// If a Ccm failed, we want to refund the user their assets.
 	/// This function will schedule a transfer to the fallback address, and emit an event on
 	/// success. IMPORTANT: Currently only used for Solana.
@@ -2775,23 +6639,93 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		broadcast_id: BroadcastId,
 		fallback: TransferAssetParams<T::TargetChain>,
 	) {
-		// let destination_address = fallback.to.clone();
-
-		match Self::schedule_egress(
-			fallback.asset,
-			fallback.amount,
-			fallback.to.clone(),
-			None,
-		) {
-			Ok(egress_details) => Self::deposit_event(Event::<T, I>::TransferFallbackRequested {
+		if let Some(egress_details) =
+			Self::schedule_ccm_refund(fallback.asset, fallback.amount, fallback.to.clone())
+		{
+			Self::deposit_event(Event::<T, I>::TransferFallbackRequested {
 				asset: fallback.asset,
 				amount: fallback.amount,
 				destination_address: fallback.to,
 				broadcast_id,
 				egress_details: Some(egress_details),
-			}),
-			Err(e) => log::error!("Ccm fallback failed to schedule the fallback egress: Target chain: {:?}, broadcast_id: {:?}, error: {:?}", T::TargetChain::get(), broadcast_id, e),
+			});
+		}
+	}
+
+	/// Schedules the refund of a failed CCM to its fallback address. The refund is a plain
+	/// transfer, so it can't fall back in turn.
+	///
+	/// If the amount left after the egress fee is zero or below the egress dust limit, nothing is
+	/// scheduled: the remainder is added to the withheld fees and
+	/// [CcmRefundBelowFeesIgnored](Event::CcmRefundBelowFeesIgnored) is emitted instead.
+	fn schedule_ccm_refund(
+		asset: TargetChainAsset<T, I>,
+		amount: TargetChainAmount<T, I>,
+		fallback_address: TargetChainAccount<T, I>,
+	) -> Option<ScheduledEgressDetails<T::TargetChain>> {
+		let AmountAndFeesWithheld { amount_after_fees, fees_withheld } =
+			Self::withhold_ingress_or_egress_fee(IngressOrEgress::Egress, asset, amount);
+
+		if amount_after_fees.is_zero() ||
+			amount_after_fees < EgressDustLimit::<T, I>::get(asset).unique_saturated_into()
+		{
+			Self::withhold_in_gas_asset(asset, amount_after_fees);
+			Self::deposit_event(Event::<T, I>::CcmRefundBelowFeesIgnored {
+				asset,
+				amount,
+				destination_address: fallback_address,
+				egress_fee: amount.saturating_sub(amount_after_fees),
+				residual_withheld: amount_after_fees,
+			});
+			return None
 		}
+
+		let id_counter = EgressIdCounter::<T, I>::mutate(|id_counter| {
+			id_counter.saturating_accrue(1);
+			*id_counter
+		});
+		Some(Self::append_transfer(
+			Self::egress_id(id_counter),
+			asset,
+			amount_after_fees,
+			fees_withheld,
+			fallback_address,
+			None,
+			EgressOrigin::Refund,
+		))
+	}
+
+	/// Adds a transfer to the egress queue. Fees must already have been withheld from the amount.
+	fn append_transfer(
+		egress_id: EgressId,
+		asset: TargetChainAsset<T, I>,
+		amount_after_fees: TargetChainAmount<T, I>,
+		fees_withheld: TargetChainAmount<T, I>,
+		destination_address: TargetChainAccount<T, I>,
+		memo: Option<EgressMemo>,
+		origin: EgressOrigin,
+	) -> ScheduledEgressDetails<T::TargetChain> {
+		let egress_details =
+			ScheduledEgressDetails::new(egress_id, amount_after_fees, fees_withheld);
+
+		Self::accrue_pending_egress_amount(asset, amount_after_fees);
+		Self::record_accounting(asset, |totals| {
+			totals.egressed.saturating_accrue(amount_after_fees)
+		});
+		Self::record_egress_total(origin, asset, amount_after_fees);
+		ScheduledEgressFetchOrTransfer::<T, I>::append({
+			FetchOrTransfer::<T::TargetChain>::Transfer {
+				asset,
+				destination_address,
+				amount: amount_after_fees,
+				egress_id,
+				scheduled_at: Self::current_block_number(),
+				memo,
+				origin,
+			}
+		});
+
+		egress_details
 	}
 
 	fn allocate_next_channel_id() -> Result<ChannelId, Error<T, I>> {
@@ -2800,20 +6734,61 @@ impl<T: Config<I>, I: 'static> Pallet<T, I> {
 			Ok(*id)
 		})
 	}
+
+	/// The egress id of this instance for the given counter value.
+	fn egress_id(id_counter: EgressCounter) -> EgressId {
+		(T::TargetChain::get(), T::InstanceId::get(), id_counter)
+	}
 }
 
 impl<T: Config<I>, I: 'static> EgressApi<T::TargetChain> for Pallet<T, I> {
 	type EgressError = Error<T, I>;
 
+	fn accepts_new_destination(_destination_address: &TargetChainAccount<T, I>) -> bool {
+		!ChainWindDown::<T, I>::exists()
+	}
+
 	fn schedule_egress(
 		asset: TargetChainAsset<T, I>,
 		amount: TargetChainAmount<T, I>,
 		destination_address: TargetChainAccount<T, I>,
 		maybe_ccm_deposit_metadata: Option<CcmDepositMetadata>,
+		egress_memo: Option<EgressMemo>,
+		origin: EgressOrigin,
 	) -> Result<ScheduledEgressDetails<T::TargetChain>, Error<T, I>> {
+		if let Some(memo) = &egress_memo {
+			ensure!(
+				maybe_ccm_deposit_metadata.is_none() && T::TargetChain::is_valid_egress_memo(memo),
+				Error::<T, I>::InvalidEgressMemo
+			);
+		}
+
+		// Nothing is sent once the grace period of the chain's wind-down has ended, for example
+		// the output of swaps that were still in progress. No fees are withheld, since the funds
+		// stay in the vault.
+		if let Some(mut status) = ChainWindDown::<T, I>::get().filter(|status| {
+			frame_system::Pallet::<T>::block_number() >= status.grace_period_ends_at
+		}) {
+			let id_counter = EgressIdCounter::<T, I>::mutate(|id_counter| {
+				*id_counter = id_counter.saturating_add(1);
+				*id_counter
+			});
+			let egress_details =
+				ScheduledEgressDetails::new(Self::egress_id(id_counter), amount, 0u32.into());
+			Self::abandon_egress(
+				&mut status,
+				egress_details.egress_id,
+				asset,
+				amount,
+				destination_address,
+			);
+			ChainWindDown::<T, I>::put(status);
+			return Ok(egress_details)
+		}
+
 		EgressIdCounter::<T, I>::try_mutate(|id_counter| {
 			*id_counter = id_counter.saturating_add(1);
-			let egress_id = (<T as Config<I>>::TargetChain::get(), *id_counter);
+			let egress_id = Self::egress_id(*id_counter);
 
 			match maybe_ccm_deposit_metadata {
 				Some(CcmDepositMetadata {
@@ -2834,8 +6809,13 @@ impl<T: Config<I>, I: 'static> EgressApi<T::TargetChain> for Pallet<T, I> {
 						);
 
 					let egress_details =
-						ScheduledEgressDetails::new(*id_counter, amount_after_fees, fees_withheld);
+						ScheduledEgressDetails::new(egress_id, amount_after_fees, fees_withheld);
 
+					Self::accrue_pending_egress_amount(asset, amount_after_fees);
+					Self::record_accounting(asset, |totals| {
+						totals.egressed.saturating_accrue(amount_after_fees)
+					});
+					Self::record_egress_total(origin, asset, amount_after_fees);
 					ScheduledEgressCcm::<T, I>::append(CrossChainMessage {
 						egress_id,
 						asset,
@@ -2846,6 +6826,8 @@ impl<T: Config<I>, I: 'static> EgressApi<T::TargetChain> for Pallet<T, I> {
 						source_chain,
 						source_address,
 						gas_budget,
+						scheduled_at: Self::current_block_number(),
+						origin,
 					});
 
 					Ok(egress_details)
@@ -2863,22 +6845,15 @@ impl<T: Config<I>, I: 'static> EgressApi<T::TargetChain> for Pallet<T, I> {
 						// We always want to benchmark the success case.
 						cfg!(all(feature = "runtime-benchmarks", not(test)))
 					{
-						let egress_details = ScheduledEgressDetails::new(
-							*id_counter,
+						Ok(Self::append_transfer(
+							egress_id,
+							asset,
 							amount_after_fees,
 							fees_withheld,
-						);
-
-						ScheduledEgressFetchOrTransfer::<T, I>::append({
-							FetchOrTransfer::<T::TargetChain>::Transfer {
-								asset,
-								destination_address: destination_address.clone(),
-								amount: amount_after_fees,
-								egress_id: egress_details.egress_id,
-							}
-						});
-
-						Ok(egress_details)
+							destination_address,
+							egress_memo,
+							origin,
+						))
 					} else {
 						// TODO: Consider tracking the ignored egresses somewhere.
 						// For example, store the egress and try it again later when fees have
@@ -2919,6 +6894,7 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 				refund_address: Some(refund_address),
 			},
 			boost_fee,
+			ChannelOpenAttempt::New,
 		)?;
 
 		Ok((
@@ -2940,6 +6916,8 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 		boost_fee: BasisPoints,
 		refund_params: Option<ChannelRefundParametersDecoded>,
 		dca_params: Option<DcaParameters>,
+		tracking_id: Option<TrackingId>,
+		egress_memo: Option<EgressMemo>,
 	) -> Result<
 		(ChannelId, ForeignChainAddress, <T::TargetChain as Chain>::ChainBlockNumber, Self::Amount),
 		DispatchError,
@@ -2950,6 +6928,16 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 		if let Some(params) = &dca_params {
 			T::SwapLimitsProvider::validate_dca_params(params)?;
 		}
+		if let Some(ccm) = &channel_metadata {
+			T::SwapLimitsProvider::validate_ccm_limits(ccm, destination_asset.into())?;
+		}
+		if let Some(memo) = &egress_memo {
+			ensure!(
+				channel_metadata.is_none() &&
+					cf_chains::is_valid_egress_memo(destination_asset.into(), memo),
+				Error::<T, I>::InvalidEgressMemo
+			);
+		}
 
 		let (channel_id, deposit_address, expiry_height, channel_opening_fee) = Self::open_channel(
 			&broker_id,
@@ -2961,8 +6949,11 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 				channel_metadata,
 				refund_params,
 				dca_params,
+				tracking_id,
+				egress_memo,
 			},
 			boost_fee,
+			ChannelOpenAttempt::New,
 		)?;
 
 		Ok((
@@ -2972,6 +6963,48 @@ impl<T: Config<I>, I: 'static> DepositApi<T::TargetChain> for Pallet<T, I> {
 			channel_opening_fee,
 		))
 	}
+
+	fn private_channel_deposit_address(
+		source_asset: TargetChainAsset<T, I>,
+		channel_id: ChannelId,
+	) -> Result<ForeignChainAddress, DispatchError> {
+		Ok(<T::TargetChain as Chain>::ChainAccount::into_foreign_chain_address(
+			T::AddressDerivation::generate_address(source_asset, channel_id)
+				.map_err(Self::address_derivation_error)?,
+		))
+	}
+
+	fn is_deferred_channel_open(error: &DispatchError) -> bool {
+		*error == Error::<T, I>::DepositChannelOpenDeferred.into()
+	}
+
+	fn has_unrecycled_deposit_channels(owner: &Self::AccountId) -> bool {
+		DepositChannelsByOwner::<T, I>::iter_prefix(owner).next().is_some()
+	}
+
+	fn expire_rejection_requests(broker_id: &Self::AccountId) {
+		let unseen_tx_ids = TransactionsMarkedForRejection::<T, I>::iter_prefix(broker_id)
+			.take(MAX_REJECTION_REQUESTS_EXPIRED_ON_DEREGISTRATION as usize)
+			.filter(|(_, status)| *status == TransactionPrewitnessedStatus::Unseen)
+			.map(|(tx_id, _)| tx_id)
+			.collect::<Vec<_>>();
+
+		// The corresponding entries in `ReportExpiresAt` are ignored once they fall due, since
+		// the requests no longer exist.
+		for tx_id in unseen_tx_ids {
+			TransactionsMarkedForRejection::<T, I>::remove(broker_id, &tx_id);
+			Self::deposit_event(Event::<T, I>::TransactionRejectionRequestExpired {
+				account_id: broker_id.clone(),
+				tx_id,
+			});
+		}
+	}
+
+	fn take_swap_request_rejection(swap_request_id: SwapRequestId) -> bool {
+		BoostedSwapRequests::<T, I>::take(swap_request_id).is_some_and(|(broker_id, tx_id)| {
+			TransactionsMarkedForRejection::<T, I>::take(&broker_id, &tx_id).is_some()
+		})
+	}
 }
 
 impl<T: Config<I>, I: 'static> IngressEgressFeeApi<T::TargetChain> for Pallet<T, I> {
@@ -2,9 +2,21 @@ use cf_runtime_utilities::PlaceholderMigration;
 use frame_support::migrations::VersionedMigration;
 
 use crate::Pallet;
+pub mod bounded_deposit_details_migration;
 pub mod deposit_channel_details_migration;
+pub mod deposit_channel_index_migration;
+pub mod deposit_channel_tracking_id_migration;
+pub mod deposit_failure_history_migration;
+pub mod egress_id_instance_migration;
+pub mod egress_memo_migration;
+pub mod egress_origin_migration;
+pub mod egress_scheduled_at_migration;
+pub mod open_channel_count_migration;
+pub mod pending_egress_amounts_migration;
 pub mod rename_scheduled_tx_for_reject;
 pub mod scheduled_egress_ccm_migration;
+pub mod stepped_migration;
+pub mod vault_deposit_witness_sender_migration;
 
 pub type PalletMigration<T, I> = (
 	VersionedMigration<
@@ -28,5 +40,88 @@ pub type PalletMigration<T, I> = (
 		Pallet<T, I>,
 		<T as frame_system::Config>::DbWeight,
 	>,
-	PlaceholderMigration<20, Pallet<T, I>>,
+	VersionedMigration<
+		20,
+		21,
+		egress_scheduled_at_migration::EgressScheduledAtMigration<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		21,
+		22,
+		deposit_channel_tracking_id_migration::DepositChannelTrackingIdMigration<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		22,
+		23,
+		deposit_channel_index_migration::DepositChannelIndexMigration<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		23,
+		24,
+		bounded_deposit_details_migration::BoundedDepositDetailsMigration<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		24,
+		25,
+		vault_deposit_witness_sender_migration::VaultDepositWitnessSenderMigration<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		25,
+		26,
+		egress_id_instance_migration::EgressIdInstanceMigration<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		26,
+		27,
+		open_channel_count_migration::OpenChannelCountMigration<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		27,
+		28,
+		egress_memo_migration::EgressMemoMigration<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		28,
+		29,
+		egress_origin_migration::EgressOriginMigration<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		29,
+		30,
+		pending_egress_amounts_migration::PendingEgressAmountsMigration<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		30,
+		31,
+		deposit_failure_history_migration::DepositFailureHistoryMigration<T, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	PlaceholderMigration<31, Pallet<T, I>>,
 );
+
+/// The stepped migration advanced by `on_idle`. Set this to a
+/// [stepped_migration::SteppedMigration] that is started through
+/// [stepped_migration::StartSteppedMigration] in [PalletMigration], and reset it to `()` once the
+/// migration has completed on all networks.
+pub type CurrentSteppedMigration = ();
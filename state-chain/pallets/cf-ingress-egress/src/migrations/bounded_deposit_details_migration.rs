@@ -0,0 +1,122 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::*;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+pub mod old {
+	use super::*;
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub struct TransactionRejectionDetails<T: Config<I>, I: 'static> {
+		pub refund_address: Option<ForeignChainAddress>,
+		pub asset: TargetChainAsset<T, I>,
+		pub amount: TargetChainAmount<T, I>,
+		pub deposit_details: <T::TargetChain as Chain>::DepositDetails,
+	}
+
+	#[frame_support::storage_alias]
+	pub type ScheduledTransactionsForRejection<T: Config<I>, I: 'static> =
+		StorageValue<Pallet<T, I>, Vec<TransactionRejectionDetails<T, I>>, ValueQuery>;
+
+	#[frame_support::storage_alias]
+	pub type FailedRejections<T: Config<I>, I: 'static> =
+		StorageValue<Pallet<T, I>, Vec<TransactionRejectionDetails<T, I>>, ValueQuery>;
+}
+
+fn bound_rejection_details<T: Config<I>, I: 'static>(
+	rejections: Vec<old::TransactionRejectionDetails<T, I>>,
+) -> Vec<TransactionRejectionDetails<T, I>> {
+	rejections
+		.into_iter()
+		.map(|rejection| TransactionRejectionDetails {
+			refund_address: rejection.refund_address,
+			asset: rejection.asset,
+			amount: rejection.amount,
+			deposit_details: Pallet::<T, I>::bound_deposit_details(rejection.deposit_details),
+		})
+		.collect()
+}
+
+pub struct BoundedDepositDetailsMigration<T: Config<I>, I: 'static = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for BoundedDepositDetailsMigration<T, I> {
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((
+			old::ScheduledTransactionsForRejection::<T, I>::get().len() as u64,
+			old::FailedRejections::<T, I>::get().len() as u64,
+		)
+			.encode())
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		crate::ScheduledTransactionsForRejection::<T, I>::put(bound_rejection_details(
+			old::ScheduledTransactionsForRejection::<T, I>::take(),
+		));
+		crate::FailedRejections::<T, I>::put(bound_rejection_details(
+			old::FailedRejections::<T, I>::take(),
+		));
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let pre_upgrade_counts = <(u64, u64)>::decode(&mut state.as_slice())
+			.map_err(|_| DispatchError::from("Failed to decode state"))?;
+
+		let post_upgrade_counts = (
+			crate::ScheduledTransactionsForRejection::<T, I>::get().len() as u64,
+			crate::FailedRejections::<T, I>::get().len() as u64,
+		);
+
+		assert_eq!(pre_upgrade_counts, post_upgrade_counts);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use crate::mock_eth::{new_test_ext, Test};
+	use cf_chains::{evm::DepositDetails, MAX_DEPOSIT_DETAILS_ENCODED_LEN};
+	use sp_core::H256;
+
+	use super::*;
+
+	#[test]
+	fn large_deposit_details_are_pruned() {
+		new_test_ext().execute_with(|| {
+			let small_details = DepositDetails { tx_hashes: Some(vec![H256::repeat_byte(1)]) };
+			let large_details = DepositDetails {
+				tx_hashes: Some(vec![H256::repeat_byte(2); MAX_DEPOSIT_DETAILS_ENCODED_LEN / 32]),
+			};
+			let rejection = |deposit_details| old::TransactionRejectionDetails::<Test, ()> {
+				refund_address: None,
+				asset: cf_chains::assets::eth::Asset::Eth,
+				amount: 1_000,
+				deposit_details,
+			};
+			old::ScheduledTransactionsForRejection::<Test, ()>::put(vec![rejection(
+				small_details.clone(),
+			)]);
+			old::FailedRejections::<Test, ()>::put(vec![rejection(large_details.clone())]);
+
+			#[cfg(feature = "try-runtime")]
+			let state: Vec<u8> = BoundedDepositDetailsMigration::<Test, ()>::pre_upgrade().unwrap();
+
+			BoundedDepositDetailsMigration::<Test, ()>::on_runtime_upgrade();
+
+			#[cfg(feature = "try-runtime")]
+			BoundedDepositDetailsMigration::<Test, ()>::post_upgrade(state).unwrap();
+
+			assert_eq!(
+				crate::ScheduledTransactionsForRejection::<Test, ()>::get()[0].deposit_details,
+				BoundedDepositDetails::Full(small_details)
+			);
+			let pruned = crate::FailedRejections::<Test, ()>::get()[0].deposit_details.clone();
+			assert!(matches!(pruned, BoundedDepositDetails::Pruned { tx_id: None, .. }));
+			assert_eq!(Pallet::<Test, ()>::full_deposit_details(&pruned), Some(large_details));
+		});
+	}
+}
@@ -95,6 +95,8 @@ impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for DepositChannelDetai
 						channel_metadata: None,
 						refund_params,
 						dca_params,
+						tracking_id: None,
+						egress_memo: None,
 					},
 					old::ChannelAction::CcmTransfer {
 						destination_asset,
@@ -114,6 +116,8 @@ impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for DepositChannelDetai
 						}),
 						refund_params,
 						dca_params,
+						tracking_id: None,
+						egress_memo: None,
 					},
 				};
 
@@ -0,0 +1,126 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::*;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+use codec::{Decode, Encode};
+
+pub mod old {
+	use super::*;
+
+	#[frame_support::storage_alias]
+	pub type DepositChannelRecycleBlocks<T: Config<I>, I: 'static> = StorageValue<
+		Pallet<T, I>,
+		Vec<(TargetChainBlockNumber<T, I>, TargetChainAccount<T, I>)>,
+		ValueQuery,
+	>;
+}
+
+/// Moves the channel recycle queue into a map keyed by recycle height and indexes the open
+/// deposit channels by owner.
+pub struct DepositChannelIndexMigration<T: Config<I>, I: 'static = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for DepositChannelIndexMigration<T, I> {
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((
+			old::DepositChannelRecycleBlocks::<T, I>::get().len() as u64,
+			crate::DepositChannelLookup::<T, I>::iter_keys().count() as u64,
+		)
+			.encode())
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		for (recycle_height, address) in old::DepositChannelRecycleBlocks::<T, I>::take() {
+			crate::DepositChannelRecycleQueue::<T, I>::append(recycle_height, address);
+			crate::NextRecycleBlockHeight::<T, I>::mutate(|next_height| match next_height {
+				Some(height) if *height <= recycle_height => {},
+				_ => *next_height = Some(recycle_height),
+			});
+		}
+
+		for (address, details) in crate::DepositChannelLookup::<T, I>::iter() {
+			crate::DepositChannelsByOwner::<T, I>::insert(details.owner, address, ());
+		}
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let (pre_recycle_queue_len, pre_deposit_channel_count) =
+			<(u64, u64)>::decode(&mut state.as_slice())
+				.map_err(|_| DispatchError::from("Failed to decode state"))?;
+
+		assert_eq!(
+			pre_recycle_queue_len,
+			crate::DepositChannelRecycleQueue::<T, I>::iter_values()
+				.map(|addresses| addresses.len() as u64)
+				.sum::<u64>()
+		);
+		assert_eq!(
+			pre_deposit_channel_count,
+			crate::DepositChannelsByOwner::<T, I>::iter_keys().count() as u64
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use super::*;
+	use crate::mock_eth::{new_test_ext, Test, ALICE, BROKER};
+	use cf_chains::{DepositChannel, Ethereum};
+	use sp_core::H160;
+
+	#[test]
+	fn test_migration() {
+		new_test_ext().execute_with(|| {
+			let channels = [(ALICE, H160::from([1; 20]), 10), (BROKER, H160::from([2; 20]), 5)];
+			for (owner, address, recycle_height) in channels {
+				crate::DepositChannelLookup::<Test, ()>::insert(
+					address,
+					DepositChannelDetails {
+						owner,
+						deposit_channel: DepositChannel::<Ethereum> {
+							channel_id: 1,
+							address,
+							asset: cf_chains::assets::eth::Asset::Eth,
+							state: Default::default(),
+						},
+						opened_at: 0,
+						expires_at: 0,
+						action: ChannelAction::LiquidityProvision {
+							lp_account: owner,
+							refund_address: None,
+						},
+						boost_fee: 0,
+						boost_status: BoostStatus::NotBoosted,
+					},
+				);
+				old::DepositChannelRecycleBlocks::<Test, ()>::append((recycle_height, address));
+			}
+
+			#[cfg(feature = "try-runtime")]
+			let state: Vec<u8> = DepositChannelIndexMigration::<Test, ()>::pre_upgrade().unwrap();
+
+			DepositChannelIndexMigration::<Test, ()>::on_runtime_upgrade();
+
+			#[cfg(feature = "try-runtime")]
+			DepositChannelIndexMigration::<Test, ()>::post_upgrade(state).unwrap();
+
+			assert!(!old::DepositChannelRecycleBlocks::<Test, ()>::exists());
+			assert_eq!(crate::NextRecycleBlockHeight::<Test, ()>::get(), Some(5));
+			assert_eq!(crate::DepositChannelRecycleQueue::<Test, ()>::count(), 2);
+			for (owner, address, recycle_height) in channels {
+				assert_eq!(
+					crate::DepositChannelRecycleQueue::<Test, ()>::get(recycle_height),
+					vec![address]
+				);
+				assert!(crate::DepositChannelsByOwner::<Test, ()>::contains_key(owner, address));
+			}
+		});
+	}
+}
@@ -0,0 +1,107 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::*;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+pub mod old {
+	use super::*;
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub struct DepositFailure<T: Config<I>, I: 'static> {
+		pub block_height: TargetChainBlockNumber<T, I>,
+		pub reason: DepositFailedReason,
+		pub details: DepositFailedDetails<T, I>,
+		pub recorded_at: BlockNumber,
+	}
+}
+
+/// Replaces the full deposit details kept in the deposit failure history with the deposit
+/// address, transaction id, asset and amount of the failed deposit.
+pub struct DepositFailureHistoryMigration<T: Config<I>, I: 'static = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for DepositFailureHistoryMigration<T, I> {
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((crate::DepositFailureHistory::<T, I>::iter_keys().count() as u64).encode())
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		crate::DepositFailureHistory::<T, I>::translate_values::<old::DepositFailure<T, I>, _>(
+			|old| {
+				Some(DepositFailure::new(
+					old.block_height,
+					old.reason,
+					&old.details,
+					old.recorded_at,
+				))
+			},
+		);
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let pre_upgrade_count = <u64>::decode(&mut state.as_slice())
+			.map_err(|_| DispatchError::from("Failed to decode state"))?;
+
+		assert_eq!(
+			pre_upgrade_count,
+			crate::DepositFailureHistory::<T, I>::iter_values().count() as u64
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use crate::mock_eth::{new_test_ext, Test};
+	use cf_chains::{assets::eth::Asset as EthAsset, evm::DepositDetails};
+	use sp_core::H160;
+
+	use super::*;
+
+	#[test]
+	fn deposit_details_are_dropped_from_the_failure_history() {
+		new_test_ext().execute_with(|| {
+			frame_support::storage::unhashed::put(
+				&crate::DepositFailureHistory::<Test, ()>::hashed_key_for(0),
+				&old::DepositFailure::<Test, ()> {
+					block_height: 10,
+					reason: DepositFailedReason::BelowMinimumDeposit,
+					details: DepositFailedDetails::DepositChannel {
+						deposit_witness: DepositWitness {
+							deposit_address: H160::repeat_byte(1),
+							asset: EthAsset::Eth,
+							amount: 1_000,
+							deposit_details: DepositDetails { tx_hashes: None },
+						},
+					},
+					recorded_at: 2,
+				},
+			);
+
+			#[cfg(feature = "try-runtime")]
+			let state: Vec<u8> = DepositFailureHistoryMigration::<Test, ()>::pre_upgrade().unwrap();
+
+			DepositFailureHistoryMigration::<Test, ()>::on_runtime_upgrade();
+
+			#[cfg(feature = "try-runtime")]
+			DepositFailureHistoryMigration::<Test, ()>::post_upgrade(state).unwrap();
+
+			assert_eq!(
+				crate::DepositFailureHistory::<Test, ()>::get(0).unwrap(),
+				DepositFailure {
+					block_height: 10,
+					reason: DepositFailedReason::BelowMinimumDeposit,
+					deposit_address: Some(H160::repeat_byte(1)),
+					tx_id: None,
+					asset: EthAsset::Eth,
+					amount: 1_000,
+					recorded_at: 2,
+				}
+			);
+		});
+	}
+}
@@ -0,0 +1,268 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::*;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+use codec::{Decode, Encode};
+
+pub mod old {
+	use cf_chains::ForeignChainAddress;
+	use cf_primitives::EgressCounter;
+
+	use super::*;
+
+	pub type EgressId = (ForeignChain, EgressCounter);
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub enum FetchOrTransfer<C: Chain> {
+		Fetch {
+			asset: C::ChainAsset,
+			deposit_address: C::ChainAccount,
+			deposit_fetch_id: Option<C::DepositFetchId>,
+			amount: C::ChainAmount,
+		},
+		Transfer {
+			egress_id: EgressId,
+			asset: C::ChainAsset,
+			destination_address: C::ChainAccount,
+			amount: C::ChainAmount,
+			scheduled_at: BlockNumber,
+		},
+	}
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub struct CrossChainMessage<C: Chain> {
+		pub egress_id: EgressId,
+		pub asset: C::ChainAsset,
+		pub amount: C::ChainAmount,
+		pub destination_address: C::ChainAccount,
+		pub message: CcmMessage,
+		pub source_chain: ForeignChain,
+		pub source_address: Option<ForeignChainAddress>,
+		pub ccm_additional_data: CcmAdditionalData,
+		pub gas_budget: GasAmount,
+		pub scheduled_at: BlockNumber,
+	}
+
+	#[frame_support::storage_alias]
+	pub type ScheduledEgressFetchOrTransfer<T: Config<I>, I: 'static> =
+		StorageValue<Pallet<T, I>, Vec<FetchOrTransfer<<T as Config<I>>::TargetChain>>, ValueQuery>;
+
+	#[frame_support::storage_alias]
+	pub type ScheduledEgressCcm<T: Config<I>, I: 'static> = StorageValue<
+		Pallet<T, I>,
+		Vec<CrossChainMessage<<T as Config<I>>::TargetChain>>,
+		ValueQuery,
+	>;
+
+	#[frame_support::storage_alias]
+	pub type CcmDeferrals<T: Config<I>, I: 'static> =
+		StorageMap<Pallet<T, I>, Twox64Concat, EgressId, CcmDeferral, OptionQuery>;
+}
+
+/// The layout written by this migration. It only differs from [old] in the egress ids.
+pub mod new {
+	use cf_chains::ForeignChainAddress;
+
+	use super::*;
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub enum FetchOrTransfer<C: Chain> {
+		Fetch {
+			asset: C::ChainAsset,
+			deposit_address: C::ChainAccount,
+			deposit_fetch_id: Option<C::DepositFetchId>,
+			amount: C::ChainAmount,
+		},
+		Transfer {
+			egress_id: EgressId,
+			asset: C::ChainAsset,
+			destination_address: C::ChainAccount,
+			amount: C::ChainAmount,
+			scheduled_at: BlockNumber,
+		},
+	}
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub struct CrossChainMessage<C: Chain> {
+		pub egress_id: EgressId,
+		pub asset: C::ChainAsset,
+		pub amount: C::ChainAmount,
+		pub destination_address: C::ChainAccount,
+		pub message: CcmMessage,
+		pub source_chain: ForeignChain,
+		pub source_address: Option<ForeignChainAddress>,
+		pub ccm_additional_data: CcmAdditionalData,
+		pub gas_budget: GasAmount,
+		pub scheduled_at: BlockNumber,
+	}
+
+	#[frame_support::storage_alias]
+	pub type ScheduledEgressFetchOrTransfer<T: Config<I>, I: 'static> =
+		StorageValue<Pallet<T, I>, Vec<FetchOrTransfer<<T as Config<I>>::TargetChain>>, ValueQuery>;
+
+	#[frame_support::storage_alias]
+	pub type ScheduledEgressCcm<T: Config<I>, I: 'static> = StorageValue<
+		Pallet<T, I>,
+		Vec<CrossChainMessage<<T as Config<I>>::TargetChain>>,
+		ValueQuery,
+	>;
+}
+
+/// Adds the instance id to the egress ids of the scheduled egresses and of the deferred CCMs.
+pub struct EgressIdInstanceMigration<T: Config<I>, I: 'static = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> EgressIdInstanceMigration<T, I> {
+	fn new_egress_id((chain, id_counter): old::EgressId) -> EgressId {
+		(chain, T::InstanceId::get(), id_counter)
+	}
+}
+
+impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for EgressIdInstanceMigration<T, I> {
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((
+			old::ScheduledEgressFetchOrTransfer::<T, I>::get().len() as u64,
+			old::ScheduledEgressCcm::<T, I>::get().len() as u64,
+			old::CcmDeferrals::<T, I>::iter().count() as u64,
+		)
+			.encode())
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		let _ = new::ScheduledEgressFetchOrTransfer::<T, I>::translate::<
+			Vec<old::FetchOrTransfer<T::TargetChain>>,
+			_,
+		>(|old_requests| {
+			old_requests.map(|old_requests| {
+				old_requests
+					.into_iter()
+					.map(|request| match request {
+						old::FetchOrTransfer::Fetch {
+							asset,
+							deposit_address,
+							deposit_fetch_id,
+							amount,
+						} => new::FetchOrTransfer::Fetch {
+							asset,
+							deposit_address,
+							deposit_fetch_id,
+							amount,
+						},
+						old::FetchOrTransfer::Transfer {
+							egress_id,
+							asset,
+							destination_address,
+							amount,
+							scheduled_at,
+						} => new::FetchOrTransfer::Transfer {
+							egress_id: Self::new_egress_id(egress_id),
+							asset,
+							destination_address,
+							amount,
+							scheduled_at,
+						},
+					})
+					.collect()
+			})
+		});
+
+		let _ = new::ScheduledEgressCcm::<T, I>::translate::<
+			Vec<old::CrossChainMessage<T::TargetChain>>,
+			_,
+		>(|old_messages| {
+			old_messages.map(|old_messages| {
+				old_messages
+					.into_iter()
+					.map(|old| new::CrossChainMessage {
+						egress_id: Self::new_egress_id(old.egress_id),
+						asset: old.asset,
+						amount: old.amount,
+						destination_address: old.destination_address,
+						message: old.message,
+						source_chain: old.source_chain,
+						source_address: old.source_address,
+						ccm_additional_data: old.ccm_additional_data,
+						gas_budget: old.gas_budget,
+						scheduled_at: old.scheduled_at,
+					})
+					.collect()
+			})
+		});
+
+		for (egress_id, deferral) in old::CcmDeferrals::<T, I>::drain().collect::<Vec<_>>() {
+			crate::CcmDeferrals::<T, I>::insert(Self::new_egress_id(egress_id), deferral);
+		}
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let (pre_fetch_or_transfer_count, pre_ccm_count, pre_deferral_count) =
+			<(u64, u64, u64)>::decode(&mut state.as_slice())
+				.map_err(|_| DispatchError::from("Failed to decode state"))?;
+
+		assert_eq!(
+			pre_fetch_or_transfer_count,
+			new::ScheduledEgressFetchOrTransfer::<T, I>::get().len() as u64
+		);
+		assert_eq!(pre_ccm_count, new::ScheduledEgressCcm::<T, I>::get().len() as u64);
+		assert_eq!(pre_deferral_count, crate::CcmDeferrals::<T, I>::iter().count() as u64);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use super::*;
+	use crate::mock_eth::{new_test_ext, Test};
+	use cf_chains::Ethereum;
+
+	#[test]
+	fn test_migration() {
+		new_test_ext().execute_with(|| {
+			old::ScheduledEgressFetchOrTransfer::<Test, ()>::put(vec![old::FetchOrTransfer::<
+				Ethereum,
+			>::Transfer {
+				egress_id: (ForeignChain::Ethereum, 1),
+				asset: cf_chains::assets::eth::Asset::Eth,
+				destination_address: Default::default(),
+				amount: 1_000,
+				scheduled_at: 1,
+			}]);
+			old::CcmDeferrals::<Test, ()>::insert(
+				(ForeignChain::Ethereum, 2),
+				CcmDeferral { count: 1, first_deferred_at: 1 },
+			);
+
+			#[cfg(feature = "try-runtime")]
+			let state: Vec<u8> = EgressIdInstanceMigration::<Test, ()>::pre_upgrade().unwrap();
+
+			EgressIdInstanceMigration::<Test, ()>::on_runtime_upgrade();
+
+			#[cfg(feature = "try-runtime")]
+			EgressIdInstanceMigration::<Test, ()>::post_upgrade(state).unwrap();
+
+			assert!(
+				new::ScheduledEgressFetchOrTransfer::<Test, ()>::get() ==
+					vec![new::FetchOrTransfer::<Ethereum>::Transfer {
+						egress_id: (ForeignChain::Ethereum, 0, 1),
+						asset: cf_chains::assets::eth::Asset::Eth,
+						destination_address: Default::default(),
+						amount: 1_000,
+						scheduled_at: 1,
+					}]
+			);
+			assert_eq!(
+				crate::CcmDeferrals::<Test, ()>::iter().collect::<Vec<_>>(),
+				vec![(
+					(ForeignChain::Ethereum, 0, 2),
+					CcmDeferral { count: 1, first_deferred_at: 1 }
+				)]
+			);
+		});
+	}
+}
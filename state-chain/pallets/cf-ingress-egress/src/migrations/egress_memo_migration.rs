@@ -0,0 +1,231 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::*;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+use codec::{Decode, Encode};
+
+use super::egress_origin_migration::old as next;
+
+pub mod old {
+	use crate::BoostStatus;
+	use cf_chains::{ChannelRefundParametersDecoded, DepositChannel, ForeignChainAddress};
+	use cf_primitives::Beneficiaries;
+	use frame_support::{pallet_prelude::OptionQuery, Twox64Concat};
+
+	use super::*;
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub enum FetchOrTransfer<C: Chain> {
+		Fetch {
+			asset: C::ChainAsset,
+			deposit_address: C::ChainAccount,
+			deposit_fetch_id: Option<C::DepositFetchId>,
+			amount: C::ChainAmount,
+		},
+		Transfer {
+			egress_id: EgressId,
+			asset: C::ChainAsset,
+			destination_address: C::ChainAccount,
+			amount: C::ChainAmount,
+			scheduled_at: BlockNumber,
+		},
+	}
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub struct DepositChannelDetails<T: Config<I>, I: 'static> {
+		pub owner: T::AccountId,
+		pub deposit_channel: DepositChannel<T::TargetChain>,
+		pub opened_at: TargetChainBlockNumber<T, I>,
+		pub expires_at: TargetChainBlockNumber<T, I>,
+		pub action: ChannelAction<T::AccountId>,
+		pub boost_fee: BasisPoints,
+		pub boost_status: BoostStatus<TargetChainAmount<T, I>>,
+	}
+
+	#[derive(Clone, PartialEq, Eq, Encode, Decode)]
+	pub enum ChannelAction<AccountId> {
+		Swap {
+			destination_asset: Asset,
+			destination_address: ForeignChainAddress,
+			broker_fees: Beneficiaries<AccountId>,
+			channel_metadata: Option<CcmChannelMetadata>,
+			refund_params: Option<ChannelRefundParametersDecoded>,
+			dca_params: Option<DcaParameters>,
+			tracking_id: Option<TrackingId>,
+		},
+		LiquidityProvision {
+			lp_account: AccountId,
+			refund_address: Option<ForeignChainAddress>,
+		},
+		Refund {
+			refund_address: ForeignChainAddress,
+			reason: DepositFailedReason,
+		},
+	}
+
+	#[frame_support::storage_alias]
+	pub type ScheduledEgressFetchOrTransfer<T: Config<I>, I: 'static> =
+		StorageValue<Pallet<T, I>, Vec<FetchOrTransfer<<T as Config<I>>::TargetChain>>, ValueQuery>;
+
+	#[frame_support::storage_alias]
+	pub type DepositChannelLookup<T: Config<I>, I: 'static> = StorageMap<
+		Pallet<T, I>,
+		Twox64Concat,
+		TargetChainAccount<T, I>,
+		DepositChannelDetails<T, I>,
+		OptionQuery,
+	>;
+}
+
+/// Adds an empty egress memo to the open swap channels and the scheduled transfers. The scheduled
+/// transfers are written in the layout expected by the
+/// [EgressOriginMigration](super::egress_origin_migration::EgressOriginMigration) that follows.
+pub struct EgressMemoMigration<T: Config<I>, I: 'static = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for EgressMemoMigration<T, I> {
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((
+			old::DepositChannelLookup::<T, I>::iter_keys().count() as u64,
+			old::ScheduledEgressFetchOrTransfer::<T, I>::decode_len().unwrap_or_default() as u64,
+		)
+			.encode())
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		crate::DepositChannelLookup::<T, I>::translate_values::<old::DepositChannelDetails<T, I>, _>(
+			|old_deposit_channel_details| {
+				let action = match old_deposit_channel_details.action {
+					old::ChannelAction::Swap {
+						destination_asset,
+						destination_address,
+						broker_fees,
+						channel_metadata,
+						refund_params,
+						dca_params,
+						tracking_id,
+					} => ChannelAction::Swap {
+						destination_asset,
+						destination_address,
+						broker_fees,
+						channel_metadata,
+						refund_params,
+						dca_params,
+						tracking_id,
+						egress_memo: None,
+					},
+					old::ChannelAction::LiquidityProvision { lp_account, refund_address } =>
+						ChannelAction::LiquidityProvision { lp_account, refund_address },
+					old::ChannelAction::Refund { refund_address, reason } =>
+						ChannelAction::Refund { refund_address, reason },
+				};
+
+				Some(DepositChannelDetails::<T, I> {
+					owner: old_deposit_channel_details.owner,
+					deposit_channel: old_deposit_channel_details.deposit_channel,
+					opened_at: old_deposit_channel_details.opened_at,
+					expires_at: old_deposit_channel_details.expires_at,
+					action,
+					boost_fee: old_deposit_channel_details.boost_fee,
+					boost_status: old_deposit_channel_details.boost_status,
+				})
+			},
+		);
+
+		let fetch_or_transfers = old::ScheduledEgressFetchOrTransfer::<T, I>::take()
+			.into_iter()
+			.map(|fetch_or_transfer| match fetch_or_transfer {
+				old::FetchOrTransfer::Fetch {
+					asset,
+					deposit_address,
+					deposit_fetch_id,
+					amount,
+				} => next::FetchOrTransfer::Fetch {
+					asset,
+					deposit_address,
+					deposit_fetch_id,
+					amount,
+				},
+				old::FetchOrTransfer::Transfer {
+					egress_id,
+					asset,
+					destination_address,
+					amount,
+					scheduled_at,
+				} => next::FetchOrTransfer::Transfer {
+					egress_id,
+					asset,
+					destination_address,
+					amount,
+					scheduled_at,
+					memo: None,
+				},
+			})
+			.collect::<Vec<_>>();
+		next::ScheduledEgressFetchOrTransfer::<T, I>::put(fetch_or_transfers);
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let (pre_deposit_channel_count, pre_fetch_or_transfer_count) =
+			<(u64, u64)>::decode(&mut state.as_slice())
+				.map_err(|_| DispatchError::from("Failed to decode state"))?;
+
+		assert_eq!(
+			pre_deposit_channel_count,
+			crate::DepositChannelLookup::<T, I>::iter().count() as u64
+		);
+		assert_eq!(
+			pre_fetch_or_transfer_count,
+			next::ScheduledEgressFetchOrTransfer::<T, I>::decode_len().unwrap_or_default() as u64
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use super::*;
+	use crate::mock_eth::{new_test_ext, Test};
+	use cf_chains::Ethereum;
+
+	#[test]
+	fn test_migration() {
+		new_test_ext().execute_with(|| {
+			old::ScheduledEgressFetchOrTransfer::<Test, ()>::put(vec![old::FetchOrTransfer::<
+				Ethereum,
+			>::Transfer {
+				egress_id: (ForeignChain::Ethereum, 0, 1),
+				asset: cf_chains::assets::eth::Asset::Eth,
+				destination_address: Default::default(),
+				amount: 1_000,
+				scheduled_at: 1,
+			}]);
+
+			#[cfg(feature = "try-runtime")]
+			let state: Vec<u8> = EgressMemoMigration::<Test, ()>::pre_upgrade().unwrap();
+
+			EgressMemoMigration::<Test, ()>::on_runtime_upgrade();
+
+			#[cfg(feature = "try-runtime")]
+			EgressMemoMigration::<Test, ()>::post_upgrade(state).unwrap();
+
+			assert!(
+				next::ScheduledEgressFetchOrTransfer::<Test, ()>::get() ==
+					vec![next::FetchOrTransfer::<Ethereum>::Transfer {
+						egress_id: (ForeignChain::Ethereum, 0, 1),
+						asset: cf_chains::assets::eth::Asset::Eth,
+						destination_address: Default::default(),
+						amount: 1_000,
+						scheduled_at: 1,
+						memo: None,
+					}]
+			);
+		});
+	}
+}
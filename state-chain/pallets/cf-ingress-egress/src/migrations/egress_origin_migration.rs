@@ -0,0 +1,181 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::*;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+use codec::{Decode, Encode};
+
+pub mod old {
+	use cf_chains::ForeignChainAddress;
+
+	use super::*;
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub enum FetchOrTransfer<C: Chain> {
+		Fetch {
+			asset: C::ChainAsset,
+			deposit_address: C::ChainAccount,
+			deposit_fetch_id: Option<C::DepositFetchId>,
+			amount: C::ChainAmount,
+		},
+		Transfer {
+			egress_id: EgressId,
+			asset: C::ChainAsset,
+			destination_address: C::ChainAccount,
+			amount: C::ChainAmount,
+			scheduled_at: BlockNumber,
+			memo: Option<EgressMemo>,
+		},
+	}
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub struct CrossChainMessage<C: Chain> {
+		pub egress_id: EgressId,
+		pub asset: C::ChainAsset,
+		pub amount: C::ChainAmount,
+		pub destination_address: C::ChainAccount,
+		pub message: CcmMessage,
+		pub source_chain: ForeignChain,
+		pub source_address: Option<ForeignChainAddress>,
+		pub ccm_additional_data: CcmAdditionalData,
+		pub gas_budget: GasAmount,
+		pub scheduled_at: BlockNumber,
+	}
+
+	#[frame_support::storage_alias]
+	pub type ScheduledEgressFetchOrTransfer<T: Config<I>, I: 'static> =
+		StorageValue<Pallet<T, I>, Vec<FetchOrTransfer<<T as Config<I>>::TargetChain>>, ValueQuery>;
+
+	#[frame_support::storage_alias]
+	pub type ScheduledEgressCcm<T: Config<I>, I: 'static> = StorageValue<
+		Pallet<T, I>,
+		Vec<CrossChainMessage<<T as Config<I>>::TargetChain>>,
+		ValueQuery,
+	>;
+}
+
+/// Tags the queued transfers and CCMs with an egress origin. The origin of egresses scheduled
+/// before the upgrade isn't recorded, so they are tagged as swap outputs, which most of them are.
+pub struct EgressOriginMigration<T: Config<I>, I: 'static = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for EgressOriginMigration<T, I> {
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((
+			old::ScheduledEgressFetchOrTransfer::<T, I>::decode_len().unwrap_or_default() as u64,
+			old::ScheduledEgressCcm::<T, I>::decode_len().unwrap_or_default() as u64,
+		)
+			.encode())
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		let fetch_or_transfers = old::ScheduledEgressFetchOrTransfer::<T, I>::take()
+			.into_iter()
+			.map(|fetch_or_transfer| match fetch_or_transfer {
+				old::FetchOrTransfer::Fetch {
+					asset,
+					deposit_address,
+					deposit_fetch_id,
+					amount,
+				} => FetchOrTransfer::Fetch { asset, deposit_address, deposit_fetch_id, amount },
+				old::FetchOrTransfer::Transfer {
+					egress_id,
+					asset,
+					destination_address,
+					amount,
+					scheduled_at,
+					memo,
+				} => FetchOrTransfer::Transfer {
+					egress_id,
+					asset,
+					destination_address,
+					amount,
+					scheduled_at,
+					memo,
+					origin: EgressOrigin::Swap,
+				},
+			})
+			.collect::<Vec<_>>();
+		crate::ScheduledEgressFetchOrTransfer::<T, I>::put(fetch_or_transfers);
+
+		let ccms = old::ScheduledEgressCcm::<T, I>::take()
+			.into_iter()
+			.map(|old| CrossChainMessage {
+				egress_id: old.egress_id,
+				asset: old.asset,
+				amount: old.amount,
+				destination_address: old.destination_address,
+				message: old.message,
+				source_chain: old.source_chain,
+				source_address: old.source_address,
+				ccm_additional_data: old.ccm_additional_data,
+				gas_budget: old.gas_budget,
+				scheduled_at: old.scheduled_at,
+				origin: EgressOrigin::Swap,
+			})
+			.collect::<Vec<_>>();
+		crate::ScheduledEgressCcm::<T, I>::put(ccms);
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let (pre_fetch_or_transfer_count, pre_ccm_count) =
+			<(u64, u64)>::decode(&mut state.as_slice())
+				.map_err(|_| DispatchError::from("Failed to decode state"))?;
+
+		assert_eq!(
+			pre_fetch_or_transfer_count,
+			crate::ScheduledEgressFetchOrTransfer::<T, I>::get().len() as u64
+		);
+		assert_eq!(pre_ccm_count, crate::ScheduledEgressCcm::<T, I>::get().len() as u64);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use super::*;
+	use crate::mock_eth::{new_test_ext, Test};
+	use cf_chains::Ethereum;
+
+	#[test]
+	fn test_migration() {
+		new_test_ext().execute_with(|| {
+			old::ScheduledEgressFetchOrTransfer::<Test, ()>::put(vec![old::FetchOrTransfer::<
+				Ethereum,
+			>::Transfer {
+				egress_id: (ForeignChain::Ethereum, 0, 1),
+				asset: cf_chains::assets::eth::Asset::Eth,
+				destination_address: Default::default(),
+				amount: 1_000,
+				scheduled_at: 1,
+				memo: None,
+			}]);
+
+			#[cfg(feature = "try-runtime")]
+			let state: Vec<u8> = EgressOriginMigration::<Test, ()>::pre_upgrade().unwrap();
+
+			EgressOriginMigration::<Test, ()>::on_runtime_upgrade();
+
+			#[cfg(feature = "try-runtime")]
+			EgressOriginMigration::<Test, ()>::post_upgrade(state).unwrap();
+
+			assert_eq!(
+				crate::ScheduledEgressFetchOrTransfer::<Test, ()>::get(),
+				vec![FetchOrTransfer::<Ethereum>::Transfer {
+					egress_id: (ForeignChain::Ethereum, 0, 1),
+					asset: cf_chains::assets::eth::Asset::Eth,
+					destination_address: Default::default(),
+					amount: 1_000,
+					scheduled_at: 1,
+					memo: None,
+					origin: EgressOrigin::Swap,
+				}]
+			);
+		});
+	}
+}
@@ -0,0 +1,194 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::*;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+use codec::{Decode, Encode};
+
+pub mod old {
+	use cf_chains::ForeignChainAddress;
+
+	use super::*;
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub enum FetchOrTransfer<C: Chain> {
+		Fetch {
+			asset: C::ChainAsset,
+			deposit_address: C::ChainAccount,
+			deposit_fetch_id: Option<C::DepositFetchId>,
+			amount: C::ChainAmount,
+		},
+		Transfer {
+			egress_id: EgressId,
+			asset: C::ChainAsset,
+			destination_address: C::ChainAccount,
+			amount: C::ChainAmount,
+		},
+	}
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub struct CrossChainMessage<C: Chain> {
+		pub egress_id: EgressId,
+		pub asset: C::ChainAsset,
+		pub amount: C::ChainAmount,
+		pub destination_address: C::ChainAccount,
+		pub message: CcmMessage,
+		pub source_chain: ForeignChain,
+		pub source_address: Option<ForeignChainAddress>,
+		pub ccm_additional_data: CcmAdditionalData,
+		pub gas_budget: GasAmount,
+	}
+
+	#[frame_support::storage_alias]
+	pub type ScheduledEgressFetchOrTransfer<T: Config<I>, I: 'static> =
+		StorageValue<Pallet<T, I>, Vec<FetchOrTransfer<<T as Config<I>>::TargetChain>>, ValueQuery>;
+
+	#[frame_support::storage_alias]
+	pub type ScheduledEgressCcm<T: Config<I>, I: 'static> = StorageValue<
+		Pallet<T, I>,
+		Vec<CrossChainMessage<<T as Config<I>>::TargetChain>>,
+		ValueQuery,
+	>;
+}
+
+/// Records the current block as the scheduling time of all queued egresses, since the real
+/// scheduling time is not known.
+pub struct EgressScheduledAtMigration<T: Config<I>, I: 'static = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for EgressScheduledAtMigration<T, I> {
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((
+			old::ScheduledEgressFetchOrTransfer::<T, I>::get().len() as u64,
+			old::ScheduledEgressCcm::<T, I>::get().len() as u64,
+		)
+			.encode())
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		let scheduled_at: BlockNumber =
+			frame_system::Pallet::<T>::block_number().unique_saturated_into();
+
+		let _ = crate::ScheduledEgressFetchOrTransfer::<T, I>::translate::<
+			Vec<old::FetchOrTransfer<T::TargetChain>>,
+			_,
+		>(|old_requests| {
+			old_requests.map(|old_requests| {
+				old_requests
+					.into_iter()
+					.map(|request| match request {
+						old::FetchOrTransfer::Fetch {
+							asset,
+							deposit_address,
+							deposit_fetch_id,
+							amount,
+						} => FetchOrTransfer::Fetch {
+							asset,
+							deposit_address,
+							deposit_fetch_id,
+							amount,
+						},
+						old::FetchOrTransfer::Transfer {
+							egress_id,
+							asset,
+							destination_address,
+							amount,
+						} => FetchOrTransfer::Transfer {
+							egress_id,
+							asset,
+							destination_address,
+							amount,
+							scheduled_at,
+							memo: None,
+							origin: EgressOrigin::Swap,
+						},
+					})
+					.collect()
+			})
+		});
+
+		let _ = crate::ScheduledEgressCcm::<T, I>::translate::<
+			Vec<old::CrossChainMessage<T::TargetChain>>,
+			_,
+		>(|old_messages| {
+			old_messages.map(|old_messages| {
+				old_messages
+					.into_iter()
+					.map(|old| CrossChainMessage {
+						egress_id: old.egress_id,
+						asset: old.asset,
+						amount: old.amount,
+						destination_address: old.destination_address,
+						message: old.message,
+						source_chain: old.source_chain,
+						source_address: old.source_address,
+						ccm_additional_data: old.ccm_additional_data,
+						gas_budget: old.gas_budget,
+						scheduled_at,
+						origin: EgressOrigin::Swap,
+					})
+					.collect()
+			})
+		});
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let (pre_fetch_or_transfer_count, pre_ccm_count) =
+			<(u64, u64)>::decode(&mut state.as_slice())
+				.map_err(|_| DispatchError::from("Failed to decode state"))?;
+
+		assert_eq!(
+			pre_fetch_or_transfer_count,
+			crate::ScheduledEgressFetchOrTransfer::<T, I>::get().len() as u64
+		);
+		assert_eq!(pre_ccm_count, crate::ScheduledEgressCcm::<T, I>::get().len() as u64);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use super::*;
+	use crate::mock_eth::{new_test_ext, Test};
+	use cf_chains::Ethereum;
+
+	#[test]
+	fn test_migration() {
+		new_test_ext().execute_with(|| {
+			old::ScheduledEgressFetchOrTransfer::<Test, ()>::put(vec![old::FetchOrTransfer::<
+				Ethereum,
+			>::Transfer {
+				egress_id: (ForeignChain::Ethereum, 0, 1),
+				asset: cf_chains::assets::eth::Asset::Eth,
+				destination_address: Default::default(),
+				amount: 1_000,
+			}]);
+
+			#[cfg(feature = "try-runtime")]
+			let state: Vec<u8> = EgressScheduledAtMigration::<Test, ()>::pre_upgrade().unwrap();
+
+			EgressScheduledAtMigration::<Test, ()>::on_runtime_upgrade();
+
+			#[cfg(feature = "try-runtime")]
+			EgressScheduledAtMigration::<Test, ()>::post_upgrade(state).unwrap();
+
+			assert_eq!(
+				crate::ScheduledEgressFetchOrTransfer::<Test, ()>::get(),
+				vec![FetchOrTransfer::<Ethereum>::Transfer {
+					egress_id: (ForeignChain::Ethereum, 0, 1),
+					asset: cf_chains::assets::eth::Asset::Eth,
+					destination_address: Default::default(),
+					amount: 1_000,
+					scheduled_at: 1,
+					memo: None,
+					origin: EgressOrigin::Swap,
+				}]
+			);
+		});
+	}
+}
@@ -0,0 +1,72 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::*;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+#[cfg(feature = "try-runtime")]
+use codec::{Decode, Encode};
+
+/// Initialises the number of open deposit channels of each owner from the owner index.
+pub struct OpenChannelCountMigration<T: Config<I>, I: 'static = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for OpenChannelCountMigration<T, I> {
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((crate::DepositChannelsByOwner::<T, I>::iter_keys().count() as u64).encode())
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		for (owner, _) in crate::DepositChannelsByOwner::<T, I>::iter_keys() {
+			crate::OpenChannelCount::<T, I>::mutate(owner, |count| count.saturating_accrue(1));
+		}
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let pre_deposit_channel_count = <u64>::decode(&mut state.as_slice())
+			.map_err(|_| DispatchError::from("Failed to decode state"))?;
+
+		assert_eq!(
+			pre_deposit_channel_count,
+			crate::OpenChannelCount::<T, I>::iter_values()
+				.map(|count| count as u64)
+				.sum::<u64>()
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use super::*;
+	use crate::mock_eth::{new_test_ext, Test, ALICE, BROKER};
+	use sp_core::H160;
+
+	#[test]
+	fn test_migration() {
+		new_test_ext().execute_with(|| {
+			for (owner, address) in [
+				(ALICE, H160::from([1; 20])),
+				(BROKER, H160::from([2; 20])),
+				(BROKER, H160::from([3; 20])),
+			] {
+				crate::DepositChannelsByOwner::<Test, ()>::insert(owner, address, ());
+			}
+
+			#[cfg(feature = "try-runtime")]
+			let state: Vec<u8> = OpenChannelCountMigration::<Test, ()>::pre_upgrade().unwrap();
+
+			OpenChannelCountMigration::<Test, ()>::on_runtime_upgrade();
+
+			#[cfg(feature = "try-runtime")]
+			OpenChannelCountMigration::<Test, ()>::post_upgrade(state).unwrap();
+
+			assert_eq!(crate::OpenChannelCount::<Test, ()>::get(ALICE), 1);
+			assert_eq!(crate::OpenChannelCount::<Test, ()>::get(BROKER), 2);
+		});
+	}
+}
@@ -0,0 +1,66 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::*;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+/// Initialises the pending egress amounts from the scheduled transfers and CCMs.
+pub struct PendingEgressAmountsMigration<T: Config<I>, I: 'static = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade for PendingEgressAmountsMigration<T, I> {
+	fn on_runtime_upgrade() -> Weight {
+		for (asset, amount) in Pallet::<T, I>::pending_egress_amounts_of_scheduled_egresses() {
+			PendingEgressAmounts::<T, I>::insert(asset, amount);
+		}
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+		assert_eq!(
+			PendingEgressAmounts::<T, I>::iter().collect::<BTreeMap<_, _>>(),
+			Pallet::<T, I>::pending_egress_amounts_of_scheduled_egresses()
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use super::*;
+	use crate::mock_eth::{new_test_ext, Test};
+	use cf_chains::{assets::eth::Asset as EthAsset, Ethereum};
+	use sp_core::H160;
+
+	#[test]
+	fn test_migration() {
+		new_test_ext().execute_with(|| {
+			for (asset, amount, egress_id) in
+				[(EthAsset::Eth, 100, 1), (EthAsset::Eth, 200, 2), (EthAsset::Flip, 300, 3)]
+			{
+				ScheduledEgressFetchOrTransfer::<Test, ()>::append(
+					FetchOrTransfer::<Ethereum>::Transfer {
+						asset,
+						amount,
+						destination_address: H160::from([1; 20]),
+						egress_id: (ForeignChain::Ethereum, 0, egress_id),
+						scheduled_at: 0,
+						memo: None,
+						origin: EgressOrigin::Swap,
+					},
+				);
+			}
+
+			PendingEgressAmountsMigration::<Test, ()>::on_runtime_upgrade();
+
+			#[cfg(feature = "try-runtime")]
+			PendingEgressAmountsMigration::<Test, ()>::post_upgrade(Vec::new()).unwrap();
+
+			assert_eq!(PendingEgressAmounts::<Test, ()>::get(EthAsset::Eth), 300);
+			assert_eq!(PendingEgressAmounts::<Test, ()>::get(EthAsset::Flip), 300);
+			assert_eq!(PendingEgressAmounts::<Test, ()>::get(EthAsset::Usdc), 0);
+		});
+	}
+}
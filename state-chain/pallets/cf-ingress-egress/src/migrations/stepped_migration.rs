@@ -0,0 +1,263 @@
+use crate::*;
+use codec::FullCodec;
+use frame_support::{
+	storage::{unhashed, IterableStorageMap, StorageMap as StorageMapT},
+	traits::UncheckedOnRuntimeUpgrade,
+	weights::WeightMeter,
+};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+/// A migration that is too heavy to run in a single block. It is started by
+/// [StartSteppedMigration] during the runtime upgrade and then advanced in `on_idle`, one
+/// weight-bounded step per block, until it reports completion.
+pub trait SteppedMigration<T: Config<I>, I: 'static> {
+	/// Identifies the migration in storage and in the completion event.
+	const ID: &'static [u8];
+
+	/// Performs as much work as the meter allows, starting after `cursor`. Returns the cursor to
+	/// resume from in the next block, or `None` once the migration is complete.
+	fn step(cursor: Option<Vec<u8>>, meter: &mut WeightMeter) -> Option<Vec<u8>>;
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok(Vec::new())
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+		Ok(())
+	}
+}
+
+/// No stepped migration pending.
+impl<T: Config<I>, I: 'static> SteppedMigration<T, I> for () {
+	const ID: &'static [u8] = b"";
+
+	fn step(_cursor: Option<Vec<u8>>, _meter: &mut WeightMeter) -> Option<Vec<u8>> {
+		None
+	}
+}
+
+#[derive(Clone, RuntimeDebug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct SteppedMigrationProgress {
+	pub migration_id: Vec<u8>,
+	/// The cursor to resume from, `None` if no step has been performed yet.
+	pub cursor: Option<Vec<u8>>,
+	pub steps: u32,
+}
+
+/// Registers `M` as the ongoing stepped migration. Meant to be wrapped in a
+/// [frame_support::migrations::VersionedMigration] so that the storage version is bumped as part
+/// of the runtime upgrade while the data itself is migrated over the following blocks.
+pub struct StartSteppedMigration<M, T, I = ()>(PhantomData<(M, T, I)>);
+
+impl<M: SteppedMigration<T, I>, T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade
+	for StartSteppedMigration<M, T, I>
+{
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		frame_support::ensure!(
+			!crate::SteppedMigrationInProgress::<T, I>::exists(),
+			DispatchError::from("A stepped migration is still in progress")
+		);
+		M::pre_upgrade()
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		if let Some(progress) = crate::SteppedMigrationInProgress::<T, I>::get() {
+			log::error!(
+				"Stepped migration {:?} not started: {:?} is still in progress.",
+				M::ID,
+				progress.migration_id
+			);
+		} else {
+			crate::SteppedMigrationInProgress::<T, I>::put(SteppedMigrationProgress {
+				migration_id: M::ID.to_vec(),
+				cursor: None,
+				steps: 0,
+			});
+		}
+		T::DbWeight::get().reads_writes(1, 1)
+	}
+
+	/// Runs the remaining steps to completion so that the migration's own checks can be applied.
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		frame_support::ensure!(
+			crate::SteppedMigrationInProgress::<T, I>::get()
+				.is_some_and(|progress| progress.migration_id == M::ID),
+			DispatchError::from("Stepped migration was not started")
+		);
+		while crate::SteppedMigrationInProgress::<T, I>::exists() {
+			Pallet::<T, I>::step_migration::<M>(Weight::MAX);
+		}
+		M::post_upgrade(state)
+	}
+}
+
+/// Translates the values of `Map` from `Old` to `New` for as many entries as the meter allows,
+/// charging `weight_per_entry` for each, and resuming after the raw key in `cursor`. Entries for
+/// which `f` returns `None` are removed.
+///
+/// Intended as the body of [SteppedMigration::step] for the pallet's larger maps.
+pub fn translate_map_step<Map, K, Old, New>(
+	cursor: Option<Vec<u8>>,
+	meter: &mut WeightMeter,
+	weight_per_entry: Weight,
+	f: impl Fn(K, Old) -> Option<New>,
+) -> Option<Vec<u8>>
+where
+	Map: IterableStorageMap<K, New> + StorageMapT<K, New>,
+	K: FullCodec,
+	Old: Decode,
+	New: FullCodec,
+{
+	let cursor_start = cursor.clone();
+	let mut keys = match cursor {
+		Some(raw_key) => Map::iter_keys_from(raw_key),
+		None => Map::iter_keys(),
+	};
+	let mut last_key = cursor_start;
+
+	loop {
+		if !meter.can_consume(weight_per_entry) {
+			// Out of weight: resume after the last migrated entry next time, or from the start
+			// if nothing has been migrated yet.
+			return Some(last_key.unwrap_or_default())
+		}
+		let Some(key) = keys.next() else { return None };
+		meter.consume(weight_per_entry);
+
+		let raw_key = Map::hashed_key_for(&key);
+		match unhashed::get::<Old>(&raw_key) {
+			Some(old) => match f(key, old) {
+				Some(new) => unhashed::put(&raw_key, &new),
+				None => unhashed::kill(&raw_key),
+			},
+			None => log::error!("Failed to decode stepped migration entry {:?}.", raw_key),
+		}
+		last_key = Some(raw_key);
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// Advances the ongoing stepped migration `M`, using at most `weight_limit`.
+	pub fn step_migration<M: SteppedMigration<T, I>>(weight_limit: Weight) -> Weight {
+		let mut meter = WeightMeter::with_limit(weight_limit);
+		let read_weight = T::DbWeight::get().reads(1);
+		if meter.try_consume(read_weight).is_err() {
+			return Weight::zero()
+		}
+		let Some(mut progress) = SteppedMigrationInProgress::<T, I>::get() else {
+			return meter.consumed()
+		};
+
+		if progress.migration_id != M::ID {
+			log_or_panic!(
+				"Stepped migration {:?} is in progress, but {:?} is configured.",
+				progress.migration_id,
+				M::ID
+			);
+			SteppedMigrationInProgress::<T, I>::kill();
+			return meter.consumed()
+		}
+
+		let write_weight = T::DbWeight::get().writes(1);
+		if !meter.can_consume(write_weight) {
+			return meter.consumed()
+		}
+		meter.consume(write_weight);
+
+		let cursor = progress.cursor.take();
+		// An empty cursor means no entry could be processed from the start, so start over.
+		let cursor = cursor.filter(|cursor| !cursor.is_empty());
+		progress.steps.saturating_inc();
+		match M::step(cursor, &mut meter) {
+			Some(next_cursor) => {
+				progress.cursor = Some(next_cursor);
+				SteppedMigrationInProgress::<T, I>::put(progress);
+			},
+			None => {
+				SteppedMigrationInProgress::<T, I>::kill();
+				Self::deposit_event(Event::<T, I>::SteppedMigrationCompleted {
+					migration_id: progress.migration_id,
+					steps: progress.steps,
+				});
+			},
+		}
+
+		meter.consumed()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::mock_eth::{new_test_ext, RuntimeEvent, System, Test};
+
+	#[frame_support::storage_alias]
+	type TestMap = StorageMap<Pallet<Test, ()>, Twox64Concat, u32, u64>;
+
+	mod old {
+		use super::*;
+
+		#[frame_support::storage_alias]
+		pub type TestMap = StorageMap<Pallet<Test, ()>, Twox64Concat, u32, u32>;
+	}
+
+	const ENTRIES: u32 = 10;
+	const WEIGHT_PER_ENTRY: Weight = Weight::from_parts(1_000, 0);
+
+	struct TestMigration;
+
+	impl SteppedMigration<Test, ()> for TestMigration {
+		const ID: &'static [u8] = b"test";
+
+		fn step(cursor: Option<Vec<u8>>, meter: &mut WeightMeter) -> Option<Vec<u8>> {
+			translate_map_step::<TestMap, _, u32, _>(
+				cursor,
+				meter,
+				WEIGHT_PER_ENTRY,
+				|key, old: u32| (key % 5 != 0).then_some(old as u64 * 2),
+			)
+		}
+	}
+
+	#[test]
+	fn stepped_migration_completes_over_several_blocks() {
+		new_test_ext().execute_with(|| {
+			for key in 0..ENTRIES {
+				old::TestMap::insert(key, key);
+			}
+
+			// Nothing happens until the migration is started.
+			Pallet::<Test, ()>::step_migration::<TestMigration>(Weight::MAX);
+			assert_eq!(old::TestMap::get(1), Some(1));
+
+			StartSteppedMigration::<TestMigration, Test>::on_runtime_upgrade();
+
+			// Enough weight for three entries per block.
+			let weight_limit = WEIGHT_PER_ENTRY.saturating_mul(3);
+
+			let mut blocks = 0;
+			while crate::SteppedMigrationInProgress::<Test, ()>::exists() {
+				assert!(Pallet::<Test, ()>::step_migration::<TestMigration>(weight_limit)
+					.all_lte(weight_limit));
+				blocks += 1;
+				assert!(blocks <= ENTRIES, "Migration is not making progress");
+			}
+
+			assert_eq!(blocks, ENTRIES.div_ceil(3));
+			for key in 0..ENTRIES {
+				assert_eq!(TestMap::get(key), (key % 5 != 0).then_some(key as u64 * 2));
+			}
+			System::assert_last_event(RuntimeEvent::IngressEgress(
+				Event::<Test, ()>::SteppedMigrationCompleted {
+					migration_id: b"test".to_vec(),
+					steps: blocks,
+				},
+			));
+		});
+	}
+}
@@ -0,0 +1,191 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::*;
+use cf_chains::address::EncodedAddress;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+pub mod old {
+	use super::*;
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub struct VaultDepositWitness<T: Config<I>, I: 'static> {
+		pub input_asset: TargetChainAsset<T, I>,
+		pub deposit_address: Option<TargetChainAccount<T, I>>,
+		pub channel_id: Option<ChannelId>,
+		pub deposit_amount: <T::TargetChain as Chain>::ChainAmount,
+		pub deposit_details: <T::TargetChain as Chain>::DepositDetails,
+		pub output_asset: Asset,
+		pub destination_address: EncodedAddress,
+		pub deposit_metadata: Option<CcmDepositMetadata>,
+		pub tx_id: TransactionInIdFor<T, I>,
+		pub broker_fee: Option<Beneficiary<T::AccountId>>,
+		pub affiliate_fees: Affiliates<AffiliateShortId>,
+		pub refund_params: Option<ChannelRefundParametersDecoded>,
+		pub dca_params: Option<DcaParameters>,
+		pub boost_fee: BasisPoints,
+		pub tracking_id: Option<TrackingId>,
+	}
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub enum DepositFailedDetails<T: Config<I>, I: 'static> {
+		DepositChannel { deposit_witness: DepositWitness<T::TargetChain> },
+		Vault { vault_witness: Box<VaultDepositWitness<T, I>> },
+	}
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub struct DepositFailure<T: Config<I>, I: 'static> {
+		pub block_height: TargetChainBlockNumber<T, I>,
+		pub reason: DepositFailedReason,
+		pub details: DepositFailedDetails<T, I>,
+		pub recorded_at: BlockNumber,
+	}
+}
+
+/// The layout of the deposit failure history written by this migration.
+pub mod new {
+	use super::*;
+
+	#[derive(PartialEq, Eq, Encode, Decode)]
+	pub struct DepositFailure<T: Config<I>, I: 'static> {
+		pub block_height: TargetChainBlockNumber<T, I>,
+		pub reason: DepositFailedReason,
+		pub details: DepositFailedDetails<T, I>,
+		pub recorded_at: BlockNumber,
+	}
+
+	#[frame_support::storage_alias]
+	pub type DepositFailureHistory<T: Config<I>, I: 'static> =
+		StorageMap<Pallet<T, I>, Twox64Concat, u64, DepositFailure<T, I>, OptionQuery>;
+}
+
+impl<T: Config<I>, I: 'static> From<old::VaultDepositWitness<T, I>> for VaultDepositWitness<T, I> {
+	fn from(old: old::VaultDepositWitness<T, I>) -> Self {
+		VaultDepositWitness {
+			input_asset: old.input_asset,
+			deposit_address: old.deposit_address,
+			channel_id: old.channel_id,
+			deposit_amount: old.deposit_amount,
+			deposit_details: old.deposit_details,
+			output_asset: old.output_asset,
+			destination_address: old.destination_address,
+			deposit_metadata: old.deposit_metadata,
+			tx_id: old.tx_id,
+			broker_fee: old.broker_fee,
+			affiliate_fees: old.affiliate_fees,
+			refund_params: old.refund_params,
+			dca_params: old.dca_params,
+			boost_fee: old.boost_fee,
+			tracking_id: old.tracking_id,
+			verified_sender: None,
+		}
+	}
+}
+
+pub struct VaultDepositWitnessSenderMigration<T: Config<I>, I: 'static = ()>(PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> UncheckedOnRuntimeUpgrade
+	for VaultDepositWitnessSenderMigration<T, I>
+{
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((crate::DepositFailureHistory::<T, I>::iter_keys().count() as u64).encode())
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		new::DepositFailureHistory::<T, I>::translate_values::<old::DepositFailure<T, I>, _>(
+			|old| {
+				Some(new::DepositFailure {
+					block_height: old.block_height,
+					reason: old.reason,
+					details: match old.details {
+						old::DepositFailedDetails::DepositChannel { deposit_witness } =>
+							DepositFailedDetails::DepositChannel { deposit_witness },
+						old::DepositFailedDetails::Vault { vault_witness } =>
+							DepositFailedDetails::Vault {
+								vault_witness: Box::new((*vault_witness).into()),
+							},
+					},
+					recorded_at: old.recorded_at,
+				})
+			},
+		);
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let pre_upgrade_count = <u64>::decode(&mut state.as_slice())
+			.map_err(|_| DispatchError::from("Failed to decode state"))?;
+
+		assert_eq!(
+			pre_upgrade_count,
+			new::DepositFailureHistory::<T, I>::iter_values().count() as u64
+		);
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod migration_tests {
+	use crate::mock_eth::{new_test_ext, Test};
+	use cf_chains::{assets::eth::Asset as EthAsset, evm::DepositDetails};
+	use sp_core::H256;
+
+	use super::*;
+
+	#[test]
+	fn vault_witnesses_in_failure_history_are_migrated() {
+		new_test_ext().execute_with(|| {
+			let old_witness = old::VaultDepositWitness::<Test, ()> {
+				input_asset: EthAsset::Eth,
+				deposit_address: None,
+				channel_id: None,
+				deposit_amount: 1_000,
+				deposit_details: DepositDetails { tx_hashes: None },
+				output_asset: Asset::Flip,
+				destination_address: EncodedAddress::Eth([1; 20]),
+				deposit_metadata: None,
+				tx_id: H256::repeat_byte(1),
+				broker_fee: None,
+				affiliate_fees: Default::default(),
+				refund_params: None,
+				dca_params: None,
+				boost_fee: 0,
+				tracking_id: None,
+			};
+			frame_support::storage::unhashed::put(
+				&crate::DepositFailureHistory::<Test, ()>::hashed_key_for(0),
+				&old::DepositFailure::<Test, ()> {
+					block_height: 10,
+					reason: DepositFailedReason::InvalidDestinationAddress,
+					details: old::DepositFailedDetails::Vault {
+						vault_witness: Box::new(old_witness),
+					},
+					recorded_at: 2,
+				},
+			);
+
+			#[cfg(feature = "try-runtime")]
+			let state: Vec<u8> = VaultDepositWitnessSenderMigration::<Test, ()>::pre_upgrade().unwrap();
+
+			VaultDepositWitnessSenderMigration::<Test, ()>::on_runtime_upgrade();
+
+			#[cfg(feature = "try-runtime")]
+			VaultDepositWitnessSenderMigration::<Test, ()>::post_upgrade(state).unwrap();
+
+			let failure = new::DepositFailureHistory::<Test, ()>::get(0).unwrap();
+			assert_eq!(failure.block_height, 10);
+			assert_eq!(failure.reason, DepositFailedReason::InvalidDestinationAddress);
+			assert_eq!(failure.recorded_at, 2);
+			match failure.details {
+				DepositFailedDetails::Vault { vault_witness } => {
+					assert_eq!(vault_witness.tx_id, H256::repeat_byte(1));
+					assert_eq!(vault_witness.verified_sender, None);
+				},
+				_ => panic!("Expected a vault deposit failure"),
+			}
+		});
+	}
+}
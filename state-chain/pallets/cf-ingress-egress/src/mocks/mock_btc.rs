@@ -27,7 +27,7 @@ use cf_traits::{
 	DummyIngressSource, NetworkEnvironmentProvider, OnDeposit,
 };
 use frame_support::derive_impl;
-use sp_core::ConstBool;
+use sp_core::{ConstBool, ConstU8};
 
 type Block = frame_system::mocking::MockBlock<Test>;
 
@@ -88,6 +88,7 @@ impl pallet_cf_ingress_egress::Config for Test {
 	const MANAGE_CHANNEL_LIFETIME: bool = true;
 	type IngressSource = DummyIngressSource<Bitcoin>;
 	type TargetChain = Bitcoin;
+	type InstanceId = ConstU8<0>;
 	type AddressDerivation = MockAddressDerivation;
 	type AddressConverter = MockAddressConverter;
 	type Balance = MockBalance;
@@ -108,6 +109,8 @@ impl pallet_cf_ingress_egress::Config for Test {
 	type SwapLimitsProvider = MockSwapLimitsProvider;
 	type CcmValidityChecker = cf_chains::ccm_checker::CcmValidityChecker;
 	type AllowTransactionReports = ConstBool<true>;
+	type DepositRiskScorer = ();
+	type OnChainWoundDown = ();
 	type AffiliateRegistry = MockAffiliateRegistry;
 }
 
@@ -24,6 +24,7 @@ use cf_traits::{
 		balance_api::MockBalance,
 		broadcaster::MockBroadcaster,
 		chain_tracking::ChainTracker,
+		deposit_risk_scorer::MockDepositRiskScorer,
 		fee_payment::MockFeePayment,
 		fetches_transfers_limit_provider::MockFetchesTransfersLimitProvider,
 		swap_limits_provider::MockSwapLimitsProvider,
@@ -33,8 +34,9 @@ use cf_traits::{
 };
 use frame_support::derive_impl;
 use frame_system as system;
-use sp_core::{ConstBool, H256};
+use sp_core::{ConstBool, ConstU8, H256};
 use sp_runtime::traits::{BlakeTwo256, IdentityLookup, Zero};
+use std::cell::RefCell;
 
 type AccountId = u64;
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -81,13 +83,26 @@ impl OnDeposit<Ethereum> for MockDepositHandler {}
 pub type MockEgressBroadcaster =
 	MockBroadcaster<(MockEthereumApiCall<MockEvmEnvironment>, RuntimeCall)>;
 
+thread_local! {
+	pub static ADDRESS_DERIVATION_ERROR: RefCell<Option<AddressDerivationError>> = RefCell::new(None);
+}
+
 pub struct MockAddressDerivation;
 
+impl MockAddressDerivation {
+	pub fn set_error(error: Option<AddressDerivationError>) {
+		ADDRESS_DERIVATION_ERROR.with(|cell| *cell.borrow_mut() = error);
+	}
+}
+
 impl AddressDerivationApi<Ethereum> for MockAddressDerivation {
 	fn generate_address(
 		_source_asset: assets::eth::Asset,
 		channel_id: ChannelId,
 	) -> Result<<Ethereum as Chain>::ChainAccount, AddressDerivationError> {
+		if let Some(error) = ADDRESS_DERIVATION_ERROR.with(|cell| *cell.borrow()) {
+			return Err(error)
+		}
 		Ok([channel_id as u8; 20].into())
 	}
 
@@ -118,6 +133,7 @@ impl crate::Config for Test {
 	const MANAGE_CHANNEL_LIFETIME: bool = true;
 	type IngressSource = DummyIngressSource<Ethereum>;
 	type TargetChain = Ethereum;
+	type InstanceId = ConstU8<0>;
 	type AddressDerivation = MockAddressDerivation;
 	type AddressConverter = MockAddressConverter;
 	type Balance = MockBalance;
@@ -138,6 +154,8 @@ impl crate::Config for Test {
 	type SwapLimitsProvider = MockSwapLimitsProvider;
 	type CcmValidityChecker = cf_chains::ccm_checker::CcmValidityChecker;
 	type AllowTransactionReports = ConstBool<true>;
+	type DepositRiskScorer = MockDepositRiskScorer;
+	type OnChainWoundDown = ();
 	type AffiliateRegistry = MockAffiliateRegistry;
 }
 
@@ -278,6 +296,8 @@ impl<Ctx: Clone> RequestAddress for TestExternalities<Test, Ctx> {
 						0,
 						None,
 						None,
+						None,
+						None,
 					)
 					.map(|(channel_id, deposit_address, ..)| {
 						(request, channel_id, TestChainAccount::try_from(deposit_address).unwrap())
@@ -1,28 +1,45 @@
 mod boost;
+mod replay_protection;
 mod screening;
 
 use crate::{
-	mock_eth::*, BoostStatus, Call as PalletCall, ChannelAction, ChannelIdCounter,
-	ChannelOpeningFee, CrossChainMessage, DepositAction, DepositChannelLifetime,
-	DepositChannelLookup, DepositChannelPool, DepositFailedDetails, DepositFailedReason,
-	DepositOrigin, DepositWitness, DisabledEgressAssets, EgressDustLimit, Event as PalletEvent,
-	Event, FailedForeignChainCall, FailedForeignChainCalls, FetchOrTransfer, MinimumDeposit,
-	NetworkFeeDeductionFromBoostPercent, Pallet, PalletConfigUpdate, PalletSafeMode,
-	PrewitnessedDepositIdCounter, ScheduledEgressCcm, ScheduledEgressFetchOrTransfer,
-	VaultDepositWitness,
+	mock_eth::*, AbandonedEgress, AbandonedEgresses, BoostExposureCaps, BoostRiskThresholds,
+	BoostStatus, BroadcastBudgetSettings, BroadcastUsage, Call as PalletCall,
+	CarriedOverBroadcastBudget, CcmDeferral, CcmDeferralTimeout, CcmDeferrals, ChainHaltDetection,
+	ChainHaltDetectionSettings, ChainHalted, ChainWindDown, ChainWindDownStatus, ChannelAction,
+	ChannelIdCounter, ChannelOpenAttempt, ChannelOpenRetryPeriod, ChannelOpeningFee,
+	CrossChainMessage, DeferredDepositWitnesses, DepositAction, DepositAddressMismatch,
+	DepositAddressMismatches, DepositAddressVerificationInProgress, DepositChannelLifetime,
+	DepositChannelLookup, DepositChannelPool, DepositChannelRecycleQueue, DepositChannelsByOwner,
+	DepositFailedDetails, DepositFailedReason, DepositFailureHistory, DepositFailureHistoryRange,
+	DepositFailureRetentionPeriod, DepositOrigin, DepositWitness, DisabledEgressAssets,
+	DynamicMinimumDepositActive, EgressDustLimit, EgressTotals, EgressUsage, Event as PalletEvent,
+	Event, FailedForeignChainCall, FailedForeignChainCalls, FeeEstimateSamples,
+	FeeEstimationStrategy, FeeEstimationTelemetry, FeeEstimationTelemetryTotals, FetchOrTransfer,
+	LastBroadcastUsage, LastEgressUsage, MinimumDeposit, NetworkFeeDeductionFromBoostPercent,
+	NextRecycleBlockHeight, ObservedReorgs, OldestUnclearedVaultSwapEpoch, OpenChannelCount,
+	Pallet, PalletConfigUpdate, PalletSafeMode, PendingChannelOpenCursor, PendingChannelOpens,
+	PendingRefundAddressUpdates, PrewitnessedDepositIdCounter, ProcessedVaultSwaps,
+	RefundAddressUpdateDelay, ScheduledEgressCcm, ScheduledEgressFetchOrTransfer,
+	SkippedConsolidations, VaultDepositWitness, VaultSwapParameterWarning, WitnessSafetyMargin,
+	WitnessSafetyMarginBounds, CONSOLIDATION_SKIP_WARNING_THRESHOLD, DEPOSIT_FAILED_REASON_CODES,
+	MAX_CHANNEL_OPEN_RETRIES_PER_BLOCK, MAX_DEPOSIT_FAILURE_HISTORY, MAX_OBSERVED_REORGS,
 };
 use cf_chains::{
 	address::{AddressConverter, EncodedAddress},
 	assets::eth::Asset as EthAsset,
 	btc::{BitcoinNetwork, ScriptPubkey},
+	ccm_checker::{CcmLimits, CcmValidityError},
+	ccm_templating::CCM_OUTPUT_AMOUNT_PLACEHOLDER,
 	evm::{DepositDetails, EvmFetchId, H256},
 	mocks::MockEthereum,
-	CcmChannelMetadata, ChannelRefundParametersDecoded, DepositChannel, DepositOriginType,
-	ExecutexSwapAndCall, SwapOrigin, TransactionInIdForAnyChain, TransferAssetParams,
+	CcmChannelMetadata, ChannelRefundParametersDecoded, ConsolidationError, DepositChannel,
+	DepositOriginType, EgressMemo, ExecutexSwapAndCall, SwapOrigin, TransactionInIdForAnyChain,
+	TransferAssetParams,
 };
 use cf_primitives::{
-	AffiliateShortId, Affiliates, AssetAmount, BasisPoints, Beneficiaries, Beneficiary, ChannelId,
-	DcaParameters, ForeignChain, MAX_AFFILIATES,
+	AccountRole, AffiliateShortId, Affiliates, AssetAmount, BasisPoints, Beneficiaries,
+	Beneficiary, ChannelId, DcaParameters, EgressId, ForeignChain, TrackingId, MAX_AFFILIATES,
 };
 use cf_test_utilities::{assert_events_eq, assert_has_event, assert_has_matching_event};
 use cf_traits::{
@@ -30,7 +47,9 @@ use cf_traits::{
 		self,
 		address_converter::MockAddressConverter,
 		affiliate_registry::MockAffiliateRegistry,
-		api_call::{MockEthAllBatch, MockEthereumApiCall, MockEvmEnvironment},
+		api_call::{
+			MockEthAllBatch, MockEthExecutexSwapAndCall, MockEthereumApiCall, MockEvmEnvironment,
+		},
 		asset_converter::MockAssetConverter,
 		asset_withholding::MockAssetWithholding,
 		balance_api::MockBalance,
@@ -38,18 +57,22 @@ use cf_traits::{
 		chain_tracking::ChainTracker,
 		fetches_transfers_limit_provider::MockFetchesTransfersLimitProvider,
 		funding_info::MockFundingInfo,
+		swap_limits_provider::MockSwapLimitsProvider,
 		swap_request_api::{MockSwapRequest, MockSwapRequestHandler},
 	},
-	BalanceApi, DepositApi, EgressApi, EpochInfo, FetchesTransfersLimitProvider, FundingInfo,
-	GetBlockHeight, SafeMode, ScheduledEgressDetails, SwapRequestType,
+	AccountRoleRegistry, BalanceApi, DepositApi, EgressApi, EgressOrigin, EpochInfo,
+	FetchesTransfersLimitProvider, FundingInfo, GetBlockHeight, SafeMode, ScheduledEgressDetails,
+	SwapLimitsProvider, SwapRequestType,
 };
+use codec::Encode;
 use frame_support::{
 	assert_err, assert_noop, assert_ok,
 	traits::{Hooks, OriginTrait},
-	weights::Weight,
+	weights::{constants::ParityDbWeight, Weight, WeightMeter},
 };
 use sp_core::{bounded_vec, H160};
 use sp_runtime::{DispatchError, DispatchResult, Percent};
+use sp_std::collections::btree_map::BTreeMap;
 
 const ALICE_ETH_ADDRESS: EthereumAddress = H160([100u8; 20]);
 const BOB_ETH_ADDRESS: EthereumAddress = H160([101u8; 20]);
@@ -86,8 +109,22 @@ fn blacklisted_asset_will_not_egress_via_batch_all() {
 		}));
 
 		// Eth should be blocked while Flip can be sent
-		assert_ok!(IngressEgress::schedule_egress(asset, 1_000, ALICE_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_FLIP, 1_000, ALICE_ETH_ADDRESS, None));
+		assert_ok!(IngressEgress::schedule_egress(
+			asset,
+			1_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			1_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
 
 		IngressEgress::on_finalize(1);
 
@@ -98,7 +135,10 @@ fn blacklisted_asset_will_not_egress_via_batch_all() {
 				asset,
 				amount: 1_000,
 				destination_address: ALICE_ETH_ADDRESS,
-				egress_id: (ForeignChain::Ethereum, 1),
+				egress_id: (ForeignChain::Ethereum, 0, 1),
+				scheduled_at: 1,
+				memo: None,
+				origin: EgressOrigin::Swap,
 			}]
 		);
 
@@ -141,12 +181,16 @@ fn blacklisted_asset_will_not_egress_via_ccm() {
 			1_000,
 			ALICE_ETH_ADDRESS,
 			Some(ccm.clone()),
+			None,
+			EgressOrigin::Swap,
 		));
 		assert_ok!(IngressEgress::schedule_egress(
 			ETH_FLIP,
 			1_000,
 			ALICE_ETH_ADDRESS,
 			Some(ccm.clone()),
+			None,
+			EgressOrigin::Swap,
 		));
 
 		IngressEgress::on_finalize(1);
@@ -155,7 +199,7 @@ fn blacklisted_asset_will_not_egress_via_ccm() {
 		assert_eq!(
 			ScheduledEgressCcm::<Test, ()>::get(),
 			vec![CrossChainMessage {
-				egress_id: (ForeignChain::Ethereum, 1),
+				egress_id: (ForeignChain::Ethereum, 0, 1),
 				asset,
 				amount: 1_000,
 				destination_address: ALICE_ETH_ADDRESS,
@@ -164,6 +208,8 @@ fn blacklisted_asset_will_not_egress_via_ccm() {
 				source_address: ccm.source_address.clone(),
 				ccm_additional_data: ccm.channel_metadata.ccm_additional_data,
 				gas_budget,
+				scheduled_at: 1,
+				origin: EgressOrigin::Swap,
 			}]
 		);
 
@@ -177,6 +223,291 @@ fn blacklisted_asset_will_not_egress_via_ccm() {
 	});
 }
 
+#[test]
+fn pending_egress_amounts_track_scheduled_egresses() {
+	new_test_ext().execute_with(|| {
+		let assert_pending_egress_amounts = |expected: &[(EthAsset, AssetAmount)]| {
+			assert_eq!(
+				PendingEgressAmounts::<Test, ()>::iter().collect::<BTreeMap<_, _>>(),
+				IngressEgress::pending_egress_amounts_of_scheduled_egresses(),
+			);
+			assert_eq!(
+				PendingEgressAmounts::<Test, ()>::iter().collect::<BTreeMap<_, _>>(),
+				expected.iter().copied().collect::<BTreeMap<_, _>>(),
+			);
+		};
+
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			1_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			2_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			3_000,
+			BOB_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			4_000,
+			BOB_ETH_ADDRESS,
+			Some(CcmDepositMetadata {
+				source_chain: ForeignChain::Ethereum,
+				source_address: None,
+				channel_metadata: CcmChannelMetadata {
+					message: vec![0x00].try_into().unwrap(),
+					gas_budget: 0,
+					ccm_additional_data: vec![].try_into().unwrap(),
+				},
+			}),
+			None,
+			EgressOrigin::Swap,
+		));
+		assert_pending_egress_amounts(&[(ETH_ETH, 3_000), (ETH_FLIP, 7_000)]);
+
+		// Egresses of disabled assets remain pending.
+		assert_ok!(IngressEgress::enable_or_disable_egress(RuntimeOrigin::root(), ETH_FLIP, true));
+		IngressEgress::on_finalize(1);
+		assert_pending_egress_amounts(&[(ETH_FLIP, 7_000)]);
+
+		assert_ok!(IngressEgress::enable_or_disable_egress(RuntimeOrigin::root(), ETH_FLIP, false));
+		IngressEgress::on_finalize(2);
+		assert_pending_egress_amounts(&[]);
+	});
+}
+
+#[test]
+fn broadcasts_are_held_back_once_the_budget_is_used_up() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IngressEgress::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetBroadcastBudget {
+				settings: Some(BroadcastBudgetSettings { per_block: 1, max_carry_over: 1 })
+			}]
+			.try_into()
+			.unwrap()
+		));
+
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			1_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			2_000,
+			BOB_ETH_ADDRESS,
+			Some(CcmDepositMetadata {
+				source_chain: ForeignChain::Ethereum,
+				source_address: None,
+				channel_metadata: CcmChannelMetadata {
+					message: vec![0x00].try_into().unwrap(),
+					gas_budget: 0,
+					ccm_additional_data: vec![].try_into().unwrap(),
+				},
+			}),
+			None,
+			EgressOrigin::Swap,
+		));
+
+		// The batch uses up the budget, so the CCM is held back.
+		IngressEgress::on_finalize(1);
+		assert_eq!(ScheduledEgressCcm::<Test, ()>::decode_len(), Some(1));
+		assert_eq!(
+			LastBroadcastUsage::<Test, ()>::get(),
+			BroadcastUsage {
+				block_number: System::block_number(),
+				broadcasts: 1,
+				budget_exhausted: true
+			}
+		);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::BroadcastBudgetExhausted {
+			broadcasts_initiated: 1,
+		}));
+		assert_eq!(CarriedOverBroadcastBudget::<Test, ()>::get(), 0);
+
+		IngressEgress::on_finalize(2);
+		assert_eq!(ScheduledEgressCcm::<Test, ()>::decode_len(), Some(0));
+		assert!(!LastBroadcastUsage::<Test, ()>::get().budget_exhausted);
+
+		// Unused budget is carried over, up to the configured maximum.
+		IngressEgress::on_finalize(3);
+		IngressEgress::on_finalize(4);
+		assert_eq!(CarriedOverBroadcastBudget::<Test, ()>::get(), 1);
+	});
+}
+
+#[test]
+fn asset_accounting_totals_track_deposits_egresses_and_fees() {
+	new_test_ext().execute_with(|| {
+		const FEE: AssetAmount = 100;
+		ChainTracker::<Ethereum>::set_fee(FEE);
+
+		request_address_and_deposit(1u64, EthAsset::Eth);
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			2_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+
+		assert_eq!(
+			AssetAccountingTotals::<Test, ()>::get(ETH_ETH),
+			AssetAccounting {
+				deposited: DEFAULT_DEPOSIT_AMOUNT,
+				egressed: 2_000 - FEE,
+				fees_withheld: 2 * FEE,
+				rejected: 0,
+			}
+		);
+		assert_eq!(AssetAccountingTotals::<Test, ()>::get(ETH_FLIP), Default::default());
+	});
+}
+
+#[test]
+fn fees_are_estimated_using_the_configured_strategy() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(IngressEgress::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetFeeEstimationStrategy {
+				strategy: FeeEstimationStrategy::Percentile {
+					blocks: 3,
+					percentile: Percent::from_percent(100),
+				},
+			}]
+			.try_into()
+			.unwrap()
+		));
+
+		// Only the estimates of the last 3 blocks are kept:
+		for (block, fee) in [(1, 300), (2, 100), (3, 100), (4, 200)] {
+			ChainTracker::<Ethereum>::set_fee(fee);
+			IngressEgress::on_initialize(block);
+		}
+		assert_eq!(FeeEstimateSamples::<Test, ()>::get(ETH_ETH).len(), 3);
+
+		ChainTracker::<Ethereum>::set_fee(50);
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			2_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_eq!(AssetAccountingTotals::<Test, ()>::get(ETH_ETH).egressed, 2_000 - 200);
+		assert_eq!(
+			FeeEstimationTelemetryTotals::<Test, ()>::get(ETH_ETH),
+			FeeEstimationTelemetry { estimates: 1, strategy_estimates: 200, latest_estimates: 50 }
+		);
+
+		// Switching back to the latest estimate clears the samples and telemetry:
+		assert_ok!(IngressEgress::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetFeeEstimationStrategy {
+				strategy: FeeEstimationStrategy::Latest
+			}]
+			.try_into()
+			.unwrap()
+		));
+		assert!(!FeeEstimateSamples::<Test, ()>::contains_key(ETH_ETH));
+		assert!(!FeeEstimationTelemetryTotals::<Test, ()>::contains_key(ETH_ETH));
+		IngressEgress::on_initialize(5);
+		assert!(!FeeEstimateSamples::<Test, ()>::contains_key(ETH_ETH));
+	});
+
+	// The moving average gives a weight of 2 / (blocks + 1) to each new estimate:
+	assert_eq!(
+		FeeEstimationStrategy::ExponentialMovingAverage { blocks: 3 }.estimate([100u128, 300], 200),
+		200
+	);
+	assert_eq!(
+		FeeEstimationStrategy::Percentile { blocks: 3, percentile: Percent::from_percent(50) }
+			.estimate([100u128, 300, 400], 200),
+		200
+	);
+}
+
+#[test]
+fn egress_memos_are_only_accepted_where_supported() {
+	new_test_ext().execute_with(|| {
+		let memo: EgressMemo = b"exchange-memo".to_vec().try_into().unwrap();
+		let request_swap_deposit_address =
+			|destination_asset: Asset,
+			 channel_metadata: Option<CcmChannelMetadata>,
+			 egress_memo: Option<EgressMemo>| {
+				IngressEgress::request_swap_deposit_address(
+					EthAsset::Eth,
+					destination_asset,
+					ForeignChainAddress::Dot(Default::default()),
+					Default::default(),
+					BROKER,
+					channel_metadata,
+					0,
+					None,
+					None,
+					None,
+					egress_memo,
+				)
+			};
+
+		// Ethereum transfers cannot carry a memo.
+		assert_noop!(
+			IngressEgress::schedule_egress(
+				ETH_ETH,
+				1_000,
+				ALICE_ETH_ADDRESS,
+				None,
+				Some(memo.clone()),
+				EgressOrigin::Swap
+			),
+			crate::Error::<Test, ()>::InvalidEgressMemo
+		);
+		assert_noop!(
+			request_swap_deposit_address(Asset::Usdc, None, Some(memo.clone())),
+			crate::Error::<Test, ()>::InvalidEgressMemo
+		);
+
+		// Polkadot transfers can, unless the memo is empty or the swap is a CCM.
+		assert_ok!(request_swap_deposit_address(Asset::Dot, None, Some(memo.clone())));
+		assert_noop!(
+			request_swap_deposit_address(Asset::Dot, None, Some(Default::default())),
+			crate::Error::<Test, ()>::InvalidEgressMemo
+		);
+		assert_noop!(
+			request_swap_deposit_address(
+				Asset::Dot,
+				Some(CcmChannelMetadata {
+					message: vec![0x00].try_into().unwrap(),
+					gas_budget: 0,
+					ccm_additional_data: vec![].try_into().unwrap(),
+				}),
+				Some(memo)
+			),
+			crate::Error::<Test, ()>::InvalidEgressMemo
+		);
+	});
+}
+
 #[test]
 fn egress_below_minimum_deposit_ignored() {
 	new_test_ext().execute_with(|| {
@@ -186,7 +517,14 @@ fn egress_below_minimum_deposit_ignored() {
 		EgressDustLimit::<Test, ()>::set(ETH_ETH, MIN_EGRESS);
 
 		assert_err!(
-			IngressEgress::schedule_egress(ETH_ETH, AMOUNT, ALICE_ETH_ADDRESS, None),
+			IngressEgress::schedule_egress(
+				ETH_ETH,
+				AMOUNT,
+				ALICE_ETH_ADDRESS,
+				None,
+				None,
+				EgressOrigin::Swap
+			),
 			crate::Error::<Test, _>::BelowEgressDustLimit
 		);
 
@@ -197,10 +535,38 @@ fn egress_below_minimum_deposit_ignored() {
 #[test]
 fn can_schedule_swap_egress_to_batch() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(IngressEgress::schedule_egress(ETH_ETH, 1_000, ALICE_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_ETH, 2_000, ALICE_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_FLIP, 3_000, BOB_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_FLIP, 4_000, BOB_ETH_ADDRESS, None));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			1_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			2_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			3_000,
+			BOB_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			4_000,
+			BOB_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
 
 		assert_eq!(
 			ScheduledEgressFetchOrTransfer::<Test, ()>::get(),
@@ -209,25 +575,37 @@ fn can_schedule_swap_egress_to_batch() {
 					asset: ETH_ETH,
 					amount: 1_000,
 					destination_address: ALICE_ETH_ADDRESS,
-					egress_id: (ForeignChain::Ethereum, 1),
+					egress_id: (ForeignChain::Ethereum, 0, 1),
+					scheduled_at: 1,
+					memo: None,
+					origin: EgressOrigin::Swap,
 				},
 				FetchOrTransfer::<Ethereum>::Transfer {
 					asset: ETH_ETH,
 					amount: 2_000,
 					destination_address: ALICE_ETH_ADDRESS,
-					egress_id: (ForeignChain::Ethereum, 2),
+					egress_id: (ForeignChain::Ethereum, 0, 2),
+					scheduled_at: 1,
+					memo: None,
+					origin: EgressOrigin::Swap,
 				},
 				FetchOrTransfer::<Ethereum>::Transfer {
 					asset: ETH_FLIP,
 					amount: 3_000,
 					destination_address: BOB_ETH_ADDRESS,
-					egress_id: (ForeignChain::Ethereum, 3),
+					egress_id: (ForeignChain::Ethereum, 0, 3),
+					scheduled_at: 1,
+					memo: None,
+					origin: EgressOrigin::Swap,
 				},
 				FetchOrTransfer::<Ethereum>::Transfer {
 					asset: ETH_FLIP,
 					amount: 4_000,
 					destination_address: BOB_ETH_ADDRESS,
-					egress_id: (ForeignChain::Ethereum, 4),
+					egress_id: (ForeignChain::Ethereum, 0, 4),
+					scheduled_at: 1,
+					memo: None,
+					origin: EgressOrigin::Swap,
 				},
 			]
 		);
@@ -298,19 +676,75 @@ fn can_schedule_deposit_fetch() {
 #[test]
 fn on_finalize_can_send_batch_all() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(IngressEgress::schedule_egress(ETH_ETH, 1_000, ALICE_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_ETH, 2_000, ALICE_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_ETH, 3_000, BOB_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_ETH, 4_000, BOB_ETH_ADDRESS, None));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			1_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			2_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			3_000,
+			BOB_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Refund
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			4_000,
+			BOB_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Withdrawal
+		));
 		request_address_and_deposit(1u64, EthAsset::Eth);
 		request_address_and_deposit(2u64, EthAsset::Eth);
 		request_address_and_deposit(3u64, EthAsset::Eth);
 		request_address_and_deposit(4u64, EthAsset::Eth);
 
-		assert_ok!(IngressEgress::schedule_egress(ETH_FLIP, 5_000, ALICE_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_FLIP, 6_000, ALICE_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_FLIP, 7_000, BOB_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_FLIP, 8_000, BOB_ETH_ADDRESS, None));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			5_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			6_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			7_000,
+			BOB_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			8_000,
+			BOB_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Rejection
+		));
 		request_address_and_deposit(5u64, EthAsset::Flip);
 
 		// Take all scheduled Egress and Broadcast as batch
@@ -319,38 +753,119 @@ fn on_finalize_can_send_batch_all() {
 		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::BatchBroadcastRequested {
 			broadcast_id: 1,
 			egress_ids: vec![
-				(ForeignChain::Ethereum, 1),
-				(ForeignChain::Ethereum, 2),
-				(ForeignChain::Ethereum, 3),
-				(ForeignChain::Ethereum, 4),
-				(ForeignChain::Ethereum, 5),
-				(ForeignChain::Ethereum, 6),
-				(ForeignChain::Ethereum, 7),
-				(ForeignChain::Ethereum, 8),
+				(ForeignChain::Ethereum, 0, 1),
+				(ForeignChain::Ethereum, 0, 2),
+				(ForeignChain::Ethereum, 0, 3),
+				(ForeignChain::Ethereum, 0, 4),
+				(ForeignChain::Ethereum, 0, 5),
+				(ForeignChain::Ethereum, 0, 6),
+				(ForeignChain::Ethereum, 0, 7),
+				(ForeignChain::Ethereum, 0, 8),
+			],
+			egress_origins: vec![
+				((ForeignChain::Ethereum, 0, 1), EgressOrigin::Swap),
+				((ForeignChain::Ethereum, 0, 2), EgressOrigin::Swap),
+				((ForeignChain::Ethereum, 0, 3), EgressOrigin::Refund),
+				((ForeignChain::Ethereum, 0, 4), EgressOrigin::Withdrawal),
+				((ForeignChain::Ethereum, 0, 5), EgressOrigin::Swap),
+				((ForeignChain::Ethereum, 0, 6), EgressOrigin::Swap),
+				((ForeignChain::Ethereum, 0, 7), EgressOrigin::Swap),
+				((ForeignChain::Ethereum, 0, 8), EgressOrigin::Rejection),
 			],
 		}));
 
 		assert!(ScheduledEgressFetchOrTransfer::<Test, ()>::get().is_empty());
+
+		for (origin, asset, count) in [
+			(EgressOrigin::Swap, ETH_ETH, 2),
+			(EgressOrigin::Refund, ETH_ETH, 1),
+			(EgressOrigin::Withdrawal, ETH_ETH, 1),
+			(EgressOrigin::Swap, ETH_FLIP, 3),
+			(EgressOrigin::Rejection, ETH_FLIP, 1),
+		] {
+			assert_eq!(EgressTotals::<Test, ()>::get(origin, asset).count, count);
+		}
+		assert_eq!(
+			EgressTotals::<Test, ()>::get(EgressOrigin::Withdrawal, ETH_ETH).amount -
+				EgressTotals::<Test, ()>::get(EgressOrigin::Refund, ETH_ETH).amount,
+			1_000
+		);
 	});
 }
 
 #[test]
 fn all_batch_apicall_creation_failure_should_rollback_storage() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(IngressEgress::schedule_egress(ETH_ETH, 1_000, ALICE_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_ETH, 2_000, ALICE_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_ETH, 3_000, BOB_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_ETH, 4_000, BOB_ETH_ADDRESS, None));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			1_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			2_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			3_000,
+			BOB_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			4_000,
+			BOB_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
 		request_address_and_deposit(1u64, EthAsset::Eth);
 		request_address_and_deposit(2u64, EthAsset::Eth);
 		request_address_and_deposit(3u64, EthAsset::Eth);
 		request_address_and_deposit(4u64, EthAsset::Eth);
 
-		assert_ok!(IngressEgress::schedule_egress(ETH_FLIP, 5_000, ALICE_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_FLIP, 6_000, ALICE_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_FLIP, 7_000, BOB_ETH_ADDRESS, None));
-		assert_ok!(IngressEgress::schedule_egress(ETH_FLIP, 8_000, BOB_ETH_ADDRESS, None));
-		request_address_and_deposit(5u64, EthAsset::Flip);
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			5_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			6_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			7_000,
+			BOB_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_FLIP,
+			8_000,
+			BOB_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		request_address_and_deposit(5u64, EthAsset::Flip);
 
 		MockEthAllBatch::<MockEvmEnvironment>::set_success(false);
 		request_address_and_deposit(4u64, EthAsset::Usdc);
@@ -479,6 +994,7 @@ fn reused_address_channel_id_matches() {
 				refund_address: Some(ForeignChainAddress::Eth([0u8; 20].into())),
 			},
 			0,
+			ChannelOpenAttempt::New,
 		)
 		.unwrap();
 		// The reused details should be the same as before.
@@ -508,7 +1024,8 @@ fn can_egress_ccm() {
 			destination_asset,
 			amount,
 			destination_address,
-			Some(ccm.clone())
+			Some(ccm.clone()),
+			None, EgressOrigin::Swap,
 		).expect("Egress should succeed");
 
 		assert!(ScheduledEgressFetchOrTransfer::<Test, ()>::get().is_empty());
@@ -523,6 +1040,8 @@ fn can_egress_ccm() {
 				source_chain: ForeignChain::Ethereum,
 				source_address: Some(ForeignChainAddress::Eth([0xcf; 20].into())),
 				gas_budget: GAS_BUDGET,
+				scheduled_at: 1,
+				origin: EgressOrigin::Swap,
 			}
 		]);
 
@@ -534,7 +1053,8 @@ fn can_egress_ccm() {
 			TransferAssetParams {
 				asset: destination_asset,
 				amount,
-				to: destination_address
+				to: destination_address,
+				memo: None,
 			},
 			ccm.source_chain,
 			ccm.source_address,
@@ -548,6 +1068,136 @@ fn can_egress_ccm() {
 	});
 }
 
+#[test]
+fn ccm_is_deferred_until_ready_or_expired() {
+	new_test_ext().execute_with(|| {
+		const TIMEOUT: u32 = 5;
+		CcmDeferralTimeout::<Test, ()>::set(TIMEOUT);
+
+		let schedule_ccm = || {
+			IngressEgress::schedule_egress(
+				EthAsset::Eth,
+				5_000,
+				[0x01; 20].into(),
+				Some(CcmDepositMetadata {
+					source_chain: ForeignChain::Ethereum,
+					source_address: None,
+					channel_metadata: CcmChannelMetadata {
+						message: vec![0x00, 0x01, 0x02].try_into().unwrap(),
+						gas_budget: 1_000,
+						ccm_additional_data: vec![].try_into().unwrap(),
+					},
+				}),
+				None,
+				EgressOrigin::Swap,
+			)
+			.unwrap()
+			.egress_id
+		};
+		let egress_at = |block: u64| {
+			System::set_block_number(block);
+			IngressEgress::on_finalize(block);
+		};
+
+		MockEthExecutexSwapAndCall::<MockEvmEnvironment>::set_ready(false);
+
+		// The CCM is kept in the queue while it can't be built.
+		let egress_id = schedule_ccm();
+		egress_at(1);
+		egress_at(2);
+		assert_eq!(ScheduledEgressCcm::<Test, ()>::decode_len(), Some(1));
+		assert_eq!(
+			CcmDeferrals::<Test, ()>::get(egress_id),
+			Some(CcmDeferral { count: 2, first_deferred_at: 1 })
+		);
+		assert_eq!(
+			System::events()
+				.into_iter()
+				.filter(|record| matches!(
+					record.event,
+					RuntimeEvent::IngressEgress(Event::CcmEgressDeferred { .. })
+				))
+				.count(),
+			1
+		);
+		assert!(MockEgressBroadcaster::get_pending_api_calls().is_empty());
+
+		// Once it can be built, it is sent as usual.
+		MockEthExecutexSwapAndCall::<MockEvmEnvironment>::set_ready(true);
+		egress_at(3);
+		assert_eq!(ScheduledEgressCcm::<Test, ()>::decode_len(), Some(0));
+		assert!(CcmDeferrals::<Test, ()>::get(egress_id).is_none());
+		assert_eq!(MockEgressBroadcaster::get_pending_api_calls().len(), 1);
+
+		// A CCM that doesn't become ready expires after the timeout.
+		MockEthExecutexSwapAndCall::<MockEvmEnvironment>::set_ready(false);
+		let egress_id = schedule_ccm();
+		for block in 10..10 + TIMEOUT as u64 {
+			egress_at(block);
+			assert_eq!(ScheduledEgressCcm::<Test, ()>::decode_len(), Some(1));
+		}
+		egress_at(10 + TIMEOUT as u64);
+		assert_eq!(ScheduledEgressCcm::<Test, ()>::decode_len(), Some(0));
+		assert!(CcmDeferrals::<Test, ()>::get(egress_id).is_none());
+		// Ethereum CCMs have no fallback address.
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::CcmEgressExpired {
+			egress_id,
+			deferral: CcmDeferral { count: TIMEOUT + 1, first_deferred_at: 10 },
+			fallback_egress: None,
+		}));
+
+		MockEthExecutexSwapAndCall::<MockEvmEnvironment>::set_ready(true);
+	});
+}
+
+#[test]
+fn ccm_output_amount_placeholder_is_substituted_at_egress() {
+	new_test_ext().execute_with(|| {
+		let destination_address: H160 = [0x01; 20].into();
+		let ccm = CcmDepositMetadata {
+			source_chain: ForeignChain::Ethereum,
+			source_address: None,
+			channel_metadata: CcmChannelMetadata {
+				message: [&[0x00][..], &CCM_OUTPUT_AMOUNT_PLACEHOLDER].concat().try_into().unwrap(),
+				gas_budget: 1_000,
+				ccm_additional_data: Default::default(),
+			},
+		};
+
+		assert_ok!(IngressEgress::schedule_egress(
+			EthAsset::Eth,
+			5_000,
+			destination_address,
+			Some(ccm.clone()),
+			None,
+			EgressOrigin::Swap,
+		));
+		let egress_amount = ScheduledEgressCcm::<Test, ()>::get()[0].amount;
+
+		IngressEgress::on_finalize(1);
+
+		let mut expected_message = vec![0x00; 33];
+		expected_message[17..].copy_from_slice(&egress_amount.to_be_bytes());
+		assert_eq!(
+			MockEgressBroadcaster::get_pending_api_calls(),
+			vec![<MockEthereumApiCall<MockEvmEnvironment> as ExecutexSwapAndCall<Ethereum>>::new_unsigned(
+				TransferAssetParams {
+					asset: EthAsset::Eth,
+					amount: egress_amount,
+					to: destination_address,
+					memo: None,
+				},
+				ccm.source_chain,
+				ccm.source_address,
+				ccm.channel_metadata.gas_budget,
+				expected_message,
+				vec![],
+			)
+			.unwrap()]
+		);
+	});
+}
+
 #[test]
 fn multi_deposit_includes_deposit_beyond_recycle_height() {
 	const ETH: EthAsset = EthAsset::Eth;
@@ -864,7 +1514,102 @@ fn deposits_below_minimum_are_rejected() {
 			action: DepositAction::LiquidityProvision { lp_account: LP_ACCOUNT },
 			channel_id: Some(channel_id),
 			origin_type: DepositOriginType::DepositChannel,
+			tracking_id: None,
+		}));
+	});
+}
+
+#[test]
+fn broker_minimum_deposits_apply_to_the_broker_channels_only() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(
+			<mocks::account_role_registry::MockAccountRoleRegistry as AccountRoleRegistry<
+				Test,
+			>>::register_as_broker(&BROKER)
+		);
+		let deposit_failed = |deposit_address| {
+			IngressEgress::deposit_failures(Some(&deposit_address), None)
+				.first()
+				.map(|failure| failure.reason.clone())
+		};
+
+		MinimumDeposit::<Test, ()>::insert(EthAsset::Eth, DEFAULT_DEPOSIT_AMOUNT / 2);
+		assert_noop!(
+			IngressEgress::set_broker_minimum_deposit(
+				RuntimeOrigin::signed(BROKER),
+				EthAsset::Eth,
+				Some(DEFAULT_DEPOSIT_AMOUNT / 4)
+			),
+			crate::Error::<Test, ()>::BrokerMinimumDepositTooLow
+		);
+		assert_ok!(IngressEgress::set_broker_minimum_deposit(
+			RuntimeOrigin::signed(BROKER),
+			EthAsset::Eth,
+			Some(DEFAULT_DEPOSIT_AMOUNT * 2)
+		));
+
+		let (_, broker_channel) = request_address_and_deposit(BROKER, EthAsset::Eth);
+		assert_eq!(deposit_failed(broker_channel), Some(DepositFailedReason::BelowMinimumDeposit));
+		let (_, other_channel) = request_address_and_deposit(ALICE, EthAsset::Eth);
+		assert_eq!(deposit_failed(other_channel), None);
+
+		assert_ok!(IngressEgress::set_broker_minimum_deposit(
+			RuntimeOrigin::signed(BROKER),
+			EthAsset::Eth,
+			None
+		));
+		let (_, broker_channel) = request_address_and_deposit(BROKER, EthAsset::Eth);
+		assert_eq!(deposit_failed(broker_channel), None);
+	});
+}
+
+#[test]
+fn implausible_deposit_amounts_emit_a_warning() {
+	new_test_ext().execute_with(|| {
+		// One USDC denominated with 18 decimals instead of 6.
+		const IMPLAUSIBLE_AMOUNT: u128 = 1_000_000_000_000_000_000;
+
+		let (_, address, ..) = IngressEgress::request_liquidity_deposit_address(
+			ALICE,
+			EthAsset::Usdc,
+			0,
+			ForeignChainAddress::Eth(Default::default()),
+		)
+		.unwrap();
+		let address: <Ethereum as Chain>::ChainAccount = address.try_into().unwrap();
+
+		assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+			&DepositWitness {
+				deposit_address: address,
+				asset: EthAsset::Usdc,
+				amount: IMPLAUSIBLE_AMOUNT,
+				deposit_details: Default::default()
+			},
+			Default::default()
+		));
+
+		System::assert_has_event(RuntimeEvent::IngressEgress(Event::ImplausibleDepositAmount {
+			deposit_address: Some(address),
+			asset: EthAsset::Usdc,
+			amount: IMPLAUSIBLE_AMOUNT,
+			block_height: Default::default(),
 		}));
+		// The deposit is still processed.
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::DepositFinalised { amount: IMPLAUSIBLE_AMOUNT, .. })
+		);
+
+		System::reset_events();
+		request_address_and_deposit(ALICE, EthAsset::Usdc);
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::DepositFinalised { .. })
+		);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::IngressEgress(Event::ImplausibleDepositAmount { .. })
+		)));
 	});
 }
 
@@ -944,7 +1689,7 @@ fn handle_pending_deployment() {
 	const ETH: EthAsset = EthAsset::Eth;
 	new_test_ext().execute_with(|| {
 		// Initial request.
-		let (_, deposit_address) = request_address_and_deposit(ALICE, EthAsset::Eth);
+		let (_, deposit_address) = request_address_and_deposit(1u64, EthAsset::Eth);
 		assert_eq!(ScheduledEgressFetchOrTransfer::<Test, _>::decode_len().unwrap_or_default(), 1);
 		// Process deposits.
 		IngressEgress::on_finalize(1);
@@ -980,7 +1725,7 @@ fn handle_pending_deployment() {
 fn handle_pending_deployment_same_block() {
 	new_test_ext().execute_with(|| {
 		// Initial request.
-		let (_, deposit_address) = request_address_and_deposit(ALICE, EthAsset::Eth);
+		let (_, deposit_address) = request_address_and_deposit(1u64, EthAsset::Eth);
 		assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
 			&DepositWitness {
 				deposit_address,
@@ -1081,7 +1826,7 @@ fn ingress_finalisation_succeeds_after_channel_expired_but_not_recycled() {
 			"Is empty after genesis"
 		);
 
-		request_address_and_deposit(ALICE, EthAsset::Eth);
+		request_address_and_deposit(1u64, EthAsset::Eth);
 
 		// Because we're only *expiring* and not recycling, we should still be able to fetch.
 		let expiry_block = IngressEgress::expiry_and_recycle_block_height().1;
@@ -1128,125 +1873,585 @@ fn can_store_failed_vault_transfers() {
 }
 
 #[test]
-fn test_default_empty_amounts() {
-	let mut channel_recycle_blocks = Default::default();
-	let can_recycle = IngressEgress::take_recyclable_addresses(&mut channel_recycle_blocks, 0, 0);
+fn ccm_refunds_below_fees_are_withheld_instead_of_sent() {
+	new_test_ext().execute_with(|| {
+		const EGRESS_FEE: u128 = 1_000;
+		const DUST_LIMIT: u128 = 500;
+		ChainTracker::<cf_chains::Ethereum>::set_fee(EGRESS_FEE);
+		MockAssetConverter::set_price(cf_primitives::Asset::Flip, cf_primitives::Asset::Eth, 1u128);
+		EgressDustLimit::<Test, ()>::set(EthAsset::Flip, DUST_LIMIT);
 
-	assert_eq!(can_recycle, vec![]);
-	assert_eq!(channel_recycle_blocks, vec![]);
-}
+		let destination_address: H160 = [0xcf; 20].into();
+		let refund = |asset, amount| {
+			IngressEgress::do_ccm_fallback(
+				1,
+				TransferAssetParams { asset, amount, to: destination_address, memo: None },
+			)
+		};
 
-#[test]
-fn test_cannot_recycle_if_block_number_less_than_current_height() {
-	let maximum_recyclable_number = 2;
-	let mut channel_recycle_blocks =
-		(1u64..5).map(|i| (i, H160::from([i as u8; 20]))).collect::<Vec<_>>();
-	let current_block_height = 3;
-
-	let can_recycle = IngressEgress::take_recyclable_addresses(
-		&mut channel_recycle_blocks,
-		maximum_recyclable_number,
-		current_block_height,
-	);
+		// The fee takes the whole refund, which is withheld in the gas asset.
+		refund(EthAsset::Eth, EGRESS_FEE / 2);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::CcmRefundBelowFeesIgnored {
+			asset: EthAsset::Eth,
+			amount: EGRESS_FEE / 2,
+			destination_address,
+			egress_fee: EGRESS_FEE / 2,
+			residual_withheld: 0,
+		}));
+		assert_eq!(
+			MockAssetWithholding::withheld_assets(ForeignChain::Ethereum.gas_asset()),
+			EGRESS_FEE / 2
+		);
 
-	assert_eq!(can_recycle, vec![H160::from([1u8; 20]), H160::from([2; 20])]);
-	assert_eq!(
-		channel_recycle_blocks,
-		vec![(3, H160::from([3u8; 20])), (4, H160::from([4u8; 20]))]
-	);
+		// A remainder below the dust limit is swapped into the gas asset along with the fee.
+		refund(EthAsset::Flip, EGRESS_FEE + DUST_LIMIT - 1);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::CcmRefundBelowFeesIgnored {
+			asset: EthAsset::Flip,
+			amount: EGRESS_FEE + DUST_LIMIT - 1,
+			destination_address,
+			egress_fee: EGRESS_FEE,
+			residual_withheld: DUST_LIMIT - 1,
+		}));
+		assert_eq!(
+			MockSwapRequestHandler::<Test>::get_swap_requests()
+				.into_iter()
+				.map(|request| (request.input_asset, request.input_amount, request.swap_type))
+				.collect::<Vec<_>>(),
+			vec![
+				(cf_primitives::Asset::Flip, EGRESS_FEE, SwapRequestType::IngressEgressFee),
+				(cf_primitives::Asset::Flip, DUST_LIMIT - 1, SwapRequestType::IngressEgressFee),
+			]
+		);
+		assert!(ScheduledEgressFetchOrTransfer::<Test, ()>::get().is_empty());
+
+		// Refunds that cover the fee are sent as usual.
+		refund(EthAsset::Eth, EGRESS_FEE * 2);
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::TransferFallbackRequested {
+				egress_details: Some(ScheduledEgressDetails { egress_amount: EGRESS_FEE, .. }),
+				..
+			})
+		);
+		assert!(matches!(
+			ScheduledEgressFetchOrTransfer::<Test, ()>::get()[..],
+			[FetchOrTransfer::Transfer { amount: EGRESS_FEE, .. }]
+		));
+	});
 }
 
-// Same test as above, but lower maximum recyclable number
 #[test]
-fn test_can_only_recycle_up_to_max_amount() {
-	let maximum_recyclable_number = 1;
-	let mut channel_recycle_blocks =
-		(1u64..5).map(|i| (i, H160::from([i as u8; 20]))).collect::<Vec<_>>();
-	let current_block_height = 3;
-
-	let can_recycle = IngressEgress::take_recyclable_addresses(
-		&mut channel_recycle_blocks,
-		maximum_recyclable_number,
-		current_block_height,
-	);
+fn deposit_address_verification_flags_mismatched_channels() {
+	new_test_ext().execute_with(|| {
+		let (_, address) = request_address_and_deposit(1u64, EthAsset::Eth);
+		let (tampered_channel_id, tampered_address) =
+			request_address_and_deposit(2u64, EthAsset::Flip);
 
-	assert_eq!(can_recycle, vec![H160::from([1u8; 20])]);
-	assert_eq!(
-		channel_recycle_blocks,
-		vec![(2, H160::from([2; 20])), (3, H160::from([3u8; 20])), (4, H160::from([4u8; 20]))]
-	);
-}
+		// Move one of the channels under an address that can't be derived for it.
+		let wrong_address: H160 = [0xcf; 20].into();
+		let details = DepositChannelLookup::<Test, ()>::take(tampered_address).unwrap();
+		DepositChannelLookup::<Test, ()>::insert(wrong_address, details);
 
-#[test]
-fn none_can_be_recycled_due_to_low_block_number() {
-	let maximum_recyclable_number = 4;
-	let mut channel_recycle_blocks =
-		(1u64..5).map(|i| (i, H160::from([i as u8; 20]))).collect::<Vec<_>>();
-	let current_block_height = 0;
-
-	let can_recycle = IngressEgress::take_recyclable_addresses(
-		&mut channel_recycle_blocks,
-		maximum_recyclable_number,
-		current_block_height,
-	);
+		assert_ok!(IngressEgress::start_deposit_address_verification(RuntimeOrigin::root()));
+		assert_noop!(
+			IngressEgress::start_deposit_address_verification(RuntimeOrigin::root()),
+			crate::Error::<Test, ()>::DepositAddressVerificationAlreadyInProgress
+		);
 
-	assert!(can_recycle.is_empty());
-	assert_eq!(
-		channel_recycle_blocks,
-		vec![
-			(1, H160::from([1u8; 20])),
-			(2, H160::from([2; 20])),
-			(3, H160::from([3; 20])),
-			(4, H160::from([4; 20]))
-		]
-	);
-}
+		IngressEgress::on_idle(1, Weight::MAX);
 
-#[test]
-fn all_can_be_recycled() {
-	let maximum_recyclable_number = 4;
-	let mut channel_recycle_blocks =
-		(1u64..5).map(|i| (i, H160::from([i as u8; 20]))).collect::<Vec<_>>();
-	let current_block_height = 4;
-
-	let can_recycle = IngressEgress::take_recyclable_addresses(
-		&mut channel_recycle_blocks,
-		maximum_recyclable_number,
-		current_block_height,
-	);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			Event::DepositAddressMismatchDetected {
+				deposit_address: wrong_address,
+				channel_id: tampered_channel_id,
+				derived_address: Some(tampered_address),
+			},
+		));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			Event::DepositAddressVerificationCompleted { channels_checked: 2, mismatches: 1 },
+		));
+		assert_eq!(
+			DepositAddressMismatches::<Test, ()>::iter().collect::<Vec<_>>(),
+			vec![(
+				wrong_address,
+				DepositAddressMismatch {
+					channel_id: tampered_channel_id,
+					derived_address: Some(tampered_address)
+				}
+			)]
+		);
+		assert!(DepositAddressMismatches::<Test, ()>::get(address).is_none());
+		assert!(!DepositAddressVerificationInProgress::<Test, ()>::exists());
 
-	assert_eq!(
-		can_recycle,
-		vec![H160::from([1u8; 20]), H160::from([2; 20]), H160::from([3; 20]), H160::from([4; 20])]
-	);
-	assert!(channel_recycle_blocks.is_empty());
+		// A new verification replaces the previous results.
+		DepositChannelLookup::<Test, ()>::remove(wrong_address);
+		assert_ok!(IngressEgress::start_deposit_address_verification(RuntimeOrigin::root()));
+		assert_eq!(DepositAddressMismatches::<Test, ()>::iter().count(), 0);
+	});
 }
 
 #[test]
-fn failed_ccm_is_stored() {
+fn chain_wind_down_blocks_new_channels_and_abandons_remaining_egresses() {
 	new_test_ext().execute_with(|| {
-		let epoch = MockEpochInfo::epoch_index();
-		let broadcast_id = 1;
-		assert_eq!(FailedForeignChainCalls::<Test, ()>::get(epoch), vec![]);
+		assert_ok!(IngressEgress::schedule_egress(
+			ETH_ETH,
+			1_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap
+		));
+		let stuck = IngressEgress::schedule_egress(
+			ETH_FLIP,
+			2_000,
+			BOB_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap,
+		)
+		.unwrap();
+		assert_ok!(IngressEgress::enable_or_disable_egress(RuntimeOrigin::root(), ETH_FLIP, true));
 
+		let started_at = System::block_number();
+		assert_ok!(IngressEgress::start_chain_wind_down(RuntimeOrigin::root(), 10));
 		assert_noop!(
-			IngressEgress::ccm_broadcast_failed(RuntimeOrigin::signed(ALICE), broadcast_id,),
-			sp_runtime::DispatchError::BadOrigin
+			IngressEgress::start_chain_wind_down(RuntimeOrigin::root(), 10),
+			crate::Error::<Test, ()>::ChainWindingDown
 		);
-		assert_ok!(IngressEgress::ccm_broadcast_failed(RuntimeOrigin::root(), broadcast_id,));
+		assert_noop!(
+			IngressEgress::request_liquidity_deposit_address(
+				ALICE,
+				EthAsset::Eth,
+				0,
+				ForeignChainAddress::Eth(Default::default()),
+			),
+			crate::Error::<Test, ()>::ChainWindingDown
+		);
+		assert!(!IngressEgress::accepts_new_destination(&ALICE_ETH_ADDRESS));
+
+		// Scheduled egresses are still sent during the grace period.
+		IngressEgress::on_finalize(started_at);
+		assert_eq!(ScheduledEgressFetchOrTransfer::<Test, ()>::decode_len(), Some(1));
+
+		IngressEgress::on_initialize(started_at + 9);
+		assert!(!ChainWindDown::<Test, ()>::get().unwrap().completed);
 
+		// Completion waits for any fetches that are still scheduled.
+		ScheduledEgressFetchOrTransfer::<Test, ()>::append(FetchOrTransfer::<Ethereum>::Fetch {
+			asset: ETH_FLIP,
+			deposit_address: ALICE_ETH_ADDRESS,
+			deposit_fetch_id: None,
+			amount: 1_000,
+		});
+		IngressEgress::on_initialize(started_at + 10);
 		assert_eq!(
-			FailedForeignChainCalls::<Test, ()>::get(epoch),
-			vec![FailedForeignChainCall { broadcast_id, original_epoch: epoch }]
+			ChainWindDown::<Test, ()>::get(),
+			Some(ChainWindDownStatus {
+				started_at,
+				grace_period_ends_at: started_at + 10,
+				abandoned_egresses: 1,
+				completed: false
+			})
 		);
-		System::assert_last_event(RuntimeEvent::IngressEgress(Event::CcmBroadcastFailed {
-			broadcast_id,
+		assert!(matches!(
+			&ScheduledEgressFetchOrTransfer::<Test, ()>::get()[..],
+			[FetchOrTransfer::<Ethereum>::Fetch { asset: ETH_FLIP, .. }]
+		));
+		assert_eq!(PendingEgressAmounts::<Test, ()>::iter().count(), 0);
+		assert_eq!(
+			AbandonedEgresses::<Test, ()>::get(stuck.egress_id),
+			Some(AbandonedEgress {
+				asset: ETH_FLIP,
+				amount: stuck.egress_amount,
+				destination_address: BOB_ETH_ADDRESS,
+			})
+		);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::EgressAbandonedOnWindDown {
+			egress_id: stuck.egress_id,
+			asset: ETH_FLIP,
+			amount: stuck.egress_amount,
+			destination_address: BOB_ETH_ADDRESS,
 		}));
+
+		ScheduledEgressFetchOrTransfer::<Test, ()>::kill();
+		IngressEgress::on_initialize(started_at + 11);
+		assert_eq!(
+			ChainWindDown::<Test, ()>::get(),
+			Some(ChainWindDownStatus {
+				started_at,
+				grace_period_ends_at: started_at + 10,
+				abandoned_egresses: 1,
+				completed: true
+			})
+		);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::ChainWindDownCompleted {
+			abandoned_egresses: 1,
+		}));
+
+		// Egresses requested after the grace period are abandoned straight away.
+		let late = IngressEgress::schedule_egress(
+			ETH_ETH,
+			3_000,
+			ALICE_ETH_ADDRESS,
+			None,
+			None,
+			EgressOrigin::Swap,
+		)
+		.unwrap();
+		assert_eq!(late.fee_withheld, 0);
+		assert!(ScheduledEgressFetchOrTransfer::<Test, ()>::get().is_empty());
+		assert_eq!(PendingEgressAmounts::<Test, ()>::iter().count(), 0);
+		assert_eq!(ChainWindDown::<Test, ()>::get().unwrap().abandoned_egresses, 2);
+		assert!(AbandonedEgresses::<Test, ()>::contains_key(late.egress_id));
+
+		// Abandoned egresses can be credited to an account by governance.
+		assert_noop!(
+			IngressEgress::credit_abandoned_egress(
+				RuntimeOrigin::signed(ALICE),
+				stuck.egress_id,
+				ALICE
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(IngressEgress::credit_abandoned_egress(
+			RuntimeOrigin::root(),
+			stuck.egress_id,
+			ALICE
+		));
+		assert_eq!(MockBalance::get_balance(&ALICE, ETH_FLIP.into()), stuck.egress_amount);
+		assert!(!AbandonedEgresses::<Test, ()>::contains_key(stuck.egress_id));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::AbandonedEgressCredited {
+			egress_id: stuck.egress_id,
+			account_id: ALICE,
+			asset: ETH_FLIP,
+			amount: stuck.egress_amount,
+		}));
+		assert_noop!(
+			IngressEgress::credit_abandoned_egress(RuntimeOrigin::root(), stuck.egress_id, ALICE),
+			crate::Error::<Test, ()>::UnknownAbandonedEgress
+		);
 	});
 }
 
+fn queue_addresses_for_recycling(recycle_heights: impl IntoIterator<Item = u64>) {
+	for height in recycle_heights {
+		DepositChannelRecycleQueue::<Test, ()>::append(height, H160::from([height as u8; 20]));
+	}
+	NextRecycleBlockHeight::<Test, ()>::put(1);
+}
+
+fn take_recyclable_addresses(
+	maximum_recyclable_number: usize,
+	current_block_height: u64,
+) -> Vec<H160> {
+	// Enough weight to read the queue, and to visit a block height and recycle an address
+	// `maximum_recyclable_number` times.
+	let mut meter = WeightMeter::with_limit(
+		ParityDbWeight::get().reads_writes(2, 1).saturating_add(
+			ParityDbWeight::get()
+				.reads_writes(3, 5)
+				.saturating_mul(maximum_recyclable_number as u64),
+		),
+	);
+	IngressEgress::take_recyclable_addresses(&mut meter, current_block_height)
+}
+
+fn remaining_recycle_queue() -> Vec<(u64, Vec<H160>)> {
+	let mut queue = DepositChannelRecycleQueue::<Test, ()>::iter().collect::<Vec<_>>();
+	queue.sort();
+	queue
+}
+
 #[test]
-fn on_finalize_handles_failed_calls() {
+fn test_default_empty_amounts() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(take_recyclable_addresses(0, 0), vec![]);
+		assert_eq!(remaining_recycle_queue(), vec![]);
+		assert_eq!(NextRecycleBlockHeight::<Test, ()>::get(), None);
+	});
+}
+
+#[test]
+fn test_cannot_recycle_if_block_number_less_than_current_height() {
+	new_test_ext().execute_with(|| {
+		queue_addresses_for_recycling(1..5);
+
+		assert_eq!(
+			take_recyclable_addresses(2, 3),
+			vec![H160::from([1u8; 20]), H160::from([2; 20])]
+		);
+		assert_eq!(
+			remaining_recycle_queue(),
+			vec![(3, vec![H160::from([3u8; 20])]), (4, vec![H160::from([4u8; 20])])]
+		);
+		assert_eq!(NextRecycleBlockHeight::<Test, ()>::get(), Some(3));
+	});
+}
+
+// Same test as above, but lower maximum recyclable number
+#[test]
+fn test_can_only_recycle_up_to_max_amount() {
+	new_test_ext().execute_with(|| {
+		queue_addresses_for_recycling(1..5);
+
+		assert_eq!(take_recyclable_addresses(1, 3), vec![H160::from([1u8; 20])]);
+		assert_eq!(
+			remaining_recycle_queue(),
+			vec![
+				(2, vec![H160::from([2; 20])]),
+				(3, vec![H160::from([3u8; 20])]),
+				(4, vec![H160::from([4u8; 20])])
+			]
+		);
+		assert_eq!(NextRecycleBlockHeight::<Test, ()>::get(), Some(2));
+	});
+}
+
+#[test]
+fn addresses_at_the_same_height_can_be_recycled_over_multiple_blocks() {
+	new_test_ext().execute_with(|| {
+		let addresses = (1u8..4).map(|i| H160::from([i; 20])).collect::<Vec<_>>();
+		for address in &addresses {
+			DepositChannelRecycleQueue::<Test, ()>::append(1, address);
+		}
+		NextRecycleBlockHeight::<Test, ()>::put(1);
+
+		assert_eq!(take_recyclable_addresses(2, 1), addresses[..2].to_vec());
+		assert_eq!(remaining_recycle_queue(), vec![(1, addresses[2..].to_vec())]);
+		assert_eq!(NextRecycleBlockHeight::<Test, ()>::get(), Some(1));
+
+		assert_eq!(take_recyclable_addresses(2, 1), addresses[2..].to_vec());
+		assert_eq!(remaining_recycle_queue(), vec![]);
+		assert_eq!(NextRecycleBlockHeight::<Test, ()>::get(), Some(2));
+
+		// The next block height is cleared once the queue is empty.
+		assert_eq!(take_recyclable_addresses(2, 1), vec![]);
+		assert_eq!(NextRecycleBlockHeight::<Test, ()>::get(), None);
+	});
+}
+
+#[test]
+fn none_can_be_recycled_due_to_low_block_number() {
+	new_test_ext().execute_with(|| {
+		queue_addresses_for_recycling(1..5);
+
+		assert!(take_recyclable_addresses(4, 0).is_empty());
+		assert_eq!(
+			remaining_recycle_queue(),
+			vec![
+				(1, vec![H160::from([1u8; 20])]),
+				(2, vec![H160::from([2; 20])]),
+				(3, vec![H160::from([3; 20])]),
+				(4, vec![H160::from([4; 20])])
+			]
+		);
+	});
+}
+
+#[test]
+fn all_can_be_recycled() {
+	new_test_ext().execute_with(|| {
+		queue_addresses_for_recycling(1..5);
+
+		assert_eq!(
+			take_recyclable_addresses(4, 4),
+			vec![
+				H160::from([1u8; 20]),
+				H160::from([2; 20]),
+				H160::from([3; 20]),
+				H160::from([4; 20])
+			]
+		);
+		assert!(remaining_recycle_queue().is_empty());
+	});
+}
+
+#[test]
+fn recycling_stays_within_the_remaining_weight() {
+	new_test_ext().execute_with(|| {
+		queue_addresses_for_recycling(1..5);
+		BlockHeightProvider::<MockEthereum>::set_block_height(4);
+
+		// Only enough weight to visit two block heights and recycle their addresses.
+		let remaining_weight = ParityDbWeight::get()
+			.reads_writes(2, 1)
+			.saturating_add(ParityDbWeight::get().reads_writes(3, 5).saturating_mul(2));
+		assert!(IngressEgress::on_idle(1, remaining_weight).all_lte(remaining_weight));
+		assert_eq!(
+			remaining_recycle_queue(),
+			vec![(3, vec![H160::from([3u8; 20])]), (4, vec![H160::from([4u8; 20])])]
+		);
+	});
+}
+
+#[test]
+fn deposit_channel_indices_are_maintained_on_open_and_recycle() {
+	new_test_ext().execute_with(|| {
+		let open_channel = |owner| {
+			let (_, address, ..) = IngressEgress::open_channel(
+				&owner,
+				EthAsset::Eth,
+				ChannelAction::LiquidityProvision { lp_account: owner, refund_address: None },
+				0,
+				ChannelOpenAttempt::New,
+			)
+			.unwrap();
+			address
+		};
+		let alice_channels = [open_channel(ALICE), open_channel(ALICE)];
+		let broker_channel = open_channel(BROKER);
+
+		let owned_by = |owner| {
+			let mut addresses =
+				DepositChannelsByOwner::<Test, ()>::iter_key_prefix(owner).collect::<Vec<_>>();
+			addresses.sort();
+			addresses
+		};
+		let mut expected_alice_channels = alice_channels.to_vec();
+		expected_alice_channels.sort();
+		assert_eq!(owned_by(ALICE), expected_alice_channels);
+		assert_eq!(owned_by(BROKER), vec![broker_channel]);
+
+		// Every channel is indexed by owner and queued for recycling.
+		for (address, details) in DepositChannelLookup::<Test, ()>::iter() {
+			assert!(DepositChannelsByOwner::<Test, ()>::contains_key(details.owner, address));
+		}
+		assert_eq!(
+			DepositChannelRecycleQueue::<Test, ()>::iter_values().flatten().count(),
+			DepositChannelLookup::<Test, ()>::iter_keys().count()
+		);
+
+		// Once expired, the channels are recycled and removed from both indices.
+		let recycle_block = IngressEgress::expiry_and_recycle_block_height().2;
+		BlockHeightProvider::<MockEthereum>::set_block_height(recycle_block);
+		IngressEgress::on_idle(1, Weight::MAX);
+
+		assert_eq!(DepositChannelLookup::<Test, ()>::iter_keys().count(), 0);
+		assert_eq!(DepositChannelsByOwner::<Test, ()>::iter_keys().count(), 0);
+		assert_eq!(DepositChannelRecycleQueue::<Test, ()>::count(), 0);
+	});
+}
+
+#[test]
+fn channels_count_as_unrecycled_until_they_are_recycled() {
+	new_test_ext().execute_with(|| {
+		assert!(!IngressEgress::has_unrecycled_deposit_channels(&BROKER));
+		assert_ok!(IngressEgress::request_swap_deposit_address(
+			EthAsset::Eth,
+			Asset::Usdc,
+			ForeignChainAddress::Eth(Default::default()),
+			Default::default(),
+			BROKER,
+			None,
+			0,
+			None,
+			None,
+			None,
+			None,
+		));
+		assert!(IngressEgress::has_unrecycled_deposit_channels(&BROKER));
+		assert!(!IngressEgress::has_unrecycled_deposit_channels(&ALICE));
+
+		// Expired channels can still receive deposits until they are recycled.
+		let (_, expiry_block, recycle_block) = IngressEgress::expiry_and_recycle_block_height();
+		BlockHeightProvider::<MockEthereum>::set_block_height(expiry_block);
+		IngressEgress::on_idle(1, Weight::MAX);
+		assert!(IngressEgress::has_unrecycled_deposit_channels(&BROKER));
+
+		BlockHeightProvider::<MockEthereum>::set_block_height(recycle_block);
+		IngressEgress::on_idle(2, Weight::MAX);
+		assert!(!IngressEgress::has_unrecycled_deposit_channels(&BROKER));
+	});
+}
+
+#[test]
+fn open_channels_are_limited_per_account_role() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(
+			<mocks::account_role_registry::MockAccountRoleRegistry as AccountRoleRegistry<
+				Test,
+			>>::register_as_broker(&BROKER)
+		);
+		assert_ok!(IngressEgress::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetMaxOpenChannels {
+				role: AccountRole::Broker,
+				max_channels: Some(2)
+			}]
+			.try_into()
+			.unwrap()
+		));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::MaxOpenChannelsSet {
+			role: AccountRole::Broker,
+			max_channels: Some(2),
+		}));
+
+		let open_channel = |owner| {
+			IngressEgress::open_channel(
+				&owner,
+				EthAsset::Eth,
+				ChannelAction::LiquidityProvision { lp_account: owner, refund_address: None },
+				0,
+				ChannelOpenAttempt::New,
+			)
+		};
+
+		assert_ok!(open_channel(BROKER));
+		assert_ok!(open_channel(BROKER));
+		assert_noop!(open_channel(BROKER), crate::Error::<Test, ()>::TooManyOpenChannels);
+		assert_eq!(IngressEgress::open_channel_usage(&BROKER), (2, Some(2)));
+
+		// Accounts with other roles are not limited:
+		for _ in 0..3 {
+			assert_ok!(open_channel(ALICE));
+		}
+		assert_eq!(IngressEgress::open_channel_usage(&ALICE), (3, None));
+
+		// Recycled channels no longer count towards the limit.
+		let recycle_block = IngressEgress::expiry_and_recycle_block_height().2;
+		BlockHeightProvider::<MockEthereum>::set_block_height(recycle_block);
+		IngressEgress::on_idle(1, Weight::MAX);
+
+		assert!(!OpenChannelCount::<Test, ()>::contains_key(BROKER));
+		assert!(!OpenChannelCount::<Test, ()>::contains_key(ALICE));
+		assert_ok!(open_channel(BROKER));
+	});
+}
+
+#[test]
+fn failed_ccm_is_stored() {
+	new_test_ext().execute_with(|| {
+		let epoch = MockEpochInfo::epoch_index();
+		let broadcast_id = 1;
+		assert_eq!(FailedForeignChainCalls::<Test, ()>::get(epoch), vec![]);
+
+		assert_noop!(
+			IngressEgress::ccm_broadcast_failed(RuntimeOrigin::signed(ALICE), broadcast_id,),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(IngressEgress::ccm_broadcast_failed(RuntimeOrigin::root(), broadcast_id,));
+
+		assert_eq!(
+			FailedForeignChainCalls::<Test, ()>::get(epoch),
+			vec![FailedForeignChainCall { broadcast_id, original_epoch: epoch }]
+		);
+		System::assert_last_event(RuntimeEvent::IngressEgress(Event::CcmBroadcastFailed {
+			broadcast_id,
+		}));
+	});
+}
+
+#[test]
+fn failed_calls_expire_two_epochs_after_failing() {
+	let call = FailedForeignChainCall { broadcast_id: 1, original_epoch: 5 };
+	assert_eq!(call.epochs_until_expiry(5), 2);
+	assert_eq!(call.epochs_until_expiry(6), 1);
+	assert_eq!(call.epochs_until_expiry(7), 0);
+	assert_eq!(call.epochs_until_expiry(8), 0);
+}
+
+#[test]
+fn on_finalize_handles_failed_calls() {
 	new_test_ext().execute_with(|| {
 		// Advance to Epoch 1 so the expiry logic start to work.
 		let epoch = 1u32;
@@ -1388,6 +2593,220 @@ fn consolidation_tx_gets_broadcasted_on_finalize() {
 	});
 }
 
+#[test]
+fn consolidation_follows_the_configured_interval() {
+	const INTERVAL: u32 = 10;
+
+	let consolidations = || {
+		System::events()
+			.iter()
+			.filter(|record| {
+				matches!(record.event, RuntimeEvent::IngressEgress(Event::UtxoConsolidation { .. }))
+			})
+			.count()
+	};
+
+	new_test_ext().execute_with(|| {
+		cf_traits::mocks::api_call::SHOULD_CONSOLIDATE.with(|cell| cell.set(true));
+
+		assert_ok!(IngressEgress::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetConsolidationInterval { blocks: INTERVAL }]
+				.try_into()
+				.unwrap()
+		));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::ConsolidationIntervalSet {
+			blocks: INTERVAL,
+		}));
+
+		for block in 1..INTERVAL {
+			IngressEgress::on_finalize(block.into());
+		}
+		assert_eq!(consolidations(), 0);
+
+		IngressEgress::on_finalize(INTERVAL.into());
+		assert_eq!(consolidations(), 1);
+
+		// Nothing is consolidated while consolidation is disabled by safe mode:
+		use cf_traits::SetSafeMode;
+		MockRuntimeSafeMode::set_safe_mode(MockRuntimeSafeMode {
+			ingress_egress_ethereum: PalletSafeMode {
+				consolidation_enabled: false,
+				..PalletSafeMode::CODE_GREEN
+			},
+		});
+		IngressEgress::on_finalize((INTERVAL * 2).into());
+		assert_eq!(consolidations(), 1);
+
+		MockRuntimeSafeMode::set_safe_mode(MockRuntimeSafeMode {
+			ingress_egress_ethereum: PalletSafeMode::CODE_GREEN,
+		});
+		IngressEgress::on_finalize((INTERVAL * 3).into());
+		assert_eq!(consolidations(), 2);
+	});
+}
+
+#[test]
+fn channel_opens_are_retried_after_transient_address_derivation_errors() {
+	const LP_ACCOUNT: u64 = 1;
+	const RETRY_PERIOD: u32 = 5;
+	const FEE: u128 = 100;
+
+	let request_channel = || {
+		IngressEgress::request_liquidity_deposit_address(
+			LP_ACCOUNT,
+			ETH_ETH,
+			0,
+			ForeignChainAddress::Eth(Default::default()),
+		)
+	};
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MockAddressDerivation::set_error(Some(AddressDerivationError::MissingSolanaApiEnvironment));
+
+		// Without a retry period, the channel open fails outright:
+		assert_err!(request_channel(), crate::Error::<Test, ()>::MissingSolanaApiEnvironment);
+		assert_eq!(PendingChannelOpens::<Test, ()>::iter().count(), 0);
+
+		assert_ok!(IngressEgress::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetChannelOpenRetryPeriod { blocks: RETRY_PERIOD }]
+				.try_into()
+				.unwrap()
+		));
+
+		// Errors that won't resolve themselves are not retried:
+		MockAddressDerivation::set_error(Some(AddressDerivationError::BitcoinChannelIdTooLarge));
+		assert_err!(request_channel(), crate::Error::<Test, ()>::BitcoinChannelIdTooLarge);
+		assert_eq!(PendingChannelOpens::<Test, ()>::iter().count(), 0);
+
+		MockAddressDerivation::set_error(Some(AddressDerivationError::MissingSolanaApiEnvironment));
+		let error = request_channel().unwrap_err();
+		assert!(IngressEgress::is_deferred_channel_open(&error));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::DepositChannelOpenDeferred {
+			request_id: 1,
+			requester: LP_ACCOUNT,
+			source_asset: ETH_ETH,
+			error: crate::Error::<Test, ()>::MissingSolanaApiEnvironment.into(),
+			retry_until: 1 + RETRY_PERIOD as u64,
+		}));
+
+		// The request stays queued while the address still can't be derived.
+		IngressEgress::on_idle(2, Weight::MAX);
+		assert!(PendingChannelOpens::<Test, ()>::contains_key(1));
+
+		MockAddressDerivation::set_error(None);
+		IngressEgress::on_idle(3, Weight::MAX);
+		assert!(!PendingChannelOpens::<Test, ()>::contains_key(1));
+		let deposit_address = System::events()
+			.into_iter()
+			.find_map(|record| match record.event {
+				RuntimeEvent::IngressEgress(Event::DeferredDepositChannelOpened {
+					request_id: 1,
+					requester: LP_ACCOUNT,
+					deposit_address,
+					..
+				}) => Some(deposit_address),
+				_ => None,
+			})
+			.expect("The deferred channel should have been opened");
+		assert!(DepositChannelLookup::<Test, ()>::contains_key(deposit_address));
+
+		// Requests that can't be served within the retry period fail permanently, and the
+		// channel opening fee is refunded.
+		ChannelOpeningFee::<Test, ()>::set(FEE);
+		MockFundingInfo::<Test>::credit_funds(&LP_ACCOUNT, FEE);
+		MockAddressDerivation::set_error(Some(AddressDerivationError::MissingSolanaApiEnvironment));
+		assert!(IngressEgress::is_deferred_channel_open(&request_channel().unwrap_err()));
+		assert_eq!(MockFundingInfo::<Test>::total_balance_of(&LP_ACCOUNT), 0);
+		IngressEgress::on_idle(1 + RETRY_PERIOD as u64, Weight::MAX);
+		assert!(!PendingChannelOpens::<Test, ()>::contains_key(2));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			Event::DeferredDepositChannelOpenFailed {
+				request_id: 2,
+				requester: LP_ACCOUNT,
+				error: crate::Error::<Test, ()>::ChannelOpenRetryPeriodExpired.into(),
+				refunded_fee: FEE,
+			},
+		));
+		assert_eq!(MockFundingInfo::<Test>::total_balance_of(&LP_ACCOUNT), FEE);
+
+		MockAddressDerivation::set_error(None);
+	});
+}
+
+#[test]
+fn all_pending_channel_opens_are_retried_over_several_blocks() {
+	const LP_ACCOUNT: u64 = 1;
+	const REQUESTS: usize = MAX_CHANNEL_OPEN_RETRIES_PER_BLOCK * 2 + 1;
+
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		ChannelOpenRetryPeriod::<Test, ()>::set(100);
+		MockAddressDerivation::set_error(Some(AddressDerivationError::MissingSolanaApiEnvironment));
+		for _ in 0..REQUESTS {
+			assert!(IngressEgress::is_deferred_channel_open(
+				&IngressEgress::request_liquidity_deposit_address(
+					LP_ACCOUNT,
+					ETH_ETH,
+					0,
+					ForeignChainAddress::Eth(Default::default()),
+				)
+				.unwrap_err()
+			));
+		}
+
+		// Requests that keep failing don't prevent the others from being retried.
+		for block in 2..5 {
+			IngressEgress::on_idle(block, Weight::MAX);
+		}
+		assert!(PendingChannelOpenCursor::<Test, ()>::get().is_none());
+
+		// Once the address can be derived, every request is served within three blocks.
+		MockAddressDerivation::set_error(None);
+		IngressEgress::on_idle(5, Weight::MAX);
+		assert_eq!(
+			PendingChannelOpens::<Test, ()>::iter().count(),
+			REQUESTS - MAX_CHANNEL_OPEN_RETRIES_PER_BLOCK
+		);
+		IngressEgress::on_idle(6, Weight::MAX);
+		IngressEgress::on_idle(7, Weight::MAX);
+		assert_eq!(PendingChannelOpens::<Test, ()>::iter().count(), 0);
+	});
+}
+
+#[test]
+fn skipped_consolidations_are_tracked() {
+	new_test_ext().execute_with(|| {
+		cf_traits::mocks::api_call::CONSOLIDATION_FAILS.with(|cell| cell.set(true));
+
+		for block in 1..CONSOLIDATION_SKIP_WARNING_THRESHOLD {
+			IngressEgress::on_finalize(block.into());
+		}
+		assert_eq!(
+			SkippedConsolidations::<Test, ()>::get(),
+			Some((CONSOLIDATION_SKIP_WARNING_THRESHOLD - 1, ConsolidationError::Other))
+		);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::IngressEgress(Event::UtxoConsolidationSkipped { .. })
+		)));
+
+		// A warning is emitted once the threshold is reached:
+		IngressEgress::on_finalize(CONSOLIDATION_SKIP_WARNING_THRESHOLD.into());
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::UtxoConsolidationSkipped {
+			consecutive_skips: CONSOLIDATION_SKIP_WARNING_THRESHOLD,
+			reason: ConsolidationError::Other,
+		}));
+
+		// The count is reset once consolidation is no longer failing:
+		cf_traits::mocks::api_call::CONSOLIDATION_FAILS.with(|cell| cell.set(false));
+		IngressEgress::on_finalize((CONSOLIDATION_SKIP_WARNING_THRESHOLD + 1).into());
+		assert_eq!(SkippedConsolidations::<Test, ()>::get(), None);
+	});
+}
+
 #[test]
 fn all_batch_errors_are_logged_as_event() {
 	new_test_ext()
@@ -1397,7 +2816,10 @@ fn all_batch_errors_are_logged_as_event() {
 					asset: ETH_ETH,
 					amount: 1_000,
 					destination_address: ALICE_ETH_ADDRESS,
-					egress_id: (ForeignChain::Ethereum, 1),
+					egress_id: (ForeignChain::Ethereum, 0, 1),
+					scheduled_at: 1,
+					memo: None,
+					origin: EgressOrigin::Swap,
 				},
 			]);
 			MockEthAllBatch::set_success(false);
@@ -1430,7 +2852,8 @@ fn broker_pays_a_fee_for_each_deposit_address() {
 				lp_account: CHANNEL_REQUESTER,
 				refund_address: Some(ForeignChainAddress::Eth(Default::default())),
 			},
-			0
+			0,
+			ChannelOpenAttempt::New
 		));
 		assert_eq!(MockFundingInfo::<Test>::total_balance_of(&CHANNEL_REQUESTER), 0);
 		assert_ok!(IngressEgress::update_pallet_config(
@@ -1447,7 +2870,8 @@ fn broker_pays_a_fee_for_each_deposit_address() {
 					lp_account: CHANNEL_REQUESTER,
 					refund_address: Some(ForeignChainAddress::Eth(Default::default())),
 				},
-				0
+				0,
+				ChannelOpenAttempt::New
 			),
 			mocks::fee_payment::ERROR_INSUFFICIENT_LIQUIDITY
 		);
@@ -1455,19 +2879,174 @@ fn broker_pays_a_fee_for_each_deposit_address() {
 }
 
 #[test]
-fn can_update_all_config_items() {
+fn minimum_deposit_is_scaled_by_ingress_fee() {
+	const ASSET: EthAsset = EthAsset::Eth;
+	const CONFIGURED_MINIMUM: u128 = 100;
+	const MULTIPLIER: u32 = 3;
+
 	new_test_ext().execute_with(|| {
-		const NEW_OPENING_FEE: u128 = 300;
-		const NEW_MIN_DEPOSIT_FLIP: u128 = 100;
-		const NEW_MIN_DEPOSIT_ETH: u128 = 200;
-		const NEW_DEPOSIT_CHANNEL_LIFETIME: u64 = 99;
-		const NETWORK_FEE_DEDUCTION: Percent = Percent::from_parts(50);
+		MinimumDeposit::<Test, ()>::insert(ASSET, CONFIGURED_MINIMUM);
+		ChainTracker::<Ethereum>::set_fee(DEFAULT_DEPOSIT_AMOUNT / 2);
 
-		// Check that the default values are different from the new ones
-		assert_eq!(ChannelOpeningFee::<Test, _>::get(), 0);
-		assert_eq!(MinimumDeposit::<Test, _>::get(EthAsset::Flip), 0);
+		// Without a multiplier, only the configured minimum applies.
+		assert_eq!(IngressEgress::effective_minimum_deposit(ASSET), CONFIGURED_MINIMUM);
+
+		assert_ok!(IngressEgress::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetMinimumDepositIngressFeeMultiplier {
+				multiplier: Some(MULTIPLIER)
+			}]
+			.try_into()
+			.unwrap()
+		));
+		assert_eq!(
+			IngressEgress::effective_minimum_deposit(ASSET),
+			DEFAULT_DEPOSIT_AMOUNT / 2 * MULTIPLIER as u128
+		);
+
+		// The deposit is rejected and dynamic scaling is reported as active.
+		let (_, deposit_address) = request_address_and_deposit(0, ASSET);
+		assert_eq!(
+			IngressEgress::deposit_failures(Some(&deposit_address), None)[0].reason,
+			DepositFailedReason::BelowMinimumDeposit
+		);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			Event::DynamicMinimumDepositActivated {
+				asset: ASSET,
+				minimum_deposit: DEFAULT_DEPOSIT_AMOUNT / 2 * MULTIPLIER as u128,
+			},
+		));
+		assert!(DynamicMinimumDepositActive::<Test, ()>::contains_key(ASSET));
+
+		// Once fees are low again, the configured minimum applies.
+		ChainTracker::<Ethereum>::set_fee(CONFIGURED_MINIMUM / MULTIPLIER as u128);
+		assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+			&DepositWitness {
+				deposit_address,
+				asset: ASSET,
+				amount: DEFAULT_DEPOSIT_AMOUNT,
+				deposit_details: Default::default()
+			},
+			Default::default()
+		));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::DepositFinalised { .. })
+		);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			Event::DynamicMinimumDepositDeactivated { asset: ASSET },
+		));
+		assert!(!DynamicMinimumDepositActive::<Test, ()>::contains_key(ASSET));
+	});
+}
+
+#[test]
+fn deposit_failures_are_recorded_and_expire() {
+	new_test_ext().execute_with(|| {
+		const RETENTION_PERIOD: u32 = 10;
+		DepositFailureRetentionPeriod::<Test, ()>::set(RETENTION_PERIOD);
+		MinimumDeposit::<Test, ()>::insert(EthAsset::Eth, DEFAULT_DEPOSIT_AMOUNT + 1);
+
+		let (_, first_address) = request_address_and_deposit(0, EthAsset::Eth);
+		let (_, second_address) = request_address_and_deposit(1, EthAsset::Eth);
+
+		let failures = IngressEgress::deposit_failures(Some(&first_address), None);
+		assert_eq!(failures.len(), 1);
+		assert_eq!(failures[0].reason, DepositFailedReason::BelowMinimumDeposit);
+		assert_eq!(failures[0].deposit_address, Some(first_address));
+		assert_eq!(failures[0].asset, EthAsset::Eth);
+		assert_eq!(failures[0].amount, DEFAULT_DEPOSIT_AMOUNT);
+		assert_eq!(IngressEgress::deposit_failures(None, None).len(), 2);
+
+		// Failures are kept for the retention period.
+		let recorded_at = System::block_number();
+		IngressEgress::on_idle(recorded_at + RETENTION_PERIOD as u64 - 1, Weight::MAX);
+		assert_eq!(IngressEgress::deposit_failures(None, None).len(), 2);
+
+		// A new failure is recorded later on.
+		System::set_block_number(recorded_at + 5);
+		assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+			&DepositWitness {
+				deposit_address: second_address,
+				asset: EthAsset::Eth,
+				amount: DEFAULT_DEPOSIT_AMOUNT,
+				deposit_details: Default::default()
+			},
+			Default::default()
+		));
+		assert_eq!(IngressEgress::deposit_failures(Some(&second_address), None).len(), 2);
+
+		// Only the expired failures are removed.
+		IngressEgress::on_idle(recorded_at + RETENTION_PERIOD as u64, Weight::MAX);
+		assert!(IngressEgress::deposit_failures(Some(&first_address), None).is_empty());
+		assert_eq!(
+			IngressEgress::deposit_failures(Some(&second_address), None)
+				.into_iter()
+				.map(|failure| failure.recorded_at)
+				.collect::<Vec<_>>(),
+			vec![recorded_at as u32 + 5]
+		);
+	});
+}
+
+#[test]
+fn deposit_failure_history_is_bounded() {
+	new_test_ext().execute_with(|| {
+		MinimumDeposit::<Test, ()>::insert(EthAsset::Eth, DEFAULT_DEPOSIT_AMOUNT + 1);
+		let (_, deposit_address) = request_address_and_deposit(0, EthAsset::Eth);
+
+		for _ in 0..MAX_DEPOSIT_FAILURE_HISTORY {
+			assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+				&DepositWitness {
+					deposit_address,
+					asset: EthAsset::Eth,
+					amount: DEFAULT_DEPOSIT_AMOUNT,
+					deposit_details: Default::default()
+				},
+				Default::default()
+			));
+		}
+
+		assert_eq!(
+			DepositFailureHistoryRange::<Test, ()>::get(),
+			(1, MAX_DEPOSIT_FAILURE_HISTORY + 1)
+		);
+		assert!(DepositFailureHistory::<Test, ()>::get(0).is_none());
+		assert_eq!(
+			IngressEgress::deposit_failures(Some(&deposit_address), None).len() as u64,
+			MAX_DEPOSIT_FAILURE_HISTORY
+		);
+	});
+}
+
+#[test]
+fn can_update_all_config_items() {
+	new_test_ext().execute_with(|| {
+		const NEW_OPENING_FEE: u128 = 300;
+		const NEW_MIN_DEPOSIT_FLIP: u128 = 100;
+		const NEW_MIN_DEPOSIT_ETH: u128 = 200;
+		const NEW_DEPOSIT_CHANNEL_LIFETIME: u64 = 99;
+		const NETWORK_FEE_DEDUCTION: Percent = Percent::from_parts(50);
+		const NEW_DEPOSIT_FAILURE_RETENTION_PERIOD: u32 = 10;
+		const NEW_BOOST_RISK_THRESHOLD: Option<BasisPoints> = Some(5_000);
+		const NEW_BOOST_EXPOSURE_CAP: Option<u128> = Some(1_000_000);
+		const NEW_CHAIN_HALT_DETECTION: Option<ChainHaltDetectionSettings> =
+			Some(ChainHaltDetectionSettings { threshold: 50, pause_deposit_witnessing: true });
+		const NEW_CCM_DEFERRAL_TIMEOUT: u32 = 20;
+
+		// Check that the default values are different from the new ones
+		assert_eq!(ChannelOpeningFee::<Test, _>::get(), 0);
+		assert_eq!(MinimumDeposit::<Test, _>::get(EthAsset::Flip), 0);
 		assert_eq!(MinimumDeposit::<Test, _>::get(EthAsset::Eth), 0);
 		assert_ne!(DepositChannelLifetime::<Test, _>::get(), NEW_DEPOSIT_CHANNEL_LIFETIME);
+		assert_ne!(
+			DepositFailureRetentionPeriod::<Test, _>::get(),
+			NEW_DEPOSIT_FAILURE_RETENTION_PERIOD
+		);
+		assert!(BoostRiskThresholds::<Test, _>::get(5).is_none());
+		assert!(BoostExposureCaps::<Test, _>::get(EthAsset::Eth).is_none());
+		assert!(ChainHaltDetection::<Test, _>::get().is_none());
+		assert_ne!(CcmDeferralTimeout::<Test, _>::get(), NEW_CCM_DEFERRAL_TIMEOUT);
 
 		// Update all config items at the same time, and updates 2 separate min deposit amounts.
 		assert_ok!(IngressEgress::update_pallet_config(
@@ -1487,7 +3066,20 @@ fn can_update_all_config_items() {
 				},
 				PalletConfigUpdate::SetNetworkFeeDeductionFromBoost {
 					deduction_percent: NETWORK_FEE_DEDUCTION
-				}
+				},
+				PalletConfigUpdate::SetDepositFailureRetentionPeriod {
+					blocks: NEW_DEPOSIT_FAILURE_RETENTION_PERIOD
+				},
+				PalletConfigUpdate::SetBoostRiskThreshold {
+					tier: 5,
+					threshold: NEW_BOOST_RISK_THRESHOLD
+				},
+				PalletConfigUpdate::SetBoostExposureCap {
+					asset: EthAsset::Eth,
+					cap: NEW_BOOST_EXPOSURE_CAP
+				},
+				PalletConfigUpdate::SetChainHaltDetection { settings: NEW_CHAIN_HALT_DETECTION },
+				PalletConfigUpdate::SetCcmDeferralTimeout { blocks: NEW_CCM_DEFERRAL_TIMEOUT },
 			]
 			.try_into()
 			.unwrap()
@@ -1499,6 +3091,14 @@ fn can_update_all_config_items() {
 		assert_eq!(MinimumDeposit::<Test, _>::get(EthAsset::Eth), NEW_MIN_DEPOSIT_ETH);
 		assert_eq!(DepositChannelLifetime::<Test, _>::get(), NEW_DEPOSIT_CHANNEL_LIFETIME);
 		assert_eq!(NetworkFeeDeductionFromBoostPercent::<Test, _>::get(), NETWORK_FEE_DEDUCTION);
+		assert_eq!(
+			DepositFailureRetentionPeriod::<Test, _>::get(),
+			NEW_DEPOSIT_FAILURE_RETENTION_PERIOD
+		);
+		assert_eq!(BoostRiskThresholds::<Test, _>::get(5), NEW_BOOST_RISK_THRESHOLD);
+		assert_eq!(BoostExposureCaps::<Test, _>::get(EthAsset::Eth), NEW_BOOST_EXPOSURE_CAP);
+		assert_eq!(ChainHaltDetection::<Test, _>::get(), NEW_CHAIN_HALT_DETECTION);
+		assert_eq!(CcmDeferralTimeout::<Test, _>::get(), NEW_CCM_DEFERRAL_TIMEOUT);
 
 		// Check that the events were emitted
 		assert_events_eq!(
@@ -1518,6 +3118,23 @@ fn can_update_all_config_items() {
 			RuntimeEvent::IngressEgress(Event::NetworkFeeDeductionFromBoostSet {
 				deduction_percent: NETWORK_FEE_DEDUCTION
 			}),
+			RuntimeEvent::IngressEgress(Event::DepositFailureRetentionPeriodSet {
+				blocks: NEW_DEPOSIT_FAILURE_RETENTION_PERIOD
+			}),
+			RuntimeEvent::IngressEgress(Event::BoostRiskThresholdSet {
+				tier: 5,
+				threshold: NEW_BOOST_RISK_THRESHOLD
+			}),
+			RuntimeEvent::IngressEgress(Event::BoostExposureCapSet {
+				asset: EthAsset::Eth,
+				cap: NEW_BOOST_EXPOSURE_CAP
+			}),
+			RuntimeEvent::IngressEgress(Event::ChainHaltDetectionSet {
+				settings: NEW_CHAIN_HALT_DETECTION
+			}),
+			RuntimeEvent::IngressEgress(Event::CcmDeferralTimeoutSet {
+				blocks: NEW_CCM_DEFERRAL_TIMEOUT
+			}),
 		);
 
 		// Make sure that only governance can update the config
@@ -1531,6 +3148,161 @@ fn can_update_all_config_items() {
 	});
 }
 
+#[test]
+fn witness_safety_margin_is_tuned_to_observed_reorgs() {
+	new_test_ext().execute_with(|| {
+		const BOUNDS: WitnessSafetyMarginBounds<u64> =
+			WitnessSafetyMarginBounds { min: 3, max: 10 };
+		WitnessSafetyMargin::<Test, ()>::put(5);
+
+		let set_bounds = |bounds| {
+			IngressEgress::update_pallet_config(
+				OriginTrait::root(),
+				vec![PalletConfigUpdate::SetWitnessSafetyMarginBounds { bounds }]
+					.try_into()
+					.unwrap(),
+			)
+		};
+
+		// Reorgs are recorded, but the margin is not adjusted unless bounds are set.
+		assert_noop!(
+			IngressEgress::report_reorg(RuntimeOrigin::signed(ALICE), 100, 2),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(IngressEgress::report_reorg(RuntimeOrigin::root(), 100, 2));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::ReorgObserved {
+			block_height: 100,
+			depth: 2,
+		}));
+		assert_eq!(IngressEgress::recommended_witness_safety_margin(), Some(2));
+		assert_eq!(WitnessSafetyMargin::<Test, ()>::get(), Some(5));
+
+		assert_noop!(
+			set_bounds(Some(WitnessSafetyMarginBounds { min: 10, max: 3 })),
+			crate::Error::<Test, ()>::InvalidWitnessSafetyMarginBounds
+		);
+
+		// Setting the bounds applies the recommended margin, clamped to the bounds.
+		assert_ok!(set_bounds(Some(BOUNDS)));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			Event::WitnessSafetyMarginBoundsSet { bounds: Some(BOUNDS) },
+		));
+		assert_eq!(WitnessSafetyMargin::<Test, ()>::get(), Some(BOUNDS.min));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::WitnessSafetyMarginChanged {
+			old_margin: Some(5),
+			new_margin: BOUNDS.min,
+		}));
+
+		// The margin follows the deepest observed reorg, up to the maximum.
+		assert_ok!(IngressEgress::report_reorg(RuntimeOrigin::root(), 110, 6));
+		assert_eq!(WitnessSafetyMargin::<Test, ()>::get(), Some(6));
+		assert_ok!(IngressEgress::report_reorg(RuntimeOrigin::root(), 120, 4));
+		assert_eq!(WitnessSafetyMargin::<Test, ()>::get(), Some(6));
+		assert_ok!(IngressEgress::report_reorg(RuntimeOrigin::root(), 130, 20));
+		assert_eq!(IngressEgress::recommended_witness_safety_margin(), Some(20));
+		assert_eq!(WitnessSafetyMargin::<Test, ()>::get(), Some(BOUNDS.max));
+
+		// Only the most recent reorgs are taken into account.
+		for height in 0..MAX_OBSERVED_REORGS as u64 {
+			assert_ok!(IngressEgress::report_reorg(RuntimeOrigin::root(), 200 + height, 1));
+		}
+		assert_eq!(ObservedReorgs::<Test, ()>::get().len(), MAX_OBSERVED_REORGS);
+		assert_eq!(IngressEgress::recommended_witness_safety_margin(), Some(1));
+		assert_eq!(WitnessSafetyMargin::<Test, ()>::get(), Some(BOUNDS.min));
+
+		// Disabling the tuning leaves the margin unchanged.
+		assert_ok!(set_bounds(None));
+		assert_ok!(IngressEgress::report_reorg(RuntimeOrigin::root(), 400, 8));
+		assert_eq!(WitnessSafetyMargin::<Test, ()>::get(), Some(BOUNDS.min));
+	});
+}
+
+#[test]
+fn chain_halt_pauses_recycling_and_defers_deposits() {
+	const ETH: EthAsset = EthAsset::Eth;
+	const THRESHOLD: u32 = 3;
+
+	new_test_ext()
+		.then_execute_at_next_block(|_| {
+			assert_ok!(IngressEgress::update_pallet_config(
+				OriginTrait::root(),
+				vec![PalletConfigUpdate::SetChainHaltDetection {
+					settings: Some(ChainHaltDetectionSettings {
+						threshold: THRESHOLD,
+						pause_deposit_witnessing: true,
+					}),
+				}]
+				.try_into()
+				.unwrap()
+			));
+			let (_, address, ..) = IngressEgress::request_liquidity_deposit_address(
+				ALICE,
+				ETH,
+				0,
+				ForeignChainAddress::Eth(Default::default()),
+			)
+			.unwrap();
+			let address: <Ethereum as Chain>::ChainAccount = address.try_into().unwrap();
+			let recycles_at = IngressEgress::expiry_and_recycle_block_height().2;
+			(address, recycles_at)
+		})
+		// The tracked block height doesn't change for the configured number of blocks.
+		.then_process_blocks(THRESHOLD - 1)
+		.then_execute_with_keep_context(|_| {
+			assert!(!ChainHalted::<Test, _>::get());
+		})
+		.then_process_next_block()
+		.then_execute_with_keep_context(|_| {
+			assert!(ChainHalted::<Test, _>::get());
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::IngressEgress(Event::ChainHaltDetected { .. })
+			);
+		})
+		.then_execute_at_next_block(|(address, recycles_at)| {
+			// Deposits witnessed while the chain is halted are held back.
+			assert_ok!(IngressEgress::process_deposits(
+				RuntimeOrigin::root(),
+				vec![DepositWitness {
+					deposit_address: address,
+					asset: ETH,
+					amount: DEFAULT_DEPOSIT_AMOUNT,
+					deposit_details: Default::default(),
+				}],
+				Default::default(),
+			));
+			assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+				Event::DepositWitnessesDeferred { block_height: 0, deposit_count: 1 },
+			));
+			assert_eq!(DeferredDepositWitnesses::<Test, _>::get().len(), 1);
+
+			// Even though the recycle height is reached, the channel isn't recycled while the
+			// chain is considered halted.
+			BlockHeightProvider::<MockEthereum>::set_block_height(recycles_at);
+			(address, recycles_at)
+		})
+		.then_execute_with_keep_context(|(address, _)| {
+			assert!(DepositChannelLookup::<Test, _>::get(address).is_some());
+		})
+		// The chain made progress, so deferred deposits are processed and recycling resumes.
+		.then_execute_at_next_block(|(address, recycles_at)| {
+			assert!(!ChainHalted::<Test, _>::get());
+			assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::ChainHaltEnded {
+				block_height: recycles_at,
+				deferred_deposits_processed: 1,
+			}));
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::IngressEgress(Event::DepositFinalised { .. })
+			);
+			assert!(DeferredDepositWitnesses::<Test, _>::get().is_empty());
+			address
+		})
+		.then_execute_with(|address| {
+			assert!(DepositChannelLookup::<Test, _>::get(address).is_none());
+		});
+}
+
 fn test_ingress_or_egress_fee_is_withheld_or_scheduled_for_swap(test_function: impl Fn(EthAsset)) {
 	new_test_ext().execute_with(|| {
 		// Set the Gas (ingress egress Fee) via ChainTracker
@@ -1598,6 +3370,8 @@ fn egress_transaction_fee_is_withheld_or_scheduled_for_swap() {
 			DEFAULT_DEPOSIT_AMOUNT,
 			Default::default(),
 			None,
+			None,
+			EgressOrigin::Swap,
 		)
 		.unwrap();
 	}
@@ -1625,6 +3399,7 @@ fn safe_mode_prevents_deposit_channel_creation() {
 				refund_address: Some(ForeignChainAddress::Eth(Default::default()))
 			},
 			0,
+			ChannelOpenAttempt::New,
 		));
 
 		use cf_traits::SetSafeMode;
@@ -1645,6 +3420,7 @@ fn safe_mode_prevents_deposit_channel_creation() {
 					refund_address: Some(ForeignChainAddress::Eth(Default::default()))
 				},
 				0,
+				ChannelOpenAttempt::New,
 			),
 			crate::Error::<Test, _>::DepositChannelCreationDisabled
 		);
@@ -1670,7 +3446,14 @@ fn do_not_batch_more_transfers_than_the_limit_allows() {
 		let transfer_limits = MockFetchesTransfersLimitProvider::maybe_transfers_limit().unwrap();
 
 		for _ in 1..=transfer_limits + EXCESS_TRANSFERS {
-			assert_ok!(IngressEgress::schedule_egress(ETH_ETH, 1_000, ALICE_ETH_ADDRESS, None));
+			assert_ok!(IngressEgress::schedule_egress(
+				ETH_ETH,
+				1_000,
+				ALICE_ETH_ADDRESS,
+				None,
+				None,
+				EgressOrigin::Swap
+			));
 		}
 
 		let scheduled_egresses = ScheduledEgressFetchOrTransfer::<Test, ()>::get();
@@ -1686,12 +3469,31 @@ fn do_not_batch_more_transfers_than_the_limit_allows() {
 		let scheduled_egresses = ScheduledEgressFetchOrTransfer::<Test, ()>::get();
 
 		assert_eq!(scheduled_egresses.len(), EXCESS_TRANSFERS, "Wrong amount of left egresses!");
+		assert_eq!(
+			LastEgressUsage::<Test, ()>::get(),
+			EgressUsage {
+				block_number: System::block_number(),
+				fetches: 0,
+				transfers: transfer_limits as u32,
+				ccms: 0
+			}
+		);
 
+		System::set_block_number(System::block_number() + 1);
 		IngressEgress::on_finalize(2);
 
 		let scheduled_egresses = ScheduledEgressFetchOrTransfer::<Test, ()>::get();
 
 		assert_eq!(scheduled_egresses.len(), 0, "Left egresses have not been fully processed!");
+		assert_eq!(
+			LastEgressUsage::<Test, ()>::get(),
+			EgressUsage {
+				block_number: System::block_number(),
+				fetches: 0,
+				transfers: EXCESS_TRANSFERS as u32,
+				ccms: 0
+			}
+		);
 	});
 }
 
@@ -1771,7 +3573,9 @@ fn do_not_process_more_ccm_swaps_than_allowed_by_limit() {
 				ETH_ETH,
 				1_000,
 				ALICE_ETH_ADDRESS,
-				Some(ccm.clone())
+				Some(ccm.clone()),
+				None,
+				EgressOrigin::Swap,
 			));
 		}
 
@@ -1797,6 +3601,94 @@ fn do_not_process_more_ccm_swaps_than_allowed_by_limit() {
 	});
 }
 
+#[test]
+fn oldest_transfers_are_egressed_first_when_limited() {
+	new_test_ext().execute_with(|| {
+		MockFetchesTransfersLimitProvider::enable_limits();
+
+		const OLD_EGRESS_ID: EgressId = (ForeignChain::Ethereum, 0, 100);
+		let transfer_limits = MockFetchesTransfersLimitProvider::maybe_transfers_limit().unwrap();
+
+		System::set_block_number(5);
+		for _ in 0..transfer_limits {
+			assert_ok!(IngressEgress::schedule_egress(
+				ETH_ETH,
+				1_000,
+				ALICE_ETH_ADDRESS,
+				None,
+				None,
+				EgressOrigin::Swap
+			));
+		}
+		// An older transfer that ended up at the back of the queue.
+		ScheduledEgressFetchOrTransfer::<Test, ()>::append(FetchOrTransfer::Transfer {
+			egress_id: OLD_EGRESS_ID,
+			asset: ETH_ETH,
+			amount: 1_000,
+			destination_address: BOB_ETH_ADDRESS,
+			scheduled_at: 1,
+			memo: None,
+			origin: EgressOrigin::Swap,
+		});
+
+		System::set_block_number(10);
+		IngressEgress::on_finalize(10);
+
+		let remaining = ScheduledEgressFetchOrTransfer::<Test, ()>::get();
+		assert_eq!(remaining.len(), 1);
+		assert!(matches!(
+			remaining[0],
+			FetchOrTransfer::Transfer { egress_id, scheduled_at: 5, .. } if egress_id != OLD_EGRESS_ID
+		));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::EgressWaitTimes { wait_times, .. })
+				if wait_times.contains(&(OLD_EGRESS_ID, 9))
+		);
+	});
+}
+
+#[test]
+fn tracking_id_is_included_in_deposit_events() {
+	const TRACKING_ID: TrackingId = [7; 32];
+
+	new_test_ext().execute_with(|| {
+		let (_, address, ..) = IngressEgress::request_swap_deposit_address(
+			EthAsset::Eth,
+			Asset::Usdc,
+			ForeignChainAddress::Eth(Default::default()),
+			Default::default(),
+			BROKER,
+			None,
+			0,
+			None,
+			None,
+			Some(TRACKING_ID),
+			None,
+		)
+		.unwrap();
+
+		assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+			&DepositWitness {
+				deposit_address: address.try_into().unwrap(),
+				asset: EthAsset::Eth,
+				amount: 1_000,
+				deposit_details: Default::default(),
+			},
+			Default::default()
+		));
+
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::DepositFinalised {
+				action: DepositAction::Swap { .. },
+				tracking_id: Some(TRACKING_ID),
+				..
+			})
+		);
+	});
+}
+
 fn submit_vault_swap_request(
 	input_asset: Asset,
 	output_asset: Asset,
@@ -1830,6 +3722,8 @@ fn submit_vault_swap_request(
 			refund_params: Some(refund_params),
 			dca_params,
 			boost_fee,
+			tracking_id: None,
+			verified_sender: None,
 		}),
 	)
 }
@@ -1865,28 +3759,248 @@ fn can_request_swap_via_extrinsic() {
 				input_asset: INPUT_ASSET,
 				output_asset: OUTPUT_ASSET,
 				input_amount: INPUT_AMOUNT,
-				swap_type: SwapRequestType::Regular { output_address, ccm_deposit_metadata: None },
+				swap_type: SwapRequestType::Regular {
+					output_address,
+					ccm_deposit_metadata: None,
+					egress_memo: None
+				},
 				broker_fees: bounded_vec![Beneficiary { account: BROKER, bps: 0 }],
 				origin: SwapOrigin::Vault {
 					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 					broker_id: Some(BROKER),
+					verified_sender: None,
 				},
 			},]
 		);
+
+		// The deposit is attributed to the broker's private channel.
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			Event::PrivateChannelVaultSwapWitnessed {
+				channel_id: 0,
+				broker_id: BROKER,
+				tx_id: Default::default(),
+				asset: ETH_ETH,
+				amount: INPUT_AMOUNT,
+				block_height: 0,
+			},
+		));
 	});
 }
 
 #[test]
-fn vault_swaps_support_affiliate_fees() {
+fn verified_sender_is_included_in_swap_origin() {
+	let sender = H160::repeat_byte(2);
+
 	new_test_ext().execute_with(|| {
-		const INPUT_ASSET: Asset = Asset::Usdc;
-		const OUTPUT_ASSET: Asset = Asset::Flip;
-		const INPUT_AMOUNT: AssetAmount = 10_000;
+		assert_ok!(IngressEgress::vault_swap_request(
+			RuntimeOrigin::root(),
+			0,
+			Box::new(VaultDepositWitness {
+				input_asset: EthAsset::Eth,
+				deposit_address: None,
+				channel_id: None,
+				deposit_amount: 1_000,
+				deposit_details: DepositDetails { tx_hashes: None },
+				output_asset: Asset::Flip,
+				destination_address: MockAddressConverter::to_encoded_address(
+					ForeignChainAddress::Eth([1; 20].into())
+				),
+				deposit_metadata: None,
+				tx_id: Default::default(),
+				broker_fee: Some(Beneficiary { account: BROKER, bps: 0 }),
+				affiliate_fees: Default::default(),
+				refund_params: Some(ETH_REFUND_PARAMS),
+				dca_params: None,
+				boost_fee: 0,
+				tracking_id: None,
+				verified_sender: Some(sender),
+			}),
+		));
 
-		const BROKER_FEE: BasisPoints = 5;
-		const AFFILIATE_FEE: BasisPoints = 10;
-		const AFFILIATE_1: u64 = 102;
-		const AFFILIATE_2: u64 = 103;
+		assert_eq!(
+			MockSwapRequestHandler::<Test>::get_swap_requests()[0].origin,
+			SwapOrigin::Vault {
+				tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
+				broker_id: Some(BROKER),
+				verified_sender: Some(MockAddressConverter::to_encoded_address(
+					ForeignChainAddress::Eth(sender)
+				)),
+			}
+		);
+	});
+}
+
+#[test]
+fn deposits_of_suspended_brokers_are_refunded() {
+	new_test_ext().execute_with(|| {
+		MockSwapRequestHandler::<Test>::set_broker_suspended(BROKER, true);
+
+		assert_ok!(submit_vault_swap_request(
+			Asset::Eth,
+			Asset::Flip,
+			1_000,
+			Default::default(),
+			MockAddressConverter::to_encoded_address(ForeignChainAddress::Eth([1; 20].into())),
+			None,
+			Default::default(),
+			DepositDetails { tx_hashes: None },
+			Beneficiary { account: BROKER, bps: 0 },
+			Default::default(),
+			ETH_REFUND_PARAMS,
+			None,
+			0
+		));
+
+		assert!(MockSwapRequestHandler::<Test>::get_swap_requests().is_empty());
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::DepositFinalised {
+				action: DepositAction::Refund {
+					reason: DepositFailedReason::BrokerSuspended,
+					egress_id: Some(_),
+					..
+				},
+				..
+			})
+		);
+	});
+}
+
+#[test]
+fn vault_swaps_with_identical_parameters_are_aggregated() {
+	const INPUT_AMOUNT: AssetAmount = 1_000u128;
+	const AGGREGATION_WINDOW: u32 = 10;
+
+	let output_address = ForeignChainAddress::Eth([1; 20].into());
+	let submit_vault_swap = |tx_id: u8| {
+		assert_ok!(submit_vault_swap_request(
+			Asset::Eth,
+			Asset::Flip,
+			INPUT_AMOUNT,
+			Default::default(),
+			MockAddressConverter::to_encoded_address(output_address.clone()),
+			None,
+			H256::repeat_byte(tx_id),
+			DepositDetails { tx_hashes: None },
+			Beneficiary { account: BROKER, bps: 0 },
+			Default::default(),
+			ETH_REFUND_PARAMS,
+			Some(DcaParameters { number_of_chunks: 5, chunk_interval: 2 }),
+			0
+		));
+	};
+	let swap_request_amounts = || {
+		MockSwapRequestHandler::<Test>::get_swap_requests()
+			.into_iter()
+			.map(|request| request.input_amount)
+			.collect::<Vec<_>>()
+	};
+
+	new_test_ext().execute_with(|| {
+		// Aggregation is disabled by default.
+		submit_vault_swap(1);
+		submit_vault_swap(2);
+		assert_eq!(swap_request_amounts(), vec![INPUT_AMOUNT, INPUT_AMOUNT]);
+
+		assert_ok!(IngressEgress::update_pallet_config(
+			RuntimeOrigin::root(),
+			vec![PalletConfigUpdate::SetVaultSwapAggregationWindow { blocks: AGGREGATION_WINDOW }]
+				.try_into()
+				.unwrap()
+		));
+
+		// The second deposit tops up the swap request opened by the first.
+		submit_vault_swap(3);
+		submit_vault_swap(4);
+		assert_eq!(swap_request_amounts(), vec![INPUT_AMOUNT, INPUT_AMOUNT, INPUT_AMOUNT * 2]);
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::DepositFinalised {
+				action: DepositAction::SwapToppedUp { .. },
+				..
+			})
+		);
+
+		// Deposits after the window has passed open a new swap request.
+		System::set_block_number(System::block_number() + AGGREGATION_WINDOW as u64);
+		submit_vault_swap(5);
+		assert_eq!(
+			swap_request_amounts(),
+			vec![INPUT_AMOUNT, INPUT_AMOUNT, INPUT_AMOUNT * 2, INPUT_AMOUNT]
+		);
+	});
+}
+
+#[test]
+fn vault_swap_witnesses_resubmitted_across_epochs_are_ignored() {
+	const INPUT_AMOUNT: AssetAmount = 1_000u128;
+
+	let submit_vault_swap = |tx_id: u8| {
+		assert_ok!(submit_vault_swap_request(
+			Asset::Eth,
+			Asset::Flip,
+			INPUT_AMOUNT,
+			Default::default(),
+			MockAddressConverter::to_encoded_address(ForeignChainAddress::Eth([1; 20].into())),
+			None,
+			H256::repeat_byte(tx_id),
+			DepositDetails { tx_hashes: None },
+			Beneficiary { account: BROKER, bps: 0 },
+			Default::default(),
+			ETH_REFUND_PARAMS,
+			None,
+			0
+		));
+	};
+	let swap_request_count = || MockSwapRequestHandler::<Test>::get_swap_requests().len();
+
+	new_test_ext().execute_with(|| {
+		let epoch = MockEpochInfo::epoch_index();
+		submit_vault_swap(1);
+		assert_eq!(swap_request_count(), 1);
+
+		// The witness is re-submitted after the rotation.
+		MockEpochInfo::set_epoch(epoch + 1);
+		submit_vault_swap(1);
+		assert_eq!(swap_request_count(), 1);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			Event::DuplicateVaultSwapWitnessIgnored {
+				tx_id: H256::repeat_byte(1),
+				block_height: 0,
+			},
+		));
+
+		// Other transactions are processed as usual.
+		submit_vault_swap(2);
+		assert_eq!(swap_request_count(), 2);
+
+		// Records from before the previous epoch are cleared.
+		MockEpochInfo::set_epoch(epoch + 2);
+		IngressEgress::on_idle(System::block_number(), Weight::MAX);
+		assert!(!ProcessedVaultSwaps::<Test, ()>::contains_key(epoch, H256::repeat_byte(1)));
+		assert!(ProcessedVaultSwaps::<Test, ()>::contains_key(epoch + 1, H256::repeat_byte(2)));
+
+		// Every stale epoch is cleared, even if several rotations happened in between.
+		submit_vault_swap(3);
+		MockEpochInfo::set_epoch(epoch + 5);
+		IngressEgress::on_idle(System::block_number(), Weight::MAX);
+		assert!(!ProcessedVaultSwaps::<Test, ()>::contains_key(epoch + 1, H256::repeat_byte(2)));
+		assert!(!ProcessedVaultSwaps::<Test, ()>::contains_key(epoch + 2, H256::repeat_byte(3)));
+		assert_eq!(OldestUnclearedVaultSwapEpoch::<Test, ()>::get(), Some(epoch + 4));
+	});
+}
+
+#[test]
+fn vault_swaps_support_affiliate_fees() {
+	new_test_ext().execute_with(|| {
+		const INPUT_ASSET: Asset = Asset::Usdc;
+		const OUTPUT_ASSET: Asset = Asset::Flip;
+		const INPUT_AMOUNT: AssetAmount = 10_000;
+
+		const BROKER_FEE: BasisPoints = 5;
+		const AFFILIATE_FEE: BasisPoints = 10;
+		const AFFILIATE_1: u64 = 102;
+		const AFFILIATE_2: u64 = 103;
 
 		const AFFILIATE_SHORT_1: AffiliateShortId = AffiliateShortId(0);
 		const AFFILIATE_SHORT_2: AffiliateShortId = AffiliateShortId(1);
@@ -1925,7 +4039,11 @@ fn vault_swaps_support_affiliate_fees() {
 				input_asset: INPUT_ASSET,
 				output_asset: OUTPUT_ASSET,
 				input_amount: INPUT_AMOUNT,
-				swap_type: SwapRequestType::Regular { output_address, ccm_deposit_metadata: None },
+				swap_type: SwapRequestType::Regular {
+					output_address,
+					ccm_deposit_metadata: None,
+					egress_memo: None
+				},
 				broker_fees: bounded_vec![
 					Beneficiary { account: BROKER, bps: BROKER_FEE },
 					// Only one affiliate is used (short id for affiliate 2 has not been
@@ -1935,6 +4053,7 @@ fn vault_swaps_support_affiliate_fees() {
 				origin: SwapOrigin::Vault {
 					tx_id: cf_chains::TransactionInIdForAnyChain::Evm(H256::default()),
 					broker_id: Some(BROKER),
+					verified_sender: None,
 				},
 			},]
 		);
@@ -1946,6 +4065,63 @@ fn vault_swaps_support_affiliate_fees() {
 	});
 }
 
+#[test]
+fn out_of_range_vault_swap_refund_parameters_are_flagged_instead_of_refunded() {
+	new_test_ext().execute_with(|| {
+		const INPUT_ASSET: Asset = Asset::Eth;
+		const OUTPUT_ASSET: Asset = Asset::Flip;
+		const RETRY_DURATION: u32 = 10_000;
+		const TX_ID: H256 = H256::repeat_byte(1);
+
+		let max_retry_duration =
+			MockSwapLimitsProvider::get_swap_limits().max_swap_retry_duration_blocks;
+		MockSwapLimitsProvider::set_max_min_price(
+			INPUT_ASSET,
+			OUTPUT_ASSET,
+			sp_core::U256::from(1_000),
+		);
+
+		assert_ok!(submit_vault_swap_request(
+			INPUT_ASSET,
+			OUTPUT_ASSET,
+			DEFAULT_DEPOSIT_AMOUNT,
+			Default::default(),
+			MockAddressConverter::to_encoded_address(ForeignChainAddress::Eth([1; 20].into())),
+			None,
+			TX_ID,
+			DepositDetails { tx_hashes: None },
+			Beneficiary { account: BROKER, bps: 0 },
+			Default::default(),
+			ChannelRefundParametersDecoded {
+				retry_duration: RETRY_DURATION,
+				min_price: sp_core::U256::from(1_001),
+				..ETH_REFUND_PARAMS
+			},
+			None,
+			0
+		));
+
+		// The swap goes ahead instead of being refunded:
+		assert_eq!(MockSwapRequestHandler::<Test>::get_swap_requests().len(), 1);
+		assert_events_eq!(
+			Test,
+			RuntimeEvent::IngressEgress(PalletEvent::VaultSwapParametersFlagged {
+				tx_id: TX_ID,
+				broker_id: Some(BROKER),
+				warning: VaultSwapParameterWarning::RetryDurationClamped {
+					encoded: RETRY_DURATION,
+					clamped_to: max_retry_duration,
+				},
+			}),
+			RuntimeEvent::IngressEgress(PalletEvent::VaultSwapParametersFlagged {
+				tx_id: TX_ID,
+				broker_id: Some(BROKER),
+				warning: VaultSwapParameterWarning::MinPriceLikelyInverted,
+			})
+		);
+	});
+}
+
 #[test]
 fn charge_no_broker_fees_on_unknown_primary_broker() {
 	new_test_ext().execute_with(|| {
@@ -1982,11 +4158,16 @@ fn charge_no_broker_fees_on_unknown_primary_broker() {
 				input_asset: INPUT_ASSET,
 				output_asset: OUTPUT_ASSET,
 				input_amount: INPUT_AMOUNT,
-				swap_type: SwapRequestType::Regular { output_address, ccm_deposit_metadata: None },
+				swap_type: SwapRequestType::Regular {
+					output_address,
+					ccm_deposit_metadata: None,
+					egress_memo: None
+				},
 				broker_fees: Default::default(),
 				origin: SwapOrigin::Vault {
 					tx_id: cf_chains::TransactionInIdForAnyChain::Evm(H256::default()),
 					broker_id: Some(NOT_A_BROKER),
+					verified_sender: None,
 				},
 			},]
 		);
@@ -2041,12 +4222,14 @@ fn can_request_ccm_swap_via_extrinsic() {
 				input_amount: INPUT_AMOUNT,
 				swap_type: SwapRequestType::Regular {
 					output_address,
-					ccm_deposit_metadata: Some(ccm_deposit_metadata)
+					ccm_deposit_metadata: Some(ccm_deposit_metadata),
+					egress_memo: None,
 				},
 				broker_fees: bounded_vec![Beneficiary { account: BROKER, bps: 0 }],
 				origin: SwapOrigin::Vault {
 					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 					broker_id: Some(BROKER),
+					verified_sender: None,
 				},
 			},]
 		);
@@ -2093,7 +4276,7 @@ fn rejects_invalid_swap_by_witnesser() {
 			Default::default(),
 			EncodedAddress::Btc(vec![0x41, 0x80, 0x41]),
 			None,
-			Default::default(),
+			H256::repeat_byte(1),
 			DepositDetails { tx_hashes: None },
 			Beneficiary { account: 0, bps: 0 },
 			Default::default(),
@@ -2156,6 +4339,63 @@ fn failed_ccm_deposit_can_deposit_event() {
 			Default::default(),
 			EncodedAddress::Eth(Default::default()),
 			Some(ccm_deposit_metadata),
+			H256::repeat_byte(1),
+			DepositDetails { tx_hashes: None },
+			Beneficiary { account: 0, bps: 0 },
+			Default::default(),
+			ETH_REFUND_PARAMS,
+			None,
+			0
+		));
+	});
+}
+
+#[test]
+fn ccm_exceeding_chain_limits_is_rejected() {
+	let ccm_deposit_metadata = CcmDepositMetadata {
+		source_chain: ForeignChain::Ethereum,
+		source_address: Some(ForeignChainAddress::Eth([0xcf; 20].into())),
+		channel_metadata: CcmChannelMetadata {
+			message: vec![0x01].try_into().unwrap(),
+			gas_budget: 1_000,
+			ccm_additional_data: Default::default(),
+		},
+	};
+
+	new_test_ext().execute_with(|| {
+		MockSwapLimitsProvider::set_ccm_limits(
+			ForeignChain::Ethereum,
+			CcmLimits {
+				max_message_length: 10,
+				max_gas_budget: 999,
+				max_additional_data_length: 0,
+			},
+		);
+
+		assert_noop!(
+			IngressEgress::request_swap_deposit_address(
+				EthAsset::Eth,
+				Asset::Flip,
+				ForeignChainAddress::Eth(Default::default()),
+				Default::default(),
+				BROKER,
+				Some(ccm_deposit_metadata.channel_metadata.clone()),
+				0,
+				None,
+				None,
+				None,
+				None,
+			),
+			DispatchError::from(CcmValidityError::CcmGasBudgetTooHigh)
+		);
+
+		assert_ok!(submit_vault_swap_request(
+			Asset::Eth,
+			Asset::Flip,
+			10_000,
+			Default::default(),
+			EncodedAddress::Eth(Default::default()),
+			Some(ccm_deposit_metadata),
 			Default::default(),
 			DepositDetails { tx_hashes: None },
 			Beneficiary { account: 0, bps: 0 },
@@ -2164,6 +4404,442 @@ fn failed_ccm_deposit_can_deposit_event() {
 			None,
 			0
 		));
+
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::DepositFailed {
+				reason: DepositFailedReason::CcmGasBudgetTooHigh,
+				..
+			})
+		);
+		assert!(MockSwapRequestHandler::<Test>::get_swap_requests().is_empty());
+	});
+}
+
+#[test]
+fn broker_can_update_swap_channel() {
+	new_test_ext().execute_with(|| {
+		let (channel_id, address, ..) = IngressEgress::request_swap_deposit_address(
+			EthAsset::Eth,
+			Asset::Usdc,
+			ForeignChainAddress::Eth(Default::default()),
+			Default::default(),
+			BROKER,
+			None,
+			0,
+			None,
+			None,
+			None,
+			None,
+		)
+		.unwrap();
+		let deposit_address: H160 = address.try_into().unwrap();
+
+		let new_broker_fees: Beneficiaries<u64> = bounded_vec![
+			Beneficiary { account: BROKER, bps: 20 },
+			Beneficiary { account: ALICE, bps: 5 }
+		];
+		let new_destination_address = ForeignChainAddress::Eth([1; 20].into());
+
+		// Only the broker that opened the channel can update it:
+		<mocks::account_role_registry::MockAccountRoleRegistry as cf_traits::AccountRoleRegistry<
+			Test,
+		>>::register_as_broker(&ALICE)
+		.unwrap();
+		assert_noop!(
+			IngressEgress::update_swap_channel(
+				OriginTrait::signed(ALICE),
+				deposit_address,
+				Some((20, Default::default())),
+				None,
+			),
+			crate::Error::<Test, ()>::NotChannelOwner
+		);
+
+		// The new values are validated:
+		assert_noop!(
+			IngressEgress::update_swap_channel(
+				OriginTrait::signed(BROKER),
+				deposit_address,
+				None,
+				Some(EncodedAddress::Dot(Default::default())),
+			),
+			crate::Error::<Test, ()>::InvalidDestinationAddress
+		);
+		assert_noop!(
+			IngressEgress::update_swap_channel(
+				OriginTrait::signed(BROKER),
+				deposit_address,
+				Some((1001, Default::default())),
+				None,
+			),
+			DispatchError::Other("Broker fees too high")
+		);
+
+		// Suspended brokers cannot update their channels:
+		MockSwapRequestHandler::<Test>::set_broker_suspended(BROKER, true);
+		assert_noop!(
+			IngressEgress::update_swap_channel(
+				OriginTrait::signed(BROKER),
+				deposit_address,
+				Some((20, Default::default())),
+				None,
+			),
+			crate::Error::<Test, ()>::BrokerSuspended
+		);
+		MockSwapRequestHandler::<Test>::set_broker_suspended(BROKER, false);
+
+		// The broker's commission always comes first and zero fees are dropped:
+		assert_ok!(IngressEgress::update_swap_channel(
+			OriginTrait::signed(BROKER),
+			deposit_address,
+			Some((
+				20,
+				bounded_vec![
+					Beneficiary { account: 789, bps: 0 },
+					Beneficiary { account: ALICE, bps: 5 }
+				]
+			)),
+			Some(MockAddressConverter::to_encoded_address(new_destination_address.clone())),
+		));
+
+		assert!(matches!(
+			DepositChannelLookup::<Test, ()>::get(deposit_address).unwrap().action,
+			ChannelAction::Swap { broker_fees, destination_address, .. }
+				if broker_fees == new_broker_fees && destination_address == new_destination_address
+		));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::SwapChannelUpdated {
+			deposit_address,
+			channel_id,
+			broker_id: BROKER,
+			broker_fees: new_broker_fees,
+			destination_address: new_destination_address,
+		}));
+
+		// Expired channels can no longer be updated:
+		assert_ok!(IngressEgress::close_deposit_channel(
+			OriginTrait::signed(BROKER),
+			deposit_address
+		));
+		assert_noop!(
+			IngressEgress::update_swap_channel(
+				OriginTrait::signed(BROKER),
+				deposit_address,
+				Some((20, Default::default())),
+				None,
+			),
+			crate::Error::<Test, ()>::DepositChannelAlreadyExpired
+		);
+	});
+}
+
+#[test]
+fn channel_owner_can_update_refund_address_after_delay() {
+	new_test_ext().execute_with(|| {
+		let (channel_id, address, ..) = IngressEgress::request_swap_deposit_address(
+			EthAsset::Eth,
+			Asset::Usdc,
+			ForeignChainAddress::Eth(Default::default()),
+			Default::default(),
+			BROKER,
+			None,
+			0,
+			Some(ETH_REFUND_PARAMS),
+			None,
+			None,
+			None,
+		)
+		.unwrap();
+		let deposit_address: H160 = address.try_into().unwrap();
+		let new_refund_address = ForeignChainAddress::Eth([1; 20].into());
+		let refund_address_of =
+			|| match DepositChannelLookup::<Test, ()>::get(deposit_address).unwrap().action {
+				ChannelAction::Swap { refund_params, .. } => refund_params.unwrap().refund_address,
+				_ => panic!("Expected a swap channel"),
+			};
+
+		assert_noop!(
+			IngressEgress::update_refund_address(
+				OriginTrait::signed(ALICE),
+				deposit_address,
+				MockAddressConverter::to_encoded_address(new_refund_address.clone()),
+			),
+			crate::Error::<Test, ()>::NotChannelOwner
+		);
+		assert_noop!(
+			IngressEgress::update_refund_address(
+				OriginTrait::signed(BROKER),
+				deposit_address,
+				EncodedAddress::Dot(Default::default()),
+			),
+			crate::Error::<Test, ()>::InvalidRefundAddress
+		);
+
+		assert_ok!(IngressEgress::update_refund_address(
+			OriginTrait::signed(BROKER),
+			deposit_address,
+			MockAddressConverter::to_encoded_address(new_refund_address.clone()),
+		));
+		let apply_at = System::block_number() + RefundAddressUpdateDelay::<Test, ()>::get() as u64;
+		System::assert_last_event(RuntimeEvent::IngressEgress(
+			Event::RefundAddressUpdateScheduled {
+				deposit_address,
+				channel_id,
+				owner: BROKER,
+				refund_address: new_refund_address.clone(),
+				apply_at,
+			},
+		));
+
+		// The refund address is only updated once the delay has passed:
+		IngressEgress::on_initialize(apply_at - 1);
+		assert_eq!(refund_address_of(), ETH_REFUND_PARAMS.refund_address);
+
+		IngressEgress::on_initialize(apply_at);
+		assert_eq!(refund_address_of(), new_refund_address);
+		assert!(PendingRefundAddressUpdates::<Test, ()>::get(deposit_address).is_none());
+		System::assert_last_event(RuntimeEvent::IngressEgress(Event::RefundAddressUpdated {
+			deposit_address,
+			channel_id,
+			owner: BROKER,
+			previous_refund_address: Some(ETH_REFUND_PARAMS.refund_address),
+			refund_address: new_refund_address,
+		}));
+	});
+}
+
+#[test]
+fn replaced_refund_address_update_is_applied_at_its_own_block() {
+	new_test_ext().execute_with(|| {
+		RefundAddressUpdateDelay::<Test, ()>::set(10);
+		let (_, address, ..) = IngressEgress::request_liquidity_deposit_address(
+			ALICE,
+			EthAsset::Eth,
+			0,
+			ForeignChainAddress::Eth(Default::default()),
+		)
+		.unwrap();
+		let deposit_address: H160 = address.try_into().unwrap();
+		let refund_address_of =
+			|| match DepositChannelLookup::<Test, ()>::get(deposit_address).unwrap().action {
+				ChannelAction::LiquidityProvision { refund_address, .. } => refund_address.unwrap(),
+				_ => panic!("Expected a liquidity channel"),
+			};
+
+		let first_apply_at = System::block_number() + 10;
+		assert_ok!(IngressEgress::update_refund_address(
+			OriginTrait::signed(ALICE),
+			deposit_address,
+			EncodedAddress::Eth([1; 20]),
+		));
+
+		// Replace the pending update with a later one:
+		System::set_block_number(System::block_number() + 1);
+		let second_apply_at = System::block_number() + 10;
+		assert_ok!(IngressEgress::update_refund_address(
+			OriginTrait::signed(ALICE),
+			deposit_address,
+			EncodedAddress::Eth([2; 20]),
+		));
+
+		// The replaced update is not applied, and the replacement is kept:
+		IngressEgress::on_initialize(first_apply_at);
+		assert_eq!(refund_address_of(), ForeignChainAddress::Eth(Default::default()));
+		assert!(PendingRefundAddressUpdates::<Test, ()>::get(deposit_address).is_some());
+
+		IngressEgress::on_initialize(second_apply_at);
+		assert_eq!(refund_address_of(), ForeignChainAddress::Eth([2; 20].into()));
+		assert!(PendingRefundAddressUpdates::<Test, ()>::get(deposit_address).is_none());
+	});
+}
+
+#[test]
+fn refund_address_can_not_be_updated_without_refund_params() {
+	new_test_ext().execute_with(|| {
+		let (_, address, ..) = IngressEgress::request_swap_deposit_address(
+			EthAsset::Eth,
+			Asset::Usdc,
+			ForeignChainAddress::Eth(Default::default()),
+			Default::default(),
+			BROKER,
+			None,
+			0,
+			None,
+			None,
+			None,
+			None,
+		)
+		.unwrap();
+
+		assert_noop!(
+			IngressEgress::update_refund_address(
+				OriginTrait::signed(BROKER),
+				address.try_into().unwrap(),
+				EncodedAddress::Eth([1; 20]),
+			),
+			crate::Error::<Test, ()>::RefundAddressNotUpdatable
+		);
+	});
+}
+
+#[test]
+fn pending_refund_address_update_is_dropped_if_channel_is_reused() {
+	new_test_ext().execute_with(|| {
+		RefundAddressUpdateDelay::<Test, ()>::set(10);
+		let (_, address, ..) = IngressEgress::request_liquidity_deposit_address(
+			ALICE,
+			EthAsset::Eth,
+			0,
+			ForeignChainAddress::Eth(Default::default()),
+		)
+		.unwrap();
+		let deposit_address: H160 = address.try_into().unwrap();
+
+		assert_ok!(IngressEgress::update_refund_address(
+			OriginTrait::signed(ALICE),
+			deposit_address,
+			EncodedAddress::Eth([1; 20]),
+		));
+
+		// The address now belongs to a different channel:
+		DepositChannelLookup::<Test, ()>::mutate(deposit_address, |details| {
+			details.as_mut().unwrap().deposit_channel.channel_id += 100;
+		});
+
+		IngressEgress::on_initialize(System::block_number() + 10);
+		assert!(matches!(
+			DepositChannelLookup::<Test, ()>::get(deposit_address).unwrap().action,
+			ChannelAction::LiquidityProvision { refund_address: Some(address), .. }
+				if address == ForeignChainAddress::Eth(Default::default())
+		));
+	});
+}
+
+#[test]
+fn broker_can_close_deposit_channel() {
+	new_test_ext().execute_with(|| {
+		let (channel_id, address, ..) = IngressEgress::request_swap_deposit_address(
+			EthAsset::Eth,
+			Asset::Usdc,
+			ForeignChainAddress::Eth(Default::default()),
+			Default::default(),
+			BROKER,
+			None,
+			0,
+			None,
+			None,
+			None,
+			None,
+		)
+		.unwrap();
+		let deposit_address: H160 = address.try_into().unwrap();
+		let (current_height, _, recycle_height) = IngressEgress::expiry_and_recycle_block_height();
+		assert!(
+			DepositChannelRecycleQueue::<Test, ()>::get(recycle_height).contains(&deposit_address)
+		);
+
+		// Only the broker that opened the channel can close it:
+		<mocks::account_role_registry::MockAccountRoleRegistry as cf_traits::AccountRoleRegistry<
+			Test,
+		>>::register_as_broker(&ALICE)
+		.unwrap();
+		assert_noop!(
+			IngressEgress::close_deposit_channel(OriginTrait::signed(ALICE), deposit_address),
+			crate::Error::<Test, ()>::NotChannelOwner
+		);
+
+		assert_ok!(IngressEgress::close_deposit_channel(
+			OriginTrait::signed(BROKER),
+			deposit_address
+		));
+
+		// The channel expires immediately and is recycled earlier than originally scheduled:
+		assert_eq!(
+			DepositChannelLookup::<Test, ()>::get(deposit_address).unwrap().expires_at,
+			current_height
+		);
+		let new_recycle_height = IngressEgress::recycle_height_for_expiry(current_height);
+		assert!(new_recycle_height < recycle_height);
+		assert!(!DepositChannelRecycleQueue::<Test, ()>::contains_key(recycle_height));
+		assert!(DepositChannelRecycleQueue::<Test, ()>::get(new_recycle_height)
+			.contains(&deposit_address));
+		assert_eq!(NextRecycleBlockHeight::<Test, ()>::get(), Some(new_recycle_height));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(Event::DepositChannelClosed {
+			deposit_address,
+			channel_id,
+			broker_id: BROKER,
+			expires_at: current_height,
+		}));
+
+		assert_noop!(
+			IngressEgress::close_deposit_channel(OriginTrait::signed(BROKER), deposit_address),
+			crate::Error::<Test, ()>::DepositChannelAlreadyExpired
+		);
+	});
+}
+
+#[test]
+fn governance_can_extend_the_expiry_of_open_channels() {
+	const EXTEND_BY: u64 = 10;
+
+	new_test_ext().execute_with(|| {
+		let (_, address, expires_at, ..) = IngressEgress::request_swap_deposit_address(
+			EthAsset::Eth,
+			Asset::Usdc,
+			ForeignChainAddress::Eth(Default::default()),
+			Default::default(),
+			BROKER,
+			None,
+			0,
+			None,
+			None,
+			None,
+			None,
+		)
+		.unwrap();
+		let deposit_address: H160 = address.try_into().unwrap();
+		let recycle_height = IngressEgress::recycle_height_for_expiry(expires_at);
+
+		assert_noop!(
+			IngressEgress::extend_deposit_channel_expiries(OriginTrait::root(), 0),
+			crate::Error::<Test, ()>::InvalidChannelExpiryExtension
+		);
+		assert_noop!(
+			IngressEgress::extend_deposit_channel_expiries(
+				OriginTrait::root(),
+				DepositChannelLifetime::<Test, ()>::get() + 1
+			),
+			crate::Error::<Test, ()>::InvalidChannelExpiryExtension
+		);
+
+		assert_ok!(IngressEgress::extend_deposit_channel_expiries(OriginTrait::root(), EXTEND_BY));
+
+		// The channel expires and is recycled later than originally scheduled:
+		assert_eq!(
+			DepositChannelLookup::<Test, ()>::get(deposit_address).unwrap().expires_at,
+			expires_at + EXTEND_BY
+		);
+		assert!(!DepositChannelRecycleQueue::<Test, ()>::contains_key(recycle_height));
+		assert!(DepositChannelRecycleQueue::<Test, ()>::get(recycle_height + EXTEND_BY)
+			.contains(&deposit_address));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			Event::DepositChannelExpiriesExtended { extended_by: EXTEND_BY, channels: 1 },
+		));
+
+		// Expired channels are not extended:
+		assert_ok!(IngressEgress::close_deposit_channel(
+			OriginTrait::signed(BROKER),
+			deposit_address
+		));
+		let closed_at = DepositChannelLookup::<Test, ()>::get(deposit_address).unwrap().expires_at;
+		assert_ok!(IngressEgress::extend_deposit_channel_expiries(OriginTrait::root(), EXTEND_BY));
+		assert_eq!(
+			DepositChannelLookup::<Test, ()>::get(deposit_address).unwrap().expires_at,
+			closed_at
+		);
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			Event::DepositChannelExpiriesExtended { extended_by: EXTEND_BY, channels: 0 },
+		));
 	});
 }
 
@@ -2180,6 +4856,7 @@ fn private_and_regular_channel_ids_do_not_overlap() {
 				EthAsset::Eth,
 				ChannelAction::LiquidityProvision { lp_account: 0, refund_address: None },
 				0,
+				ChannelOpenAttempt::New,
 			)
 			.unwrap();
 
@@ -2255,7 +4932,7 @@ fn ignore_change_of_minimum_deposit_if_deposit_is_not_boosted() {
 				None,
 				ChannelAction::LiquidityProvision { lp_account: 0, refund_address: None },
 				0,
-				DepositOrigin::Vault { tx_id: H256::default(), broker_id: Some(BROKER) },
+				DepositOrigin::vault(H256::default(), Some(BROKER), None),
 			)
 			.err(),
 			Some(DepositFailedReason::BelowMinimumDeposit)
@@ -2276,8 +4953,41 @@ fn ignore_change_of_minimum_deposit_if_deposit_is_not_boosted() {
 			None,
 			ChannelAction::LiquidityProvision { lp_account: 0, refund_address: None },
 			0,
-			DepositOrigin::Vault { tx_id: H256::default(), broker_id: Some(BROKER) },
+			DepositOrigin::vault(H256::default(), Some(BROKER), None),
 		)
 		.is_ok());
 	});
 }
+
+#[test]
+fn deposit_failed_reason_codes_are_stable() {
+	// These codes are relied upon by downstream systems and must never change.
+	let reasons = [
+		(DepositFailedReason::BelowMinimumDeposit, 0),
+		(DepositFailedReason::NotEnoughToPayFees, 1),
+		(DepositFailedReason::TransactionRejectedByBroker, 2),
+		(DepositFailedReason::DepositWitnessRejected(DispatchError::Other("")), 3),
+		(DepositFailedReason::InvalidDestinationAddress, 4),
+		(DepositFailedReason::InvalidBrokerFees, 5),
+		(DepositFailedReason::InvalidRefundParameters, 6),
+		(DepositFailedReason::InvalidDcaParameters, 7),
+		(DepositFailedReason::CcmUnsupportedForTargetChain, 8),
+		(DepositFailedReason::CcmInvalidMetadata, 9),
+		(DepositFailedReason::CcmMessageTooLong, 10),
+		(DepositFailedReason::CcmGasBudgetTooHigh, 11),
+		(DepositFailedReason::CcmAdditionalDataTooLong, 12),
+		(DepositFailedReason::BrokerSuspended, 13),
+		(DepositFailedReason::CcmDestinationNotAllowed, 14),
+		(DepositFailedReason::DestinationChainWindingDown, 15),
+	];
+	assert_eq!(reasons.len(), DEPOSIT_FAILED_REASON_CODES.len());
+
+	for ((reason, code), (listed_code, name, _)) in reasons.iter().zip(DEPOSIT_FAILED_REASON_CODES)
+	{
+		assert_eq!(reason.code(), *code);
+		assert_eq!(listed_code, code);
+		// The code is also the index of the variant in encoded events.
+		assert_eq!(reason.encode()[0], *code);
+		assert!(format!("{reason:?}").starts_with(name));
+	}
+}
@@ -2,10 +2,11 @@ use super::*;
 
 use cf_chains::{ChannelRefundParametersDecoded, DepositOriginType, FeeEstimationApi};
 use cf_primitives::{AssetAmount, BasisPoints, PrewitnessedDepositId, SwapRequestId};
-use cf_test_utilities::assert_event_sequence;
+use cf_test_utilities::{assert_event_sequence, assert_events_match};
 use cf_traits::{
 	mocks::{
-		account_role_registry::MockAccountRoleRegistry, tracked_data_provider::TrackedDataProvider,
+		account_role_registry::MockAccountRoleRegistry, deposit_risk_scorer::MockDepositRiskScorer,
+		tracked_data_provider::TrackedDataProvider,
 	},
 	AccountRoleRegistry, BalanceApi, SafeMode, SetSafeMode,
 };
@@ -13,7 +14,12 @@ use frame_support::assert_noop;
 use sp_runtime::Percent;
 use sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet};
 
-use crate::{BoostPoolId, BoostPoolTier, BoostPools, Event, PalletSafeMode};
+use crate::{
+	BoostAmountTolerance, BoostExposure, BoostExposureCaps, BoostFeeRebateSettings,
+	BoostFinalisationTolerance, BoostPoolId, BoostPoolTier, BoostPools, BoostRiskThresholds,
+	BoostedDepositPrewitnessHeights, DepositAction, Event, NetworkFeeDeductionFromBoostPercent,
+	PalletConfigUpdate, PalletSafeMode,
+};
 
 type AccountId = u64;
 
@@ -232,6 +238,7 @@ fn basic_passive_boosting() {
 				boost_fee: POOL_1_FEE + POOL_2_FEE,
 				action: DepositAction::LiquidityProvision { lp_account: LP_ACCOUNT },
 				origin_type: DepositOriginType::DepositChannel,
+				tracking_id: None,
 			}));
 
 			assert_boosted(deposit_address, prewitnessed_deposit_id, [TIER_5_BPS, TIER_10_BPS]);
@@ -265,6 +272,7 @@ fn basic_passive_boosting() {
 				},
 				channel_id: Some(channel_id),
 				origin_type: DepositOriginType::DepositChannel,
+				tracking_id: None,
 			}));
 
 			assert_eq!(get_available_amount(ASSET, TIER_5_BPS), BOOSTER_AMOUNT_1 + POOL_1_FEE);
@@ -516,6 +524,292 @@ fn witnessed_amount_does_not_match_boosted() {
 	});
 }
 
+#[test]
+fn boost_is_finalised_if_witnessed_amount_is_within_tolerance() {
+	new_test_ext().execute_with(|| {
+		const BOOSTER_AMOUNT_1: AssetAmount = 500_000_000;
+		const DEPOSIT_AMOUNT: AssetAmount = 100_000_000;
+		const BOOST_FEE: AssetAmount = DEPOSIT_AMOUNT / 2000;
+		// 10 bps of the deposit amount:
+		const TOLERANCE: AssetAmount = DEPOSIT_AMOUNT / 1000;
+		const DIFFERENCE: AssetAmount = TOLERANCE / 2;
+
+		setup();
+
+		assert_ok!(IngressEgress::add_boost_funds(
+			RuntimeOrigin::signed(BOOSTER_1),
+			EthAsset::Eth,
+			BOOSTER_AMOUNT_1,
+			TIER_5_BPS
+		));
+		BoostFinalisationTolerance::<Test, ()>::put(BoostAmountTolerance::BasisPoints(10));
+
+		// A shortfall is absorbed by the network fee, and boosters are credited in full. The
+		// depositor's credit is left untouched:
+		NetworkFeeDeductionFromBoostPercent::<Test, ()>::put(Percent::from_percent(100));
+		let (_, deposit_address) = request_deposit_address_eth(LP_ACCOUNT, TIER_5_BPS);
+		let deposit_id = prewitness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT);
+		assert_boosted(deposit_address, deposit_id, [TIER_5_BPS]);
+		let lp_balance = get_lp_eth_balance(&LP_ACCOUNT);
+		assert_eq!(lp_balance, DEPOSIT_AMOUNT - BOOST_FEE - INGRESS_FEE);
+
+		witness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT - DIFFERENCE);
+		assert_not_boosted(deposit_address);
+		assert_eq!(get_available_amount(EthAsset::Eth, TIER_5_BPS), BOOSTER_AMOUNT_1);
+		assert_eq!(BoostExposure::<Test, ()>::get(EthAsset::Eth), 0);
+		assert_eq!(get_lp_eth_balance(&LP_ACCOUNT), lp_balance);
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::DepositFinalised {
+				action: DepositAction::BoostersCredited { network_fee_from_boost, .. },
+				..
+			}) if *network_fee_from_boost == BOOST_FEE - DIFFERENCE
+		);
+		// Nothing was paid out to the depositor, so no reconciliation is reported:
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::IngressEgress(Event::BoostedDepositAmountReconciled { .. })
+		)));
+
+		// A surplus is passed on to the depositor:
+		NetworkFeeDeductionFromBoostPercent::<Test, ()>::put(Percent::from_percent(0));
+		let deposit_id = prewitness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT);
+		assert_boosted(deposit_address, deposit_id, [TIER_5_BPS]);
+		let lp_balance = get_lp_eth_balance(&LP_ACCOUNT);
+
+		witness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT + DIFFERENCE);
+		assert_not_boosted(deposit_address);
+		assert_eq!(get_available_amount(EthAsset::Eth, TIER_5_BPS), BOOSTER_AMOUNT_1 + BOOST_FEE);
+		assert_eq!(get_lp_eth_balance(&LP_ACCOUNT), lp_balance + DIFFERENCE);
+		System::assert_has_event(RuntimeEvent::IngressEgress(
+			Event::BoostedDepositAmountReconciled {
+				prewitnessed_deposit_id: deposit_id,
+				asset: EthAsset::Eth,
+				boosted_amount: DEPOSIT_AMOUNT,
+				deposit_amount: DEPOSIT_AMOUNT + DIFFERENCE,
+				surplus_action: DepositAction::LiquidityProvision { lp_account: LP_ACCOUNT },
+			},
+		));
+
+		// Amounts outside of the tolerance are processed as non-boosted deposits:
+		let deposit_id = prewitness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT);
+		witness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT + 2 * TOLERANCE);
+		assert_boosted(deposit_address, deposit_id, [TIER_5_BPS]);
+	});
+}
+
+#[test]
+fn boost_is_not_finalised_if_shortfall_exceeds_network_fee() {
+	new_test_ext().execute_with(|| {
+		const BOOSTER_AMOUNT_1: AssetAmount = 500_000_000;
+		const DEPOSIT_AMOUNT: AssetAmount = 100_000_000;
+		const BOOST_FEE: AssetAmount = DEPOSIT_AMOUNT / 2000;
+		// Within the 10 bps tolerance, but more than the network fee taken from the boost:
+		const NETWORK_FEE: AssetAmount = BOOST_FEE / 2;
+		const DIFFERENCE: AssetAmount = NETWORK_FEE + 1;
+
+		setup();
+
+		assert_ok!(IngressEgress::add_boost_funds(
+			RuntimeOrigin::signed(BOOSTER_1),
+			EthAsset::Eth,
+			BOOSTER_AMOUNT_1,
+			TIER_5_BPS
+		));
+		BoostFinalisationTolerance::<Test, ()>::put(BoostAmountTolerance::BasisPoints(10));
+		NetworkFeeDeductionFromBoostPercent::<Test, ()>::put(Percent::from_percent(50));
+
+		let (_, deposit_address) = request_deposit_address_eth(LP_ACCOUNT, TIER_5_BPS);
+		let deposit_id = prewitness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT);
+		assert_boosted(deposit_address, deposit_id, [TIER_5_BPS]);
+		let lp_balance = get_lp_eth_balance(&LP_ACCOUNT);
+
+		// Boosters can't be credited in full, so the deposit is processed as non-boosted:
+		witness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT - DIFFERENCE);
+		assert_boosted(deposit_address, deposit_id, [TIER_5_BPS]);
+		assert_eq!(
+			get_available_amount(EthAsset::Eth, TIER_5_BPS),
+			BOOSTER_AMOUNT_1 - (DEPOSIT_AMOUNT - BOOST_FEE)
+		);
+		assert_eq!(
+			get_lp_eth_balance(&LP_ACCOUNT),
+			lp_balance + DEPOSIT_AMOUNT - DIFFERENCE - INGRESS_FEE
+		);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::IngressEgress(Event::BoostedDepositAmountReconciled { .. })
+		)));
+	});
+}
+
+#[test]
+fn boost_fee_is_rebated_if_finalised_within_fast_window() {
+	new_test_ext().execute_with(|| {
+		const BOOSTER_AMOUNT_1: AssetAmount = 500_000_000;
+		const DEPOSIT_AMOUNT: AssetAmount = 100_000_000;
+		const BOOST_FEE: AssetAmount = DEPOSIT_AMOUNT / 2000;
+		const REBATE: AssetAmount = BOOST_FEE / 2;
+		const FAST_WINDOW: u64 = 5;
+
+		let deposit = |deposit_address| DepositWitness::<Ethereum> {
+			deposit_address,
+			asset: EthAsset::Eth,
+			amount: DEPOSIT_AMOUNT,
+			deposit_details: Default::default(),
+		};
+
+		setup();
+
+		assert_ok!(IngressEgress::add_boost_funds(
+			RuntimeOrigin::signed(BOOSTER_1),
+			EthAsset::Eth,
+			BOOSTER_AMOUNT_1,
+			TIER_5_BPS
+		));
+		assert_ok!(IngressEgress::update_pallet_config(
+			RuntimeOrigin::root(),
+			vec![PalletConfigUpdate::SetBoostFeeRebate {
+				settings: Some(BoostFeeRebateSettings {
+					fast_window: FAST_WINDOW,
+					rebate: Percent::from_percent(50),
+				}),
+			}]
+			.try_into()
+			.unwrap()
+		));
+
+		// Finalised within the fast window, so half of the fee is rebated:
+		let (_, deposit_address) = request_deposit_address_eth(LP_ACCOUNT, TIER_5_BPS);
+		assert_ok!(IngressEgress::process_channel_deposit_prewitness(deposit(deposit_address), 10));
+		let deposit_id = PrewitnessedDepositIdCounter::<Test, _>::get();
+		assert_boosted(deposit_address, deposit_id, [TIER_5_BPS]);
+		let lp_balance = get_lp_eth_balance(&LP_ACCOUNT);
+
+		assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+			&deposit(deposit_address),
+			10 + FAST_WINDOW,
+		));
+		assert_not_boosted(deposit_address);
+		assert_eq!(
+			get_available_amount(EthAsset::Eth, TIER_5_BPS),
+			BOOSTER_AMOUNT_1 + BOOST_FEE - REBATE
+		);
+		assert_eq!(get_lp_eth_balance(&LP_ACCOUNT), lp_balance + REBATE);
+		assert!(BoostedDepositPrewitnessHeights::<Test, ()>::get(deposit_id).is_none());
+		System::assert_has_event(RuntimeEvent::IngressEgress(Event::BoostFeeRebated {
+			prewitnessed_deposit_id: deposit_id,
+			asset: EthAsset::Eth,
+			prewitness_block_height: 10,
+			finalisation_block_height: 10 + FAST_WINDOW,
+			amount: REBATE,
+			action: DepositAction::LiquidityProvision { lp_account: LP_ACCOUNT },
+		}));
+
+		// Finalised after the fast window, so boosters keep the full fee:
+		assert_ok!(IngressEgress::process_channel_deposit_prewitness(deposit(deposit_address), 20));
+		let deposit_id = PrewitnessedDepositIdCounter::<Test, _>::get();
+		assert_boosted(deposit_address, deposit_id, [TIER_5_BPS]);
+		let lp_balance = get_lp_eth_balance(&LP_ACCOUNT);
+
+		assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+			&deposit(deposit_address),
+			21 + FAST_WINDOW,
+		));
+		assert_not_boosted(deposit_address);
+		assert_eq!(
+			get_available_amount(EthAsset::Eth, TIER_5_BPS),
+			BOOSTER_AMOUNT_1 + 2 * BOOST_FEE - REBATE
+		);
+		assert_eq!(get_lp_eth_balance(&LP_ACCOUNT), lp_balance);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::IngressEgress(Event::BoostFeeRebated { prewitnessed_deposit_id, .. })
+				if prewitnessed_deposit_id == deposit_id
+		)));
+	});
+}
+
+#[test]
+fn boost_fee_rebate_and_surplus_are_paid_out_together() {
+	new_test_ext().execute_with(|| {
+		const BOOSTER_AMOUNT_1: AssetAmount = 500_000_000;
+		const DEPOSIT_AMOUNT: AssetAmount = 100_000_000;
+		const BOOST_FEE: AssetAmount = DEPOSIT_AMOUNT / 2000;
+		const REBATE: AssetAmount = BOOST_FEE / 2;
+		const SURPLUS: AssetAmount = DEPOSIT_AMOUNT / 2000;
+
+		setup();
+
+		assert_ok!(IngressEgress::add_boost_funds(
+			RuntimeOrigin::signed(BOOSTER_1),
+			EthAsset::Eth,
+			BOOSTER_AMOUNT_1,
+			TIER_5_BPS
+		));
+		BoostFinalisationTolerance::<Test, ()>::put(BoostAmountTolerance::BasisPoints(10));
+		assert_ok!(IngressEgress::update_pallet_config(
+			RuntimeOrigin::root(),
+			vec![PalletConfigUpdate::SetBoostFeeRebate {
+				settings: Some(BoostFeeRebateSettings {
+					fast_window: 5,
+					rebate: Percent::from_percent(50),
+				}),
+			}]
+			.try_into()
+			.unwrap()
+		));
+
+		let (_, deposit_address, _, _) = IngressEgress::open_channel(
+			&BROKER,
+			EthAsset::Eth,
+			ChannelAction::Swap {
+				destination_asset: Asset::Flip,
+				destination_address: ForeignChainAddress::Eth([1; 20].into()),
+				broker_fees: Default::default(),
+				channel_metadata: None,
+				refund_params: None,
+				dca_params: None,
+				tracking_id: None,
+				egress_memo: None,
+			},
+			TIER_5_BPS,
+			ChannelOpenAttempt::New,
+		)
+		.unwrap();
+
+		let deposit_id = prewitness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT);
+		assert_eq!(MockSwapRequestHandler::<Test>::get_swap_requests().len(), 1);
+
+		witness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT + SURPLUS);
+		assert_not_boosted(deposit_address);
+
+		// A single swap is requested for both the rebate and the surplus:
+		let swap_requests = MockSwapRequestHandler::<Test>::get_swap_requests();
+		assert_eq!(swap_requests.len(), 2);
+		assert_eq!(swap_requests[1].input_amount, REBATE + SURPLUS);
+
+		let action = assert_events_match!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::BoostFeeRebated {
+				prewitnessed_deposit_id,
+				amount: REBATE,
+				action,
+				..
+			}) if prewitnessed_deposit_id == deposit_id => action
+		);
+		assert!(matches!(action, DepositAction::Swap { .. }));
+		System::assert_has_event(RuntimeEvent::IngressEgress(
+			Event::BoostedDepositAmountReconciled {
+				prewitnessed_deposit_id: deposit_id,
+				asset: EthAsset::Eth,
+				boosted_amount: DEPOSIT_AMOUNT,
+				deposit_amount: DEPOSIT_AMOUNT + SURPLUS,
+				surplus_action: action,
+			},
+		));
+	});
+}
+
 #[test]
 fn double_prewitness_due_to_reorg() {
 	new_test_ext().execute_with(|| {
@@ -696,6 +990,110 @@ fn insufficient_funds_for_boost() {
 	});
 }
 
+#[test]
+fn risky_deposits_are_only_boosted_by_tiers_that_accept_the_risk() {
+	new_test_ext().execute_with(|| {
+		const BOOSTER_AMOUNT: AssetAmount = 500_000_000;
+		const DEPOSIT_AMOUNT: AssetAmount = 100_000_000;
+
+		setup();
+
+		for (booster, tier) in [(BOOSTER_1, TIER_5_BPS), (BOOSTER_2, TIER_10_BPS)] {
+			assert_ok!(IngressEgress::add_boost_funds(
+				RuntimeOrigin::signed(booster),
+				EthAsset::Eth,
+				BOOSTER_AMOUNT,
+				tier
+			));
+		}
+
+		BoostRiskThresholds::<Test, ()>::insert(TIER_5_BPS, 1_000);
+		BoostRiskThresholds::<Test, ()>::insert(TIER_10_BPS, 5_000);
+
+		// The deposit is too risky for the 5 bps tier, so it is boosted by the 10 bps tier only:
+		{
+			MockDepositRiskScorer::set_risk_score(2_000);
+
+			let (_, deposit_address) = request_deposit_address_eth(LP_ACCOUNT, TIER_10_BPS);
+			let deposit_id = prewitness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT);
+
+			assert_boosted(deposit_address, deposit_id, [TIER_10_BPS]);
+			assert_eq!(get_available_amount(EthAsset::Eth, TIER_5_BPS), BOOSTER_AMOUNT);
+		}
+
+		// The deposit is too risky for all tiers, so it is not boosted even though a fee is
+		// offered:
+		{
+			MockDepositRiskScorer::set_risk_score(6_000);
+
+			let (channel_id, deposit_address) =
+				request_deposit_address_eth(LP_ACCOUNT, TIER_30_BPS);
+			System::reset_events();
+			let deposit_id = prewitness_deposit(deposit_address, EthAsset::Eth, DEPOSIT_AMOUNT);
+
+			assert_not_boosted(deposit_address);
+			System::assert_last_event(RuntimeEvent::IngressEgress(Event::BoostSkippedDueToRisk {
+				prewitnessed_deposit_id: deposit_id,
+				asset: EthAsset::Eth,
+				amount_attempted: DEPOSIT_AMOUNT,
+				risk_score: 6_000,
+				channel_id: Some(channel_id),
+				origin_type: DepositOriginType::DepositChannel,
+			}));
+		}
+	});
+}
+
+#[test]
+fn boosts_are_skipped_once_exposure_cap_is_reached() {
+	new_test_ext().execute_with(|| {
+		const DEPOSIT_AMOUNT: AssetAmount = 100_000_000;
+		const CAP: AssetAmount = 150_000_000;
+
+		setup();
+
+		assert_ok!(IngressEgress::add_boost_funds(
+			RuntimeOrigin::signed(BOOSTER_1),
+			EthAsset::Eth,
+			500_000_000,
+			TIER_5_BPS
+		));
+		BoostExposureCaps::<Test, ()>::insert(EthAsset::Eth, CAP);
+
+		// The first deposit is within the cap:
+		let (_, deposit_address_1) = request_deposit_address_eth(LP_ACCOUNT, TIER_5_BPS);
+		let deposit_id = prewitness_deposit(deposit_address_1, EthAsset::Eth, DEPOSIT_AMOUNT);
+		assert_boosted(deposit_address_1, deposit_id, [TIER_5_BPS]);
+		assert_eq!(BoostExposure::<Test, ()>::get(EthAsset::Eth), DEPOSIT_AMOUNT);
+
+		// The second deposit would exceed the cap, so it isn't boosted:
+		let (channel_id, deposit_address_2) = request_deposit_address_eth(LP_ACCOUNT, TIER_5_BPS);
+		System::reset_events();
+		let deposit_id = prewitness_deposit(deposit_address_2, EthAsset::Eth, DEPOSIT_AMOUNT);
+		assert_not_boosted(deposit_address_2);
+		System::assert_last_event(RuntimeEvent::IngressEgress(
+			Event::BoostSkippedDueToExposureCap {
+				prewitnessed_deposit_id: deposit_id,
+				asset: EthAsset::Eth,
+				amount_attempted: DEPOSIT_AMOUNT,
+				current_exposure: DEPOSIT_AMOUNT,
+				cap: CAP,
+				channel_id: Some(channel_id),
+				origin_type: DepositOriginType::DepositChannel,
+			},
+		));
+
+		// Finalising the boosted deposit frees up exposure for new boosts:
+		witness_deposit(deposit_address_1, EthAsset::Eth, DEPOSIT_AMOUNT);
+		assert_eq!(BoostExposure::<Test, ()>::get(EthAsset::Eth), 0);
+
+		let (_, deposit_address_3) = request_deposit_address_eth(LP_ACCOUNT, TIER_5_BPS);
+		let deposit_id = prewitness_deposit(deposit_address_3, EthAsset::Eth, DEPOSIT_AMOUNT);
+		assert_boosted(deposit_address_3, deposit_id, [TIER_5_BPS]);
+		assert_eq!(BoostExposure::<Test, ()>::get(EthAsset::Eth), DEPOSIT_AMOUNT);
+	});
+}
+
 #[test]
 fn lost_funds_are_acknowledged_by_boost_pool() {
 	new_test_ext().execute_with(|| {
@@ -1018,7 +1416,7 @@ fn failed_prewitness_does_not_discard_remaining_deposits_in_a_batch() {
 			&ALICE,
 			EthAsset::Eth,
 			ChannelAction::LiquidityProvision { lp_account: 0, refund_address: None },
-			TIER_5_BPS,
+			TIER_5_BPS, ChannelOpenAttempt::New,
 		)
 		.unwrap();
 
@@ -1055,8 +1453,6 @@ fn taking_network_fee_from_boost_fee() {
 	// we get a non-zero amount of the input asset, and we schedule a swap to FLIP as
 	// network fee.
 
-	use crate::NetworkFeeDeductionFromBoostPercent;
-
 	new_test_ext().execute_with(|| {
 		const ASSET: EthAsset = EthAsset::Eth;
 		const BOOSTER_AMOUNT: AssetAmount = 1_000_000;
@@ -1127,7 +1523,7 @@ fn taking_network_fee_from_boost_fee() {
 					input_asset: ASSET.into(),
 					output_asset: Asset::Flip,
 					input_amount: 10,
-					swap_type: SwapRequestType::NetworkFee,
+					swap_type: SwapRequestType::BoostNetworkFee,
 					broker_fees: Default::default(),
 					origin: SwapOrigin::Internal
 				}]
@@ -1150,7 +1546,10 @@ fn taking_network_fee_from_boost_fee() {
 
 mod vault_swaps {
 
-	use crate::BoostedVaultTransactions;
+	use crate::{
+		BoostedVaultTransactions, FullyWitnessedThisBlock, PendingPrewitness, PendingPrewitnesses,
+		PrewitnessOrder, PrewitnessProcessingOrder,
+	};
 
 	use super::*;
 
@@ -1206,6 +1605,8 @@ mod vault_swaps {
 				}),
 				dca_params: None,
 				boost_fee: 5,
+				tracking_id: None,
+				verified_sender: None,
 			};
 
 			// Prewitnessing a deposit for the first time should result in a boost:
@@ -1230,12 +1631,14 @@ mod vault_swaps {
 						input_amount: DEPOSIT_AMOUNT - BOOST_FEE,
 						swap_type: SwapRequestType::Regular {
 							output_address,
-							ccm_deposit_metadata: None
+							ccm_deposit_metadata: None,
+							egress_memo: None,
 						},
 						broker_fees: bounded_vec![Beneficiary { account: BROKER, bps: 5 }],
 						origin: SwapOrigin::Vault {
 							tx_id: TransactionInIdForAnyChain::Evm(tx_id),
-							broker_id: Some(BROKER)
+							broker_id: Some(BROKER),
+							verified_sender: None,
 						},
 					},]
 				);
@@ -1323,4 +1726,206 @@ mod vault_swaps {
 			}
 		});
 	}
+
+	#[test]
+	fn vault_swap_with_invalid_broker_fees_is_boosted_as_refund() {
+		new_test_ext().execute_with(|| {
+			const DEPOSIT_AMOUNT: AssetAmount = 100_000_000;
+			const PREWITNESS_DEPOSIT_ID: PrewitnessedDepositId = 1;
+
+			setup();
+
+			assert_ok!(IngressEgress::add_boost_funds(
+				RuntimeOrigin::signed(BOOSTER_1),
+				EthAsset::Eth,
+				500_000_000,
+				TIER_5_BPS
+			));
+
+			let refund_address = ForeignChainAddress::Eth([2; 20].into());
+			let tx_id = [9u8; 32].into();
+
+			let deposit = VaultDepositWitness {
+				input_asset: EthAsset::Eth,
+				deposit_address: Some([1; 20].into()),
+				channel_id: Some(1),
+				deposit_amount: DEPOSIT_AMOUNT,
+				deposit_details: Default::default(),
+				output_asset: Asset::Flip,
+				destination_address: MockAddressConverter::to_encoded_address(
+					ForeignChainAddress::Eth([1; 20].into()),
+				),
+				deposit_metadata: None,
+				tx_id,
+				// Broker fees above the limit make the swap invalid:
+				broker_fee: Some(Beneficiary { account: BROKER, bps: 2000 }),
+				affiliate_fees: Default::default(),
+				refund_params: Some(ChannelRefundParametersDecoded {
+					retry_duration: 2,
+					refund_address: refund_address.clone(),
+					min_price: Default::default(),
+				}),
+				dca_params: None,
+				boost_fee: 5,
+				tracking_id: None,
+				verified_sender: None,
+			};
+
+			// Without a refund address, the deposit can't be refunded so it is not boosted:
+			IngressEgress::process_vault_swap_request_prewitness(
+				10,
+				VaultDepositWitness { refund_params: None, ..deposit.clone() },
+			);
+			assert_eq!(PrewitnessedDepositIdCounter::<Test, _>::get(), 0);
+
+			// With a refund address, the deposit is boosted and refunded straight away:
+			IngressEgress::process_vault_swap_request_prewitness(10, deposit.clone());
+			assert_eq!(PrewitnessedDepositIdCounter::<Test, _>::get(), PREWITNESS_DEPOSIT_ID);
+			assert!(MockSwapRequestHandler::<Test>::get_swap_requests().is_empty());
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::IngressEgress(Event::DepositBoosted {
+					prewitnessed_deposit_id: PREWITNESS_DEPOSIT_ID,
+					action: DepositAction::Refund {
+						reason: DepositFailedReason::InvalidBrokerFees,
+						egress_id: Some(_),
+						..
+					},
+					..
+				})
+			);
+			assert!(ScheduledEgressFetchOrTransfer::<Test, ()>::get().iter().any(
+				|egress| matches!(
+					egress,
+					FetchOrTransfer::Transfer { destination_address, .. }
+						if ForeignChainAddress::Eth(*destination_address) == refund_address
+				)
+			));
+
+			// Fully witnessing the deposit credits the boosters:
+			IngressEgress::process_vault_swap_request_full_witness(10, deposit);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::IngressEgress(Event::DepositFinalised {
+					action: DepositAction::BoostersCredited {
+						prewitnessed_deposit_id: PREWITNESS_DEPOSIT_ID,
+						..
+					},
+					..
+				})
+			);
+			assert!(!BoostedVaultTransactions::<Test, ()>::contains_key(tx_id));
+		});
+	}
+
+	fn boostable_vault_deposit(tx_id: H256) -> VaultDepositWitness<Test, ()> {
+		VaultDepositWitness {
+			input_asset: EthAsset::Eth,
+			deposit_address: Some([1; 20].into()),
+			channel_id: Some(1),
+			deposit_amount: 100_000_000,
+			deposit_details: Default::default(),
+			output_asset: Asset::Flip,
+			destination_address: MockAddressConverter::to_encoded_address(
+				ForeignChainAddress::Eth([1; 20].into()),
+			),
+			deposit_metadata: None,
+			tx_id,
+			broker_fee: Some(Beneficiary { account: BROKER, bps: 5 }),
+			affiliate_fees: Default::default(),
+			refund_params: Some(ChannelRefundParametersDecoded {
+				retry_duration: 2,
+				refund_address: ForeignChainAddress::Eth([2; 20].into()),
+				min_price: Default::default(),
+			}),
+			dca_params: None,
+			boost_fee: 5,
+			tracking_id: None,
+			verified_sender: None,
+		}
+	}
+
+	fn setup_with_boost_funds() {
+		setup();
+		assert_ok!(IngressEgress::add_boost_funds(
+			RuntimeOrigin::signed(BOOSTER_1),
+			EthAsset::Eth,
+			500_000_000,
+			TIER_5_BPS
+		));
+	}
+
+	#[test]
+	fn deferred_prewitness_is_skipped_if_fully_witnessed_in_same_block() {
+		new_test_ext().execute_with(|| {
+			setup_with_boost_funds();
+			PrewitnessOrder::<Test, ()>::set(PrewitnessProcessingOrder::EndOfBlock);
+
+			let tx_id = H256::repeat_byte(9);
+			let deposit = boostable_vault_deposit(tx_id);
+
+			IngressEgress::prewitness_or_defer(PendingPrewitness::Vault {
+				vault_witness: Box::new(deposit.clone()),
+				block_height: 10,
+			});
+			assert_eq!(PrewitnessedDepositIdCounter::<Test, _>::get(), 0);
+
+			IngressEgress::process_vault_swap_request_full_witness(10, deposit);
+			IngressEgress::on_finalize(1);
+
+			// The deposit is swapped once, without a boost fee:
+			assert_eq!(PrewitnessedDepositIdCounter::<Test, _>::get(), 0);
+			assert!(!BoostedVaultTransactions::<Test, ()>::contains_key(tx_id));
+			assert_eq!(get_available_amount(EthAsset::Eth, TIER_5_BPS), 500_000_000);
+			assert_eq!(MockSwapRequestHandler::<Test>::get_swap_requests().len(), 1);
+			assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+				Event::BoostSkippedDueToFullWitness { tx_id, block_height: 10 },
+			));
+			assert!(PendingPrewitnesses::<Test, ()>::get().is_empty());
+			assert!(FullyWitnessedThisBlock::<Test, ()>::get().is_empty());
+		});
+	}
+
+	#[test]
+	fn prewitness_after_full_witness_in_same_block_is_ignored() {
+		new_test_ext().execute_with(|| {
+			setup_with_boost_funds();
+
+			let tx_id = H256::repeat_byte(9);
+			let deposit = boostable_vault_deposit(tx_id);
+
+			IngressEgress::process_vault_swap_request_full_witness(10, deposit.clone());
+			IngressEgress::prewitness_or_defer(PendingPrewitness::Vault {
+				vault_witness: Box::new(deposit),
+				block_height: 10,
+			});
+
+			assert_eq!(PrewitnessedDepositIdCounter::<Test, _>::get(), 0);
+			assert_eq!(MockSwapRequestHandler::<Test>::get_swap_requests().len(), 1);
+			assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+				Event::BoostSkippedDueToFullWitness { tx_id, block_height: 10 },
+			));
+		});
+	}
+
+	#[test]
+	fn deferred_prewitness_is_boosted_at_end_of_block() {
+		new_test_ext().execute_with(|| {
+			setup_with_boost_funds();
+			PrewitnessOrder::<Test, ()>::set(PrewitnessProcessingOrder::EndOfBlock);
+
+			let tx_id = H256::repeat_byte(9);
+			IngressEgress::prewitness_or_defer(PendingPrewitness::Vault {
+				vault_witness: Box::new(boostable_vault_deposit(tx_id)),
+				block_height: 10,
+			});
+			assert!(!BoostedVaultTransactions::<Test, ()>::contains_key(tx_id));
+
+			IngressEgress::on_finalize(1);
+
+			assert_eq!(PrewitnessedDepositIdCounter::<Test, _>::get(), 1);
+			assert!(BoostedVaultTransactions::<Test, ()>::contains_key(tx_id));
+			assert!(PendingPrewitnesses::<Test, ()>::get().is_empty());
+		});
+	}
 }
@@ -0,0 +1,151 @@
+use crate::{
+	mock_btc::*, tests::ALICE, DepositWitness, Event, ProcessedDeposits,
+	ProcessedDepositsQueueRange, PROCESSED_DEPOSIT_RETENTION_BLOCKS,
+};
+
+use cf_chains::{
+	btc::{deposit_address::DepositAddress, Hash, ScriptPubkey, Utxo, UtxoId},
+	DepositDetailsToTransactionInId, ForeignChainAddress,
+};
+use cf_primitives::chains::assets::btc;
+use cf_traits::{BalanceApi, DepositApi};
+use frame_support::{assert_ok, traits::Hooks, weights::Weight};
+
+const DEPOSIT_AMOUNT: u64 = 1_000;
+const BLOCK_HEIGHT: u64 = 100;
+
+fn open_lp_channel() -> <Bitcoin as Chain>::ChainAccount {
+	let (_, deposit_address, ..) = IngressEgress::request_liquidity_deposit_address(
+		ALICE,
+		btc::Asset::Btc,
+		0,
+		ForeignChainAddress::Btc(ScriptPubkey::P2SH([0; 20])),
+	)
+	.unwrap();
+	deposit_address.try_into().unwrap()
+}
+
+fn utxo_deposit(
+	deposit_address: &<Bitcoin as Chain>::ChainAccount,
+	tx_id: Hash,
+	vout: u32,
+) -> DepositWitness<Bitcoin> {
+	DepositWitness {
+		deposit_address: deposit_address.clone(),
+		asset: btc::Asset::Btc,
+		amount: DEPOSIT_AMOUNT,
+		deposit_details: Utxo {
+			amount: DEPOSIT_AMOUNT,
+			id: UtxoId { tx_id, vout },
+			deposit_address: DepositAddress { pubkey_x: [0; 32], script_path: None },
+		},
+	}
+}
+
+#[test]
+fn duplicate_deposits_are_not_credited_twice() {
+	new_test_ext().execute_with(|| {
+		let deposit_address = open_lp_channel();
+		let deposit_witness = utxo_deposit(&deposit_address, Hash::random(), 0);
+
+		assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+			&deposit_witness,
+			BLOCK_HEIGHT
+		));
+		let balance_after_deposit =
+			<Test as crate::Config>::Balance::get_balance(&ALICE, btc::Asset::Btc.into());
+		assert!(balance_after_deposit > 0);
+
+		// The same deposit is witnessed again, e.g. due to a witnesser bug:
+		assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+			&deposit_witness,
+			BLOCK_HEIGHT
+		));
+		assert_eq!(
+			<Test as crate::Config>::Balance::get_balance(&ALICE, btc::Asset::Btc.into()),
+			balance_after_deposit
+		);
+		System::assert_last_event(RuntimeEvent::IngressEgress(Event::DuplicateDepositIgnored {
+			deposit_address: deposit_address.clone(),
+			asset: btc::Asset::Btc,
+			amount: DEPOSIT_AMOUNT,
+			deposit_details: deposit_witness.deposit_details.clone(),
+			block_height: BLOCK_HEIGHT,
+		}));
+
+		// The record of the deposit is eventually removed:
+		let key = (
+			deposit_address,
+			deposit_witness.deposit_details.unique_deposit_id().unwrap(),
+			BLOCK_HEIGHT,
+		);
+		IngressEgress::on_idle(
+			System::block_number() + PROCESSED_DEPOSIT_RETENTION_BLOCKS as u64 - 1,
+			Weight::MAX,
+		);
+		assert!(ProcessedDeposits::<Test, ()>::contains_key(&key));
+		IngressEgress::on_idle(
+			System::block_number() + PROCESSED_DEPOSIT_RETENTION_BLOCKS as u64,
+			Weight::MAX,
+		);
+		assert!(!ProcessedDeposits::<Test, ()>::contains_key(&key));
+	});
+}
+
+#[test]
+fn outputs_of_the_same_transaction_are_distinct_deposits() {
+	new_test_ext().execute_with(|| {
+		let deposit_address = open_lp_channel();
+		let tx_id = Hash::random();
+
+		assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+			&utxo_deposit(&deposit_address, tx_id, 0),
+			BLOCK_HEIGHT
+		));
+		let balance_after_first_deposit =
+			<Test as crate::Config>::Balance::get_balance(&ALICE, btc::Asset::Btc.into());
+
+		assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+			&utxo_deposit(&deposit_address, tx_id, 1),
+			BLOCK_HEIGHT
+		));
+		assert!(
+			<Test as crate::Config>::Balance::get_balance(&ALICE, btc::Asset::Btc.into()) >
+				balance_after_first_deposit
+		);
+	});
+}
+
+#[test]
+fn expiry_of_processed_deposits_is_bounded_by_weight() {
+	new_test_ext().execute_with(|| {
+		let deposit_address = open_lp_channel();
+		for vout in 0..3 {
+			assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+				&utxo_deposit(&deposit_address, Hash::random(), vout),
+				BLOCK_HEIGHT
+			));
+		}
+		assert_eq!(ProcessedDepositsQueueRange::<Test, ()>::get(), (0, 3));
+
+		let expiry_block = System::block_number() + PROCESSED_DEPOSIT_RETENTION_BLOCKS as u64;
+		let db_weight = frame_support::weights::constants::ParityDbWeight::get();
+
+		// Only enough weight to expire a single entry:
+		IngressEgress::expire_processed_deposits(
+			&mut Weight::zero(),
+			db_weight.reads_writes(1, 1).saturating_add(db_weight.reads_writes(1, 2)),
+			expiry_block,
+		);
+		assert_eq!(ProcessedDepositsQueueRange::<Test, ()>::get(), (1, 3));
+
+		// The remaining entries are expired later, even if their expiry block has passed:
+		IngressEgress::expire_processed_deposits(
+			&mut Weight::zero(),
+			Weight::MAX,
+			expiry_block + 1,
+		);
+		assert_eq!(ProcessedDepositsQueueRange::<Test, ()>::get(), (3, 3));
+		assert_eq!(ProcessedDeposits::<Test, ()>::iter().count(), 0);
+	});
+}
@@ -1,9 +1,10 @@
 use crate::{
 	mock_btc::*,
 	tests::{ALICE, BROKER},
-	BoostPoolId, DepositChannelLookup, DepositFailedDetails, DepositFailedReason, DepositWitness,
-	Event, ReportExpiresAt, ScheduledTransactionsForRejection, TransactionPrewitnessedStatus,
-	TransactionRejectionDetails, TransactionsMarkedForRejection, MARKED_TX_EXPIRATION_BLOCKS,
+	BoostPoolId, BoostedSwapRequests, DepositChannelLookup, DepositFailedDetails,
+	DepositFailedReason, DepositWitness, Event, ProtocolRefundTransactions, ReportExpiresAt,
+	ScheduledTransactionsForRejection, TransactionPrewitnessedStatus, TransactionRejectionDetails,
+	TransactionsMarkedForRejection, MARKED_TX_EXPIRATION_BLOCKS,
 };
 
 use frame_support::{
@@ -14,12 +15,15 @@ use frame_support::{
 
 use cf_chains::{
 	btc::{deposit_address::DepositAddress, Hash, ScriptPubkey, UtxoId},
-	ForeignChainAddress,
+	BoundedDepositDetails, ForeignChainAddress,
 };
 
 use cf_traits::{
-	mocks::account_role_registry::MockAccountRoleRegistry, AccountRoleRegistry, BalanceApi,
-	DepositApi,
+	mocks::{
+		account_role_registry::MockAccountRoleRegistry, api_call::BITCOIN_REJECTION_REFUND_TX_ID,
+		chain_tracking::ChainTracker,
+	},
+	AccountRoleRegistry, BalanceApi, DepositApi, MAX_REJECTION_REQUESTS_EXPIRED_ON_DEREGISTRATION,
 };
 
 use cf_primitives::{chains::assets::btc, Beneficiaries, ChannelId};
@@ -91,6 +95,8 @@ mod helpers {
 			10,
 			None,
 			None,
+			None,
+			None,
 		)
 		.unwrap();
 
@@ -203,6 +209,50 @@ fn finalize_boosted_tx_if_marked_after_prewitness() {
 	});
 }
 
+#[test]
+fn boosted_swap_is_flagged_if_marked_before_completion() {
+	new_test_ext().execute_with(|| {
+		let tx_id = Hash::random();
+		let deposit_details = helpers::generate_btc_deposit(tx_id);
+
+		assert_ok!(<MockAccountRoleRegistry as AccountRoleRegistry<Test>>::register_as_broker(
+			&BROKER,
+		));
+
+		let address: <Bitcoin as Chain>::ChainAccount =
+			helpers::setup_boost_swap().try_into().unwrap();
+
+		assert_ok!(IngressEgress::process_channel_deposit_prewitness(
+			DepositWitness {
+				deposit_address: address,
+				asset: btc::Asset::Btc,
+				amount: DEFAULT_DEPOSIT_AMOUNT,
+				deposit_details,
+			},
+			10,
+		));
+
+		let (swap_request_id, tracked_deposit) =
+			BoostedSwapRequests::<Test, ()>::iter().next().expect("boosted swap is tracked");
+		assert_eq!(tracked_deposit, (BROKER, tx_id));
+
+		// Until the deposit is marked for rejection, the swap output is sent as usual.
+		assert!(!IngressEgress::take_swap_request_rejection(swap_request_id));
+		assert!(!BoostedSwapRequests::<Test, ()>::contains_key(swap_request_id));
+
+		// Track the swap request again, as if it had not completed yet.
+		BoostedSwapRequests::<Test, ()>::insert(swap_request_id, (BROKER, tx_id));
+		assert_ok!(IngressEgress::mark_transaction_for_rejection(
+			OriginTrait::signed(BROKER),
+			tx_id,
+		));
+
+		assert!(IngressEgress::take_swap_request_rejection(swap_request_id));
+		assert!(!BoostedSwapRequests::<Test, ()>::contains_key(swap_request_id));
+		assert!(!TransactionsMarkedForRejection::<Test, ()>::contains_key(BROKER, tx_id));
+	});
+}
+
 #[test]
 fn reject_tx_if_marked_before_prewitness() {
 	new_test_ext().execute_with(|| {
@@ -333,6 +383,55 @@ fn do_not_expire_marked_transactions_if_prewitnessed() {
 	});
 }
 
+#[test]
+fn unseen_rejection_requests_are_expired_on_broker_deregistration() {
+	new_test_ext().execute_with(|| {
+		let unseen = Hash::random();
+		let prewitnessed = Hash::random();
+
+		TransactionsMarkedForRejection::<Test, ()>::insert(
+			BROKER,
+			unseen,
+			TransactionPrewitnessedStatus::Unseen,
+		);
+		TransactionsMarkedForRejection::<Test, ()>::insert(
+			BROKER,
+			prewitnessed,
+			TransactionPrewitnessedStatus::Prewitnessed,
+		);
+
+		IngressEgress::expire_rejection_requests(&BROKER);
+
+		assert!(!TransactionsMarkedForRejection::<Test, ()>::contains_key(BROKER, unseen));
+		assert!(TransactionsMarkedForRejection::<Test, ()>::contains_key(BROKER, prewitnessed));
+		assert_has_event::<Test>(RuntimeEvent::IngressEgress(
+			Event::TransactionRejectionRequestExpired { account_id: BROKER, tx_id: unseen },
+		));
+	});
+}
+
+#[test]
+fn rejection_requests_expired_on_broker_deregistration_are_bounded() {
+	new_test_ext().execute_with(|| {
+		const EXCESS_REQUESTS: usize = 5;
+		for _ in 0..MAX_REJECTION_REQUESTS_EXPIRED_ON_DEREGISTRATION as usize + EXCESS_REQUESTS {
+			TransactionsMarkedForRejection::<Test, ()>::insert(
+				BROKER,
+				Hash::random(),
+				TransactionPrewitnessedStatus::Unseen,
+			);
+		}
+
+		IngressEgress::expire_rejection_requests(&BROKER);
+
+		// The remaining requests expire at their regular expiry block.
+		assert_eq!(
+			TransactionsMarkedForRejection::<Test, ()>::iter_prefix(BROKER).count(),
+			EXCESS_REQUESTS
+		);
+	});
+}
+
 #[test]
 fn can_not_report_transaction_after_witnessing() {
 	new_test_ext().execute_with(|| {
@@ -382,7 +481,7 @@ fn send_funds_back_after_they_have_been_rejected() {
 			refund_address: Some(ForeignChainAddress::Btc(ScriptPubkey::P2SH(DEFAULT_BTC_ADDRESS))),
 			amount: DEFAULT_DEPOSIT_AMOUNT,
 			asset: btc::Asset::Btc,
-			deposit_details,
+			deposit_details: BoundedDepositDetails::Full(deposit_details),
 		});
 
 		IngressEgress::on_finalize(1);
@@ -399,6 +498,55 @@ fn send_funds_back_after_they_have_been_rejected() {
 	});
 }
 
+#[test]
+fn refunds_landing_in_deposit_channels_are_not_charged_ingress_fee_again() {
+	const INGRESS_FEE: u64 = 100;
+
+	new_test_ext().execute_with(|| {
+		ChainTracker::<Bitcoin>::set_fee(INGRESS_FEE);
+
+		ScheduledTransactionsForRejection::<Test, ()>::append(TransactionRejectionDetails {
+			refund_address: Some(ForeignChainAddress::Btc(ScriptPubkey::P2SH(DEFAULT_BTC_ADDRESS))),
+			amount: DEFAULT_DEPOSIT_AMOUNT,
+			asset: btc::Asset::Btc,
+			deposit_details: BoundedDepositDetails::Full(helpers::generate_btc_deposit(
+				Hash::random(),
+			)),
+		});
+		IngressEgress::on_finalize(1);
+
+		let refund_tx_id: Hash = BITCOIN_REJECTION_REFUND_TX_ID.into();
+		assert!(ProtocolRefundTransactions::<Test, ()>::contains_key(refund_tx_id));
+
+		// The refund is sent to one of our deposit channels, so it is credited in full:
+		let (_, address) = helpers::request_address_and_deposit(
+			BROKER,
+			btc::Asset::Btc,
+			helpers::generate_btc_deposit(refund_tx_id),
+		);
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::DepositFinalised { ingress_fee: 0, .. })
+		);
+		assert!(!ProtocolRefundTransactions::<Test, ()>::contains_key(refund_tx_id));
+
+		// Other deposits into the same channel are charged as usual:
+		assert_ok!(IngressEgress::process_channel_deposit_full_witness_inner(
+			&DepositWitness {
+				deposit_address: address,
+				asset: btc::Asset::Btc,
+				amount: DEFAULT_DEPOSIT_AMOUNT,
+				deposit_details: helpers::generate_btc_deposit(Hash::random()),
+			},
+			Default::default()
+		));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::IngressEgress(Event::DepositFinalised { ingress_fee: INGRESS_FEE, .. })
+		);
+	});
+}
+
 #[test]
 fn can_report_between_prewitness_and_witness_if_tx_was_not_boosted() {
 	new_test_ext().execute_with(|| {
@@ -34,7 +34,9 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn disable_asset_egress() -> Weight;
 	fn process_channel_deposit_full_witness() -> Weight;
+	fn record_deposit_failure() -> Weight;
 	fn finalise_ingress(a: u32, ) -> Weight;
+	fn egress_scheduled_fetches(n: u32, ) -> Weight;
 	fn vault_transfer_failed() -> Weight;
 	fn ccm_broadcast_failed() -> Weight;
 	fn add_boost_funds() -> Weight;
@@ -45,6 +47,15 @@ pub trait WeightInfo {
 	fn boost_finalised() -> Weight;
 	fn create_boost_pools() -> Weight;
 	fn mark_transaction_for_rejection() -> Weight;
+	fn update_swap_channel() -> Weight;
+	fn update_refund_address() -> Weight;
+	fn close_deposit_channel() -> Weight;
+	fn report_reorg() -> Weight;
+	fn start_deposit_address_verification() -> Weight;
+	fn start_chain_wind_down() -> Weight;
+	fn set_broker_minimum_deposit() -> Weight;
+	fn extend_deposit_channel_expiries(n: u32, ) -> Weight;
+	fn credit_abandoned_egress() -> Weight;
 }
 
 /// Weights for pallet_cf_ingress_egress using the Substrate node and recommended hardware.
@@ -84,6 +95,19 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(7_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: `EthereumIngressEgress::DepositFailureHistoryRange` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositFailureHistoryRange` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositFailureHistory` (r:0 w:2)
+	/// Proof: `EthereumIngressEgress::DepositFailureHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn record_deposit_failure() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1491`
+		// Minimum execution time: 9_114_000 picoseconds.
+		Weight::from_parts(9_482_000, 1491)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
 	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// The range of component `a` is `[1, 100]`.
@@ -98,6 +122,69 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DisabledEgressAssets` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DisabledEgressAssets` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:100 w:100)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::LastEgressUsage` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::LastEgressUsage` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::EthereumVaultAddress` (r:1 w:0)
+	/// Proof: `Environment::EthereumVaultAddress` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::EthereumSignatureNonce` (r:1 w:1)
+	/// Proof: `Environment::EthereumSignatureNonce` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::EthereumChainId` (r:1 w:0)
+	/// Proof: `Environment::EthereumChainId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::EthereumKeyManagerAddress` (r:1 w:0)
+	/// Proof: `Environment::EthereumKeyManagerAddress` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumBroadcaster::BroadcastIdCounter` (r:1 w:1)
+	/// Proof: `EthereumBroadcaster::BroadcastIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(40), added: 535, mode: `MaxEncodedLen`)
+	/// Storage: `EvmThresholdSigner::CurrentKeyEpoch` (r:1 w:0)
+	/// Proof: `EvmThresholdSigner::CurrentKeyEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::Keys` (r:1 w:0)
+	/// Proof: `EvmThresholdSigner::Keys` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::ThresholdSignatureRequestIdCounter` (r:1 w:1)
+	/// Proof: `EvmThresholdSigner::ThresholdSignatureRequestIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::CurrentEpoch` (r:1 w:0)
+	/// Proof: `Validator::CurrentEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::HistoricalAuthorities` (r:1 w:0)
+	/// Proof: `Validator::HistoricalAuthorities` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Reputation::Suspensions` (r:4 w:0)
+	/// Proof: `Reputation::Suspensions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::CeremonyIdCounter` (r:1 w:1)
+	/// Proof: `EvmThresholdSigner::CeremonyIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::ThresholdSignatureResponseTimeout` (r:1 w:0)
+	/// Proof: `EvmThresholdSigner::ThresholdSignatureResponseTimeout` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::CeremonyRetryQueues` (r:1 w:1)
+	/// Proof: `EvmThresholdSigner::CeremonyRetryQueues` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CfeInterface::CfeEvents` (r:1 w:1)
+	/// Proof: `CfeInterface::CfeEvents` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::SignerAndSignature` (r:0 w:1)
+	/// Proof: `EvmThresholdSigner::SignerAndSignature` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::PendingCeremonies` (r:0 w:1)
+	/// Proof: `EvmThresholdSigner::PendingCeremonies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::RequestCallback` (r:0 w:1)
+	/// Proof: `EvmThresholdSigner::RequestCallback` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumBroadcaster::RequestSuccessCallbacks` (r:0 w:1)
+	/// Proof: `EthereumBroadcaster::RequestSuccessCallbacks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[1, 100]`.
+	fn egress_scheduled_fetches(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1843 + n * (271 ±0)`
+		//  Estimated: `12208 + n * (2746 ±0)`
+		// Minimum execution time: 118_000_000 picoseconds.
+		Weight::from_parts(121_604_253, 12208)
+			// Standard Error: 11_379
+			.saturating_add(Weight::from_parts(6_512_848, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(22_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(12_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2746).saturating_mul(n.into()))
+	}
 	/// Storage: `Validator::CurrentEpoch` (r:1 w:0)
 	/// Proof: `Validator::CurrentEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Environment::EthereumVaultAddress` (r:1 w:0)
@@ -298,6 +385,153 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 		// Minimum execution time: 0 picoseconds.
 		Weight::from_parts(0, 0)
 	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn update_swap_channel() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1025`
+		//  Estimated: `4490`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 4490)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::RefundAddressUpdateDelay` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::RefundAddressUpdateDelay` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::RefundAddressUpdatesDueAt` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::RefundAddressUpdatesDueAt` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::PendingRefundAddressUpdates` (r:0 w:1)
+	/// Proof: `EthereumIngressEgress::PendingRefundAddressUpdates` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn update_refund_address() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1012`
+		//  Estimated: `4477`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 4477)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleQueue` (r:2 w:2)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::CounterForDepositChannelRecycleQueue` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::CounterForDepositChannelRecycleQueue` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::NextRecycleBlockHeight` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::NextRecycleBlockHeight` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn close_deposit_channel() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1159`
+		//  Estimated: `7099`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(42_000_000, 7099)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `EthereumIngressEgress::ObservedReorgs` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ObservedReorgs` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::WitnessSafetyMarginAutoTuning` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::WitnessSafetyMarginAutoTuning` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::WitnessSafetyMargin` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::WitnessSafetyMargin` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn report_reorg() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1817`
+		//  Estimated: `3302`
+		// Minimum execution time: 16_000_000 picoseconds.
+		Weight::from_parts(17_000_000, 3302)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `EthereumIngressEgress::DepositAddressVerificationInProgress` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositAddressVerificationInProgress` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositAddressMismatches` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositAddressMismatches` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn start_deposit_address_verification() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `3471`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(10_000_000, 3471)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `EthereumIngressEgress::ChainWindDown` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ChainWindDown` (`max_values`: Some(1), `max_size`: Some(17), added: 512, mode: `MaxEncodedLen`)
+	fn start_chain_wind_down() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1498`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 1498)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(49), added: 2524, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::MinimumDeposit` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::MinimumDeposit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::BrokerMinimumDeposits` (r:0 w:1)
+	/// Proof: `EthereumIngressEgress::BrokerMinimumDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_broker_minimum_deposit() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `589`
+		//  Estimated: `4054`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 4054)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:101 w:100)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleQueue` (r:2 w:2)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::CounterForDepositChannelRecycleQueue` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::CounterForDepositChannelRecycleQueue` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::NextRecycleBlockHeight` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::NextRecycleBlockHeight` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[1, 100]`.
+	fn extend_deposit_channel_expiries(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1082 + n * (299 ±0)`
+		//  Estimated: `6840 + n * (2774 ±0)`
+		// Minimum execution time: 38_000_000 picoseconds.
+		Weight::from_parts(31_214_873, 6840)
+			// Standard Error: 6_412
+			.saturating_add(Weight::from_parts(14_372_145, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2774).saturating_mul(n.into()))
+	}
+	/// Storage: `EthereumIngressEgress::AbandonedEgresses` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::AbandonedEgresses` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	/// Storage: `AssetBalances::FreeBalances` (r:1 w:1)
+	/// Proof: `AssetBalances::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn credit_abandoned_egress() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3877`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 3877)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -336,6 +570,19 @@ impl WeightInfo for () {
 			.saturating_add(ParityDbWeight::get().reads(7_u64))
 			.saturating_add(ParityDbWeight::get().writes(2_u64))
 	}
+	/// Storage: `EthereumIngressEgress::DepositFailureHistoryRange` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositFailureHistoryRange` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositFailureHistory` (r:0 w:2)
+	/// Proof: `EthereumIngressEgress::DepositFailureHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn record_deposit_failure() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1491`
+		// Minimum execution time: 9_114_000 picoseconds.
+		Weight::from_parts(9_482_000, 1491)
+			.saturating_add(ParityDbWeight::get().reads(1_u64))
+			.saturating_add(ParityDbWeight::get().writes(3_u64))
+	}
 	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
 	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// The range of component `a` is `[1, 100]`.
@@ -350,6 +597,69 @@ impl WeightInfo for () {
 			.saturating_add(ParityDbWeight::get().reads(1_u64))
 			.saturating_add(ParityDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DisabledEgressAssets` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DisabledEgressAssets` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:100 w:100)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::LastEgressUsage` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::LastEgressUsage` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::EthereumVaultAddress` (r:1 w:0)
+	/// Proof: `Environment::EthereumVaultAddress` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::EthereumSignatureNonce` (r:1 w:1)
+	/// Proof: `Environment::EthereumSignatureNonce` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::EthereumChainId` (r:1 w:0)
+	/// Proof: `Environment::EthereumChainId` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::EthereumKeyManagerAddress` (r:1 w:0)
+	/// Proof: `Environment::EthereumKeyManagerAddress` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumBroadcaster::BroadcastIdCounter` (r:1 w:1)
+	/// Proof: `EthereumBroadcaster::BroadcastIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(40), added: 535, mode: `MaxEncodedLen`)
+	/// Storage: `EvmThresholdSigner::CurrentKeyEpoch` (r:1 w:0)
+	/// Proof: `EvmThresholdSigner::CurrentKeyEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::Keys` (r:1 w:0)
+	/// Proof: `EvmThresholdSigner::Keys` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::ThresholdSignatureRequestIdCounter` (r:1 w:1)
+	/// Proof: `EvmThresholdSigner::ThresholdSignatureRequestIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::CurrentEpoch` (r:1 w:0)
+	/// Proof: `Validator::CurrentEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Validator::HistoricalAuthorities` (r:1 w:0)
+	/// Proof: `Validator::HistoricalAuthorities` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Reputation::Suspensions` (r:4 w:0)
+	/// Proof: `Reputation::Suspensions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::CeremonyIdCounter` (r:1 w:1)
+	/// Proof: `EvmThresholdSigner::CeremonyIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::ThresholdSignatureResponseTimeout` (r:1 w:0)
+	/// Proof: `EvmThresholdSigner::ThresholdSignatureResponseTimeout` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::CeremonyRetryQueues` (r:1 w:1)
+	/// Proof: `EvmThresholdSigner::CeremonyRetryQueues` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `CfeInterface::CfeEvents` (r:1 w:1)
+	/// Proof: `CfeInterface::CfeEvents` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::SignerAndSignature` (r:0 w:1)
+	/// Proof: `EvmThresholdSigner::SignerAndSignature` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::PendingCeremonies` (r:0 w:1)
+	/// Proof: `EvmThresholdSigner::PendingCeremonies` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EvmThresholdSigner::RequestCallback` (r:0 w:1)
+	/// Proof: `EvmThresholdSigner::RequestCallback` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumBroadcaster::RequestSuccessCallbacks` (r:0 w:1)
+	/// Proof: `EthereumBroadcaster::RequestSuccessCallbacks` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[1, 100]`.
+	fn egress_scheduled_fetches(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1843 + n * (271 ±0)`
+		//  Estimated: `12208 + n * (2746 ±0)`
+		// Minimum execution time: 118_000_000 picoseconds.
+		Weight::from_parts(121_604_253, 12208)
+			// Standard Error: 11_379
+			.saturating_add(Weight::from_parts(6_512_848, 0).saturating_mul(n.into()))
+			.saturating_add(ParityDbWeight::get().reads(22_u64))
+			.saturating_add(ParityDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(ParityDbWeight::get().writes(12_u64))
+			.saturating_add(ParityDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2746).saturating_mul(n.into()))
+	}
 	/// Storage: `Validator::CurrentEpoch` (r:1 w:0)
 	/// Proof: `Validator::CurrentEpoch` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `Environment::EthereumVaultAddress` (r:1 w:0)
@@ -550,4 +860,151 @@ impl WeightInfo for () {
 		// Minimum execution time: 0 picoseconds.
 		Weight::from_parts(0, 0)
 	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn update_swap_channel() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1025`
+		//  Estimated: `4490`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 4490)
+			.saturating_add(ParityDbWeight::get().reads(2_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::RefundAddressUpdateDelay` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::RefundAddressUpdateDelay` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::RefundAddressUpdatesDueAt` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::RefundAddressUpdatesDueAt` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::PendingRefundAddressUpdates` (r:0 w:1)
+	/// Proof: `EthereumIngressEgress::PendingRefundAddressUpdates` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn update_refund_address() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1012`
+		//  Estimated: `4477`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 4477)
+			.saturating_add(ParityDbWeight::get().reads(3_u64))
+			.saturating_add(ParityDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleQueue` (r:2 w:2)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::CounterForDepositChannelRecycleQueue` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::CounterForDepositChannelRecycleQueue` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::NextRecycleBlockHeight` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::NextRecycleBlockHeight` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn close_deposit_channel() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1159`
+		//  Estimated: `7099`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(42_000_000, 7099)
+			.saturating_add(ParityDbWeight::get().reads(8_u64))
+			.saturating_add(ParityDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `EthereumIngressEgress::ObservedReorgs` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ObservedReorgs` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::WitnessSafetyMarginAutoTuning` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::WitnessSafetyMarginAutoTuning` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::WitnessSafetyMargin` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::WitnessSafetyMargin` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn report_reorg() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1817`
+		//  Estimated: `3302`
+		// Minimum execution time: 16_000_000 picoseconds.
+		Weight::from_parts(17_000_000, 3302)
+			.saturating_add(ParityDbWeight::get().reads(3_u64))
+			.saturating_add(ParityDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `EthereumIngressEgress::DepositAddressVerificationInProgress` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositAddressVerificationInProgress` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositAddressMismatches` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositAddressMismatches` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn start_deposit_address_verification() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `3471`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(10_000_000, 3471)
+			.saturating_add(ParityDbWeight::get().reads(2_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `EthereumIngressEgress::ChainWindDown` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ChainWindDown` (`max_values`: Some(1), `max_size`: Some(17), added: 512, mode: `MaxEncodedLen`)
+	fn start_chain_wind_down() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1498`
+		// Minimum execution time: 8_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 1498)
+			.saturating_add(ParityDbWeight::get().reads(1_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: Some(49), added: 2524, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::MinimumDeposit` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::MinimumDeposit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::BrokerMinimumDeposits` (r:0 w:1)
+	/// Proof: `EthereumIngressEgress::BrokerMinimumDeposits` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_broker_minimum_deposit() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `589`
+		//  Estimated: `4054`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 4054)
+			.saturating_add(ParityDbWeight::get().reads(2_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:101 w:100)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleQueue` (r:2 w:2)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::CounterForDepositChannelRecycleQueue` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::CounterForDepositChannelRecycleQueue` (`max_values`: Some(1), `max_size`: Some(4), added: 499, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::NextRecycleBlockHeight` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::NextRecycleBlockHeight` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[1, 100]`.
+	fn extend_deposit_channel_expiries(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1082 + n * (299 ±0)`
+		//  Estimated: `6840 + n * (2774 ±0)`
+		// Minimum execution time: 38_000_000 picoseconds.
+		Weight::from_parts(31_214_873, 6840)
+			// Standard Error: 6_412
+			.saturating_add(Weight::from_parts(14_372_145, 0).saturating_mul(n.into()))
+			.saturating_add(ParityDbWeight::get().reads(6_u64))
+			.saturating_add(ParityDbWeight::get().reads((2_u64).saturating_mul(n.into())))
+			.saturating_add(ParityDbWeight::get().writes(4_u64))
+			.saturating_add(ParityDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2774).saturating_mul(n.into()))
+	}
+	/// Storage: `EthereumIngressEgress::AbandonedEgresses` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::AbandonedEgresses` (`max_values`: None, `max_size`: Some(73), added: 2548, mode: `MaxEncodedLen`)
+	/// Storage: `AssetBalances::FreeBalances` (r:1 w:1)
+	/// Proof: `AssetBalances::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn credit_abandoned_egress() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `412`
+		//  Estimated: `3877`
+		// Minimum execution time: 17_000_000 picoseconds.
+		Weight::from_parts(18_000_000, 3877)
+			.saturating_add(ParityDbWeight::get().reads(2_u64))
+			.saturating_add(ParityDbWeight::get().writes(2_u64))
+	}
 }
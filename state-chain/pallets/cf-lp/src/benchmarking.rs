@@ -98,5 +98,61 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn open_withdrawal_session() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::LiquidityProvider,
+		)
+		.unwrap();
+
+		#[extrinsic_call]
+		open_withdrawal_session(RawOrigin::Signed(caller.clone()));
+
+		assert!(WithdrawalSessions::<T>::contains_key(caller));
+	}
+
+	#[benchmark]
+	fn add_withdrawal_to_session() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::LiquidityProvider,
+		)
+		.unwrap();
+		T::BalanceApi::credit_account(&caller, Asset::Eth, 1_000_000);
+		assert_ok!(Pallet::<T>::open_withdrawal_session(RawOrigin::Signed(caller.clone()).into()));
+
+		#[extrinsic_call]
+		add_withdrawal_to_session(
+			RawOrigin::Signed(caller.clone()),
+			1_000_000,
+			Asset::Eth,
+			EncodedAddress::benchmark_value(),
+		);
+
+		assert_eq!(WithdrawalSessions::<T>::get(caller).unwrap().withdrawals.len(), 1);
+	}
+
+	#[benchmark]
+	fn close_withdrawal_session(n: Linear<1, MAX_WITHDRAWALS_PER_SESSION>) {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::LiquidityProvider,
+		)
+		.unwrap();
+		T::BalanceApi::credit_account(&caller, Asset::Eth, 1_000_000 * n as u128);
+		assert_ok!(Pallet::<T>::open_withdrawal_session(RawOrigin::Signed(caller.clone()).into()));
+		for i in 0..n {
+			assert_ok!(Pallet::<T>::add_withdrawal_to_session(
+				RawOrigin::Signed(caller.clone()).into(),
+				1_000_000,
+				Asset::Eth,
+				EncodedAddress::Eth([i as u8; 20]),
+			));
+		}
+
+		#[extrinsic_call]
+		close_withdrawal_session(RawOrigin::Signed(caller.clone()));
+
+		assert!(!WithdrawalSessions::<T>::contains_key(caller));
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
@@ -5,10 +5,10 @@ use cf_chains::{address::AddressConverter, AnyChain, ForeignChainAddress};
 use cf_primitives::{AccountRole, Asset, AssetAmount, BasisPoints, ForeignChain};
 use cf_traits::{
 	impl_pallet_safe_mode, AccountRoleRegistry, BalanceApi, BoostApi, Chainflip, DepositApi,
-	EgressApi, LpRegistration, PoolApi, ScheduledEgressDetails,
+	EgressApi, EgressOrigin, LpRegistration, PoolApi, ScheduledEgressDetails,
 };
 
-use sp_std::vec;
+use sp_std::{collections::btree_map::BTreeMap, vec, vec::Vec};
 
 use frame_support::{pallet_prelude::*, sp_runtime::DispatchResult};
 use frame_system::pallet_prelude::*;
@@ -31,6 +31,16 @@ pub const PALLET_VERSION: StorageVersion = StorageVersion::new(3);
 
 impl_pallet_safe_mode!(PalletSafeMode; deposit_enabled, withdrawal_enabled);
 
+/// How long a withdrawal session may stay open before it is closed automatically.
+pub const WITHDRAWAL_SESSION_TIMEOUT: u32 = 100;
+
+/// The maximum number of distinct asset/destination pairs a single withdrawal session can hold.
+pub const MAX_WITHDRAWALS_PER_SESSION: u32 = 50;
+
+/// The maximum number of expired withdrawal sessions closed in a single block. Any further
+/// sessions are closed in the following blocks.
+pub const MAX_WITHDRAWAL_SESSIONS_CLOSED_PER_BLOCK: u32 = 10;
+
 #[frame_support::pallet]
 pub mod pallet {
 	use cf_chains::Chain;
@@ -48,6 +58,18 @@ pub mod pallet {
 		External(EncodedAddress),
 	}
 
+	/// A batch of withdrawals that is egressed in one go when the session is closed.
+	///
+	/// Withdrawals to the same destination in the same asset are merged, so that only a single
+	/// egress (and egress fee) is incurred for each of them.
+	#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+	pub struct WithdrawalSession<BlockNumber> {
+		/// The earliest block at which the session is closed automatically. Sessions that can't
+		/// be closed at that block are closed in a later one.
+		pub expires_at: BlockNumber,
+		pub withdrawals: BTreeMap<(Asset, EncodedAddress), AssetAmount>,
+	}
+
 	#[pallet::config]
 	#[pallet::disable_frame_system_supertrait_check]
 	pub trait Config: Chainflip {
@@ -128,6 +150,14 @@ pub mod pallet {
 		CannotTransferToOriginAccount,
 		/// The account still has funds remaining in the boost pools
 		BoostedFundsRemaining,
+		/// The account already has an open withdrawal session.
+		WithdrawalSessionAlreadyOpen,
+		/// The account does not have an open withdrawal session.
+		NoOpenWithdrawalSession,
+		/// The withdrawal session has reached the maximum number of withdrawals.
+		TooManyWithdrawalsInSession,
+		/// The account still has an open withdrawal session.
+		WithdrawalSessionOpen,
 	}
 
 	#[pallet::event]
@@ -161,6 +191,27 @@ pub mod pallet {
 			asset: Asset,
 			amount: AssetAmount,
 		},
+		WithdrawalSessionOpened {
+			account_id: T::AccountId,
+			expires_at: BlockNumberFor<T>,
+		},
+		WithdrawalAddedToSession {
+			account_id: T::AccountId,
+			asset: Asset,
+			amount: AssetAmount,
+			destination_address: EncodedAddress,
+		},
+		WithdrawalSessionClosed {
+			account_id: T::AccountId,
+			egress_count: u32,
+		},
+		/// A consolidated withdrawal could not be egressed and was credited back to the account.
+		SessionWithdrawalRefunded {
+			account_id: T::AccountId,
+			asset: Asset,
+			amount: AssetAmount,
+			destination_address: EncodedAddress,
+		},
 	}
 
 	#[pallet::pallet]
@@ -179,6 +230,58 @@ pub mod pallet {
 		ForeignChainAddress,
 	>;
 
+	/// The open withdrawal session of each Liquidity Provider, if any.
+	#[pallet::storage]
+	pub type WithdrawalSessions<T: Config> =
+		StorageMap<_, Identity, T::AccountId, WithdrawalSession<BlockNumberFor<T>>>;
+
+	/// The accounts whose withdrawal sessions expire at a given block.
+	#[pallet::storage]
+	pub type WithdrawalSessionExpiries<T: Config> =
+		StorageMap<_, Twox64Concat, BlockNumberFor<T>, Vec<T::AccountId>, ValueQuery>;
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(current_block: BlockNumberFor<T>) -> Weight {
+			let mut expiring = WithdrawalSessionExpiries::<T>::take(current_block);
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+			if expiring.is_empty() {
+				return weight
+			}
+
+			// Sessions that can't be closed in this block are moved to the next one. Sessions are
+			// held open while withdrawals are disabled.
+			let spilled = if T::SafeMode::get().withdrawal_enabled {
+				expiring.split_off(
+					expiring.len().min(MAX_WITHDRAWAL_SESSIONS_CLOSED_PER_BLOCK as usize),
+				)
+			} else {
+				sp_std::mem::take(&mut expiring)
+			};
+			if !spilled.is_empty() {
+				WithdrawalSessionExpiries::<T>::mutate(current_block + 1u32.into(), |next| {
+					*next = spilled.into_iter().chain(sp_std::mem::take(next)).collect();
+				});
+				weight.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+			}
+
+			for account_id in expiring {
+				// The session may have been closed (and possibly re-opened) in the meantime.
+				if WithdrawalSessions::<T>::get(&account_id)
+					.is_some_and(|session| session.expires_at <= current_block)
+				{
+					let egress_count = Self::close_session(account_id);
+					weight.saturating_accrue(T::WeightInfo::close_withdrawal_session(egress_count));
+				} else {
+					weight.saturating_accrue(T::DbWeight::get().reads(1));
+				}
+			}
+
+			weight
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// For when the user wants to deposit assets into the Chain.
@@ -198,12 +301,19 @@ pub mod pallet {
 				LiquidityRefundAddress::<T>::get(&account_id, ForeignChain::from(asset))
 			{
 				let (channel_id, deposit_address, expiry_block, channel_opening_fee) =
-					T::DepositHandler::request_liquidity_deposit_address(
+					match T::DepositHandler::request_liquidity_deposit_address(
 						account_id.clone(),
 						asset,
 						boost_fee,
 						refund_address,
-					)?;
+					) {
+						Ok(channel) => channel,
+						// The LP is notified by the ingress-egress pallet once the channel is
+						// ready.
+						Err(error) if T::DepositHandler::is_deferred_channel_open(&error) =>
+							return Ok(()),
+						Err(error) => return Err(error),
+					};
 
 				Self::deposit_event(Event::LiquidityDepositAddressReady {
 					channel_id,
@@ -305,6 +415,11 @@ pub mod pallet {
 				Error::<T>::BoostedFundsRemaining
 			);
 
+			ensure!(
+				!WithdrawalSessions::<T>::contains_key(&account_id),
+				Error::<T>::WithdrawalSessionOpen
+			);
+
 			let _ = LiquidityRefundAddress::<T>::clear_prefix(&account_id, u32::MAX, None);
 
 			T::AccountRoleRegistry::deregister_as_liquidity_provider(&account_id)?;
@@ -329,6 +444,107 @@ pub mod pallet {
 				AccountOrAddress::Internal(destination),
 			)
 		}
+
+		/// Open a withdrawal session. Withdrawals added to the session are held until the session
+		/// is closed, either explicitly or after [WITHDRAWAL_SESSION_TIMEOUT] blocks, at which
+		/// point they are consolidated and egressed together.
+		///
+		/// ## Events
+		///
+		/// - [On Success](Event::WithdrawalSessionOpened)
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::open_withdrawal_session())]
+		pub fn open_withdrawal_session(origin: OriginFor<T>) -> DispatchResult {
+			ensure!(T::SafeMode::get().withdrawal_enabled, Error::<T>::WithdrawalsDisabled);
+			let account_id = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+
+			ensure!(
+				!WithdrawalSessions::<T>::contains_key(&account_id),
+				Error::<T>::WithdrawalSessionAlreadyOpen
+			);
+
+			let expires_at =
+				frame_system::Pallet::<T>::block_number() + WITHDRAWAL_SESSION_TIMEOUT.into();
+			WithdrawalSessions::<T>::insert(
+				&account_id,
+				WithdrawalSession { expires_at, withdrawals: Default::default() },
+			);
+			WithdrawalSessionExpiries::<T>::append(expires_at, account_id.clone());
+
+			Self::deposit_event(Event::<T>::WithdrawalSessionOpened { account_id, expires_at });
+
+			Ok(())
+		}
+
+		/// Add a withdrawal to the caller's open withdrawal session. The amount is debited from
+		/// the free balance immediately.
+		///
+		/// ## Events
+		///
+		/// - [On Success](Event::WithdrawalAddedToSession)
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::add_withdrawal_to_session())]
+		pub fn add_withdrawal_to_session(
+			origin: OriginFor<T>,
+			amount: AssetAmount,
+			asset: Asset,
+			destination_address: EncodedAddress,
+		) -> DispatchResult {
+			ensure!(T::SafeMode::get().withdrawal_enabled, Error::<T>::WithdrawalsDisabled);
+			let account_id = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+
+			let mut session = WithdrawalSessions::<T>::get(&account_id)
+				.ok_or(Error::<T>::NoOpenWithdrawalSession)?;
+
+			if amount > 0 {
+				Self::ensure_valid_withdrawal_address(&account_id, asset, &destination_address)?;
+
+				let key = (asset, destination_address.clone());
+				ensure!(
+					session.withdrawals.contains_key(&key) ||
+						session.withdrawals.len() < MAX_WITHDRAWALS_PER_SESSION as usize,
+					Error::<T>::TooManyWithdrawalsInSession
+				);
+
+				T::PoolApi::sweep(&account_id)?;
+				T::BalanceApi::try_debit_account(&account_id, asset, amount)?;
+
+				let total = session.withdrawals.entry(key).or_default();
+				*total = total.checked_add(amount).ok_or(Error::<T>::BalanceOverflow)?;
+				WithdrawalSessions::<T>::insert(&account_id, session);
+
+				Self::deposit_event(Event::<T>::WithdrawalAddedToSession {
+					account_id,
+					asset,
+					amount,
+					destination_address,
+				});
+			}
+
+			Ok(())
+		}
+
+		/// Close the caller's withdrawal session and schedule one egress for each asset and
+		/// destination pair in the session.
+		///
+		/// ## Events
+		///
+		/// - [On Success](Event::WithdrawalSessionClosed)
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::close_withdrawal_session(MAX_WITHDRAWALS_PER_SESSION))]
+		pub fn close_withdrawal_session(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			ensure!(T::SafeMode::get().withdrawal_enabled, Error::<T>::WithdrawalsDisabled);
+			let account_id = T::AccountRoleRegistry::ensure_liquidity_provider(origin)?;
+
+			ensure!(
+				WithdrawalSessions::<T>::contains_key(&account_id),
+				Error::<T>::NoOpenWithdrawalSession
+			);
+
+			let egress_count = Self::close_session(account_id);
+
+			Ok(Some(T::WeightInfo::close_withdrawal_session(egress_count)).into())
+		}
 	}
 }
 
@@ -374,15 +590,11 @@ impl<T: Config> Pallet<T> {
 					});
 				},
 				AccountOrAddress::External(destination_address) => {
-					let destination_address_internal =
-						T::AddressConverter::try_from_encoded_address(destination_address.clone())
-							.map_err(|_| Error::<T>::InvalidEgressAddress)?;
-
-					// Check validity of Chain and Asset
-					ensure!(
-						destination_address_internal.chain() == ForeignChain::from(asset),
-						Error::<T>::InvalidEgressAddress
-					);
+					let destination_address_internal = Self::ensure_valid_withdrawal_address(
+						&account_id,
+						asset,
+						&destination_address,
+					)?;
 
 					// Sweep earned fees
 					T::PoolApi::sweep(&account_id)?;
@@ -396,6 +608,8 @@ impl<T: Config> Pallet<T> {
 							amount,
 							destination_address_internal,
 							None,
+							None,
+							EgressOrigin::Withdrawal,
 						)
 						.map_err(Into::into)?;
 
@@ -411,6 +625,75 @@ impl<T: Config> Pallet<T> {
 		}
 		Ok(())
 	}
+
+	fn ensure_valid_withdrawal_address(
+		account_id: &T::AccountId,
+		asset: Asset,
+		destination_address: &EncodedAddress,
+	) -> Result<ForeignChainAddress, DispatchError> {
+		let destination_address_internal =
+			T::AddressConverter::try_from_encoded_address(destination_address.clone())
+				.map_err(|_| Error::<T>::InvalidEgressAddress)?;
+
+		// Check validity of Chain and Asset
+		ensure!(
+			destination_address_internal.chain() == ForeignChain::from(asset),
+			Error::<T>::InvalidEgressAddress
+		);
+
+		T::BalanceApi::check_withdrawal_address(account_id, destination_address)?;
+
+		Ok(destination_address_internal)
+	}
+
+	/// Closes the account's withdrawal session, scheduling all of its withdrawals for egress.
+	/// Withdrawals that cannot be egressed are credited back to the account.
+	///
+	/// Returns the number of consolidated withdrawals in the session.
+	fn close_session(account_id: T::AccountId) -> u32 {
+		let Some(session) = WithdrawalSessions::<T>::take(&account_id) else { return 0 };
+		let mut egress_count = 0u32;
+
+		for ((asset, destination_address), amount) in session.withdrawals {
+			egress_count += 1;
+			match T::AddressConverter::try_from_encoded_address(destination_address.clone())
+				.map_err(|()| DispatchError::from(Error::<T>::InvalidEgressAddress))
+				.and_then(|destination_address_internal| {
+					T::EgressHandler::schedule_egress(
+						asset,
+						amount,
+						destination_address_internal,
+						None,
+						None,
+						EgressOrigin::Withdrawal,
+					)
+					.map_err(Into::<DispatchError>::into)
+				}) {
+				Ok(ScheduledEgressDetails { egress_id, egress_amount, fee_withheld }) => {
+					Self::deposit_event(Event::<T>::WithdrawalEgressScheduled {
+						egress_id,
+						asset,
+						amount: egress_amount,
+						destination_address,
+						fee: fee_withheld,
+					});
+				},
+				Err(_) => {
+					T::BalanceApi::credit_account(&account_id, asset, amount);
+					Self::deposit_event(Event::<T>::SessionWithdrawalRefunded {
+						account_id: account_id.clone(),
+						asset,
+						amount,
+						destination_address,
+					});
+				},
+			}
+		}
+
+		Self::deposit_event(Event::<T>::WithdrawalSessionClosed { account_id, egress_count });
+
+		egress_count
+	}
 }
 
 impl<T: Config> LpRegistration for Pallet<T> {
@@ -120,6 +120,13 @@ impl BalanceApi for MockBalanceApi {
 	fn get_balance(_who: &Self::AccountId, _asset: Asset) -> AssetAmount {
 		todo!()
 	}
+
+	fn check_withdrawal_address(
+		_who: &Self::AccountId,
+		_destination: &cf_chains::address::EncodedAddress,
+	) -> frame_support::dispatch::DispatchResult {
+		Ok(())
+	}
 }
 
 impl MockBalanceApi {
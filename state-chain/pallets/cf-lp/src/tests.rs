@@ -1,11 +1,21 @@
-use crate::{mock::*, Error, Event, LiquidityRefundAddress};
+use crate::{
+	mock::*, Error, Event, LiquidityRefundAddress, WithdrawalSessionExpiries, WithdrawalSessions,
+	MAX_WITHDRAWAL_SESSIONS_CLOSED_PER_BLOCK, WITHDRAWAL_SESSION_TIMEOUT,
+};
 
-use cf_chains::{address::EncodedAddress, ForeignChainAddress};
+use cf_chains::{address::EncodedAddress, AnyChain, ForeignChainAddress};
 use cf_primitives::{AccountId, Asset, AssetAmount, ForeignChain};
 
 use cf_test_utilities::assert_events_match;
-use cf_traits::{AccountRoleRegistry, BalanceApi, Chainflip, SetSafeMode};
-use frame_support::{assert_noop, assert_ok, error::BadOrigin, traits::OriginTrait};
+use cf_traits::{
+	mocks::egress_handler::{MockEgressHandler, MockEgressParameter},
+	AccountRoleRegistry, BalanceApi, Chainflip, SetSafeMode,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	error::BadOrigin,
+	traits::{Hooks, OriginTrait},
+};
 use sp_runtime::AccountId32;
 
 #[test]
@@ -363,3 +373,139 @@ fn account_registration_and_deregistration() {
 			.all(|(_, amount)| *amount == 0));
 	});
 }
+
+#[test]
+fn withdrawals_in_a_session_are_consolidated_on_close() {
+	new_test_ext().execute_with(|| {
+		const ADDRESS_1: [u8; 20] = [0x01; 20];
+		const ADDRESS_2: [u8; 20] = [0x02; 20];
+		let account_id = AccountId::from(LP_ACCOUNT);
+		MockBalanceApi::insert_balance(account_id.clone(), 1_000);
+
+		assert_noop!(
+			LiquidityProvider::add_withdrawal_to_session(
+				RuntimeOrigin::signed(account_id.clone()),
+				100,
+				Asset::Eth,
+				EncodedAddress::Eth(ADDRESS_1),
+			),
+			Error::<Test>::NoOpenWithdrawalSession
+		);
+
+		assert_ok!(LiquidityProvider::open_withdrawal_session(RuntimeOrigin::signed(
+			account_id.clone()
+		)));
+		assert_noop!(
+			LiquidityProvider::open_withdrawal_session(RuntimeOrigin::signed(account_id.clone())),
+			Error::<Test>::WithdrawalSessionAlreadyOpen
+		);
+
+		for (amount, address) in [(100, ADDRESS_1), (200, ADDRESS_2), (300, ADDRESS_1)] {
+			assert_ok!(LiquidityProvider::add_withdrawal_to_session(
+				RuntimeOrigin::signed(account_id.clone()),
+				amount,
+				Asset::Eth,
+				EncodedAddress::Eth(address),
+			));
+		}
+
+		// Funds are debited as soon as they are added, but nothing is egressed yet.
+		assert_eq!(MockBalanceApi::get_balance(&account_id), Some(400));
+		assert!(MockEgressHandler::<AnyChain>::get_scheduled_egresses().is_empty());
+
+		// The account can't be deregistered while the session is open.
+		MockBalanceApi::insert_balance(account_id.clone(), 0);
+		assert_noop!(
+			LiquidityProvider::deregister_lp_account(RuntimeOrigin::signed(account_id.clone())),
+			Error::<Test>::WithdrawalSessionOpen
+		);
+
+		assert_ok!(LiquidityProvider::close_withdrawal_session(RuntimeOrigin::signed(
+			account_id.clone()
+		)));
+
+		// One egress per destination.
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses()
+				.iter()
+				.map(|egress| match egress {
+					MockEgressParameter::Swap { amount, destination_address, .. } =>
+						(*amount, destination_address.clone()),
+					_ => panic!("Unexpected CCM egress"),
+				})
+				.collect::<Vec<_>>(),
+			vec![
+				(400, ForeignChainAddress::Eth(ADDRESS_1.into())),
+				(200, ForeignChainAddress::Eth(ADDRESS_2.into())),
+			]
+		);
+		assert!(!WithdrawalSessions::<Test>::contains_key(&account_id));
+		System::assert_last_event(RuntimeEvent::LiquidityProvider(
+			Event::<Test>::WithdrawalSessionClosed { account_id, egress_count: 2 },
+		));
+	});
+}
+
+#[test]
+fn withdrawal_session_is_closed_after_timeout() {
+	new_test_ext().execute_with(|| {
+		let account_id = AccountId::from(LP_ACCOUNT);
+		MockBalanceApi::insert_balance(account_id.clone(), 1_000);
+
+		assert_ok!(LiquidityProvider::open_withdrawal_session(RuntimeOrigin::signed(
+			account_id.clone()
+		)));
+		let expires_at = System::block_number() + WITHDRAWAL_SESSION_TIMEOUT as u64;
+		assert_ok!(LiquidityProvider::add_withdrawal_to_session(
+			RuntimeOrigin::signed(account_id.clone()),
+			500,
+			Asset::Eth,
+			EncodedAddress::Eth(Default::default()),
+		));
+
+		LiquidityProvider::on_initialize(expires_at - 1);
+		assert!(WithdrawalSessions::<Test>::contains_key(&account_id));
+
+		// Expired sessions are held open while withdrawals are disabled.
+		<MockRuntimeSafeMode as SetSafeMode<MockRuntimeSafeMode>>::set_code_red();
+		LiquidityProvider::on_initialize(expires_at);
+		assert!(WithdrawalSessions::<Test>::contains_key(&account_id));
+		assert!(MockEgressHandler::<AnyChain>::get_scheduled_egresses().is_empty());
+
+		<MockRuntimeSafeMode as SetSafeMode<MockRuntimeSafeMode>>::set_code_green();
+		LiquidityProvider::on_initialize(expires_at + 1);
+		assert!(!WithdrawalSessions::<Test>::contains_key(&account_id));
+		assert_eq!(MockEgressHandler::<AnyChain>::get_scheduled_egresses().len(), 1);
+	});
+}
+
+#[test]
+fn expired_withdrawal_sessions_closed_per_block_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let accounts = (0..=MAX_WITHDRAWAL_SESSIONS_CLOSED_PER_BLOCK as u8)
+			.map(|i| AccountId::from([100 + i; 32]))
+			.collect::<Vec<_>>();
+		for account_id in &accounts {
+			assert_ok!(<Test as Chainflip>::AccountRoleRegistry::register_as_liquidity_provider(
+				account_id
+			));
+			assert_ok!(LiquidityProvider::open_withdrawal_session(RuntimeOrigin::signed(
+				account_id.clone()
+			)));
+		}
+		let expires_at = System::block_number() + WITHDRAWAL_SESSION_TIMEOUT as u64;
+
+		// The sessions that don't fit in the block are closed in the next one.
+		LiquidityProvider::on_initialize(expires_at);
+		let (closed, open) = accounts.split_at(MAX_WITHDRAWAL_SESSIONS_CLOSED_PER_BLOCK as usize);
+		assert!(closed
+			.iter()
+			.all(|account_id| !WithdrawalSessions::<Test>::contains_key(account_id)));
+		assert!(WithdrawalSessions::<Test>::contains_key(&open[0]));
+		assert_eq!(WithdrawalSessionExpiries::<Test>::get(expires_at + 1), open.to_vec());
+
+		LiquidityProvider::on_initialize(expires_at + 1);
+		assert!(!WithdrawalSessions::<Test>::contains_key(&open[0]));
+		assert!(WithdrawalSessionExpiries::<Test>::get(expires_at + 1).is_empty());
+	});
+}
@@ -37,6 +37,9 @@ pub trait WeightInfo {
 	fn register_lp_account() -> Weight;
 	fn deregister_lp_account() -> Weight;
 	fn register_liquidity_refund_address() -> Weight;
+	fn open_withdrawal_session() -> Weight;
+	fn add_withdrawal_to_session() -> Weight;
+	fn close_withdrawal_session(n: u32, ) -> Weight;
 }
 
 /// Weights for pallet_cf_lp using the Substrate node and recommended hardware.
@@ -95,14 +98,18 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 	/// Proof: `EthereumIngressEgress::EgressDustLimit` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
 	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::WithdrawalAddressProtection` (r:1 w:0)
+	/// Proof: `AssetBalances::WithdrawalAddressProtection` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::WithdrawalAddressHistory` (r:1 w:1)
+	/// Proof: `AssetBalances::WithdrawalAddressHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn withdraw_asset() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1447`
 		//  Estimated: `4912`
 		// Minimum execution time: 50_845_000 picoseconds.
 		Weight::from_parts(51_759_000, 4912)
-			.saturating_add(T::DbWeight::get().reads(10_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: `AccountRoles::AccountRoles` (r:1 w:1)
 	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -153,6 +160,69 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::WithdrawalSessions` (r:1 w:1)
+	/// Proof: `LiquidityProvider::WithdrawalSessions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::WithdrawalSessionExpiries` (r:1 w:1)
+	/// Proof: `LiquidityProvider::WithdrawalSessionExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn open_withdrawal_session() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `578`
+		//  Estimated: `4043`
+		// Minimum execution time: 21_412_000 picoseconds.
+		Weight::from_parts(22_007_000, 4043)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::WithdrawalSessions` (r:1 w:1)
+	/// Proof: `LiquidityProvider::WithdrawalSessions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::Pools` (r:1 w:0)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::FreeBalances` (r:1 w:1)
+	/// Proof: `AssetBalances::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn add_withdrawal_to_session() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1102`
+		//  Estimated: `4567`
+		// Minimum execution time: 34_118_000 picoseconds.
+		Weight::from_parts(35_002_000, 4567)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::WithdrawalSessions` (r:1 w:1)
+	/// Proof: `LiquidityProvider::WithdrawalSessions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(40), added: 535, mode: `MaxEncodedLen`)
+	/// Storage: `AssetBalances::WithheldAssets` (r:1 w:1)
+	/// Proof: `AssetBalances::WithheldAssets` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::EgressDustLimit` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::EgressDustLimit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[1, 50]`.
+	fn close_withdrawal_session(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1523 + n * (68 ±0)`
+		//  Estimated: `4988 + n * (68 ±0)`
+		// Minimum execution time: 38_640_000 picoseconds.
+		Weight::from_parts(30_215_113, 4988)
+			// Standard Error: 9_862
+			.saturating_add(Weight::from_parts(19_884_406, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 68).saturating_mul(n.into()))
+	}
 }
 
 // For backwards compatibility and tests
@@ -210,14 +280,18 @@ impl WeightInfo for () {
 	/// Proof: `EthereumIngressEgress::EgressDustLimit` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
 	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::WithdrawalAddressProtection` (r:1 w:0)
+	/// Proof: `AssetBalances::WithdrawalAddressProtection` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::WithdrawalAddressHistory` (r:1 w:1)
+	/// Proof: `AssetBalances::WithdrawalAddressHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn withdraw_asset() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1447`
 		//  Estimated: `4912`
 		// Minimum execution time: 50_845_000 picoseconds.
 		Weight::from_parts(51_759_000, 4912)
-			.saturating_add(ParityDbWeight::get().reads(10_u64))
-			.saturating_add(ParityDbWeight::get().writes(4_u64))
+			.saturating_add(ParityDbWeight::get().reads(12_u64))
+			.saturating_add(ParityDbWeight::get().writes(5_u64))
 	}
 	/// Storage: `AccountRoles::AccountRoles` (r:1 w:1)
 	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
@@ -268,4 +342,67 @@ impl WeightInfo for () {
 			.saturating_add(ParityDbWeight::get().reads(1_u64))
 			.saturating_add(ParityDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::WithdrawalSessions` (r:1 w:1)
+	/// Proof: `LiquidityProvider::WithdrawalSessions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::WithdrawalSessionExpiries` (r:1 w:1)
+	/// Proof: `LiquidityProvider::WithdrawalSessionExpiries` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn open_withdrawal_session() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `578`
+		//  Estimated: `4043`
+		// Minimum execution time: 21_412_000 picoseconds.
+		Weight::from_parts(22_007_000, 4043)
+			.saturating_add(ParityDbWeight::get().reads(4_u64))
+			.saturating_add(ParityDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::WithdrawalSessions` (r:1 w:1)
+	/// Proof: `LiquidityProvider::WithdrawalSessions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::Pools` (r:1 w:0)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::FreeBalances` (r:1 w:1)
+	/// Proof: `AssetBalances::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn add_withdrawal_to_session() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1102`
+		//  Estimated: `4567`
+		// Minimum execution time: 34_118_000 picoseconds.
+		Weight::from_parts(35_002_000, 4567)
+			.saturating_add(ParityDbWeight::get().reads(5_u64))
+			.saturating_add(ParityDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityProvider::WithdrawalSessions` (r:1 w:1)
+	/// Proof: `LiquidityProvider::WithdrawalSessions` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(40), added: 535, mode: `MaxEncodedLen`)
+	/// Storage: `AssetBalances::WithheldAssets` (r:1 w:1)
+	/// Proof: `AssetBalances::WithheldAssets` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::EgressDustLimit` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::EgressDustLimit` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// The range of component `n` is `[1, 50]`.
+	fn close_withdrawal_session(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1523 + n * (68 ±0)`
+		//  Estimated: `4988 + n * (68 ±0)`
+		// Minimum execution time: 38_640_000 picoseconds.
+		Weight::from_parts(30_215_113, 4988)
+			// Standard Error: 9_862
+			.saturating_add(Weight::from_parts(19_884_406, 0).saturating_mul(n.into()))
+			.saturating_add(ParityDbWeight::get().reads(7_u64))
+			.saturating_add(ParityDbWeight::get().writes(3_u64))
+			.saturating_add(Weight::from_parts(0, 68).saturating_mul(n.into()))
+	}
 }
@@ -1130,6 +1130,34 @@ impl<T: Config> SwappingApi for Pallet<T> {
 			Ok(output_amount)
 		})
 	}
+
+	fn swap_rate(from: any::Asset, to: any::Asset) -> Option<Price> {
+		use cf_amm::math::PRICE_FRACTIONAL_BITS;
+		use sp_core::U512;
+
+		let leg_rate = |from: any::Asset, to: any::Asset| {
+			let price = Self::current_price(from, to)?.price;
+			match AssetPair::from_swap(from, to)?.1 {
+				Side::Sell => Some(price),
+				// Pool prices are quoted in the quote asset, so buying the base asset is done at
+				// the inverse price.
+				Side::Buy => (U512::one() << (2 * PRICE_FRACTIONAL_BITS))
+					.checked_div(U512::from(price))
+					.and_then(|rate| Price::try_from(rate).ok()),
+			}
+		};
+
+		match (from, to) {
+			_ if from == to => None,
+			(_, STABLE_ASSET) | (STABLE_ASSET, _) => leg_rate(from, to),
+			_ => Price::try_from(
+				(U512::from(leg_rate(from, STABLE_ASSET)?) *
+					U512::from(leg_rate(STABLE_ASSET, to)?)) >>
+					PRICE_FRACTIONAL_BITS,
+			)
+			.ok(),
+		}
+	}
 }
 
 impl<T: Config> PoolApi for Pallet<T> {
@@ -91,6 +91,13 @@ impl BalanceApi for MockBalance {
 	fn get_balance(_who: &Self::AccountId, _asset: Asset) -> AssetAmount {
 		unimplemented!()
 	}
+
+	fn check_withdrawal_address(
+		_who: &Self::AccountId,
+		_destination: &cf_chains::address::EncodedAddress,
+	) -> DispatchResult {
+		Ok(())
+	}
 }
 
 impl MockBalance {
@@ -3,7 +3,9 @@
 use super::*;
 
 use cf_chains::{address::EncodedAddress, benchmarking_value::BenchmarkValue};
-use cf_primitives::{AccountRole, AffiliateShortId, Beneficiary, FLIPPERINOS_PER_FLIP};
+use cf_primitives::{
+	AccountRole, AffiliateShortId, Beneficiary, FLIPPERINOS_PER_FLIP, MAX_AFFILIATES,
+};
 use cf_traits::{AccountRoleRegistry, Chainflip, FeePayment};
 use frame_benchmarking::v2::*;
 use frame_support::{
@@ -69,7 +71,7 @@ mod benchmarks {
 			.unwrap();
 
 		let origin = RawOrigin::Signed(caller.clone());
-		let call = Call::<T>::request_swap_deposit_address_with_affiliates {
+		let call = Call::<T>::request_swap_deposit_address_with_parameters {
 			source_asset: Asset::Eth,
 			destination_asset: Asset::Usdc,
 			destination_address: EncodedAddress::benchmark_value(),
@@ -79,12 +81,50 @@ mod benchmarks {
 			refund_parameters: None,
 			affiliate_fees,
 			dca_parameters: None,
+			tracking_id: Some([1u8; 32]),
+			use_default_affiliates: true,
+			egress_memo: None,
+		};
+
+		#[block]
+		{
+			assert_ok!(call.dispatch_bypass_filter(origin.into()));
+		}
+	}
+
+	#[benchmark]
+	fn request_swap_deposit_address_with_quote() {
+		let caller = <T as Chainflip>::AccountRoleRegistry::whitelisted_caller_with_role(
+			AccountRole::Broker,
+		)
+		.unwrap();
+
+		// A non-zero balance is required to pay for the channel opening fee.
+		T::FeePayment::mint_to_account(&caller, (5 * FLIPPERINOS_PER_FLIP).into());
+
+		let origin = RawOrigin::Signed(caller.clone());
+		let call = Call::<T>::request_swap_deposit_address_with_quote {
+			source_asset: Asset::Eth,
+			destination_asset: Asset::Usdc,
+			destination_address: EncodedAddress::benchmark_value(),
+			broker_commission: 10,
+			boost_fee: 0,
+			channel_metadata: None,
+			refund_parameters: None,
+			affiliate_fees: Default::default(),
+			dca_parameters: None,
+			tracking_id: Some([1u8; 32]),
+			use_default_affiliates: false,
+			egress_memo: None,
+			quote: SwapQuote { input_amount: 1_000, output_amount: 1_000 },
 		};
 
 		#[block]
 		{
 			assert_ok!(call.dispatch_bypass_filter(origin.into()));
 		}
+
+		assert_eq!(SwapQuotes::<T>::iter().count(), 1);
 	}
 
 	#[benchmark]
@@ -199,5 +239,131 @@ mod benchmarks {
 		);
 	}
 
+	#[benchmark]
+	fn deregister_affiliate() {
+		let broker_id =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Broker).unwrap();
+
+		const IDX: u8 = 0;
+		let caller = OriginFor::<T>::signed(broker_id.clone());
+		let affiliate_id = frame_benchmarking::account::<T::AccountId>("affiliate", 0, 0);
+
+		assert_ok!(Pallet::<T>::register_affiliate(caller.clone(), affiliate_id, IDX.into()));
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T>::deregister_affiliate(caller.clone(), IDX.into()));
+		}
+
+		assert!(
+			!AffiliateIdMapping::<T>::contains_key(&broker_id, AffiliateShortId::from(IDX)),
+			"Affiliate must have been deregistered"
+		);
+	}
+
+	#[benchmark]
+	fn set_default_affiliates() {
+		let broker_id =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Broker).unwrap();
+		let caller = OriginFor::<T>::signed(broker_id.clone());
+
+		let affiliate_fees: Affiliates<T::AccountId> = (0..MAX_AFFILIATES)
+			.map(|i| Beneficiary {
+				account: frame_benchmarking::account::<T::AccountId>("affiliate", i, 0),
+				bps: 10,
+			})
+			.collect::<Vec<_>>()
+			.try_into()
+			.unwrap();
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T>::set_default_affiliates(caller, affiliate_fees.clone()));
+		}
+
+		assert_eq!(BrokerDefaultAffiliates::<T>::get(&broker_id), affiliate_fees);
+	}
+
+	#[benchmark]
+	fn swap_on_chain() {
+		let lp_id =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::LiquidityProvider)
+				.unwrap();
+		let caller = OriginFor::<T>::signed(lp_id.clone());
+		T::BalanceApi::credit_account(&lp_id, Asset::Eth, 1_000);
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T>::swap_on_chain(
+				caller,
+				Asset::Eth,
+				1_000,
+				Asset::Flip,
+				0,
+				Default::default(),
+				Some(DcaParameters { number_of_chunks: 2, chunk_interval: SWAP_DELAY_BLOCKS }),
+			));
+		}
+
+		assert_eq!(T::BalanceApi::get_balance(&lp_id, Asset::Eth), 0);
+	}
+
+	#[benchmark]
+	fn suspend_broker() {
+		let broker_id =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Broker).unwrap();
+		let caller = OriginFor::<T>::signed(broker_id.clone());
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T>::suspend_broker(caller));
+		}
+
+		assert!(SuspendedBrokers::<T>::contains_key(&broker_id), "Broker must have been suspended");
+	}
+
+	#[benchmark]
+	fn sweep_collected_rejected_funds() {
+		let gov_origin = T::EnsureGovernance::try_successful_origin().unwrap();
+		CollectedRejectedFunds::<T>::insert(Asset::Eth, 1_000);
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T>::sweep_collected_rejected_funds(
+				gov_origin,
+				Asset::Eth,
+				RejectedFundsDestination::Egress(EncodedAddress::benchmark_value()),
+			));
+		}
+
+		assert_eq!(CollectedRejectedFunds::<T>::get(Asset::Eth), 0);
+	}
+
+	#[benchmark]
+	fn update_ccm_destination_allowlist() {
+		let broker_id =
+			T::AccountRoleRegistry::whitelisted_caller_with_role(AccountRole::Broker).unwrap();
+		let caller = OriginFor::<T>::signed(broker_id.clone());
+		let destination_address = EncodedAddress::benchmark_value();
+		// Worst case: the allowlist is one entry short of being full.
+		CcmDestinationAllowlist::<T>::insert(
+			&broker_id,
+			destination_address.chain(),
+			(1..MAX_CCM_DESTINATIONS_PER_CHAIN as u8)
+				.map(|i| ForeignChainAddress::Eth([i; 20].into()))
+				.collect::<BTreeSet<_>>(),
+		);
+
+		#[block]
+		{
+			assert_ok!(Pallet::<T>::allow_ccm_destination(caller, destination_address.clone()));
+		}
+
+		assert!(
+			CcmDestinationAllowlist::<T>::contains_key(&broker_id, destination_address.chain()),
+			"Destination must have been allowed"
+		);
+	}
+
 	impl_benchmark_test_suite!(Pallet, crate::mock::new_test_ext(), crate::mock::Test,);
 }
@@ -1,24 +1,31 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![feature(extract_if)]
 
-use cf_amm::common::Side;
+use cf_amm::{
+	common::Side,
+	math::{mul_div_floor, Price, PRICE_FRACTIONAL_BITS},
+};
 use cf_chains::{
-	address::{AddressConverter, AddressError, ForeignChainAddress},
-	ccm_checker::CcmValidityCheck,
-	CcmChannelMetadata, CcmDepositMetadata, ChannelRefundParametersDecoded,
-	ChannelRefundParametersEncoded, SwapOrigin, SwapRefundParameters,
+	address::{AddressConverter, AddressError, EncodedAddress, ForeignChainAddress},
+	ccm_checker::{CcmLimits, CcmValidityCheck, CcmValidityError},
+	ccm_templating::{substitute_ccm_placeholder, CCM_SWAP_REQUEST_ID_PLACEHOLDER},
+	CcmChannelMetadata, CcmDepositMetadata, ChannelRefundParameters,
+	ChannelRefundParametersDecoded, ChannelRefundParametersEncoded, EgressMemo, SwapOrigin,
+	SwapRefundParameters,
 };
 use cf_primitives::{
-	AffiliateShortId, Affiliates, Asset, AssetAmount, Beneficiaries, Beneficiary, BlockNumber,
-	ChannelId, DcaParameters, ForeignChain, SwapId, SwapLeg, SwapRequestId,
-	BASIS_POINTS_PER_MILLION, FLIPPERINOS_PER_FLIP, MAX_BASIS_POINTS, SECONDS_PER_BLOCK,
-	STABLE_ASSET, SWAP_DELAY_BLOCKS,
+	AccountRole, AffiliateShortId, Affiliates, Asset, AssetAmount, BasisPoints, Beneficiaries,
+	Beneficiary, BlockNumber, ChannelId, DcaParameters, EpochIndex, ForeignChain, SwapId, SwapLeg,
+	SwapRequestId, TrackingId, BASIS_POINTS_PER_MILLION, FLIPPERINOS_PER_FLIP, MAX_BASIS_POINTS,
+	SECONDS_PER_BLOCK, STABLE_ASSET, SWAP_DELAY_BLOCKS,
 };
 use cf_runtime_utilities::log_or_panic;
 use cf_traits::{
 	impl_pallet_safe_mode, AffiliateRegistry, BalanceApi, Bonding, ChannelIdAllocator, DepositApi,
-	FundingInfo, IngressEgressFeeApi, SwapLimitsProvider, SwapRequestHandler, SwapRequestType,
+	EgressApi, EgressOrigin, EpochTransitionHandler, FundingInfo, IngressEgressFeeApi,
+	OnChainSwapHandler, OnSwapCompleted, SwapLimitsProvider, SwapRequestHandler, SwapRequestType,
 	SwapRequestTypeEncoded, SwapType, SwappingApi,
+	MAX_REJECTION_REQUESTS_EXPIRED_ON_DEREGISTRATION,
 };
 use frame_support::{
 	pallet_prelude::*,
@@ -37,7 +44,11 @@ use sp_arithmetic::{
 	traits::{UniqueSaturatedInto, Zero},
 	Rounding,
 };
-use sp_std::{collections::btree_map::BTreeMap, vec, vec::Vec};
+use sp_std::{
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	vec,
+	vec::Vec,
+};
 #[cfg(test)]
 mod mock;
 
@@ -50,11 +61,18 @@ pub mod migrations;
 pub mod weights;
 pub use weights::WeightInfo;
 
-pub const PALLET_VERSION: StorageVersion = StorageVersion::new(7);
+pub const PALLET_VERSION: StorageVersion = StorageVersion::new(11);
 
 pub(crate) const DEFAULT_SWAP_RETRY_DELAY_BLOCKS: u32 = 5;
 const DEFAULT_MAX_SWAP_RETRY_DURATION_BLOCKS: u32 = 3600 / SECONDS_PER_BLOCK as u32; // 1 hour
 const DEFAULT_MAX_SWAP_REQUEST_DURATION_BLOCKS: u32 = 86_400 / SECONDS_PER_BLOCK as u32; // 24 hours
+/// Supplied retry durations that differ from the default by more than this factor are reported.
+const REFUND_PARAMETERS_DEVIATION_FACTOR: u32 = 10;
+/// How many blocks are searched for one with capacity when the block a swap would be scheduled
+/// for is full.
+pub(crate) const MAX_BLOCKS_SEARCHED_FOR_SWAP_CAPACITY: u32 = 10;
+/// The maximum number of CCM destinations a broker can allow per destination chain.
+pub const MAX_CCM_DESTINATIONS_PER_CHAIN: u32 = 100;
 
 pub struct DefaultSwapRetryDelay<T> {
 	_phantom: PhantomData<T>,
@@ -68,12 +86,15 @@ impl<T: Config> Get<BlockNumberFor<T>> for DefaultSwapRetryDelay<T> {
 struct FeeTaken {
 	pub remaining_amount: AssetAmount,
 	pub fee: AssetAmount,
+	/// Whether the fee was determined by the minimum fee rather than by the fee rate.
+	pub minimum_applied: bool,
 }
 
 #[derive(CloneNoBound, DebugNoBound)]
 pub struct SwapState<T: Config> {
 	pub swap: Swap<T>,
 	pub network_fee_taken: Option<AssetAmount>,
+	pub network_fee_minimum_applied: bool,
 	pub broker_fee_taken: Option<AssetAmount>,
 	pub stable_amount: Option<AssetAmount>,
 	pub final_output: Option<AssetAmount>,
@@ -85,6 +106,7 @@ impl<T: Config> SwapState<T> {
 			stable_amount: if swap.from == STABLE_ASSET { Some(swap.input_amount) } else { None },
 			final_output: if swap.from == swap.to { Some(swap.input_amount) } else { None },
 			network_fee_taken: None,
+			network_fee_minimum_applied: false,
 			broker_fee_taken: None,
 			swap,
 		}
@@ -229,6 +251,10 @@ pub enum BatchExecutionError<T: Config> {
 struct BatchExecutionOutcomes<T: Config> {
 	successful_swaps: Vec<SwapState<T>>,
 	failed_swaps: Vec<Swap<T>>,
+	/// The assets of the pools whose swap leg failed at least once.
+	failed_pools: BTreeSet<Asset>,
+	/// The failed swaps that were removed from the batch for not meeting their minimum price.
+	price_violations: BTreeSet<u64>,
 }
 
 /// This impl is never used. This is purely used to satisfy trait requirement
@@ -253,6 +279,9 @@ pub struct DcaState {
 	remaining_chunks: u32,
 	chunk_interval: u32,
 	accumulated_output_amount: AssetAmount,
+	completed_chunks: u32,
+	/// The input accepted for the request so far, including any top-ups.
+	total_input_amount: AssetAmount,
 }
 
 impl DcaState {
@@ -270,6 +299,8 @@ impl DcaState {
 			// set a reasonable default than unwrap Option when it is needed:
 			chunk_interval: params.as_ref().map(|p| p.chunk_interval).unwrap_or(SWAP_DELAY_BLOCKS),
 			accumulated_output_amount: 0,
+			completed_chunks: 0,
+			total_input_amount: input_amount,
 		};
 
 		let first_chunk_amount = state.prepare_next_chunk(None).unwrap_or_else(|| {
@@ -299,6 +330,7 @@ impl DcaState {
 
 			self.status = DcaStatus::ChunkToBeScheduled;
 			self.accumulated_output_amount += prev_chunk_output_amount;
+			self.completed_chunks.saturating_accrue(1);
 		}
 
 		let chunk_input_amount = self
@@ -317,6 +349,30 @@ impl DcaState {
 	}
 }
 
+/// The amounts swapped so far for a swap request, summed over the [Event::SwapExecuted] events of
+/// all of its chunks.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct SwapRequestExecution {
+	pub input_amount: AssetAmount,
+	pub output_amount: AssetAmount,
+	/// The volume-weighted average price of the executed swaps: the output per unit of input.
+	pub average_price: Price,
+}
+
+impl SwapRequestExecution {
+	fn record(&mut self, input_amount: AssetAmount, output_amount: AssetAmount) {
+		self.input_amount.saturating_accrue(input_amount);
+		self.output_amount.saturating_accrue(output_amount);
+		if self.input_amount > 0 {
+			self.average_price = mul_div_floor(
+				self.output_amount.into(),
+				Price::one() << PRICE_FRACTIONAL_BITS,
+				self.input_amount,
+			);
+		}
+	}
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(CloneNoBound, DebugNoBound, PartialEq, Eq, Encode, Decode, TypeInfo)]
 #[scale_info(skip_type_params(T))]
@@ -326,9 +382,16 @@ enum SwapRequestState<T: Config> {
 		output_address: ForeignChainAddress,
 		dca_state: DcaState,
 		broker_fees: Beneficiaries<T::AccountId>,
+		egress_memo: Option<EgressMemo>,
 	},
 	NetworkFee,
 	IngressEgressFee,
+	/// Both the output and any refund are credited to the account's free balance.
+	OnChainSwap {
+		account_id: T::AccountId,
+		dca_state: DcaState,
+		refund_params: Option<ChannelRefundParameters<T::AccountId>>,
+	},
 }
 
 #[derive(CloneNoBound, DebugNoBound, PartialEq, Eq, Encode, Decode, TypeInfo)]
@@ -339,12 +402,181 @@ struct SwapRequest<T: Config> {
 	output_asset: Asset,
 	refund_params: Option<ChannelRefundParametersDecoded>,
 	state: SwapRequestState<T>,
+	tracking_id: Option<TrackingId>,
+}
+
+/// Cumulative network fees taken by the pallet, in USDC, split by the kind of swap they were
+/// taken from.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct NetworkFeeTotals {
+	/// Fees taken from user swaps.
+	pub regular_swaps: AssetAmount,
+	/// Fees taken from internal swaps, such as ingress/egress fee and gas swaps.
+	pub internal_swaps: AssetAmount,
+}
+
+/// The network fee that applies to a swap: a rate charged on the USDC amount, subject to a
+/// minimum fee per chunk.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct FeeRateAndMinimum {
+	pub rate: Permill,
+	pub minimum: AssetAmount,
+}
+
+/// The fees expected to be taken from a swap at current prices, in USDC.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct BrokerFeeEstimate<AccountId> {
+	/// The input amount converted into USDC, before any fees are taken.
+	pub stable_amount: AssetAmount,
+	pub network_fee: AssetAmount,
+	/// The fee credited to each beneficiary, in the order the beneficiaries were given.
+	pub broker_fees: Vec<(AccountId, AssetAmount)>,
+}
+
+/// Where the refund of a swap request is sent.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum RefundDestination<AccountId> {
+	/// Egressed to the refund address.
+	Address(ForeignChainAddress),
+	/// Credited to the free balance of the account that requested the on-chain swap.
+	Account(AccountId),
+}
+
+/// What a swap request would return if it were refunded now.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub struct RefundOutcomeEstimate<AccountId> {
+	pub refund_asset: Asset,
+	/// The input that hasn't been swapped yet, before fees.
+	pub gross_refund_amount: AssetAmount,
+	/// The estimated egress fee in the refund asset. Zero if the refund is credited to an account.
+	pub egress_fee: AssetAmount,
+	/// What the destination receives. Zero if the egress fee exceeds the refund, in which case
+	/// the refund is not sent.
+	pub net_refund_amount: AssetAmount,
+	pub destination: RefundDestination<AccountId>,
+	/// The output of DCA chunks swapped so far. It's sent to the output address rather than
+	/// refunded.
+	pub swapped_output: (Asset, AssetAmount),
+}
+
+/// Controls the order in which scheduled swaps are executed and how many of them are included in a
+/// single batch. Swaps are ordered by priority first and by age (oldest first) second.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct SwapExecutionPolicy {
+	/// The maximum number of swaps included in a single batch. Swaps that don't fit in the batch
+	/// are retried in a later block without counting as failed attempts. `None` means there is
+	/// no limit.
+	pub max_swaps_per_batch: Option<u32>,
+	/// Swaps that are not small and have failed at least this many times are executed after all
+	/// other swaps. `None` disables deprioritisation.
+	pub deprioritise_after_failures: Option<u32>,
+}
+
+/// How the network fee portion of boost fees is converted into FLIP.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum BoostNetworkFeeRoute {
+	/// Swapped like other network fees, without being charged a network fee.
+	#[default]
+	Standard,
+	/// Swapped like internal swaps, i.e. charged the network fee rate without the minimum.
+	Internal,
+}
+
+/// Why a swap was not executed at its scheduled block and was rescheduled instead.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub enum SwapDelayReason {
+	/// Swaps are disabled by safe mode.
+	SwapsDisabled,
+	/// The swap goes through a pool paused by the circuit breaker.
+	PoolPaused { asset: Asset },
+	/// The swap didn't fit in the batch allowed by the execution policy.
+	BatchFull,
+	/// The swap output would have been below the swap's minimum price.
+	MinPriceNotMet { output_asset: Asset },
+}
+
+/// Pauses swaps through a pool after its swap leg has failed in a number of consecutive batches,
+/// so that swaps through a broken pool don't keep being retried.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct PoolCircuitBreaker {
+	/// The number of consecutive batches in which the pool's swap leg must fail for the pool to
+	/// be paused.
+	pub failure_threshold: u32,
+	/// The number of blocks for which swaps through the pool are paused. Paused swaps are retried
+	/// or refunded as if they had failed.
+	pub cool_down_blocks: BlockNumber,
+}
+
+/// Default refund parameters for swap channels opened on a given source chain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct RefundParametersDefaults {
+	/// The retry duration used when a channel is opened with a retry duration of zero.
+	pub retry_duration: BlockNumber,
+	/// The maximum slippage relative to the current market price. Channels opened with a minimum
+	/// price of zero get a minimum price this far below the market price, and lower minimum
+	/// prices are flagged.
+	pub max_oracle_slippage: BasisPoints,
+}
+
+impl RefundParametersDefaults {
+	fn min_price_with_max_slippage(&self, market_price: Price) -> Price {
+		mul_div_floor(
+			market_price,
+			MAX_BASIS_POINTS.saturating_sub(self.max_oracle_slippage).into(),
+			MAX_BASIS_POINTS,
+		)
+	}
+
+	fn deviates_from_retry_duration(&self, retry_duration: BlockNumber) -> bool {
+		retry_duration > self.retry_duration.saturating_mul(REFUND_PARAMETERS_DEVIATION_FACTOR) ||
+			retry_duration.saturating_mul(REFUND_PARAMETERS_DEVIATION_FACTOR) <
+				self.retry_duration
+	}
+}
+
+/// The quote shown to the user when a swap channel was opened.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct SwapQuote {
+	/// The amount the user intends to deposit into the channel.
+	pub input_amount: AssetAmount,
+	/// The output amount the user was quoted for `input_amount`, net of all fees.
+	pub output_amount: AssetAmount,
+}
+
+/// A quote recorded for a swap channel, see [SwapQuotes].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct ChannelSwapQuote {
+	pub channel_id: ChannelId,
+	pub quote: SwapQuote,
+	/// The expiry block of the channel on the source chain. Deposits witnessed after this block
+	/// were made into a later channel that reuses the channel id.
+	pub source_chain_expiry_block: u64,
+}
+
+/// Where funds collected from rejected swaps are sent when they are swept.
+#[derive(Clone, Debug, PartialEq, Eq, Encode, Decode, TypeInfo)]
+pub enum RejectedFundsDestination<AccountId> {
+	/// Add the funds to the withheld ingress/egress fees. Only possible for gas assets.
+	WithheldFees,
+	/// Credit the funds to the free balance of the given account, e.g. a treasury account.
+	Account(AccountId),
+	/// Egress the funds to the given address.
+	Egress(EncodedAddress),
+}
+
+/// Priority bucket of a scheduled swap. Lower values are executed first.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum SwapPriority {
+	Small,
+	Regular,
+	Deprioritised,
 }
 
 #[derive(Clone, RuntimeDebugNoBound, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 #[scale_info(skip_type_params(T, I))]
 pub enum PalletConfigUpdate<T: Config> {
-	/// Set the maximum amount allowed to be put into a swap. Excess amounts are confiscated.
+	/// Set the maximum amount allowed to be put into a swap. Excess amounts are confiscated,
+	/// unless refunding of the excess is enabled.
 	MaximumSwapAmount { asset: Asset, amount: Option<AssetAmount> },
 	/// Set the delay in blocks before retrying a previously failed swap.
 	SwapRetryDelay { delay: BlockNumberFor<T> },
@@ -363,13 +595,40 @@ pub enum PalletConfigUpdate<T: Config> {
 	SetBrokerBond { bond: T::Amount },
 	/// Set the minimum fee in USDC paid per chunk
 	SetMinimumNetworkFeePerChunk { min_fee: AssetAmount },
+	/// Set the limits for CCMs egressed to the given chain. `None` removes the limits.
+	SetCcmLimits { chain: ForeignChain, limits: Option<CcmLimits> },
+	/// Set the policy used to order and limit the swaps executed in a batch.
+	SetSwapExecutionPolicy { policy: SwapExecutionPolicy },
+	/// Set the input amount up to which swaps of the given asset are considered small and are
+	/// executed first. `None` removes the threshold.
+	SetSmallSwapThreshold { asset: Asset, threshold: Option<AssetAmount> },
+	/// Set the default refund parameters for channels opened on the given source chain. `None`
+	/// removes the defaults.
+	SetDefaultRefundParameters { chain: ForeignChain, defaults: Option<RefundParametersDefaults> },
+	/// Set whether amounts above the maximum swap amount are refunded to the refund address
+	/// instead of being confiscated.
+	SetRefundExcessSwapAmount { enabled: bool },
+	/// Set the maximum multiple of the current market price that the minimum price of a swap
+	/// channel may have. `None` disables the check.
+	SetMaxMinPriceMultiple { multiple: Option<u32> },
+	/// Set the circuit breaker that pauses swaps through repeatedly failing pools. `None`
+	/// disables it.
+	SetPoolCircuitBreaker { circuit_breaker: Option<PoolCircuitBreaker> },
+	/// Resume swaps through the pool of the given asset and reset its failure count.
+	ResetPoolCircuitBreaker { asset: Asset },
+	/// Set the maximum number of swaps that can be scheduled for the same block. Swaps scheduled
+	/// beyond this limit are moved to the next block with capacity, searching a bounded number of
+	/// blocks ahead. `None` removes the limit.
+	SetMaxSwapsScheduledPerBlock { max_swaps: Option<u32> },
+	/// Set how the network fee portion of boost fees is converted into FLIP.
+	SetBoostNetworkFeeRoute { route: BoostNetworkFeeRoute },
 }
 
 impl_pallet_safe_mode! {
 	PalletSafeMode; swaps_enabled, withdrawals_enabled, broker_registration_enabled,
 }
 
-fn address_error_to_pallet_error<T>(error: AddressError) -> Error<T>
+pub fn address_error_to_pallet_error<T>(error: AddressError) -> Error<T>
 where
 	T: Config,
 {
@@ -383,7 +642,9 @@ where
 pub mod pallet {
 	use core::cmp::max;
 
-	use cf_amm::math::{output_amount_ceil, sqrt_price_to_price, SqrtPriceQ64F96};
+	use cf_amm::math::{
+		output_amount_ceil, output_amount_floor, sqrt_price_to_price, Price, SqrtPriceQ64F96,
+	};
 	use cf_chains::{address::EncodedAddress, AnyChain, Chain};
 	use cf_primitives::{
 		AffiliateShortId, Asset, AssetAmount, BasisPoints, BlockNumber, DcaParameters, EgressId,
@@ -446,6 +707,9 @@ pub mod pallet {
 			AccountId = <Self as frame_system::Config>::AccountId,
 			Amount = <Self as Chainflip>::Amount,
 		>;
+
+		/// A hook for other pallets to react to executed user swaps.
+		type OnSwapCompleted: OnSwapCompleted<<Self as frame_system::Config>::AccountId>;
 	}
 
 	#[pallet::pallet]
@@ -457,6 +721,25 @@ pub mod pallet {
 	pub(super) type SwapRequests<T: Config> =
 		StorageMap<_, Twox64Concat, SwapRequestId, SwapRequest<T>>;
 
+	/// The amounts swapped so far for swap requests that are still in progress. Removed once the
+	/// request completes, at which point it's included in the [Event::SwapRequestCompleted] event.
+	#[pallet::storage]
+	pub type SwapRequestExecutions<T: Config> =
+		StorageMap<_, Twox64Concat, SwapRequestId, SwapRequestExecution>;
+
+	/// The quotes of swap channels opened with a quote, keyed by source chain and channel id.
+	/// Moved to [SwapRequestQuotes] by the first swap request started by a deposit into the
+	/// channel.
+	#[pallet::storage]
+	pub type SwapQuotes<T: Config> =
+		StorageMap<_, Twox64Concat, (ForeignChain, ChannelId), ChannelSwapQuote>;
+
+	/// The quotes of swap requests in progress. Compared against the execution of the request
+	/// once it completes, see [Event::SwapQuoteExecuted].
+	#[pallet::storage]
+	pub type SwapRequestQuotes<T: Config> =
+		StorageMap<_, Twox64Concat, SwapRequestId, ChannelSwapQuote>;
+
 	/// Scheduled Swaps
 	#[pallet::storage]
 	#[pallet::getter(fn swap_queue)]
@@ -475,11 +758,28 @@ pub mod pallet {
 	pub type CollectedRejectedFunds<T: Config> =
 		StorageMap<_, Twox64Concat, Asset, AssetAmount, ValueQuery>;
 
-	/// Maximum amount allowed to be put into a swap. Excess amounts are confiscated.
+	/// The amount of each asset held by user swap requests: deposited and not yet swapped, or
+	/// swapped and not yet scheduled for egress.
+	#[pallet::storage]
+	pub type InFlightSwapAmounts<T: Config> =
+		StorageMap<_, Twox64Concat, Asset, AssetAmount, ValueQuery>;
+
+	/// Maximum amount allowed to be put into a swap. Excess amounts are confiscated, unless
+	/// `RefundExcessSwapAmount` is enabled.
 	#[pallet::storage]
 	#[pallet::getter(fn maximum_swap_amount)]
 	pub type MaximumSwapAmount<T: Config> = StorageMap<_, Twox64Concat, Asset, AssetAmount>;
 
+	/// If enabled, amounts above the maximum swap amount are refunded to the swap's refund address
+	/// rather than confiscated. Swaps without a refund address still have the excess confiscated.
+	#[pallet::storage]
+	pub type RefundExcessSwapAmount<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	/// How the network fee portion of boost fees is converted into FLIP.
+	#[pallet::storage]
+	pub type BoostNetworkFeeSwapRoute<T: Config> =
+		StorageValue<_, BoostNetworkFeeRoute, ValueQuery>;
+
 	/// FLIP ready to be burned.
 	#[pallet::storage]
 	pub type FlipToBurn<T: Config> = StorageValue<_, AssetAmount, ValueQuery>;
@@ -523,6 +823,12 @@ pub mod pallet {
 	pub type BrokerPrivateBtcChannels<T: Config> =
 		StorageMap<_, Identity, T::AccountId, ChannelId, OptionQuery>;
 
+	/// Brokers that have been suspended, e.g. because their keys have been compromised. Suspended
+	/// brokers can't open channels or withdraw their fees, and deposits on their behalf are
+	/// refunded.
+	#[pallet::storage]
+	pub type SuspendedBrokers<T: Config> = StorageMap<_, Identity, T::AccountId, (), OptionQuery>;
+
 	/// Associates for a given broker an affiliate broker account with short id (u8) so that
 	/// it can be used in place of the full account id in order to save space (e.g. in UTXO encoding
 	/// for BTC)
@@ -537,6 +843,26 @@ pub mod pallet {
 		OptionQuery,
 	>;
 
+	/// The default affiliates of each broker, which can be added to the affiliates of a deposit
+	/// channel when it is opened.
+	#[pallet::storage]
+	pub type BrokerDefaultAffiliates<T: Config> =
+		StorageMap<_, Identity, T::AccountId, Affiliates<T::AccountId>, ValueQuery>;
+
+	/// The destination addresses that CCM swaps of each broker may call, per destination chain.
+	/// CCM swaps to a chain for which the broker has no entry are not restricted. Holds at most
+	/// [MAX_CCM_DESTINATIONS_PER_CHAIN] addresses per chain.
+	#[pallet::storage]
+	pub type CcmDestinationAllowlist<T: Config> = StorageDoubleMap<
+		_,
+		Identity,
+		T::AccountId,
+		Twox64Concat,
+		ForeignChain,
+		BTreeSet<ForeignChainAddress>,
+		ValueQuery,
+	>;
+
 	/// The bond for a broker to open a private channel.
 	#[pallet::storage]
 	pub type BrokerBond<T: Config> = StorageValue<_, T::Amount, ValueQuery, DefaultBrokerBond<T>>;
@@ -547,6 +873,67 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type MinimumNetworkFeePerChunk<T: Config> = StorageValue<_, AssetAmount, ValueQuery>;
 
+	/// Limits for CCMs, keyed by destination chain. CCMs to chains without an entry are only
+	/// subject to the chain-specific validity checks.
+	#[pallet::storage]
+	pub type CcmLimitsPerChain<T: Config> =
+		StorageMap<_, Twox64Concat, ForeignChain, CcmLimits, OptionQuery>;
+
+	/// The policy used to order and limit the swaps executed in a batch.
+	#[pallet::storage]
+	pub type ExecutionPolicy<T: Config> = StorageValue<_, SwapExecutionPolicy, ValueQuery>;
+
+	/// Swaps with an input amount up to this threshold are considered small and are given
+	/// priority during execution.
+	#[pallet::storage]
+	pub type SmallSwapThreshold<T: Config> =
+		StorageMap<_, Twox64Concat, Asset, AssetAmount, OptionQuery>;
+
+	/// Default refund parameters, keyed by the source chain of the swap channel.
+	#[pallet::storage]
+	pub type DefaultRefundParameters<T: Config> =
+		StorageMap<_, Twox64Concat, ForeignChain, RefundParametersDefaults, OptionQuery>;
+
+	/// Swap channels can't be opened with a minimum price above this multiple of the current
+	/// market price, since every chunk of the swap would be refunded.
+	#[pallet::storage]
+	pub type MaxMinPriceMultiple<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+	/// The maximum number of swaps that can be scheduled for execution in the same block. No limit
+	/// if not set.
+	#[pallet::storage]
+	pub type MaxSwapsScheduledPerBlock<T: Config> = StorageValue<_, u32, OptionQuery>;
+
+	/// The circuit breaker applied to pools whose swap leg keeps failing. Disabled if not set.
+	#[pallet::storage]
+	pub type PoolCircuitBreakerConfig<T: Config> = StorageValue<_, PoolCircuitBreaker, OptionQuery>;
+
+	/// The number of consecutive batches in which the swap leg through the pool of the given
+	/// asset has failed.
+	#[pallet::storage]
+	pub type ConsecutivePoolFailures<T: Config> =
+		StorageMap<_, Twox64Concat, Asset, u32, ValueQuery>;
+
+	/// Pools through which swaps are paused by the circuit breaker, and the block at which swaps
+	/// through them resume.
+	#[pallet::storage]
+	pub type PausedPools<T: Config> =
+		StorageMap<_, Twox64Concat, Asset, BlockNumberFor<T>, OptionQuery>;
+
+	/// Cumulative network fees taken from swaps.
+	#[pallet::storage]
+	pub type NetworkFeesTaken<T: Config> = StorageValue<_, NetworkFeeTotals, ValueQuery>;
+
+	/// Cumulative network fees charged outside of swaps (currently the network fee portion of
+	/// boost fees), in the asset they were charged in.
+	#[pallet::storage]
+	pub type BoostNetworkFeesTaken<T: Config> =
+		StorageMap<_, Twox64Concat, Asset, AssetAmount, ValueQuery>;
+
+	/// The number of times each scheduled swap has failed to execute.
+	#[pallet::storage]
+	pub type FailedSwapAttempts<T: Config> = StorageMap<_, Twox64Concat, SwapId, u32, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -557,13 +944,23 @@ pub mod pallet {
 			input_amount: AssetAmount, // includes broker fee
 			output_asset: Asset,
 			origin: SwapOrigin<T::AccountId>,
-			request_type: SwapRequestTypeEncoded,
+			request_type: SwapRequestTypeEncoded<T::AccountId>,
 			broker_fees: Beneficiaries<T::AccountId>,
 			refund_parameters: Option<ChannelRefundParametersEncoded>,
 			dca_parameters: Option<DcaParameters>,
+			tracking_id: Option<TrackingId>,
 		},
+		/// A swap request has been completed. `execution` holds the amounts swapped across all of
+		/// its chunks, and is `None` if none of them were executed.
 		SwapRequestCompleted {
 			swap_request_id: SwapRequestId,
+			execution: Option<SwapRequestExecution>,
+		},
+		/// The input of a swap request has been increased. The additional input is spread over
+		/// the remaining chunks.
+		SwapRequestToppedUp {
+			swap_request_id: SwapRequestId,
+			additional_input_amount: AssetAmount,
 		},
 		/// An new swap deposit channel has been opened.
 		SwapDepositAddressReady {
@@ -581,6 +978,7 @@ pub mod pallet {
 			affiliate_fees: Affiliates<T::AccountId>,
 			refund_parameters: Option<ChannelRefundParametersEncoded>,
 			dca_parameters: Option<DcaParameters>,
+			tracking_id: Option<TrackingId>,
 		},
 		/// A swap is scheduled for the first time
 		SwapScheduled {
@@ -604,9 +1002,23 @@ pub mod pallet {
 			// this amount excludes all fees (e.g. network fee, broker fee, etc.)
 			input_amount: AssetAmount,
 			network_fee: AssetAmount,
+			/// Whether the network fee was determined by the minimum network fee rather than by
+			/// the network fee rate.
+			network_fee_minimum_applied: bool,
 			broker_fee: AssetAmount,
 			intermediate_amount: Option<AssetAmount>,
 			output_amount: AssetAmount,
+			tracking_id: Option<TrackingId>,
+		},
+		/// A chunk of a DCA swap request has been executed. `chunk_index` is zero-based, and the
+		/// remaining amounts include the next chunk if one has been scheduled.
+		DcaChunkCompleted {
+			swap_request_id: SwapRequestId,
+			swap_id: SwapId,
+			chunk_index: u32,
+			chunks_remaining: u32,
+			accumulated_output_amount: AssetAmount,
+			remaining_input_amount: AssetAmount,
 		},
 		/// A swap egress has been scheduled.
 		SwapEgressScheduled {
@@ -615,6 +1027,7 @@ pub mod pallet {
 			asset: Asset,
 			amount: AssetAmount,
 			egress_fee: (AssetAmount, Asset),
+			tracking_id: Option<TrackingId>,
 		},
 		RefundEgressScheduled {
 			swap_request_id: SwapRequestId,
@@ -623,6 +1036,14 @@ pub mod pallet {
 			amount: AssetAmount,
 			egress_fee: (AssetAmount, Asset),
 		},
+		/// Refunds of the same asset to the same address were combined into a single egress.
+		AggregatedRefundEgressScheduled {
+			swap_request_ids: Vec<SwapRequestId>,
+			egress_id: EgressId,
+			asset: Asset,
+			amount: AssetAmount,
+			egress_fee: (AssetAmount, Asset),
+		},
 		/// A broker fee withdrawal has been requested.
 		WithdrawalRequested {
 			egress_id: EgressId,
@@ -648,6 +1069,14 @@ pub mod pallet {
 			total_amount: AssetAmount,
 			confiscated_amount: AssetAmount,
 		},
+		/// The amount above the maximum swap amount is being refunded to the refund address.
+		SwapAmountExcessRefunded {
+			swap_request_id: SwapRequestId,
+			asset: Asset,
+			total_amount: AssetAmount,
+			swap_amount: AssetAmount,
+			refunded_amount: AssetAmount,
+		},
 		SwapEgressIgnored {
 			swap_request_id: SwapRequestId,
 			asset: Asset,
@@ -685,18 +1114,173 @@ pub mod pallet {
 			broker_id: T::AccountId,
 			channel_id: ChannelId,
 		},
+		/// The deposit address of a private channel changed because the Bitcoin vault was rotated.
+		PrivateBrokerChannelAddressRotated {
+			broker_id: T::AccountId,
+			channel_id: ChannelId,
+			deposit_address: EncodedAddress,
+		},
 		AffiliateRegistrationUpdated {
 			broker_id: T::AccountId,
 			affiliate_short_id: AffiliateShortId,
 			affiliate_id: T::AccountId,
 			previous_affiliate_id: Option<T::AccountId>,
 		},
+		AffiliateDeregistered {
+			broker_id: T::AccountId,
+			affiliate_short_id: AffiliateShortId,
+			affiliate_id: T::AccountId,
+		},
+		DefaultAffiliatesSet {
+			broker_id: T::AccountId,
+			affiliate_fees: Affiliates<T::AccountId>,
+		},
 		BrokerBondSet {
 			bond: T::Amount,
 		},
 		MinimumNetworkFeeSet {
 			min_fee: AssetAmount,
 		},
+		CcmLimitsSet {
+			chain: ForeignChain,
+			limits: Option<CcmLimits>,
+		},
+		SwapExecutionPolicySet {
+			policy: SwapExecutionPolicy,
+		},
+		SmallSwapThresholdSet {
+			asset: Asset,
+			threshold: Option<AssetAmount>,
+		},
+		DefaultRefundParametersSet {
+			chain: ForeignChain,
+			defaults: Option<RefundParametersDefaults>,
+		},
+		RefundExcessSwapAmountSet {
+			enabled: bool,
+		},
+		/// The refund parameters supplied when opening a channel differ significantly from the
+		/// defaults for the source chain.
+		RefundParametersDeviateFromDefaults {
+			channel_id: ChannelId,
+			retry_duration: BlockNumber,
+			default_retry_duration: BlockNumber,
+		},
+		MaxMinPriceMultipleSet {
+			multiple: Option<u32>,
+		},
+		/// The minimum price supplied when opening a channel is above the current market price,
+		/// so the swap will be refunded unless the price moves.
+		MinPriceAboveMarketPrice {
+			channel_id: ChannelId,
+			min_price: Price,
+			market_price: Price,
+		},
+		/// The minimum price supplied when opening a channel allows for more slippage than the
+		/// default maximum oracle slippage of the source chain.
+		MinPriceBelowMaxOracleSlippage {
+			channel_id: ChannelId,
+			min_price: Price,
+			min_price_with_max_slippage: Price,
+		},
+		/// An account has requested a swap of its free balance.
+		OnChainSwapRequested {
+			swap_request_id: SwapRequestId,
+			account_id: T::AccountId,
+			retry_duration: BlockNumber,
+			min_price: Price,
+		},
+		/// The output of a swap has been credited to the account's free balance.
+		SwapOutputCredited {
+			swap_request_id: SwapRequestId,
+			account_id: T::AccountId,
+			asset: Asset,
+			amount: AssetAmount,
+		},
+		/// A swap has been refunded to the account's free balance.
+		RefundCredited {
+			swap_request_id: SwapRequestId,
+			account_id: T::AccountId,
+			asset: Asset,
+			amount: AssetAmount,
+		},
+		PoolCircuitBreakerSet {
+			circuit_breaker: Option<PoolCircuitBreaker>,
+		},
+		/// The swap leg through the pool has failed too many times in a row, so swaps through the
+		/// pool are paused.
+		PoolCircuitBreakerTripped {
+			asset: Asset,
+			consecutive_failures: u32,
+			paused_until: BlockNumberFor<T>,
+		},
+		/// Swaps through the pool have resumed, either because the cool-down has elapsed or
+		/// because the circuit breaker was reset by governance.
+		PoolCircuitBreakerReset {
+			asset: Asset,
+		},
+		MaxSwapsScheduledPerBlockSet {
+			max_swaps: Option<u32>,
+		},
+		BrokerSuspended {
+			broker_id: T::AccountId,
+		},
+		BrokerUnsuspended {
+			broker_id: T::AccountId,
+		},
+		/// The quote shown to the user was recorded when opening the swap channel, along with the
+		/// market price at the time, if there is one.
+		SwapQuoteRecorded {
+			channel_id: ChannelId,
+			source_asset: Asset,
+			destination_asset: Asset,
+			quote: SwapQuote,
+			market_price: Option<Price>,
+		},
+		/// A swap request started by a deposit into a channel opened with a quote has completed.
+		/// `quoted_output_amount` is the quoted output scaled to the input amount that was
+		/// actually swapped, for comparison with the executed output amount.
+		SwapQuoteExecuted {
+			swap_request_id: SwapRequestId,
+			channel_id: ChannelId,
+			quote: SwapQuote,
+			quoted_output_amount: AssetAmount,
+			execution: SwapRequestExecution,
+		},
+		/// Funds collected from rejected swaps were swept. `egress_id` is set if the funds were
+		/// egressed.
+		CollectedRejectedFundsSwept {
+			asset: Asset,
+			amount: AssetAmount,
+			destination: RejectedFundsDestination<T::AccountId>,
+			egress_id: Option<EgressId>,
+		},
+		CcmDestinationAllowed {
+			broker_id: T::AccountId,
+			destination_address: EncodedAddress,
+		},
+		CcmDestinationDisallowed {
+			broker_id: T::AccountId,
+			destination_address: EncodedAddress,
+		},
+		/// The deposit that started the swap request was marked for rejection after it was
+		/// boosted, so the output is refunded, or confiscated if there is no refund address,
+		/// instead of being sent to the destination.
+		SwapOutputDivertedForRejectedDeposit {
+			swap_request_id: SwapRequestId,
+			asset: Asset,
+			amount: AssetAmount,
+		},
+		BoostNetworkFeeRouteSet {
+			route: BoostNetworkFeeRoute,
+		},
+		/// The swap was rescheduled without being attempted, or because its output didn't meet
+		/// its minimum price, so its execution is delayed for the given reason.
+		SwapDelayed {
+			swap_request_id: SwapRequestId,
+			swap_id: SwapId,
+			reason: SwapDelayReason,
+		},
 	}
 	#[pallet::error]
 	pub enum Error<T> {
@@ -726,6 +1310,10 @@ pub mod pallet {
 		ZeroBuyIntervalNotAllowed,
 		/// Setting the swap retry delay to zero is not allowed.
 		ZeroSwapRetryDelayNotAllowed,
+		/// Limiting the number of swaps per batch to zero is not allowed.
+		ZeroMaxSwapsPerBatchNotAllowed,
+		/// Limiting the number of swaps scheduled per block to zero is not allowed.
+		ZeroMaxSwapsScheduledPerBlockNotAllowed,
 		/// Setting the max swap request duration to less than the swap delay is not allowed.
 		MaxSwapRequestDurationTooShort,
 		/// Swap Retry duration is set above the max allowed.
@@ -754,10 +1342,44 @@ pub mod pallet {
 		AffiliateFeeTooHigh,
 		/// The affiliate id is not registered with the broker.
 		AffiliateNotRegistered,
-		/// Bitcoin vault swaps only support up to 2 affiliates.
+		/// Too many affiliates: Bitcoin vault swaps only support up to 2 affiliates, and the
+		/// combined explicit and default affiliates of a channel must not exceed the maximum.
 		TooManyAffiliates,
 		/// The Bonder does not have enough Funds to cover the bond.
 		InsufficientFunds,
+		/// Affiliates should withdraw their earned fees before being deregistered.
+		AffiliateEarnedFeesNotWithdrawn,
+		/// The minimum price exceeds the current market price by more than the allowed multiple.
+		MinPriceTooFarAboveMarketPrice,
+		/// Swaps are disabled due to Safe Mode.
+		SwapsDisabled,
+		/// The input and output assets of a swap must differ.
+		SameInputAndOutputAsset,
+		/// The circuit breaker failure threshold and cool-down must be greater than zero.
+		InvalidPoolCircuitBreaker,
+		/// There is no market price for the given assets.
+		NoMarketPrice,
+		/// The broker has been suspended.
+		BrokerSuspended,
+		/// The quoted input amount must be greater than zero.
+		InvalidQuote,
+		/// The channel could not be opened immediately, so the quote can't be linked to it.
+		QuotedChannelOpenDeferred,
+		/// Only funds in a gas asset can be added to the withheld fees.
+		NotAGasAsset,
+		/// Broker cannot deregister while it owns deposit channels that have not been recycled
+		/// yet, since they can still receive deposits.
+		UnrecycledDepositChannelsExistForBroker,
+		/// The destination address of the CCM is not on the broker's allowlist.
+		CcmDestinationNotAllowed,
+		/// The broker has already allowed the maximum number of CCM destinations on this chain.
+		TooManyCcmDestinations,
+		/// The destination chain is being wound down and no longer accepts new swaps.
+		DestinationChainWindingDown,
+		/// The swap request does not exist. It may have completed already.
+		UnknownSwapRequest,
+		/// The swap request can't be refunded, for example because it has no refund parameters.
+		SwapRequestNotRefundable,
 	}
 
 	#[pallet::genesis_config]
@@ -792,7 +1414,7 @@ pub mod pallet {
 				{
 					weight_used.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
 					CollectedNetworkFee::<T>::mutate(|collected_fee| {
-						Self::init_swap_request(
+						Self::init_swap_request_inner(
 							Asset::Usdc,
 							*collected_fee,
 							Asset::Flip,
@@ -801,6 +1423,7 @@ pub mod pallet {
 							None, /* no refund */
 							None, /* no DCA */
 							SwapOrigin::Internal,
+							None, /* no tracking id */
 						);
 
 						collected_fee.set_zero();
@@ -812,40 +1435,102 @@ pub mod pallet {
 
 		/// Execute all swaps in the SwapQueue
 		fn on_finalize(current_block: BlockNumberFor<T>) {
-			let swaps_to_execute = SwapQueue::<T>::take(current_block);
 			let retry_block = current_block + max(SwapRetryDelay::<T>::get(), 1u32.into());
 
 			if !T::SafeMode::get().swaps_enabled {
 				// Since we won't be executing swaps at this block, we need to reschedule them:
-				for swap in swaps_to_execute {
-					Self::reschedule_swap(swap, retry_block);
+				for swap in SwapQueue::<T>::take(current_block) {
+					Self::reschedule_swap(swap, retry_block, Some(SwapDelayReason::SwapsDisabled));
 				}
 
 				return
 			}
 
-			let BatchExecutionOutcomes { successful_swaps, failed_swaps } =
-				Self::execute_batch(swaps_to_execute.clone());
+			let paused_pools = Self::update_paused_pools(current_block);
+			let (swaps_to_execute, swaps_through_paused_pools): (Vec<_>, Vec<_>) =
+				SwapQueue::<T>::take(current_block).into_iter().partition(|swap| {
+					!utilities::pools(swap.from, swap.to).any(|asset| paused_pools.contains(&asset))
+				});
+
+			let (swaps_to_execute, deferred_swaps) = Self::prioritise_swaps(swaps_to_execute);
+
+			// Swaps that didn't fit in the batch were never attempted, so they are always retried,
+			// even if their refund block has been reached:
+			for swap in deferred_swaps {
+				Self::reschedule_swap(swap, retry_block, Some(SwapDelayReason::BatchFull));
+			}
+
+			let mut delay_reasons: BTreeMap<u64, SwapDelayReason> = swaps_through_paused_pools
+				.iter()
+				.filter_map(|swap| {
+					utilities::pools(swap.from, swap.to)
+						.find(|asset| paused_pools.contains(asset))
+						.map(|asset| (swap.swap_id.0, SwapDelayReason::PoolPaused { asset }))
+				})
+				.collect();
+
+			let BatchExecutionOutcomes {
+				successful_swaps,
+				mut failed_swaps,
+				failed_pools,
+				price_violations,
+			} = Self::execute_batch(swaps_to_execute);
+
+			Self::update_pool_circuit_breakers(current_block, &successful_swaps, failed_pools);
 
 			for swap in successful_swaps {
 				Self::process_swap_outcome(swap);
 			}
 
+			for swap in &failed_swaps {
+				FailedSwapAttempts::<T>::mutate(swap.swap_id, |attempts| {
+					attempts.saturating_accrue(1)
+				});
+				if price_violations.contains(&swap.swap_id.0) {
+					delay_reasons.insert(
+						swap.swap_id.0,
+						SwapDelayReason::MinPriceNotMet { output_asset: swap.to },
+					);
+				}
+			}
+			// Swaps that go through a paused pool are retried or refunded like failed swaps, but
+			// don't count as failed attempts:
+			failed_swaps.extend(swaps_through_paused_pools);
+
+			let refund_due = |swap: &Swap<T>| {
+				matches!(
+					swap.refund_params,
+					Some(ref params) if BlockNumberFor::<T>::from(params.refund_block) < retry_block
+				)
+			};
+
+			// Refunds of the same asset to the same address are aggregated into a single egress.
+			let mut refunds_per_destination = BTreeMap::<_, u32>::new();
+			for swap in failed_swaps.iter().filter(|swap| refund_due(swap)) {
+				if let Some(destination) = Self::refund_destination(swap.swap_request_id) {
+					refunds_per_destination.entry(destination).or_default().saturating_accrue(1);
+				}
+			}
+			let mut aggregated_refunds = BTreeMap::new();
+
 			for swap in failed_swaps {
-				match swap.refund_params {
-					Some(ref params)
-						if BlockNumberFor::<T>::from(params.refund_block) < retry_block =>
-					{
-						// Reached refund block, process refund:
-						Self::refund_failed_swap(swap);
-					},
-					_ => {
-						// Either refund parameters not set, or refund block not
-						// reached:
-						Self::reschedule_swap(swap, retry_block);
-					},
+				if refund_due(&swap) {
+					// Reached refund block, process refund:
+					let aggregated_refund = Self::refund_destination(swap.swap_request_id)
+						.filter(|destination| refunds_per_destination.get(destination) > Some(&1))
+						.map(|destination| aggregated_refunds.entry(destination).or_default());
+					Self::refund_failed_swap(swap, aggregated_refund);
+				} else {
+					// Either refund parameters not set, or refund block not
+					// reached:
+					let reason = delay_reasons.remove(&swap.swap_id.0);
+					Self::reschedule_swap(swap, retry_block, reason);
 				}
 			}
+
+			for ((asset, address), refunds) in aggregated_refunds {
+				Self::egress_aggregated_refund(asset, address, refunds);
+			}
 		}
 	}
 
@@ -898,6 +1583,7 @@ pub mod pallet {
 			ensure!(T::SafeMode::get().withdrawals_enabled, Error::<T>::WithdrawalsDisabled);
 
 			let account_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+			Self::ensure_broker_not_suspended(&account_id)?;
 
 			let destination_address_internal =
 				T::AddressConverter::decode_and_validate_address_for_asset(
@@ -906,6 +1592,8 @@ pub mod pallet {
 				)
 				.map_err(address_error_to_pallet_error::<T>)?;
 
+			T::BalanceApi::check_withdrawal_address(&account_id, &destination_address)?;
+
 			let earned_fees = T::BalanceApi::get_balance(&account_id, asset);
 			ensure!(earned_fees != 0, Error::<T>::NoFundsAvailable);
 			T::BalanceApi::try_debit_account(&account_id, asset, earned_fees)?;
@@ -916,6 +1604,8 @@ pub mod pallet {
 					earned_fees,
 					destination_address_internal,
 					None,
+					None,
+					EgressOrigin::Withdrawal,
 				)
 				.map_err(Into::into)?;
 
@@ -1007,6 +1697,65 @@ pub mod pallet {
 						MinimumNetworkFeePerChunk::<T>::set(min_fee);
 						Self::deposit_event(Event::<T>::MinimumNetworkFeeSet { min_fee });
 					},
+					PalletConfigUpdate::SetCcmLimits { chain, limits } => {
+						CcmLimitsPerChain::<T>::set(chain, limits);
+						Self::deposit_event(Event::<T>::CcmLimitsSet { chain, limits });
+					},
+					PalletConfigUpdate::SetSwapExecutionPolicy { policy } => {
+						ensure!(
+							policy.max_swaps_per_batch != Some(0),
+							Error::<T>::ZeroMaxSwapsPerBatchNotAllowed
+						);
+						ExecutionPolicy::<T>::set(policy);
+						Self::deposit_event(Event::<T>::SwapExecutionPolicySet { policy });
+					},
+					PalletConfigUpdate::SetSmallSwapThreshold { asset, threshold } => {
+						SmallSwapThreshold::<T>::set(asset, threshold);
+						Self::deposit_event(Event::<T>::SmallSwapThresholdSet { asset, threshold });
+					},
+					PalletConfigUpdate::SetDefaultRefundParameters { chain, defaults } => {
+						DefaultRefundParameters::<T>::set(chain, defaults);
+						Self::deposit_event(Event::<T>::DefaultRefundParametersSet {
+							chain,
+							defaults,
+						});
+					},
+					PalletConfigUpdate::SetRefundExcessSwapAmount { enabled } => {
+						RefundExcessSwapAmount::<T>::set(enabled);
+						Self::deposit_event(Event::<T>::RefundExcessSwapAmountSet { enabled });
+					},
+					PalletConfigUpdate::SetMaxMinPriceMultiple { multiple } => {
+						MaxMinPriceMultiple::<T>::set(multiple);
+						Self::deposit_event(Event::<T>::MaxMinPriceMultipleSet { multiple });
+					},
+					PalletConfigUpdate::SetPoolCircuitBreaker { circuit_breaker } => {
+						ensure!(
+							circuit_breaker.map_or(true, |circuit_breaker| {
+								circuit_breaker.failure_threshold > 0 &&
+									circuit_breaker.cool_down_blocks > 0
+							}),
+							Error::<T>::InvalidPoolCircuitBreaker
+						);
+						PoolCircuitBreakerConfig::<T>::set(circuit_breaker);
+						Self::deposit_event(Event::<T>::PoolCircuitBreakerSet { circuit_breaker });
+					},
+					PalletConfigUpdate::ResetPoolCircuitBreaker { asset } => {
+						PausedPools::<T>::remove(asset);
+						ConsecutivePoolFailures::<T>::remove(asset);
+						Self::deposit_event(Event::<T>::PoolCircuitBreakerReset { asset });
+					},
+					PalletConfigUpdate::SetMaxSwapsScheduledPerBlock { max_swaps } => {
+						ensure!(
+							max_swaps != Some(0),
+							Error::<T>::ZeroMaxSwapsScheduledPerBlockNotAllowed
+						);
+						MaxSwapsScheduledPerBlock::<T>::set(max_swaps);
+						Self::deposit_event(Event::<T>::MaxSwapsScheduledPerBlockSet { max_swaps });
+					},
+					PalletConfigUpdate::SetBoostNetworkFeeRoute { route } => {
+						BoostNetworkFeeSwapRoute::<T>::set(route);
+						Self::deposit_event(Event::<T>::BoostNetworkFeeRouteSet { route });
+					},
 				}
 			}
 
@@ -1017,21 +1766,38 @@ pub mod pallet {
 		///
 		/// Account roles are immutable once registered.
 		#[pallet::call_index(9)]
-		#[pallet::weight(T::WeightInfo::deregister_as_broker())]
+		#[pallet::weight(T::WeightInfo::deregister_as_broker().saturating_add(
+			// Expiring the broker's rejection requests on every chain.
+			T::DbWeight::get().reads_writes(
+				MAX_REJECTION_REQUESTS_EXPIRED_ON_DEREGISTRATION as u64 *
+					ForeignChain::iter().count() as u64,
+				MAX_REJECTION_REQUESTS_EXPIRED_ON_DEREGISTRATION as u64 *
+					ForeignChain::iter().count() as u64,
+			)
+		))]
 		pub fn deregister_as_broker(who: OriginFor<T>) -> DispatchResult {
 			let account_id = T::AccountRoleRegistry::ensure_broker(who)?;
+			Self::ensure_broker_not_suspended(&account_id)?;
 
 			ensure!(
 				!BrokerPrivateBtcChannels::<T>::contains_key(&account_id),
 				Error::<T>::PrivateChannelExistsForBroker
 			);
 
+			ensure!(
+				!T::DepositHandler::has_unrecycled_deposit_channels(&account_id),
+				Error::<T>::UnrecycledDepositChannelsExistForBroker
+			);
+
 			ensure!(
 				T::BalanceApi::free_balances(&account_id).iter().all(|(_, amount)| *amount == 0),
 				Error::<T>::EarnedFeesNotWithdrawn,
 			);
 
 			let _ = AffiliateIdMapping::<T>::clear_prefix(&account_id, u32::MAX, None);
+			BrokerDefaultAffiliates::<T>::remove(&account_id);
+			let _ = CcmDestinationAllowlist::<T>::clear_prefix(&account_id, u32::MAX, None);
+			T::DepositHandler::expire_rejection_requests(&account_id);
 
 			T::AccountRoleRegistry::deregister_as_broker(&account_id)?;
 
@@ -1057,21 +1823,540 @@ pub mod pallet {
 			refund_parameters: Option<ChannelRefundParametersEncoded>,
 			dca_parameters: Option<DcaParameters>,
 		) -> DispatchResult {
-			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
-
-			let mut beneficiaries = Beneficiaries::new();
-			for beneficiary in [Beneficiary { account: broker.clone(), bps: broker_commission }]
-				.into_iter()
-				.chain(affiliate_fees.iter().cloned())
-			{
-				if beneficiary.bps > 0 {
-					beneficiaries.try_push(beneficiary).expect(
-						"We are pushing affiiliates + 1 which is exactly the maximum Beneficiaries size",
-					);
-				}
-			}
+			Self::request_swap_deposit_address_with_parameters(
+				origin,
+				source_asset,
+				destination_asset,
+				destination_address,
+				broker_commission,
+				channel_metadata,
+				boost_fee,
+				affiliate_fees,
+				refund_parameters,
+				dca_parameters,
+				// Kept for backwards compatibility. Tracking ids, default affiliates and egress
+				// memos are only supported by `request_swap_deposit_address_with_parameters`.
+				None,
+				false,
+				None,
+			)
+		}
 
-			Pallet::<T>::validate_broker_fees(&beneficiaries)?;
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::open_private_btc_channel())]
+		pub fn open_private_btc_channel(origin: OriginFor<T>) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+			Self::ensure_broker_not_suspended(&broker_id)?;
+
+			ensure!(
+				!BrokerPrivateBtcChannels::<T>::contains_key(&broker_id),
+				Error::<T>::PrivateChannelExistsForBroker
+			);
+
+			ensure!(
+				T::FundingInfo::total_balance_of(&broker_id) >= BrokerBond::<T>::get(),
+				Error::<T>::InsufficientFunds
+			);
+
+			let channel_id = T::ChannelIdAllocator::allocate_private_channel_id()?;
+
+			BrokerPrivateBtcChannels::<T>::insert(broker_id.clone(), channel_id);
+
+			T::Bonder::update_bond(&broker_id, BrokerBond::<T>::get());
+
+			Self::deposit_event(Event::<T>::PrivateBrokerChannelOpened { broker_id, channel_id });
+
+			Ok(())
+		}
+
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::close_private_btc_channel())]
+		pub fn close_private_btc_channel(origin: OriginFor<T>) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let Some(channel_id) = BrokerPrivateBtcChannels::<T>::take(&broker_id) else {
+				return Err(Error::<T>::NoPrivateChannelExistsForBroker.into())
+			};
+
+			T::Bonder::update_bond(&broker_id, 0u128.into());
+
+			Self::deposit_event(Event::<T>::PrivateBrokerChannelClosed { broker_id, channel_id });
+
+			Ok(())
+		}
+
+		/// Associates `short_id` with `affiliate_id` for a given broker. Overwrites the record
+		/// under `short_id` if already taken by another affiliate.
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::register_affiliate())]
+		pub fn register_affiliate(
+			origin: OriginFor<T>,
+			affiliate_id: T::AccountId,
+			short_id: AffiliateShortId,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let previous_affiliate_id = AffiliateIdMapping::<T>::take(&broker_id, short_id);
+
+			AffiliateIdMapping::<T>::insert(&broker_id, short_id, &affiliate_id);
+
+			Self::deposit_event(Event::<T>::AffiliateRegistrationUpdated {
+				broker_id,
+				affiliate_short_id: short_id,
+				affiliate_id,
+				previous_affiliate_id,
+			});
+
+			Ok(())
+		}
+
+		/// Removes the affiliate registered under `short_id` for the calling broker, freeing the
+		/// short id for reuse. The affiliate must not hold any unwithdrawn fees.
+		///
+		/// Note that vault swaps referencing the short id after it has been freed will no longer
+		/// pay the affiliate fee, so short ids should only be removed once no longer in use.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::deregister_affiliate())]
+		pub fn deregister_affiliate(
+			origin: OriginFor<T>,
+			short_id: AffiliateShortId,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let affiliate_id = AffiliateIdMapping::<T>::get(&broker_id, short_id)
+				.ok_or(Error::<T>::AffiliateNotRegistered)?;
+
+			ensure!(
+				T::BalanceApi::free_balances(&affiliate_id)
+					.iter()
+					.all(|(_, amount)| *amount == 0),
+				Error::<T>::AffiliateEarnedFeesNotWithdrawn,
+			);
+
+			AffiliateIdMapping::<T>::remove(&broker_id, short_id);
+
+			Self::deposit_event(Event::<T>::AffiliateDeregistered {
+				broker_id,
+				affiliate_short_id: short_id,
+				affiliate_id,
+			});
+
+			Ok(())
+		}
+
+		/// Sets the default affiliates of the calling broker, replacing any previous defaults.
+		/// An empty list removes the defaults.
+		///
+		/// The defaults are only applied to deposit channels opened with `use_default_affiliates`.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::set_default_affiliates())]
+		pub fn set_default_affiliates(
+			origin: OriginFor<T>,
+			affiliate_fees: Affiliates<T::AccountId>,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			Self::validate_broker_fees(
+				&affiliate_fees.iter().cloned().collect::<Vec<_>>().try_into().expect(
+					"Affiliates are bounded by less than the maximum number of beneficiaries",
+				),
+			)?;
+
+			if affiliate_fees.is_empty() {
+				BrokerDefaultAffiliates::<T>::remove(&broker_id);
+			} else {
+				BrokerDefaultAffiliates::<T>::insert(&broker_id, &affiliate_fees);
+			}
+
+			Self::deposit_event(Event::<T>::DefaultAffiliatesSet { broker_id, affiliate_fees });
+
+			Ok(())
+		}
+
+		/// Swaps some of the caller's free balance. The output is credited back to the caller's
+		/// free balance once all chunks have been swapped. If the swap cannot be executed at
+		/// `min_price` or better within `retry_duration` blocks, the remaining input (along with
+		/// any output swapped so far) is credited back instead.
+		///
+		/// Only available to Liquidity Providers and Brokers. Any amount above the maximum swap
+		/// amount for the input asset is left in the caller's account.
+		///
+		/// ## Events
+		///
+		/// - [SwapRequested](Event::SwapRequested)
+		/// - [OnChainSwapRequested](Event::OnChainSwapRequested)
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::swap_on_chain())]
+		pub fn swap_on_chain(
+			origin: OriginFor<T>,
+			input_asset: Asset,
+			input_amount: AssetAmount,
+			output_asset: Asset,
+			retry_duration: BlockNumber,
+			min_price: Price,
+			dca_parameters: Option<DcaParameters>,
+		) -> DispatchResult {
+			let account_id = ensure_signed(origin)?;
+			ensure!(
+				T::AccountRoleRegistry::has_account_role(
+					&account_id,
+					AccountRole::LiquidityProvider
+				) || T::AccountRoleRegistry::has_account_role(&account_id, AccountRole::Broker),
+				frame_support::sp_runtime::traits::BadOrigin
+			);
+			Self::swap_from_on_chain_balance(
+				&account_id,
+				input_asset,
+				input_amount,
+				output_asset,
+				retry_duration,
+				min_price,
+				dca_parameters,
+			)?;
+
+			Ok(())
+		}
+
+		/// Suspends the calling broker with immediate effect. Intended for brokers whose keys
+		/// have been compromised. Only governance can lift the suspension.
+		///
+		/// ## Events
+		///
+		/// - [BrokerSuspended](Event::BrokerSuspended)
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::suspend_broker())]
+		pub fn suspend_broker(origin: OriginFor<T>) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			Self::set_broker_suspended(broker_id, true);
+
+			Ok(())
+		}
+
+		/// Suspends or unsuspends a broker.
+		///
+		/// Requires Governance.
+		///
+		/// ## Events
+		///
+		/// - [BrokerSuspended](Event::BrokerSuspended)
+		/// - [BrokerUnsuspended](Event::BrokerUnsuspended)
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::suspend_broker())]
+		pub fn set_broker_suspension(
+			origin: OriginFor<T>,
+			broker_id: T::AccountId,
+			suspended: bool,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			Self::set_broker_suspended(broker_id, suspended);
+
+			Ok(())
+		}
+
+		/// Request a swap deposit address, recording the quote shown to the user together with
+		/// the market price at the time the channel is opened. The quote is compared against the
+		/// execution of the swap started by the first deposit into the channel.
+		///
+		/// Fails if the channel open is deferred, since the quote can't be linked to a channel that
+		/// hasn't been opened yet. Otherwise identical to
+		/// [request_swap_deposit_address_with_parameters](Call::request_swap_deposit_address_with_parameters).
+		///
+		/// ## Events
+		///
+		/// - [SwapDepositAddressReady](Event::SwapDepositAddressReady)
+		/// - [SwapQuoteRecorded](Event::SwapQuoteRecorded)
+		/// - [SwapQuoteExecuted](Event::SwapQuoteExecuted)
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::request_swap_deposit_address_with_quote())]
+		pub fn request_swap_deposit_address_with_quote(
+			origin: OriginFor<T>,
+			source_asset: Asset,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			broker_commission: BasisPoints,
+			channel_metadata: Option<CcmChannelMetadata>,
+			boost_fee: BasisPoints,
+			affiliate_fees: Affiliates<T::AccountId>,
+			refund_parameters: Option<ChannelRefundParametersEncoded>,
+			dca_parameters: Option<DcaParameters>,
+			tracking_id: Option<TrackingId>,
+			use_default_affiliates: bool,
+			egress_memo: Option<EgressMemo>,
+			quote: SwapQuote,
+		) -> DispatchResult {
+			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			ensure!(quote.input_amount > 0, Error::<T>::InvalidQuote);
+
+			let (channel_id, source_chain_expiry_block) = Self::open_swap_deposit_channel(
+				broker,
+				source_asset,
+				destination_asset,
+				destination_address,
+				broker_commission,
+				channel_metadata,
+				boost_fee,
+				affiliate_fees,
+				refund_parameters,
+				dca_parameters,
+				tracking_id,
+				use_default_affiliates,
+				egress_memo,
+			)?
+			.ok_or(Error::<T>::QuotedChannelOpenDeferred)?;
+
+			SwapQuotes::<T>::insert(
+				(ForeignChain::from(source_asset), channel_id),
+				ChannelSwapQuote { channel_id, quote, source_chain_expiry_block },
+			);
+			Self::deposit_event(Event::<T>::SwapQuoteRecorded {
+				channel_id,
+				source_asset,
+				destination_asset,
+				quote,
+				market_price: T::SwappingApi::swap_rate(source_asset, destination_asset),
+			});
+
+			Ok(())
+		}
+
+		/// Sweeps all funds of the given asset collected from rejected swaps to the given
+		/// destination.
+		///
+		/// Requires Governance.
+		///
+		/// ## Events
+		///
+		/// - [CollectedRejectedFundsSwept](Event::CollectedRejectedFundsSwept)
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::sweep_collected_rejected_funds())]
+		pub fn sweep_collected_rejected_funds(
+			origin: OriginFor<T>,
+			asset: Asset,
+			destination: RejectedFundsDestination<T::AccountId>,
+		) -> DispatchResult {
+			T::EnsureGovernance::ensure_origin(origin)?;
+
+			let amount = CollectedRejectedFunds::<T>::get(asset);
+			ensure!(amount != 0, Error::<T>::NoFundsAvailable);
+
+			let egress_id = match &destination {
+				RejectedFundsDestination::WithheldFees => {
+					ensure!(
+						asset == ForeignChain::from(asset).gas_asset(),
+						Error::<T>::NotAGasAsset
+					);
+					T::IngressEgressFeeHandler::accrue_withheld_fee(asset, amount);
+					None
+				},
+				RejectedFundsDestination::Account(account_id) => {
+					T::BalanceApi::credit_account(account_id, asset, amount);
+					None
+				},
+				RejectedFundsDestination::Egress(destination_address) => {
+					let destination_address =
+						T::AddressConverter::decode_and_validate_address_for_asset(
+							destination_address.clone(),
+							asset,
+						)
+						.map_err(address_error_to_pallet_error::<T>)?;
+
+					let ScheduledEgressDetails { egress_id, .. } =
+						T::EgressHandler::schedule_egress(
+							asset,
+							amount,
+							destination_address,
+							None,
+							None,
+							EgressOrigin::Rejection,
+						)
+						.map_err(Into::into)?;
+					Some(egress_id)
+				},
+			};
+
+			CollectedRejectedFunds::<T>::remove(asset);
+
+			Self::deposit_event(Event::<T>::CollectedRejectedFundsSwept {
+				asset,
+				amount,
+				destination,
+				egress_id,
+			});
+
+			Ok(())
+		}
+
+		/// Adds an address to the destinations that the calling broker's CCM swaps may call.
+		/// Once a broker has allowed a destination on a chain, CCM swaps of the broker to any
+		/// other destination on that chain are rejected. At most [MAX_CCM_DESTINATIONS_PER_CHAIN]
+		/// destinations can be allowed per chain.
+		///
+		/// ## Events
+		///
+		/// - [CcmDestinationAllowed](Event::CcmDestinationAllowed)
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::update_ccm_destination_allowlist())]
+		pub fn allow_ccm_destination(
+			origin: OriginFor<T>,
+			destination_address: EncodedAddress,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let address =
+				T::AddressConverter::try_from_encoded_address(destination_address.clone())
+					.map_err(|_| Error::<T>::InvalidDestinationAddress)?;
+			CcmDestinationAllowlist::<T>::try_mutate(&broker_id, address.chain(), |allowlist| {
+				ensure!(
+					allowlist.contains(&address) ||
+						allowlist.len() < MAX_CCM_DESTINATIONS_PER_CHAIN as usize,
+					Error::<T>::TooManyCcmDestinations
+				);
+				allowlist.insert(address);
+				Ok::<_, DispatchError>(())
+			})?;
+
+			Self::deposit_event(Event::<T>::CcmDestinationAllowed {
+				broker_id,
+				destination_address,
+			});
+
+			Ok(())
+		}
+
+		/// Removes an address from the destinations that the calling broker's CCM swaps may
+		/// call. Removing the last address of a chain lifts the restriction for that chain.
+		///
+		/// ## Events
+		///
+		/// - [CcmDestinationDisallowed](Event::CcmDestinationDisallowed)
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::update_ccm_destination_allowlist())]
+		pub fn disallow_ccm_destination(
+			origin: OriginFor<T>,
+			destination_address: EncodedAddress,
+		) -> DispatchResult {
+			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			let address =
+				T::AddressConverter::try_from_encoded_address(destination_address.clone())
+					.map_err(|_| Error::<T>::InvalidDestinationAddress)?;
+			CcmDestinationAllowlist::<T>::mutate_exists(&broker_id, address.chain(), |allowlist| {
+				if let Some(addresses) = allowlist {
+					addresses.remove(&address);
+					if addresses.is_empty() {
+						*allowlist = None;
+					}
+				}
+			});
+
+			Self::deposit_event(Event::<T>::CcmDestinationDisallowed {
+				broker_id,
+				destination_address,
+			});
+
+			Ok(())
+		}
+
+		/// Request a swap deposit address, with optional parameters that aren't supported by
+		/// [request_swap_deposit_address_with_affiliates](Call::request_swap_deposit_address_with_affiliates).
+		///
+		/// `tracking_id` is an opaque identifier chosen by the broker that is included in the
+		/// events of the channel's deposits and swaps.
+		///
+		/// If `use_default_affiliates` is set, the broker's default affiliates are added to the
+		/// given `affiliate_fees`. Explicitly provided affiliates take precedence over default
+		/// affiliates with the same account.
+		///
+		/// `egress_memo` is attached to the egress of the swap output, for destinations such as
+		/// exchange deposit addresses that require one. Only chains that support memos accept it.
+		///
+		/// ## Events
+		///
+		/// - [SwapDepositAddressReady](Event::SwapDepositAddressReady)
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::request_swap_deposit_address_with_affiliates())]
+		pub fn request_swap_deposit_address_with_parameters(
+			origin: OriginFor<T>,
+			source_asset: Asset,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			broker_commission: BasisPoints,
+			channel_metadata: Option<CcmChannelMetadata>,
+			boost_fee: BasisPoints,
+			affiliate_fees: Affiliates<T::AccountId>,
+			refund_parameters: Option<ChannelRefundParametersEncoded>,
+			dca_parameters: Option<DcaParameters>,
+			tracking_id: Option<TrackingId>,
+			use_default_affiliates: bool,
+			egress_memo: Option<EgressMemo>,
+		) -> DispatchResult {
+			let broker = T::AccountRoleRegistry::ensure_broker(origin)?;
+
+			Self::open_swap_deposit_channel(
+				broker,
+				source_asset,
+				destination_asset,
+				destination_address,
+				broker_commission,
+				channel_metadata,
+				boost_fee,
+				affiliate_fees,
+				refund_parameters,
+				dca_parameters,
+				tracking_id,
+				use_default_affiliates,
+				egress_memo,
+			)?;
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Opens a swap deposit channel on behalf of the broker and returns its channel id and its
+		/// expiry block on the source chain. Returns `None` if the channel open has been deferred,
+		/// in which case the broker is notified by the ingress-egress pallet once the channel is
+		/// ready.
+		#[allow(clippy::too_many_arguments)]
+		fn open_swap_deposit_channel(
+			broker: T::AccountId,
+			source_asset: Asset,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			broker_commission: BasisPoints,
+			channel_metadata: Option<CcmChannelMetadata>,
+			boost_fee: BasisPoints,
+			affiliate_fees: Affiliates<T::AccountId>,
+			refund_parameters: Option<ChannelRefundParametersEncoded>,
+			dca_parameters: Option<DcaParameters>,
+			tracking_id: Option<TrackingId>,
+			use_default_affiliates: bool,
+			egress_memo: Option<EgressMemo>,
+		) -> Result<Option<(ChannelId, <AnyChain as Chain>::ChainBlockNumber)>, DispatchError> {
+			Self::ensure_broker_not_suspended(&broker)?;
+
+			let affiliate_fees = if use_default_affiliates {
+				Self::merge_default_affiliates(&broker, affiliate_fees)?
+			} else {
+				affiliate_fees
+			};
+
+			let mut beneficiaries = Beneficiaries::new();
+			for beneficiary in [Beneficiary { account: broker.clone(), bps: broker_commission }]
+				.into_iter()
+				.chain(affiliate_fees.iter().cloned())
+			{
+				if beneficiary.bps > 0 {
+					beneficiaries.try_push(beneficiary).expect(
+					"We are pushing affiiliates + 1 which is exactly the maximum Beneficiaries size",
+				);
+				}
+			}
+
+			Pallet::<T>::validate_broker_fees(&beneficiaries)?;
 
 			let destination_address_internal =
 				T::AddressConverter::decode_and_validate_address_for_asset(
@@ -1079,6 +2364,39 @@ pub mod pallet {
 					destination_asset,
 				)
 				.map_err(address_error_to_pallet_error::<T>)?;
+			Self::validate_destination(&destination_address_internal)?;
+
+			let source_chain = ForeignChain::from(source_asset);
+			let refund_defaults = DefaultRefundParameters::<T>::get(source_chain);
+			let min_price_with_max_slippage = refund_defaults.and_then(|defaults| {
+				T::SwappingApi::swap_rate(source_asset, destination_asset)
+					.map(|market_price| defaults.min_price_with_max_slippage(market_price))
+			});
+			// Channels opened without refund parameters get the chain's defaults if the refund
+			// address can be derived, which is only the case when the destination address is on
+			// the source chain. Otherwise the channel is opened without refund parameters.
+			let refund_parameters = refund_parameters.or_else(|| {
+				refund_defaults
+					.filter(|_| ForeignChain::from(destination_asset) == source_chain)
+					.map(|defaults| ChannelRefundParametersEncoded {
+						retry_duration: defaults.retry_duration,
+						refund_address: destination_address.clone(),
+						min_price: Default::default(),
+					})
+			});
+			// A retry duration or minimum price of zero is treated as not set and replaced by the
+			// chain's default.
+			let refund_parameters = refund_parameters.map(|mut params| {
+				if let Some(defaults) = refund_defaults.filter(|_| params.retry_duration == 0) {
+					params.retry_duration = defaults.retry_duration;
+				}
+				if let Some(min_price) =
+					min_price_with_max_slippage.filter(|_| params.min_price.is_zero())
+				{
+					params.min_price = min_price;
+				}
+				params
+			});
 
 			// Convert the refund parameter from `EncodedAddress` into `ForeignChainAddress` type.
 			let refund_params_internal = refund_parameters
@@ -1091,6 +2409,14 @@ pub mod pallet {
 				})
 				.transpose()?;
 
+			let market_price_below_min_price = refund_parameters
+				.as_ref()
+				.map(|params| {
+					Self::check_min_price(source_asset, destination_asset, params.min_price)
+				})
+				.transpose()?
+				.flatten();
+
 			if let Some(ccm) = channel_metadata.as_ref() {
 				let destination_chain: ForeignChain = destination_asset.into();
 				ensure!(destination_chain.ccm_support(), Error::<T>::CcmUnsupportedForTargetChain);
@@ -1105,10 +2431,12 @@ pub mod pallet {
 						Error::<T>::InvalidCcm
 					},
 				)?;
+
+				Self::validate_ccm_destination(&broker, &destination_address_internal)?;
 			}
 
 			let (channel_id, deposit_address, expiry_height, channel_opening_fee) =
-				T::DepositHandler::request_swap_deposit_address(
+				match T::DepositHandler::request_swap_deposit_address(
 					source_asset,
 					destination_asset,
 					destination_address_internal,
@@ -1118,7 +2446,46 @@ pub mod pallet {
 					boost_fee,
 					refund_params_internal,
 					dca_parameters.clone(),
-				)?;
+					tracking_id,
+					egress_memo,
+				) {
+					Ok(channel) => channel,
+					Err(error) if T::DepositHandler::is_deferred_channel_open(&error) =>
+						return Ok(None),
+					Err(error) => return Err(error),
+				};
+
+			if let (Some(defaults), Some(params)) = (refund_defaults, refund_parameters.as_ref()) {
+				if defaults.deviates_from_retry_duration(params.retry_duration) {
+					Self::deposit_event(Event::<T>::RefundParametersDeviateFromDefaults {
+						channel_id,
+						retry_duration: params.retry_duration,
+						default_retry_duration: defaults.retry_duration,
+					});
+				}
+			}
+
+			if let (Some(min_price_with_max_slippage), Some(params)) =
+				(min_price_with_max_slippage, refund_parameters.as_ref())
+			{
+				if params.min_price < min_price_with_max_slippage {
+					Self::deposit_event(Event::<T>::MinPriceBelowMaxOracleSlippage {
+						channel_id,
+						min_price: params.min_price,
+						min_price_with_max_slippage,
+					});
+				}
+			}
+
+			if let (Some(market_price), Some(params)) =
+				(market_price_below_min_price, refund_parameters.as_ref())
+			{
+				Self::deposit_event(Event::<T>::MinPriceAboveMarketPrice {
+					channel_id,
+					min_price: params.min_price,
+					market_price,
+				});
+			}
 
 			Self::deposit_event(Event::<T>::SwapDepositAddressReady {
 				deposit_address: T::AddressConverter::to_encoded_address(deposit_address),
@@ -1135,90 +2502,60 @@ pub mod pallet {
 				affiliate_fees,
 				refund_parameters,
 				dca_parameters,
+				tracking_id,
 			});
 
-			Ok(())
+			Ok(Some((channel_id, expiry_height)))
 		}
 
-		#[pallet::call_index(12)]
-		#[pallet::weight(T::WeightInfo::open_private_btc_channel())]
-		pub fn open_private_btc_channel(origin: OriginFor<T>) -> DispatchResult {
-			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
-
-			ensure!(
-				!BrokerPrivateBtcChannels::<T>::contains_key(&broker_id),
-				Error::<T>::PrivateChannelExistsForBroker
-			);
-
-			ensure!(
-				T::FundingInfo::total_balance_of(&broker_id) >= BrokerBond::<T>::get(),
-				Error::<T>::InsufficientFunds
-			);
-
-			let channel_id = T::ChannelIdAllocator::allocate_private_channel_id()?;
-
-			BrokerPrivateBtcChannels::<T>::insert(broker_id.clone(), channel_id);
-
-			T::Bonder::update_bond(&broker_id, BrokerBond::<T>::get());
-
-			Self::deposit_event(Event::<T>::PrivateBrokerChannelOpened { broker_id, channel_id });
-
-			Ok(())
-		}
-
-		#[pallet::call_index(13)]
-		#[pallet::weight(T::WeightInfo::close_private_btc_channel())]
-		pub fn close_private_btc_channel(origin: OriginFor<T>) -> DispatchResult {
-			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
-
-			let Some(channel_id) = BrokerPrivateBtcChannels::<T>::take(&broker_id) else {
-				return Err(Error::<T>::NoPrivateChannelExistsForBroker.into())
-			};
-
-			T::Bonder::update_bond(&broker_id, 0u128.into());
-
-			Self::deposit_event(Event::<T>::PrivateBrokerChannelClosed { broker_id, channel_id });
-
+		fn ensure_broker_not_suspended(broker_id: &T::AccountId) -> DispatchResult {
+			ensure!(!SuspendedBrokers::<T>::contains_key(broker_id), Error::<T>::BrokerSuspended);
 			Ok(())
 		}
 
-		/// Associates `short_id` with `affiliate_id` for a given broker. Overwrites the record
-		/// under `short_id` if already taken by another affiliate.
-		#[pallet::call_index(14)]
-		#[pallet::weight(T::WeightInfo::register_affiliate())]
-		pub fn register_affiliate(
-			origin: OriginFor<T>,
-			affiliate_id: T::AccountId,
-			short_id: AffiliateShortId,
-		) -> DispatchResult {
-			let broker_id = T::AccountRoleRegistry::ensure_broker(origin)?;
-
-			let previous_affiliate_id = AffiliateIdMapping::<T>::take(&broker_id, short_id);
-
-			AffiliateIdMapping::<T>::insert(&broker_id, short_id, &affiliate_id);
-
-			Self::deposit_event(Event::<T>::AffiliateRegistrationUpdated {
-				broker_id,
-				affiliate_short_id: short_id,
-				affiliate_id,
-				previous_affiliate_id,
-			});
-
-			Ok(())
+		fn set_broker_suspended(broker_id: T::AccountId, suspended: bool) {
+			if suspended {
+				if !SuspendedBrokers::<T>::contains_key(&broker_id) {
+					SuspendedBrokers::<T>::insert(&broker_id, ());
+					Self::deposit_event(Event::<T>::BrokerSuspended { broker_id });
+				}
+			} else if SuspendedBrokers::<T>::take(&broker_id).is_some() {
+				Self::deposit_event(Event::<T>::BrokerUnsuspended { broker_id });
+			}
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
 		#[allow(clippy::result_unit_err)]
 		pub fn get_scheduled_swap_legs(
 			swaps: Vec<Swap<T>>,
 			base_asset: Asset,
 			pool_sell_price: Option<SqrtPriceQ64F96>,
+		) -> Vec<SwapLegInfo> {
+			Self::scheduled_swap_legs(swaps, base_asset, pool_sell_price, true)
+		}
+
+		/// Like [Self::get_scheduled_swap_legs], but without simulating the swaps. The amounts of
+		/// legs bought with a non-stable asset are estimated using the pool price only, which is
+		/// much cheaper but less accurate.
+		pub fn estimate_scheduled_swap_legs(
+			swaps: Vec<Swap<T>>,
+			base_asset: Asset,
+			pool_sell_price: Option<SqrtPriceQ64F96>,
+		) -> Vec<SwapLegInfo> {
+			Self::scheduled_swap_legs(swaps, base_asset, pool_sell_price, false)
+		}
+
+		fn scheduled_swap_legs(
+			swaps: Vec<Swap<T>>,
+			base_asset: Asset,
+			pool_sell_price: Option<SqrtPriceQ64F96>,
+			simulate: bool,
 		) -> Vec<SwapLegInfo> {
 			let mut swaps: Vec<_> = swaps.into_iter().map(SwapState::new).collect();
 
-			// Can ignore the result here because we use pool price fallback below
-			let _res = Self::swap_into_stable_taking_fees(&mut swaps);
+			if simulate {
+				// Can ignore the result here because we use pool price fallback below
+				let _res = Self::swap_into_stable_taking_fees(&mut swaps);
+			}
 
 			swaps
 				.into_iter()
@@ -1226,7 +2563,8 @@ pub mod pallet {
 					let swap_request = SwapRequests::<T>::get(swap.swap.swap_request_id)
 						.expect("Swap request should exist");
 					let dca_state = match swap_request.state {
-						SwapRequestState::UserSwap { dca_state, .. } => Some(dca_state),
+						SwapRequestState::UserSwap { dca_state, .. } |
+						SwapRequestState::OnChainSwap { dca_state, .. } => Some(dca_state),
 						_ => None,
 					};
 					let remaining_chunks =
@@ -1258,8 +2596,11 @@ pub mod pallet {
 						};
 
 						let amount = swap.stable_amount.or_else(|| {
-							// If the swap into stable asset failed, fallback to estimating the
-							// amount via pool price.
+							if swap.input_asset() == STABLE_ASSET {
+								return Some(swap.input_amount())
+							}
+							// If the swap into stable asset failed or wasn't simulated, fallback to
+							// estimating the amount via pool price.
 							Some(
 								output_amount_ceil(
 									cf_amm::math::Amount::from(swap.input_amount()),
@@ -1293,36 +2634,111 @@ pub mod pallet {
 			stable_amount: AssetAmount,
 			broker_fees: &Beneficiaries<T::AccountId>,
 		) -> FeeTaken {
-			// Sanity check: it should already not be possible to open a channel with broker fees
-			// this high, but if the total broker fee would exceed 100% we charge no broker fee
-			// instead (for simplicity):
-			let total_fee_bps =
-				broker_fees.iter().fold(0u16, |fee_accumulator, Beneficiary { bps, .. }| {
-					fee_accumulator.saturating_add(*bps)
+			let total_fee = utilities::calculate_broker_fees(stable_amount, broker_fees).fold(
+				0u128,
+				|fee_accumulator, (account, fee)| {
+					T::BalanceApi::credit_account(account, STABLE_ASSET, fee);
+
+					fee_accumulator.saturating_add(fee)
+				},
+			);
+
+			assert!(total_fee <= stable_amount, "Broker fee cannot be more than the amount");
+
+			FeeTaken {
+				remaining_amount: stable_amount.saturating_sub(total_fee),
+				fee: total_fee,
+				minimum_applied: false,
+			}
+		}
+
+		/// Estimates the outcome of refunding the swap request now. `egress_fee` estimates the
+		/// egress fee of the refund asset, `None` if it can't be estimated.
+		pub fn estimate_refund_outcome(
+			swap_request_id: SwapRequestId,
+			egress_fee: impl FnOnce(Asset) -> Option<AssetAmount>,
+		) -> Result<RefundOutcomeEstimate<T::AccountId>, DispatchError> {
+			let request =
+				SwapRequests::<T>::get(swap_request_id).ok_or(Error::<T>::UnknownSwapRequest)?;
+
+			let (dca_state, destination) = match request.state {
+				SwapRequestState::UserSwap { dca_state, .. } => (
+					dca_state,
+					RefundDestination::Address(
+						request
+							.refund_params
+							.ok_or(Error::<T>::SwapRequestNotRefundable)?
+							.refund_address,
+					),
+				),
+				SwapRequestState::OnChainSwap { account_id, dca_state, .. } =>
+					(dca_state, RefundDestination::Account(account_id)),
+				SwapRequestState::NetworkFee | SwapRequestState::IngressEgressFee =>
+					return Err(Error::<T>::SwapRequestNotRefundable.into()),
+			};
+
+			// Same as in `refund_failed_swap`: the input of the scheduled chunk is refunded
+			// together with the input of the chunks that haven't been scheduled yet.
+			let gross_refund_amount = SwapQueue::<T>::iter_values()
+				.flatten()
+				.filter(|swap| swap.swap_request_id == swap_request_id)
+				.fold(dca_state.remaining_input_amount, |total, swap| {
+					total.saturating_add(swap.input_amount)
 				});
 
-			if total_fee_bps > MAX_BASIS_POINTS {
-				FeeTaken { remaining_amount: stable_amount, fee: 0 }
-			} else {
-				let total_fee = broker_fees.iter().fold(
-					0u128,
-					|fee_accumulator, Beneficiary { account, bps }| {
-						let fee = Permill::from_parts(*bps as u32 * BASIS_POINTS_PER_MILLION) *
-							stable_amount;
+			let egress_fee = match destination {
+				RefundDestination::Address(_) =>
+					egress_fee(request.input_asset).ok_or(Error::<T>::NoMarketPrice)?,
+				RefundDestination::Account(_) => 0,
+			};
 
-						T::BalanceApi::credit_account(account, STABLE_ASSET, fee);
+			Ok(RefundOutcomeEstimate {
+				refund_asset: request.input_asset,
+				gross_refund_amount,
+				egress_fee,
+				net_refund_amount: if egress_fee < gross_refund_amount {
+					gross_refund_amount - egress_fee
+				} else {
+					0
+				},
+				destination,
+				swapped_output: (request.output_asset, dca_state.accumulated_output_amount),
+			})
+		}
 
-						fee_accumulator.saturating_add(fee)
-					},
-				);
+		/// Estimates the fees taken from a swap of `input_amount` at current prices, including the
+		/// fee credited to each of the given beneficiaries. Fees are taken in the same order as
+		/// during swap execution, i.e. broker fees are charged on the amount remaining after the
+		/// network fee. DCA swaps may be charged a higher network fee due to the minimum fee per
+		/// chunk.
+		pub fn estimate_broker_fees(
+			input_asset: Asset,
+			output_asset: Asset,
+			input_amount: AssetAmount,
+			broker_fees: &Beneficiaries<T::AccountId>,
+		) -> Result<BrokerFeeEstimate<T::AccountId>, DispatchError> {
+			ensure!(input_asset != output_asset, Error::<T>::SameInputAndOutputAsset);
 
-				assert!(total_fee <= stable_amount, "Broker fee cannot be more than the amount");
+			let stable_amount = if input_asset == STABLE_ASSET {
+				input_amount
+			} else {
+				let price = T::SwappingApi::swap_rate(input_asset, STABLE_ASSET)
+					.ok_or(Error::<T>::NoMarketPrice)?;
+				output_amount_floor(cf_amm::math::Amount::from(input_amount), price)
+					.saturated_into()
+			};
 
-				FeeTaken {
-					remaining_amount: stable_amount.saturating_sub(total_fee),
-					fee: total_fee,
-				}
-			}
+			let FeeRateAndMinimum { rate, minimum } = Self::effective_network_fee(false);
+			let (remaining_amount, network_fee) =
+				utilities::calculate_network_fee(rate, minimum, stable_amount);
+
+			Ok(BrokerFeeEstimate {
+				stable_amount,
+				network_fee,
+				broker_fees: utilities::calculate_broker_fees(remaining_amount, broker_fees)
+					.map(|(account, fee)| (account.clone(), fee))
+					.collect(),
+			})
 		}
 
 		fn swap_into_stable_taking_fees(
@@ -1342,13 +2758,14 @@ pub mod pallet {
 				for fee_type in &swap.swap.fees {
 					let remaining_amount = match fee_type {
 						FeeType::NetworkFee { min_fee_enforced } => {
-							let FeeTaken { remaining_amount, fee } =
+							let FeeTaken { remaining_amount, fee, minimum_applied } =
 								Self::take_network_fee(stable_amount, *min_fee_enforced);
 							swap.network_fee_taken = Some(fee);
+							swap.network_fee_minimum_applied = minimum_applied;
 							remaining_amount
 						},
 						FeeType::BrokerFee(beneficiaries) => {
-							let FeeTaken { remaining_amount, fee } =
+							let FeeTaken { remaining_amount, fee, .. } =
 								Self::take_broker_fees(stable_amount, beneficiaries);
 							swap.broker_fee_taken = Some(fee);
 							remaining_amount
@@ -1397,20 +2814,114 @@ pub mod pallet {
 			}
 		}
 
+		/// Orders the swaps according to the [SwapExecutionPolicy]: small swaps first, then
+		/// regular swaps, then large swaps that have repeatedly failed, with older swaps first
+		/// within each bucket. Returns the swaps to execute and the swaps that don't fit in the
+		/// batch.
+		fn prioritise_swaps(mut swaps: Vec<Swap<T>>) -> (Vec<Swap<T>>, Vec<Swap<T>>) {
+			let policy = ExecutionPolicy::<T>::get();
+
+			swaps.sort_by_cached_key(|swap| {
+				let priority = if SmallSwapThreshold::<T>::get(swap.from)
+					.is_some_and(|threshold| swap.input_amount <= threshold)
+				{
+					SwapPriority::Small
+				} else if policy.deprioritise_after_failures.is_some_and(|max_failures| {
+					FailedSwapAttempts::<T>::get(swap.swap_id) >= max_failures
+				}) {
+					SwapPriority::Deprioritised
+				} else {
+					SwapPriority::Regular
+				};
+				// Swap ids are assigned sequentially, so lower ids belong to older swaps.
+				(priority, swap.swap_id.0)
+			});
+
+			let deferred_swaps = match policy.max_swaps_per_batch {
+				Some(max_swaps) if swaps.len() > max_swaps as usize =>
+					swaps.split_off(max_swaps as usize),
+				_ => vec![],
+			};
+
+			(swaps, deferred_swaps)
+		}
+
+		/// Resumes swaps through pools whose cool-down has elapsed and returns the pools that
+		/// remain paused.
+		fn update_paused_pools(current_block: BlockNumberFor<T>) -> BTreeSet<Asset> {
+			PausedPools::<T>::translate(|asset, paused_until: BlockNumberFor<T>| {
+				if paused_until <= current_block {
+					Self::deposit_event(Event::<T>::PoolCircuitBreakerReset { asset });
+					None
+				} else {
+					Some(paused_until)
+				}
+			});
+			PausedPools::<T>::iter_keys().collect()
+		}
+
+		/// Resets the failure count of pools that were swapped through successfully and pauses
+		/// pools that have failed in too many consecutive batches.
+		fn update_pool_circuit_breakers(
+			current_block: BlockNumberFor<T>,
+			successful_swaps: &[SwapState<T>],
+			failed_pools: BTreeSet<Asset>,
+		) {
+			for asset in successful_swaps
+				.iter()
+				.flat_map(|swap| utilities::pools(swap.input_asset(), swap.output_asset()))
+				.filter(|asset| !failed_pools.contains(asset))
+			{
+				ConsecutivePoolFailures::<T>::remove(asset);
+			}
+
+			let Some(circuit_breaker) = PoolCircuitBreakerConfig::<T>::get() else { return };
+
+			for asset in failed_pools {
+				let consecutive_failures =
+					ConsecutivePoolFailures::<T>::mutate(asset, |failures| {
+						failures.saturating_accrue(1);
+						*failures
+					});
+				if consecutive_failures >= circuit_breaker.failure_threshold {
+					let paused_until = current_block + circuit_breaker.cool_down_blocks.into();
+					ConsecutivePoolFailures::<T>::remove(asset);
+					PausedPools::<T>::insert(asset, paused_until);
+					Self::deposit_event(Event::<T>::PoolCircuitBreakerTripped {
+						asset,
+						consecutive_failures,
+						paused_until,
+					});
+				}
+			}
+		}
+
 		/// Attempts to find (and execute) a batch of swaps that wouldn't result in hitting the
 		/// price impact limit, starting with the given batch, and taking swaps out of the batch if
 		/// needed.
 		fn execute_batch(mut swaps_to_execute: Vec<Swap<T>>) -> BatchExecutionOutcomes<T> {
 			let mut failed_swaps = vec![];
+			let mut failed_pools = BTreeSet::new();
+			let mut price_violations = BTreeSet::new();
 
 			loop {
 				if swaps_to_execute.is_empty() {
-					return BatchExecutionOutcomes { successful_swaps: vec![], failed_swaps };
+					return BatchExecutionOutcomes {
+						successful_swaps: vec![],
+						failed_swaps,
+						failed_pools,
+						price_violations,
+					};
 				}
 
 				match Self::try_execute_without_violations(swaps_to_execute.clone()) {
 					Ok(successful_swaps) =>
-						return BatchExecutionOutcomes { successful_swaps, failed_swaps },
+						return BatchExecutionOutcomes {
+							successful_swaps,
+							failed_swaps,
+							failed_pools,
+							price_violations,
+						},
 					Err(BatchExecutionError::SwapLegFailed {
 						asset,
 						direction,
@@ -1422,6 +2933,7 @@ pub mod pallet {
 							direction,
 							amount,
 						});
+						failed_pools.insert(asset);
 
 						// Find the largest swap from the failing pool/direction and remove it
 						// so we can try the remaining swaps again. We should always be able to
@@ -1440,6 +2952,7 @@ pub mod pallet {
 						violating_swaps,
 						non_violating_swaps,
 					}) => {
+						price_violations.extend(violating_swaps.iter().map(|swap| swap.swap_id.0));
 						failed_swaps.extend(violating_swaps);
 						swaps_to_execute = non_violating_swaps;
 					},
@@ -1454,49 +2967,125 @@ pub mod pallet {
 
 			// If we are here, consider all swaps as failed:
 			failed_swaps.extend(swaps_to_execute);
-			BatchExecutionOutcomes { successful_swaps: vec![], failed_swaps }
+			BatchExecutionOutcomes {
+				successful_swaps: vec![],
+				failed_swaps,
+				failed_pools,
+				price_violations,
+			}
 		}
 
-		fn refund_failed_swap(swap: Swap<T>) {
+		fn refund_destination(
+			swap_request_id: SwapRequestId,
+		) -> Option<(Asset, ForeignChainAddress)> {
+			SwapRequests::<T>::get(swap_request_id).and_then(|request| {
+				Some((request.input_asset, request.refund_params?.refund_address))
+			})
+		}
+
+		/// Refunds the swap request. If `aggregated_refund` is provided, the refund amount is added
+		/// to it instead of being egressed immediately.
+		fn refund_failed_swap(
+			swap: Swap<T>,
+			aggregated_refund: Option<&mut Vec<(SwapRequestId, AssetAmount)>>,
+		) {
 			let swap_request_id = swap.swap_request_id;
+			FailedSwapAttempts::<T>::remove(swap.swap_id);
 
 			let Some(mut request) = SwapRequests::<T>::take(swap_request_id) else {
 				log_or_panic!("Swap request {swap_request_id} not found");
 				return;
 			};
 
-			let Some(refund_params) = &request.refund_params else {
-				log_or_panic!("Trying to refund swap request {swap_request_id}, but missing refund parameters");
-				return;
-			};
-
 			match &mut request.state {
 				SwapRequestState::UserSwap {
 					ccm_deposit_metadata: _,
 					output_address,
 					dca_state: DcaState { remaining_input_amount, accumulated_output_amount, .. },
 					broker_fees: _,
+					egress_memo,
 				} => {
-					Self::egress_for_swap(
-						request.id,
-						swap.input_amount + *remaining_input_amount,
+					Self::reduce_in_flight_amount(
 						request.input_asset,
-						refund_params.refund_address.clone(),
-						None, /* refunds don't use ccm parameters */
-						true, /* refund */
+						swap.input_amount + *remaining_input_amount,
 					);
+					Self::reduce_in_flight_amount(request.output_asset, *accumulated_output_amount);
+
+					let Some(refund_params) = &request.refund_params else {
+						log_or_panic!("Trying to refund swap request {swap_request_id}, but missing refund parameters");
+						return;
+					};
+
+					let refund_amount = swap.input_amount + *remaining_input_amount;
+					let deposit_rejected =
+						T::DepositHandler::take_swap_request_rejection(request.id);
+					if let Some(aggregated_refund) = aggregated_refund {
+						aggregated_refund.push((request.id, refund_amount));
+					} else {
+						Self::egress_for_swap(
+							request.id,
+							refund_amount,
+							request.input_asset,
+							refund_params.refund_address.clone(),
+							None, /* refunds don't use ccm parameters */
+							true, /* refund */
+							request.tracking_id,
+							None, /* refunds don't use egress memos */
+						);
+					}
 
 					// In case of DCA we may have partially swapped and now have some output
 					// asset to egress to the output address:
 					if *accumulated_output_amount > 0 {
-						Self::egress_for_swap(
-							swap.swap_request_id,
-							*accumulated_output_amount,
+						if deposit_rejected {
+							Self::divert_output_for_rejected_deposit(
+								swap.swap_request_id,
+								*accumulated_output_amount,
+								request.output_asset,
+								&request.refund_params,
+								request.tracking_id,
+							);
+						} else {
+							Self::egress_for_swap(
+								swap.swap_request_id,
+								*accumulated_output_amount,
+								request.output_asset,
+								output_address.clone(),
+								None,  /* ccm */
+								false, /* refund */
+								request.tracking_id,
+								egress_memo.clone(),
+							);
+						}
+					}
+				},
+				SwapRequestState::OnChainSwap {
+					account_id,
+					dca_state: DcaState { remaining_input_amount, accumulated_output_amount, .. },
+					refund_params: _,
+				} => {
+					// Nothing is egressed, so there is nothing to aggregate:
+					let refund_amount = swap.input_amount + *remaining_input_amount;
+					T::BalanceApi::credit_account(account_id, request.input_asset, refund_amount);
+					Self::deposit_event(Event::<T>::RefundCredited {
+						swap_request_id,
+						account_id: account_id.clone(),
+						asset: request.input_asset,
+						amount: refund_amount,
+					});
+
+					if *accumulated_output_amount > 0 {
+						T::BalanceApi::credit_account(
+							account_id,
 							request.output_asset,
-							output_address.clone(),
-							None,  /* ccm */
-							false, /* refund */
+							*accumulated_output_amount,
 						);
+						Self::deposit_event(Event::<T>::SwapOutputCredited {
+							swap_request_id,
+							account_id: account_id.clone(),
+							asset: request.output_asset,
+							amount: *accumulated_output_amount,
+						});
 					}
 				},
 				non_refundable_request => {
@@ -1506,11 +3095,38 @@ pub mod pallet {
 				},
 			};
 
-			Self::deposit_event(Event::<T>::SwapRequestCompleted { swap_request_id: request.id });
+			Self::deposit_swap_request_completed(request.id);
+		}
+
+		/// Emits [Event::SwapRequestCompleted], preceded by [Event::SwapQuoteExecuted] if the
+		/// request was quoted.
+		fn deposit_swap_request_completed(swap_request_id: SwapRequestId) {
+			let execution = SwapRequestExecutions::<T>::take(swap_request_id);
+
+			if let (Some(ChannelSwapQuote { channel_id, quote, .. }), Some(execution)) =
+				(SwapRequestQuotes::<T>::take(swap_request_id), execution.clone())
+			{
+				Self::deposit_event(Event::<T>::SwapQuoteExecuted {
+					swap_request_id,
+					channel_id,
+					quote,
+					quoted_output_amount: multiply_by_rational_with_rounding(
+						quote.output_amount,
+						execution.input_amount,
+						quote.input_amount,
+						Rounding::Down,
+					)
+					.unwrap_or(AssetAmount::MAX),
+					execution,
+				});
+			}
+
+			Self::deposit_event(Event::<T>::SwapRequestCompleted { swap_request_id, execution });
 		}
 
 		fn process_swap_outcome(swap: SwapState<T>) {
 			let swap_request_id = swap.swap.swap_request_id;
+			FailedSwapAttempts::<T>::remove(swap.swap_id());
 
 			let Some(mut request) = SwapRequests::<T>::take(swap_request_id) else {
 				log_or_panic!("Swap request {swap_request_id} not found");
@@ -1522,27 +3138,53 @@ pub mod pallet {
 				return;
 			};
 
+			// To be consistent with `swap_output` and `intermediate_amount` (which do not include
+			// the network fee), we report input amount without the network fee for swaps from
+			// STABLE_ASSET:
+			let input_amount = if swap.input_asset() == STABLE_ASSET {
+				swap.stable_amount.unwrap_or_else(|| {
+					log_or_panic!("stable amount must be set for swaps from STABLE_ASSET");
+					swap.input_amount()
+				})
+			} else {
+				swap.input_amount()
+			};
 			Self::deposit_event(Event::<T>::SwapExecuted {
 				swap_request_id,
 				swap_id: swap.swap_id(),
-				// To be consistent with `swap_output` and `intermediate_amount` (which do
-				// not include the network fee), we report input amount without the network fee
-				// for swaps from STABLE_ASSET:
-				input_amount: if swap.input_asset() == STABLE_ASSET {
-					swap.stable_amount.unwrap_or_else(|| {
-						log_or_panic!("stable amount must be set for swaps from STABLE_ASSET");
-						swap.input_amount()
-					})
-				} else {
-					swap.input_amount()
-				},
+				input_amount,
 				input_asset: swap.input_asset(),
 				network_fee: swap.network_fee_taken.unwrap_or_default(),
+				network_fee_minimum_applied: swap.network_fee_minimum_applied,
 				broker_fee: swap.broker_fee_taken.unwrap_or_default(),
 				output_asset: swap.output_asset(),
 				output_amount,
 				intermediate_amount: swap.intermediate_amount(),
+				tracking_id: request.tracking_id,
 			});
+			SwapRequestExecutions::<T>::mutate(swap_request_id, |execution| {
+				execution
+					.get_or_insert_with(Default::default)
+					.record(input_amount, output_amount)
+			});
+
+			let accounts = match &request.state {
+				SwapRequestState::UserSwap { broker_fees, .. } => Some(
+					broker_fees.iter().map(|beneficiary| beneficiary.account.clone()).collect(),
+				),
+				SwapRequestState::OnChainSwap { account_id, .. } => Some(vec![account_id.clone()]),
+				SwapRequestState::NetworkFee | SwapRequestState::IngressEgressFee => None,
+			};
+			if let Some(accounts) = accounts {
+				T::OnSwapCompleted::on_swap_completed(
+					swap_request_id,
+					&accounts,
+					swap.input_asset(),
+					swap.input_amount(),
+					swap.output_asset(),
+					output_amount,
+				);
+			}
 
 			let request_completed = match &mut request.state {
 				SwapRequestState::UserSwap {
@@ -1550,38 +3192,79 @@ pub mod pallet {
 					output_address,
 					dca_state,
 					broker_fees,
-				} =>
-					if let Some(chunk_input_amount) =
-						dca_state.prepare_next_chunk(Some((swap.swap_id(), output_amount)))
-					{
-						let swap_id = Self::schedule_swap(
-							request.input_asset,
-							request.output_asset,
-							chunk_input_amount,
-							request.refund_params.as_ref(),
-							SwapType::Swap,
-							broker_fees.clone(),
-							request.id,
-							dca_state.chunk_interval.into(),
-						);
-
-						dca_state.status = DcaStatus::ChunkScheduled(swap_id);
+					egress_memo,
+				} => {
+					Self::reduce_in_flight_amount(swap.input_asset(), swap.input_amount());
+					Self::accrue_in_flight_amount(swap.output_asset(), output_amount);
 
-						false
-					} else {
-						debug_assert!(dca_state.remaining_input_amount == 0);
+					let all_chunks_swapped = Self::advance_dca(
+						&request.refund_params,
+						request.input_asset,
+						request.output_asset,
+						request.id,
+						&swap,
+						output_amount,
+						dca_state,
+						broker_fees.clone(),
+					);
 
-						Self::egress_for_swap(
-							swap_request_id,
+					if all_chunks_swapped {
+						Self::reduce_in_flight_amount(
+							swap.output_asset(),
 							dca_state.accumulated_output_amount,
+						);
+						if T::DepositHandler::take_swap_request_rejection(swap_request_id) {
+							Self::divert_output_for_rejected_deposit(
+								swap_request_id,
+								dca_state.accumulated_output_amount,
+								swap.output_asset(),
+								&request.refund_params,
+								request.tracking_id,
+							);
+						} else {
+							Self::egress_for_swap(
+								swap_request_id,
+								dca_state.accumulated_output_amount,
+								swap.output_asset(),
+								output_address.clone(),
+								ccm_deposit_metadata.clone(), /* ccm */
+								false,                        /* refund */
+								request.tracking_id,
+								egress_memo.clone(),
+							);
+						}
+					}
+
+					all_chunks_swapped
+				},
+				SwapRequestState::OnChainSwap { account_id, dca_state, refund_params } => {
+					let all_chunks_swapped = Self::advance_dca(
+						refund_params,
+						request.input_asset,
+						request.output_asset,
+						request.id,
+						&swap,
+						output_amount,
+						dca_state,
+						Default::default(),
+					);
+
+					if all_chunks_swapped {
+						T::BalanceApi::credit_account(
+							account_id,
 							swap.output_asset(),
-							output_address.clone(),
-							ccm_deposit_metadata.clone(), /* ccm */
-							false,                        /* refund */
+							dca_state.accumulated_output_amount,
 						);
+						Self::deposit_event(Event::<T>::SwapOutputCredited {
+							swap_request_id,
+							account_id: account_id.clone(),
+							asset: swap.output_asset(),
+							amount: dca_state.accumulated_output_amount,
+						});
+					}
 
-						true
-					},
+					all_chunks_swapped
+				},
 				SwapRequestState::NetworkFee => {
 					if swap.output_asset() == Asset::Flip {
 						FlipToBurn::<T>::mutate(|total| {
@@ -1613,12 +3296,66 @@ pub mod pallet {
 			};
 
 			if request_completed {
-				Self::deposit_event(Event::<T>::SwapRequestCompleted { swap_request_id });
+				Self::deposit_swap_request_completed(swap_request_id);
 			} else {
 				SwapRequests::<T>::insert(swap_request_id, request);
 			}
 		}
 
+		/// Records the output of a completed chunk and schedules the next one, if any. Returns
+		/// `true` once all chunks have been swapped.
+		#[allow(clippy::too_many_arguments)]
+		fn advance_dca<A: Clone>(
+			refund_params: &Option<ChannelRefundParameters<A>>,
+			input_asset: Asset,
+			output_asset: Asset,
+			swap_request_id: SwapRequestId,
+			swap: &SwapState<T>,
+			output_amount: AssetAmount,
+			dca_state: &mut DcaState,
+			broker_fees: Beneficiaries<T::AccountId>,
+		) -> bool {
+			let next_chunk_input_amount =
+				dca_state.prepare_next_chunk(Some((swap.swap_id(), output_amount)));
+
+			let chunks_remaining = dca_state
+				.remaining_chunks
+				.saturating_add(next_chunk_input_amount.map_or(0, |_| 1));
+			// Non-DCA swaps are executed as a single chunk and are not reported here.
+			if dca_state.completed_chunks.saturating_add(chunks_remaining) > 1 {
+				Self::deposit_event(Event::<T>::DcaChunkCompleted {
+					swap_request_id,
+					swap_id: swap.swap_id(),
+					chunk_index: dca_state.completed_chunks.saturating_sub(1),
+					chunks_remaining,
+					accumulated_output_amount: dca_state.accumulated_output_amount,
+					remaining_input_amount: dca_state
+						.remaining_input_amount
+						.saturating_add(next_chunk_input_amount.unwrap_or_default()),
+				});
+			}
+
+			if let Some(chunk_input_amount) = next_chunk_input_amount {
+				let swap_id = Self::schedule_swap(
+					input_asset,
+					output_asset,
+					chunk_input_amount,
+					refund_params.as_ref(),
+					SwapType::Swap,
+					broker_fees,
+					swap_request_id,
+					dca_state.chunk_interval.into(),
+				);
+
+				dca_state.status = DcaStatus::ChunkScheduled(swap_id);
+
+				false
+			} else {
+				debug_assert!(dca_state.remaining_input_amount == 0);
+				true
+			}
+		}
+
 		// Helper function that splits swaps of a given direction, group them by asset
 		// and do the swaps of a given direction. Processed and unprocessed swaps are
 		// returned.
@@ -1714,7 +3451,7 @@ pub mod pallet {
 			input_asset: Asset,
 			output_asset: Asset,
 			input_amount: AssetAmount,
-			refund_params: Option<&ChannelRefundParametersDecoded>,
+			refund_params: Option<&ChannelRefundParameters<impl Clone>>,
 			swap_type: SwapType,
 			broker_fees: Beneficiaries<T::AccountId>,
 			swap_request_id: SwapRequestId,
@@ -1725,7 +3462,9 @@ pub mod pallet {
 				*id
 			});
 
-			let execute_at = frame_system::Pallet::<T>::block_number() + delay_blocks;
+			let execute_at = Self::first_block_with_capacity(
+				frame_system::Pallet::<T>::block_number() + delay_blocks,
+			);
 
 			let refund_params = refund_params.map(|params| {
 				utilities::calculate_swap_refund_parameters(
@@ -1749,6 +3488,11 @@ pub mod pallet {
 					SwapType::NetworkFee => {
 						// Network fee is not charged for network fee swaps
 					},
+					SwapType::BoostNetworkFee => {
+						if BoostNetworkFeeSwapRoute::<T>::get() == BoostNetworkFeeRoute::Internal {
+							fees.push(FeeType::NetworkFee { min_fee_enforced: false });
+						}
+					},
 				}
 
 				if !broker_fees.is_empty() {
@@ -1782,7 +3526,34 @@ pub mod pallet {
 			swap_id
 		}
 
-		fn reschedule_swap(swap: Swap<T>, execute_at: BlockNumberFor<T>) {
+		/// Returns the first block at or after `block` for which fewer than the maximum number of
+		/// swaps are scheduled. At most [MAX_BLOCKS_SEARCHED_FOR_SWAP_CAPACITY] blocks are
+		/// searched, so if they are all full the swap is scheduled for the last of them regardless.
+		fn first_block_with_capacity(mut block: BlockNumberFor<T>) -> BlockNumberFor<T> {
+			if let Some(max_swaps) = MaxSwapsScheduledPerBlock::<T>::get() {
+				for _ in 1..MAX_BLOCKS_SEARCHED_FOR_SWAP_CAPACITY {
+					if SwapQueue::<T>::decode_len(block).unwrap_or_default() < max_swaps as usize {
+						break
+					}
+					block.saturating_inc();
+				}
+			}
+			block
+		}
+
+		fn reschedule_swap(
+			swap: Swap<T>,
+			execute_at: BlockNumberFor<T>,
+			reason: Option<SwapDelayReason>,
+		) {
+			if let Some(reason) = reason {
+				Self::deposit_event(Event::<T>::SwapDelayed {
+					swap_request_id: swap.swap_request_id,
+					swap_id: swap.swap_id,
+					reason,
+				});
+			}
+			let execute_at = Self::first_block_with_capacity(execute_at);
 			Self::deposit_event(Event::<T>::SwapRescheduled { swap_id: swap.swap_id, execute_at });
 			SwapQueue::<T>::append(execute_at, swap);
 		}
@@ -1795,7 +3566,7 @@ pub mod pallet {
 		) -> Result<SwapOutput, DispatchError> {
 			Ok(match (from, to) {
 				(_, STABLE_ASSET) => {
-					let FeeTaken { remaining_amount: output, fee } = Self::take_network_fee(
+					let FeeTaken { remaining_amount: output, fee, .. } = Self::take_network_fee(
 						T::SwappingApi::swap_single_leg(from, to, input_amount)?,
 						false,
 					);
@@ -1803,7 +3574,7 @@ pub mod pallet {
 					SwapOutput { intermediary: None, output, network_fee: fee }
 				},
 				(STABLE_ASSET, _) => {
-					let FeeTaken { remaining_amount: input_amount, fee } =
+					let FeeTaken { remaining_amount: input_amount, fee, .. } =
 						Self::take_network_fee(input_amount, false);
 
 					SwapOutput {
@@ -1813,10 +3584,11 @@ pub mod pallet {
 					}
 				},
 				_ => {
-					let FeeTaken { remaining_amount: intermediary, fee } = Self::take_network_fee(
-						T::SwappingApi::swap_single_leg(from, STABLE_ASSET, input_amount)?,
-						false,
-					);
+					let FeeTaken { remaining_amount: intermediary, fee, .. } =
+						Self::take_network_fee(
+							T::SwappingApi::swap_single_leg(from, STABLE_ASSET, input_amount)?,
+							false,
+						);
 
 					SwapOutput {
 						intermediary: Some(intermediary),
@@ -1827,20 +3599,214 @@ pub mod pallet {
 			})
 		}
 
-		pub(super) fn take_network_fee(input: AssetAmount, min_fee_enforced: bool) -> FeeTaken {
-			if input.is_zero() {
-				return FeeTaken { remaining_amount: 0, fee: 0 };
+		/// Compares a channel's minimum price against the current market price. Fails if it exceeds
+		/// the market price by more than [MaxMinPriceMultiple], and otherwise returns the market
+		/// price if the minimum price is above it. No check is done if there is no market price.
+		pub fn check_min_price(
+			source_asset: Asset,
+			destination_asset: Asset,
+			min_price: Price,
+		) -> Result<Option<Price>, DispatchError> {
+			let Some(market_price) = T::SwappingApi::swap_rate(source_asset, destination_asset)
+			else {
+				return Ok(None)
+			};
+
+			if let Some(multiple) = MaxMinPriceMultiple::<T>::get() {
+				ensure!(
+					min_price <= market_price.saturating_mul(multiple.into()),
+					Error::<T>::MinPriceTooFarAboveMarketPrice
+				);
+			}
+
+			Ok((min_price > market_price).then_some(market_price))
+		}
+
+		/// Appends the broker's default affiliates to the given affiliates, skipping any default
+		/// affiliate whose account is already among the given ones.
+		fn merge_default_affiliates(
+			broker_id: &T::AccountId,
+			mut affiliate_fees: Affiliates<T::AccountId>,
+		) -> Result<Affiliates<T::AccountId>, DispatchError> {
+			for default_affiliate in BrokerDefaultAffiliates::<T>::get(broker_id) {
+				if !affiliate_fees
+					.iter()
+					.any(|affiliate| affiliate.account == default_affiliate.account)
+				{
+					affiliate_fees
+						.try_push(default_affiliate)
+						.map_err(|_| Error::<T>::TooManyAffiliates)?;
+				}
+			}
+			Ok(affiliate_fees)
+		}
+
+		/// The network fee applied to swaps, after resolving all applicable settings. The minimum
+		/// fee is only enforced for user swaps, not for internal swaps such as ingress/egress fee
+		/// and gas swaps.
+		pub fn effective_network_fee(is_internal: bool) -> FeeRateAndMinimum {
+			FeeRateAndMinimum {
+				rate: T::NetworkFee::get(),
+				minimum: if is_internal { 0 } else { MinimumNetworkFeePerChunk::<T>::get() },
+			}
+		}
+
+		pub(super) fn take_network_fee(input: AssetAmount, min_fee_enforced: bool) -> FeeTaken {
+			if input.is_zero() {
+				return FeeTaken { remaining_amount: 0, fee: 0, minimum_applied: false };
+			}
+
+			let FeeRateAndMinimum { rate, minimum } =
+				Self::effective_network_fee(!min_fee_enforced);
+
+			let (remaining, fee) = utilities::calculate_network_fee(rate, minimum, input);
+
+			CollectedNetworkFee::<T>::mutate(|total| {
+				total.saturating_accrue(fee);
+			});
+			// The minimum fee is only enforced for user swaps.
+			NetworkFeesTaken::<T>::mutate(|totals| {
+				if min_fee_enforced {
+					totals.regular_swaps.saturating_accrue(fee);
+				} else {
+					totals.internal_swaps.saturating_accrue(fee);
+				}
+			});
+			FeeTaken { remaining_amount: remaining, fee, minimum_applied: minimum > rate * input }
+		}
+
+		fn accrue_in_flight_amount(asset: Asset, amount: AssetAmount) {
+			if !amount.is_zero() {
+				InFlightSwapAmounts::<T>::mutate(asset, |total| total.saturating_accrue(amount));
+			}
+		}
+
+		fn reduce_in_flight_amount(asset: Asset, amount: AssetAmount) {
+			InFlightSwapAmounts::<T>::mutate_exists(asset, |total| {
+				*total = total
+					.map(|total| total.saturating_sub(amount))
+					.filter(|remaining| !remaining.is_zero());
+			});
+		}
+
+		/// The amount of each asset held by user swap requests, computed from the swap requests
+		/// and the swaps scheduled for them. This is what [InFlightSwapAmounts] tracks.
+		pub(crate) fn in_flight_swap_amounts_of_requests() -> BTreeMap<Asset, AssetAmount> {
+			let mut amounts = BTreeMap::<Asset, AssetAmount>::new();
+			let mut user_swap_requests = BTreeSet::new();
+			for request in SwapRequests::<T>::iter_values() {
+				if let SwapRequestState::UserSwap { dca_state, .. } = &request.state {
+					user_swap_requests.insert(request.id);
+					amounts
+						.entry(request.input_asset)
+						.or_default()
+						.saturating_accrue(dca_state.remaining_input_amount);
+					amounts
+						.entry(request.output_asset)
+						.or_default()
+						.saturating_accrue(dca_state.accumulated_output_amount);
+				}
+			}
+			for swap in SwapQueue::<T>::iter_values().flatten() {
+				if user_swap_requests.contains(&swap.swap_request_id) {
+					amounts.entry(swap.from).or_default().saturating_accrue(swap.input_amount);
+				}
+			}
+			amounts.retain(|_, amount| !amount.is_zero());
+			amounts
+		}
+
+		/// Sends the output of a swap request whose deposit was rejected after it was boosted to
+		/// the refund address, the same way a rejected deposit is refunded. The refund address is
+		/// on the chain of the input asset, so if the output can't be sent there, or there is no
+		/// refund address, the output is added to the collected rejected funds instead.
+		fn divert_output_for_rejected_deposit(
+			swap_request_id: SwapRequestId,
+			amount: AssetAmount,
+			asset: Asset,
+			refund_params: &Option<ChannelRefundParametersDecoded>,
+			tracking_id: Option<TrackingId>,
+		) {
+			Self::deposit_event(Event::<T>::SwapOutputDivertedForRejectedDeposit {
+				swap_request_id,
+				asset,
+				amount,
+			});
+			match refund_params.as_ref().filter(|refund_params| {
+				refund_params.refund_address.chain() == ForeignChain::from(asset)
+			}) {
+				Some(refund_params) => Self::egress_for_swap(
+					swap_request_id,
+					amount,
+					asset,
+					refund_params.refund_address.clone(),
+					None, /* refunds don't use ccm parameters */
+					true, /* refund */
+					tracking_id,
+					None, /* refunds don't use egress memos */
+				),
+				None => {
+					CollectedRejectedFunds::<T>::mutate(asset, |fund| {
+						*fund = fund.saturating_add(amount)
+					});
+					Self::deposit_event(Event::<T>::SwapAmountConfiscated {
+						swap_request_id,
+						asset,
+						total_amount: amount,
+						confiscated_amount: amount,
+					});
+				},
 			}
+		}
 
-			let min_fee = if min_fee_enforced { MinimumNetworkFeePerChunk::<T>::get() } else { 0 };
-
-			let (remaining, fee) =
-				utilities::calculate_network_fee(T::NetworkFee::get(), min_fee, input);
-
-			CollectedNetworkFee::<T>::mutate(|total| {
-				total.saturating_accrue(fee);
-			});
-			FeeTaken { remaining_amount: remaining, fee }
+		/// Refunds the part of a swap input above the maximum swap amount if refunds of the excess
+		/// are enabled and a refund address is known, otherwise confiscates it.
+		#[allow(clippy::too_many_arguments)]
+		fn refund_or_confiscate_excess_swap_amount(
+			swap_request_id: SwapRequestId,
+			asset: Asset,
+			total_amount: AssetAmount,
+			swap_amount: AssetAmount,
+			excess_amount: AssetAmount,
+			refund_params: &Option<ChannelRefundParametersDecoded>,
+			tracking_id: Option<TrackingId>,
+		) {
+			match refund_params
+				.as_ref()
+				.filter(|_| RefundExcessSwapAmount::<T>::get())
+				.map(|params| params.refund_address.clone())
+			{
+				Some(refund_address) => {
+					Self::deposit_event(Event::<T>::SwapAmountExcessRefunded {
+						swap_request_id,
+						asset,
+						total_amount,
+						swap_amount,
+						refunded_amount: excess_amount,
+					});
+					Self::egress_for_swap(
+						swap_request_id,
+						excess_amount,
+						asset,
+						refund_address,
+						None, /* refunds don't use ccm parameters */
+						true, /* refund */
+						tracking_id,
+						None, /* refunds don't use egress memos */
+					);
+				},
+				None => {
+					CollectedRejectedFunds::<T>::mutate(asset, |fund| {
+						*fund = fund.saturating_add(excess_amount)
+					});
+					Self::deposit_event(Event::<T>::SwapAmountConfiscated {
+						swap_request_id,
+						asset,
+						total_amount,
+						confiscated_amount: excess_amount,
+					});
+				},
+			}
 		}
 
 		fn egress_for_swap(
@@ -1850,10 +3816,35 @@ pub mod pallet {
 			address: ForeignChainAddress,
 			maybe_ccm_metadata: Option<CcmDepositMetadata>,
 			is_refund: bool,
+			tracking_id: Option<TrackingId>,
+			egress_memo: Option<EgressMemo>,
 		) {
 			let is_ccm_swap = maybe_ccm_metadata.is_some();
 
-			match T::EgressHandler::schedule_egress(asset, amount, address, maybe_ccm_metadata) {
+			// The swap request id isn't known to the egress handler, so it's substituted here.
+			// The output amount placeholder is substituted at egress, once fees are known.
+			let maybe_ccm_metadata = maybe_ccm_metadata.map(|mut metadata| {
+				let mut message = metadata.channel_metadata.message.to_vec();
+				substitute_ccm_placeholder(
+					&mut message,
+					&CCM_SWAP_REQUEST_ID_PLACEHOLDER,
+					(*swap_request_id).into(),
+					asset.into(),
+				);
+				if let Ok(message) = message.try_into() {
+					metadata.channel_metadata.message = message;
+				}
+				metadata
+			});
+
+			match T::EgressHandler::schedule_egress(
+				asset,
+				amount,
+				address,
+				maybe_ccm_metadata,
+				egress_memo,
+				if is_refund { EgressOrigin::Refund } else { EgressOrigin::Swap },
+			) {
 				Ok(ScheduledEgressDetails { egress_id, egress_amount, fee_withheld }) =>
 					if is_refund {
 						Self::deposit_event(Event::<T>::RefundEgressScheduled {
@@ -1870,6 +3861,7 @@ pub mod pallet {
 							asset,
 							amount: egress_amount,
 							egress_fee: (fee_withheld, asset),
+							tracking_id,
 						});
 					},
 				Err(err) => {
@@ -1895,64 +3887,174 @@ pub mod pallet {
 				},
 			};
 		}
-	}
 
-	impl<T: Config> SwapRequestHandler for Pallet<T> {
-		type AccountId = T::AccountId;
+		fn egress_aggregated_refund(
+			asset: Asset,
+			address: ForeignChainAddress,
+			refunds: Vec<(SwapRequestId, AssetAmount)>,
+		) {
+			let total_amount = refunds
+				.iter()
+				.fold(0, |total: AssetAmount, (_, amount)| total.saturating_add(*amount));
+
+			match T::EgressHandler::schedule_egress(
+				asset,
+				total_amount,
+				address,
+				None,
+				None,
+				EgressOrigin::Refund,
+			) {
+				Ok(ScheduledEgressDetails { egress_id, egress_amount, fee_withheld }) => {
+					Self::deposit_event(Event::<T>::AggregatedRefundEgressScheduled {
+						swap_request_ids: refunds.into_iter().map(|(id, _)| id).collect(),
+						egress_id,
+						asset,
+						amount: egress_amount,
+						egress_fee: (fee_withheld, asset),
+					});
+				},
+				Err(err) => {
+					let reason: DispatchError = err.into();
+					for (swap_request_id, amount) in refunds {
+						Self::deposit_event(Event::<T>::RefundEgressIgnored {
+							swap_request_id,
+							asset,
+							amount,
+							reason,
+						});
+					}
+				},
+			}
+		}
 
-		fn init_swap_request(
+		/// Restricts the number of chunks based on the minimum chunk size.
+		fn limit_dca_chunks(
 			input_asset: Asset,
 			input_amount: AssetAmount,
+			dca_params: Option<DcaParameters>,
+		) -> Option<DcaParameters> {
+			dca_params.map(|mut dca_params| {
+				let minimum_chunk_size = MinimumChunkSize::<T>::get(input_asset);
+				if minimum_chunk_size > 0 {
+					dca_params.number_of_chunks = core::cmp::min(
+						max((input_amount / minimum_chunk_size) as u32, 1),
+						dca_params.number_of_chunks,
+					);
+				}
+				dca_params
+			})
+		}
+
+		/// Schedules the first chunk of a swap request whose output is credited to `account_id`.
+		#[allow(clippy::too_many_arguments)]
+		fn schedule_on_chain_swap(
+			request_id: SwapRequestId,
+			input_asset: Asset,
 			output_asset: Asset,
-			request_type: SwapRequestType,
-			broker_fees: Beneficiaries<Self::AccountId>,
+			input_amount: AssetAmount,
+			account_id: T::AccountId,
+			refund_params: Option<ChannelRefundParameters<T::AccountId>>,
+			dca_params: Option<DcaParameters>,
+			tracking_id: Option<TrackingId>,
+		) {
+			let (mut dca_state, chunk_input_amount) =
+				DcaState::create_with_first_chunk(input_amount, dca_params);
+
+			let swap_id = Self::schedule_swap(
+				input_asset,
+				output_asset,
+				chunk_input_amount,
+				refund_params.as_ref(),
+				SwapType::Swap,
+				Default::default(),
+				request_id,
+				SWAP_DELAY_BLOCKS.into(),
+			);
+
+			dca_state.status = DcaStatus::ChunkScheduled(swap_id);
+
+			SwapRequests::<T>::insert(
+				request_id,
+				SwapRequest {
+					id: request_id,
+					input_asset,
+					output_asset,
+					refund_params: None,
+					state: SwapRequestState::OnChainSwap { account_id, dca_state, refund_params },
+					tracking_id,
+				},
+			);
+		}
+
+		#[allow(clippy::too_many_arguments)]
+		fn init_swap_request_inner(
+			input_asset: Asset,
+			input_amount: AssetAmount,
+			output_asset: Asset,
+			request_type: SwapRequestType<T::AccountId>,
+			broker_fees: Beneficiaries<T::AccountId>,
 			refund_params: Option<ChannelRefundParametersDecoded>,
 			dca_params: Option<DcaParameters>,
-			origin: SwapOrigin<Self::AccountId>,
+			origin: SwapOrigin<T::AccountId>,
+			tracking_id: Option<TrackingId>,
 		) -> SwapRequestId {
 			let request_id = SwapRequestIdCounter::<T>::mutate(|id| {
 				id.saturating_accrue(1);
 				*id
 			});
 
+			if let SwapOrigin::DepositChannel { channel_id, deposit_block_height, .. } = &origin {
+				// Only the first deposit into a quoted channel is compared against the quote.
+				if let Some(channel_quote) =
+					SwapQuotes::<T>::take((ForeignChain::from(input_asset), *channel_id))
+						.filter(|quote| *deposit_block_height <= quote.source_chain_expiry_block)
+				{
+					SwapRequestQuotes::<T>::insert(request_id, channel_quote);
+				}
+			}
+
 			// Do not limit the maximum swap amount for network fee swaps.
 			let net_amount = if matches!(
 				request_type,
-				SwapRequestType::NetworkFee | SwapRequestType::IngressEgressFee
+				SwapRequestType::NetworkFee |
+					SwapRequestType::IngressEgressFee |
+					SwapRequestType::BoostNetworkFee
 			) {
 				input_amount
 			} else {
-				let (swap_amount, confiscated_amount) =
-					match MaximumSwapAmount::<T>::get(input_asset) {
-						Some(max) =>
-							(sp_std::cmp::min(input_amount, max), input_amount.saturating_sub(max)),
-						None => (input_amount, Zero::zero()),
-					};
-				if !confiscated_amount.is_zero() {
-					CollectedRejectedFunds::<T>::mutate(input_asset, |fund| {
-						*fund = fund.saturating_add(confiscated_amount)
-					});
-					Self::deposit_event(Event::<T>::SwapAmountConfiscated {
-						swap_request_id: request_id,
-						asset: input_asset,
-						total_amount: input_amount,
-						confiscated_amount,
-					});
+				let (swap_amount, excess_amount) = match MaximumSwapAmount::<T>::get(input_asset) {
+					Some(max) =>
+						(sp_std::cmp::min(input_amount, max), input_amount.saturating_sub(max)),
+					None => (input_amount, Zero::zero()),
+				};
+				if !excess_amount.is_zero() {
+					if let SwapRequestType::CreditOnChain { account_id } = &request_type {
+						// The excess never leaves the chain, so it is always refunded.
+						T::BalanceApi::credit_account(account_id, input_asset, excess_amount);
+						Self::deposit_event(Event::<T>::SwapAmountExcessRefunded {
+							swap_request_id: request_id,
+							asset: input_asset,
+							total_amount: input_amount,
+							swap_amount,
+							refunded_amount: excess_amount,
+						});
+					} else {
+						Self::refund_or_confiscate_excess_swap_amount(
+							request_id,
+							input_asset,
+							input_amount,
+							swap_amount,
+							excess_amount,
+							&refund_params,
+							tracking_id,
+						);
+					}
 				}
 				swap_amount
 			};
 
-			// Restrict the number of chunks based on the minimum chunk size.
-			let dca_params = dca_params.map(|mut dca_params| {
-				let minimum_chunk_size = MinimumChunkSize::<T>::get(input_asset);
-				if minimum_chunk_size > 0 {
-					dca_params.number_of_chunks = core::cmp::min(
-						max((input_amount / minimum_chunk_size) as u32, 1),
-						dca_params.number_of_chunks,
-					);
-				}
-				dca_params
-			});
+			let dca_params = Self::limit_dca_chunks(input_asset, input_amount, dca_params);
 
 			Self::deposit_event(Event::<T>::SwapRequested {
 				swap_request_id: request_id,
@@ -1961,16 +4063,23 @@ pub mod pallet {
 				output_asset,
 				request_type: match &request_type {
 					SwapRequestType::NetworkFee => SwapRequestTypeEncoded::NetworkFee,
+					SwapRequestType::BoostNetworkFee => SwapRequestTypeEncoded::BoostNetworkFee,
 					SwapRequestType::IngressEgressFee => SwapRequestTypeEncoded::IngressEgressFee,
-					SwapRequestType::Regular { output_address, ccm_deposit_metadata } =>
-						SwapRequestTypeEncoded::Regular {
-							output_address: T::AddressConverter::to_encoded_address(
-								output_address.clone(),
-							),
-							ccm_deposit_metadata: ccm_deposit_metadata
-								.clone()
-								.map(|metadata| metadata.to_encoded::<T::AddressConverter>()),
-						},
+					SwapRequestType::Regular {
+						output_address,
+						ccm_deposit_metadata,
+						egress_memo,
+					} => SwapRequestTypeEncoded::Regular {
+						output_address: T::AddressConverter::to_encoded_address(
+							output_address.clone(),
+						),
+						ccm_deposit_metadata: ccm_deposit_metadata
+							.clone()
+							.map(|metadata| metadata.to_encoded::<T::AddressConverter>()),
+						egress_memo: egress_memo.clone(),
+					},
+					SwapRequestType::CreditOnChain { account_id } =>
+						SwapRequestTypeEncoded::CreditOnChain { account_id: account_id.clone() },
 				},
 				origin: origin.clone(),
 				broker_fees: broker_fees.clone(),
@@ -1978,16 +4087,21 @@ pub mod pallet {
 					.clone()
 					.map(|params| params.map_address(T::AddressConverter::to_encoded_address)),
 				dca_parameters: dca_params.clone(),
+				tracking_id,
 			});
 
 			match request_type {
-				SwapRequestType::NetworkFee => {
+				SwapRequestType::NetworkFee | SwapRequestType::BoostNetworkFee => {
 					Self::schedule_swap(
 						input_asset,
 						output_asset,
 						net_amount,
-						None,
-						SwapType::NetworkFee,
+						None::<&ChannelRefundParametersDecoded>,
+						if request_type == SwapRequestType::BoostNetworkFee {
+							SwapType::BoostNetworkFee
+						} else {
+							SwapType::NetworkFee
+						},
 						Default::default(),
 						request_id,
 						SWAP_DELAY_BLOCKS.into(),
@@ -2001,6 +4115,7 @@ pub mod pallet {
 							output_asset,
 							refund_params: None,
 							state: SwapRequestState::NetworkFee,
+							tracking_id,
 						},
 					);
 				},
@@ -2009,7 +4124,7 @@ pub mod pallet {
 						input_asset,
 						output_asset,
 						net_amount,
-						None,
+						None::<&ChannelRefundParametersDecoded>,
 						SwapType::IngressEgressFee,
 						Default::default(),
 						request_id,
@@ -2024,10 +4139,13 @@ pub mod pallet {
 							output_asset,
 							refund_params: None,
 							state: SwapRequestState::IngressEgressFee,
+							tracking_id,
 						},
 					);
 				},
-				SwapRequestType::Regular { output_address, ccm_deposit_metadata } => {
+				SwapRequestType::Regular { output_address, ccm_deposit_metadata, egress_memo } => {
+					Self::accrue_in_flight_amount(input_asset, net_amount);
+
 					let (mut dca_state, chunk_input_amount) =
 						DcaState::create_with_first_chunk(net_amount, dca_params);
 
@@ -2056,16 +4174,212 @@ pub mod pallet {
 								output_address: output_address.clone(),
 								broker_fees,
 								dca_state,
+								egress_memo,
 							},
+							tracking_id,
 						},
 					);
 				},
+				SwapRequestType::CreditOnChain { account_id } => {
+					// Refunds are credited to the account, so the refund address is not used.
+					let refund_params =
+						refund_params.map(|params| params.map_address(|_| account_id.clone()));
+					Self::schedule_on_chain_swap(
+						request_id,
+						input_asset,
+						output_asset,
+						net_amount,
+						account_id,
+						refund_params,
+						dca_params,
+						tracking_id,
+					);
+				},
 			};
 
 			request_id
 		}
 	}
 
+	impl<T: Config> SwapRequestHandler for Pallet<T> {
+		type AccountId = T::AccountId;
+
+		fn init_swap_request(
+			input_asset: Asset,
+			input_amount: AssetAmount,
+			output_asset: Asset,
+			request_type: SwapRequestType<Self::AccountId>,
+			broker_fees: Beneficiaries<Self::AccountId>,
+			refund_params: Option<ChannelRefundParametersDecoded>,
+			dca_params: Option<DcaParameters>,
+			origin: SwapOrigin<Self::AccountId>,
+			tracking_id: Option<TrackingId>,
+		) -> SwapRequestId {
+			if request_type == SwapRequestType::BoostNetworkFee {
+				BoostNetworkFeesTaken::<T>::mutate(input_asset, |total| {
+					total.saturating_accrue(input_amount);
+				});
+			}
+
+			Self::init_swap_request_inner(
+				input_asset,
+				input_amount,
+				output_asset,
+				request_type,
+				broker_fees,
+				refund_params,
+				dca_params,
+				origin,
+				tracking_id,
+			)
+		}
+
+		fn top_up_swap_request(
+			swap_request_id: SwapRequestId,
+			additional_input_amount: AssetAmount,
+		) -> Result<(), ()> {
+			let (input_asset, refund_params, tracking_id, accepted_amount) =
+				SwapRequests::<T>::try_mutate(swap_request_id, |maybe_request| {
+					// CCMs are excluded since their gas budget is tied to the original input.
+					let Some(SwapRequest {
+						input_asset,
+						refund_params,
+						tracking_id,
+						state:
+							SwapRequestState::UserSwap { ccm_deposit_metadata: None, dca_state, .. },
+						..
+					}) = maybe_request
+					else {
+						return Err(())
+					};
+
+					// Only requests with chunks left to schedule can take more input.
+					ensure!(
+						dca_state.remaining_chunks > 0 &&
+							matches!(
+								dca_state.status,
+								DcaStatus::ChunkToBeScheduled | DcaStatus::ChunkScheduled(_)
+							),
+						()
+					);
+
+					// The maximum swap amount applies to the cumulative input of the request.
+					let accepted_amount = match MaximumSwapAmount::<T>::get(*input_asset) {
+						Some(max) => sp_std::cmp::min(
+							additional_input_amount,
+							max.saturating_sub(dca_state.total_input_amount),
+						),
+						None => additional_input_amount,
+					};
+
+					dca_state.remaining_input_amount.saturating_accrue(accepted_amount);
+					dca_state.total_input_amount.saturating_accrue(accepted_amount);
+					Ok((*input_asset, refund_params.clone(), *tracking_id, accepted_amount))
+				})?;
+
+			let excess_amount = additional_input_amount.saturating_sub(accepted_amount);
+			if !excess_amount.is_zero() {
+				Self::refund_or_confiscate_excess_swap_amount(
+					swap_request_id,
+					input_asset,
+					additional_input_amount,
+					accepted_amount,
+					excess_amount,
+					&refund_params,
+					tracking_id,
+				);
+			}
+
+			if !accepted_amount.is_zero() {
+				Self::accrue_in_flight_amount(input_asset, accepted_amount);
+				Self::deposit_event(Event::<T>::SwapRequestToppedUp {
+					swap_request_id,
+					additional_input_amount: accepted_amount,
+				});
+			}
+
+			Ok(())
+		}
+
+		fn is_broker_suspended(broker_id: &Self::AccountId) -> bool {
+			SuspendedBrokers::<T>::contains_key(broker_id)
+		}
+	}
+
+	impl<T: Config> OnChainSwapHandler for Pallet<T> {
+		type AccountId = T::AccountId;
+
+		#[transactional]
+		fn swap_from_on_chain_balance(
+			account_id: &Self::AccountId,
+			input_asset: Asset,
+			input_amount: AssetAmount,
+			output_asset: Asset,
+			retry_duration: BlockNumber,
+			min_price: Price,
+			dca_parameters: Option<DcaParameters>,
+		) -> Result<SwapRequestId, DispatchError> {
+			ensure!(T::SafeMode::get().swaps_enabled, Error::<T>::SwapsDisabled);
+			ensure!(input_asset != output_asset, Error::<T>::SameInputAndOutputAsset);
+
+			Self::validate_refund_params(retry_duration)?;
+			if let Some(params) = &dca_parameters {
+				Self::validate_dca_params(params)?;
+			}
+			Self::check_min_price(input_asset, output_asset, min_price)?;
+
+			let input_amount = MaximumSwapAmount::<T>::get(input_asset)
+				.map_or(input_amount, |max| sp_std::cmp::min(input_amount, max));
+			T::BalanceApi::try_debit_account(account_id, input_asset, input_amount)?;
+
+			let swap_request_id = SwapRequestIdCounter::<T>::mutate(|id| {
+				id.saturating_accrue(1);
+				*id
+			});
+			let dca_parameters = Self::limit_dca_chunks(input_asset, input_amount, dca_parameters);
+
+			Self::deposit_event(Event::<T>::SwapRequested {
+				swap_request_id,
+				input_asset,
+				input_amount,
+				output_asset,
+				origin: SwapOrigin::OnChainAccount(account_id.clone()),
+				request_type: SwapRequestTypeEncoded::CreditOnChain {
+					account_id: account_id.clone(),
+				},
+				broker_fees: Default::default(),
+				// There is no refund address: refunds are credited to the account.
+				refund_parameters: None,
+				dca_parameters: dca_parameters.clone(),
+				tracking_id: None,
+			});
+
+			Self::schedule_on_chain_swap(
+				swap_request_id,
+				input_asset,
+				output_asset,
+				input_amount,
+				account_id.clone(),
+				Some(ChannelRefundParameters {
+					retry_duration,
+					refund_address: account_id.clone(),
+					min_price,
+				}),
+				dca_parameters,
+				None,
+			);
+
+			Self::deposit_event(Event::<T>::OnChainSwapRequested {
+				swap_request_id,
+				account_id: account_id.clone(),
+				retry_duration,
+				min_price,
+			});
+
+			Ok(swap_request_id)
+		}
+	}
+
 	impl<T: Config> cf_traits::AssetConverter for Pallet<T> {
 		fn calculate_input_for_gas_output<C: Chain>(
 			input_asset: C::ChainAsset,
@@ -2118,6 +4432,26 @@ pub mod pallet {
 	}
 }
 
+impl<T: Config> EpochTransitionHandler for Pallet<T> {
+	/// Private channel addresses are derived from the current Bitcoin vault key, so brokers are
+	/// notified of their new deposit address whenever a new epoch (and key) becomes active.
+	fn on_new_epoch(_new: EpochIndex) {
+		for (broker_id, channel_id) in BrokerPrivateBtcChannels::<T>::iter() {
+			match T::DepositHandler::private_channel_deposit_address(Asset::Btc, channel_id) {
+				Ok(deposit_address) =>
+					Self::deposit_event(Event::<T>::PrivateBrokerChannelAddressRotated {
+						broker_id,
+						channel_id,
+						deposit_address: T::AddressConverter::to_encoded_address(deposit_address),
+					}),
+				Err(err) => log::error!(
+					"Failed to derive private channel address for channel {channel_id}: {err:?}"
+				),
+			}
+		}
+	}
+}
+
 impl<T: Config> cf_traits::FlipBurnInfo for Pallet<T> {
 	fn take_flip_to_burn() -> AssetAmount {
 		FlipToBurn::<T>::take()
@@ -2142,6 +4476,14 @@ impl<T: Config> SwapLimitsProvider for Pallet<T> {
 		Ok(())
 	}
 
+	fn validate_min_price(
+		source_asset: Asset,
+		destination_asset: Asset,
+		min_price: Price,
+	) -> Result<(), DispatchError> {
+		Self::check_min_price(source_asset, destination_asset, min_price).map(|_| ())
+	}
+
 	fn validate_dca_params(params: &cf_primitives::DcaParameters) -> Result<(), DispatchError> {
 		let max_swap_request_duration_blocks = MaxSwapRequestDurationBlocks::<T>::get();
 
@@ -2176,6 +4518,35 @@ impl<T: Config> SwapLimitsProvider for Pallet<T> {
 
 		Ok(())
 	}
+
+	fn validate_ccm_limits(
+		ccm: &CcmChannelMetadata,
+		destination_chain: ForeignChain,
+	) -> Result<(), CcmValidityError> {
+		CcmLimitsPerChain::<T>::get(destination_chain).map_or(Ok(()), |limits| limits.check(ccm))
+	}
+
+	fn validate_ccm_destination(
+		broker_id: &Self::AccountId,
+		destination_address: &ForeignChainAddress,
+	) -> Result<(), DispatchError> {
+		let allowlist = CcmDestinationAllowlist::<T>::get(broker_id, destination_address.chain());
+		ensure!(
+			allowlist.is_empty() || allowlist.contains(destination_address),
+			Error::<T>::CcmDestinationNotAllowed
+		);
+		Ok(())
+	}
+
+	fn validate_destination(
+		destination_address: &ForeignChainAddress,
+	) -> Result<(), DispatchError> {
+		ensure!(
+			T::EgressHandler::accepts_new_destination(destination_address),
+			Error::<T>::DestinationChainWindingDown
+		);
+		Ok(())
+	}
 }
 
 impl<T: Config> AffiliateRegistry for Pallet<T> {
@@ -2242,8 +4613,35 @@ pub(crate) mod utilities {
 		}
 	}
 
-	pub(super) fn calculate_swap_refund_parameters(
-		params: &ChannelRefundParametersDecoded,
+	/// The fee owed to each beneficiary, charged on `stable_amount`. Sanity check: it should
+	/// already not be possible to open a channel with broker fees this high, but if the total
+	/// broker fee would exceed 100% we charge no broker fee instead (for simplicity).
+	pub(super) fn calculate_broker_fees<AccountId>(
+		stable_amount: AssetAmount,
+		broker_fees: &Beneficiaries<AccountId>,
+	) -> impl Iterator<Item = (&AccountId, AssetAmount)> {
+		let total_fee_bps =
+			broker_fees.iter().fold(0u16, |fee_accumulator, Beneficiary { bps, .. }| {
+				fee_accumulator.saturating_add(*bps)
+			});
+
+		broker_fees.iter().filter(move |_| total_fee_bps <= MAX_BASIS_POINTS).map(
+			move |Beneficiary { account, bps }| {
+				(
+					account,
+					Permill::from_parts(*bps as u32 * BASIS_POINTS_PER_MILLION) * stable_amount,
+				)
+			},
+		)
+	}
+
+	/// The pools a swap from `from` to `to` goes through, identified by their non-stable asset.
+	pub(super) fn pools(from: Asset, to: Asset) -> impl Iterator<Item = Asset> {
+		[from, to].into_iter().filter(|asset| *asset != STABLE_ASSET)
+	}
+
+	pub(super) fn calculate_swap_refund_parameters<A: Clone>(
+		params: &ChannelRefundParameters<A>,
 		execute_at_block: u32,
 		input_amount: AssetAmount,
 	) -> SwapRefundParameters {
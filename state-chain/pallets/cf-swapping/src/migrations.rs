@@ -2,7 +2,11 @@ use cf_runtime_utilities::PlaceholderMigration;
 use frame_support::migrations::VersionedMigration;
 
 use crate::Pallet;
+pub mod dca_completed_chunks_migration;
+pub mod in_flight_swap_amounts_migration;
 pub mod swap_and_swap_request_migration;
+pub mod swap_request_egress_memo_migration;
+pub mod swap_request_tracking_id_migration;
 
 pub type PalletMigration<T> = (
 	VersionedMigration<
@@ -12,5 +16,33 @@ pub type PalletMigration<T> = (
 		Pallet<T>,
 		<T as frame_system::Config>::DbWeight,
 	>,
-	PlaceholderMigration<7, Pallet<T>>,
+	VersionedMigration<
+		7,
+		8,
+		swap_request_tracking_id_migration::Migration<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		8,
+		9,
+		dca_completed_chunks_migration::Migration<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		9,
+		10,
+		swap_request_egress_memo_migration::Migration<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	VersionedMigration<
+		10,
+		11,
+		in_flight_swap_amounts_migration::Migration<T>,
+		Pallet<T>,
+		<T as frame_system::Config>::DbWeight,
+	>,
+	PlaceholderMigration<11, Pallet<T>>,
 );
@@ -0,0 +1,28 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::*;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+/// Initialises the in-flight amounts from the existing swap requests.
+pub struct Migration<T: Config>(PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for Migration<T> {
+	fn on_runtime_upgrade() -> Weight {
+		for (asset, amount) in Pallet::<T>::in_flight_swap_amounts_of_requests() {
+			InFlightSwapAmounts::<T>::insert(asset, amount);
+		}
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), DispatchError> {
+		assert_eq!(
+			InFlightSwapAmounts::<T>::iter().collect::<BTreeMap<_, _>>(),
+			Pallet::<T>::in_flight_swap_amounts_of_requests()
+		);
+		Ok(())
+	}
+}
@@ -107,10 +107,12 @@ impl<T: Config> UncheckedOnRuntimeUpgrade for Migration<T> {
 						output_address,
 						dca_state,
 						broker_fees,
+						egress_memo: None,
 					},
 					old::SwapRequestState::NetworkFee => SwapRequestState::NetworkFee,
 					old::SwapRequestState::IngressEgressFee => SwapRequestState::IngressEgressFee,
 				},
+				tracking_id: None,
 			})
 		});
 
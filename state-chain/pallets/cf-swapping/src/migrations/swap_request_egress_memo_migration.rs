@@ -0,0 +1,100 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::Config;
+
+use crate::*;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+use codec::{Decode, Encode};
+
+pub mod old {
+	use super::*;
+	use cf_chains::{CcmDepositMetadata, ChannelRefundParametersDecoded, ForeignChainAddress};
+	use cf_primitives::{Asset, Beneficiaries, TrackingId};
+	use frame_support::Twox64Concat;
+
+	#[allow(clippy::large_enum_variant)]
+	#[derive(Encode, Decode)]
+	pub enum SwapRequestState<T: Config> {
+		UserSwap {
+			ccm_deposit_metadata: Option<CcmDepositMetadata>,
+			output_address: ForeignChainAddress,
+			dca_state: DcaState,
+			broker_fees: Beneficiaries<T::AccountId>,
+		},
+		NetworkFee,
+		IngressEgressFee,
+		OnChainSwap {
+			account_id: T::AccountId,
+			dca_state: DcaState,
+			refund_params: Option<ChannelRefundParameters<T::AccountId>>,
+		},
+	}
+
+	#[derive(Encode, Decode)]
+	pub(crate) struct SwapRequest<T: Config> {
+		pub id: SwapRequestId,
+		pub input_asset: Asset,
+		pub output_asset: Asset,
+		pub refund_params: Option<ChannelRefundParametersDecoded>,
+		pub state: SwapRequestState<T>,
+		pub tracking_id: Option<TrackingId>,
+	}
+
+	#[frame_support::storage_alias]
+	pub(crate) type SwapRequests<T: Config> =
+		StorageMap<Pallet<T>, Twox64Concat, SwapRequestId, SwapRequest<T>>;
+}
+
+/// Adds an empty egress memo to the existing user swap requests.
+pub struct Migration<T: Config>(PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for Migration<T> {
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((old::SwapRequests::<T>::iter().count() as u64).encode())
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		crate::SwapRequests::<T>::translate_values::<old::SwapRequest<T>, _>(|old_swap_request| {
+			Some(SwapRequest {
+				id: old_swap_request.id,
+				input_asset: old_swap_request.input_asset,
+				output_asset: old_swap_request.output_asset,
+				refund_params: old_swap_request.refund_params,
+				state: match old_swap_request.state {
+					old::SwapRequestState::UserSwap {
+						ccm_deposit_metadata,
+						output_address,
+						dca_state,
+						broker_fees,
+					} => SwapRequestState::UserSwap {
+						ccm_deposit_metadata,
+						output_address,
+						dca_state,
+						broker_fees,
+						egress_memo: None,
+					},
+					old::SwapRequestState::NetworkFee => SwapRequestState::NetworkFee,
+					old::SwapRequestState::IngressEgressFee => SwapRequestState::IngressEgressFee,
+					old::SwapRequestState::OnChainSwap { account_id, dca_state, refund_params } =>
+						SwapRequestState::OnChainSwap { account_id, dca_state, refund_params },
+				},
+				tracking_id: old_swap_request.tracking_id,
+			})
+		});
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let pre_swap_request_count = <u64>::decode(&mut state.as_slice())
+			.map_err(|_| DispatchError::from("Failed to decode state"))?;
+
+		assert_eq!(pre_swap_request_count, crate::SwapRequests::<T>::iter().count() as u64);
+		Ok(())
+	}
+}
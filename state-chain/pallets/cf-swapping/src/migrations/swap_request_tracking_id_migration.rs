@@ -0,0 +1,63 @@
+use frame_support::traits::UncheckedOnRuntimeUpgrade;
+
+use crate::Config;
+
+use crate::*;
+use frame_support::pallet_prelude::Weight;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::DispatchError;
+
+use codec::{Decode, Encode};
+
+pub mod old {
+	use super::*;
+	use cf_chains::ChannelRefundParametersDecoded;
+	use cf_primitives::Asset;
+	use frame_support::Twox64Concat;
+
+	#[derive(Encode, Decode)]
+	pub(crate) struct SwapRequest<T: Config> {
+		pub id: SwapRequestId,
+		pub input_asset: Asset,
+		pub output_asset: Asset,
+		pub refund_params: Option<ChannelRefundParametersDecoded>,
+		pub state: SwapRequestState<T>,
+	}
+
+	#[frame_support::storage_alias]
+	pub(crate) type SwapRequests<T: Config> =
+		StorageMap<Pallet<T>, Twox64Concat, SwapRequestId, SwapRequest<T>>;
+}
+
+pub struct Migration<T: Config>(PhantomData<T>);
+
+impl<T: Config> UncheckedOnRuntimeUpgrade for Migration<T> {
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, DispatchError> {
+		Ok((old::SwapRequests::<T>::iter().count() as u64).encode())
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		crate::SwapRequests::<T>::translate_values::<old::SwapRequest<T>, _>(|old_swap_request| {
+			Some(SwapRequest {
+				id: old_swap_request.id,
+				input_asset: old_swap_request.input_asset,
+				output_asset: old_swap_request.output_asset,
+				refund_params: old_swap_request.refund_params,
+				state: old_swap_request.state,
+				tracking_id: None,
+			})
+		});
+
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), DispatchError> {
+		let pre_swap_request_count = <u64>::decode(&mut state.as_slice())
+			.map_err(|_| DispatchError::from("Failed to decode state"))?;
+
+		assert_eq!(pre_swap_request_count, crate::SwapRequests::<T>::iter().count() as u64);
+		Ok(())
+	}
+}
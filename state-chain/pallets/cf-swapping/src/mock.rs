@@ -1,8 +1,9 @@
 use core::cell::Cell;
 
 use crate::{self as pallet_cf_swapping, PalletSafeMode, WeightInfo};
+use cf_amm::math::Price;
 use cf_chains::{ccm_checker::CcmValidityCheck, AnyChain};
-use cf_primitives::{Asset, AssetAmount, ChannelId};
+use cf_primitives::{Asset, AssetAmount, ChannelId, SwapRequestId, STABLE_ASSET};
 #[cfg(feature = "runtime-benchmarks")]
 use cf_traits::mocks::fee_payment::MockFeePayment;
 use cf_traits::{
@@ -12,7 +13,7 @@ use cf_traits::{
 		deposit_handler::MockDepositHandler, egress_handler::MockEgressHandler,
 		ingress_egress_fee_handler::MockIngressEgressFeeHandler,
 	},
-	AccountRoleRegistry, ChannelIdAllocator, SwappingApi,
+	AccountRoleRegistry, ChannelIdAllocator, OnSwapCompleted, SwappingApi,
 };
 use frame_support::{derive_impl, pallet_prelude::DispatchError, parameter_types, weights::Weight};
 use sp_core::ConstU32;
@@ -45,6 +46,7 @@ parameter_types! {
 	pub static SwapRate: f64 = DEFAULT_SWAP_RATE as f64;
 	pub storage Liquidity: BoundedBTreeMap<Asset, AssetAmount, ConstU32<100>> = Default::default();
 	pub storage NextChannelId: u64 = 0;
+	pub static CompletedSwaps: Vec<CompletedSwap> = vec![];
 }
 
 thread_local! {
@@ -113,6 +115,15 @@ impl SwappingApi for MockSwappingApi {
 
 		Ok(output_amount)
 	}
+
+	fn swap_rate(from: Asset, to: Asset) -> Option<Price> {
+		if from == to {
+			return None
+		}
+		let legs = if from == STABLE_ASSET || to == STABLE_ASSET { 1 } else { 2 };
+		let rate = SwapRate::get().powi(legs);
+		Some(Price::from((rate * (1u128 << 64) as f64) as u128) << 64)
+	}
 }
 
 pub struct MockWeightInfo;
@@ -126,6 +137,10 @@ impl WeightInfo for MockWeightInfo {
 		Weight::from_parts(100, 0)
 	}
 
+	fn request_swap_deposit_address_with_quote() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
 	fn withdraw() -> Weight {
 		Weight::from_parts(100, 0)
 	}
@@ -149,6 +164,30 @@ impl WeightInfo for MockWeightInfo {
 	fn register_affiliate() -> Weight {
 		Weight::from_parts(100, 0)
 	}
+
+	fn deregister_affiliate() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn set_default_affiliates() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn swap_on_chain() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn suspend_broker() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn sweep_collected_rejected_funds() -> Weight {
+		Weight::from_parts(100, 0)
+	}
+
+	fn update_ccm_destination_allowlist() -> Weight {
+		Weight::from_parts(100, 0)
+	}
 }
 
 pub struct AlwaysValid;
@@ -164,6 +203,40 @@ impl ChannelIdAllocator for MockChannelIdAllocator {
 	}
 }
 
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompletedSwap {
+	pub swap_request_id: SwapRequestId,
+	pub accounts: Vec<u64>,
+	pub input_asset: Asset,
+	pub input_amount: AssetAmount,
+	pub output_asset: Asset,
+	pub output_amount: AssetAmount,
+}
+
+pub struct MockOnSwapCompleted;
+
+impl OnSwapCompleted<u64> for MockOnSwapCompleted {
+	fn on_swap_completed(
+		swap_request_id: SwapRequestId,
+		accounts: &[u64],
+		input_asset: Asset,
+		input_amount: AssetAmount,
+		output_asset: Asset,
+		output_amount: AssetAmount,
+	) {
+		CompletedSwaps::mutate(|swaps| {
+			swaps.push(CompletedSwap {
+				swap_request_id,
+				accounts: accounts.to_vec(),
+				input_asset,
+				input_amount,
+				output_asset,
+				output_amount,
+			})
+		});
+	}
+}
+
 impl pallet_cf_swapping::Config for Test {
 	type RuntimeEvent = RuntimeEvent;
 	type DepositHandler = MockDepositHandler<AnyChain, Self>;
@@ -180,6 +253,7 @@ impl pallet_cf_swapping::Config for Test {
 	type NetworkFee = NetworkFee;
 	type ChannelIdAllocator = MockChannelIdAllocator;
 	type Bonder = MockBonderFor<Self>;
+	type OnSwapCompleted = MockOnSwapCompleted;
 }
 
 pub const ALICE: <Test as frame_system::Config>::AccountId = 123u64;
@@ -26,12 +26,16 @@ use cf_primitives::{
 use cf_test_utilities::{assert_event_sequence, assert_events_eq, assert_has_matching_event};
 use cf_traits::{
 	mocks::{
+		account_role_registry::MockAccountRoleRegistry,
 		address_converter::MockAddressConverter,
+		balance_api::MockBalance,
+		deposit_handler::MockDepositHandler,
 		egress_handler::{MockEgressHandler, MockEgressParameter},
 		funding_info::MockFundingInfo,
 		ingress_egress_fee_handler::MockIngressEgressFeeHandler,
 	},
-	AccountRoleRegistry, AssetConverter, Chainflip, SetSafeMode,
+	AccountRoleRegistry, AssetConverter, Chainflip, OnChainSwapHandler, SetSafeMode,
+	SwapRequestHandler,
 };
 use frame_support::{
 	assert_noop, assert_ok,
@@ -133,7 +137,9 @@ fn create_test_swap(
 				dca_state: DcaState::create_with_first_chunk(amount, dca_params).0,
 				ccm_deposit_metadata: None,
 				broker_fees: Default::default(),
+				egress_memo: None,
 			},
+			tracking_id: None,
 		},
 	);
 
@@ -201,6 +207,7 @@ fn insert_swaps(swaps: &[TestSwapParams]) {
 		let request_type = SwapRequestType::Regular {
 			output_address: swap.output_address.clone(),
 			ccm_deposit_metadata,
+			egress_memo: None,
 		};
 
 		Swapping::init_swap_request(
@@ -214,7 +221,9 @@ fn insert_swaps(swaps: &[TestSwapParams]) {
 			SwapOrigin::Vault {
 				tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 				broker_id: Some(BROKER),
+				verified_sender: None,
 			},
+			None,
 		);
 	}
 }
@@ -257,6 +266,7 @@ fn swap_with_custom_broker_fee(
 		SwapRequestType::Regular {
 			output_address: ForeignChainAddress::Eth(Default::default()),
 			ccm_deposit_metadata: None,
+			egress_memo: None,
 		},
 		broker_fees,
 		None,
@@ -269,13 +279,14 @@ fn swap_with_custom_broker_fee(
 			deposit_block_height: 0,
 			broker_id: BROKER,
 		},
+		None,
 	);
 }
 
 #[test]
 fn request_swap_success_with_valid_parameters() {
 	new_test_ext().execute_with(|| {
-		assert_ok!(Swapping::request_swap_deposit_address_with_affiliates(
+		assert_ok!(Swapping::request_swap_deposit_address_with_parameters(
 			RuntimeOrigin::signed(BROKER),
 			Asset::Eth,
 			Asset::Usdc,
@@ -286,6 +297,9 @@ fn request_swap_success_with_valid_parameters() {
 			Default::default(),
 			None,
 			None,
+			None,
+			false,
+			None,
 		));
 	});
 }
@@ -342,6 +356,7 @@ fn cannot_swap_with_incorrect_destination_address_type() {
 			SwapRequestType::Regular {
 				output_address: ForeignChainAddress::Eth([2; 20].into()),
 				ccm_deposit_metadata: None,
+				egress_memo: None,
 			},
 			Default::default(),
 			None,
@@ -349,7 +364,9 @@ fn cannot_swap_with_incorrect_destination_address_type() {
 			SwapOrigin::Vault {
 				tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 				broker_id: Some(BROKER),
+				verified_sender: None,
 			},
+			None,
 		);
 
 		assert_swaps_queue_is_empty();
@@ -363,7 +380,7 @@ fn expect_swap_id_to_be_emitted() {
 	new_test_ext()
 		.then_execute_at_block(INIT_BLOCK, |_| {
 			// 1. Request a deposit address -> SwapDepositAddressReady
-			assert_ok!(Swapping::request_swap_deposit_address_with_affiliates(
+			assert_ok!(Swapping::request_swap_deposit_address_with_parameters(
 				RuntimeOrigin::signed(BROKER),
 				Asset::Eth,
 				Asset::Usdc,
@@ -374,6 +391,9 @@ fn expect_swap_id_to_be_emitted() {
 				Default::default(),
 				None,
 				None,
+				None,
+				false,
+				None,
 			));
 
 			// 2. Schedule the swap -> SwapScheduled
@@ -409,11 +429,70 @@ fn expect_swap_id_to_be_emitted() {
 				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(1), .. }),
 				RuntimeEvent::Swapping(Event::SwapEgressScheduled {
 					swap_request_id: SwapRequestId(1),
-					egress_id: (ForeignChain::Ethereum, 1),
+					egress_id: (ForeignChain::Ethereum, 0, 1),
+					..
+				}),
+				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
+					swap_request_id: SwapRequestId(1),
+					..
+				}),
+			);
+		});
+}
+
+#[test]
+fn tracking_id_is_included_in_swap_events() {
+	const AMOUNT: AssetAmount = 500;
+	const TRACKING_ID: TrackingId = [7; 32];
+
+	new_test_ext()
+		.then_execute_at_block(INIT_BLOCK, |_| {
+			Swapping::init_swap_request(
+				Asset::Eth,
+				AMOUNT,
+				Asset::Usdc,
+				SwapRequestType::Regular {
+					output_address: ForeignChainAddress::Eth(Default::default()),
+					ccm_deposit_metadata: None,
+					egress_memo: None,
+				},
+				Default::default(),
+				None,
+				None,
+				SwapOrigin::Vault {
+					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
+					broker_id: Some(BROKER),
+					verified_sender: None,
+				},
+				Some(TRACKING_ID),
+			);
+
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapRequested {
+					swap_request_id: SwapRequestId(1),
+					tracking_id: Some(TRACKING_ID),
+					..
+				})
+			);
+		})
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|_| {
+			assert_event_sequence!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted {
+					swap_id: SwapId(1),
+					tracking_id: Some(TRACKING_ID),
+					..
+				}),
+				RuntimeEvent::Swapping(Event::SwapEgressScheduled {
+					swap_request_id: SwapRequestId(1),
+					tracking_id: Some(TRACKING_ID),
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(1)
+					swap_request_id: SwapRequestId(1),
+					..
 				}),
 			);
 		});
@@ -425,7 +504,7 @@ fn rejects_invalid_swap_deposit() {
 		let ccm = generate_ccm_channel();
 
 		assert_noop!(
-			Swapping::request_swap_deposit_address_with_affiliates(
+			Swapping::request_swap_deposit_address_with_parameters(
 				RuntimeOrigin::signed(BROKER),
 				Asset::Btc,
 				Asset::Eth,
@@ -436,12 +515,15 @@ fn rejects_invalid_swap_deposit() {
 				Default::default(),
 				None,
 				None,
+				None,
+				false,
+				None,
 			),
 			Error::<Test>::IncompatibleAssetAndAddress
 		);
 
 		assert_noop!(
-			Swapping::request_swap_deposit_address_with_affiliates(
+			Swapping::request_swap_deposit_address_with_parameters(
 				RuntimeOrigin::signed(BROKER),
 				Asset::Eth,
 				Asset::Dot,
@@ -452,12 +534,349 @@ fn rejects_invalid_swap_deposit() {
 				Default::default(),
 				None,
 				None,
+				None,
+				false,
+				None,
 			),
 			Error::<Test>::CcmUnsupportedForTargetChain
 		);
 	});
 }
 
+#[test]
+fn quote_is_recorded_when_opening_channel_with_quote() {
+	new_test_ext().execute_with(|| {
+		const QUOTE: SwapQuote = SwapQuote { input_amount: 1_000, output_amount: 1_900 };
+
+		let open_channel = |quote| {
+			Swapping::request_swap_deposit_address_with_quote(
+				RuntimeOrigin::signed(BROKER),
+				Asset::Eth,
+				Asset::Usdc,
+				EncodedAddress::Eth(Default::default()),
+				0,
+				None,
+				0,
+				Default::default(),
+				None,
+				None,
+				None,
+				false,
+				None,
+				quote,
+			)
+		};
+
+		assert_noop!(
+			open_channel(SwapQuote { input_amount: 0, output_amount: 0 }),
+			Error::<Test>::InvalidQuote
+		);
+
+		assert_ok!(open_channel(QUOTE));
+
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapDepositAddressReady { .. })
+		);
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapQuoteRecorded {
+				source_asset: Asset::Eth,
+				destination_asset: Asset::Usdc,
+				quote: QUOTE,
+				market_price: Some(_),
+				..
+			})
+		);
+	});
+}
+
+#[test]
+fn quote_is_compared_against_the_execution_of_the_first_deposit() {
+	const QUOTE: SwapQuote = SwapQuote { input_amount: 1_000, output_amount: 1_900 };
+	const AMOUNT: AssetAmount = 500;
+
+	let swap_from_channel = |channel_id, deposit_block_height| {
+		Swapping::init_swap_request(
+			Asset::Eth,
+			AMOUNT,
+			Asset::Usdc,
+			SwapRequestType::Regular {
+				output_address: ForeignChainAddress::Eth(Default::default()),
+				ccm_deposit_metadata: None,
+				egress_memo: None,
+			},
+			Default::default(),
+			None,
+			None,
+			SwapOrigin::DepositChannel {
+				deposit_address: EncodedAddress::Eth(Default::default()),
+				channel_id,
+				deposit_block_height,
+				broker_id: BROKER,
+			},
+			None,
+		)
+	};
+
+	new_test_ext()
+		.then_execute_at_block(INIT_BLOCK, |_| {
+			assert_ok!(Swapping::request_swap_deposit_address_with_quote(
+				RuntimeOrigin::signed(BROKER),
+				Asset::Eth,
+				Asset::Usdc,
+				EncodedAddress::Eth(Default::default()),
+				0,
+				None,
+				0,
+				Default::default(),
+				None,
+				None,
+				None,
+				false,
+				None,
+				QUOTE,
+			));
+			let channel_quote = SwapQuotes::<Test>::get((ForeignChain::Ethereum, 0)).unwrap();
+			assert_eq!(channel_quote.quote, QUOTE);
+
+			// Only the first deposit into the channel is linked to the quote.
+			let quoted_request = swap_from_channel(0, channel_quote.source_chain_expiry_block);
+			let unquoted_request = swap_from_channel(0, channel_quote.source_chain_expiry_block);
+			assert!(SwapQuotes::<Test>::get((ForeignChain::Ethereum, 0)).is_none());
+			assert_eq!(SwapRequestQuotes::<Test>::get(quoted_request), Some(channel_quote));
+			assert!(SwapRequestQuotes::<Test>::get(unquoted_request).is_none());
+			quoted_request
+		})
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|quoted_request| {
+			assert!(SwapRequestQuotes::<Test>::get(quoted_request).is_none());
+			let executed_quotes = System::events()
+				.into_iter()
+				.filter_map(|record| match record.event {
+					RuntimeEvent::Swapping(Event::SwapQuoteExecuted {
+						swap_request_id,
+						channel_id: 0,
+						quote: QUOTE,
+						quoted_output_amount,
+						execution,
+					}) => Some((swap_request_id, quoted_output_amount, execution)),
+					_ => None,
+				})
+				.collect::<Vec<_>>();
+			assert_eq!(executed_quotes.len(), 1);
+			let (swap_request_id, quoted_output_amount, execution) = &executed_quotes[0];
+			assert_eq!(*swap_request_id, quoted_request);
+			assert!(execution.input_amount > 0);
+			assert_eq!(
+				*quoted_output_amount,
+				QUOTE.output_amount * execution.input_amount / QUOTE.input_amount
+			);
+		});
+}
+
+#[test]
+fn quote_is_not_linked_to_deposits_into_a_reused_channel() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Swapping::request_swap_deposit_address_with_quote(
+			RuntimeOrigin::signed(BROKER),
+			Asset::Eth,
+			Asset::Usdc,
+			EncodedAddress::Eth(Default::default()),
+			0,
+			None,
+			0,
+			Default::default(),
+			None,
+			None,
+			None,
+			false,
+			None,
+			SwapQuote { input_amount: 1_000, output_amount: 1_900 },
+		));
+		let expiry_block = SwapQuotes::<Test>::get((ForeignChain::Ethereum, 0))
+			.unwrap()
+			.source_chain_expiry_block;
+
+		// A deposit witnessed after the quoted channel expired belongs to a later channel.
+		let swap_request_id = Swapping::init_swap_request(
+			Asset::Eth,
+			500,
+			Asset::Usdc,
+			SwapRequestType::Regular {
+				output_address: ForeignChainAddress::Eth(Default::default()),
+				ccm_deposit_metadata: None,
+				egress_memo: None,
+			},
+			Default::default(),
+			None,
+			None,
+			SwapOrigin::DepositChannel {
+				deposit_address: EncodedAddress::Eth(Default::default()),
+				channel_id: 0,
+				deposit_block_height: expiry_block + 1,
+				broker_id: BROKER,
+			},
+			None,
+		);
+		assert!(SwapRequestQuotes::<Test>::get(swap_request_id).is_none());
+		assert!(SwapQuotes::<Test>::get((ForeignChain::Ethereum, 0)).is_none());
+	});
+}
+
+#[test]
+fn suspended_brokers_cannot_open_channels_or_withdraw() {
+	new_test_ext().execute_with(|| {
+		let request_channel = || {
+			Swapping::request_swap_deposit_address_with_parameters(
+				RuntimeOrigin::signed(BROKER),
+				Asset::Eth,
+				Asset::Usdc,
+				EncodedAddress::Eth(Default::default()),
+				0,
+				None,
+				0,
+				Default::default(),
+				None,
+				None,
+				None,
+				false,
+				None,
+			)
+		};
+		let withdraw = || {
+			Swapping::withdraw(
+				RuntimeOrigin::signed(BROKER),
+				Asset::Eth,
+				EncodedAddress::Eth(Default::default()),
+			)
+		};
+		<Test as Config>::BalanceApi::credit_account(&BROKER, Asset::Eth, 200);
+
+		// Only the broker itself can suspend itself without governance.
+		assert_noop!(
+			Swapping::suspend_broker(RuntimeOrigin::signed(ALICE)),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(Swapping::suspend_broker(RuntimeOrigin::signed(BROKER)));
+		System::assert_last_event(RuntimeEvent::Swapping(Event::<Test>::BrokerSuspended {
+			broker_id: BROKER,
+		}));
+		assert!(<Swapping as SwapRequestHandler>::is_broker_suspended(&BROKER));
+
+		assert_noop!(request_channel(), Error::<Test>::BrokerSuspended);
+		assert_noop!(withdraw(), Error::<Test>::BrokerSuspended);
+		assert_noop!(
+			Swapping::open_private_btc_channel(RuntimeOrigin::signed(BROKER)),
+			Error::<Test>::BrokerSuspended
+		);
+		assert_noop!(
+			Swapping::deregister_as_broker(RuntimeOrigin::signed(BROKER)),
+			Error::<Test>::BrokerSuspended
+		);
+
+		// Only governance can lift the suspension.
+		assert_noop!(
+			Swapping::set_broker_suspension(RuntimeOrigin::signed(BROKER), BROKER, false),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(Swapping::set_broker_suspension(OriginTrait::root(), BROKER, false));
+		System::assert_last_event(RuntimeEvent::Swapping(Event::<Test>::BrokerUnsuspended {
+			broker_id: BROKER,
+		}));
+		assert!(!<Swapping as SwapRequestHandler>::is_broker_suspended(&BROKER));
+
+		assert_ok!(request_channel());
+		assert_ok!(withdraw());
+	});
+}
+
+#[test]
+fn can_sweep_collected_rejected_funds() {
+	new_test_ext().execute_with(|| {
+		const TREASURY: u64 = 1234;
+
+		CollectedRejectedFunds::<Test>::insert(Asset::Eth, 100);
+		CollectedRejectedFunds::<Test>::insert(Asset::Flip, 200);
+		CollectedRejectedFunds::<Test>::insert(Asset::Usdc, 300);
+
+		assert_noop!(
+			Swapping::sweep_collected_rejected_funds(
+				RuntimeOrigin::signed(BROKER),
+				Asset::Eth,
+				RejectedFundsDestination::WithheldFees,
+			),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_noop!(
+			Swapping::sweep_collected_rejected_funds(
+				OriginTrait::root(),
+				Asset::Dot,
+				RejectedFundsDestination::WithheldFees,
+			),
+			Error::<Test>::NoFundsAvailable
+		);
+		assert_noop!(
+			Swapping::sweep_collected_rejected_funds(
+				OriginTrait::root(),
+				Asset::Flip,
+				RejectedFundsDestination::WithheldFees,
+			),
+			Error::<Test>::NotAGasAsset
+		);
+
+		// Into the withheld fees:
+		assert_ok!(Swapping::sweep_collected_rejected_funds(
+			OriginTrait::root(),
+			Asset::Eth,
+			RejectedFundsDestination::WithheldFees,
+		));
+		assert_eq!(MockIngressEgressFeeHandler::<AnyChain>::withheld_assets(Asset::Eth), 100);
+		System::assert_last_event(RuntimeEvent::Swapping(
+			Event::<Test>::CollectedRejectedFundsSwept {
+				asset: Asset::Eth,
+				amount: 100,
+				destination: RejectedFundsDestination::WithheldFees,
+				egress_id: None,
+			},
+		));
+
+		// Into an account:
+		assert_ok!(Swapping::sweep_collected_rejected_funds(
+			OriginTrait::root(),
+			Asset::Flip,
+			RejectedFundsDestination::Account(TREASURY),
+		));
+		assert_eq!(get_broker_balance::<Test>(&TREASURY, Asset::Flip), 200);
+
+		// To an external address:
+		assert_ok!(Swapping::sweep_collected_rejected_funds(
+			OriginTrait::root(),
+			Asset::Usdc,
+			RejectedFundsDestination::Egress(EncodedAddress::Eth([1; 20])),
+		));
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses()
+				.iter()
+				.map(|egress| egress.amount())
+				.collect::<Vec<_>>(),
+			vec![300]
+		);
+		System::assert_last_event(RuntimeEvent::Swapping(
+			Event::<Test>::CollectedRejectedFundsSwept {
+				asset: Asset::Usdc,
+				amount: 300,
+				destination: RejectedFundsDestination::Egress(EncodedAddress::Eth([1; 20])),
+				egress_id: Some((ForeignChain::Ethereum, 0, 0)),
+			},
+		));
+
+		for asset in [Asset::Eth, Asset::Flip, Asset::Usdc] {
+			assert_eq!(CollectedRejectedFunds::<Test>::get(asset), 0);
+		}
+	});
+}
+
 #[test]
 fn swap_by_deposit_happy_path() {
 	const INPUT_ASSET: Asset = Asset::Eth;
@@ -519,6 +938,7 @@ fn process_all_into_stable_swaps_first() {
 					SwapRequestType::Regular {
 						output_address: ForeignChainAddress::Eth([1; 20].into()),
 						ccm_deposit_metadata: None,
+						egress_memo: None,
 					},
 					Default::default(),
 					None,
@@ -526,7 +946,9 @@ fn process_all_into_stable_swaps_first() {
 					SwapOrigin::Vault {
 						tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 						broker_id: Some(BROKER),
+						verified_sender: None,
 					},
+					None,
 				);
 			});
 
@@ -602,7 +1024,7 @@ fn process_all_into_stable_swaps_first() {
 			RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(1), .. }),
 			RuntimeEvent::Swapping(Event::SwapEgressScheduled {
 				swap_request_id: SwapRequestId(1),
-				egress_id: (ForeignChain::Ethereum, 1),
+				egress_id: (ForeignChain::Ethereum, 0, 1),
 				amount,
 				..
 			}) if amount == usdc_amount_swapped_after_fee * DEFAULT_SWAP_RATE,
@@ -610,7 +1032,7 @@ fn process_all_into_stable_swaps_first() {
 			RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(2), .. }),
 			RuntimeEvent::Swapping(Event::SwapEgressScheduled {
 				swap_request_id: SwapRequestId(2),
-				egress_id: (ForeignChain::Ethereum, 2),
+				egress_id: (ForeignChain::Ethereum, 0, 2),
 				amount,
 				..
 			}) if amount == usdc_amount_swapped_after_fee * DEFAULT_SWAP_RATE,
@@ -618,7 +1040,7 @@ fn process_all_into_stable_swaps_first() {
 			RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(3), .. }),
 			RuntimeEvent::Swapping(Event::SwapEgressScheduled {
 				swap_request_id: SwapRequestId(3),
-				egress_id: (ForeignChain::Ethereum, 3),
+				egress_id: (ForeignChain::Ethereum, 0, 3),
 				amount,
 				..
 			}) if amount == usdc_amount_swapped_after_fee * DEFAULT_SWAP_RATE,
@@ -626,7 +1048,7 @@ fn process_all_into_stable_swaps_first() {
 			RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(4), .. }),
 			RuntimeEvent::Swapping(Event::SwapEgressScheduled {
 				swap_request_id: SwapRequestId(4),
-				egress_id: (ForeignChain::Ethereum, 4),
+				egress_id: (ForeignChain::Ethereum, 0, 4),
 				amount,
 				..
 			}) if amount == usdc_amount_deposited_after_fee * DEFAULT_SWAP_RATE,
@@ -657,6 +1079,7 @@ fn can_handle_ccm_with_zero_swap_outputs() {
 				SwapRequestType::Regular {
 					ccm_deposit_metadata: Some(ccm.clone()),
 					output_address: eth_address,
+					egress_memo: None,
 				},
 				Default::default(),
 				None,
@@ -664,7 +1087,9 @@ fn can_handle_ccm_with_zero_swap_outputs() {
 				SwapOrigin::Vault {
 					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 					broker_id: Some(BROKER),
+					verified_sender: None,
 				},
+				None,
 			);
 
 			// Change the swap rate so swap output will be 0
@@ -680,12 +1105,14 @@ fn can_handle_ccm_with_zero_swap_outputs() {
 					swap_request_id: SwapRequestId(1),
 					swap_id: SwapId(1),
 					network_fee: 0,
+					network_fee_minimum_applied: false,
 					broker_fee: 0,
 					input_amount: PRINCIPAL_AMOUNT,
 					input_asset: INPUT_ASSET,
 					output_asset: OUTPUT_ASSET,
 					output_amount: ZERO_AMOUNT,
 					intermediate_amount: None,
+					tracking_id: None,
 				}),
 			);
 		})
@@ -722,7 +1149,8 @@ fn can_handle_swaps_with_zero_outputs() {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::<Test>::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(1)
+					swap_request_id: SwapRequestId(1),
+					..
 				}),
 				RuntimeEvent::Swapping(Event::<Test>::SwapExecuted {
 					swap_id: SwapId(2),
@@ -735,7 +1163,8 @@ fn can_handle_swaps_with_zero_outputs() {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::<Test>::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(2)
+					swap_request_id: SwapRequestId(2),
+					..
 				}),
 			);
 
@@ -787,21 +1216,107 @@ fn swap_excess_are_confiscated() {
 }
 
 #[test]
-fn swaps_are_executed_according_to_execute_at_field() {
-	let mut swaps = generate_test_swaps();
-	let later_swaps = swaps.split_off(2);
+fn swap_excess_is_refunded_if_enabled() {
+	const MAX_SWAP: AssetAmount = 100;
+	const AMOUNT: AssetAmount = 1_000;
+	const REFUNDED_AMOUNT: AssetAmount = AMOUNT - MAX_SWAP;
 
-	new_test_ext()
-		.then_execute_at_block(1_u64, |_| {
-			// Block 1, swaps should be scheduled at block 3
-			insert_swaps(&swaps);
+	new_test_ext().execute_with(|| {
+		let from: Asset = Asset::Usdc;
+		let to: Asset = Asset::Flip;
+		let refund_address = ForeignChainAddress::Eth([10; 20].into());
 
-			assert_has_matching_event!(
-				Test,
-				RuntimeEvent::Swapping(Event::SwapScheduled {
-					swap_id: SwapId(1),
-					execute_at: 3,
-					..
+		set_maximum_swap_amount(from, Some(MAX_SWAP));
+		assert_ok!(Swapping::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetRefundExcessSwapAmount { enabled: true }]
+				.try_into()
+				.unwrap()
+		));
+
+		Swapping::init_swap_request(
+			from,
+			AMOUNT,
+			to,
+			SwapRequestType::Regular {
+				output_address: ForeignChainAddress::Eth([1; 20].into()),
+				ccm_deposit_metadata: None,
+				egress_memo: None,
+			},
+			Default::default(),
+			Some(ChannelRefundParametersDecoded {
+				retry_duration: 0,
+				refund_address: refund_address.clone(),
+				min_price: 0.into(),
+			}),
+			None,
+			SwapOrigin::Vault {
+				tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
+				broker_id: Some(BROKER),
+				verified_sender: None,
+			},
+			None,
+		);
+
+		System::assert_has_event(RuntimeEvent::Swapping(Event::<Test>::SwapAmountExcessRefunded {
+			swap_request_id: SwapRequestId(1),
+			asset: from,
+			total_amount: AMOUNT,
+			swap_amount: MAX_SWAP,
+			refunded_amount: REFUNDED_AMOUNT,
+		}));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::RefundEgressScheduled {
+				swap_request_id: SwapRequestId(1),
+				amount: REFUNDED_AMOUNT,
+				..
+			}),
+		);
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses(),
+			vec![MockEgressParameter::Swap {
+				asset: from,
+				amount: REFUNDED_AMOUNT,
+				fee: 0,
+				destination_address: refund_address,
+				memo: None,
+			}]
+		);
+
+		// Only the maximum amount is swapped, and nothing is confiscated.
+		assert_eq!(
+			SwapQueue::<Test>::get(System::block_number() + u64::from(SWAP_DELAY_BLOCKS)),
+			vec![Swap::new(
+				1.into(),
+				1.into(),
+				from,
+				to,
+				MAX_SWAP,
+				None,
+				[FeeType::NetworkFee { min_fee_enforced: true }]
+			)]
+		);
+		assert_eq!(CollectedRejectedFunds::<Test>::get(from), 0);
+	});
+}
+
+#[test]
+fn swaps_are_executed_according_to_execute_at_field() {
+	let mut swaps = generate_test_swaps();
+	let later_swaps = swaps.split_off(2);
+
+	new_test_ext()
+		.then_execute_at_block(1_u64, |_| {
+			// Block 1, swaps should be scheduled at block 3
+			insert_swaps(&swaps);
+
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapScheduled {
+					swap_id: SwapId(1),
+					execute_at: 3,
+					..
 				}),
 			);
 
@@ -850,7 +1365,8 @@ fn swaps_are_executed_according_to_execute_at_field() {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::<Test>::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(1)
+					swap_request_id: SwapRequestId(1),
+					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(2), .. }),
 				RuntimeEvent::Swapping(Event::SwapEgressScheduled {
@@ -858,7 +1374,8 @@ fn swaps_are_executed_according_to_execute_at_field() {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::<Test>::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(2)
+					swap_request_id: SwapRequestId(2),
+					..
 				}),
 			);
 		})
@@ -875,7 +1392,8 @@ fn swaps_are_executed_according_to_execute_at_field() {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::<Test>::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(3)
+					swap_request_id: SwapRequestId(3),
+					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(4), .. }),
 				RuntimeEvent::Swapping(Event::SwapEgressScheduled {
@@ -883,7 +1401,8 @@ fn swaps_are_executed_according_to_execute_at_field() {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::<Test>::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(4)
+					swap_request_id: SwapRequestId(4),
+					..
 				}),
 			);
 		});
@@ -985,7 +1504,8 @@ fn swaps_get_retried_after_failure() {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(3)
+					swap_request_id: SwapRequestId(3),
+					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(4), .. }),
 				RuntimeEvent::Swapping(Event::SwapEgressScheduled {
@@ -993,70 +1513,822 @@ fn swaps_get_retried_after_failure() {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(4)
+					swap_request_id: SwapRequestId(4),
+					..
 				}),
 			);
 		})
 		.then_process_blocks_until_block(RETRY_AT_BLOCK)
 		.then_execute_with(|_| {
 			// Re-trying failed swaps originally scheduled for block 3 (which should
-			// now be successful):
+			// now be successful), oldest first:
 			assert_event_sequence!(
 				Test,
-				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(2), .. }),
+				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(1), .. }),
 				RuntimeEvent::Swapping(Event::SwapEgressScheduled {
-					swap_request_id: SwapRequestId(2),
+					swap_request_id: SwapRequestId(1),
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(2)
+					swap_request_id: SwapRequestId(1),
+					..
 				}),
-				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(1), .. }),
+				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(2), .. }),
 				RuntimeEvent::Swapping(Event::SwapEgressScheduled {
-					swap_request_id: SwapRequestId(1),
+					swap_request_id: SwapRequestId(2),
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(1)
+					swap_request_id: SwapRequestId(2),
+					..
+				}),
+			);
+			assert!(!FailedSwapAttempts::<Test>::contains_key(SwapId(1)));
+			assert!(!FailedSwapAttempts::<Test>::contains_key(SwapId(2)));
+		});
+}
+
+fn flip_to_usdc_swap(input_amount: AssetAmount) -> TestSwapParams {
+	TestSwapParams {
+		input_asset: Asset::Flip,
+		output_asset: Asset::Usdc,
+		input_amount,
+		refund_params: None,
+		dca_params: None,
+		output_address: ForeignChainAddress::Eth([2; 20].into()),
+		is_ccm: false,
+	}
+}
+
+#[test]
+fn small_swaps_are_prioritised_when_batch_size_is_limited() {
+	const EXECUTE_AT_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+	const RETRY_AT_BLOCK: u64 = EXECUTE_AT_BLOCK + DEFAULT_SWAP_RETRY_DELAY_BLOCKS as u64;
+
+	new_test_ext()
+		.then_execute_at_block(INIT_BLOCK, |_| {
+			ExecutionPolicy::<Test>::set(SwapExecutionPolicy {
+				max_swaps_per_batch: Some(1),
+				deprioritise_after_failures: None,
+			});
+			SmallSwapThreshold::<Test>::insert(Asset::Flip, 1_000);
+
+			// The large swap is older, but the small one should be executed first:
+			insert_swaps(&[flip_to_usdc_swap(100_000), flip_to_usdc_swap(500)]);
+		})
+		.then_process_blocks_until_block(EXECUTE_AT_BLOCK)
+		.then_execute_with(|_| {
+			assert_event_sequence!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapDelayed {
+					swap_id: SwapId(1),
+					reason: SwapDelayReason::BatchFull,
+					..
+				}),
+				RuntimeEvent::Swapping(Event::SwapRescheduled {
+					swap_id: SwapId(1),
+					execute_at: RETRY_AT_BLOCK
 				}),
+				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(2), .. }),
+				RuntimeEvent::Swapping(Event::SwapEgressScheduled { .. }),
+				RuntimeEvent::Swapping(Event::SwapRequestCompleted { .. }),
+			);
+			// Swaps that didn't fit in the batch are not counted as failed:
+			assert_eq!(FailedSwapAttempts::<Test>::get(SwapId(1)), 0);
+		})
+		.then_process_blocks_until_block(RETRY_AT_BLOCK)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(1), .. })
+			);
+			assert_swaps_queue_is_empty();
+		});
+}
+
+#[test]
+fn swaps_that_do_not_fit_in_the_batch_are_retried_even_if_refund_is_due() {
+	const EXECUTE_AT_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+	const RETRY_AT_BLOCK: u64 = EXECUTE_AT_BLOCK + DEFAULT_SWAP_RETRY_DELAY_BLOCKS as u64;
+
+	new_test_ext()
+		.then_execute_at_block(INIT_BLOCK, |_| {
+			ExecutionPolicy::<Test>::set(SwapExecutionPolicy {
+				max_swaps_per_batch: Some(1),
+				deprioritise_after_failures: None,
+			});
+			SmallSwapThreshold::<Test>::insert(Asset::Flip, 1_000);
+
+			// The large swap would be refunded if it failed, since it can't be retried:
+			insert_swaps(&[
+				TestSwapParams {
+					refund_params: Some(
+						TestRefundParams { retry_duration: 0, min_output: 0 }
+							.into_channel_params(100_000),
+					),
+					..flip_to_usdc_swap(100_000)
+				},
+				flip_to_usdc_swap(500),
+			]);
+		})
+		.then_process_blocks_until_block(EXECUTE_AT_BLOCK)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapRescheduled {
+					swap_id: SwapId(1),
+					execute_at: RETRY_AT_BLOCK
+				})
+			);
+			// The swap request has not been refunded:
+			assert!(SwapRequests::<Test>::contains_key(SwapRequestId(1)));
+		})
+		.then_process_blocks_until_block(RETRY_AT_BLOCK)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(1), .. })
+			);
+			assert_swaps_queue_is_empty();
+		});
+}
+
+#[test]
+fn repeatedly_failing_large_swaps_are_deprioritised() {
+	const EXECUTE_AT_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+	const RETRY_AT_BLOCK: u64 = EXECUTE_AT_BLOCK + DEFAULT_SWAP_RETRY_DELAY_BLOCKS as u64;
+	const SECOND_RETRY_AT_BLOCK: u64 = RETRY_AT_BLOCK + DEFAULT_SWAP_RETRY_DELAY_BLOCKS as u64;
+
+	new_test_ext()
+		.then_execute_at_block(INIT_BLOCK, |_| {
+			ExecutionPolicy::<Test>::set(SwapExecutionPolicy {
+				max_swaps_per_batch: Some(1),
+				deprioritise_after_failures: Some(1),
+			});
+			insert_swaps(&[flip_to_usdc_swap(100_000)]);
+			MockSwappingApi::set_swaps_should_fail(true);
+		})
+		.then_process_blocks_until_block(EXECUTE_AT_BLOCK)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapRescheduled {
+					swap_id: SwapId(1),
+					execute_at: RETRY_AT_BLOCK
+				})
+			);
+			assert_eq!(FailedSwapAttempts::<Test>::get(SwapId(1)), 1);
+			MockSwappingApi::set_swaps_should_fail(false);
+		})
+		.then_process_blocks_until_block(RETRY_AT_BLOCK - SWAP_DELAY_BLOCKS as u64 - 1)
+		.then_execute_at_next_block(|_| {
+			// A newer swap that is due at the same block as the retry:
+			insert_swaps(&[flip_to_usdc_swap(50_000)]);
+		})
+		.then_process_blocks_until_block(RETRY_AT_BLOCK)
+		.then_execute_with(|_| {
+			assert_event_sequence!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapDelayed {
+					swap_id: SwapId(1),
+					reason: SwapDelayReason::BatchFull,
+					..
+				}),
+				RuntimeEvent::Swapping(Event::SwapRescheduled {
+					swap_id: SwapId(1),
+					execute_at: SECOND_RETRY_AT_BLOCK
+				}),
+				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(2), .. }),
+				RuntimeEvent::Swapping(Event::SwapEgressScheduled { .. }),
+				RuntimeEvent::Swapping(Event::SwapRequestCompleted { .. }),
+			);
+			assert_eq!(FailedSwapAttempts::<Test>::get(SwapId(1)), 1);
+		})
+		.then_process_blocks_until_block(SECOND_RETRY_AT_BLOCK)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(1), .. })
+			);
+			assert!(!FailedSwapAttempts::<Test>::contains_key(SwapId(1)));
+		});
+}
+
+#[test]
+fn swaps_spill_into_next_block_when_max_swaps_per_block_is_reached() {
+	const EXECUTE_AT_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+
+	new_test_ext()
+		.then_execute_at_block(INIT_BLOCK, |_| {
+			MaxSwapsScheduledPerBlock::<Test>::set(Some(2));
+			insert_swaps(&(0..5).map(|_| flip_to_usdc_swap(1_000)).collect::<Vec<_>>());
+
+			assert_eq!(SwapQueue::<Test>::decode_len(EXECUTE_AT_BLOCK), Some(2));
+			assert_eq!(SwapQueue::<Test>::decode_len(EXECUTE_AT_BLOCK + 1), Some(2));
+			assert_eq!(SwapQueue::<Test>::decode_len(EXECUTE_AT_BLOCK + 2), Some(1));
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapScheduled {
+					swap_id: SwapId(5),
+					execute_at: block,
+					..
+				}) if *block == EXECUTE_AT_BLOCK + 2
+			);
+		})
+		.then_process_blocks_until_block(EXECUTE_AT_BLOCK + 2)
+		.then_execute_with(|_| {
+			assert_swaps_queue_is_empty();
+		});
+}
+
+#[test]
+fn search_for_block_with_capacity_is_bounded() {
+	const EXECUTE_AT_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+	const LAST_BLOCK_SEARCHED: u64 =
+		EXECUTE_AT_BLOCK + MAX_BLOCKS_SEARCHED_FOR_SWAP_CAPACITY as u64 - 1;
+
+	new_test_ext().then_execute_at_block(INIT_BLOCK, |_| {
+		MaxSwapsScheduledPerBlock::<Test>::set(Some(1));
+		insert_swaps(
+			&(0..MAX_BLOCKS_SEARCHED_FOR_SWAP_CAPACITY + 1)
+				.map(|_| flip_to_usdc_swap(1_000))
+				.collect::<Vec<_>>(),
+		);
+
+		// Once all searched blocks are full, further swaps go into the last of them:
+		for block in EXECUTE_AT_BLOCK..LAST_BLOCK_SEARCHED {
+			assert_eq!(SwapQueue::<Test>::decode_len(block), Some(1));
+		}
+		assert_eq!(SwapQueue::<Test>::decode_len(LAST_BLOCK_SEARCHED), Some(2));
+		assert_eq!(SwapQueue::<Test>::decode_len(LAST_BLOCK_SEARCHED + 1), None);
+	});
+}
+
+#[test]
+fn repeatedly_failing_pools_are_paused_by_circuit_breaker() {
+	const EXECUTE_AT_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+	const RETRY_AT_BLOCK: u64 = EXECUTE_AT_BLOCK + DEFAULT_SWAP_RETRY_DELAY_BLOCKS as u64;
+	const SECOND_RETRY_AT_BLOCK: u64 = RETRY_AT_BLOCK + DEFAULT_SWAP_RETRY_DELAY_BLOCKS as u64;
+	const THIRD_RETRY_AT_BLOCK: u64 =
+		SECOND_RETRY_AT_BLOCK + DEFAULT_SWAP_RETRY_DELAY_BLOCKS as u64;
+	const COOL_DOWN_BLOCKS: BlockNumber = 100;
+
+	new_test_ext()
+		.then_execute_at_block(INIT_BLOCK, |_| {
+			assert_ok!(Swapping::update_pallet_config(
+				OriginTrait::root(),
+				bounded_vec![PalletConfigUpdate::SetPoolCircuitBreaker {
+					circuit_breaker: Some(PoolCircuitBreaker {
+						failure_threshold: 2,
+						cool_down_blocks: COOL_DOWN_BLOCKS,
+					}),
+				}],
+			));
+			insert_swaps(&[flip_to_usdc_swap(100_000)]);
+			MockSwappingApi::set_swaps_should_fail(true);
+		})
+		.then_process_blocks_until_block(EXECUTE_AT_BLOCK)
+		.then_execute_with(|_| {
+			assert_eq!(ConsecutivePoolFailures::<Test>::get(Asset::Flip), 1);
+			assert!(!PausedPools::<Test>::contains_key(Asset::Flip));
+		})
+		.then_process_blocks_until_block(RETRY_AT_BLOCK)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::PoolCircuitBreakerTripped {
+					asset: Asset::Flip,
+					consecutive_failures: 2,
+					paused_until,
+				}) if *paused_until == RETRY_AT_BLOCK + COOL_DOWN_BLOCKS as u64
+			);
+			assert_eq!(FailedSwapAttempts::<Test>::get(SwapId(1)), 2);
+		})
+		.then_process_blocks_until_block(SECOND_RETRY_AT_BLOCK)
+		.then_execute_with(|_| {
+			// The swap is not attempted while the pool is paused:
+			assert_event_sequence!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapDelayed {
+					swap_id: SwapId(1),
+					reason: SwapDelayReason::PoolPaused { asset: Asset::Flip },
+					..
+				}),
+				RuntimeEvent::Swapping(Event::SwapRescheduled {
+					swap_id: SwapId(1),
+					execute_at: THIRD_RETRY_AT_BLOCK
+				}),
+			);
+			assert_eq!(FailedSwapAttempts::<Test>::get(SwapId(1)), 2);
+
+			// Governance can resume swaps through the pool before the cool-down has elapsed:
+			assert_ok!(Swapping::update_pallet_config(
+				OriginTrait::root(),
+				bounded_vec![PalletConfigUpdate::ResetPoolCircuitBreaker { asset: Asset::Flip }],
+			));
+			assert!(!PausedPools::<Test>::contains_key(Asset::Flip));
+			MockSwappingApi::set_swaps_should_fail(false);
+		})
+		.then_process_blocks_until_block(THIRD_RETRY_AT_BLOCK)
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: SwapId(1), .. })
+			);
+			assert_swaps_queue_is_empty();
+		});
+}
+
+#[test]
+fn deposit_address_ready_event_contains_correct_parameters() {
+	new_test_ext().execute_with(|| {
+		let refund_parameters = ChannelRefundParametersEncoded {
+			retry_duration: 10,
+			refund_address: EncodedAddress::Eth([10; 20]),
+			min_price: 100.into(),
+		};
+
+		let dca_parameters = DcaParameters { number_of_chunks: 5, chunk_interval: 2 };
+
+		const BOOST_FEE: u16 = 100;
+		assert_ok!(Swapping::request_swap_deposit_address_with_parameters(
+			RuntimeOrigin::signed(BROKER),
+			Asset::Eth,
+			Asset::Usdc,
+			EncodedAddress::Eth(Default::default()),
+			0,
+			None,
+			BOOST_FEE,
+			Default::default(),
+			Some(refund_parameters.clone()),
+			Some(dca_parameters.clone()),
+			None,
+			false,
+			None,
+		));
+		assert_event_sequence!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapDepositAddressReady {
+				boost_fee: BOOST_FEE,
+				refund_parameters: Some(ref refund_params_in_event),
+				dca_parameters: Some(ref dca_params_in_event),
+				..
+			}) if *refund_params_in_event == refund_parameters && dca_params_in_event == &dca_parameters
+		);
+	});
+}
+
+#[test]
+fn refund_parameters_fall_back_to_chain_defaults() {
+	new_test_ext().execute_with(|| {
+		const DEFAULT_RETRY_DURATION: u32 = 100;
+
+		assert_ok!(Swapping::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetDefaultRefundParameters {
+				chain: ForeignChain::Ethereum,
+				defaults: Some(RefundParametersDefaults {
+					retry_duration: DEFAULT_RETRY_DURATION,
+					max_oracle_slippage: 50,
+				}),
+			}]
+			.try_into()
+			.unwrap()
+		));
+
+		// With the mock swap rate of 2, the market price of ETH in USDC is 2.
+		let market_price = U256::from(2u128) << PRICE_FRACTIONAL_BITS;
+		let min_price_with_max_slippage = market_price * 9_950 / 10_000;
+
+		const DESTINATION_ADDRESS: EncodedAddress = EncodedAddress::Eth([1; 20]);
+
+		let open_channel = |refund_parameters: Option<ChannelRefundParametersEncoded>| {
+			Swapping::request_swap_deposit_address_with_parameters(
+				RuntimeOrigin::signed(BROKER),
+				Asset::Eth,
+				Asset::Usdc,
+				DESTINATION_ADDRESS,
+				0,
+				None,
+				0,
+				Default::default(),
+				refund_parameters,
+				None,
+				None,
+				false,
+				None,
+			)
+		};
+		let refund_parameters = |retry_duration: u32, min_price: U256| {
+			Some(ChannelRefundParametersEncoded {
+				retry_duration,
+				refund_address: EncodedAddress::Eth([10; 20]),
+				min_price,
+			})
+		};
+
+		// Without refund parameters, the defaults are applied and refunds go to the destination
+		// address, which is on the source chain.
+		assert_ok!(open_channel(None));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapDepositAddressReady {
+				refund_parameters: Some(ChannelRefundParametersEncoded {
+					retry_duration,
+					refund_address,
+					min_price,
+				}),
+				..
+			}) if *retry_duration == DEFAULT_RETRY_DURATION &&
+				*refund_address == DESTINATION_ADDRESS &&
+				*min_price == min_price_with_max_slippage
+		);
+
+		// A retry duration and minimum price of zero are replaced by the defaults.
+		System::reset_events();
+		assert_ok!(open_channel(refund_parameters(0, U256::zero())));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapDepositAddressReady {
+				refund_parameters: Some(ChannelRefundParametersEncoded {
+					retry_duration,
+					min_price,
+					..
+				}),
+				..
+			}) if *retry_duration == DEFAULT_RETRY_DURATION && *min_price == min_price_with_max_slippage
+		);
+		assert!(!System::events().iter().any(|record| matches!(
+			record.event,
+			RuntimeEvent::Swapping(
+				Event::RefundParametersDeviateFromDefaults { .. } |
+					Event::MinPriceBelowMaxOracleSlippage { .. }
+			)
+		)));
+
+		// A retry duration far below the default is flagged.
+		System::reset_events();
+		assert_ok!(open_channel(refund_parameters(1, min_price_with_max_slippage)));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::RefundParametersDeviateFromDefaults {
+				retry_duration: 1,
+				default_retry_duration: DEFAULT_RETRY_DURATION,
+				..
+			})
+		);
+
+		// So is a minimum price that allows for more than the maximum slippage.
+		System::reset_events();
+		assert_ok!(open_channel(refund_parameters(DEFAULT_RETRY_DURATION, 100.into())));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::MinPriceBelowMaxOracleSlippage {
+				min_price,
+				min_price_with_max_slippage: expected_min_price,
+				..
+			}) if *min_price == U256::from(100) && *expected_min_price == min_price_with_max_slippage
+		);
+	});
+}
+
+#[test]
+fn channels_without_a_derivable_refund_address_are_opened_without_refund_parameters() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Swapping::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetDefaultRefundParameters {
+				chain: ForeignChain::Ethereum,
+				defaults: Some(RefundParametersDefaults {
+					retry_duration: 100,
+					max_oracle_slippage: 50,
+				}),
+			}]
+			.try_into()
+			.unwrap()
+		));
+
+		// The destination address is not on the source chain, so it can't be refunded to. The
+		// legacy extrinsic, which never passes refund parameters, still opens the channel.
+		assert_ok!(Swapping::request_swap_deposit_address(
+			RuntimeOrigin::signed(BROKER),
+			Asset::Eth,
+			Asset::Dot,
+			EncodedAddress::Dot(Default::default()),
+			0,
+			None,
+			0,
+		));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapDepositAddressReady { refund_parameters: None, .. })
+		);
+	});
+}
+
+#[test]
+fn min_price_is_checked_against_market_price() {
+	new_test_ext().execute_with(|| {
+		// With the mock swap rate of 2 per leg, the market price of ETH in FLIP is 4.
+		let market_price = U256::from(4u128) << PRICE_FRACTIONAL_BITS;
+
+		let open_channel = |min_price: U256| {
+			Swapping::request_swap_deposit_address_with_parameters(
+				RuntimeOrigin::signed(BROKER),
+				Asset::Eth,
+				Asset::Flip,
+				EncodedAddress::Eth(Default::default()),
+				0,
+				None,
+				0,
+				Default::default(),
+				Some(ChannelRefundParametersEncoded {
+					retry_duration: 0,
+					refund_address: EncodedAddress::Eth([10; 20]),
+					min_price,
+				}),
+				None,
+				None,
+				false,
+				None,
+			)
+		};
+		let min_price_flagged = || {
+			System::events().iter().any(|record| {
+				matches!(
+					record.event,
+					RuntimeEvent::Swapping(Event::MinPriceAboveMarketPrice { .. })
+				)
+			})
+		};
+
+		// A min price below the market price is accepted without warning.
+		assert_ok!(open_channel(market_price / 2));
+		assert!(!min_price_flagged());
+
+		// Without a limit, any min price above the market price is accepted, but flagged.
+		assert_ok!(open_channel(market_price * 100));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::MinPriceAboveMarketPrice {
+				market_price: price,
+				..
+			}) if *price == market_price
+		);
+
+		assert_ok!(Swapping::update_pallet_config(
+			OriginTrait::root(),
+			vec![PalletConfigUpdate::SetMaxMinPriceMultiple { multiple: Some(10) }]
+				.try_into()
+				.unwrap()
+		));
+
+		System::reset_events();
+		assert_ok!(open_channel(market_price * 10));
+		assert!(min_price_flagged());
+		assert_noop!(
+			open_channel(market_price * 10 + U256::one()),
+			Error::<Test>::MinPriceTooFarAboveMarketPrice
+		);
+	});
+}
+
+#[test]
+fn on_chain_swap_is_credited_to_account() {
+	const INPUT_AMOUNT: AssetAmount = 1000;
+	const CHUNK_INTERVAL: u32 = 3;
+	const LAST_CHUNK_BLOCK: u64 = INIT_BLOCK + (SWAP_DELAY_BLOCKS + CHUNK_INTERVAL) as u64;
+
+	new_test_ext()
+		.then_execute_at_block(INIT_BLOCK, |_| {
+			<MockAccountRoleRegistry as AccountRoleRegistry<Test>>::register_as_liquidity_provider(
+				&ALICE,
+			)
+			.unwrap();
+			MockBalance::credit_account(&ALICE, Asset::Eth, INPUT_AMOUNT);
+
+			// Only LPs and brokers can swap on chain:
+			assert_noop!(
+				Swapping::swap_on_chain(
+					RuntimeOrigin::signed(BOB),
+					Asset::Eth,
+					INPUT_AMOUNT,
+					Asset::Usdc,
+					0,
+					U256::zero(),
+					None,
+				),
+				sp_runtime::traits::BadOrigin
+			);
+
+			<MockRuntimeSafeMode as SetSafeMode<MockRuntimeSafeMode>>::set_code_red();
+			assert_noop!(
+				Swapping::swap_on_chain(
+					RuntimeOrigin::signed(ALICE),
+					Asset::Eth,
+					INPUT_AMOUNT,
+					Asset::Usdc,
+					0,
+					U256::zero(),
+					None,
+				),
+				Error::<Test>::SwapsDisabled
+			);
+			<MockRuntimeSafeMode as SetSafeMode<MockRuntimeSafeMode>>::set_code_green();
+
+			assert_ok!(Swapping::swap_on_chain(
+				RuntimeOrigin::signed(ALICE),
+				Asset::Eth,
+				INPUT_AMOUNT,
+				Asset::Usdc,
+				0,
+				U256::zero(),
+				Some(DcaParameters { number_of_chunks: 2, chunk_interval: CHUNK_INTERVAL }),
+			));
+			assert_eq!(MockBalance::get_balance(&ALICE, Asset::Eth), 0);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapRequested {
+					origin: SwapOrigin::OnChainAccount(ALICE),
+					request_type: SwapRequestTypeEncoded::CreditOnChain { account_id: ALICE },
+					..
+				})
+			);
+		})
+		.then_process_blocks_until_block(LAST_CHUNK_BLOCK)
+		.then_execute_with(|_| {
+			assert_eq!(
+				MockBalance::get_balance(&ALICE, Asset::Usdc),
+				INPUT_AMOUNT * DEFAULT_SWAP_RATE
+			);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapOutputCredited {
+					account_id: ALICE,
+					asset: Asset::Usdc,
+					amount,
+					..
+				}) if *amount == INPUT_AMOUNT * DEFAULT_SWAP_RATE
+			);
+			assert_eq!(MockEgressHandler::<AnyChain>::get_scheduled_egresses(), vec![]);
+		});
+}
+
+#[test]
+fn on_chain_swap_is_refunded_to_account() {
+	const INPUT_AMOUNT: AssetAmount = 1000;
+	const SWAP_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+
+	new_test_ext()
+		.then_execute_at_block(INIT_BLOCK, |_| {
+			MockBalance::credit_account(&BROKER, Asset::Eth, INPUT_AMOUNT);
+
+			// The min price is above the swap rate, so the swap can't be executed:
+			assert_ok!(Swapping::swap_on_chain(
+				RuntimeOrigin::signed(BROKER),
+				Asset::Eth,
+				INPUT_AMOUNT,
+				Asset::Usdc,
+				0,
+				U256::from(DEFAULT_SWAP_RATE + 1) << PRICE_FRACTIONAL_BITS,
+				None,
+			));
+			assert_eq!(MockBalance::get_balance(&BROKER, Asset::Eth), 0);
+		})
+		.then_process_blocks_until_block(SWAP_BLOCK)
+		.then_execute_with(|_| {
+			assert_eq!(MockBalance::get_balance(&BROKER, Asset::Eth), INPUT_AMOUNT);
+			assert_eq!(MockBalance::get_balance(&BROKER, Asset::Usdc), 0);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::RefundCredited {
+					account_id: BROKER,
+					asset: Asset::Eth,
+					amount: INPUT_AMOUNT,
+					..
+				})
+			);
+			assert_eq!(MockEgressHandler::<AnyChain>::get_scheduled_egresses(), vec![]);
+		});
+}
+
+#[test]
+fn other_pallets_can_swap_on_chain_balances() {
+	const INPUT_AMOUNT: AssetAmount = 1000;
+	const SWAP_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+	// Any account can be used, regardless of its role:
+	const PALLET_ACCOUNT: u64 = 1234;
+
+	new_test_ext()
+		.then_execute_at_block(INIT_BLOCK, |_| {
+			MockBalance::credit_account(&PALLET_ACCOUNT, Asset::Eth, INPUT_AMOUNT);
+
+			// Nothing is debited if the swap can't be requested:
+			assert_noop!(
+				Swapping::swap_from_on_chain_balance(
+					&PALLET_ACCOUNT,
+					Asset::Eth,
+					INPUT_AMOUNT,
+					Asset::Eth,
+					0,
+					U256::zero(),
+					None,
+				),
+				Error::<Test>::SameInputAndOutputAsset
+			);
+			assert!(Swapping::swap_from_on_chain_balance(
+				&PALLET_ACCOUNT,
+				Asset::Eth,
+				INPUT_AMOUNT + 1,
+				Asset::Usdc,
+				0,
+				U256::zero(),
+				None,
+			)
+			.is_err());
+			assert_eq!(MockBalance::get_balance(&PALLET_ACCOUNT, Asset::Eth), INPUT_AMOUNT);
+
+			let swap_request_id = Swapping::swap_from_on_chain_balance(
+				&PALLET_ACCOUNT,
+				Asset::Eth,
+				INPUT_AMOUNT,
+				Asset::Usdc,
+				0,
+				U256::zero(),
+				None,
+			)
+			.unwrap();
+			assert_eq!(MockBalance::get_balance(&PALLET_ACCOUNT, Asset::Eth), 0);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::OnChainSwapRequested {
+					swap_request_id: id,
+					account_id: PALLET_ACCOUNT,
+					..
+				}) if *id == swap_request_id
+			);
+		})
+		.then_process_blocks_until_block(SWAP_BLOCK)
+		.then_execute_with(|_| {
+			assert_eq!(
+				MockBalance::get_balance(&PALLET_ACCOUNT, Asset::Usdc),
+				INPUT_AMOUNT * DEFAULT_SWAP_RATE
 			);
+			assert_eq!(MockEgressHandler::<AnyChain>::get_scheduled_egresses(), vec![]);
 		});
 }
 
 #[test]
-fn deposit_address_ready_event_contains_correct_parameters() {
-	new_test_ext().execute_with(|| {
-		let refund_parameters = ChannelRefundParametersEncoded {
-			retry_duration: 10,
-			refund_address: EncodedAddress::Eth([10; 20]),
-			min_price: 100.into(),
-		};
+fn swap_completed_hook_is_called_for_user_swaps_only() {
+	const INPUT_AMOUNT: AssetAmount = 1000;
 
-		let dca_parameters = DcaParameters { number_of_chunks: 5, chunk_interval: 2 };
-
-		const BOOST_FEE: u16 = 100;
-		assert_ok!(Swapping::request_swap_deposit_address_with_affiliates(
-			RuntimeOrigin::signed(BROKER),
-			Asset::Eth,
-			Asset::Usdc,
-			EncodedAddress::Eth(Default::default()),
-			0,
-			None,
-			BOOST_FEE,
-			Default::default(),
-			Some(refund_parameters.clone()),
-			Some(dca_parameters.clone()),
-		));
-		assert_event_sequence!(
-			Test,
-			RuntimeEvent::Swapping(Event::SwapDepositAddressReady {
-				boost_fee: BOOST_FEE,
-				refund_parameters: Some(ref refund_params_in_event),
-				dca_parameters: Some(ref dca_params_in_event),
-				..
-			}) if *refund_params_in_event == refund_parameters && dca_params_in_event == &dca_parameters
-		);
-	});
+	new_test_ext()
+		.execute_with(|| {
+			Swapping::init_swap_request(
+				Asset::Btc,
+				INPUT_AMOUNT,
+				Asset::Eth,
+				SwapRequestType::Regular {
+					output_address: ForeignChainAddress::Eth(H160::zero()),
+					ccm_deposit_metadata: None,
+					egress_memo: None,
+				},
+				bounded_vec![Beneficiary { account: BROKER, bps: 0 }],
+				None,
+				None,
+				SwapOrigin::Vault {
+					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
+					broker_id: Some(BROKER),
+					verified_sender: None,
+				},
+				None,
+			);
+			Swapping::init_swap_request(
+				Asset::Usdc,
+				INPUT_AMOUNT,
+				Asset::Flip,
+				SwapRequestType::NetworkFee,
+				Default::default(),
+				None,
+				None,
+				SwapOrigin::Internal,
+				None,
+			);
+		})
+		.then_process_blocks(SWAP_DELAY_BLOCKS)
+		.then_execute_with(|_| {
+			assert_eq!(
+				CompletedSwaps::get(),
+				vec![CompletedSwap {
+					swap_request_id: SwapRequestId(1),
+					accounts: vec![BROKER],
+					input_asset: Asset::Btc,
+					input_amount: INPUT_AMOUNT,
+					output_asset: Asset::Eth,
+					output_amount: INPUT_AMOUNT * DEFAULT_SWAP_RATE * DEFAULT_SWAP_RATE,
+				}]
+			);
+		});
 }
 
 #[test]
@@ -1187,6 +2459,37 @@ fn test_get_scheduled_swap_legs_fallback() {
 	});
 }
 
+#[test]
+fn test_estimate_scheduled_swap_legs() {
+	new_test_ext().execute_with(|| {
+		const INIT_AMOUNT: AssetAmount = 1000000000000000000000;
+		const PRICE: u128 = 2;
+
+		let swaps = vec![
+			create_test_swap(1, Asset::Flip, Asset::Eth, INIT_AMOUNT, None),
+			create_test_swap(2, Asset::Usdc, Asset::Eth, INIT_AMOUNT, None),
+			create_test_swap(3, Asset::Eth, Asset::Usdc, INIT_AMOUNT, None),
+		];
+
+		// The swaps would succeed, but the estimate must only use the pool price.
+		SwapRate::set(PRICE.checked_add(1).unwrap() as f64);
+
+		let sqrt_price = price_to_sqrt_price((U256::from(PRICE)) << PRICE_FRACTIONAL_BITS);
+
+		assert_eq!(
+			Swapping::estimate_scheduled_swap_legs(swaps, Asset::Eth, Some(sqrt_price))
+				.into_iter()
+				.map(|leg| (leg.swap_id, leg.side, leg.amount))
+				.collect::<Vec<_>>(),
+			vec![
+				(SwapId(1), Side::Buy, INIT_AMOUNT * PRICE),
+				(SwapId(2), Side::Buy, INIT_AMOUNT),
+				(SwapId(3), Side::Sell, INIT_AMOUNT),
+			]
+		);
+	});
+}
+
 #[test]
 fn test_get_scheduled_swap_legs_for_dca() {
 	new_test_ext().execute_with(|| {
@@ -1280,6 +2583,41 @@ fn broker_deregistration_checks_private_channels() {
 	});
 }
 
+#[test]
+fn broker_deregistration_checks_open_channels_and_expires_rejection_requests() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Swapping::request_swap_deposit_address_with_parameters(
+			RuntimeOrigin::signed(BROKER),
+			Asset::Eth,
+			Asset::Usdc,
+			EncodedAddress::Eth(Default::default()),
+			0,
+			None,
+			0,
+			Default::default(),
+			None,
+			None,
+			None,
+			false,
+			None,
+		));
+
+		assert_noop!(
+			Swapping::deregister_as_broker(OriginTrait::signed(BROKER)),
+			Error::<Test>::UnrecycledDepositChannelsExistForBroker,
+		);
+
+		MockDepositHandler::<AnyChain, Test>::close_all_channels();
+
+		assert_ok!(Swapping::deregister_as_broker(OriginTrait::signed(BROKER)));
+
+		assert_eq!(
+			MockDepositHandler::<AnyChain, Test>::get_expired_rejection_requests(),
+			vec![BROKER]
+		);
+	});
+}
+
 #[test]
 fn swap_output_amounts_correctly_account_for_fees() {
 	for (from, to) in
@@ -1316,6 +2654,7 @@ fn swap_output_amounts_correctly_account_for_fees() {
 					SwapRequestType::Regular {
 						output_address: ForeignChainAddress::Eth(H160::zero()),
 						ccm_deposit_metadata: None,
+						egress_memo: None,
 					},
 					Default::default(),
 					None,
@@ -1323,7 +2662,9 @@ fn swap_output_amounts_correctly_account_for_fees() {
 					SwapOrigin::Vault {
 						tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 						broker_id: Some(BROKER),
+						verified_sender: None,
 					},
+					None,
 				);
 
 				Swapping::on_finalize(System::block_number() + SWAP_DELAY_BLOCKS as u64);
@@ -1335,6 +2676,7 @@ fn swap_output_amounts_correctly_account_for_fees() {
 						amount: expected_output,
 						fee: 0,
 						destination_address: ForeignChainAddress::Eth(H160::zero()),
+						memo: None,
 					},]
 				);
 			}
@@ -1342,6 +2684,128 @@ fn swap_output_amounts_correctly_account_for_fees() {
 	}
 }
 
+#[test]
+fn egress_memo_is_passed_to_the_swap_egress() {
+	new_test_ext().execute_with(|| {
+		const INPUT_AMOUNT: AssetAmount = 1000;
+		let output_address = ForeignChainAddress::Dot(Default::default());
+		let memo: EgressMemo = b"exchange-memo".to_vec().try_into().unwrap();
+
+		Swapping::init_swap_request(
+			Asset::Eth,
+			INPUT_AMOUNT,
+			Asset::Dot,
+			SwapRequestType::Regular {
+				output_address: output_address.clone(),
+				ccm_deposit_metadata: None,
+				egress_memo: Some(memo.clone()),
+			},
+			Default::default(),
+			None,
+			None,
+			SwapOrigin::Vault {
+				tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
+				broker_id: Some(BROKER),
+				verified_sender: None,
+			},
+			None,
+		);
+
+		Swapping::on_finalize(System::block_number() + SWAP_DELAY_BLOCKS as u64);
+
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses(),
+			vec![MockEgressParameter::Swap {
+				asset: Asset::Dot,
+				amount: INPUT_AMOUNT * DEFAULT_SWAP_RATE * DEFAULT_SWAP_RATE,
+				fee: 0,
+				destination_address: output_address,
+				memo: Some(memo),
+			}]
+		);
+	});
+}
+
+#[test]
+fn swap_output_is_diverted_if_the_boosted_deposit_is_rejected() {
+	new_test_ext().execute_with(|| {
+		const INPUT_AMOUNT: AssetAmount = 1000;
+		let refund_params =
+			TestRefundParams { retry_duration: 0, min_output: 0 }.into_channel_params(INPUT_AMOUNT);
+
+		let swap_request = |output_asset| {
+			Swapping::init_swap_request(
+				Asset::Eth,
+				INPUT_AMOUNT,
+				output_asset,
+				SwapRequestType::Regular {
+					output_address: ForeignChainAddress::Dot(Default::default()),
+					ccm_deposit_metadata: None,
+					egress_memo: None,
+				},
+				Default::default(),
+				Some(refund_params.clone()),
+				None,
+				SwapOrigin::Vault {
+					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
+					broker_id: Some(BROKER),
+					verified_sender: None,
+				},
+				None,
+			)
+		};
+
+		// The output can be refunded to the refund address on the same chain.
+		let same_chain_request_id = swap_request(Asset::Flip);
+		// The output can't be sent to the refund address, so it's confiscated.
+		let other_chain_request_id = swap_request(Asset::Dot);
+		MockDepositHandler::<AnyChain, Test>::mark_swap_request_deposit_rejected(
+			same_chain_request_id,
+		);
+		MockDepositHandler::<AnyChain, Test>::mark_swap_request_deposit_rejected(
+			other_chain_request_id,
+		);
+
+		Swapping::on_finalize(System::block_number() + SWAP_DELAY_BLOCKS as u64);
+
+		const OUTPUT_AMOUNT: AssetAmount = INPUT_AMOUNT * DEFAULT_SWAP_RATE * DEFAULT_SWAP_RATE;
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapOutputDivertedForRejectedDeposit {
+				swap_request_id,
+				asset: Asset::Flip,
+				amount: OUTPUT_AMOUNT,
+			}) if *swap_request_id == same_chain_request_id
+		);
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::RefundEgressScheduled { swap_request_id, .. })
+				if *swap_request_id == same_chain_request_id
+		);
+		assert_eq!(
+			MockEgressHandler::<AnyChain>::get_scheduled_egresses(),
+			vec![MockEgressParameter::Swap {
+				asset: Asset::Flip,
+				amount: OUTPUT_AMOUNT,
+				fee: 0,
+				destination_address: refund_params.refund_address,
+				memo: None,
+			}]
+		);
+
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapAmountConfiscated {
+				swap_request_id,
+				asset: Asset::Dot,
+				confiscated_amount: OUTPUT_AMOUNT,
+				..
+			}) if *swap_request_id == other_chain_request_id
+		);
+		assert_eq!(CollectedRejectedFunds::<Test>::get(Asset::Dot), OUTPUT_AMOUNT);
+	});
+}
+
 #[test]
 fn test_buy_back_flip() {
 	new_test_ext().execute_with(|| {
@@ -1636,7 +3100,9 @@ mod swap_batching {
 mod private_channels {
 
 	use super::*;
-	use cf_traits::mocks::account_role_registry::MockAccountRoleRegistry;
+	use cf_traits::mocks::{
+		account_role_registry::MockAccountRoleRegistry, deposit_handler::MockDepositHandler,
+	};
 	use sp_runtime::DispatchError::BadOrigin;
 
 	#[test]
@@ -1727,6 +3193,42 @@ mod private_channels {
 		});
 	}
 
+	#[test]
+	fn private_channel_addresses_are_published_on_new_epoch() {
+		new_test_ext().execute_with(|| {
+			const CHANNEL_ID: u64 = 0;
+			MockFundingInfo::<Test>::credit_funds(&BROKER, FLIPPERINOS_PER_FLIP * 200);
+			assert_ok!(Swapping::open_private_btc_channel(OriginTrait::signed(BROKER)));
+
+			let old_address =
+				MockDepositHandler::<AnyChain, Test>::private_channel_deposit_address(
+					Asset::Btc,
+					CHANNEL_ID,
+				)
+				.unwrap();
+
+			// Rotating the vault changes the address of the private channel.
+			MockDepositHandler::<AnyChain, Test>::set_vault_key(1);
+			<Swapping as EpochTransitionHandler>::on_new_epoch(1);
+
+			let new_address =
+				MockDepositHandler::<AnyChain, Test>::private_channel_deposit_address(
+					Asset::Btc,
+					CHANNEL_ID,
+				)
+				.unwrap();
+			assert_ne!(old_address, new_address);
+
+			System::assert_has_event(RuntimeEvent::Swapping(
+				Event::<Test>::PrivateBrokerChannelAddressRotated {
+					broker_id: BROKER,
+					channel_id: CHANNEL_ID,
+					deposit_address: MockAddressConverter::to_encoded_address(new_address),
+				},
+			));
+		});
+	}
+
 	#[test]
 	fn register_affiliate() {
 		new_test_ext().execute_with(|| {
@@ -1780,6 +3282,153 @@ mod private_channels {
 		});
 	}
 
+	#[test]
+	fn deregister_affiliate() {
+		new_test_ext().execute_with(|| {
+			const SHORT_ID: AffiliateShortId = AffiliateShortId(0);
+
+			assert_noop!(
+				Swapping::deregister_affiliate(OriginTrait::signed(BROKER), SHORT_ID),
+				Error::<Test>::AffiliateNotRegistered
+			);
+
+			assert_ok!(Swapping::register_affiliate(OriginTrait::signed(BROKER), BOB, SHORT_ID));
+
+			// Only the broker that registered the affiliate can deregister it.
+			assert_noop!(
+				Swapping::deregister_affiliate(OriginTrait::signed(ALICE), SHORT_ID),
+				BadOrigin
+			);
+
+			// Affiliates with earned fees can't be deregistered.
+			<Test as Config>::BalanceApi::credit_account(&BOB, Asset::Eth, 100);
+			assert_noop!(
+				Swapping::deregister_affiliate(OriginTrait::signed(BROKER), SHORT_ID),
+				Error::<Test>::AffiliateEarnedFeesNotWithdrawn
+			);
+			<Test as Config>::BalanceApi::try_debit_account(&BOB, Asset::Eth, 100).unwrap();
+
+			assert_ok!(Swapping::deregister_affiliate(OriginTrait::signed(BROKER), SHORT_ID));
+			System::assert_has_event(RuntimeEvent::Swapping(
+				Event::<Test>::AffiliateDeregistered {
+					broker_id: BROKER,
+					affiliate_short_id: SHORT_ID,
+					affiliate_id: BOB,
+				},
+			));
+			assert_eq!(Swapping::get_short_id(&BROKER, &BOB), None);
+
+			// The short id can be reused.
+			assert_ok!(Swapping::register_affiliate(OriginTrait::signed(BROKER), ALICE, SHORT_ID));
+			assert_eq!(Swapping::get_short_id(&BROKER, &ALICE), Some(SHORT_ID));
+		});
+	}
+
+	#[test]
+	fn default_affiliates_are_merged_at_channel_open() {
+		new_test_ext().execute_with(|| {
+			const CHARLIE: u64 = 999;
+
+			let open_channel = |affiliate_fees: Vec<Beneficiary<u64>>, use_defaults: bool| {
+				Swapping::request_swap_deposit_address_with_parameters(
+					RuntimeOrigin::signed(BROKER),
+					Asset::Eth,
+					Asset::Usdc,
+					EncodedAddress::Eth(Default::default()),
+					100,
+					None,
+					0,
+					affiliate_fees.try_into().unwrap(),
+					None,
+					None,
+					None,
+					use_defaults,
+					None,
+				)
+			};
+			let last_channel_affiliates = || {
+				System::events()
+					.into_iter()
+					.rev()
+					.find_map(|record| match record.event {
+						RuntimeEvent::Swapping(Event::SwapDepositAddressReady {
+							affiliate_fees,
+							..
+						}) => Some(affiliate_fees.into_inner()),
+						_ => None,
+					})
+					.unwrap()
+			};
+
+			// The total fees of the defaults are limited.
+			assert_noop!(
+				Swapping::set_default_affiliates(
+					OriginTrait::signed(BROKER),
+					vec![Beneficiary { account: ALICE, bps: 1001 }].try_into().unwrap()
+				),
+				Error::<Test>::BrokerCommissionBpsTooHigh
+			);
+
+			let default_affiliates: Affiliates<u64> = vec![
+				Beneficiary { account: ALICE, bps: 10 },
+				Beneficiary { account: BOB, bps: 20 },
+			]
+			.try_into()
+			.unwrap();
+			assert_ok!(Swapping::set_default_affiliates(
+				OriginTrait::signed(BROKER),
+				default_affiliates.clone()
+			));
+			System::assert_has_event(RuntimeEvent::Swapping(Event::<Test>::DefaultAffiliatesSet {
+				broker_id: BROKER,
+				affiliate_fees: default_affiliates.clone(),
+			}));
+
+			// Defaults are only applied if requested.
+			assert_ok!(open_channel(vec![Beneficiary { account: CHARLIE, bps: 5 }], false));
+			assert_eq!(last_channel_affiliates(), vec![Beneficiary { account: CHARLIE, bps: 5 }]);
+
+			// Explicit affiliates take precedence over defaults for the same account.
+			assert_ok!(open_channel(
+				vec![
+					Beneficiary { account: CHARLIE, bps: 5 },
+					Beneficiary { account: BOB, bps: 30 }
+				],
+				true
+			));
+			assert_eq!(
+				last_channel_affiliates(),
+				vec![
+					Beneficiary { account: CHARLIE, bps: 5 },
+					Beneficiary { account: BOB, bps: 30 },
+					Beneficiary { account: ALICE, bps: 10 },
+				]
+			);
+
+			// The merged fees are subject to the same limit as explicit fees.
+			assert_noop!(
+				open_channel(vec![Beneficiary { account: CHARLIE, bps: 871 }], true),
+				Error::<Test>::BrokerCommissionBpsTooHigh
+			);
+
+			// The merged affiliates must fit in the maximum number of affiliates.
+			assert_noop!(
+				open_channel(
+					(1000..1004).map(|account| Beneficiary { account, bps: 1 }).collect(),
+					true
+				),
+				Error::<Test>::TooManyAffiliates
+			);
+
+			// An empty list removes the defaults.
+			assert_ok!(Swapping::set_default_affiliates(
+				OriginTrait::signed(BROKER),
+				Default::default()
+			));
+			assert!(!BrokerDefaultAffiliates::<Test>::contains_key(BROKER));
+		});
+	}
+
 	#[test]
 	fn default_broker_bond() {
 		new_test_ext().execute_with(|| {
@@ -10,6 +10,7 @@ fn init_ccm_swap_request(input_asset: Asset, output_asset: Asset, input_amount:
 	let origin = SwapOrigin::Vault {
 		tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 		broker_id: Some(BROKER),
+		verified_sender: None,
 	};
 
 	Swapping::init_swap_request(
@@ -19,11 +20,13 @@ fn init_ccm_swap_request(input_asset: Asset, output_asset: Asset, input_amount:
 		SwapRequestType::Regular {
 			ccm_deposit_metadata: Some(ccm_deposit_metadata.clone()),
 			output_address,
+			egress_memo: None,
 		},
 		Default::default(),
 		None,
 		None,
 		origin.clone(),
+		None,
 	);
 
 	System::assert_has_event(RuntimeEvent::Swapping(Event::SwapRequested {
@@ -36,11 +39,13 @@ fn init_ccm_swap_request(input_asset: Asset, output_asset: Asset, input_amount:
 				ccm_deposit_metadata.to_encoded::<<Test as pallet::Config>::AddressConverter>(),
 			),
 			output_address: encoded_output_address,
+			egress_memo: None,
 		},
 		dca_parameters: None,
 		refund_parameters: None,
 		broker_fees: Default::default(),
 		origin,
+		tracking_id: None,
 	}));
 }
 
@@ -85,7 +90,7 @@ fn can_process_ccms_via_swap_deposit_address() {
 			let ccm_deposit_metadata = generate_ccm_deposit();
 
 			// Can process CCM via Swap deposit
-			assert_ok!(Swapping::request_swap_deposit_address_with_affiliates(
+			assert_ok!(Swapping::request_swap_deposit_address_with_parameters(
 				RuntimeOrigin::signed(BROKER),
 				Asset::Dot,
 				Asset::Eth,
@@ -96,6 +101,9 @@ fn can_process_ccms_via_swap_deposit_address() {
 				Default::default(),
 				None,
 				None,
+				None,
+				false,
+				None,
 			));
 
 			Swapping::init_swap_request(
@@ -105,6 +113,7 @@ fn can_process_ccms_via_swap_deposit_address() {
 				SwapRequestType::Regular {
 					ccm_deposit_metadata: Some(ccm_deposit_metadata.clone()),
 					output_address: (*EVM_OUTPUT_ADDRESS).clone(),
+					egress_memo: None,
 				},
 				Default::default(),
 				None,
@@ -112,7 +121,9 @@ fn can_process_ccms_via_swap_deposit_address() {
 				SwapOrigin::Vault {
 					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 					broker_id: Some(BROKER),
+					verified_sender: None,
 				},
+				None,
 			);
 
 			// Principal swap is scheduled first
@@ -200,3 +211,108 @@ fn ccm_principal_swap_only() {
 			assert_eq!(CollectedRejectedFunds::<Test>::get(OUTPUT_ASSET), 0);
 		});
 }
+
+#[test]
+fn ccm_destinations_can_be_restricted_per_broker() {
+	new_test_ext().execute_with(|| {
+		let allowed_address =
+			MockAddressConverter::to_encoded_address((*EVM_OUTPUT_ADDRESS).clone());
+		let other_address = EncodedAddress::Eth([0xAA; 20]);
+
+		let request_ccm_channel = |destination_address: EncodedAddress| {
+			Swapping::request_swap_deposit_address_with_parameters(
+				RuntimeOrigin::signed(BROKER),
+				Asset::Dot,
+				Asset::Eth,
+				destination_address,
+				0,
+				Some(generate_ccm_channel()),
+				0,
+				Default::default(),
+				None,
+				None,
+				None,
+				false,
+				None,
+			)
+		};
+
+		// Without an allowlist, any destination can be called.
+		assert_ok!(request_ccm_channel(other_address.clone()));
+
+		assert_noop!(
+			Swapping::allow_ccm_destination(RuntimeOrigin::signed(ALICE), allowed_address.clone()),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(Swapping::allow_ccm_destination(
+			RuntimeOrigin::signed(BROKER),
+			allowed_address.clone()
+		));
+		System::assert_last_event(RuntimeEvent::Swapping(Event::<Test>::CcmDestinationAllowed {
+			broker_id: BROKER,
+			destination_address: allowed_address.clone(),
+		}));
+
+		assert_ok!(request_ccm_channel(allowed_address.clone()));
+		assert_noop!(
+			request_ccm_channel(other_address.clone()),
+			Error::<Test>::CcmDestinationNotAllowed
+		);
+		// Destinations on other chains are not affected.
+		assert_ok!(Swapping::request_swap_deposit_address_with_parameters(
+			RuntimeOrigin::signed(BROKER),
+			Asset::Eth,
+			Asset::Dot,
+			EncodedAddress::Dot(Default::default()),
+			0,
+			None,
+			0,
+			Default::default(),
+			None,
+			None,
+			None,
+			false,
+			None,
+		));
+
+		// Removing the last allowed destination lifts the restriction.
+		assert_ok!(Swapping::disallow_ccm_destination(
+			RuntimeOrigin::signed(BROKER),
+			allowed_address.clone()
+		));
+		System::assert_last_event(RuntimeEvent::Swapping(
+			Event::<Test>::CcmDestinationDisallowed {
+				broker_id: BROKER,
+				destination_address: allowed_address,
+			},
+		));
+		assert!(!CcmDestinationAllowlist::<Test>::contains_key(BROKER, ForeignChain::Ethereum));
+		assert_ok!(request_ccm_channel(other_address));
+	});
+}
+
+#[test]
+fn ccm_destinations_per_chain_are_bounded() {
+	new_test_ext().execute_with(|| {
+		let allow = |i: u32| {
+			let mut address = [0; 20];
+			address[..4].copy_from_slice(&i.to_be_bytes());
+			Swapping::allow_ccm_destination(
+				RuntimeOrigin::signed(BROKER),
+				EncodedAddress::Eth(address),
+			)
+		};
+		for i in 0..MAX_CCM_DESTINATIONS_PER_CHAIN {
+			assert_ok!(allow(i));
+		}
+
+		assert_noop!(allow(MAX_CCM_DESTINATIONS_PER_CHAIN), Error::<Test>::TooManyCcmDestinations);
+		// Allowing a destination again is still possible.
+		assert_ok!(allow(0));
+		// Other chains have their own bound.
+		assert_ok!(Swapping::allow_ccm_destination(
+			RuntimeOrigin::signed(BROKER),
+			EncodedAddress::Dot(Default::default()),
+		));
+	});
+}
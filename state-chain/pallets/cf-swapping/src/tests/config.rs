@@ -11,6 +11,22 @@ fn can_update_all_config_items() {
 		const MAX_SWAP_REQUEST_DURATION: u32 = 420_u32;
 		const NEW_MINIMUM_CHUNK_SIZE: AssetAmount = 10_000;
 		const NEW_MINIMUM_NETWORK_FEE: AssetAmount = 10;
+		const NEW_CCM_LIMITS: CcmLimits = CcmLimits {
+			max_message_length: 1_000,
+			max_gas_budget: 1_000_000,
+			max_additional_data_length: 100,
+		};
+		const NEW_SWAP_EXECUTION_POLICY: SwapExecutionPolicy = SwapExecutionPolicy {
+			max_swaps_per_batch: Some(50),
+			deprioritise_after_failures: Some(3),
+		};
+		const NEW_SMALL_SWAP_THRESHOLD: Option<AssetAmount> = Some(1_000);
+		const NEW_REFUND_DEFAULTS: RefundParametersDefaults =
+			RefundParametersDefaults { retry_duration: 100, max_oracle_slippage: 50 };
+		const NEW_MAX_MIN_PRICE_MULTIPLE: Option<u32> = Some(10);
+		const NEW_POOL_CIRCUIT_BREAKER: Option<PoolCircuitBreaker> =
+			Some(PoolCircuitBreaker { failure_threshold: 5, cool_down_blocks: 100 });
+		const NEW_MAX_SWAPS_SCHEDULED_PER_BLOCK: Option<u32> = Some(20);
 
 		NetworkFee::set(Permill::from_perthousand(1));
 
@@ -23,8 +39,18 @@ fn can_update_all_config_items() {
 		assert_ne!(MaxSwapRequestDurationBlocks::<Test>::get(), MAX_SWAP_REQUEST_DURATION);
 		assert_ne!(MinimumChunkSize::<Test>::get(Asset::Eth), NEW_MINIMUM_CHUNK_SIZE);
 		assert_ne!(MinimumNetworkFeePerChunk::<Test>::get(), NEW_MINIMUM_NETWORK_FEE);
-
-		// Update all config items at the same time, and updates 2 separate max swap amounts.
+		assert!(CcmLimitsPerChain::<Test>::get(ForeignChain::Ethereum).is_none());
+		assert_ne!(ExecutionPolicy::<Test>::get(), NEW_SWAP_EXECUTION_POLICY);
+		assert!(SmallSwapThreshold::<Test>::get(Asset::Flip).is_none());
+		assert!(DefaultRefundParameters::<Test>::get(ForeignChain::Ethereum).is_none());
+		assert!(!RefundExcessSwapAmount::<Test>::get());
+		assert!(MaxMinPriceMultiple::<Test>::get().is_none());
+		assert!(PoolCircuitBreakerConfig::<Test>::get().is_none());
+		assert!(MaxSwapsScheduledPerBlock::<Test>::get().is_none());
+		assert_eq!(BoostNetworkFeeSwapRoute::<Test>::get(), BoostNetworkFeeRoute::Standard);
+
+		// Update all config items, and updates 2 separate max swap amounts. The updates are split
+		// across two calls since there are more items than fit in a single call.
 		assert_ok!(Swapping::update_pallet_config(
 			OriginTrait::root(),
 			vec![
@@ -47,6 +73,37 @@ fn can_update_all_config_items() {
 				PalletConfigUpdate::SetMinimumNetworkFeePerChunk {
 					min_fee: NEW_MINIMUM_NETWORK_FEE
 				},
+				PalletConfigUpdate::SetCcmLimits {
+					chain: ForeignChain::Ethereum,
+					limits: Some(NEW_CCM_LIMITS)
+				},
+				PalletConfigUpdate::SetSwapExecutionPolicy { policy: NEW_SWAP_EXECUTION_POLICY },
+			]
+			.try_into()
+			.unwrap()
+		));
+		assert_ok!(Swapping::update_pallet_config(
+			OriginTrait::root(),
+			vec![
+				PalletConfigUpdate::SetSmallSwapThreshold {
+					asset: Asset::Flip,
+					threshold: NEW_SMALL_SWAP_THRESHOLD
+				},
+				PalletConfigUpdate::SetDefaultRefundParameters {
+					chain: ForeignChain::Ethereum,
+					defaults: Some(NEW_REFUND_DEFAULTS)
+				},
+				PalletConfigUpdate::SetRefundExcessSwapAmount { enabled: true },
+				PalletConfigUpdate::SetMaxMinPriceMultiple { multiple: NEW_MAX_MIN_PRICE_MULTIPLE },
+				PalletConfigUpdate::SetPoolCircuitBreaker {
+					circuit_breaker: NEW_POOL_CIRCUIT_BREAKER
+				},
+				PalletConfigUpdate::SetMaxSwapsScheduledPerBlock {
+					max_swaps: NEW_MAX_SWAPS_SCHEDULED_PER_BLOCK
+				},
+				PalletConfigUpdate::SetBoostNetworkFeeRoute {
+					route: BoostNetworkFeeRoute::Internal
+				},
 			]
 			.try_into()
 			.unwrap()
@@ -61,6 +118,18 @@ fn can_update_all_config_items() {
 		assert_eq!(MaxSwapRequestDurationBlocks::<Test>::get(), MAX_SWAP_REQUEST_DURATION);
 		assert_eq!(MinimumChunkSize::<Test>::get(Asset::Usdc), NEW_MINIMUM_CHUNK_SIZE);
 		assert_eq!(MinimumNetworkFeePerChunk::<Test>::get(), NEW_MINIMUM_NETWORK_FEE);
+		assert_eq!(CcmLimitsPerChain::<Test>::get(ForeignChain::Ethereum), Some(NEW_CCM_LIMITS));
+		assert_eq!(ExecutionPolicy::<Test>::get(), NEW_SWAP_EXECUTION_POLICY);
+		assert_eq!(SmallSwapThreshold::<Test>::get(Asset::Flip), NEW_SMALL_SWAP_THRESHOLD);
+		assert_eq!(
+			DefaultRefundParameters::<Test>::get(ForeignChain::Ethereum),
+			Some(NEW_REFUND_DEFAULTS)
+		);
+		assert!(RefundExcessSwapAmount::<Test>::get());
+		assert_eq!(MaxMinPriceMultiple::<Test>::get(), NEW_MAX_MIN_PRICE_MULTIPLE);
+		assert_eq!(PoolCircuitBreakerConfig::<Test>::get(), NEW_POOL_CIRCUIT_BREAKER);
+		assert_eq!(MaxSwapsScheduledPerBlock::<Test>::get(), NEW_MAX_SWAPS_SCHEDULED_PER_BLOCK);
+		assert_eq!(BoostNetworkFeeSwapRoute::<Test>::get(), BoostNetworkFeeRoute::Internal);
 
 		// Check that the events were emitted
 		assert_events_eq!(
@@ -90,6 +159,34 @@ fn can_update_all_config_items() {
 			RuntimeEvent::Swapping(Event::MinimumNetworkFeeSet {
 				min_fee: NEW_MINIMUM_NETWORK_FEE
 			}),
+			RuntimeEvent::Swapping(Event::CcmLimitsSet {
+				chain: ForeignChain::Ethereum,
+				limits: Some(NEW_CCM_LIMITS)
+			}),
+			RuntimeEvent::Swapping(Event::SwapExecutionPolicySet {
+				policy: NEW_SWAP_EXECUTION_POLICY
+			}),
+			RuntimeEvent::Swapping(Event::SmallSwapThresholdSet {
+				asset: Asset::Flip,
+				threshold: NEW_SMALL_SWAP_THRESHOLD
+			}),
+			RuntimeEvent::Swapping(Event::DefaultRefundParametersSet {
+				chain: ForeignChain::Ethereum,
+				defaults: Some(NEW_REFUND_DEFAULTS)
+			}),
+			RuntimeEvent::Swapping(Event::RefundExcessSwapAmountSet { enabled: true }),
+			RuntimeEvent::Swapping(Event::MaxMinPriceMultipleSet {
+				multiple: NEW_MAX_MIN_PRICE_MULTIPLE
+			}),
+			RuntimeEvent::Swapping(Event::PoolCircuitBreakerSet {
+				circuit_breaker: NEW_POOL_CIRCUIT_BREAKER
+			}),
+			RuntimeEvent::Swapping(Event::MaxSwapsScheduledPerBlockSet {
+				max_swaps: NEW_MAX_SWAPS_SCHEDULED_PER_BLOCK
+			}),
+			RuntimeEvent::Swapping(Event::BoostNetworkFeeRouteSet {
+				route: BoostNetworkFeeRoute::Internal
+			}),
 		);
 
 		// Make sure that only governance can update the config
@@ -119,6 +216,7 @@ fn max_swap_amount_can_be_removed() {
 				SwapRequestType::Regular {
 					output_address: ForeignChainAddress::Eth([1; 20].into()),
 					ccm_deposit_metadata: None,
+					egress_memo: None,
 				},
 				Default::default(),
 				None,
@@ -126,7 +224,9 @@ fn max_swap_amount_can_be_removed() {
 				SwapOrigin::Vault {
 					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 					broker_id: Some(BROKER),
+					verified_sender: None,
 				},
+				None,
 			);
 		};
 
@@ -220,6 +320,7 @@ fn can_swap_below_max_amount() {
 			SwapRequestType::Regular {
 				output_address: ForeignChainAddress::Eth([1; 20].into()),
 				ccm_deposit_metadata: None,
+				egress_memo: None,
 			},
 			Default::default(),
 			None,
@@ -227,7 +328,9 @@ fn can_swap_below_max_amount() {
 			SwapOrigin::Vault {
 				tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 				broker_id: Some(BROKER),
+				verified_sender: None,
 			},
+			None,
 		);
 
 		assert_eq!(CollectedRejectedFunds::<Test>::get(from), 0u128);
@@ -271,7 +374,7 @@ fn swap_broker_fee_cannot_exceed_amount() {
 fn broker_bps_is_limited() {
 	new_test_ext().execute_with(|| {
 		assert_noop!(
-			Swapping::request_swap_deposit_address_with_affiliates(
+			Swapping::request_swap_deposit_address_with_parameters(
 				RuntimeOrigin::signed(BROKER),
 				Asset::Eth,
 				Asset::Usdc,
@@ -282,6 +385,9 @@ fn broker_bps_is_limited() {
 				Default::default(),
 				None,
 				None,
+				None,
+				false,
+				None,
 			),
 			Error::<Test>::BrokerCommissionBpsTooHigh
 		);
@@ -305,6 +411,13 @@ fn cannot_swap_in_safe_mode() {
 
 		let retry_at_block = swaps_scheduled_at + SwapRetryDelay::<Test>::get();
 		assert_eq!(SwapQueue::<Test>::decode_len(retry_at_block), Some(4));
+		assert_has_matching_event!(
+			Test,
+			RuntimeEvent::Swapping(Event::SwapDelayed {
+				reason: SwapDelayReason::SwapsDisabled,
+				..
+			}),
+		);
 
 		<MockRuntimeSafeMode as SetSafeMode<MockRuntimeSafeMode>>::set_code_green();
 
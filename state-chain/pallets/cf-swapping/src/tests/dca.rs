@@ -55,6 +55,8 @@ fn setup_dca_swap(
 			remaining_chunks: number_of_chunks - 1,
 			chunk_interval,
 			accumulated_output_amount: 0,
+			completed_chunks: 0,
+			total_input_amount: INPUT_AMOUNT,
 		}
 	);
 }
@@ -87,6 +89,15 @@ fn assert_chunk_1_executed(number_of_chunks: u32) {
 		}) if *execute_at == System::block_number() + CHUNK_INTERVAL as u64 && *input_amount == chunk_amount
 	);
 
+	System::assert_has_event(RuntimeEvent::Swapping(Event::DcaChunkCompleted {
+		swap_request_id: SWAP_REQUEST_ID,
+		swap_id: SwapId(1),
+		chunk_index: 0,
+		chunks_remaining: number_of_chunks - 1,
+		accumulated_output_amount: chunk_amount_after_fee * DEFAULT_SWAP_RATE,
+		remaining_input_amount: INPUT_AMOUNT - chunk_amount,
+	}));
+
 	assert_eq!(
 		get_dca_state(SWAP_REQUEST_ID),
 		DcaState {
@@ -94,7 +105,9 @@ fn assert_chunk_1_executed(number_of_chunks: u32) {
 			remaining_input_amount: INPUT_AMOUNT - (chunk_amount * 2),
 			remaining_chunks: number_of_chunks - 2,
 			chunk_interval: CHUNK_INTERVAL,
-			accumulated_output_amount: chunk_amount_after_fee * DEFAULT_SWAP_RATE
+			accumulated_output_amount: chunk_amount_after_fee * DEFAULT_SWAP_RATE,
+			completed_chunks: 1,
+			total_input_amount: INPUT_AMOUNT,
 		}
 	);
 }
@@ -142,10 +155,19 @@ fn dca_happy_path(is_ccm: bool) {
 		.then_process_blocks_until_block(CHUNK_1_BLOCK)
 		.then_execute_with(|_| {
 			assert_chunk_1_executed(NUMBER_OF_CHUNKS);
+			assert_eq!(
+				SwapRequestExecutions::<Test>::get(SWAP_REQUEST_ID),
+				Some(SwapRequestExecution {
+					input_amount: CHUNK_AMOUNT_AFTER_FEE,
+					output_amount: CHUNK_OUTPUT,
+					average_price: U256::from(DEFAULT_SWAP_RATE) << PRICE_FRACTIONAL_BITS,
+				})
+			);
 		})
 		.then_process_blocks_until_block(CHUNK_2_BLOCK)
 		.then_execute_with(|_| {
 			assert_eq!(SwapRequests::<Test>::get(SWAP_REQUEST_ID), None);
+			assert_eq!(SwapRequestExecutions::<Test>::get(SWAP_REQUEST_ID), None);
 
 			assert_event_sequence!(
 				Test,
@@ -157,15 +179,32 @@ fn dca_happy_path(is_ccm: bool) {
 					broker_fee: CHUNK_BROKER_FEE,
 					..
 				}),
+				RuntimeEvent::Swapping(Event::DcaChunkCompleted {
+					swap_request_id: SWAP_REQUEST_ID,
+					swap_id: SwapId(2),
+					chunk_index: 1,
+					chunks_remaining: 0,
+					accumulated_output_amount: TOTAL_OUTPUT_AMOUNT,
+					remaining_input_amount: 0,
+				}),
 				RuntimeEvent::Swapping(Event::SwapEgressScheduled {
 					swap_request_id: SWAP_REQUEST_ID,
 					amount: TOTAL_OUTPUT_AMOUNT,
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SWAP_REQUEST_ID
+					swap_request_id: SWAP_REQUEST_ID,
+					..
 				}),
 			);
+			System::assert_has_event(RuntimeEvent::Swapping(Event::SwapRequestCompleted {
+				swap_request_id: SWAP_REQUEST_ID,
+				execution: Some(SwapRequestExecution {
+					input_amount: CHUNK_AMOUNT_AFTER_FEE * 2,
+					output_amount: TOTAL_OUTPUT_AMOUNT,
+					average_price: U256::from(DEFAULT_SWAP_RATE) << PRICE_FRACTIONAL_BITS,
+				}),
+			}));
 		});
 }
 
@@ -211,7 +250,8 @@ fn dca_single_chunk(is_ccm: bool) {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SWAP_REQUEST_ID
+					swap_request_id: SWAP_REQUEST_ID,
+					..
 				})
 			);
 		});
@@ -267,7 +307,9 @@ fn dca_with_fok_full_refund(is_ccm: bool) {
 					remaining_input_amount: CHUNK_AMOUNT,
 					remaining_chunks: 1,
 					chunk_interval: CHUNK_INTERVAL,
-					accumulated_output_amount: 0
+					accumulated_output_amount: 0,
+					completed_chunks: 0,
+					total_input_amount: INPUT_AMOUNT,
 				}
 			);
 		})
@@ -286,7 +328,8 @@ fn dca_with_fok_full_refund(is_ccm: bool) {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SWAP_REQUEST_ID
+					swap_request_id: SWAP_REQUEST_ID,
+					..
 				}),
 			);
 		});
@@ -355,7 +398,9 @@ fn dca_with_fok_partial_refund(is_ccm: bool) {
 					remaining_input_amount: REFUNDED_AMOUNT - CHUNK_AMOUNT,
 					remaining_chunks: 2,
 					chunk_interval: CHUNK_INTERVAL,
-					accumulated_output_amount: CHUNK_OUTPUT
+					accumulated_output_amount: CHUNK_OUTPUT,
+					completed_chunks: 1,
+					total_input_amount: INPUT_AMOUNT,
 				}
 			);
 		})
@@ -381,9 +426,61 @@ fn dca_with_fok_partial_refund(is_ccm: bool) {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SWAP_REQUEST_ID
+					swap_request_id: SWAP_REQUEST_ID,
+					..
+				}),
+			);
+		});
+}
+
+#[track_caller]
+fn assert_in_flight_swap_amounts(expected: &[(Asset, AssetAmount)]) {
+	let in_flight_amounts = InFlightSwapAmounts::<Test>::iter().collect::<BTreeMap<_, _>>();
+	assert_eq!(in_flight_amounts, Swapping::in_flight_swap_amounts_of_requests());
+	assert_eq!(in_flight_amounts, expected.iter().copied().collect::<BTreeMap<_, _>>());
+}
+
+#[test]
+fn in_flight_swap_amounts_track_dca_swaps() {
+	const CHUNK_1_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+	const CHUNK_2_BLOCK: u64 = CHUNK_1_BLOCK + CHUNK_INTERVAL as u64;
+	const CHUNK_2_RESCHEDULED_AT_BLOCK: u64 =
+		CHUNK_2_BLOCK + (DEFAULT_SWAP_RETRY_DELAY_BLOCKS as u64);
+
+	const NUMBER_OF_CHUNKS: u32 = 4;
+	const CHUNK_AMOUNT: AssetAmount = INPUT_AMOUNT / NUMBER_OF_CHUNKS as u128;
+	const CHUNK_BROKER_FEE: AssetAmount = CHUNK_AMOUNT * BROKER_FEE_BPS as u128 / 10_000;
+	const CHUNK_OUTPUT: AssetAmount = (CHUNK_AMOUNT - CHUNK_BROKER_FEE) * DEFAULT_SWAP_RATE;
+
+	new_test_ext()
+		.execute_with(|| {
+			setup_dca_swap(
+				NUMBER_OF_CHUNKS,
+				CHUNK_INTERVAL,
+				Some(TestRefundParams {
+					retry_duration: DEFAULT_SWAP_RETRY_DELAY_BLOCKS,
+					min_output: INPUT_AMOUNT,
 				}),
+				false,
 			);
+			assert_in_flight_swap_amounts(&[(INPUT_ASSET, INPUT_AMOUNT)]);
+		})
+		.then_process_blocks_until_block(CHUNK_1_BLOCK)
+		.then_execute_with(|_| {
+			// The broker fee leaves the swap request along with the swapped chunk.
+			assert_in_flight_swap_amounts(&[
+				(INPUT_ASSET, INPUT_AMOUNT - CHUNK_AMOUNT),
+				(OUTPUT_ASSET, CHUNK_OUTPUT),
+			]);
+		})
+		.then_execute_at_block(CHUNK_2_BLOCK, |_| {
+			// The second chunk fails, so that the rest of the request is refunded.
+			SwapRate::set(0.5);
+		})
+		.then_process_blocks_until_block(CHUNK_2_RESCHEDULED_AT_BLOCK)
+		.then_execute_with(|_| {
+			assert_eq!(SwapRequests::<Test>::get(SWAP_REQUEST_ID), None);
+			assert_in_flight_swap_amounts(&[]);
 		});
 }
 
@@ -444,7 +541,9 @@ fn dca_with_fok_fully_executed(is_ccm: bool) {
 					remaining_input_amount: CHUNK_AMOUNT,
 					remaining_chunks: 1,
 					chunk_interval: CHUNK_INTERVAL,
-					accumulated_output_amount: 0
+					accumulated_output_amount: 0,
+					completed_chunks: 0,
+					total_input_amount: INPUT_AMOUNT,
 				}
 			);
 		})
@@ -463,6 +562,14 @@ fn dca_with_fok_fully_executed(is_ccm: bool) {
 					broker_fee: CHUNK_BROKER_FEE,
 					..
 				}),
+				RuntimeEvent::Swapping(Event::DcaChunkCompleted {
+					swap_request_id: SWAP_REQUEST_ID,
+					swap_id: SwapId(1),
+					chunk_index: 0,
+					chunks_remaining: 1,
+					accumulated_output_amount: CHUNK_OUTPUT,
+					remaining_input_amount: CHUNK_AMOUNT,
+				}),
 				// Second chunk should be scheduled 2 blocks after the first is executed:
 				RuntimeEvent::Swapping(Event::SwapScheduled {
 					swap_request_id: SWAP_REQUEST_ID,
@@ -480,7 +587,9 @@ fn dca_with_fok_fully_executed(is_ccm: bool) {
 					remaining_input_amount: 0,
 					remaining_chunks: 0,
 					chunk_interval: CHUNK_INTERVAL,
-					accumulated_output_amount: CHUNK_OUTPUT
+					accumulated_output_amount: CHUNK_OUTPUT,
+					completed_chunks: 1,
+					total_input_amount: INPUT_AMOUNT,
 				}
 			);
 		})
@@ -497,6 +606,14 @@ fn dca_with_fok_fully_executed(is_ccm: bool) {
 					output_amount: CHUNK_OUTPUT,
 					..
 				}),
+				RuntimeEvent::Swapping(Event::DcaChunkCompleted {
+					swap_request_id: SWAP_REQUEST_ID,
+					swap_id: SwapId(2),
+					chunk_index: 1,
+					chunks_remaining: 0,
+					accumulated_output_amount: TOTAL_OUTPUT,
+					remaining_input_amount: 0,
+				}),
 				RuntimeEvent::Swapping(Event::SwapEgressScheduled {
 					swap_request_id: SWAP_REQUEST_ID,
 					asset: OUTPUT_ASSET,
@@ -505,7 +622,8 @@ fn dca_with_fok_fully_executed(is_ccm: bool) {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SWAP_REQUEST_ID
+					swap_request_id: SWAP_REQUEST_ID,
+					..
 				}),
 			);
 		});
@@ -587,6 +705,8 @@ fn can_handle_dca_chunk_size_of_zero(is_ccm: bool) {
 					remaining_chunks: NUMBER_OF_CHUNKS - 1,
 					chunk_interval: CHUNK_INTERVAL,
 					accumulated_output_amount: 0,
+					completed_chunks: 0,
+					total_input_amount: INPUT_AMOUNT,
 				}
 			);
 		})
@@ -624,6 +744,8 @@ fn can_handle_dca_chunk_size_of_zero(is_ccm: bool) {
 					chunk_interval: CHUNK_INTERVAL,
 					// Should still be 0
 					accumulated_output_amount: 0,
+					completed_chunks: 1,
+					total_input_amount: INPUT_AMOUNT,
 				}
 			);
 		})
@@ -646,7 +768,8 @@ fn can_handle_dca_chunk_size_of_zero(is_ccm: bool) {
 			assert_has_matching_event!(
 				Test,
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SWAP_REQUEST_ID
+					swap_request_id: SWAP_REQUEST_ID,
+					..
 				}),
 			);
 		});
@@ -681,6 +804,7 @@ fn test_minimum_chunk_size() {
 			SwapRequestType::Regular {
 				output_address: ForeignChainAddress::Eth([1; 20].into()),
 				ccm_deposit_metadata: None,
+				egress_memo: None,
 			},
 			vec![].try_into().unwrap(),
 			None,
@@ -688,7 +812,9 @@ fn test_minimum_chunk_size() {
 			SwapOrigin::Vault {
 				tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 				broker_id: Some(BROKER),
+				verified_sender: None,
 			},
+			None,
 		);
 
 		// Check that the swap was initiated with the updated number of chunks
@@ -766,3 +892,107 @@ fn test_dca_parameter_validation() {
 		);
 	});
 }
+
+#[test]
+fn dca_swap_request_can_be_topped_up() {
+	const CHUNK_1_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+	const NUMBER_OF_CHUNKS: u32 = 2;
+	const CHUNK_AMOUNT: AssetAmount = INPUT_AMOUNT / 2;
+	const TOP_UP_AMOUNT: AssetAmount = 100;
+
+	new_test_ext()
+		.execute_with(|| {
+			setup_dca_swap(NUMBER_OF_CHUNKS, CHUNK_INTERVAL, None, false);
+
+			assert_ok!(<Swapping as cf_traits::SwapRequestHandler>::top_up_swap_request(
+				SWAP_REQUEST_ID,
+				TOP_UP_AMOUNT
+			));
+			System::assert_last_event(RuntimeEvent::Swapping(Event::SwapRequestToppedUp {
+				swap_request_id: SWAP_REQUEST_ID,
+				additional_input_amount: TOP_UP_AMOUNT,
+			}));
+			assert_eq!(
+				get_dca_state(SWAP_REQUEST_ID).remaining_input_amount,
+				INPUT_AMOUNT - CHUNK_AMOUNT + TOP_UP_AMOUNT
+			);
+		})
+		.then_process_blocks_until_block(CHUNK_1_BLOCK)
+		.then_execute_with(|_| {
+			// The top-up is swapped as part of the remaining chunk.
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapScheduled {
+					swap_request_id: SWAP_REQUEST_ID,
+					swap_id: SwapId(2),
+					input_amount,
+					..
+				}) if *input_amount == CHUNK_AMOUNT + TOP_UP_AMOUNT
+			);
+
+			// Once all chunks are scheduled, the request can no longer be topped up.
+			assert_err!(
+				<Swapping as cf_traits::SwapRequestHandler>::top_up_swap_request(
+					SWAP_REQUEST_ID,
+					TOP_UP_AMOUNT
+				),
+				()
+			);
+		});
+}
+
+#[test]
+fn top_ups_are_limited_by_the_maximum_swap_amount() {
+	const NUMBER_OF_CHUNKS: u32 = 2;
+	const TOP_UP_AMOUNT: AssetAmount = 100;
+	const MAX_SWAP: AssetAmount = INPUT_AMOUNT + TOP_UP_AMOUNT / 2;
+
+	new_test_ext().execute_with(|| {
+		setup_dca_swap(NUMBER_OF_CHUNKS, CHUNK_INTERVAL, None, false);
+		set_maximum_swap_amount(INPUT_ASSET, Some(MAX_SWAP));
+
+		// Only the part of the top-up that keeps the request within the maximum is accepted.
+		// Without a refund address, the rest is confiscated.
+		assert_ok!(<Swapping as cf_traits::SwapRequestHandler>::top_up_swap_request(
+			SWAP_REQUEST_ID,
+			TOP_UP_AMOUNT
+		));
+		System::assert_has_event(RuntimeEvent::Swapping(Event::SwapAmountConfiscated {
+			swap_request_id: SWAP_REQUEST_ID,
+			asset: INPUT_ASSET,
+			total_amount: TOP_UP_AMOUNT,
+			confiscated_amount: TOP_UP_AMOUNT / 2,
+		}));
+		System::assert_last_event(RuntimeEvent::Swapping(Event::SwapRequestToppedUp {
+			swap_request_id: SWAP_REQUEST_ID,
+			additional_input_amount: TOP_UP_AMOUNT / 2,
+		}));
+		assert_eq!(get_dca_state(SWAP_REQUEST_ID).total_input_amount, MAX_SWAP);
+
+		// Once the request is at the maximum, any further top-up is confiscated in full.
+		assert_ok!(<Swapping as cf_traits::SwapRequestHandler>::top_up_swap_request(
+			SWAP_REQUEST_ID,
+			TOP_UP_AMOUNT
+		));
+		System::assert_last_event(RuntimeEvent::Swapping(Event::SwapAmountConfiscated {
+			swap_request_id: SWAP_REQUEST_ID,
+			asset: INPUT_ASSET,
+			total_amount: TOP_UP_AMOUNT,
+			confiscated_amount: TOP_UP_AMOUNT,
+		}));
+		assert_eq!(get_dca_state(SWAP_REQUEST_ID).total_input_amount, MAX_SWAP);
+		assert_eq!(CollectedRejectedFunds::<Test>::get(INPUT_ASSET), TOP_UP_AMOUNT * 3 / 2);
+	});
+}
+
+#[test]
+fn ccm_swap_requests_cannot_be_topped_up() {
+	new_test_ext().execute_with(|| {
+		setup_dca_swap(2, CHUNK_INTERVAL, None, true);
+
+		assert_err!(
+			<Swapping as cf_traits::SwapRequestHandler>::top_up_swap_request(SWAP_REQUEST_ID, 100),
+			()
+		);
+	});
+}
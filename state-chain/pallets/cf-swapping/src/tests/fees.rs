@@ -36,6 +36,7 @@ fn swap_output_amounts_correctly_account_for_fees() {
 					SwapRequestType::Regular {
 						output_address: ForeignChainAddress::Eth(H160::zero()),
 						ccm_deposit_metadata: None,
+						egress_memo: None,
 					},
 					Default::default(),
 					None,
@@ -43,7 +44,9 @@ fn swap_output_amounts_correctly_account_for_fees() {
 					SwapOrigin::Vault {
 						tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 						broker_id: Some(BROKER),
+						verified_sender: None,
 					},
+					None,
 				);
 
 				Swapping::on_finalize(System::block_number() + SWAP_DELAY_BLOCKS as u64);
@@ -55,6 +58,7 @@ fn swap_output_amounts_correctly_account_for_fees() {
 						amount: expected_output,
 						fee: 0,
 						destination_address: ForeignChainAddress::Eth(H160::zero()),
+						memo: None,
 					},]
 				);
 			}
@@ -107,6 +111,75 @@ fn test_buy_back_flip() {
 	});
 }
 
+#[test]
+fn network_fees_taken_are_tracked_by_source() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: AssetAmount = 1000;
+		const BOOST_NETWORK_FEE: AssetAmount = 50;
+
+		NetworkFee::set(Permill::from_percent(2));
+		FlipBuyInterval::<Test>::set(1);
+
+		let regular_fee = Swapping::take_network_fee(AMOUNT, true).fee;
+		let internal_fee = Swapping::take_network_fee(AMOUNT * 2, false).fee;
+
+		assert_eq!(
+			NetworkFeesTaken::<Test>::get(),
+			NetworkFeeTotals { regular_swaps: regular_fee, internal_swaps: internal_fee }
+		);
+
+		// Network fees charged outside of swaps are handed over as network fee swap requests.
+		Swapping::init_swap_request(
+			Asset::Btc,
+			BOOST_NETWORK_FEE,
+			Asset::Flip,
+			SwapRequestType::BoostNetworkFee,
+			Default::default(),
+			None,
+			None,
+			SwapOrigin::Internal,
+			None,
+		);
+
+		// Converting the collected network fees into FLIP is not counted again.
+		Swapping::on_initialize(System::block_number());
+		assert_eq!(CollectedNetworkFee::<Test>::get(), 0);
+
+		assert_eq!(
+			BoostNetworkFeesTaken::<Test>::iter().collect::<Vec<_>>(),
+			vec![(Asset::Btc, BOOST_NETWORK_FEE)]
+		);
+		assert_eq!(
+			NetworkFeesTaken::<Test>::get(),
+			NetworkFeeTotals { regular_swaps: regular_fee, internal_swaps: internal_fee }
+		);
+	});
+}
+
+#[test]
+fn effective_network_fee_matches_fee_taken() {
+	new_test_ext().execute_with(|| {
+		const AMOUNT: AssetAmount = 1000;
+		const MIN_FEE: AssetAmount = 50;
+
+		NetworkFee::set(Permill::from_percent(2));
+		MinimumNetworkFeePerChunk::<Test>::set(MIN_FEE);
+
+		assert_eq!(
+			Swapping::effective_network_fee(false),
+			FeeRateAndMinimum { rate: Permill::from_percent(2), minimum: MIN_FEE }
+		);
+		assert_eq!(
+			Swapping::effective_network_fee(true),
+			FeeRateAndMinimum { rate: Permill::from_percent(2), minimum: 0 }
+		);
+
+		// The minimum fee only applies to user swaps.
+		assert_eq!(Swapping::take_network_fee(AMOUNT, true).fee, MIN_FEE);
+		assert_eq!(Swapping::take_network_fee(AMOUNT, false).fee, 20);
+	});
+}
+
 #[test]
 fn test_network_fee_calculation() {
 	const MIN_FEE: u128 = 0;
@@ -220,6 +293,7 @@ fn network_fee_swap_gets_burnt() {
 				None,
 				None,
 				SwapOrigin::Internal,
+				None,
 			);
 
 			assert_eq!(FlipToBurn::<Test>::get(), 0);
@@ -250,6 +324,54 @@ fn network_fee_swap_gets_burnt() {
 		});
 }
 
+#[test]
+fn boost_network_fee_route_determines_network_fee_charged() {
+	const SWAP_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+	const AMOUNT: AssetAmount = 1000;
+
+	for (route, expected_flip) in [
+		(BoostNetworkFeeRoute::Standard, AMOUNT * DEFAULT_SWAP_RATE * DEFAULT_SWAP_RATE),
+		(BoostNetworkFeeRoute::Internal, AMOUNT * DEFAULT_SWAP_RATE * 99 / 100 * DEFAULT_SWAP_RATE),
+	] {
+		new_test_ext()
+			.execute_with(|| {
+				NetworkFee::set(Permill::from_percent(1));
+				BoostNetworkFeeSwapRoute::<Test>::set(route);
+
+				Swapping::init_swap_request(
+					Asset::Btc,
+					AMOUNT,
+					Asset::Flip,
+					SwapRequestType::BoostNetworkFee,
+					Default::default(),
+					None,
+					None,
+					SwapOrigin::Internal,
+					None,
+				);
+
+				assert_has_matching_event!(
+					Test,
+					RuntimeEvent::Swapping(Event::SwapRequested {
+						request_type: SwapRequestTypeEncoded::BoostNetworkFee,
+						..
+					}),
+				);
+				assert_has_matching_event!(
+					Test,
+					RuntimeEvent::Swapping(Event::SwapScheduled {
+						swap_type: SwapType::BoostNetworkFee,
+						..
+					}),
+				);
+			})
+			.then_process_blocks_until_block(SWAP_BLOCK)
+			.then_execute_with(|_| {
+				assert_eq!(FlipToBurn::<Test>::get(), expected_flip);
+			});
+	}
+}
+
 #[test]
 fn transaction_fees_are_collected() {
 	const SWAP_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
@@ -269,6 +391,7 @@ fn transaction_fees_are_collected() {
 				None,
 				None,
 				SwapOrigin::Internal,
+				None,
 			);
 
 			assert_has_matching_event!(
@@ -346,6 +469,59 @@ fn swap_broker_fee_calculated_correctly() {
 			assert_eq!(get_broker_balance::<Test>(&ALICE, Asset::Usdc), total_fees);
 		});
 }
+#[test]
+fn broker_fee_estimate_matches_fees_taken() {
+	const INPUT_AMOUNT: AssetAmount = 100_000;
+	const NETWORK_FEE: Permill = Permill::from_percent(1);
+
+	new_test_ext()
+		.execute_with(|| {
+			NetworkFee::set(NETWORK_FEE);
+			let broker_fees: Beneficiaries<u64> = bounded_vec![
+				Beneficiary { account: BROKER, bps: 100 },
+				Beneficiary { account: ALICE, bps: 50 }
+			];
+
+			let estimate =
+				Swapping::estimate_broker_fees(Asset::Flip, Asset::Eth, INPUT_AMOUNT, &broker_fees)
+					.unwrap();
+
+			let stable_amount = INPUT_AMOUNT * DEFAULT_SWAP_RATE;
+			let network_fee = NETWORK_FEE * stable_amount;
+			let amount_after_network_fee = stable_amount - network_fee;
+			assert_eq!(
+				estimate,
+				BrokerFeeEstimate {
+					stable_amount,
+					network_fee,
+					broker_fees: vec![
+						(BROKER, Permill::from_percent(1) * amount_after_network_fee),
+						(ALICE, Permill::from_parts(5_000) * amount_after_network_fee),
+					],
+				}
+			);
+			assert_eq!(
+				Swapping::estimate_broker_fees(
+					Asset::Flip,
+					Asset::Flip,
+					INPUT_AMOUNT,
+					&broker_fees
+				),
+				Err(Error::<Test>::SameInputAndOutputAsset.into())
+			);
+
+			swap_with_custom_broker_fee(Asset::Flip, Asset::Eth, INPUT_AMOUNT, broker_fees);
+
+			estimate
+		})
+		.then_process_blocks_until_block(INIT_BLOCK + SWAP_DELAY_BLOCKS as u64)
+		.then_execute_with(|estimate| {
+			for (account, fee) in estimate.broker_fees {
+				assert_eq!(get_broker_balance::<Test>(&account, Asset::Usdc), fee);
+			}
+		});
+}
+
 #[test]
 fn input_amount_excludes_network_fee() {
 	const AMOUNT: AssetAmount = 1_000;
@@ -367,6 +543,7 @@ fn input_amount_excludes_network_fee() {
 				SwapRequestType::Regular {
 					output_address: output_address.clone(),
 					ccm_deposit_metadata: None,
+					egress_memo: None,
 				},
 				bounded_vec![],
 				None,
@@ -374,7 +551,9 @@ fn input_amount_excludes_network_fee() {
 				SwapOrigin::Vault {
 					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 					broker_id: Some(BROKER),
+					verified_sender: None,
 				},
+				None,
 			);
 		})
 		.then_process_blocks_until(|_| System::block_number() == 3)
@@ -388,10 +567,12 @@ fn input_amount_excludes_network_fee() {
 				input_asset: FROM_ASSET,
 				output_asset: TO_ASSET,
 				network_fee,
+				network_fee_minimum_applied: false,
 				broker_fee: 0,
 				input_amount: expected_input_amount,
 				output_amount: expected_input_amount * DEFAULT_SWAP_RATE,
 				intermediate_amount: None,
+				tracking_id: None,
 			}));
 		});
 }
@@ -418,7 +599,7 @@ fn withdraw_broker_fees() {
 		assert!(egresses.len() == 1);
 		assert_eq!(egresses.pop().expect("must exist").amount(), 200);
 		System::assert_last_event(RuntimeEvent::Swapping(Event::<Test>::WithdrawalRequested {
-			egress_id: (ForeignChain::Ethereum, 1),
+			egress_id: (ForeignChain::Ethereum, 0, 1),
 			egress_asset: Asset::Eth,
 			egress_amount: 200,
 			destination_address: EncodedAddress::Eth(Default::default()),
@@ -472,12 +653,14 @@ fn expect_earned_fees_to_be_recorded() {
 				swap_request_id: 1.into(),
 				swap_id: 1.into(),
 				network_fee: NETWORK_FEE_1,
+				network_fee_minimum_applied: false,
 				broker_fee: ALICE_FEE_1,
 				input_amount: INPUT_AMOUNT,
 				input_asset: Asset::Flip,
 				output_asset: Asset::Usdc,
 				output_amount: INTERMEDIATE_AMOUNT - NETWORK_FEE_1 - ALICE_FEE_1,
 				intermediate_amount: None,
+				tracking_id: None,
 			}));
 
 			assert_eq!(get_broker_balance::<Test>(&ALICE, Asset::Usdc), ALICE_FEE_1);
@@ -497,12 +680,14 @@ fn expect_earned_fees_to_be_recorded() {
 				swap_request_id: 2.into(),
 				swap_id: 2.into(),
 				network_fee: NETWORK_FEE_2,
+				network_fee_minimum_applied: false,
 				broker_fee: ALICE_FEE_2,
 				input_amount: AMOUNT_AFTER_FEES,
 				input_asset: Asset::Usdc,
 				output_asset: Asset::Flip,
 				output_amount: AMOUNT_AFTER_FEES * DEFAULT_SWAP_RATE,
 				intermediate_amount: None,
+				tracking_id: None,
 			}));
 
 			assert_eq!(get_broker_balance::<Test>(&ALICE, Asset::Usdc), ALICE_FEE_1 + ALICE_FEE_2);
@@ -528,12 +713,14 @@ fn expect_earned_fees_to_be_recorded() {
 				swap_request_id: 3.into(),
 				swap_id: 3.into(),
 				network_fee: NETWORK_FEE_3,
+				network_fee_minimum_applied: false,
 				broker_fee: TOTAL_BROKER_FEES,
 				input_amount: INPUT_AMOUNT,
 				input_asset: Asset::ArbEth,
 				output_asset: Asset::Flip,
 				output_amount: INTERMEDIATE_AMOUNT_AFTER_FEES * DEFAULT_SWAP_RATE,
 				intermediate_amount: Some(INTERMEDIATE_AMOUNT_AFTER_FEES),
+				tracking_id: None,
 			}));
 
 			assert_eq!(
@@ -573,6 +760,7 @@ fn min_network_fee_is_enforced_in_regular_swaps() {
 				SwapRequestType::Regular {
 					output_address: ForeignChainAddress::Eth(H160::zero()),
 					ccm_deposit_metadata: None,
+					egress_memo: None,
 				},
 				Default::default(),
 				None,
@@ -580,7 +768,9 @@ fn min_network_fee_is_enforced_in_regular_swaps() {
 				SwapOrigin::Vault {
 					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 					broker_id: Some(BROKER),
+					verified_sender: None,
 				},
+				None,
 			);
 
 			assert_eq!(CollectedNetworkFee::<Test>::get(), 0);
@@ -588,6 +778,14 @@ fn min_network_fee_is_enforced_in_regular_swaps() {
 		.then_process_blocks_until_block(SWAP_BLOCK)
 		.then_execute_with(|_| {
 			assert_eq!(CollectedNetworkFee::<Test>::get(), MIN_NETWORK_FEE);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted {
+					network_fee_minimum_applied: true,
+					..
+				})
+			);
+			System::reset_events();
 
 			// Check that min fee does NOT apply to fee swaps
 			Swapping::init_swap_request(
@@ -601,7 +799,9 @@ fn min_network_fee_is_enforced_in_regular_swaps() {
 				SwapOrigin::Vault {
 					tx_id: TransactionInIdForAnyChain::Evm(H256::default()),
 					broker_id: Some(BROKER),
+					verified_sender: None,
 				},
+				None,
 			);
 		})
 		.then_process_blocks_until_block(FEE_SWAP_BLOCK)
@@ -610,5 +810,12 @@ fn min_network_fee_is_enforced_in_regular_swaps() {
 				CollectedNetworkFee::<Test>::get(),
 				MIN_NETWORK_FEE + NETWORK_FEE * INPUT_AMOUNT
 			);
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::SwapExecuted {
+					network_fee_minimum_applied: false,
+					..
+				})
+			);
 		});
 }
@@ -1,4 +1,5 @@
 use frame_support::assert_err;
+use sp_std::collections::btree_set::BTreeSet;
 
 use super::*;
 
@@ -74,7 +75,8 @@ fn both_fok_and_regular_swaps_succeed_first_try(is_ccm: bool) {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: REGULAR_REQUEST_ID
+					swap_request_id: REGULAR_REQUEST_ID,
+					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: FOK_SWAP_ID, .. }),
 				RuntimeEvent::Swapping(Event::SwapEgressScheduled {
@@ -82,7 +84,8 @@ fn both_fok_and_regular_swaps_succeed_first_try(is_ccm: bool) {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: FOK_REQUEST_ID
+					swap_request_id: FOK_REQUEST_ID,
+					..
 				}),
 			);
 		});
@@ -151,7 +154,8 @@ fn price_limit_is_respected_in_fok_swap(is_ccm: bool) {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(1)
+					swap_request_id: SwapRequestId(1),
+					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapExecuted { swap_id: FOK_SWAP_2_ID, .. }),
 				RuntimeEvent::Swapping(Event::SwapEgressScheduled {
@@ -159,7 +163,13 @@ fn price_limit_is_respected_in_fok_swap(is_ccm: bool) {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(3)
+					swap_request_id: SwapRequestId(3),
+					..
+				}),
+				RuntimeEvent::Swapping(Event::SwapDelayed {
+					swap_id: FOK_SWAP_1_ID,
+					reason: SwapDelayReason::MinPriceNotMet { output_asset: OUTPUT_ASSET },
+					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRescheduled {
 					swap_id: FOK_SWAP_1_ID,
@@ -182,7 +192,8 @@ fn price_limit_is_respected_in_fok_swap(is_ccm: bool) {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: SwapRequestId(2)
+					swap_request_id: SwapRequestId(2),
+					..
 				}),
 			);
 
@@ -242,7 +253,13 @@ fn fok_swap_gets_refunded_due_to_price_limit(is_ccm: bool) {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: OTHER_SWAP_REQUEST_ID
+					swap_request_id: OTHER_SWAP_REQUEST_ID,
+					..
+				}),
+				RuntimeEvent::Swapping(Event::SwapDelayed {
+					swap_request_id: FOK_SWAP_REQUEST_ID,
+					swap_id: FOK_SWAP_ID,
+					reason: SwapDelayReason::MinPriceNotMet { output_asset: OUTPUT_ASSET },
 				}),
 				RuntimeEvent::Swapping(Event::SwapRescheduled {
 					swap_id: FOK_SWAP_ID,
@@ -263,7 +280,8 @@ fn fok_swap_gets_refunded_due_to_price_limit(is_ccm: bool) {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: FOK_SWAP_REQUEST_ID
+					swap_request_id: FOK_SWAP_REQUEST_ID,
+					..
 				}),
 			);
 		});
@@ -408,7 +426,8 @@ fn fok_swap_gets_refunded_due_to_price_impact_protection(is_ccm: bool) {
 					..
 				}),
 				RuntimeEvent::Swapping(Event::SwapRequestCompleted {
-					swap_request_id: FOK_SWAP_REQUEST_ID
+					swap_request_id: FOK_SWAP_REQUEST_ID,
+					..
 				}),
 				// Non-fok swap will continue to be retried:
 				RuntimeEvent::Swapping(Event::SwapRescheduled { swap_id: REGULAR_SWAP_ID, .. }),
@@ -460,6 +479,81 @@ fn fok_test_zero_refund_duration(is_ccm: bool) {
 		});
 }
 
+#[test]
+fn refund_outcome_is_estimated_at_current_fees() {
+	const EGRESS_FEE: AssetAmount = 1_000;
+
+	new_test_ext().then_execute_at_block(INIT_BLOCK, |_| {
+		insert_swaps(&[fok_swap(
+			Some(TestRefundParams { retry_duration: 0, min_output: INPUT_AMOUNT }),
+			false,
+		)]);
+
+		let estimate =
+			Swapping::estimate_refund_outcome(SwapRequestId(1), |_| Some(EGRESS_FEE)).unwrap();
+		assert_eq!(estimate.gross_refund_amount, INPUT_AMOUNT);
+		assert_eq!(estimate.egress_fee, EGRESS_FEE);
+		assert_eq!(estimate.net_refund_amount, INPUT_AMOUNT - EGRESS_FEE);
+		assert_eq!(
+			estimate.destination,
+			RefundDestination::Address(ForeignChainAddress::Eth([10; 20].into()))
+		);
+		assert_eq!(estimate.swapped_output.1, 0);
+
+		// A refund that doesn't cover the egress fee is not sent.
+		assert_eq!(
+			Swapping::estimate_refund_outcome(SwapRequestId(1), |_| Some(INPUT_AMOUNT * 2))
+				.unwrap()
+				.net_refund_amount,
+			0
+		);
+		assert_err!(
+			Swapping::estimate_refund_outcome(SwapRequestId(1), |_| None),
+			Error::<Test>::NoMarketPrice
+		);
+		assert_err!(
+			Swapping::estimate_refund_outcome(SwapRequestId(2), |_| Some(EGRESS_FEE)),
+			Error::<Test>::UnknownSwapRequest
+		);
+	});
+}
+
+#[test]
+fn refunds_to_the_same_address_are_aggregated() {
+	const SWAPS_SCHEDULED_FOR_BLOCK: u64 = INIT_BLOCK + SWAP_DELAY_BLOCKS as u64;
+	const REFUND_PARAMS: TestRefundParams =
+		TestRefundParams { retry_duration: 0, min_output: INPUT_AMOUNT };
+
+	new_test_ext()
+		.then_execute_at_block(INIT_BLOCK, |_| {
+			// All swaps share the same refund address:
+			insert_swaps(&[
+				fok_swap(Some(REFUND_PARAMS), false),
+				fok_swap(Some(REFUND_PARAMS), false),
+				fok_swap(Some(REFUND_PARAMS), false),
+			]);
+		})
+		.then_execute_at_block(SWAPS_SCHEDULED_FOR_BLOCK, |_| {
+			MockSwappingApi::set_swaps_should_fail(true);
+		})
+		.then_execute_with(|_| {
+			assert_has_matching_event!(
+				Test,
+				RuntimeEvent::Swapping(Event::AggregatedRefundEgressScheduled {
+					swap_request_ids,
+					..
+				}) if swap_request_ids.iter().copied().collect::<BTreeSet<_>>() ==
+					BTreeSet::from([SwapRequestId(1), SwapRequestId(2), SwapRequestId(3)]),
+			);
+			for id in 1..=3 {
+				assert_eq!(SwapRequests::<Test>::get(SwapRequestId(id)), None);
+			}
+
+			// Only a single egress is scheduled for all refunds:
+			assert_eq!(MockEgressHandler::<AnyChain>::get_scheduled_egresses().len(), 1);
+		});
+}
+
 #[test]
 fn test_refund_parameter_validation() {
 	use cf_traits::SwapLimitsProvider;
@@ -34,12 +34,19 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn request_swap_deposit_address() -> Weight;
 	fn request_swap_deposit_address_with_affiliates() -> Weight;
+	fn request_swap_deposit_address_with_quote() -> Weight;
 	fn withdraw() -> Weight;
 	fn register_as_broker() -> Weight;
 	fn deregister_as_broker() -> Weight;
 	fn open_private_btc_channel() -> Weight;
 	fn close_private_btc_channel() -> Weight;
 	fn register_affiliate() -> Weight;
+	fn deregister_affiliate() -> Weight;
+	fn set_default_affiliates() -> Weight;
+	fn swap_on_chain() -> Weight;
+	fn suspend_broker() -> Weight;
+	fn sweep_collected_rejected_funds() -> Weight;
+	fn update_ccm_destination_allowlist() -> Weight;
 }
 
 /// Weights for pallet_cf_swapping using the Substrate node and recommended hardware.
@@ -115,6 +122,45 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(12_u64))
 			.saturating_add(T::DbWeight::get().writes(4_u64))
 	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaxSwapRetryDurationBlocks` (r:1 w:0)
+	/// Proof: `Swapping::MaxSwapRetryDurationBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaxSwapRequestDurationBlocks` (r:1 w:0)
+	/// Proof: `Swapping::MaxSwapRequestDurationBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ChannelOpeningFee` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::ChannelOpeningFee` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::Account` (r:1 w:1)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::DepositChannelPool` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelPool` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ChannelIdCounter` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ChannelIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::EthereumVaultAddress` (r:1 w:0)
+	/// Proof: `Environment::EthereumVaultAddress` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(40), added: 535, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleBlocks` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:0 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::Pools` (r:2 w:0)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapQuotes` (r:0 w:1)
+	/// Proof: `Swapping::SwapQuotes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn request_swap_deposit_address_with_quote() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2412`
+		//  Estimated: `8184`
+		// Minimum execution time: 91_240_000 picoseconds.
+		Weight::from_parts(92_871_000, 8184)
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
 	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
 	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
@@ -133,14 +179,18 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 	/// Proof: `EthereumIngressEgress::EgressDustLimit` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
 	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::WithdrawalAddressProtection` (r:1 w:0)
+	/// Proof: `AssetBalances::WithdrawalAddressProtection` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::WithdrawalAddressHistory` (r:1 w:1)
+	/// Proof: `AssetBalances::WithdrawalAddressHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn withdraw() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1441`
 		//  Estimated: `4906`
 		// Minimum execution time: 49_208_000 picoseconds.
 		Weight::from_parts(49_929_000, 4906)
-			.saturating_add(T::DbWeight::get().reads(9_u64))
-			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().reads(11_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
 	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
@@ -209,6 +259,112 @@ impl<T: frame_system::Config> WeightInfo for PalletWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::AffiliateIdMapping` (r:1 w:1)
+	/// Proof: `Swapping::AffiliateIdMapping` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::FreeBalances` (r:10 w:0)
+	/// Proof: `AssetBalances::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn deregister_affiliate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `657`
+		//  Estimated: `26547`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(32_000_000, 26547)
+			.saturating_add(T::DbWeight::get().reads(12_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::BrokerDefaultAffiliates` (r:0 w:1)
+	/// Proof: `Swapping::BrokerDefaultAffiliates` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_default_affiliates() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `607`
+		//  Estimated: `4072`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 4072)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaxSwapRetryDurationBlocks` (r:1 w:0)
+	/// Proof: `Swapping::MaxSwapRetryDurationBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaxSwapRequestDurationBlocks` (r:1 w:0)
+	/// Proof: `Swapping::MaxSwapRequestDurationBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaxMinPriceMultiple` (r:1 w:0)
+	/// Proof: `Swapping::MaxMinPriceMultiple` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::Pools` (r:2 w:0)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaximumSwapAmount` (r:1 w:0)
+	/// Proof: `Swapping::MaximumSwapAmount` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::FreeBalances` (r:1 w:1)
+	/// Proof: `AssetBalances::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapRequestIdCounter` (r:1 w:1)
+	/// Proof: `Swapping::SwapRequestIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MinimumChunkSize` (r:1 w:0)
+	/// Proof: `Swapping::MinimumChunkSize` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapIdCounter` (r:1 w:1)
+	/// Proof: `Swapping::SwapIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaxSwapsScheduledPerBlock` (r:1 w:0)
+	/// Proof: `Swapping::MaxSwapsScheduledPerBlock` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapQueue` (r:10 w:1)
+	/// Proof: `Swapping::SwapQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapRequests` (r:0 w:1)
+	/// Proof: `Swapping::SwapRequests` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn swap_on_chain() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1534`
+		//  Estimated: `7474`
+		// Minimum execution time: 61_000_000 picoseconds.
+		Weight::from_parts(63_000_000, 7474)
+			.saturating_add(T::DbWeight::get().reads(23_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SuspendedBrokers` (r:1 w:1)
+	/// Proof: `Swapping::SuspendedBrokers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn suspend_broker() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `607`
+		//  Estimated: `4072`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 4072)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Swapping::CollectedRejectedFunds` (r:1 w:1)
+	/// Proof: `Swapping::CollectedRejectedFunds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::EgressIdCounter` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::EgressIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn sweep_collected_rejected_funds() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1127`
+		//  Estimated: `4592`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(32_000_000, 4592)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::CcmDestinationAllowlist` (r:1 w:1)
+	/// Proof: `Swapping::CcmDestinationAllowlist` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn update_ccm_destination_allowlist() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `607`
+		//  Estimated: `4072`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 4072)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -283,6 +439,45 @@ impl WeightInfo for () {
 			.saturating_add(ParityDbWeight::get().reads(12_u64))
 			.saturating_add(ParityDbWeight::get().writes(4_u64))
 	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaxSwapRetryDurationBlocks` (r:1 w:0)
+	/// Proof: `Swapping::MaxSwapRetryDurationBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaxSwapRequestDurationBlocks` (r:1 w:0)
+	/// Proof: `Swapping::MaxSwapRequestDurationBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ChannelOpeningFee` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::ChannelOpeningFee` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Flip::Account` (r:1 w:1)
+	/// Proof: `Flip::Account` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::DepositChannelPool` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelPool` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ChannelIdCounter` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ChannelIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::EthereumVaultAddress` (r:1 w:0)
+	/// Proof: `Environment::EthereumVaultAddress` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumChainTracking::CurrentChainState` (r:1 w:0)
+	/// Proof: `EthereumChainTracking::CurrentChainState` (`max_values`: Some(1), `max_size`: Some(40), added: 535, mode: `MaxEncodedLen`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLifetime` (r:1 w:0)
+	/// Proof: `EthereumIngressEgress::DepositChannelLifetime` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelRecycleBlocks` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelRecycleBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::DepositChannelLookup` (r:0 w:1)
+	/// Proof: `EthereumIngressEgress::DepositChannelLookup` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::Pools` (r:2 w:0)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapQuotes` (r:0 w:1)
+	/// Proof: `Swapping::SwapQuotes` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn request_swap_deposit_address_with_quote() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `2412`
+		//  Estimated: `8184`
+		// Minimum execution time: 91_240_000 picoseconds.
+		Weight::from_parts(92_871_000, 8184)
+			.saturating_add(ParityDbWeight::get().reads(14_u64))
+			.saturating_add(ParityDbWeight::get().writes(5_u64))
+	}
 	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
 	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
 	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
@@ -301,14 +496,18 @@ impl WeightInfo for () {
 	/// Proof: `EthereumIngressEgress::EgressDustLimit` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
 	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::WithdrawalAddressProtection` (r:1 w:0)
+	/// Proof: `AssetBalances::WithdrawalAddressProtection` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::WithdrawalAddressHistory` (r:1 w:1)
+	/// Proof: `AssetBalances::WithdrawalAddressHistory` (`max_values`: None, `max_size`: None, mode: `Measured`)
 	fn withdraw() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1441`
 		//  Estimated: `4906`
 		// Minimum execution time: 49_208_000 picoseconds.
 		Weight::from_parts(49_929_000, 4906)
-			.saturating_add(ParityDbWeight::get().reads(9_u64))
-			.saturating_add(ParityDbWeight::get().writes(4_u64))
+			.saturating_add(ParityDbWeight::get().reads(11_u64))
+			.saturating_add(ParityDbWeight::get().writes(5_u64))
 	}
 	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
 	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
@@ -377,4 +576,110 @@ impl WeightInfo for () {
 			.saturating_add(ParityDbWeight::get().reads(2_u64))
 			.saturating_add(ParityDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::AffiliateIdMapping` (r:1 w:1)
+	/// Proof: `Swapping::AffiliateIdMapping` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::FreeBalances` (r:10 w:0)
+	/// Proof: `AssetBalances::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn deregister_affiliate() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `657`
+		//  Estimated: `26547`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(32_000_000, 26547)
+			.saturating_add(ParityDbWeight::get().reads(12_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::BrokerDefaultAffiliates` (r:0 w:1)
+	/// Proof: `Swapping::BrokerDefaultAffiliates` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn set_default_affiliates() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `607`
+		//  Estimated: `4072`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 4072)
+			.saturating_add(ParityDbWeight::get().reads(1_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Environment::RuntimeSafeMode` (r:1 w:0)
+	/// Proof: `Environment::RuntimeSafeMode` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaxSwapRetryDurationBlocks` (r:1 w:0)
+	/// Proof: `Swapping::MaxSwapRetryDurationBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaxSwapRequestDurationBlocks` (r:1 w:0)
+	/// Proof: `Swapping::MaxSwapRequestDurationBlocks` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaxMinPriceMultiple` (r:1 w:0)
+	/// Proof: `Swapping::MaxMinPriceMultiple` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `LiquidityPools::Pools` (r:2 w:0)
+	/// Proof: `LiquidityPools::Pools` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaximumSwapAmount` (r:1 w:0)
+	/// Proof: `Swapping::MaximumSwapAmount` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `AssetBalances::FreeBalances` (r:1 w:1)
+	/// Proof: `AssetBalances::FreeBalances` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapRequestIdCounter` (r:1 w:1)
+	/// Proof: `Swapping::SwapRequestIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MinimumChunkSize` (r:1 w:0)
+	/// Proof: `Swapping::MinimumChunkSize` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapIdCounter` (r:1 w:1)
+	/// Proof: `Swapping::SwapIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::MaxSwapsScheduledPerBlock` (r:1 w:0)
+	/// Proof: `Swapping::MaxSwapsScheduledPerBlock` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapQueue` (r:10 w:1)
+	/// Proof: `Swapping::SwapQueue` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SwapRequests` (r:0 w:1)
+	/// Proof: `Swapping::SwapRequests` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn swap_on_chain() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1534`
+		//  Estimated: `7474`
+		// Minimum execution time: 61_000_000 picoseconds.
+		Weight::from_parts(63_000_000, 7474)
+			.saturating_add(ParityDbWeight::get().reads(23_u64))
+			.saturating_add(ParityDbWeight::get().writes(5_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::SuspendedBrokers` (r:1 w:1)
+	/// Proof: `Swapping::SuspendedBrokers` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn suspend_broker() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `607`
+		//  Estimated: `4072`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 4072)
+			.saturating_add(ParityDbWeight::get().reads(2_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Swapping::CollectedRejectedFunds` (r:1 w:1)
+	/// Proof: `Swapping::CollectedRejectedFunds` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::EgressIdCounter` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::EgressIdCounter` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	/// Storage: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (r:1 w:1)
+	/// Proof: `EthereumIngressEgress::ScheduledEgressFetchOrTransfer` (`max_values`: Some(1), `max_size`: None, mode: `Measured`)
+	fn sweep_collected_rejected_funds() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1127`
+		//  Estimated: `4592`
+		// Minimum execution time: 31_000_000 picoseconds.
+		Weight::from_parts(32_000_000, 4592)
+			.saturating_add(ParityDbWeight::get().reads(3_u64))
+			.saturating_add(ParityDbWeight::get().writes(3_u64))
+	}
+	/// Storage: `AccountRoles::AccountRoles` (r:1 w:0)
+	/// Proof: `AccountRoles::AccountRoles` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	/// Storage: `Swapping::CcmDestinationAllowlist` (r:1 w:1)
+	/// Proof: `Swapping::CcmDestinationAllowlist` (`max_values`: None, `max_size`: None, mode: `Measured`)
+	fn update_ccm_destination_allowlist() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `607`
+		//  Estimated: `4072`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 4072)
+			.saturating_add(ParityDbWeight::get().reads(2_u64))
+			.saturating_add(ParityDbWeight::get().writes(1_u64))
+	}
 }
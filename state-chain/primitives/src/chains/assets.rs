@@ -14,6 +14,8 @@
 //! assert_eq!(any::Asset::Flip, any::Asset::from(eth::Asset::Flip));
 //! ```
 
+use crate::AssetAmount;
+
 macro_rules! assets {
 	(@ legacy_encoding) => {};
 	(
@@ -761,6 +763,47 @@ assets!(
 	}
 );
 
+/// The number of decimals used for the canonical representation of asset amounts. Amounts of all
+/// assets can be normalised to this precision to compare them independently of the precision used
+/// by the native chain.
+pub const CANONICAL_ASSET_DECIMALS: u8 = 18;
+
+/// No single deposit is expected to be larger than this many whole units of any asset. Witnessed
+/// amounts above this are almost certainly the result of a decimal mismatch.
+pub const MAX_PLAUSIBLE_WHOLE_UNITS: AssetAmount = 100_000_000_000;
+
+impl any::Asset {
+	/// The number of decimals of the asset's chain-native representation. All amounts handled by
+	/// the State Chain are denominated in this precision.
+	pub fn decimals(&self) -> u8 {
+		match self {
+			any::Asset::Eth | any::Asset::Flip | any::Asset::ArbEth => 18,
+			any::Asset::Usdc | any::Asset::Usdt | any::Asset::ArbUsdc | any::Asset::SolUsdc => 6,
+			any::Asset::Dot => 10,
+			any::Asset::Btc => 8,
+			any::Asset::Sol => 9,
+		}
+	}
+
+	/// Converts a chain-native amount to [CANONICAL_ASSET_DECIMALS] precision. Returns `None` on
+	/// overflow.
+	pub fn to_canonical_amount(&self, amount: AssetAmount) -> Option<AssetAmount> {
+		amount.checked_mul(10u128.pow((CANONICAL_ASSET_DECIMALS - self.decimals()) as u32))
+	}
+
+	/// Converts an amount in [CANONICAL_ASSET_DECIMALS] precision to the chain-native precision,
+	/// truncating any excess precision.
+	pub fn from_canonical_amount(&self, amount: AssetAmount) -> AssetAmount {
+		amount / 10u128.pow((CANONICAL_ASSET_DECIMALS - self.decimals()) as u32)
+	}
+
+	/// Whether a chain-native amount is within the plausible range for the asset's decimals, ie.
+	/// no more than [MAX_PLAUSIBLE_WHOLE_UNITS] whole units.
+	pub fn is_plausible_amount(&self, amount: AssetAmount) -> bool {
+		amount <= MAX_PLAUSIBLE_WHOLE_UNITS.saturating_mul(10u128.pow(self.decimals() as u32))
+	}
+}
+
 #[cfg(test)]
 mod test_assets {
 	use super::*;
@@ -1029,4 +1072,39 @@ mod test_assets {
 		assert_eq!(asset_map[any::Asset::Dot], 2);
 		assert_eq!(asset_map[any::Asset::Flip], 0);
 	}
+
+	#[test]
+	fn canonical_amount_normalisation() {
+		for asset in any::Asset::all() {
+			assert!(asset.decimals() <= CANONICAL_ASSET_DECIMALS);
+			let one_unit = 10u128.pow(asset.decimals() as u32);
+			assert_eq!(
+				asset.to_canonical_amount(one_unit),
+				Some(10u128.pow(CANONICAL_ASSET_DECIMALS as u32))
+			);
+			assert_eq!(
+				asset.from_canonical_amount(asset.to_canonical_amount(one_unit).unwrap()),
+				one_unit
+			);
+		}
+
+		// USDC has the same precision on all chains.
+		assert_eq!(
+			any::Asset::Usdc.to_canonical_amount(1_000_000),
+			any::Asset::SolUsdc.to_canonical_amount(1_000_000)
+		);
+		// Excess precision is truncated.
+		assert_eq!(any::Asset::SolUsdc.from_canonical_amount(1_999_999_999_999), 1);
+		assert_eq!(any::Asset::Usdc.to_canonical_amount(AssetAmount::MAX), None);
+	}
+
+	#[test]
+	fn amount_plausibility() {
+		// One million USDC is plausible.
+		assert!(any::Asset::SolUsdc.is_plausible_amount(1_000_000 * 1_000_000));
+		// One USDC with 18 decimals, misinterpreted as 6 decimals, is not.
+		assert!(!any::Asset::SolUsdc.is_plausible_amount(10u128.pow(18)));
+		assert!(any::Asset::Eth.is_plausible_amount(10u128.pow(18)));
+		assert!(!any::Asset::Btc.is_plausible_amount(AssetAmount::MAX));
+	}
 }
@@ -83,7 +83,10 @@ pub type ChannelId = u64;
 
 pub type EgressCounter = u64;
 
-pub type EgressId = (ForeignChain, EgressCounter);
+/// Distinguishes the ingress-egress pallet instances that target the same chain.
+pub type IngressEgressInstanceId = u8;
+
+pub type EgressId = (ForeignChain, IngressEgressInstanceId, EgressCounter);
 
 pub type EthAmount = u128;
 
@@ -273,6 +276,10 @@ pub enum SwapLeg {
 
 pub type TransactionHash = [u8; 32];
 
+/// An opaque identifier optionally supplied when a swap is requested. It is included in all events
+/// emitted over the lifetime of the swap so that indexers can follow it from deposit to egress.
+pub type TrackingId = [u8; 32];
+
 #[derive(
 	Copy,
 	Clone,
@@ -4,6 +4,7 @@ pub mod backup_node_rewards;
 pub mod boost_api;
 pub mod cons_key_rotator;
 pub mod decompose_recompose;
+pub mod deposit_channel_uri;
 pub mod epoch_transition;
 pub mod evm_vault_activator;
 mod missed_authorship_slots;
@@ -58,19 +59,20 @@ use cf_chains::{
 		NONCE_AVAILABILITY_THRESHOLD_FOR_INITIATING_TRANSFER,
 	},
 	AnyChain, ApiCall, Arbitrum, CcmChannelMetadata, CcmDepositMetadata, Chain, ChainCrypto,
-	ChainEnvironment, ChainState, ChannelRefundParametersDecoded, ForeignChain,
+	ChainEnvironment, ChainState, ChannelRefundParametersDecoded, EgressMemo, ForeignChain,
 	ReplayProtectionProvider, RequiresSignatureRefresh, SetCommKeyWithAggKey, SetGovKeyWithAggKey,
 	Solana, TransactionBuilder,
 };
 use cf_primitives::{
 	chains::assets, AccountRole, Asset, BasisPoints, Beneficiaries, ChannelId, DcaParameters,
+	SwapRequestId, TrackingId,
 };
 use cf_traits::{
 	AccountInfo, AccountRoleRegistry, BackupRewardsNotifier, BlockEmissions,
 	BroadcastAnyChainGovKey, Broadcaster, Chainflip, CommKeyBroadcaster, DepositApi, EgressApi,
-	EpochInfo, FetchesTransfersLimitProvider, Heartbeat, IngressEgressFeeApi, Issuance,
-	KeyProvider, OnBroadcastReady, OnDeposit, QualifyNode, RewardsDistribution, RuntimeUpgrade,
-	ScheduledEgressDetails,
+	EgressOrigin, EpochInfo, FetchesTransfersLimitProvider, Heartbeat, IngressEgressFeeApi,
+	Issuance, KeyProvider, OnBroadcastReady, OnDeposit, QualifyNode, RewardsDistribution,
+	RuntimeUpgrade, ScheduledEgressDetails,
 };
 
 use cf_chains::{btc::ScriptPubkey, instances::BitcoinInstance, sol::api::SolanaTransactionType};
@@ -698,6 +700,8 @@ macro_rules! impl_deposit_api_for_anychain {
 				boost_fee: BasisPoints,
 				refund_parameters: Option<ChannelRefundParametersDecoded>,
 				dca_parameters: Option<DcaParameters>,
+				tracking_id: Option<TrackingId>,
+				egress_memo: Option<EgressMemo>,
 			) -> Result<(ChannelId, ForeignChainAddress, <AnyChain as cf_chains::Chain>::ChainBlockNumber, FlipBalance), DispatchError> {
 				match source_asset.into() {
 					$(
@@ -711,10 +715,40 @@ macro_rules! impl_deposit_api_for_anychain {
 							boost_fee,
 							refund_parameters,
 							dca_parameters,
+							tracking_id,
+							egress_memo,
 						).map(|(channel, address, block_number, channel_opening_fee)| (channel, address, block_number.into(), channel_opening_fee)),
 					)+
 				}
 			}
+
+			fn private_channel_deposit_address(
+				source_asset: Asset,
+				channel_id: ChannelId,
+			) -> Result<ForeignChainAddress, DispatchError> {
+				match source_asset.into() {
+					$(
+						ForeignChainAndAsset::$chain(source_asset) =>
+							$pallet::private_channel_deposit_address(source_asset, channel_id),
+					)+
+				}
+			}
+
+			fn is_deferred_channel_open(error: &DispatchError) -> bool {
+				$( $pallet::is_deferred_channel_open(error) )||+
+			}
+
+			fn has_unrecycled_deposit_channels(owner: &Self::AccountId) -> bool {
+				$( $pallet::has_unrecycled_deposit_channels(owner) )||+
+			}
+
+			fn expire_rejection_requests(broker_id: &Self::AccountId) {
+				$( $pallet::expire_rejection_requests(broker_id); )+
+			}
+
+			fn take_swap_request_rejection(swap_request_id: SwapRequestId) -> bool {
+				$( $pallet::take_swap_request_rejection(swap_request_id) )||+
+			}
 		}
 	}
 }
@@ -730,6 +764,8 @@ macro_rules! impl_egress_api_for_anychain {
 				amount: <AnyChain as Chain>::ChainAmount,
 				destination_address: <AnyChain as Chain>::ChainAccount,
 				maybe_ccm_deposit_metadata: Option<CcmDepositMetadata>,
+				egress_memo: Option<EgressMemo>,
+				origin: EgressOrigin,
 			) -> Result<ScheduledEgressDetails<AnyChain>, DispatchError> {
 				match asset.into() {
 					$(
@@ -740,12 +776,25 @@ macro_rules! impl_egress_api_for_anychain {
 								.try_into()
 								.expect("This address cast is ensured to succeed."),
 							maybe_ccm_deposit_metadata,
+							egress_memo,
+							origin,
 						)
 						.map(|ScheduledEgressDetails { egress_id, egress_amount, fee_withheld }| ScheduledEgressDetails { egress_id, egress_amount: egress_amount.into(), fee_withheld: fee_withheld.into() })
 						.map_err(Into::into),
 					)+
 				}
 			}
+
+			fn accepts_new_destination(destination_address: &ForeignChainAddress) -> bool {
+				match destination_address.chain() {
+					$(
+						ForeignChain::$chain => destination_address
+							.clone()
+							.try_into()
+							.is_ok_and(|address| <$pallet as EgressApi<$chain>>::accepts_new_destination(&address)),
+					)+
+				}
+			}
 		}
 	}
 }
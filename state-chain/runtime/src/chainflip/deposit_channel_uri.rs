@@ -0,0 +1,171 @@
+use crate::{
+	chainflip::{EvmEnvironment, SolEnvironment},
+	runtime_apis::DispatchErrorWithMessage,
+	Environment,
+};
+
+use cf_chains::{
+	address::ForeignChainAddress,
+	btc::BitcoinNetwork,
+	evm::{api::EvmEnvironmentProvider, Address as EvmAddress},
+	sol::api::SolanaEnvironment,
+	Arbitrum, Ethereum,
+};
+use cf_primitives::{chains::assets, Asset};
+use scale_info::prelude::{format, string::String};
+use sp_core::hexdisplay::HexDisplay;
+use sp_std::vec::Vec;
+
+/// The query parameter that carries the last block, on the channel's chain, at which deposits to
+/// the channel are witnessed.
+const EXPIRY_PARAMETER: &str = "cf-expires-at";
+
+fn evm_address(address: &EvmAddress) -> String {
+	format!("0x{}", HexDisplay::from(&address.0))
+}
+
+/// Assembles a URI of the form `<scheme>:<target>[/<function>][?<key>=<value>&...]`.
+fn payment_uri(
+	scheme: &str,
+	target: &str,
+	function: Option<&str>,
+	parameters: &[(&str, String)],
+) -> String {
+	let mut uri = format!("{scheme}:{target}");
+	if let Some(function) = function {
+		uri.push('/');
+		uri.push_str(function);
+	}
+	let query = parameters
+		.iter()
+		.map(|(key, value)| format!("{key}={value}"))
+		.collect::<Vec<_>>()
+		.join("&");
+	if !query.is_empty() {
+		uri.push('?');
+		uri.push_str(&query);
+	}
+	uri
+}
+
+/// Builds the payment URI for deposits of `asset` into the channel at `deposit_address`,
+/// following the conventions of the channel's chain: EIP-681 for EVM chains, BIP-21 for Bitcoin
+/// and Solana Pay for Solana.
+pub fn deposit_channel_uri(
+	deposit_address: &ForeignChainAddress,
+	asset: Asset,
+	expires_at: u64,
+) -> Result<String, DispatchErrorWithMessage> {
+	fn evm_uri(
+		chain_id: u64,
+		deposit_address: &EvmAddress,
+		token_address: Option<EvmAddress>,
+		expiry: (&str, String),
+	) -> String {
+		match token_address {
+			None => payment_uri(
+				"ethereum",
+				&format!("{}@{chain_id}", evm_address(deposit_address)),
+				None,
+				&[expiry],
+			),
+			Some(token_address) => payment_uri(
+				"ethereum",
+				&format!("{}@{chain_id}", evm_address(&token_address)),
+				Some("transfer"),
+				&[("address", evm_address(deposit_address)), expiry],
+			),
+		}
+	}
+
+	let expiry = (EXPIRY_PARAMETER, format!("{expires_at}"));
+
+	Ok(match (deposit_address, asset) {
+		(ForeignChainAddress::Eth(address), Asset::Eth) => evm_uri(
+			<EvmEnvironment as EvmEnvironmentProvider<Ethereum>>::chain_id(),
+			address,
+			None,
+			expiry,
+		),
+		(ForeignChainAddress::Eth(address), _) => evm_uri(
+			<EvmEnvironment as EvmEnvironmentProvider<Ethereum>>::chain_id(),
+			address,
+			Some(
+				<EvmEnvironment as EvmEnvironmentProvider<Ethereum>>::token_address(
+					assets::eth::Asset::try_from(asset)
+						.map_err(|_| "Asset is not an Ethereum asset")?,
+				)
+				.ok_or("Asset is not supported")?,
+			),
+			expiry,
+		),
+		(ForeignChainAddress::Arb(address), Asset::ArbEth) => evm_uri(
+			<EvmEnvironment as EvmEnvironmentProvider<Arbitrum>>::chain_id(),
+			address,
+			None,
+			expiry,
+		),
+		(ForeignChainAddress::Arb(address), _) => evm_uri(
+			<EvmEnvironment as EvmEnvironmentProvider<Arbitrum>>::chain_id(),
+			address,
+			Some(
+				<EvmEnvironment as EvmEnvironmentProvider<Arbitrum>>::token_address(
+					assets::arb::Asset::try_from(asset)
+						.map_err(|_| "Asset is not an Arbitrum asset")?,
+				)
+				.ok_or("Asset is not supported")?,
+			),
+			expiry,
+		),
+		(ForeignChainAddress::Btc(script_pubkey), _) => payment_uri(
+			"bitcoin",
+			&script_pubkey.to_address(&BitcoinNetwork::from(Environment::network_environment())),
+			None,
+			&[expiry],
+		),
+		(ForeignChainAddress::Sol(address), Asset::Sol) =>
+			payment_uri("solana", &format!("{address}"), None, &[expiry]),
+		(ForeignChainAddress::Sol(address), _) => {
+			let api_environment = SolEnvironment::api_environment()
+				.map_err(|_| "Failed to load Solana API environment")?;
+			payment_uri(
+				"solana",
+				&format!("{address}"),
+				None,
+				&[("spl-token", format!("{}", api_environment.usdc_token_mint_pubkey)), expiry],
+			)
+		},
+		(ForeignChainAddress::Dot(account_id), _) => payment_uri(
+			"polkadot",
+			&format!("0x{}", HexDisplay::from(account_id.aliased_ref())),
+			None,
+			&[expiry],
+		),
+	})
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn payment_uri_format() {
+		assert_eq!(payment_uri("bitcoin", "bc1qxyz", None, &[]), "bitcoin:bc1qxyz");
+		assert_eq!(
+			payment_uri("bitcoin", "bc1qxyz", None, &[(EXPIRY_PARAMETER, "100".into())]),
+			"bitcoin:bc1qxyz?cf-expires-at=100"
+		);
+		assert_eq!(
+			payment_uri(
+				"ethereum",
+				&format!("{}@1", evm_address(&EvmAddress::repeat_byte(0xab))),
+				Some("transfer"),
+				&[
+					("address", evm_address(&EvmAddress::repeat_byte(0x01))),
+					(EXPIRY_PARAMETER, "200".into())
+				],
+			),
+			"ethereum:0xabababababababababababababababababababab@1/transfer?address=0x0101010101010101010101010101010101010101&cf-expires-at=200"
+		);
+	}
+}
@@ -2,7 +2,9 @@ use crate::AssetBalances;
 use cf_primitives::EpochIndex;
 use cf_traits::EpochTransitionHandler;
 
-use crate::{ArbitrumVault, BitcoinVault, EthereumVault, PolkadotVault, SolanaVault, Witnesser};
+use crate::{
+	ArbitrumVault, BitcoinVault, EthereumVault, PolkadotVault, SolanaVault, Swapping, Witnesser,
+};
 
 pub struct ChainflipEpochTransitions;
 
@@ -15,7 +17,8 @@ impl EpochTransitionHandler for ChainflipEpochTransitions {
 		<ArbitrumVault as EpochTransitionHandler>::on_expired_epoch(expired);
 		<SolanaVault as EpochTransitionHandler>::on_expired_epoch(expired);
 	}
-	fn on_new_epoch(_new: EpochIndex) {
+	fn on_new_epoch(new: EpochIndex) {
 		AssetBalances::trigger_reconciliation();
+		<Swapping as EpochTransitionHandler>::on_new_epoch(new);
 	}
 }
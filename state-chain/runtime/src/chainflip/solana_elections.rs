@@ -20,7 +20,7 @@ use cf_primitives::{AffiliateShortId, Affiliates, Beneficiary, DcaParameters};
 use cf_runtime_utilities::log_or_panic;
 use cf_traits::{
 	offence_reporting::OffenceReporter, AdjustedFeeEstimationApi, Broadcaster, Chainflip,
-	ElectionEgressWitnesser, GetBlockHeight, IngressSource, SolanaNonceWatch,
+	ElectionEgressWitnesser, GetBlockHeight, IngressSource, OnChainWoundDown, SolanaNonceWatch,
 };
 use codec::{Decode, Encode};
 use frame_system::pallet_prelude::BlockNumberFor;
@@ -490,6 +490,52 @@ impl IngressSource for SolanaIngress {
 			},
 		)
 	}
+
+	fn close_channel(
+		channel: <Self::Chain as Chain>::ChainAccount,
+		close_block: <Self::Chain as Chain>::ChainBlockNumber,
+	) -> DispatchResult {
+		pallet_cf_elections::Pallet::<Runtime, SolanaInstance>::with_election_identifiers(
+			|composite_election_identifiers| {
+				SolanaElectoralSystemRunner::with_identifiers(
+					composite_election_identifiers,
+					|grouped_election_identifiers| {
+						let (_, _, election_identifiers, ..) = grouped_election_identifiers;
+						SolanaIngressTracking::close_channel::<
+							DerivedElectoralAccess<
+								_,
+								SolanaIngressTracking,
+								RunnerStorageAccess<Runtime, SolanaInstance>,
+							>,
+						>(election_identifiers, channel, close_block)
+					},
+				)
+			},
+		)
+	}
+
+	fn extend_channel(
+		channel: <Self::Chain as Chain>::ChainAccount,
+		close_block: <Self::Chain as Chain>::ChainBlockNumber,
+	) -> DispatchResult {
+		pallet_cf_elections::Pallet::<Runtime, SolanaInstance>::with_election_identifiers(
+			|composite_election_identifiers| {
+				SolanaElectoralSystemRunner::with_identifiers(
+					composite_election_identifiers,
+					|grouped_election_identifiers| {
+						let (_, _, election_identifiers, ..) = grouped_election_identifiers;
+						SolanaIngressTracking::extend_channel::<
+							DerivedElectoralAccess<
+								_,
+								SolanaIngressTracking,
+								RunnerStorageAccess<Runtime, SolanaInstance>,
+							>,
+						>(election_identifiers, channel, close_block)
+					},
+				)
+			},
+		)
+	}
 }
 
 pub struct SolanaNonceTrackingTrigger;
@@ -511,6 +557,17 @@ impl SolanaNonceWatch for SolanaNonceTrackingTrigger {
 	}
 }
 
+/// Stops the Solana elections once Solana has been wound down, since nothing is left to witness.
+pub struct SolanaWindDownHook;
+
+impl OnChainWoundDown for SolanaWindDownHook {
+	fn on_chain_wound_down() {
+		if let Err(error) = pallet_cf_elections::Pallet::<Runtime, SolanaInstance>::pause() {
+			log::warn!("Failed to pause the Solana elections after the wind-down: {error:?}");
+		}
+	}
+}
+
 pub struct SolanaEgressWitnessingTrigger;
 
 impl ElectionEgressWitnesser for SolanaEgressWitnessingTrigger {
@@ -576,7 +633,10 @@ impl
 		SolanaTransactionBuildingError,
 	> for SolanaVaultSwapsHandler
 {
-	fn initiate_vault_swap(swap_details: SolanaVaultSwapDetails) {
+	fn initiate_vault_swap(
+		account: &VaultSwapAccountAndSender,
+		swap_details: SolanaVaultSwapDetails,
+	) {
 		let block_height = swap_details.creation_slot;
 		SolanaIngressEgress::process_vault_swap_request_full_witness(
 			block_height,
@@ -595,6 +655,10 @@ impl
 				dca_params: swap_details.dca_params,
 				refund_params: Some(swap_details.refund_params),
 				boost_fee: swap_details.boost_fee.into(),
+				tracking_id: None,
+				// The Solana program requires the sender to sign the transaction that opens the
+				// vault swap account.
+				verified_sender: Some(account.swap_sender),
 			},
 		);
 	}
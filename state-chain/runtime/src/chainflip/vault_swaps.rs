@@ -3,7 +3,9 @@ use crate::{
 		address_derivation::btc::derive_btc_vault_deposit_address, AddressConverter,
 		ChainAddressConverter, EvmEnvironment, SolEnvironment,
 	},
-	runtime_apis::{DispatchErrorWithMessage, EvmVaultSwapDetails, VaultSwapDetails},
+	runtime_apis::{
+		DispatchErrorWithMessage, EvmVaultSwapDetails, VaultSwapDetails, VaultSwapParametersPayload,
+	},
 	AccountId, BlockNumber, Environment, Runtime, Swapping,
 };
 
@@ -11,6 +13,11 @@ use cf_chains::{
 	address::EncodedAddress,
 	btc::vault_swap_encoding::{
 		encode_swap_params_in_nulldata_payload, BtcCfParameters, UtxoEncodedData,
+		MAX_NULLDATA_LENGTH,
+	},
+	ccm_checker::{
+		check_ccm_for_blacklisted_accounts, CcmValidityCheck, CcmValidityChecker,
+		DecodedCcmAdditionalData, VersionedSolanaCcmAdditionalData,
 	},
 	cf_parameters::build_cf_parameters,
 	evm::api::{EvmCall, EvmEnvironmentProvider},
@@ -18,14 +25,17 @@ use cf_chains::{
 		api::SolanaEnvironment, instruction_builder::SolanaInstructionBuilder, SolAmount, SolPubkey,
 	},
 	Arbitrum, CcmChannelMetadata, ChannelRefundParametersEncoded, Ethereum, ForeignChain,
+	VaultSwapExtraParameters, VaultSwapExtraParametersEncoded,
 };
 use cf_primitives::{
-	AffiliateAndFee, Affiliates, Asset, AssetAmount, BasisPoints, DcaParameters, SWAP_DELAY_BLOCKS,
+	AffiliateAndFee, Affiliates, Asset, AssetAmount, BasisPoints, DcaParameters, TrackingId,
+	SWAP_DELAY_BLOCKS,
 };
 use cf_traits::AffiliateRegistry;
 use scale_info::prelude::string::String;
 use sp_core::U256;
-use sp_std::vec::Vec;
+use sp_runtime::DispatchError;
+use sp_std::{vec, vec::Vec};
 
 fn to_affiliate_and_fees(
 	broker_id: &AccountId,
@@ -48,8 +58,93 @@ fn to_affiliate_and_fees(
 		.collect::<Result<Vec<AffiliateAndFee>, _>>()
 }
 
-pub fn bitcoin_vault_swap(
-	broker_id: AccountId,
+/// Validates the parameters of a vault swap. Returns the boost fee, converted to the type used in
+/// the encoded parameters.
+pub fn validate_parameters(
+	source_chain: ForeignChain,
+	destination_asset: Asset,
+	destination_address: &EncodedAddress,
+	extra_parameters: &VaultSwapExtraParametersEncoded,
+	channel_metadata: Option<&CcmChannelMetadata>,
+	boost_fee: BasisPoints,
+	dca_parameters: Option<&DcaParameters>,
+) -> Result<u8, DispatchErrorWithMessage> {
+	let destination_chain = ForeignChain::from(destination_asset);
+
+	if let Some(params) = dca_parameters {
+		pallet_cf_swapping::Pallet::<Runtime>::validate_dca_params(params)?;
+	}
+	// Conversion implicitly verifies address validity.
+	frame_support::ensure!(
+		ChainAddressConverter::try_from_encoded_address(destination_address.clone())
+			.map_err(|_| pallet_cf_swapping::Error::<Runtime>::InvalidDestinationAddress)?
+			.chain() == destination_chain,
+		"Destination address and asset are on different chains."
+	);
+
+	// Validate boost fee.
+	let boost_fee: u8 = boost_fee
+		.try_into()
+		.map_err(|_| pallet_cf_swapping::Error::<Runtime>::BoostFeeTooHigh)?;
+
+	// Validate refund duration.
+	pallet_cf_swapping::Pallet::<Runtime>::validate_refund_params(match extra_parameters {
+		VaultSwapExtraParametersEncoded::Bitcoin { retry_duration, .. } => *retry_duration,
+		VaultSwapExtraParametersEncoded::Ethereum(extra_params) =>
+			extra_params.refund_parameters.retry_duration,
+		VaultSwapExtraParametersEncoded::Arbitrum(extra_params) =>
+			extra_params.refund_parameters.retry_duration,
+		VaultSwapExtraParametersEncoded::Solana { refund_parameters, .. } =>
+			refund_parameters.retry_duration,
+	})?;
+
+	// Validate CCM.
+	if let Some(ccm) = channel_metadata {
+		if source_chain == ForeignChain::Bitcoin {
+			return Err(DispatchErrorWithMessage::from(
+				"Vault swaps with CCM are not supported for the Bitcoin Chain",
+			));
+		}
+		if !destination_chain.ccm_support() {
+			return Err(DispatchErrorWithMessage::from("Destination chain does not support CCM"));
+		}
+
+		// Ensure CCM message is valid
+		match CcmValidityChecker::check_and_decode(ccm, destination_asset) {
+			Ok(DecodedCcmAdditionalData::Solana(VersionedSolanaCcmAdditionalData::V0(
+				ccm_accounts,
+			))) => {
+				// Ensure the CCM parameters do not contain blacklisted accounts.
+				// Load up environment variables.
+				let api_environment = SolEnvironment::api_environment()
+					.map_err(|_| "Failed to load Solana API environment")?;
+
+				let agg_key: SolPubkey = SolEnvironment::current_agg_key()
+					.map_err(|_| "Failed to load Solana Agg key")?
+					.into();
+
+				let on_chain_key: SolPubkey = SolEnvironment::current_on_chain_key()
+					.map(|key| key.into())
+					.unwrap_or_else(|_| agg_key);
+
+				check_ccm_for_blacklisted_accounts(
+					&ccm_accounts,
+					vec![api_environment.token_vault_pda_account.into(), agg_key, on_chain_key],
+				)
+				.map_err(DispatchError::from)?;
+			},
+			Ok(DecodedCcmAdditionalData::NotRequired) => {},
+			Err(_) =>
+				return Err(DispatchErrorWithMessage::from("Solana Ccm additional data is invalid")),
+		};
+	}
+
+	Ok(boost_fee)
+}
+
+/// Encodes the swap parameters into the payload of a Bitcoin vault swap's nulldata output.
+fn bitcoin_nulldata_payload(
+	broker_id: &AccountId,
 	destination_asset: Asset,
 	destination_address: EncodedAddress,
 	broker_commission: BasisPoints,
@@ -58,11 +153,9 @@ pub fn bitcoin_vault_swap(
 	boost_fee: u8,
 	affiliate_fees: Affiliates<AccountId>,
 	dca_parameters: Option<DcaParameters>,
-) -> Result<VaultSwapDetails<String>, DispatchErrorWithMessage> {
-	let private_channel_id =
-		pallet_cf_swapping::BrokerPrivateBtcChannels::<Runtime>::get(&broker_id)
-			.ok_or(pallet_cf_swapping::Error::<Runtime>::NoPrivateChannelExistsForBroker)?;
-	let params = UtxoEncodedData {
+	tracking_id: Option<TrackingId>,
+) -> Result<Vec<u8>, DispatchErrorWithMessage> {
+	let payload = encode_swap_params_in_nulldata_payload(UtxoEncodedData {
 		output_asset: destination_asset,
 		output_address: destination_address,
 		parameters: BtcCfParameters {
@@ -86,31 +179,31 @@ pub fn bitcoin_vault_swap(
 			broker_fee: broker_commission
 				.try_into()
 				.map_err(|_| pallet_cf_swapping::Error::<Runtime>::BrokerFeeTooHigh)?,
-			affiliates: to_affiliate_and_fees(&broker_id, affiliate_fees)?
+			affiliates: to_affiliate_and_fees(broker_id, affiliate_fees)?
 				.try_into()
 				.map_err(|_| "Too many affiliates.")?,
 		},
-	};
-
-	Ok(VaultSwapDetails::Bitcoin {
-		nulldata_payload: encode_swap_params_in_nulldata_payload(params),
-		deposit_address: derive_btc_vault_deposit_address(private_channel_id),
-	})
+		tracking_id,
+	});
+	frame_support::ensure!(
+		payload.len() <= MAX_NULLDATA_LENGTH,
+		"The swap parameters don't fit in a Bitcoin nulldata output."
+	);
+	Ok(payload)
 }
 
-pub fn evm_vault_swap<A>(
+/// Encodes the `cf_parameters` that are passed to the EVM vault contracts and the Solana swap
+/// endpoint program.
+fn encode_cf_parameters(
 	broker_id: AccountId,
-	source_asset: Asset,
-	amount: AssetAmount,
-	destination_asset: Asset,
-	destination_address: EncodedAddress,
 	broker_commission: BasisPoints,
 	refund_params: ChannelRefundParametersEncoded,
 	boost_fee: u8,
 	affiliate_fees: Affiliates<AccountId>,
 	dca_parameters: Option<DcaParameters>,
-	channel_metadata: Option<cf_chains::CcmChannelMetadata>,
-) -> Result<VaultSwapDetails<A>, DispatchErrorWithMessage> {
+	channel_metadata: Option<&CcmChannelMetadata>,
+	tracking_id: Option<TrackingId>,
+) -> Result<Vec<u8>, DispatchErrorWithMessage> {
 	let refund_params = refund_params.try_map_address(|addr| {
 		ChainAddressConverter::try_from_encoded_address(addr)
 			.map_err(|_| "Invalid refund address".into())
@@ -119,15 +212,156 @@ pub fn evm_vault_swap<A>(
 		.try_into()
 		.map_err(|_| "Too many affiliates.")?;
 
-	let cf_parameters = build_cf_parameters(
+	Ok(build_cf_parameters(
 		refund_params,
 		dca_parameters,
 		boost_fee,
 		broker_id,
 		broker_commission,
 		processed_affiliate_fees,
+		channel_metadata,
+		tracking_id,
+	))
+}
+
+/// Encodes the swap parameters exactly as they need to be embedded in a vault swap transaction
+/// on the source chain. These are the same encoders used to build the full vault swap details.
+pub fn encode_vault_swap_parameters(
+	broker_id: AccountId,
+	source_chain: ForeignChain,
+	destination_asset: Asset,
+	destination_address: EncodedAddress,
+	broker_commission: BasisPoints,
+	extra_parameters: VaultSwapExtraParametersEncoded,
+	channel_metadata: Option<CcmChannelMetadata>,
+	boost_fee: u8,
+	affiliate_fees: Affiliates<AccountId>,
+	dca_parameters: Option<DcaParameters>,
+	tracking_id: Option<TrackingId>,
+) -> Result<VaultSwapParametersPayload, DispatchErrorWithMessage> {
+	match (source_chain, extra_parameters) {
+		(
+			ForeignChain::Bitcoin,
+			VaultSwapExtraParameters::Bitcoin { min_output_amount, retry_duration },
+		) => Ok(VaultSwapParametersPayload::Bitcoin {
+			nulldata_payload: bitcoin_nulldata_payload(
+				&broker_id,
+				destination_asset,
+				destination_address,
+				broker_commission,
+				min_output_amount,
+				retry_duration,
+				boost_fee,
+				affiliate_fees,
+				dca_parameters,
+				tracking_id,
+			)?,
+		}),
+		(ForeignChain::Ethereum, VaultSwapExtraParameters::Ethereum(extra_params)) =>
+			Ok(VaultSwapParametersPayload::Ethereum {
+				cf_parameters: encode_cf_parameters(
+					broker_id,
+					broker_commission,
+					extra_params.refund_parameters,
+					boost_fee,
+					affiliate_fees,
+					dca_parameters,
+					channel_metadata.as_ref(),
+					tracking_id,
+				)?,
+			}),
+		(ForeignChain::Arbitrum, VaultSwapExtraParameters::Arbitrum(extra_params)) =>
+			Ok(VaultSwapParametersPayload::Arbitrum {
+				cf_parameters: encode_cf_parameters(
+					broker_id,
+					broker_commission,
+					extra_params.refund_parameters,
+					boost_fee,
+					affiliate_fees,
+					dca_parameters,
+					channel_metadata.as_ref(),
+					tracking_id,
+				)?,
+			}),
+		(ForeignChain::Solana, VaultSwapExtraParameters::Solana { refund_parameters, .. }) => {
+			// The Solana vault swap witnessing doesn't carry tracking ids.
+			frame_support::ensure!(
+				tracking_id.is_none(),
+				"Tracking ids are not supported for Solana vault swaps."
+			);
+			Ok(VaultSwapParametersPayload::Solana {
+				cf_parameters: encode_cf_parameters(
+					broker_id,
+					broker_commission,
+					refund_parameters,
+					boost_fee,
+					affiliate_fees,
+					dca_parameters,
+					channel_metadata.as_ref(),
+					None,
+				)?,
+			})
+		},
+		_ => Err(DispatchErrorWithMessage::from(
+			"Incompatible or unsupported source_chain and extra_parameters",
+		)),
+	}
+}
+
+pub fn bitcoin_vault_swap(
+	broker_id: AccountId,
+	destination_asset: Asset,
+	destination_address: EncodedAddress,
+	broker_commission: BasisPoints,
+	min_output_amount: AssetAmount,
+	retry_duration: BlockNumber,
+	boost_fee: u8,
+	affiliate_fees: Affiliates<AccountId>,
+	dca_parameters: Option<DcaParameters>,
+) -> Result<VaultSwapDetails<String>, DispatchErrorWithMessage> {
+	let private_channel_id =
+		pallet_cf_swapping::BrokerPrivateBtcChannels::<Runtime>::get(&broker_id)
+			.ok_or(pallet_cf_swapping::Error::<Runtime>::NoPrivateChannelExistsForBroker)?;
+	Ok(VaultSwapDetails::Bitcoin {
+		nulldata_payload: bitcoin_nulldata_payload(
+			&broker_id,
+			destination_asset,
+			destination_address,
+			broker_commission,
+			min_output_amount,
+			retry_duration,
+			boost_fee,
+			affiliate_fees,
+			dca_parameters,
+			None,
+		)?,
+		deposit_address: derive_btc_vault_deposit_address(private_channel_id),
+	})
+}
+
+pub fn evm_vault_swap<A>(
+	broker_id: AccountId,
+	source_asset: Asset,
+	amount: AssetAmount,
+	destination_asset: Asset,
+	destination_address: EncodedAddress,
+	broker_commission: BasisPoints,
+	refund_params: ChannelRefundParametersEncoded,
+	boost_fee: u8,
+	affiliate_fees: Affiliates<AccountId>,
+	dca_parameters: Option<DcaParameters>,
+	channel_metadata: Option<cf_chains::CcmChannelMetadata>,
+) -> Result<VaultSwapDetails<A>, DispatchErrorWithMessage> {
+	let cf_parameters = encode_cf_parameters(
+		broker_id,
+		broker_commission,
+		refund_params,
+		boost_fee,
+		affiliate_fees,
+		dca_parameters,
 		channel_metadata.as_ref(),
-	);
+		None,
+	)?;
 
 	let calldata = match source_asset {
 		Asset::Eth | Asset::ArbEth =>
@@ -238,28 +472,20 @@ pub fn solana_vault_swap<A>(
 		.map_err(|_| "Failed to derive swap_endpoint_native_vault")?
 		.address;
 
-	let processed_affiliate_fees = to_affiliate_and_fees(&broker_id, affiliate_fees)?
-		.try_into()
-		.map_err(|_| "Too many affiliates")?;
-
 	let from = SolPubkey::try_from(from).map_err(|_| "Invalid Solana Address: from")?;
-	let refund_parameters = refund_parameters.try_map_address(|addr| {
-		ChainAddressConverter::try_from_encoded_address(addr)
-			.map_err(|_| "Invalid refund address".into())
-	})?;
 	let event_data_account = SolPubkey::try_from(event_data_account)
 		.map_err(|_| "Invalid Solana Address: event_data_account")?;
 	let input_amount =
 		SolAmount::try_from(input_amount).map_err(|_| "Input amount exceeded MAX")?;
-	let cf_parameters = build_cf_parameters(
-		refund_parameters,
-		dca_parameters,
-		boost_fee,
+	let cf_parameters = encode_cf_parameters(
 		broker_id,
 		broker_commission,
-		processed_affiliate_fees,
+		refund_parameters,
+		boost_fee,
+		affiliate_fees,
+		dca_parameters,
 		channel_metadata.as_ref(),
-	);
+	)?;
 
 	Ok(VaultSwapDetails::Solana {
 		instruction: match source_asset {
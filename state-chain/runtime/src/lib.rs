@@ -21,16 +21,20 @@ use crate::{
 	},
 	migrations::solana_transaction_data_migration::NoopUpgrade,
 	monitoring_apis::{
-		ActivateKeysBroadcastIds, AuthoritiesInfo, BtcUtxos, EpochState, ExternalChainsBlockHeight,
+		ActivateKeysBroadcastIds, AuthoritiesInfo, BroadcastBudgetUsage, BtcUtxos,
+		EgressThroughput, EpochState, ExternalChainsBlockHeight, FailedCallInfo, FailedCalls,
 		FeeImbalance, FlipSupply, LastRuntimeUpgradeInfo, MonitoringDataV2, OpenDepositChannels,
 		PendingBroadcasts, PendingTssCeremonies, RedemptionsInfo, SolanaNonces,
 	},
 	runtime_apis::{
-		runtime_decl_for_custom_runtime_api::CustomRuntimeApi, AuctionState, BoostPoolDepth,
-		BoostPoolDetails, BrokerInfo, CcmData, DispatchErrorWithMessage, FailingWitnessValidators,
-		FeeTypes, LiquidityProviderBoostPoolInfo, LiquidityProviderInfo, RuntimeApiPenalty,
-		SimulateSwapAdditionalOrder, SimulatedSwapInformation, TransactionScreeningEvents,
-		ValidatorInfo, VaultSwapDetails,
+		runtime_decl_for_custom_runtime_api::CustomRuntimeApi, AccountingReconciliation,
+		AuctionState, BoostPoolDepth, BoostPoolDetails, BrokerAffiliateDetails, BrokerDetails,
+		BrokerInfo, CcmData, ChainWindDownProgress, DepositChannelQrPayload,
+		DepositFailedReasonCode, DepositFailure, DispatchErrorWithMessage,
+		FailingWitnessValidators, FeeTypes, InFlightValue, LiquidityProviderBoostPoolInfo,
+		LiquidityProviderInfo, NetworkFeeAccounting, OpenChannelUsage, RuntimeApiPenalty,
+		SimulateSwapAdditionalOrder, SimulatedSwapInformation, TransactionRejections,
+		TransactionScreeningEvents, ValidatorInfo, VaultSwapDetails, VaultSwapParametersPayload,
 	},
 };
 use cf_amm::{
@@ -47,25 +51,23 @@ use cf_chains::{
 	arb::api::ArbitrumApi,
 	assets::any::{AssetMap, ForeignChainAndAsset},
 	btc::{api::BitcoinApi, BitcoinCrypto, BitcoinRetryPolicy, ScriptPubkey},
-	ccm_checker::{
-		check_ccm_for_blacklisted_accounts, CcmValidityCheck, CcmValidityChecker,
-		DecodedCcmAdditionalData, VersionedSolanaCcmAdditionalData,
-	},
+	ccm_checker::CcmValidityChecker,
 	dot::{self, PolkadotAccountId, PolkadotCrypto},
 	eth::{self, api::EthereumApi, Address as EthereumAddress, Ethereum},
 	evm::EvmCrypto,
-	sol::{api::SolanaEnvironment, SolAddress, SolPubkey, SolanaCrypto},
-	Arbitrum, Bitcoin, CcmChannelMetadata, DefaultRetryPolicy, ForeignChain, Polkadot, Solana,
-	TransactionBuilder, VaultSwapExtraParameters, VaultSwapExtraParametersEncoded,
+	sol::{SolAddress, SolanaCrypto},
+	Arbitrum, Bitcoin, CcmChannelMetadata, DefaultRetryPolicy, ForeignChain, ForeignChainAddress,
+	Polkadot, Solana, TransactionBuilder, VaultSwapExtraParameters,
+	VaultSwapExtraParametersEncoded,
 };
 use cf_primitives::{
-	AffiliateShortId, Affiliates, BasisPoints, Beneficiary, BroadcastId, DcaParameters, EpochIndex,
-	NetworkEnvironment, STABLE_ASSET,
+	AffiliateShortId, Affiliates, BasisPoints, Beneficiaries, Beneficiary, BroadcastId, ChannelId,
+	DcaParameters, EpochIndex, NetworkEnvironment, SwapRequestId, TrackingId, STABLE_ASSET,
 };
 use cf_traits::{
-	AdjustedFeeEstimationApi, AssetConverter, BalanceApi, DummyEgressSuccessWitnesser,
-	DummyIngressSource, EpochKey, GetBlockHeight, KeyProvider, NoLimit, SwapLimits,
-	SwapLimitsProvider,
+	AdjustedFeeEstimationApi, AffiliateRegistry, AssetConverter, BalanceApi,
+	DummyEgressSuccessWitnesser, DummyIngressSource, EpochKey, FetchesTransfersLimitProvider,
+	GetBlockHeight, KeyProvider, NoLimit, SwapLimits, SwapLimitsProvider,
 };
 use codec::{alloc::string::ToString, Decode, Encode};
 use core::ops::Range;
@@ -312,6 +314,7 @@ impl pallet_cf_swapping::Config for Runtime {
 	type BalanceApi = AssetBalances;
 	type ChannelIdAllocator = BitcoinIngressEgress;
 	type Bonder = Bonder<Runtime>;
+	type OnSwapCompleted = ();
 }
 
 impl pallet_cf_vaults::Config<Instance1> for Runtime {
@@ -377,6 +380,7 @@ impl pallet_cf_ingress_egress::Config<Instance1> for Runtime {
 	const MANAGE_CHANNEL_LIFETIME: bool = true;
 	type IngressSource = DummyIngressSource<Ethereum>;
 	type TargetChain = Ethereum;
+	type InstanceId = ConstU8<0>;
 	type AddressDerivation = AddressDerivation;
 	type AddressConverter = ChainAddressConverter;
 	type Balance = AssetBalances;
@@ -397,6 +401,8 @@ impl pallet_cf_ingress_egress::Config<Instance1> for Runtime {
 	type CcmValidityChecker = CcmValidityChecker;
 	type AffiliateRegistry = Swapping;
 	type AllowTransactionReports = ConstBool<false>;
+	type DepositRiskScorer = ();
+	type OnChainWoundDown = ();
 }
 
 impl pallet_cf_ingress_egress::Config<Instance2> for Runtime {
@@ -405,6 +411,7 @@ impl pallet_cf_ingress_egress::Config<Instance2> for Runtime {
 	const MANAGE_CHANNEL_LIFETIME: bool = true;
 	type IngressSource = DummyIngressSource<Polkadot>;
 	type TargetChain = Polkadot;
+	type InstanceId = ConstU8<0>;
 	type AddressDerivation = AddressDerivation;
 	type AddressConverter = ChainAddressConverter;
 	type Balance = AssetBalances;
@@ -425,6 +432,8 @@ impl pallet_cf_ingress_egress::Config<Instance2> for Runtime {
 	type CcmValidityChecker = CcmValidityChecker;
 	type AffiliateRegistry = Swapping;
 	type AllowTransactionReports = ConstBool<false>;
+	type DepositRiskScorer = ();
+	type OnChainWoundDown = ();
 }
 
 impl pallet_cf_ingress_egress::Config<Instance3> for Runtime {
@@ -433,6 +442,7 @@ impl pallet_cf_ingress_egress::Config<Instance3> for Runtime {
 	const MANAGE_CHANNEL_LIFETIME: bool = true;
 	type IngressSource = DummyIngressSource<Bitcoin>;
 	type TargetChain = Bitcoin;
+	type InstanceId = ConstU8<0>;
 	type AddressDerivation = AddressDerivation;
 	type AddressConverter = ChainAddressConverter;
 	type Balance = AssetBalances;
@@ -453,6 +463,8 @@ impl pallet_cf_ingress_egress::Config<Instance3> for Runtime {
 	type CcmValidityChecker = CcmValidityChecker;
 	type AffiliateRegistry = Swapping;
 	type AllowTransactionReports = ConstBool<true>;
+	type DepositRiskScorer = ();
+	type OnChainWoundDown = ();
 }
 
 impl pallet_cf_ingress_egress::Config<Instance4> for Runtime {
@@ -461,6 +473,7 @@ impl pallet_cf_ingress_egress::Config<Instance4> for Runtime {
 	const MANAGE_CHANNEL_LIFETIME: bool = true;
 	type IngressSource = DummyIngressSource<Arbitrum>;
 	type TargetChain = Arbitrum;
+	type InstanceId = ConstU8<0>;
 	type AddressDerivation = AddressDerivation;
 	type AddressConverter = ChainAddressConverter;
 	type Balance = AssetBalances;
@@ -481,6 +494,8 @@ impl pallet_cf_ingress_egress::Config<Instance4> for Runtime {
 	type CcmValidityChecker = CcmValidityChecker;
 	type AffiliateRegistry = Swapping;
 	type AllowTransactionReports = ConstBool<false>;
+	type DepositRiskScorer = ();
+	type OnChainWoundDown = ();
 }
 
 impl pallet_cf_ingress_egress::Config<Instance5> for Runtime {
@@ -489,6 +504,7 @@ impl pallet_cf_ingress_egress::Config<Instance5> for Runtime {
 	const MANAGE_CHANNEL_LIFETIME: bool = false;
 	type IngressSource = SolanaIngress;
 	type TargetChain = Solana;
+	type InstanceId = ConstU8<0>;
 	type AddressDerivation = AddressDerivation;
 	type AddressConverter = ChainAddressConverter;
 	type Balance = AssetBalances;
@@ -509,6 +525,8 @@ impl pallet_cf_ingress_egress::Config<Instance5> for Runtime {
 	type CcmValidityChecker = CcmValidityChecker;
 	type AffiliateRegistry = Swapping;
 	type AllowTransactionReports = ConstBool<false>;
+	type DepositRiskScorer = ();
+	type OnChainWoundDown = chainflip::solana_elections::SolanaWindDownHook;
 }
 
 impl pallet_cf_pools::Config for Runtime {
@@ -956,6 +974,7 @@ impl pallet_cf_asset_balances::Config for Runtime {
 	type EgressHandler = chainflip::AnyChainIngressEgressHandler;
 	type PolkadotKeyProvider = PolkadotThresholdSigner;
 	type SafeMode = RuntimeSafeMode;
+	type WeightInfo = pallet_cf_asset_balances::weights::PalletWeight<Runtime>;
 }
 
 impl pallet_cf_broadcast::Config<Instance5> for Runtime {
@@ -1017,6 +1036,7 @@ impl pallet_cf_chain_tracking::Config<Instance5> for Runtime {
 impl pallet_cf_elections::Config<Instance5> for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type ElectoralSystemRunner = chainflip::solana_elections::SolanaElectoralSystemRunner;
+	const INCLUDE_VOTER_STAKE: bool = false;
 	type WeightInfo = pallet_cf_elections::weights::PalletWeight<Runtime>;
 }
 
@@ -1426,6 +1446,14 @@ impl_runtime_apis! {
 		fn cf_filter_votes(account_id: AccountId, proposed_votes: Vec<u8>) -> Vec<u8> {
 			SolanaElections::filter_votes(&account_id, Decode::decode(&mut &proposed_votes[..]).unwrap_or_default()).encode()
 		}
+
+		fn cf_consensus_archive() -> Vec<u8> {
+			pallet_cf_elections::ConsensusArchive::<Runtime, SolanaInstance>::get().encode()
+		}
+
+		fn cf_vote_storage_usage() -> Vec<u8> {
+			SolanaElections::vote_storage_usage().encode()
+		}
 	}
 
 	// START custom runtime APIs
@@ -1836,13 +1864,12 @@ impl_runtime_apis! {
 		}
 
 		fn cf_min_deposit_amount(asset: Asset) -> AssetAmount {
-			use pallet_cf_ingress_egress::MinimumDeposit;
 			match asset.into() {
-				ForeignChainAndAsset::Ethereum(asset) => MinimumDeposit::<Runtime, EthereumInstance>::get(asset),
-				ForeignChainAndAsset::Polkadot(asset) => MinimumDeposit::<Runtime, PolkadotInstance>::get(asset),
-				ForeignChainAndAsset::Bitcoin(asset) => MinimumDeposit::<Runtime, BitcoinInstance>::get(asset).into(),
-				ForeignChainAndAsset::Arbitrum(asset) => MinimumDeposit::<Runtime, ArbitrumInstance>::get(asset),
-				ForeignChainAndAsset::Solana(asset) => MinimumDeposit::<Runtime, SolanaInstance>::get(asset).into(),
+				ForeignChainAndAsset::Ethereum(asset) => EthereumIngressEgress::effective_minimum_deposit(asset),
+				ForeignChainAndAsset::Polkadot(asset) => PolkadotIngressEgress::effective_minimum_deposit(asset),
+				ForeignChainAndAsset::Bitcoin(asset) => BitcoinIngressEgress::effective_minimum_deposit(asset).into(),
+				ForeignChainAndAsset::Arbitrum(asset) => ArbitrumIngressEgress::effective_minimum_deposit(asset),
+				ForeignChainAndAsset::Solana(asset) => SolanaIngressEgress::effective_minimum_deposit(asset).into(),
 			}
 		}
 
@@ -2060,30 +2087,64 @@ impl_runtime_apis! {
 			all_prewitnessed_swaps
 		}
 
-		fn cf_scheduled_swaps(base_asset: Asset, quote_asset: Asset) -> Vec<(SwapLegInfo, BlockNumber)> {
+		fn cf_scheduled_swaps(
+			base_asset: Asset,
+			quote_asset: Asset,
+			page: u32,
+			page_size: u32,
+			estimate_only: bool,
+		) -> Vec<(SwapLegInfo, BlockNumber)> {
 			assert_eq!(quote_asset, STABLE_ASSET, "Only USDC is supported as quote asset");
 
 			let current_block = System::block_number();
+			let page_size = core::cmp::min(page_size, runtime_apis::MAX_SCHEDULED_SWAPS_PAGE_SIZE);
 
-			pallet_cf_swapping::SwapQueue::<Runtime>::iter().flat_map(|(block, swaps_for_block)| {
-				// In case `block` has already passed, the swaps will be re-tried at the next block:
-				let execute_at = core::cmp::max(block, current_block.saturating_add(1));
+			let pool_sell_price = LiquidityPools::pool_price(base_asset, quote_asset).
+				expect("Pool should exist")
+				.sell
+				.map(|price| price.sqrt_price);
 
-				let swaps: Vec<_> = swaps_for_block
-					.iter()
-					.filter(|swap| swap.from == base_asset || swap.to == base_asset)
-					.cloned()
-					.collect();
+			let mut swaps_by_block: Vec<_> = pallet_cf_swapping::SwapQueue::<Runtime>::iter()
+				.map(|(block, swaps_for_block)| {
+					(
+						block,
+						swaps_for_block
+							.into_iter()
+							.filter(|swap| swap.from == base_asset || swap.to == base_asset)
+							.collect::<Vec<_>>(),
+					)
+				})
+				.filter(|(_, swaps)| !swaps.is_empty())
+				.collect();
+			swaps_by_block.sort_by_key(|(block, _)| *block);
+
+			// Only the swaps in the requested page are simulated:
+			let mut skip = page.saturating_mul(page_size) as usize;
+			let mut take = page_size as usize;
+			let mut legs = Vec::new();
+			for (block, swaps) in swaps_by_block {
+				if take == 0 {
+					break;
+				}
+				if skip >= swaps.len() {
+					skip -= swaps.len();
+					continue;
+				}
+				let swaps: Vec<_> = swaps.into_iter().skip(skip).take(take).collect();
+				skip = 0;
+				take -= swaps.len();
 
-				let pool_sell_price = LiquidityPools::pool_price(base_asset, quote_asset).
-					expect("Pool should exist")
-					.sell
-					.map(|price| price.sqrt_price);
+				// In case `block` has already passed, the swaps will be re-tried at the next block:
+				let execute_at = core::cmp::max(block, current_block.saturating_add(1));
 
-				Swapping::get_scheduled_swap_legs(swaps, base_asset, pool_sell_price)
-					.into_iter()
-					.map(move |swap| (swap, execute_at))
-			}).collect()
+				let swap_legs = if estimate_only {
+					Swapping::estimate_scheduled_swap_legs(swaps, base_asset, pool_sell_price)
+				} else {
+					Swapping::get_scheduled_swap_legs(swaps, base_asset, pool_sell_price)
+				};
+				legs.extend(swap_legs.into_iter().map(|swap| (swap, execute_at)));
+			}
+			legs
 		}
 
 		fn cf_failed_call_ethereum(broadcast_id: BroadcastId) -> Option<<cf_chains::Ethereum as cf_chains::Chain>::Transaction> {
@@ -2229,70 +2290,15 @@ impl_runtime_apis! {
 			dca_parameters: Option<DcaParameters>,
 		) -> Result<VaultSwapDetails<String>, DispatchErrorWithMessage> {
 			let source_chain = ForeignChain::from(source_asset);
-			let destination_chain = ForeignChain::from(destination_asset);
-
-			// Validate parameters.
-			if let Some(params) = dca_parameters.as_ref() {
-				pallet_cf_swapping::Pallet::<Runtime>::validate_dca_params(params)?;
-			}
-			// Conversion implicitly verifies address validity.
-			frame_support::ensure!(
-				ChainAddressConverter::try_from_encoded_address(destination_address.clone())
-					.map_err(|_| pallet_cf_swapping::Error::<Runtime>::InvalidDestinationAddress)?
-					.chain() == destination_chain
-				,
-				"Destination address and asset are on different chains."
-			);
-
-			// Validate boost fee.
-			let boost_fee: u8 = boost_fee
-				.try_into()
-				.map_err(|_| pallet_cf_swapping::Error::<Runtime>::BoostFeeTooHigh)?;
-
-			// Validate refund duration.
-			pallet_cf_swapping::Pallet::<Runtime>::validate_refund_params(match &extra_parameters {
-				VaultSwapExtraParametersEncoded::Bitcoin { retry_duration, .. } => *retry_duration,
-				VaultSwapExtraParametersEncoded::Ethereum(extra_params) => extra_params.refund_parameters.retry_duration,
-				VaultSwapExtraParametersEncoded::Arbitrum(extra_params) => extra_params.refund_parameters.retry_duration,
-				VaultSwapExtraParametersEncoded::Solana { refund_parameters, .. } => refund_parameters.retry_duration,
-			})?;
-
-			// Validate CCM.
-			if let Some(ccm) = channel_metadata.as_ref() {
-				if source_chain == ForeignChain::Bitcoin {
-					return Err(DispatchErrorWithMessage::from("Vault swaps with CCM are not supported for the Bitcoin Chain"));
-				}
-				if !destination_chain.ccm_support() {
-					return Err(DispatchErrorWithMessage::from("Destination chain does not support CCM"));
-				}
-
-				// Ensure CCM message is valid
-				match CcmValidityChecker::check_and_decode(ccm, destination_asset)
-				{
-					Ok(DecodedCcmAdditionalData::Solana(VersionedSolanaCcmAdditionalData::V0(ccm_accounts))) => {
-						// Ensure the CCM parameters do not contain blacklisted accounts.
-						// Load up environment variables.
-						let api_environment =
-							SolEnvironment::api_environment().map_err(|_| "Failed to load Solana API environment")?;
-
-						let agg_key: SolPubkey = SolEnvironment::current_agg_key()
-							.map_err(|_| "Failed to load Solana Agg key")?
-							.into();
-
-						let on_chain_key: SolPubkey = SolEnvironment::current_on_chain_key()
-							.map(|key| key.into())
-							.unwrap_or_else(|_| agg_key);
-
-						check_ccm_for_blacklisted_accounts(
-							&ccm_accounts,
-							vec![api_environment.token_vault_pda_account.into(), agg_key, on_chain_key],
-						)
-						.map_err(DispatchError::from)?;
-					},
-					Ok(DecodedCcmAdditionalData::NotRequired) => {},
-					Err(_) => return Err(DispatchErrorWithMessage::from("Solana Ccm additional data is invalid")),
-				};
-			}
+			let boost_fee = crate::chainflip::vault_swaps::validate_parameters(
+				source_chain,
+				destination_asset,
+				&destination_address,
+				&extra_parameters,
+				channel_metadata.as_ref(),
+				boost_fee,
+				dca_parameters.as_ref(),
+			)?;
 
 			// Encode swap
 			match (source_chain, extra_parameters) {
@@ -2384,11 +2390,109 @@ impl_runtime_apis! {
 			}
 		}
 
+		fn cf_encode_vault_swap_parameters(
+			broker_id: AccountId,
+			source_chain: ForeignChain,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			broker_commission: BasisPoints,
+			extra_parameters: VaultSwapExtraParametersEncoded,
+			channel_metadata: Option<CcmChannelMetadata>,
+			boost_fee: BasisPoints,
+			affiliate_fees: Affiliates<AccountId>,
+			dca_parameters: Option<DcaParameters>,
+			tracking_id: Option<TrackingId>,
+		) -> Result<VaultSwapParametersPayload, DispatchErrorWithMessage> {
+			let boost_fee = crate::chainflip::vault_swaps::validate_parameters(
+				source_chain,
+				destination_asset,
+				&destination_address,
+				&extra_parameters,
+				channel_metadata.as_ref(),
+				boost_fee,
+				dca_parameters.as_ref(),
+			)?;
+
+			if channel_metadata.is_some() {
+				pallet_cf_swapping::Pallet::<Runtime>::validate_ccm_destination(
+					&broker_id,
+					&ChainAddressConverter::try_from_encoded_address(destination_address.clone())
+						.map_err(|_| pallet_cf_swapping::Error::<Runtime>::InvalidDestinationAddress)?,
+				)?;
+			}
+
+			crate::chainflip::vault_swaps::encode_vault_swap_parameters(
+				broker_id,
+				source_chain,
+				destination_asset,
+				destination_address,
+				broker_commission,
+				extra_parameters,
+				channel_metadata,
+				boost_fee,
+				affiliate_fees,
+				dca_parameters,
+				tracking_id,
+			)
+		}
+
+		fn cf_deposit_failures(
+			chain: ForeignChain,
+			deposit_address: Option<EncodedAddress>,
+			tx_id: Option<Vec<u8>>,
+		) -> Result<Vec<DepositFailure>, DispatchErrorWithMessage> {
+			fn deposit_failures<I: 'static>(
+				deposit_address: Option<EncodedAddress>,
+				tx_id: Option<Vec<u8>>,
+			) -> Result<Vec<DepositFailure>, DispatchErrorWithMessage>
+				where Runtime: pallet_cf_ingress_egress::Config<I>
+			{
+				let deposit_address = deposit_address
+					.map(|address| {
+						ChainAddressConverter::try_from_encoded_address(address)
+							.ok()
+							.and_then(|address| address.try_into().ok())
+							.ok_or(DispatchErrorWithMessage::from("Invalid deposit address"))
+					})
+					.transpose()?;
+				let tx_id = tx_id
+					.map(|tx_id| {
+						pallet_cf_ingress_egress::TransactionInIdFor::<Runtime, I>::decode(&mut &tx_id[..])
+							.map_err(|_| DispatchErrorWithMessage::from("Invalid transaction id"))
+					})
+					.transpose()?;
+
+				Ok(pallet_cf_ingress_egress::Pallet::<Runtime, I>::deposit_failures(deposit_address.as_ref(), tx_id.as_ref())
+					.into_iter()
+					.map(|failure| DepositFailure {
+						block_height: failure.block_height.into(),
+						deposit_address: failure.deposit_address.map(|address| address.into_foreign_chain_address()),
+						tx_id: failure.tx_id.map(|tx_id| tx_id.encode()),
+						asset: failure.asset.into(),
+						amount: failure.amount.into(),
+						reason: failure.reason,
+						recorded_at: failure.recorded_at,
+					})
+					.collect())
+			}
+
+			match chain {
+				ForeignChain::Ethereum => deposit_failures::<EthereumInstance>(deposit_address, tx_id),
+				ForeignChain::Polkadot => deposit_failures::<PolkadotInstance>(deposit_address, tx_id),
+				ForeignChain::Bitcoin => deposit_failures::<BitcoinInstance>(deposit_address, tx_id),
+				ForeignChain::Arbitrum => deposit_failures::<ArbitrumInstance>(deposit_address, tx_id),
+				ForeignChain::Solana => deposit_failures::<SolanaInstance>(deposit_address, tx_id),
+			}
+		}
+
 		fn cf_get_open_deposit_channels(account_id: Option<AccountId>) -> ChainAccounts {
-			let btc_chain_accounts = pallet_cf_ingress_egress::DepositChannelLookup::<Runtime,BitcoinInstance>::iter_values()
-				.filter(|channel_details| account_id.is_none() || Some(&channel_details.owner) == account_id.as_ref())
-				.map(|channel_details| channel_details.deposit_channel.address)
-				.collect::<Vec<_>>();
+			let btc_chain_accounts = match account_id {
+				Some(account_id) => pallet_cf_ingress_egress::DepositChannelsByOwner::<Runtime, BitcoinInstance>::iter_key_prefix(account_id)
+					.collect::<Vec<_>>(),
+				None => pallet_cf_ingress_egress::DepositChannelLookup::<Runtime, BitcoinInstance>::iter_values()
+					.map(|channel_details| channel_details.deposit_channel.address)
+					.collect::<Vec<_>>(),
+			};
 
 			ChainAccounts {
 				btc_chain_accounts
@@ -2404,7 +2508,9 @@ impl_runtime_apis! {
 						pallet_cf_ingress_egress::Event::TransactionRejectionRequestReceived{ account_id, tx_id, expires_at: _ } =>
 							Some(TransactionScreeningEvent::TransactionRejectionRequestReceived{account_id, tx_id }),
 						pallet_cf_ingress_egress::Event::TransactionRejectedByBroker{ broadcast_id, tx_id } =>
-							Some(TransactionScreeningEvent::TransactionRejectedByBroker{ refund_broadcast_id: broadcast_id, tx_id: tx_id.id.tx_id }),
+							cf_chains::DepositDetailsToTransactionInId::deposit_id(&tx_id).map(|tx_id|
+								TransactionScreeningEvent::TransactionRejectedByBroker{ refund_broadcast_id: broadcast_id, tx_id }
+							),
 						_ => None,
 					}
 				} else {
@@ -2422,6 +2528,312 @@ impl_runtime_apis! {
 		) -> Vec<(AffiliateShortId, AccountId)>{
 			pallet_cf_swapping::AffiliateIdMapping::<Runtime>::iter_prefix(&broker).collect()
 		}
+
+		fn cf_transaction_rejections(
+			chain: ForeignChain,
+			broker: AccountId,
+			page: u32,
+			page_size: u32,
+		) -> TransactionRejections {
+			fn transaction_rejections<I: 'static>(
+				broker: AccountId,
+				page: u32,
+				page_size: u32,
+			) -> TransactionRejections
+				where Runtime: pallet_cf_ingress_egress::Config<I>
+			{
+				use cf_chains::DepositDetailsToTransactionInId;
+
+				let skip = page.saturating_mul(page_size) as usize;
+				let take = page_size as usize;
+				let to_rejection = |details: pallet_cf_ingress_egress::TransactionRejectionDetails<Runtime, I>| {
+					crate::runtime_apis::TransactionRejection {
+						tx_id: details.deposit_details.deposit_id().map(|tx_id| tx_id.encode()),
+						asset: details.asset.into(),
+						amount: details.amount.into(),
+						refund_address: details.refund_address,
+					}
+				};
+
+				TransactionRejections {
+					marked_for_rejection: pallet_cf_ingress_egress::Pallet::<Runtime, I>::transactions_marked_for_rejection(&broker)
+						.into_iter()
+						.skip(skip)
+						.take(take)
+						.map(|(tx_id, status, expires_at)| crate::runtime_apis::MarkedTransaction {
+							tx_id: tx_id.encode(),
+							prewitnessed: status == pallet_cf_ingress_egress::TransactionPrewitnessedStatus::Prewitnessed,
+							expires_at,
+						})
+						.collect(),
+					scheduled_for_rejection: pallet_cf_ingress_egress::Pallet::<Runtime, I>::scheduled_transactions_for_rejection()
+						.into_iter()
+						.skip(skip)
+						.take(take)
+						.map(to_rejection)
+						.collect(),
+					failed_rejections: pallet_cf_ingress_egress::Pallet::<Runtime, I>::failed_rejections()
+						.into_iter()
+						.skip(skip)
+						.take(take)
+						.map(to_rejection)
+						.collect(),
+				}
+			}
+
+			match chain {
+				ForeignChain::Ethereum => transaction_rejections::<EthereumInstance>(broker, page, page_size),
+				ForeignChain::Polkadot => transaction_rejections::<PolkadotInstance>(broker, page, page_size),
+				ForeignChain::Bitcoin => transaction_rejections::<BitcoinInstance>(broker, page, page_size),
+				ForeignChain::Arbitrum => transaction_rejections::<ArbitrumInstance>(broker, page, page_size),
+				ForeignChain::Solana => transaction_rejections::<SolanaInstance>(broker, page, page_size),
+			}
+		}
+
+		fn cf_network_fee_accounting() -> NetworkFeeAccounting {
+			NetworkFeeAccounting {
+				collected_network_fee: pallet_cf_swapping::CollectedNetworkFee::<Runtime>::get(),
+				flip_to_burn: pallet_cf_swapping::FlipToBurn::<Runtime>::get(),
+				collected_rejected_funds: pallet_cf_swapping::CollectedRejectedFunds::<Runtime>::iter()
+					.collect(),
+				network_fees_taken: pallet_cf_swapping::NetworkFeesTaken::<Runtime>::get(),
+				boost_network_fees_taken: pallet_cf_swapping::BoostNetworkFeesTaken::<Runtime>::iter()
+					.collect(),
+			}
+		}
+
+		fn cf_effective_network_fee(
+			_input_asset: Asset,
+			_output_asset: Asset,
+			is_internal: bool,
+		) -> pallet_cf_swapping::FeeRateAndMinimum {
+			// The network fee currently doesn't depend on the asset pair.
+			Swapping::effective_network_fee(is_internal)
+		}
+
+		fn cf_recommended_witness_safety_margin(chain: ForeignChain) -> Option<u64> {
+			match chain {
+				ForeignChain::Bitcoin => pallet_cf_ingress_egress::Pallet::<Runtime, BitcoinInstance>::recommended_witness_safety_margin(),
+				ForeignChain::Ethereum => pallet_cf_ingress_egress::Pallet::<Runtime, EthereumInstance>::recommended_witness_safety_margin(),
+				ForeignChain::Polkadot => pallet_cf_ingress_egress::Pallet::<Runtime, PolkadotInstance>::recommended_witness_safety_margin().map(Into::into),
+				ForeignChain::Arbitrum => pallet_cf_ingress_egress::Pallet::<Runtime, ArbitrumInstance>::recommended_witness_safety_margin(),
+				ForeignChain::Solana => pallet_cf_ingress_egress::Pallet::<Runtime, SolanaInstance>::recommended_witness_safety_margin(),
+			}
+		}
+
+		fn cf_private_channel_owner(channel_id: ChannelId) -> Option<AccountId> {
+			BrokerPrivateBtcChannels::<Runtime>::iter()
+				.find_map(|(broker_id, id)| (id == channel_id).then_some(broker_id))
+		}
+
+		fn cf_decode_and_validate_address(
+			encoded_address: EncodedAddress,
+			asset: Asset,
+		) -> Result<ForeignChainAddress, DispatchErrorWithMessage> {
+			ChainAddressConverter::decode_and_validate_address_for_asset(encoded_address, asset)
+				.map_err(|error| pallet_cf_swapping::address_error_to_pallet_error::<Runtime>(error).into())
+		}
+
+		fn cf_encode_address(address: ForeignChainAddress) -> EncodedAddress {
+			ChainAddressConverter::to_encoded_address(address)
+		}
+
+		fn cf_estimate_broker_fees(
+			input_asset: Asset,
+			output_asset: Asset,
+			input_amount: AssetAmount,
+			broker_fees: Beneficiaries<AccountId>,
+		) -> Result<pallet_cf_swapping::BrokerFeeEstimate<AccountId>, DispatchErrorWithMessage> {
+			Swapping::estimate_broker_fees(input_asset, output_asset, input_amount, &broker_fees)
+				.map_err(Into::into)
+		}
+
+		fn cf_deposit_failed_reason_codes() -> Vec<DepositFailedReasonCode> {
+			pallet_cf_ingress_egress::DEPOSIT_FAILED_REASON_CODES
+				.iter()
+				.map(|(code, name, description)| DepositFailedReasonCode {
+					code: *code,
+					name: (*name).into(),
+					description: (*description).into(),
+				})
+				.collect()
+		}
+
+		fn cf_broker_details(account_id: AccountId) -> BrokerDetails {
+			fn open_deposit_channels<I: 'static>(account_id: &AccountId) -> u32
+				where Runtime: pallet_cf_ingress_egress::Config<I>
+			{
+				pallet_cf_ingress_egress::OpenChannelCount::<Runtime, I>::get(account_id)
+			}
+
+			let earned_fees = |account_id: &AccountId| Asset::all().map(|asset|
+				(asset, AssetBalances::get_balance(account_id, asset))
+			).collect::<Vec<_>>();
+
+			let private_channel_id = BrokerPrivateBtcChannels::<Runtime>::get(&account_id);
+
+			BrokerDetails {
+				bond: pallet_cf_flip::Account::<Runtime>::get(&account_id).bond(),
+				earned_fees: earned_fees(&account_id),
+				open_deposit_channels: open_deposit_channels::<EthereumInstance>(&account_id)
+					.saturating_add(open_deposit_channels::<PolkadotInstance>(&account_id))
+					.saturating_add(open_deposit_channels::<BitcoinInstance>(&account_id))
+					.saturating_add(open_deposit_channels::<ArbitrumInstance>(&account_id))
+					.saturating_add(open_deposit_channels::<SolanaInstance>(&account_id)),
+				private_channel_id,
+				btc_vault_deposit_address: private_channel_id.map(derive_btc_vault_deposit_address),
+				affiliates: <Swapping as AffiliateRegistry>::reverse_mapping(&account_id)
+					.into_iter()
+					.map(|(affiliate_id, short_id)| BrokerAffiliateDetails {
+						short_id,
+						earned_fees: earned_fees(&affiliate_id),
+						account_id: affiliate_id,
+					})
+					.collect(),
+				withdrawal_addresses: pallet_cf_asset_balances::WithdrawalAddressHistory::<Runtime>::get(&account_id)
+					.into_inner(),
+			}
+		}
+
+		fn cf_open_channel_usage(account_id: AccountId) -> Vec<OpenChannelUsage> {
+			ForeignChain::iter().map(|chain| {
+				let (open_channels, max_channels) = match chain {
+					ForeignChain::Ethereum => pallet_cf_ingress_egress::Pallet::<Runtime, EthereumInstance>::open_channel_usage(&account_id),
+					ForeignChain::Polkadot => pallet_cf_ingress_egress::Pallet::<Runtime, PolkadotInstance>::open_channel_usage(&account_id),
+					ForeignChain::Bitcoin => pallet_cf_ingress_egress::Pallet::<Runtime, BitcoinInstance>::open_channel_usage(&account_id),
+					ForeignChain::Arbitrum => pallet_cf_ingress_egress::Pallet::<Runtime, ArbitrumInstance>::open_channel_usage(&account_id),
+					ForeignChain::Solana => pallet_cf_ingress_egress::Pallet::<Runtime, SolanaInstance>::open_channel_usage(&account_id),
+				};
+				OpenChannelUsage { chain, open_channels, max_channels }
+			}).collect()
+		}
+
+		fn cf_deposit_channel_qr_payload(
+			deposit_address: EncodedAddress,
+		) -> Result<DepositChannelQrPayload, DispatchErrorWithMessage> {
+			fn qr_payload<I: 'static>(
+				deposit_address: ForeignChainAddress,
+			) -> Result<DepositChannelQrPayload, DispatchErrorWithMessage>
+				where Runtime: pallet_cf_ingress_egress::Config<I>
+			{
+				let channel_address: <<Runtime as pallet_cf_ingress_egress::Config<I>>::TargetChain as cf_chains::Chain>::ChainAccount =
+					deposit_address.clone()
+						.try_into()
+						.map_err(|_| DispatchErrorWithMessage::from("Invalid deposit address"))?;
+				let channel_details = pallet_cf_ingress_egress::DepositChannelLookup::<Runtime, I>::get(channel_address)
+					.ok_or(DispatchErrorWithMessage::from("No open deposit channel at this address"))?;
+
+				let asset = channel_details.deposit_channel.asset.into();
+				let expires_at = channel_details.expires_at.into();
+				Ok(DepositChannelQrPayload {
+					uri: crate::chainflip::deposit_channel_uri::deposit_channel_uri(
+						&deposit_address,
+						asset,
+						expires_at,
+					)?,
+					asset,
+					expires_at,
+				})
+			}
+
+			let deposit_address = ChainAddressConverter::try_from_encoded_address(deposit_address)
+				.map_err(|_| DispatchErrorWithMessage::from("Invalid deposit address"))?;
+			match deposit_address.chain() {
+				ForeignChain::Ethereum => qr_payload::<EthereumInstance>(deposit_address),
+				ForeignChain::Polkadot => qr_payload::<PolkadotInstance>(deposit_address),
+				ForeignChain::Bitcoin => qr_payload::<BitcoinInstance>(deposit_address),
+				ForeignChain::Arbitrum => qr_payload::<ArbitrumInstance>(deposit_address),
+				ForeignChain::Solana => qr_payload::<SolanaInstance>(deposit_address),
+			}
+		}
+
+		fn cf_in_flight_value() -> InFlightValue {
+			use pallet_cf_ingress_egress::PendingEgressAmounts;
+
+			InFlightValue {
+				in_swap: AssetMap::from_fn(pallet_cf_swapping::InFlightSwapAmounts::<Runtime>::get),
+				awaiting_egress: AssetMap::from_fn(|generic_asset| match generic_asset.into() {
+					ForeignChainAndAsset::Ethereum(asset) => PendingEgressAmounts::<Runtime, EthereumInstance>::get(asset),
+					ForeignChainAndAsset::Polkadot(asset) => PendingEgressAmounts::<Runtime, PolkadotInstance>::get(asset),
+					ForeignChainAndAsset::Bitcoin(asset) => PendingEgressAmounts::<Runtime, BitcoinInstance>::get(asset).into(),
+					ForeignChainAndAsset::Arbitrum(asset) => PendingEgressAmounts::<Runtime, ArbitrumInstance>::get(asset),
+					ForeignChainAndAsset::Solana(asset) => PendingEgressAmounts::<Runtime, SolanaInstance>::get(asset).into(),
+				}),
+			}
+		}
+
+		fn cf_accounting_reconciliation() -> AccountingReconciliation {
+			use pallet_cf_ingress_egress::{AssetAccounting, AssetAccountingTotals};
+
+			fn into_asset_amounts<A: Into<AssetAmount>>(
+				totals: AssetAccounting<A>,
+			) -> AssetAccounting<AssetAmount> {
+				AssetAccounting {
+					deposited: totals.deposited.into(),
+					egressed: totals.egressed.into(),
+					fees_withheld: totals.fees_withheld.into(),
+					rejected: totals.rejected.into(),
+				}
+			}
+
+			let totals = AssetMap::from_fn(|generic_asset| match generic_asset.into() {
+				ForeignChainAndAsset::Ethereum(asset) => into_asset_amounts(AssetAccountingTotals::<Runtime, EthereumInstance>::get(asset)),
+				ForeignChainAndAsset::Polkadot(asset) => into_asset_amounts(AssetAccountingTotals::<Runtime, PolkadotInstance>::get(asset)),
+				ForeignChainAndAsset::Bitcoin(asset) => into_asset_amounts(AssetAccountingTotals::<Runtime, BitcoinInstance>::get(asset)),
+				ForeignChainAndAsset::Arbitrum(asset) => into_asset_amounts(AssetAccountingTotals::<Runtime, ArbitrumInstance>::get(asset)),
+				ForeignChainAndAsset::Solana(asset) => into_asset_amounts(AssetAccountingTotals::<Runtime, SolanaInstance>::get(asset)),
+			});
+
+			AccountingReconciliation {
+				deposited: totals.clone().map(|totals| totals.deposited),
+				egressed: totals.clone().map(|totals| totals.egressed),
+				fees_withheld: totals.clone().map(|totals| totals.fees_withheld),
+				rejected: totals.map(|totals| totals.rejected),
+				awaiting_egress: Self::cf_in_flight_value().awaiting_egress,
+			}
+		}
+
+		fn cf_chain_wind_down_progress(chain: ForeignChain) -> Option<ChainWindDownProgress> {
+			use pallet_cf_ingress_egress::{
+				ChainWindDown, DepositChannelLookup, FetchOrTransfer, ScheduledEgressCcm,
+				ScheduledEgressFetchOrTransfer,
+			};
+
+			fn progress_of<I: 'static>() -> Option<ChainWindDownProgress>
+			where
+				Runtime: pallet_cf_ingress_egress::Config<I>,
+			{
+				let status = ChainWindDown::<Runtime, I>::get()?;
+				let scheduled_transfers = ScheduledEgressFetchOrTransfer::<Runtime, I>::get()
+					.into_iter()
+					.filter(|request| matches!(request, FetchOrTransfer::Transfer { .. }))
+					.count();
+				Some(ChainWindDownProgress {
+					started_at: status.started_at,
+					grace_period_ends_at: status.grace_period_ends_at,
+					completed: status.completed,
+					open_channels: DepositChannelLookup::<Runtime, I>::iter_keys().count() as u32,
+					scheduled_egresses: (scheduled_transfers +
+						ScheduledEgressCcm::<Runtime, I>::decode_len().unwrap_or_default())
+						as u32,
+				})
+			}
+
+			match chain {
+				ForeignChain::Ethereum => progress_of::<EthereumInstance>(),
+				ForeignChain::Polkadot => progress_of::<PolkadotInstance>(),
+				ForeignChain::Bitcoin => progress_of::<BitcoinInstance>(),
+				ForeignChain::Arbitrum => progress_of::<ArbitrumInstance>(),
+				ForeignChain::Solana => progress_of::<SolanaInstance>(),
+			}
+		}
+
+		fn cf_estimate_refund_outcome(
+			swap_request_id: SwapRequestId,
+		) -> Result<pallet_cf_swapping::RefundOutcomeEstimate<AccountId>, DispatchErrorWithMessage> {
+			Swapping::estimate_refund_outcome(swap_request_id, Self::cf_egress_fee)
+				.map_err(Into::into)
+		}
 	}
 
 
@@ -2647,6 +3059,102 @@ impl_runtime_apis! {
 				Self::cf_validator_info(account_id)
 			}).collect()
 		}
+		fn cf_egress_throughput(chain: ForeignChain) -> EgressThroughput {
+			fn egress_throughput<I: 'static>() -> EgressThroughput
+				where Runtime: pallet_cf_ingress_egress::Config<I>
+			{
+				type Limits<I> = <Runtime as pallet_cf_ingress_egress::Config<I>>::FetchesTransfersLimitProvider;
+
+				let last_egress = pallet_cf_ingress_egress::LastEgressUsage::<Runtime, I>::get();
+				let (scheduled_fetches, scheduled_transfers) = pallet_cf_ingress_egress::ScheduledEgressFetchOrTransfer::<Runtime, I>::get()
+					.iter()
+					.fold((0u32, 0u32), |(fetches, transfers), request| match request {
+						pallet_cf_ingress_egress::FetchOrTransfer::Fetch { .. } => (fetches + 1, transfers),
+						pallet_cf_ingress_egress::FetchOrTransfer::Transfer { .. } => (fetches, transfers + 1),
+					});
+
+				EgressThroughput {
+					transfers_limit: Limits::<I>::maybe_transfers_limit().map(|limit| limit as u32),
+					fetches_limit: Limits::<I>::maybe_fetches_limit().map(|limit| limit as u32),
+					ccms_limit: Limits::<I>::maybe_ccm_limit().map(|limit| limit as u32),
+					last_egress_block: last_egress.block_number,
+					transfers_sent: last_egress.transfers,
+					fetches_sent: last_egress.fetches,
+					ccms_sent: last_egress.ccms,
+					scheduled_transfers,
+					scheduled_fetches,
+					scheduled_ccms: pallet_cf_ingress_egress::ScheduledEgressCcm::<Runtime, I>::decode_len().unwrap_or_default() as u32,
+				}
+			}
+
+			match chain {
+				ForeignChain::Ethereum => egress_throughput::<EthereumInstance>(),
+				ForeignChain::Polkadot => egress_throughput::<PolkadotInstance>(),
+				ForeignChain::Bitcoin => egress_throughput::<BitcoinInstance>(),
+				ForeignChain::Arbitrum => egress_throughput::<ArbitrumInstance>(),
+				ForeignChain::Solana => egress_throughput::<SolanaInstance>(),
+			}
+		}
+		fn cf_failed_calls(chain: ForeignChain) -> FailedCalls {
+			fn failed_calls<I: 'static>(chain: ForeignChain) -> FailedCalls
+				where Runtime: pallet_cf_ingress_egress::Config<I> + pallet_cf_broadcast::Config<I>
+			{
+				use cf_chains::ApiCall;
+
+				let current_epoch = <Runtime as Chainflip>::EpochInfo::epoch_index();
+				let mut count_per_epoch = Vec::new();
+				let mut calls = Vec::new();
+
+				for (stored_in_epoch, failed_calls) in pallet_cf_ingress_egress::FailedForeignChainCalls::<Runtime, I>::iter() {
+					count_per_epoch.push((stored_in_epoch, failed_calls.len() as u32));
+					calls.extend(failed_calls.into_iter().map(|call| FailedCallInfo {
+						broadcast_id: call.broadcast_id,
+						original_epoch: call.original_epoch,
+						stored_in_epoch,
+						is_signed: pallet_cf_broadcast::PendingApiCalls::<Runtime, I>::get(call.broadcast_id)
+							.is_some_and(|api_call| api_call.is_signed()),
+						epochs_until_expiry: call.epochs_until_expiry(current_epoch),
+					}));
+				}
+				count_per_epoch.sort();
+
+				FailedCalls { chain, count_per_epoch, calls }
+			}
+
+			match chain {
+				ForeignChain::Ethereum => failed_calls::<EthereumInstance>(chain),
+				ForeignChain::Polkadot => failed_calls::<PolkadotInstance>(chain),
+				ForeignChain::Bitcoin => failed_calls::<BitcoinInstance>(chain),
+				ForeignChain::Arbitrum => failed_calls::<ArbitrumInstance>(chain),
+				ForeignChain::Solana => failed_calls::<SolanaInstance>(chain),
+			}
+		}
+
+		fn cf_broadcast_budget_usage(chain: ForeignChain) -> BroadcastBudgetUsage {
+			fn broadcast_budget_usage<I: 'static>() -> BroadcastBudgetUsage
+				where Runtime: pallet_cf_ingress_egress::Config<I>
+			{
+				let settings = pallet_cf_ingress_egress::BroadcastBudget::<Runtime, I>::get();
+				let last_usage = pallet_cf_ingress_egress::LastBroadcastUsage::<Runtime, I>::get();
+
+				BroadcastBudgetUsage {
+					per_block_limit: settings.map(|settings| settings.per_block),
+					max_carry_over: settings.map(|settings| settings.max_carry_over),
+					carried_over: pallet_cf_ingress_egress::CarriedOverBroadcastBudget::<Runtime, I>::get(),
+					last_broadcast_block: last_usage.block_number,
+					broadcasts_initiated: last_usage.broadcasts,
+					budget_exhausted: last_usage.budget_exhausted,
+				}
+			}
+
+			match chain {
+				ForeignChain::Ethereum => broadcast_budget_usage::<EthereumInstance>(),
+				ForeignChain::Polkadot => broadcast_budget_usage::<PolkadotInstance>(),
+				ForeignChain::Bitcoin => broadcast_budget_usage::<BitcoinInstance>(),
+				ForeignChain::Arbitrum => broadcast_budget_usage::<ArbitrumInstance>(),
+				ForeignChain::Solana => broadcast_budget_usage::<SolanaInstance>(),
+			}
+		}
 	}
 
 	// END custom runtime APIs
@@ -3,7 +3,7 @@ use cf_chains::{
 	dot::PolkadotAccountId,
 	sol::{api::DurableNonceAndAccount, SolAddress, SolSignature},
 };
-use cf_primitives::AssetAmount;
+use cf_primitives::{AssetAmount, BroadcastId, EpochIndex, ForeignChain};
 use codec::{Decode, Encode};
 use frame_support::sp_runtime::AccountId32;
 use pallet_cf_asset_balances::VaultImbalance;
@@ -125,6 +125,37 @@ pub struct ActivateKeysBroadcastIds {
 	pub solana: (Option<u32>, Option<SolSignature>),
 }
 
+/// The per-block egress limits of a chain, what the most recent egress consumed of them, and the
+/// egresses still waiting to be sent.
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct EgressThroughput {
+	pub transfers_limit: Option<u32>,
+	pub fetches_limit: Option<u32>,
+	pub ccms_limit: Option<u32>,
+	/// The State Chain block of the most recent egress.
+	pub last_egress_block: u32,
+	pub transfers_sent: u32,
+	pub fetches_sent: u32,
+	pub ccms_sent: u32,
+	pub scheduled_transfers: u32,
+	pub scheduled_fetches: u32,
+	pub scheduled_ccms: u32,
+}
+
+/// The broadcast budget of a chain's ingress-egress pallet and the broadcasts it initiated most
+/// recently.
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct BroadcastBudgetUsage {
+	/// `None` if the number of broadcasts per block is not limited.
+	pub per_block_limit: Option<u32>,
+	pub max_carry_over: Option<u32>,
+	pub carried_over: u32,
+	/// The State Chain block in which broadcasts were most recently initiated or held back.
+	pub last_broadcast_block: u32,
+	pub broadcasts_initiated: u32,
+	pub budget_exhausted: bool,
+}
+
 #[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
 pub struct MonitoringDataV2 {
 	pub external_chains_height: ExternalChainsBlockHeight,
@@ -189,8 +220,33 @@ impl From<MonitoringData> for MonitoringDataV2 {
 		}
 	}
 }
+
+/// A call to an external chain that failed to be broadcast, and which the user can broadcast
+/// themselves.
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct FailedCallInfo {
+	pub broadcast_id: BroadcastId,
+	/// The epoch the call originally failed in.
+	pub original_epoch: EpochIndex,
+	/// The epoch under which the call is currently stored, i.e. the epoch it was last signed for.
+	pub stored_in_epoch: EpochIndex,
+	/// Whether a threshold signature for the call is available.
+	pub is_signed: bool,
+	/// The number of epoch transitions left before the call expires.
+	pub epochs_until_expiry: EpochIndex,
+}
+
+/// The failed calls of a chain that are waiting to be broadcast by the user.
+#[derive(Serialize, Deserialize, Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct FailedCalls {
+	pub chain: ForeignChain,
+	/// The number of failed calls stored under each epoch.
+	pub count_per_epoch: Vec<(EpochIndex, u32)>,
+	pub calls: Vec<FailedCallInfo>,
+}
+
 decl_runtime_apis!(
-	#[api_version(2)]
+	#[api_version(5)]
 	pub trait MonitoringRuntimeApi {
 		fn cf_authorities() -> AuthoritiesInfo;
 		fn cf_external_chains_block_height() -> ExternalChainsBlockHeight;
@@ -215,5 +271,14 @@ decl_runtime_apis!(
 		fn cf_accounts_info(
 			accounts: BoundedVec<AccountId32, sp_core::ConstU32<10>>,
 		) -> Vec<ValidatorInfo>;
+		#[changed_in(3)]
+		fn cf_egress_throughput();
+		fn cf_egress_throughput(chain: ForeignChain) -> EgressThroughput;
+		#[changed_in(4)]
+		fn cf_failed_calls();
+		fn cf_failed_calls(chain: ForeignChain) -> FailedCalls;
+		#[changed_in(5)]
+		fn cf_broadcast_budget_usage();
+		fn cf_broadcast_budget_usage(chain: ForeignChain) -> BroadcastBudgetUsage;
 	}
 );
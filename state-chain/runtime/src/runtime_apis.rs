@@ -10,21 +10,21 @@ use cf_chains::{
 	VaultSwapExtraParametersEncoded,
 };
 use cf_primitives::{
-	AccountRole, AffiliateShortId, Affiliates, Asset, AssetAmount, BasisPoints, BlockNumber,
-	BroadcastId, DcaParameters, EpochIndex, FlipBalance, ForeignChain, GasAmount,
-	NetworkEnvironment, PrewitnessedDepositId, SemVer,
+	AccountRole, AffiliateShortId, Affiliates, Asset, AssetAmount, BasisPoints, Beneficiaries,
+	BlockNumber, BroadcastId, ChannelId, DcaParameters, EpochIndex, FlipBalance, ForeignChain,
+	GasAmount, NetworkEnvironment, PrewitnessedDepositId, SemVer, SwapRequestId, TrackingId,
 };
 use cf_traits::SwapLimits;
 use codec::{Decode, Encode};
 use core::{ops::Range, str};
 use frame_support::sp_runtime::AccountId32;
 use pallet_cf_governance::GovCallHash;
-pub use pallet_cf_ingress_egress::OwedAmount;
+pub use pallet_cf_ingress_egress::{DepositFailedReason, OwedAmount};
 use pallet_cf_pools::{
 	AskBidMap, PoolInfo, PoolLiquidity, PoolOrderbook, PoolOrders, PoolPriceV1, PoolPriceV2,
 	UnidirectionalPoolDepth,
 };
-use pallet_cf_swapping::SwapLegInfo;
+use pallet_cf_swapping::{FeeRateAndMinimum, NetworkFeeTotals, SwapLegInfo};
 use pallet_cf_witnesser::CallHash;
 use scale_info::{prelude::string::String, TypeInfo};
 use serde::{Deserialize, Serialize};
@@ -37,6 +37,10 @@ use sp_std::{
 
 type VanityName = Vec<u8>;
 
+/// The maximum number of scheduled swap legs returned by a single call to
+/// `cf_scheduled_swaps`. Larger page sizes are clamped to this value.
+pub const MAX_SCHEDULED_SWAPS_PAGE_SIZE: u32 = 500;
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize)]
 #[serde(tag = "chain")]
 pub enum VaultSwapDetails<BtcAddress> {
@@ -67,6 +71,32 @@ pub struct EvmVaultSwapDetails {
 	pub to: sp_core::H160,    // The vault address for either Ethereum or Arbitrum
 }
 
+/// The encoded swap parameters that need to be embedded in a vault swap transaction.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, TypeInfo, Serialize, Deserialize)]
+#[serde(tag = "chain")]
+pub enum VaultSwapParametersPayload {
+	/// The payload of the nulldata (OP_RETURN) output.
+	Bitcoin {
+		#[serde(with = "sp_core::bytes")]
+		nulldata_payload: Vec<u8>,
+	},
+	/// The `cfParameters` argument of the vault contract call.
+	Ethereum {
+		#[serde(with = "sp_core::bytes")]
+		cf_parameters: Vec<u8>,
+	},
+	/// The `cfParameters` argument of the vault contract call.
+	Arbitrum {
+		#[serde(with = "sp_core::bytes")]
+		cf_parameters: Vec<u8>,
+	},
+	/// The `cf_parameters` argument of the swap endpoint program instruction.
+	Solana {
+		#[serde(with = "sp_core::bytes")]
+		cf_parameters: Vec<u8>,
+	},
+}
+
 impl<BtcAddress> VaultSwapDetails<BtcAddress> {
 	pub fn ethereum(details: EvmVaultSwapDetails) -> Self {
 		VaultSwapDetails::Ethereum { details }
@@ -90,6 +120,44 @@ impl<BtcAddress> VaultSwapDetails<BtcAddress> {
 	}
 }
 
+/// Network fee related balances held by the swapping pallet, along with cumulative counters of
+/// the network fees taken.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct NetworkFeeAccounting {
+	/// USDC collected as network fees that has not yet been swapped into FLIP.
+	pub collected_network_fee: AssetAmount,
+	/// FLIP bought with network fees that has not yet been burned.
+	pub flip_to_burn: AssetAmount,
+	pub collected_rejected_funds: BTreeMap<Asset, AssetAmount>,
+	/// Cumulative network fees taken from swaps, in USDC.
+	pub network_fees_taken: NetworkFeeTotals,
+	/// Cumulative network fees taken from boosted deposits, in the deposited asset.
+	pub boost_network_fees_taken: BTreeMap<Asset, AssetAmount>,
+}
+
+/// A failed deposit, as recorded in the deposit failure history of the source chain.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct DepositFailure {
+	/// The block height on the source chain at which the deposit was witnessed.
+	pub block_height: u64,
+	pub reason: DepositFailedReason,
+	pub deposit_address: Option<ForeignChainAddress>,
+	/// The SCALE-encoded id of the deposit transaction, if known.
+	pub tx_id: Option<Vec<u8>>,
+	pub asset: Asset,
+	pub amount: AssetAmount,
+	/// The State Chain block at which the failure was recorded.
+	pub recorded_at: BlockNumber,
+}
+
+/// The stable numeric code of a [DepositFailedReason], with a human-readable description.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone, Serialize, Deserialize)]
+pub struct DepositFailedReasonCode {
+	pub code: u8,
+	pub name: String,
+	pub description: String,
+}
+
 #[derive(PartialEq, Eq, Clone, Encode, Decode, Copy, TypeInfo, Serialize, Deserialize)]
 pub enum BackupOrPassive {
 	Backup,
@@ -199,6 +267,86 @@ pub struct BrokerInfo {
 	pub bond: AssetAmount,
 }
 
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct BrokerAffiliateDetails {
+	pub short_id: AffiliateShortId,
+	pub account_id: AccountId32,
+	pub earned_fees: Vec<(Asset, AssetAmount)>,
+}
+
+/// Everything there is to know about a broker account, so that it can be fetched in a single
+/// call.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct BrokerDetails {
+	pub bond: AssetAmount,
+	pub earned_fees: Vec<(Asset, AssetAmount)>,
+	/// The number of deposit channels currently open by the broker, across all chains.
+	pub open_deposit_channels: u32,
+	pub private_channel_id: Option<ChannelId>,
+	pub btc_vault_deposit_address: Option<String>,
+	pub affiliates: Vec<BrokerAffiliateDetails>,
+	/// The addresses most recently withdrawn to, oldest first.
+	pub withdrawal_addresses: Vec<EncodedAddress>,
+}
+
+/// The number of deposit channels an account has open on a chain, and the maximum number it may
+/// have open there given its role.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct OpenChannelUsage {
+	pub chain: ForeignChain,
+	pub open_channels: u32,
+	pub max_channels: Option<u32>,
+}
+
+/// A payment URI for deposits into a channel, in the format used by wallets on the channel's
+/// chain, for rendering as a QR code.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct DepositChannelQrPayload {
+	pub uri: String,
+	pub asset: Asset,
+	/// The last block on the channel's chain at which deposits into the channel are witnessed.
+	pub expires_at: u64,
+}
+
+/// The value held by the protocol on behalf of users between deposit and egress.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct InFlightValue {
+	/// Held by swap requests: deposited and not yet swapped, or swapped and not yet scheduled for
+	/// egress.
+	pub in_swap: AssetMap<AssetAmount>,
+	/// Scheduled for egress and not yet broadcast.
+	pub awaiting_egress: AssetMap<AssetAmount>,
+}
+
+/// Cumulative totals of what each asset's deposits and egresses should have done to the vault
+/// balances, for reconciliation against the balances observed on each chain.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct AccountingReconciliation {
+	/// Finalised deposits that were accepted, before fees.
+	pub deposited: AssetMap<AssetAmount>,
+	/// Scheduled egresses, after fees.
+	pub egressed: AssetMap<AssetAmount>,
+	/// Ingress and egress fees withheld.
+	pub fees_withheld: AssetMap<AssetAmount>,
+	/// Finalised deposits that were rejected and scheduled to be refunded.
+	pub rejected: AssetMap<AssetAmount>,
+	/// Scheduled for egress and not yet broadcast, so not yet reflected in the vault balances.
+	pub awaiting_egress: AssetMap<AssetAmount>,
+}
+
+/// Progress of the wind-down of a chain.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize, Clone, Debug)]
+pub struct ChainWindDownProgress {
+	pub started_at: u32,
+	pub grace_period_ends_at: u32,
+	pub completed: bool,
+	/// Deposit channels that are still open. They expire as usual.
+	pub open_channels: u32,
+	/// Transfers and CCMs that are scheduled and not yet broadcast. Those still scheduled when
+	/// the grace period ends are abandoned.
+	pub scheduled_egresses: u32,
+}
+
 #[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Serialize, Deserialize)]
 pub struct CcmData {
 	pub gas_budget: GasAmount,
@@ -297,6 +445,37 @@ pub struct TransactionScreeningEvents {
 	pub btc_events: Vec<BrokerRejectionEventFor<cf_chains::Bitcoin>>,
 }
 
+/// A transaction that a broker has marked for rejection.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct MarkedTransaction {
+	/// The SCALE-encoded transaction id.
+	pub tx_id: Vec<u8>,
+	/// Whether the transaction has been prewitnessed since it was marked.
+	pub prewitnessed: bool,
+	/// The State Chain block at which the report expires, unless the transaction has been
+	/// prewitnessed by then.
+	pub expires_at: Option<BlockNumber>,
+}
+
+/// A transaction that is scheduled to be rejected, or whose rejection failed.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone)]
+pub struct TransactionRejection {
+	/// The SCALE-encoded transaction id, if known.
+	pub tx_id: Option<Vec<u8>>,
+	pub asset: Asset,
+	pub amount: AssetAmount,
+	pub refund_address: Option<ForeignChainAddress>,
+}
+
+/// The state of transaction rejections on a chain. Scheduled and failed rejections are not
+/// attributed to a broker.
+#[derive(Encode, Decode, Eq, PartialEq, TypeInfo, Debug, Clone, Default)]
+pub struct TransactionRejections {
+	pub marked_for_rejection: Vec<MarkedTransaction>,
+	pub scheduled_for_rejection: Vec<TransactionRejection>,
+	pub failed_rejections: Vec<TransactionRejection>,
+}
+
 // READ THIS BEFORE UPDATING THIS TRAIT:
 //
 // ## When changing an existing method:
@@ -313,7 +492,7 @@ pub struct TransactionScreeningEvents {
 //  - Handle the dummy method gracefully in the custom rpc implementation using
 //    runtime_api().api_version().
 decl_runtime_apis!(
-	#[api_version(3)]
+	#[api_version(4)]
 	pub trait CustomRuntimeApi {
 		/// Returns true if the current phase is the auction phase.
 		fn cf_is_auction_phase() -> bool;
@@ -410,10 +589,21 @@ decl_runtime_apis!(
 			quote_asset: Asset,
 			side: Side,
 		) -> Vec<AssetAmount>;
+		#[changed_in(4)]
 		fn cf_scheduled_swaps(
 			base_asset: Asset,
 			quote_asset: Asset,
 		) -> Vec<(SwapLegInfo, BlockNumber)>;
+		/// Returns a page of the scheduled swap legs involving `base_asset`, ordered by execution
+		/// block. At most `MAX_SCHEDULED_SWAPS_PAGE_SIZE` legs are returned. If `estimate_only` is
+		/// set, the swaps are not simulated and amounts are estimated using the pool price only.
+		fn cf_scheduled_swaps(
+			base_asset: Asset,
+			quote_asset: Asset,
+			page: u32,
+			page_size: u32,
+			estimate_only: bool,
+		) -> Vec<(SwapLegInfo, BlockNumber)>;
 		fn cf_liquidity_provider_info(account_id: AccountId32) -> LiquidityProviderInfo;
 		fn cf_broker_info(account_id: AccountId32) -> BrokerInfo;
 		fn cf_account_role(account_id: AccountId32) -> Option<AccountRole>;
@@ -462,13 +652,138 @@ decl_runtime_apis!(
 			affiliate_fees: Affiliates<AccountId32>,
 			dca_parameters: Option<DcaParameters>,
 		) -> Result<VaultSwapDetails<String>, DispatchErrorWithMessage>;
+		#[changed_in(4)]
+		fn cf_encode_vault_swap_parameters();
+		/// Returns the swap parameters, encoded exactly as they need to be embedded in a vault
+		/// swap transaction on the source chain.
+		fn cf_encode_vault_swap_parameters(
+			broker: AccountId32,
+			source_chain: ForeignChain,
+			destination_asset: Asset,
+			destination_address: EncodedAddress,
+			broker_commission: BasisPoints,
+			extra_parameters: VaultSwapExtraParametersEncoded,
+			channel_metadata: Option<CcmChannelMetadata>,
+			boost_fee: BasisPoints,
+			affiliate_fees: Affiliates<AccountId32>,
+			dca_parameters: Option<DcaParameters>,
+			tracking_id: Option<TrackingId>,
+		) -> Result<VaultSwapParametersPayload, DispatchErrorWithMessage>;
+		#[changed_in(4)]
+		fn cf_deposit_failures();
+		/// Returns the recent deposit failures on the given chain, optionally filtered by deposit
+		/// address and/or SCALE-encoded transaction id.
+		fn cf_deposit_failures(
+			chain: ForeignChain,
+			deposit_address: Option<EncodedAddress>,
+			tx_id: Option<Vec<u8>>,
+		) -> Result<Vec<DepositFailure>, DispatchErrorWithMessage>;
 		fn cf_get_open_deposit_channels(account_id: Option<AccountId32>) -> ChainAccounts;
 		fn cf_transaction_screening_events() -> TransactionScreeningEvents;
 		fn cf_get_affiliates(broker: AccountId32) -> Vec<(AffiliateShortId, AccountId32)>;
+		#[changed_in(4)]
+		fn cf_network_fee_accounting();
+		/// Returns the network fees collected and not yet burned, and the cumulative network fees
+		/// taken from swaps and boosted deposits.
+		fn cf_network_fee_accounting() -> NetworkFeeAccounting;
+		#[changed_in(4)]
+		fn cf_transaction_rejections();
+		/// Returns a page of the transactions marked for rejection by the broker, and of the
+		/// scheduled and failed rejections on the given chain. The same page is applied to each
+		/// list.
+		fn cf_transaction_rejections(
+			chain: ForeignChain,
+			broker: AccountId32,
+			page: u32,
+			page_size: u32,
+		) -> TransactionRejections;
+		#[changed_in(4)]
+		fn cf_effective_network_fee();
+		/// Returns the network fee rate and minimum fee that would be applied to a swap between
+		/// the given assets. Internal swaps, such as ingress/egress fee swaps, are not subject to
+		/// the minimum fee.
+		fn cf_effective_network_fee(
+			input_asset: Asset,
+			output_asset: Asset,
+			is_internal: bool,
+		) -> FeeRateAndMinimum;
+		#[changed_in(4)]
+		fn cf_recommended_witness_safety_margin();
+		/// Returns the witness safety margin recommended for the given chain based on recently
+		/// observed reorgs, or `None` if no reorgs have been observed.
+		fn cf_recommended_witness_safety_margin(chain: ForeignChain) -> Option<u64>;
+		#[changed_in(4)]
+		fn cf_private_channel_owner();
+		/// Returns the broker that owns the given private BTC channel, if any.
+		fn cf_private_channel_owner(channel_id: ChannelId) -> Option<AccountId32>;
+		#[changed_in(4)]
+		fn cf_decode_and_validate_address();
+		/// Decodes the address and checks that it is valid for the given asset, in the same way as
+		/// destination addresses of swaps are checked. The error distinguishes addresses that
+		/// can't be decoded from addresses that belong to a different chain.
+		fn cf_decode_and_validate_address(
+			encoded_address: EncodedAddress,
+			asset: Asset,
+		) -> Result<ForeignChainAddress, DispatchErrorWithMessage>;
+		#[changed_in(4)]
+		fn cf_encode_address();
+		/// Encodes the address in the format used by extrinsics and events.
+		fn cf_encode_address(address: ForeignChainAddress) -> EncodedAddress;
+		#[changed_in(4)]
+		fn cf_estimate_broker_fees();
+		/// Estimates the network fee and the fee credited to each beneficiary for a swap of
+		/// `input_amount` at current prices. All amounts are denominated in USDC.
+		fn cf_estimate_broker_fees(
+			input_asset: Asset,
+			output_asset: Asset,
+			input_amount: AssetAmount,
+			broker_fees: Beneficiaries<AccountId32>,
+		) -> Result<pallet_cf_swapping::BrokerFeeEstimate<AccountId32>, DispatchErrorWithMessage>;
+		#[changed_in(4)]
+		fn cf_deposit_failed_reason_codes();
+		/// Returns the code of every reason a deposit can fail with. Codes are stable across
+		/// runtime versions.
+		fn cf_deposit_failed_reason_codes() -> Vec<DepositFailedReasonCode>;
+		#[changed_in(4)]
+		fn cf_broker_details();
+		/// Returns the channels, affiliates, balances and withdrawal addresses of the broker.
+		fn cf_broker_details(account_id: AccountId32) -> BrokerDetails;
+		#[changed_in(4)]
+		fn cf_open_channel_usage();
+		/// Returns the number of deposit channels the account has open on each chain, and the
+		/// maximum number it may have open.
+		fn cf_open_channel_usage(account_id: AccountId32) -> Vec<OpenChannelUsage>;
+		#[changed_in(4)]
+		fn cf_deposit_channel_qr_payload();
+		/// Returns the payment URI for deposits into the open channel at the given address.
+		fn cf_deposit_channel_qr_payload(
+			deposit_address: EncodedAddress,
+		) -> Result<DepositChannelQrPayload, DispatchErrorWithMessage>;
+		#[changed_in(4)]
+		fn cf_in_flight_value();
+		/// Returns the value per asset held on behalf of users between deposit and egress.
+		fn cf_in_flight_value() -> InFlightValue;
+		#[changed_in(4)]
+		fn cf_accounting_reconciliation();
+		/// Returns the cumulative deposit, egress, fee and rejection totals per asset, to be
+		/// reconciled against the vault balances observed on each chain.
+		fn cf_accounting_reconciliation() -> AccountingReconciliation;
+		#[changed_in(4)]
+		fn cf_chain_wind_down_progress();
+		/// Returns the progress of the chain's wind-down, if it has been started.
+		fn cf_chain_wind_down_progress(chain: ForeignChain) -> Option<ChainWindDownProgress>;
+		#[changed_in(4)]
+		fn cf_estimate_refund_outcome();
+		/// Estimates the refunded amount, the egress fee and the destination of the refund if the
+		/// swap request were refunded now. The egress fee is estimated at current prices.
+		fn cf_estimate_refund_outcome(
+			swap_request_id: SwapRequestId,
+		) -> Result<pallet_cf_swapping::RefundOutcomeEstimate<AccountId32>, DispatchErrorWithMessage>;
 	}
 );
 
 decl_runtime_apis!(
+	#[api_version(3)]
 	pub trait ElectoralRuntimeApi<Instance: 'static> {
 		/// Returns SCALE encoded `Option<ElectoralDataFor<state_chain_runtime::Runtime,
 		/// Instance>>`
@@ -477,5 +792,17 @@ decl_runtime_apis!(
 		/// Returns SCALE encoded `BTreeSet<ElectionIdentifierOf<<state_chain_runtime::Runtime as
 		/// pallet_cf_elections::Config<Instance>>::ElectoralSystem>>`
 		fn cf_filter_votes(account_id: AccountId32, proposed_votes: Vec<u8>) -> Vec<u8>;
+
+		#[changed_in(2)]
+		fn cf_consensus_archive();
+		/// Returns SCALE encoded `Vec<ArchivedConsensusFor<state_chain_runtime::Runtime,
+		/// Instance>>`
+		fn cf_consensus_archive() -> Vec<u8>;
+
+		#[changed_in(3)]
+		fn cf_vote_storage_usage();
+		/// Returns SCALE encoded `VoteStorageUsage`, the estimated storage consumed by the votes of
+		/// all current elections.
+		fn cf_vote_storage_usage() -> Vec<u8>;
 	}
 );
@@ -8,7 +8,9 @@ pub mod safe_mode;
 pub use safe_mode::*;
 mod swapping;
 
-pub use swapping::{SwapRequestHandler, SwapRequestType, SwapRequestTypeEncoded, SwapType};
+pub use swapping::{
+	OnChainSwapHandler, SwapRequestHandler, SwapRequestType, SwapRequestTypeEncoded, SwapType,
+};
 
 pub mod mocks;
 pub mod offence_reporting;
@@ -17,18 +19,20 @@ use core::fmt::Debug;
 
 pub use async_result::AsyncResult;
 
+use cf_amm::math::Price;
 use cf_chains::{
-	address::ForeignChainAddress,
+	address::{EncodedAddress, ForeignChainAddress},
 	assets::any::AssetMap,
+	ccm_checker::CcmValidityError,
 	sol::{SolAddress, SolHash},
 	ApiCall, CcmChannelMetadata, CcmDepositMetadata, Chain, ChainCrypto,
-	ChannelRefundParametersDecoded, Ethereum,
+	ChannelRefundParametersDecoded, EgressMemo, Ethereum,
 };
 use cf_primitives::{
 	AccountRole, AffiliateShortId, Asset, AssetAmount, AuthorityCount, BasisPoints, Beneficiaries,
-	BlockNumber, BroadcastId, ChannelId, DcaParameters, Ed25519PublicKey, EgressCounter, EgressId,
-	EpochIndex, FlipBalance, ForeignChain, GasAmount, Ipv6Addr, NetworkEnvironment, SemVer,
-	ThresholdSignatureRequestId,
+	BlockNumber, BroadcastId, ChannelId, DcaParameters, Ed25519PublicKey, EgressId, EpochIndex,
+	FlipBalance, ForeignChain, GasAmount, Ipv6Addr, NetworkEnvironment, SemVer, SwapRequestId,
+	ThresholdSignatureRequestId, TrackingId,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
@@ -714,6 +718,9 @@ pub trait FeePayment {
 
 	/// Burns an amount of tokens, if the account has enough. Otherwise fails.
 	fn try_burn_fee(account_id: &Self::AccountId, amount: Self::Amount) -> DispatchResult;
+
+	/// Mints back a fee that was burned for a service that could not be provided.
+	fn refund_fee(account_id: &Self::AccountId, amount: Self::Amount);
 }
 
 /// Provides information about on-chain funds.
@@ -726,6 +733,9 @@ pub trait FundingInfo {
 	fn total_onchain_funds() -> Self::Balance;
 }
 
+/// The maximum number of rejection requests per chain that are visited when a broker deregisters.
+pub const MAX_REJECTION_REQUESTS_EXPIRED_ON_DEREGISTRATION: u32 = 100;
+
 /// Allow pallets to open and expire deposit addresses.
 pub trait DepositApi<C: Chain> {
 	type AccountId;
@@ -750,7 +760,39 @@ pub trait DepositApi<C: Chain> {
 		boost_fee: BasisPoints,
 		refund_params: Option<ChannelRefundParametersDecoded>,
 		dca_params: Option<DcaParameters>,
+		tracking_id: Option<TrackingId>,
+		egress_memo: Option<EgressMemo>,
 	) -> Result<(ChannelId, ForeignChainAddress, C::ChainBlockNumber, Self::Amount), DispatchError>;
+
+	/// Derives the deposit address of a broker's private channel for the current vault key.
+	fn private_channel_deposit_address(
+		source_asset: C::ChainAsset,
+		channel_id: ChannelId,
+	) -> Result<ForeignChainAddress, DispatchError>;
+
+	/// Whether a channel request failed only because it has been queued to be retried. The channel
+	/// opening fee has been paid and the requester is notified once the channel is ready, so the
+	/// error should not be propagated.
+	fn is_deferred_channel_open(_error: &DispatchError) -> bool {
+		false
+	}
+
+	/// Whether the account owns any deposit channels that have not been recycled yet. Expired
+	/// channels can still receive deposits until they are recycled.
+	fn has_unrecycled_deposit_channels(owner: &Self::AccountId) -> bool;
+
+	/// Removes the broker's requests to reject transactions that have not been witnessed yet.
+	/// Requests for transactions that have already been prewitnessed are kept, since the deposit
+	/// still has to be rejected once it is finalised.
+	///
+	/// At most [MAX_REJECTION_REQUESTS_EXPIRED_ON_DEREGISTRATION] requests are visited per chain.
+	/// Any remaining requests expire at their regular expiry block.
+	fn expire_rejection_requests(broker_id: &Self::AccountId);
+
+	/// Whether the deposit that started the swap request was boosted and then marked for
+	/// rejection before the swap request completed. The rejection is considered handled, so
+	/// the swap request is no longer tracked afterwards.
+	fn take_swap_request_rejection(swap_request_id: SwapRequestId) -> bool;
 }
 
 pub trait AccountRoleRegistry<T: frame_system::Config> {
@@ -863,37 +905,76 @@ pub struct ScheduledEgressDetails<C: Chain> {
 
 impl<C: Chain + Get<ForeignChain>> Default for ScheduledEgressDetails<C> {
 	fn default() -> Self {
-		Self::new(Default::default(), Default::default(), Default::default())
+		Self::new(
+			(<C as Get<ForeignChain>>::get(), Default::default(), Default::default()),
+			Default::default(),
+			Default::default(),
+		)
 	}
 }
 
-impl<C: Chain + Get<ForeignChain>> ScheduledEgressDetails<C> {
+impl<C: Chain> ScheduledEgressDetails<C> {
 	pub fn new(
-		id_counter: EgressCounter,
+		egress_id: EgressId,
 		egress_amount: C::ChainAmount,
 		fee_withheld: C::ChainAmount,
 	) -> Self {
-		Self {
-			egress_id: (<C as Get<ForeignChain>>::get(), id_counter),
-			egress_amount,
-			fee_withheld,
-		}
+		Self { egress_id, egress_amount, fee_withheld }
 	}
 }
 
+/// Why funds leave the protocol, so that egresses of different kinds can be told apart.
+#[derive(
+	Copy,
+	Clone,
+	RuntimeDebug,
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+pub enum EgressOrigin {
+	/// The output of a swap.
+	Swap,
+	/// Funds returned to the sender of a swap or of a deposit that could not be processed.
+	Refund,
+	/// A withdrawal of an account's free balance.
+	Withdrawal,
+	/// Protocol fee handling, such as burning the network fee or reimbursing transaction fees.
+	FeeManagement,
+	/// Consolidation of the vault's funds.
+	Consolidation,
+	/// The return of rejected deposits or of funds collected from rejected swaps.
+	Rejection,
+}
+
 /// API that allows other pallets to Egress assets out of the State Chain.
 pub trait EgressApi<C: Chain> {
 	type EgressError: Into<DispatchError>;
 
 	/// Schedule the egress of an asset to a destination address.
 	///
-	/// May take a fee and will return an error if egress cannot be scheduled.
+	/// May take a fee and will return an error if egress cannot be scheduled. An `egress_memo` is
+	/// only accepted for transfers on chains that support memos, and never for CCMs. The `origin`
+	/// is carried through to the broadcast events and egress totals.
 	fn schedule_egress(
 		asset: C::ChainAsset,
 		amount: C::ChainAmount,
 		destination_address: C::ChainAccount,
 		maybe_ccm_deposit_metadata: Option<CcmDepositMetadata>,
+		egress_memo: Option<EgressMemo>,
+		origin: EgressOrigin,
 	) -> Result<ScheduledEgressDetails<C>, Self::EgressError>;
+
+	/// Whether new swaps to the destination may still be requested. This is not the case once
+	/// the destination chain is being wound down.
+	fn accepts_new_destination(_destination_address: &C::ChainAccount) -> bool {
+		true
+	}
 }
 
 pub trait VaultKeyWitnessedHandler<C: Chain> {
@@ -926,6 +1007,33 @@ pub trait OnDeposit<C: Chain> {
 	fn on_deposit_made(_deposit_details: C::DepositDetails) {}
 }
 
+/// Allows other pallets to react to executed swaps.
+pub trait OnSwapCompleted<AccountId> {
+	/// Called for every swap, or DCA chunk, executed on behalf of a user. `accounts` are the
+	/// broker fee beneficiaries of the swap request, or the account that requested an on-chain
+	/// swap.
+	fn on_swap_completed(
+		swap_request_id: SwapRequestId,
+		accounts: &[AccountId],
+		input_asset: Asset,
+		input_amount: AssetAmount,
+		output_asset: Asset,
+		output_amount: AssetAmount,
+	);
+}
+
+impl<AccountId> OnSwapCompleted<AccountId> for () {
+	fn on_swap_completed(
+		_swap_request_id: SwapRequestId,
+		_accounts: &[AccountId],
+		_input_asset: Asset,
+		_input_amount: AssetAmount,
+		_output_asset: Asset,
+		_output_amount: AssetAmount,
+	) {
+	}
+}
+
 pub trait NetworkEnvironmentProvider {
 	fn get_network_environment() -> NetworkEnvironment;
 }
@@ -997,6 +1105,39 @@ pub trait AssetWithholding {
 	fn withhold_assets(asset: Asset, amount: AssetAmount);
 }
 
+/// Assesses the risk of a pre-witnessed deposit, for example based on screening verdicts or on the
+/// deposit details. Used to decide whether the deposit can be boosted.
+pub trait DepositRiskScorer<C: Chain> {
+	/// Returns the risk score of the deposit in basis points, where higher means riskier.
+	fn risk_score(
+		asset: C::ChainAsset,
+		amount: C::ChainAmount,
+		deposit_details: &C::DepositDetails,
+		source_address: Option<&ForeignChainAddress>,
+	) -> BasisPoints;
+}
+
+impl<C: Chain> DepositRiskScorer<C> for () {
+	fn risk_score(
+		_asset: C::ChainAsset,
+		_amount: C::ChainAmount,
+		_deposit_details: &C::DepositDetails,
+		_source_address: Option<&ForeignChainAddress>,
+	) -> BasisPoints {
+		0
+	}
+}
+
+/// Called once the wind-down of a chain has completed, for example to stop the chain's
+/// elections.
+pub trait OnChainWoundDown {
+	fn on_chain_wound_down();
+}
+
+impl OnChainWoundDown for () {
+	fn on_chain_wound_down() {}
+}
+
 pub trait FetchesTransfersLimitProvider {
 	fn maybe_transfers_limit() -> Option<usize> {
 		None
@@ -1025,9 +1166,29 @@ pub trait SwapLimitsProvider {
 	fn get_swap_limits() -> SwapLimits;
 	fn validate_dca_params(dca_params: &DcaParameters) -> Result<(), DispatchError>;
 	fn validate_refund_params(retry_duration: BlockNumber) -> Result<(), DispatchError>;
+	/// Checks that the min price is not so far above the market price that the swap is unlikely
+	/// to ever be executed.
+	fn validate_min_price(
+		source_asset: Asset,
+		destination_asset: Asset,
+		min_price: Price,
+	) -> Result<(), DispatchError>;
 	fn validate_broker_fees(
 		broker_fees: &Beneficiaries<Self::AccountId>,
 	) -> Result<(), DispatchError>;
+	/// Checks the CCM against the limits configured for the destination chain, if any.
+	fn validate_ccm_limits(
+		ccm: &CcmChannelMetadata,
+		destination_chain: ForeignChain,
+	) -> Result<(), CcmValidityError>;
+	/// Checks that the broker allows its CCM swaps to call the destination address.
+	fn validate_ccm_destination(
+		broker_id: &Self::AccountId,
+		destination_address: &ForeignChainAddress,
+	) -> Result<(), DispatchError>;
+	/// Checks that new swaps to the destination's chain are still accepted.
+	fn validate_destination(destination_address: &ForeignChainAddress)
+		-> Result<(), DispatchError>;
 }
 
 /// API for interacting with the asset-balance pallet.
@@ -1055,6 +1216,14 @@ pub trait BalanceApi {
 
 	/// Returns the balance of the given account for the given asset.
 	fn get_balance(who: &Self::AccountId, asset: Asset) -> AssetAmount;
+
+	/// Records the destination of a withdrawal in the account's withdrawal history. Fails if the
+	/// account has address poisoning protection enabled and the destination looks like, but is
+	/// not, an address it has withdrawn to before.
+	fn check_withdrawal_address(
+		who: &Self::AccountId,
+		destination: &EncodedAddress,
+	) -> DispatchResult;
 }
 
 pub trait IngressSink {
@@ -1082,6 +1251,18 @@ pub trait IngressSource {
 		asset: <Self::Chain as Chain>::ChainAsset,
 		close_block: <Self::Chain as Chain>::ChainBlockNumber,
 	) -> DispatchResult;
+
+	/// Brings forward the close block of a channel that is already open.
+	fn close_channel(
+		channel: <Self::Chain as Chain>::ChainAccount,
+		close_block: <Self::Chain as Chain>::ChainBlockNumber,
+	) -> DispatchResult;
+
+	/// Pushes back the close block of a channel that is already open.
+	fn extend_channel(
+		channel: <Self::Chain as Chain>::ChainAccount,
+		close_block: <Self::Chain as Chain>::ChainBlockNumber,
+	) -> DispatchResult;
 }
 pub struct DummyIngressSource<TargetChain: Chain> {
 	_phantom: core::marker::PhantomData<TargetChain>,
@@ -1096,6 +1277,20 @@ impl<TargetChain: Chain> IngressSource for DummyIngressSource<TargetChain> {
 	) -> DispatchResult {
 		Ok(())
 	}
+
+	fn close_channel(
+		_channel: <Self::Chain as Chain>::ChainAccount,
+		_close_block: <Self::Chain as Chain>::ChainBlockNumber,
+	) -> DispatchResult {
+		Ok(())
+	}
+
+	fn extend_channel(
+		_channel: <Self::Chain as Chain>::ChainAccount,
+		_close_block: <Self::Chain as Chain>::ChainBlockNumber,
+	) -> DispatchResult {
+		Ok(())
+	}
 }
 
 pub trait SolanaNonceWatch {
@@ -1,4 +1,4 @@
-use cf_amm::common::PoolPairsMap;
+use cf_amm::{common::PoolPairsMap, math::Price};
 use cf_chains::assets::any::AssetMap;
 use cf_primitives::{Asset, AssetAmount};
 use frame_support::pallet_prelude::{DispatchError, DispatchResult};
@@ -83,6 +83,10 @@ pub trait SwappingApi {
 		to: Asset,
 		input_amount: AssetAmount,
 	) -> Result<AssetAmount, DispatchError>;
+
+	/// The current marginal rate for swapping `from` into `to`, in units of output per unit of
+	/// input, routed through the Stable asset if necessary. No network fee is taken into account.
+	fn swap_rate(from: Asset, to: Asset) -> Option<Price>;
 }
 
 pub trait BoostApi {
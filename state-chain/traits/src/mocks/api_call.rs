@@ -15,6 +15,7 @@ pub const ETHEREUM_ETH_ADDRESS: [u8; 20] = [0xee; 20];
 pub const ETHEREUM_FLIP_ADDRESS: [u8; 20] = [0xcf; 20];
 pub const ETHEREUM_USDC_ADDRESS: [u8; 20] = [0x45; 20];
 pub const ETHEREUM_USDT_ADDRESS: [u8; 20] = [0xba; 20];
+pub const BITCOIN_REJECTION_REFUND_TX_ID: [u8; 32] = [0xaf; 32];
 #[derive(Encode, Decode, TypeInfo, Eq, PartialEq)]
 pub struct MockEvmEnvironment;
 
@@ -90,6 +91,7 @@ impl MockEthAllBatch<MockEvmEnvironment> {
 thread_local! {
 	static ALL_BATCH_SUCCESS: std::cell::RefCell<bool> = const { std::cell::RefCell::new(true) };
 	pub static SHOULD_CONSOLIDATE: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+	pub static CONSOLIDATION_FAILS: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
 }
 
 impl AllBatch<Ethereum> for MockEthereumApiCall<MockEvmEnvironment> {
@@ -119,7 +121,9 @@ impl cf_chains::ConsolidateCall<Ethereum> for MockEthereumApiCall<MockEvmEnviron
 		// Consolidation isn't necessary for Ethereum, but this implementation
 		// helps in testing some generic behaviour
 
-		if SHOULD_CONSOLIDATE.with(|cell| cell.get()) {
+		if CONSOLIDATION_FAILS.with(|cell| cell.get()) {
+			Err(ConsolidationError::Other)
+		} else if SHOULD_CONSOLIDATE.with(|cell| cell.get()) {
 			Ok(Self::AllBatch(MockEthAllBatch {
 				nonce: Default::default(),
 				fetch_params: Default::default(),
@@ -143,6 +147,16 @@ pub struct MockEthExecutexSwapAndCall<MockEvmEnvironment> {
 	_phantom: PhantomData<MockEvmEnvironment>,
 }
 
+impl MockEthExecutexSwapAndCall<MockEvmEnvironment> {
+	pub fn set_ready(ready: bool) {
+		EXECUTEX_SWAP_AND_CALL_READY.with(|cell| cell.set(ready));
+	}
+}
+
+thread_local! {
+	static EXECUTEX_SWAP_AND_CALL_READY: std::cell::Cell<bool> = const { std::cell::Cell::new(true) };
+}
+
 impl ExecutexSwapAndCall<Ethereum> for MockEthereumApiCall<MockEvmEnvironment> {
 	fn new_unsigned(
 		transfer_param: TransferAssetParams<Ethereum>,
@@ -154,6 +168,8 @@ impl ExecutexSwapAndCall<Ethereum> for MockEthereumApiCall<MockEvmEnvironment> {
 	) -> Result<Self, ExecutexSwapAndCallError> {
 		if MockEvmEnvironment::lookup(transfer_param.asset).is_none() {
 			Err(ExecutexSwapAndCallError::DispatchError(DispatchError::CannotLookup))
+		} else if !EXECUTEX_SWAP_AND_CALL_READY.with(|cell| cell.get()) {
+			Err(ExecutexSwapAndCallError::NotReady)
 		} else {
 			Ok(Self::ExecutexSwapAndCall(MockEthExecutexSwapAndCall {
 				nonce: Default::default(),
@@ -346,6 +362,10 @@ impl RejectCall<Bitcoin> for MockBitcoinApiCall<MockBtcEnvironment> {
 	) -> Result<Self, RejectError> {
 		Ok(Self::RejectCall)
 	}
+
+	fn refund_transaction_in_id(&self) -> Option<<BitcoinCrypto as ChainCrypto>::TransactionInId> {
+		Some(BITCOIN_REJECTION_REFUND_TX_ID.into())
+	}
 }
 
 impl RejectCall<Ethereum> for MockEthereumApiCall<MockEvmEnvironment> {
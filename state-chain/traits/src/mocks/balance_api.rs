@@ -1,5 +1,8 @@
 use crate::{BalanceApi, LpDepositHandler};
-use cf_chains::assets::any::{Asset, AssetMap};
+use cf_chains::{
+	address::EncodedAddress,
+	assets::any::{Asset, AssetMap},
+};
 use cf_primitives::AssetAmount;
 use frame_support::sp_runtime::{
 	traits::{CheckedSub, Saturating},
@@ -81,6 +84,13 @@ impl BalanceApi for MockBalance {
 	fn get_balance(who: &Self::AccountId, asset: Asset) -> AssetAmount {
 		Self::get_storage(FREE_BALANCES, (who, asset)).unwrap_or_default()
 	}
+
+	fn check_withdrawal_address(
+		_who: &Self::AccountId,
+		_destination: &EncodedAddress,
+	) -> DispatchResult {
+		Ok(())
+	}
 }
 
 pub struct MockLpRegistration;
@@ -1,10 +1,13 @@
 use super::{MockPallet, MockPalletStorage};
 use crate::{Chainflip, DepositApi};
 use cf_chains::{
-	address::ForeignChainAddress, dot::PolkadotAccountId, CcmChannelMetadata, Chain,
-	ChannelRefundParametersDecoded, ForeignChain,
+	address::ForeignChainAddress, btc::ScriptPubkey, dot::PolkadotAccountId, CcmChannelMetadata,
+	Chain, ChannelRefundParametersDecoded, EgressMemo, ForeignChain,
+};
+use cf_primitives::{
+	chains::assets::any, BasisPoints, Beneficiaries, ChannelId, DcaParameters, SwapRequestId,
+	TrackingId,
 };
-use cf_primitives::{chains::assets::any, BasisPoints, Beneficiaries, ChannelId, DcaParameters};
 use codec::{Decode, Encode};
 use frame_support::sp_runtime::DispatchError;
 use scale_info::TypeInfo;
@@ -79,6 +82,39 @@ impl<C: Chain, T: Chainflip> MockDepositHandler<C, T> {
 	pub fn get_swap_channels() -> Vec<SwapChannel<C, T>> {
 		<Self as MockPalletStorage>::get_value(b"SWAP_INGRESS_CHANNELS").unwrap_or_default()
 	}
+
+	/// Simulates a vault key rotation, which changes the derived private channel addresses.
+	pub fn set_vault_key(key: u8) {
+		<Self as MockPalletStorage>::put_value(b"VAULT_KEY", key);
+	}
+
+	/// Simulates all deposit channels being closed.
+	pub fn close_all_channels() {
+		<Self as MockPalletStorage>::put_value(
+			b"SWAP_INGRESS_CHANNELS",
+			Vec::<SwapChannel<C, T>>::new(),
+		);
+		<Self as MockPalletStorage>::put_value(
+			b"LP_INGRESS_CHANNELS",
+			Vec::<LpChannel<C, T>>::new(),
+		);
+	}
+
+	/// The brokers whose rejection requests have been expired.
+	pub fn get_expired_rejection_requests() -> Vec<T::AccountId> {
+		<Self as MockPalletStorage>::get_value(b"EXPIRED_REJECTION_REQUESTS").unwrap_or_default()
+	}
+
+	/// Simulates the deposit that started the swap request being marked for rejection after it
+	/// was boosted.
+	pub fn mark_swap_request_deposit_rejected(swap_request_id: SwapRequestId) {
+		<Self as MockPalletStorage>::mutate_value(
+			b"REJECTED_SWAP_REQUESTS",
+			|ids: &mut Option<Vec<SwapRequestId>>| {
+				ids.get_or_insert_with(Vec::new).push(swap_request_id);
+			},
+		);
+	}
 }
 
 impl<C: Chain, T: Chainflip> DepositApi<C> for MockDepositHandler<C, T> {
@@ -127,6 +163,8 @@ impl<C: Chain, T: Chainflip> DepositApi<C> for MockDepositHandler<C, T> {
 		boost_fee: BasisPoints,
 		_refund_params: Option<ChannelRefundParametersDecoded>,
 		_dca_params: Option<DcaParameters>,
+		_tracking_id: Option<TrackingId>,
+		_egress_memo: Option<EgressMemo>,
 	) -> Result<
 		(cf_primitives::ChannelId, ForeignChainAddress, C::ChainBlockNumber, Self::Amount),
 		DispatchError,
@@ -152,4 +190,41 @@ impl<C: Chain, T: Chainflip> DepositApi<C> for MockDepositHandler<C, T> {
 		});
 		Ok((channel_id, deposit_address, 0u32.into(), channel_opening_fee))
 	}
+
+	fn private_channel_deposit_address(
+		_source_asset: <C as Chain>::ChainAsset,
+		channel_id: ChannelId,
+	) -> Result<ForeignChainAddress, DispatchError> {
+		let mut address =
+			[<Self as MockPalletStorage>::get_value(b"VAULT_KEY").unwrap_or_default(); 32];
+		address[..8].copy_from_slice(&channel_id.to_be_bytes());
+		Ok(ForeignChainAddress::Btc(ScriptPubkey::Taproot(address)))
+	}
+	fn has_unrecycled_deposit_channels(owner: &Self::AccountId) -> bool {
+		Self::get_swap_channels().iter().any(|channel| &channel.broker_id == owner) ||
+			Self::get_liquidity_channels()
+				.iter()
+				.any(|channel| &channel.lp_account == owner)
+	}
+
+	fn expire_rejection_requests(broker_id: &Self::AccountId) {
+		<Self as MockPalletStorage>::mutate_value(
+			b"EXPIRED_REJECTION_REQUESTS",
+			|brokers: &mut Option<Vec<Self::AccountId>>| {
+				brokers.get_or_insert_with(Vec::new).push(broker_id.clone());
+			},
+		);
+	}
+
+	fn take_swap_request_rejection(swap_request_id: SwapRequestId) -> bool {
+		<Self as MockPalletStorage>::mutate_value(
+			b"REJECTED_SWAP_REQUESTS",
+			|ids: &mut Option<Vec<SwapRequestId>>| {
+				let ids = ids.get_or_insert_with(Vec::new);
+				let rejected = ids.contains(&swap_request_id);
+				ids.retain(|id| *id != swap_request_id);
+				rejected
+			},
+		)
+	}
 }
@@ -0,0 +1,31 @@
+use crate::{
+	mocks::{MockPallet, MockPalletStorage},
+	DepositRiskScorer,
+};
+use cf_chains::{address::ForeignChainAddress, Chain};
+use cf_primitives::BasisPoints;
+
+pub struct MockDepositRiskScorer;
+
+impl MockPallet for MockDepositRiskScorer {
+	const PREFIX: &'static [u8] = b"MockDepositRiskScorer";
+}
+
+const RISK_SCORE: &[u8] = b"RISK_SCORE";
+
+impl MockDepositRiskScorer {
+	pub fn set_risk_score(risk_score: BasisPoints) {
+		Self::put_value(RISK_SCORE, risk_score);
+	}
+}
+
+impl<C: Chain> DepositRiskScorer<C> for MockDepositRiskScorer {
+	fn risk_score(
+		_asset: C::ChainAsset,
+		_amount: C::ChainAmount,
+		_deposit_details: &C::DepositDetails,
+		_source_address: Option<&ForeignChainAddress>,
+	) -> BasisPoints {
+		Self::get_value(RISK_SCORE).unwrap_or_default()
+	}
+}
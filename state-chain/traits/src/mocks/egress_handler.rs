@@ -1,6 +1,6 @@
 use super::{MockPallet, MockPalletStorage};
-use crate::{EgressApi, ScheduledEgressDetails};
-use cf_chains::{CcmAdditionalData, CcmDepositMetadata, CcmMessage, Chain};
+use crate::{EgressApi, EgressOrigin, ScheduledEgressDetails};
+use cf_chains::{CcmAdditionalData, CcmDepositMetadata, CcmMessage, Chain, EgressMemo};
 use cf_primitives::{AssetAmount, EgressCounter, GasAmount};
 use codec::{Decode, Encode};
 use frame_support::sp_runtime::{
@@ -23,6 +23,7 @@ pub enum MockEgressParameter<C: Chain> {
 		amount: C::ChainAmount,
 		fee: C::ChainAmount,
 		destination_address: C::ChainAccount,
+		memo: Option<EgressMemo>,
 	},
 	Ccm {
 		asset: C::ChainAsset,
@@ -60,6 +61,11 @@ impl<C: Chain> MockEgressHandler<C> {
 		<Self as MockPalletStorage>::get_value(b"SCHEDULED_EGRESSES").unwrap_or_default()
 	}
 
+	/// The origins of the scheduled egresses, in the order they were scheduled.
+	pub fn get_scheduled_egress_origins() -> Vec<EgressOrigin> {
+		<Self as MockPalletStorage>::get_value(b"SCHEDULED_EGRESS_ORIGINS").unwrap_or_default()
+	}
+
 	pub fn set_fee(amount: C::ChainAmount) {
 		<Self as MockPalletStorage>::put_value(b"EGRESS_FEE", amount);
 	}
@@ -76,6 +82,8 @@ impl<C: Chain> EgressApi<C> for MockEgressHandler<C> {
 		amount: <C as Chain>::ChainAmount,
 		destination_address: <C as Chain>::ChainAccount,
 		maybe_ccm_deposit_metadata: Option<CcmDepositMetadata>,
+		egress_memo: Option<EgressMemo>,
+		origin: EgressOrigin,
 	) -> Result<ScheduledEgressDetails<C>, DispatchError> {
 		if amount.is_zero() && maybe_ccm_deposit_metadata.is_none() {
 			return Err(DispatchError::from("Ignoring zero egress amount."))
@@ -103,13 +111,20 @@ impl<C: Chain> EgressApi<C> for MockEgressHandler<C> {
 						amount: amount.saturating_sub(egress_fee),
 						destination_address,
 						fee: egress_fee,
+						memo: egress_memo,
 					},
 				});
 			})
 		});
+		<Self as MockPalletStorage>::mutate_value(
+			b"SCHEDULED_EGRESS_ORIGINS",
+			|origins: &mut Option<Vec<EgressOrigin>>| {
+				origins.get_or_insert_with(Vec::new).push(origin);
+			},
+		);
 		let len = Self::get_scheduled_egresses().len();
 		Ok(ScheduledEgressDetails {
-			egress_id: (asset.into(), len as EgressCounter),
+			egress_id: (asset.into(), 0, len as EgressCounter),
 			egress_amount: amount.saturating_sub(egress_fee),
 			fee_withheld: egress_fee,
 		})
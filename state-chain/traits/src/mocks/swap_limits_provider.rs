@@ -1,10 +1,51 @@
-use cf_primitives::BlockNumber;
+use cf_amm::math::Price;
+use cf_chains::{
+	address::ForeignChainAddress,
+	ccm_checker::{CcmLimits, CcmValidityError},
+	CcmChannelMetadata,
+};
+use cf_primitives::{Asset, BlockNumber, ForeignChain};
 use frame_support::sp_runtime::DispatchError;
+use sp_std::collections::btree_set::BTreeSet;
 
 use crate::{SwapLimits, SwapLimitsProvider};
 
+use super::{MockPallet, MockPalletStorage};
+
 pub struct MockSwapLimitsProvider;
 
+impl MockPallet for MockSwapLimitsProvider {
+	const PREFIX: &'static [u8] = b"MockSwapLimitsProvider";
+}
+
+const CCM_LIMITS: &[u8] = b"CCM_LIMITS";
+const CCM_DESTINATION_ALLOWLIST: &[u8] = b"CCM_DESTINATION_ALLOWLIST";
+const WINDING_DOWN_CHAINS: &[u8] = b"WINDING_DOWN_CHAINS";
+const MAX_MIN_PRICE: &[u8] = b"MAX_MIN_PRICE";
+
+impl MockSwapLimitsProvider {
+	pub fn set_ccm_limits(destination_chain: ForeignChain, limits: CcmLimits) {
+		Self::put_storage(CCM_LIMITS, destination_chain, limits);
+	}
+
+	pub fn set_ccm_destination_allowlist(
+		broker_id: u64,
+		destination_chain: ForeignChain,
+		allowlist: BTreeSet<ForeignChainAddress>,
+	) {
+		Self::put_storage(CCM_DESTINATION_ALLOWLIST, (broker_id, destination_chain), allowlist);
+	}
+
+	pub fn set_chain_winding_down(chain: ForeignChain, winding_down: bool) {
+		Self::put_storage(WINDING_DOWN_CHAINS, chain, winding_down);
+	}
+
+	/// Min prices above this value are rejected, as if they were too far above the market price.
+	pub fn set_max_min_price(source_asset: Asset, destination_asset: Asset, max_min_price: Price) {
+		Self::put_storage(MAX_MIN_PRICE, (source_asset, destination_asset), max_min_price);
+	}
+}
+
 impl SwapLimitsProvider for MockSwapLimitsProvider {
 	type AccountId = u64;
 
@@ -23,6 +64,18 @@ impl SwapLimitsProvider for MockSwapLimitsProvider {
 		Ok(())
 	}
 
+	fn validate_min_price(
+		source_asset: Asset,
+		destination_asset: Asset,
+		min_price: Price,
+	) -> Result<(), DispatchError> {
+		match Self::get_storage::<_, Price>(MAX_MIN_PRICE, (source_asset, destination_asset)) {
+			Some(max_min_price) if min_price > max_min_price =>
+				Err(DispatchError::Other("Min price too far above market price")),
+			_ => Ok(()),
+		}
+	}
+
 	fn validate_dca_params(params: &cf_primitives::DcaParameters) -> Result<(), DispatchError> {
 		let limits = Self::get_swap_limits();
 
@@ -57,4 +110,39 @@ impl SwapLimitsProvider for MockSwapLimitsProvider {
 
 		Ok(())
 	}
+
+	fn validate_ccm_limits(
+		ccm: &CcmChannelMetadata,
+		destination_chain: ForeignChain,
+	) -> Result<(), CcmValidityError> {
+		match Self::get_storage::<_, CcmLimits>(CCM_LIMITS, destination_chain) {
+			Some(limits) => limits.check(ccm),
+			None => Ok(()),
+		}
+	}
+
+	fn validate_ccm_destination(
+		broker_id: &Self::AccountId,
+		destination_address: &ForeignChainAddress,
+	) -> Result<(), DispatchError> {
+		match Self::get_storage::<_, BTreeSet<ForeignChainAddress>>(
+			CCM_DESTINATION_ALLOWLIST,
+			(broker_id, destination_address.chain()),
+		) {
+			Some(allowlist) if !allowlist.contains(destination_address) =>
+				Err(DispatchError::Other("CCM destination not allowed")),
+			_ => Ok(()),
+		}
+	}
+
+	fn validate_destination(
+		destination_address: &ForeignChainAddress,
+	) -> Result<(), DispatchError> {
+		if Self::get_storage::<_, bool>(WINDING_DOWN_CHAINS, destination_address.chain())
+			.unwrap_or_default()
+		{
+			return Err(DispatchError::Other("Destination chain is winding down"));
+		}
+		Ok(())
+	}
 }
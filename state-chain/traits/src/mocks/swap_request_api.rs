@@ -1,6 +1,6 @@
-use crate::{swapping::SwapRequestType, EgressApi, SwapRequestHandler};
+use crate::{swapping::SwapRequestType, EgressApi, EgressOrigin, SwapRequestHandler};
 use cf_chains::{Chain, ChannelRefundParametersDecoded, SwapOrigin};
-use cf_primitives::{Asset, AssetAmount, Beneficiaries, DcaParameters, SwapRequestId};
+use cf_primitives::{Asset, AssetAmount, Beneficiaries, DcaParameters, SwapRequestId, TrackingId};
 use codec::{Decode, Encode};
 use scale_info::TypeInfo;
 
@@ -16,7 +16,7 @@ pub struct MockSwapRequest {
 	pub input_asset: Asset,
 	pub output_asset: Asset,
 	pub input_amount: AssetAmount,
-	pub swap_type: SwapRequestType,
+	pub swap_type: SwapRequestType<u64>,
 	pub broker_fees: Beneficiaries<u64>,
 	pub origin: SwapOrigin<u64>,
 }
@@ -26,11 +26,22 @@ impl<T> MockPallet for MockSwapRequestHandler<T> {
 }
 
 const SWAP_REQUESTS: &[u8] = b"SWAP_REQUESTS";
+const SUSPENDED_BROKERS: &[u8] = b"SUSPENDED_BROKERS";
 
 impl<T> MockSwapRequestHandler<T> {
 	pub fn get_swap_requests() -> Vec<MockSwapRequest> {
 		Self::get_value(SWAP_REQUESTS).unwrap_or_default()
 	}
+
+	pub fn set_broker_suspended(broker_id: u64, suspended: bool) {
+		Self::mutate_value(SUSPENDED_BROKERS, |brokers: &mut Option<Vec<u64>>| {
+			let brokers = brokers.get_or_insert(vec![]);
+			brokers.retain(|id| *id != broker_id);
+			if suspended {
+				brokers.push(broker_id);
+			}
+		});
+	}
 }
 
 impl<C: Chain, E: EgressApi<C>> SwapRequestHandler for MockSwapRequestHandler<(C, E)>
@@ -43,11 +54,12 @@ where
 		input_asset: Asset,
 		input_amount: AssetAmount,
 		output_asset: Asset,
-		swap_type: SwapRequestType,
+		swap_type: SwapRequestType<Self::AccountId>,
 		broker_fees: Beneficiaries<Self::AccountId>,
 		_refund_params: Option<ChannelRefundParametersDecoded>,
 		_dca_params: Option<DcaParameters>,
 		origin: SwapOrigin<Self::AccountId>,
+		_tracking_id: Option<TrackingId>,
 	) -> SwapRequestId {
 		let id = Self::mutate_value(SWAP_REQUESTS, |swaps: &mut Option<Vec<MockSwapRequest>>| {
 			let swaps = swaps.get_or_insert(vec![]);
@@ -64,12 +76,14 @@ where
 		});
 
 		match swap_type {
-			SwapRequestType::Regular { output_address, ccm_deposit_metadata } => {
+			SwapRequestType::Regular { output_address, ccm_deposit_metadata, egress_memo } => {
 				let _ = E::schedule_egress(
 					output_asset.try_into().unwrap_or_else(|_| panic!("Unable to convert")),
 					input_amount.try_into().unwrap_or_else(|_| panic!("Unable to convert")),
 					output_address.try_into().unwrap_or_else(|_| panic!("Unable to convert")),
 					ccm_deposit_metadata,
+					egress_memo,
+					EgressOrigin::Swap,
 				);
 			},
 			_ => { /* do nothing */ },
@@ -77,4 +91,24 @@ where
 
 		(id as u64).into()
 	}
+
+	fn top_up_swap_request(
+		swap_request_id: SwapRequestId,
+		additional_input_amount: AssetAmount,
+	) -> Result<(), ()> {
+		Self::mutate_value(SWAP_REQUESTS, |swaps: &mut Option<Vec<MockSwapRequest>>| {
+			let request = swaps
+				.as_mut()
+				.and_then(|swaps| swaps.get_mut(swap_request_id.0 as usize))
+				.ok_or(())?;
+			request.input_amount += additional_input_amount;
+			Ok(())
+		})
+	}
+
+	fn is_broker_suspended(broker_id: &Self::AccountId) -> bool {
+		Self::get_value::<Vec<u64>>(SUSPENDED_BROKERS)
+			.unwrap_or_default()
+			.contains(broker_id)
+	}
 }
@@ -1,29 +1,44 @@
+use cf_amm::math::Price;
 use cf_chains::{
-	CcmDepositMetadataGeneric, ChannelRefundParametersDecoded, ForeignChainAddress, SwapOrigin,
+	CcmDepositMetadataGeneric, ChannelRefundParametersDecoded, EgressMemo, ForeignChainAddress,
+	SwapOrigin,
+};
+use cf_primitives::{
+	Asset, AssetAmount, Beneficiaries, BlockNumber, DcaParameters, SwapRequestId, TrackingId,
 };
-use cf_primitives::{Asset, AssetAmount, Beneficiaries, DcaParameters, SwapRequestId};
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
+use sp_runtime::DispatchError;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Encode, Decode, TypeInfo, MaxEncodedLen)]
 pub enum SwapType {
 	Swap,
 	NetworkFee,
 	IngressEgressFee,
+	BoostNetworkFee,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, TypeInfo)]
-pub enum SwapRequestTypeGeneric<Address> {
+pub enum SwapRequestTypeGeneric<Address, AccountId> {
 	NetworkFee,
 	IngressEgressFee,
 	Regular {
 		output_address: Address,
 		ccm_deposit_metadata: Option<CcmDepositMetadataGeneric<Address>>,
+		/// Attached to the egress of the output, for destinations that need a memo.
+		egress_memo: Option<EgressMemo>,
+	},
+	/// The output is credited to the free balance of an account instead of being egressed.
+	CreditOnChain {
+		account_id: AccountId,
 	},
+	/// The network fee portion of a boost fee, converted into FLIP to be burned.
+	BoostNetworkFee,
 }
 
-pub type SwapRequestType = SwapRequestTypeGeneric<ForeignChainAddress>;
-pub type SwapRequestTypeEncoded = SwapRequestTypeGeneric<cf_chains::address::EncodedAddress>;
+pub type SwapRequestType<AccountId> = SwapRequestTypeGeneric<ForeignChainAddress, AccountId>;
+pub type SwapRequestTypeEncoded<AccountId> =
+	SwapRequestTypeGeneric<cf_chains::address::EncodedAddress, AccountId>;
 
 pub trait SwapRequestHandler {
 	type AccountId;
@@ -32,10 +47,47 @@ pub trait SwapRequestHandler {
 		input_asset: Asset,
 		input_amount: AssetAmount,
 		output_asset: Asset,
-		request_type: SwapRequestType,
+		request_type: SwapRequestType<Self::AccountId>,
 		broker_fees: Beneficiaries<Self::AccountId>,
 		refund_params: Option<ChannelRefundParametersDecoded>,
 		dca_params: Option<DcaParameters>,
 		origin: SwapOrigin<Self::AccountId>,
+		tracking_id: Option<TrackingId>,
 	) -> SwapRequestId;
+
+	/// Adds to the input of an existing swap request. The additional input is spread over the
+	/// request's chunks that have not yet been scheduled, so this fails if there are none left,
+	/// and for CCM requests. Input above the maximum swap amount for the request as a whole is
+	/// refunded or confiscated in the same way as for new requests.
+	fn top_up_swap_request(
+		swap_request_id: SwapRequestId,
+		additional_input_amount: AssetAmount,
+	) -> Result<(), ()>;
+
+	/// Whether the broker has been suspended, in which case deposits on its behalf should be
+	/// refunded rather than swapped.
+	fn is_broker_suspended(_broker_id: &Self::AccountId) -> bool {
+		false
+	}
+}
+
+/// Swaps funds held in an account's free balance, so that subsystems holding on-chain balances
+/// can convert them without going through a deposit.
+pub trait OnChainSwapHandler {
+	type AccountId;
+
+	/// Debits the input amount from the account's free balance and swaps it, crediting the output
+	/// back to the account's free balance. If the swap can't be executed at `min_price` or better
+	/// within `retry_duration` blocks, the remaining input is credited back instead.
+	///
+	/// Nothing is debited if the swap can't be requested.
+	fn swap_from_on_chain_balance(
+		account_id: &Self::AccountId,
+		input_asset: Asset,
+		input_amount: AssetAmount,
+		output_asset: Asset,
+		retry_duration: BlockNumber,
+		min_price: Price,
+		dca_params: Option<DcaParameters>,
+	) -> Result<SwapRequestId, DispatchError>;
 }